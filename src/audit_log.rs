@@ -0,0 +1,321 @@
+//! An opt-in (`audit_log = true` in the config) local record of every transaction that near CLI
+//! signs or broadcasts: one JSON line per transaction in `<data dir>/near-cli/audit-log.jsonl`.
+//!
+//! Every line has the SHA-256 of the previous line (`previous_line_sha256`), so a line that is
+//! edited, removed or inserted, or the start of the file cut off, breaks the chain; the hash of
+//! the last line printed by `audit-log show --verify` can be kept elsewhere to detect the
+//! removal of the latest lines. The log never contains keys, signatures or function call
+//! arguments.
+
+use color_eyre::eyre::{ContextCompat, WrapErr};
+use std::io::Write;
+
+const AUDIT_LOG_FILE_NAME: &str = "audit-log.jsonl";
+/// The `previous_line_sha256` of the first line
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct AuditLogEntry {
+    /// RFC 3339, in UTC
+    pub timestamp: String,
+    pub network: String,
+    pub signer_id: near_primitives::types::AccountId,
+    pub receiver_id: near_primitives::types::AccountId,
+    pub actions: Vec<String>,
+    /// `None` for the delegate actions sent to a relayer
+    pub transaction_hash: Option<near_primitives::hash::CryptoHash>,
+    /// e.g. `success`, `failure: ...` or `saved to file ...`
+    pub outcome: String,
+    pub previous_line_sha256: String,
+}
+
+/// What is logged about a transaction (or a delegate action).
+pub struct AuditedTransaction<'a> {
+    pub signer_id: &'a near_primitives::types::AccountId,
+    pub receiver_id: &'a near_primitives::types::AccountId,
+    pub actions: Vec<near_primitives::views::ActionView>,
+    pub transaction_hash: Option<near_primitives::hash::CryptoHash>,
+}
+
+impl<'a> From<&'a near_primitives::transaction::SignedTransaction> for AuditedTransaction<'a> {
+    fn from(signed_transaction: &'a near_primitives::transaction::SignedTransaction) -> Self {
+        Self {
+            signer_id: signed_transaction.transaction.signer_id(),
+            receiver_id: signed_transaction.transaction.receiver_id(),
+            actions: signed_transaction
+                .transaction
+                .actions()
+                .iter()
+                .cloned()
+                .map(Into::into)
+                .collect(),
+            transaction_hash: Some(signed_transaction.get_hash()),
+        }
+    }
+}
+
+impl<'a> From<&'a crate::transaction_signature_options::SignedTransactionOrSignedDelegateAction>
+    for AuditedTransaction<'a>
+{
+    fn from(
+        signed: &'a crate::transaction_signature_options::SignedTransactionOrSignedDelegateAction,
+    ) -> Self {
+        match signed {
+            crate::transaction_signature_options::SignedTransactionOrSignedDelegateAction::SignedTransaction(
+                signed_transaction,
+            ) => signed_transaction.into(),
+            crate::transaction_signature_options::SignedTransactionOrSignedDelegateAction::SignedDelegateAction(
+                signed_delegate_action,
+            ) => Self {
+                signer_id: &signed_delegate_action.delegate_action.sender_id,
+                receiver_id: &signed_delegate_action.delegate_action.receiver_id,
+                actions: signed_delegate_action
+                    .delegate_action
+                    .get_actions()
+                    .into_iter()
+                    .map(Into::into)
+                    .collect(),
+                transaction_hash: None,
+            },
+        }
+    }
+}
+
+/// The error of a transaction that the RPC server rejected before broadcasting it (e.g. an
+/// invalid nonce), so it was certainly not executed.
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+pub struct RejectedTransaction(pub String);
+
+/// The outcome of a sent transaction as it is logged: after an error other than a
+/// [`RejectedTransaction`] (e.g. a timeout) the transaction may still be executed.
+pub fn sent_transaction_outcome(
+    transaction_info: &color_eyre::eyre::Result<near_primitives::views::FinalExecutionOutcomeView>,
+) -> String {
+    match transaction_info {
+        Ok(transaction_info) => match &transaction_info.status {
+            near_primitives::views::FinalExecutionStatus::SuccessValue(_) => "success".to_string(),
+            near_primitives::views::FinalExecutionStatus::Failure(err) => {
+                format!("failure: {err}")
+            }
+            near_primitives::views::FinalExecutionStatus::NotStarted
+            | near_primitives::views::FinalExecutionStatus::Started => "pending".to_string(),
+        },
+        Err(err) if err.downcast_ref::<RejectedTransaction>().is_some() => {
+            format!("not sent: {err}")
+        }
+        Err(err) => format!("unknown ({err})"),
+    }
+}
+
+/// Appends the transaction to the audit log if it is enabled. A failure to write (e.g. a full
+/// disk) is a warning: the transaction itself must not fail because of the log.
+pub fn record(
+    config: &crate::config::Config,
+    network_config: &crate::config::NetworkConfig,
+    transaction: AuditedTransaction,
+    outcome: impl Into<String>,
+) {
+    if !config.audit_log {
+        return;
+    }
+    let entry = AuditLogEntry {
+        timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        network: network_config.network_name.clone(),
+        signer_id: transaction.signer_id.clone(),
+        receiver_id: transaction.receiver_id.clone(),
+        actions: transaction.actions.iter().map(action_summary).collect(),
+        transaction_hash: transaction.transaction_hash,
+        outcome: outcome.into(),
+        previous_line_sha256: String::new(),
+    };
+    if let Err(err) = get_audit_log_path().and_then(|path| append_entry(&path, entry)) {
+        eprintln!("WARNING: The transaction could not be written to the audit log: {err:#}");
+    }
+}
+
+pub fn get_audit_log_path() -> color_eyre::eyre::Result<std::path::PathBuf> {
+    let mut path = dirs::data_dir().wrap_err("Impossible to get your data dir!")?;
+    path.push("near-cli");
+    path.push(AUDIT_LOG_FILE_NAME);
+    Ok(path)
+}
+
+fn append_entry(path: &std::path::Path, mut entry: AuditLogEntry) -> crate::CliResult {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    entry.previous_line_sha256 = match std::fs::read_to_string(path) {
+        Ok(audit_log) => audit_log
+            .lines()
+            .rev()
+            .find(|line| !line.trim().is_empty())
+            .map_or_else(|| GENESIS_HASH.to_string(), line_sha256),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => GENESIS_HASH.to_string(),
+        Err(err) => {
+            return Err(err).wrap_err_with(|| format!("Failed to read file: {}", path.display()))
+        }
+    };
+    let mut line = serde_json::to_string(&entry)?;
+    line.push('\n');
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(line.as_bytes()))
+        .wrap_err_with(|| format!("Failed to write to file: {}", path.display()))
+}
+
+pub fn line_sha256(line: &str) -> String {
+    hex::encode(near_primitives::hash::hash(line.as_bytes()))
+}
+
+/// The parsed entries of the audit log, with the first line that breaks the hash chain (its
+/// number, from 1, and the reason).
+pub fn verify(audit_log: &str) -> (Vec<AuditLogEntry>, Option<(usize, String)>) {
+    let mut entries = Vec::new();
+    let mut expected_previous_hash = GENESIS_HASH.to_string();
+    for (line_index, line) in audit_log.lines().enumerate() {
+        let line_number = line_index + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry = match serde_json::from_str::<AuditLogEntry>(line) {
+            Ok(entry) => entry,
+            Err(err) => {
+                return (
+                    entries,
+                    Some((line_number, format!("invalid entry: {err}"))),
+                )
+            }
+        };
+        if entry.previous_line_sha256 != expected_previous_hash {
+            let reason = if line_number == 1 {
+                "the first entry does not start the chain (the start of the log is missing)"
+            } else {
+                "the hash of the previous line does not match (a line was changed, removed or inserted)"
+            };
+            return (entries, Some((line_number, reason.to_string())));
+        }
+        expected_previous_hash = line_sha256(line);
+        entries.push(entry);
+    }
+    (entries, None)
+}
+
+fn action_summary(action: &near_primitives::views::ActionView) -> String {
+    match action {
+        near_primitives::views::ActionView::CreateAccount => "create account".to_string(),
+        near_primitives::views::ActionView::DeployContract { code } => {
+            match near_primitives::hash::CryptoHash::try_from(code.as_slice()) {
+                Ok(code_hash) => format!("deploy contract (code hash: {code_hash})"),
+                Err(_) => "deploy contract".to_string(),
+            }
+        }
+        near_primitives::views::ActionView::FunctionCall {
+            method_name,
+            args,
+            gas,
+            deposit,
+        } => format!(
+            "function call \"{method_name}\" ({} of args, {} attached gas, {} deposit)",
+            bytesize::ByteSize(args.len() as u64),
+            crate::common::NearGas::from_gas(*gas),
+            crate::types::near_token::NearToken::from_yoctonear(*deposit)
+        ),
+        near_primitives::views::ActionView::Transfer { deposit } => format!(
+            "transfer {}",
+            crate::types::near_token::NearToken::from_yoctonear(*deposit)
+        ),
+        near_primitives::views::ActionView::Stake { stake, public_key } => format!(
+            "stake {} with the public key {public_key}",
+            crate::types::near_token::NearToken::from_yoctonear(*stake)
+        ),
+        near_primitives::views::ActionView::AddKey {
+            public_key,
+            access_key,
+        } => format!(
+            "add key {public_key} ({})",
+            crate::common::access_key_permission_message(&access_key.permission)
+        ),
+        near_primitives::views::ActionView::DeleteKey { public_key } => {
+            format!("delete key {public_key}")
+        }
+        near_primitives::views::ActionView::DeleteAccount { beneficiary_id } => {
+            format!("delete account (beneficiary: <{beneficiary_id}>)")
+        }
+        near_primitives::views::ActionView::Delegate {
+            delegate_action,
+            signature: _,
+        } => format!(
+            "delegate {} action(s) of <{}> to <{}>",
+            delegate_action.actions.len(),
+            delegate_action.sender_id,
+            delegate_action.receiver_id
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(outcome: &str) -> AuditLogEntry {
+        AuditLogEntry {
+            timestamp: "2026-10-14T12:00:00.000Z".to_string(),
+            network: "testnet".to_string(),
+            signer_id: "alice.testnet".parse().unwrap(),
+            receiver_id: "bob.testnet".parse().unwrap(),
+            actions: vec![action_summary(
+                &near_primitives::views::ActionView::Transfer {
+                    deposit: 10u128.pow(24),
+                },
+            )],
+            transaction_hash: Some(Default::default()),
+            outcome: outcome.to_string(),
+            previous_line_sha256: String::new(),
+        }
+    }
+
+    #[test]
+    fn outcome_of_a_transaction_that_may_still_be_executed() {
+        assert_eq!(
+            sent_transaction_outcome(&Err(color_eyre::Report::new(RejectedTransaction(
+                "invalid nonce".to_string()
+            )))),
+            "not sent: invalid nonce"
+        );
+        assert_eq!(
+            sent_transaction_outcome(&Err(color_eyre::eyre::eyre!("Timeout error transaction"))),
+            "unknown (Timeout error transaction)"
+        );
+    }
+
+    #[test]
+    fn the_hash_chain_detects_edits_and_truncation() {
+        let path = std::env::temp_dir().join(format!(
+            "near-cli-audit-log-test-{}.jsonl",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        for outcome in ["success", "failure: no funds", "saved to file tx.json"] {
+            append_entry(&path, entry(outcome)).unwrap();
+        }
+        let audit_log = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let (entries, broken) = verify(&audit_log);
+        assert_eq!(broken, None);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].previous_line_sha256, GENESIS_HASH);
+        assert_eq!(entries[1].actions, vec!["transfer 1 NEAR".to_string()]);
+
+        let edited = audit_log.replacen("no funds", "no fund", 1);
+        assert_eq!(verify(&edited).1.map(|(line, _)| line), Some(3));
+
+        let without_first_line = audit_log.lines().skip(1).collect::<Vec<_>>().join("\n");
+        let (entries, broken) = verify(&without_first_line);
+        assert!(entries.is_empty());
+        assert_eq!(broken.map(|(line, _)| line), Some(1));
+    }
+}
@@ -0,0 +1,386 @@
+use color_eyre::eyre::WrapErr;
+use prettytable::Table;
+
+use crate::common::RpcQueryResponseExt;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = AccessKeyDiffContext)]
+pub struct AccessKeyDiff {
+    #[interactive_clap(skip_default_input_arg)]
+    /// What Account ID do you need to compare the access keys of?
+    account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    /// Compare from the block (a block height, a block hash, "timestamp:<unix seconds>" or "final"):
+    from: crate::types::block_selector::BlockSelector,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Compare to the block (a block height, a block hash, "timestamp:<unix seconds>" or "final", default: final)
+    to: Option<crate::types::block_selector::BlockSelector>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Output format: plaintext (default) or json
+    output: Option<crate::common::OutputFormat>,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network::Network,
+}
+
+#[derive(Clone)]
+pub struct AccessKeyDiffContext(crate::network::NetworkContext);
+
+impl AccessKeyDiffContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<AccessKeyDiff as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let on_after_getting_network_callback: crate::network::OnAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let account_id: near_primitives::types::AccountId = scope.account_id.clone().into();
+                let from = scope.from;
+                let to = scope
+                    .to
+                    .unwrap_or(crate::types::block_selector::BlockSelector::Final);
+                let output = scope.output.clone().unwrap_or_default();
+
+                move |network_config| {
+                    let (from_keys, to_keys) = get_access_key_lists(
+                        network_config,
+                        &account_id,
+                        from.to_block_reference(network_config)?,
+                        to.to_block_reference(network_config)?,
+                    )?;
+                    let diff = access_key_diff(&account_id, from_keys, to_keys);
+                    match output {
+                        crate::common::OutputFormat::Plaintext => display_access_key_diff(&diff),
+                        crate::common::OutputFormat::Json => {
+                            println!("{}", serde_json::to_string_pretty(&diff)?)
+                        }
+                    }
+                    Ok(())
+                }
+            });
+
+        Ok(Self(crate::network::NetworkContext {
+            config: previous_context.config,
+            interacting_with_account_ids: vec![scope.account_id.clone().into()],
+            on_after_getting_network_callback,
+        }))
+    }
+}
+
+impl From<AccessKeyDiffContext> for crate::network::NetworkContext {
+    fn from(item: AccessKeyDiffContext) -> Self {
+        item.0
+    }
+}
+
+impl AccessKeyDiff {
+    pub fn input_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_non_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "What Account ID do you need to compare the access keys of?",
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+struct BlockAccessKeys {
+    block_height: near_primitives::types::BlockHeight,
+    block_hash: near_primitives::hash::CryptoHash,
+    #[serde(skip)]
+    keys: Vec<near_primitives::views::AccessKeyInfoView>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+struct AccessKeyState {
+    public_key: near_crypto::PublicKey,
+    nonce: near_primitives::types::Nonce,
+    permission: near_primitives::views::AccessKeyPermissionView,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+struct ChangedAccessKey {
+    public_key: near_crypto::PublicKey,
+    from: AccessKeyState,
+    to: AccessKeyState,
+}
+
+/// The JSON output (`--output json`) is this structure serialized as is.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+struct AccessKeyDiffReport {
+    account_id: near_primitives::types::AccountId,
+    from: BlockAccessKeys,
+    to: BlockAccessKeys,
+    added: Vec<AccessKeyState>,
+    removed: Vec<AccessKeyState>,
+    changed: Vec<ChangedAccessKey>,
+}
+
+#[tracing::instrument(name = "Getting the access keys at both blocks ...", skip_all)]
+fn get_access_key_lists(
+    network_config: &crate::config::NetworkConfig,
+    account_id: &near_primitives::types::AccountId,
+    from_block_reference: near_primitives::types::BlockReference,
+    to_block_reference: near_primitives::types::BlockReference,
+) -> color_eyre::eyre::Result<(BlockAccessKeys, BlockAccessKeys)> {
+    let runtime = crate::common::multi_thread_runtime()?;
+    let json_rpc_client = network_config.json_rpc_client();
+    let get_access_key_list = |block_reference: near_primitives::types::BlockReference| {
+        let json_rpc_client = &json_rpc_client;
+        async move {
+            let response = crate::rpc_rate_limit::call(
+                json_rpc_client,
+                near_jsonrpc_client::methods::query::RpcQueryRequest {
+                    block_reference,
+                    request: near_primitives::views::QueryRequest::ViewAccessKeyList {
+                        account_id: account_id.clone(),
+                    },
+                },
+            )
+            .await
+            .wrap_err_with(|| {
+                crate::common::old_block_request_error(
+                    format_args!("the access keys of <{account_id}>"),
+                    network_config,
+                )
+            })?;
+            Ok::<_, color_eyre::eyre::Error>(BlockAccessKeys {
+                block_height: response.block_height,
+                block_hash: response.block_hash,
+                keys: response.access_key_list_view()?.keys,
+            })
+        }
+    };
+    let (from, to) = runtime.block_on(async {
+        futures::join!(
+            get_access_key_list(from_block_reference),
+            get_access_key_list(to_block_reference)
+        )
+    });
+    Ok((from?, to?))
+}
+
+fn access_key_state(access_key: &near_primitives::views::AccessKeyInfoView) -> AccessKeyState {
+    AccessKeyState {
+        public_key: access_key.public_key.clone(),
+        nonce: access_key.access_key.nonce,
+        permission: access_key.access_key.permission.clone(),
+    }
+}
+
+fn access_key_diff(
+    account_id: &near_primitives::types::AccountId,
+    from: BlockAccessKeys,
+    to: BlockAccessKeys,
+) -> AccessKeyDiffReport {
+    let from_keys = from
+        .keys
+        .iter()
+        .map(|access_key| {
+            (
+                access_key.public_key.to_string(),
+                access_key_state(access_key),
+            )
+        })
+        .collect::<std::collections::BTreeMap<_, _>>();
+    let to_keys = to
+        .keys
+        .iter()
+        .map(|access_key| {
+            (
+                access_key.public_key.to_string(),
+                access_key_state(access_key),
+            )
+        })
+        .collect::<std::collections::BTreeMap<_, _>>();
+
+    let added = to_keys
+        .iter()
+        .filter(|(public_key, _)| !from_keys.contains_key(*public_key))
+        .map(|(_, state)| state.clone())
+        .collect();
+    let removed = from_keys
+        .iter()
+        .filter(|(public_key, _)| !to_keys.contains_key(*public_key))
+        .map(|(_, state)| state.clone())
+        .collect();
+    let changed = from_keys
+        .iter()
+        .filter_map(|(public_key, from_state)| {
+            let to_state = to_keys.get(public_key)?;
+            (from_state != to_state).then(|| ChangedAccessKey {
+                public_key: from_state.public_key.clone(),
+                from: from_state.clone(),
+                to: to_state.clone(),
+            })
+        })
+        .collect();
+
+    AccessKeyDiffReport {
+        account_id: account_id.clone(),
+        from,
+        to,
+        added,
+        removed,
+        changed,
+    }
+}
+
+fn display_access_key_diff(diff: &AccessKeyDiffReport) {
+    eprintln!(
+        "\nAccess keys of <{}> from block #{} ({}) to block #{} ({}):",
+        diff.account_id,
+        diff.from.block_height,
+        diff.from.block_hash,
+        diff.to.block_height,
+        diff.to.block_hash
+    );
+    if diff.added.is_empty() && diff.removed.is_empty() && diff.changed.is_empty() {
+        eprintln!("No access keys were added, removed or changed.");
+        return;
+    }
+
+    let mut table = Table::new();
+    table.set_titles(prettytable::row![Fg=>"", "Public Key", "Nonce", "Permissions"]);
+    for state in &diff.added {
+        table.add_row(prettytable::row![
+            Fg->"+ added",
+            Fg->state.public_key,
+            Fg->state.nonce,
+            Fg->crate::common::access_key_permission_message(&state.permission)
+        ]);
+    }
+    for state in &diff.removed {
+        table.add_row(prettytable::row![
+            Fr->"- removed",
+            Fr->state.public_key,
+            Fr->state.nonce,
+            Fr->crate::common::access_key_permission_message(&state.permission)
+        ]);
+    }
+    for changed in &diff.changed {
+        let nonce = if changed.from.nonce == changed.to.nonce {
+            changed.to.nonce.to_string()
+        } else {
+            format!("{} -> {}", changed.from.nonce, changed.to.nonce)
+        };
+        let permission = if changed.from.permission == changed.to.permission {
+            crate::common::access_key_permission_message(&changed.to.permission)
+        } else {
+            format!(
+                "{}\n-> {}",
+                crate::common::access_key_permission_message(&changed.from.permission),
+                crate::common::access_key_permission_message(&changed.to.permission)
+            )
+        };
+        table.add_row(prettytable::row![
+            Fy->"~ changed",
+            Fy->changed.public_key,
+            Fy->nonce,
+            Fy->permission
+        ]);
+    }
+    table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    table.printstd();
+    eprintln!(
+        "Added: {}, removed: {}, changed: {}",
+        diff.added.len(),
+        diff.removed.len(),
+        diff.changed.len()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn access_key(
+        seed: &str,
+        nonce: near_primitives::types::Nonce,
+        permission: near_primitives::views::AccessKeyPermissionView,
+    ) -> near_primitives::views::AccessKeyInfoView {
+        near_primitives::views::AccessKeyInfoView {
+            public_key: near_crypto::SecretKey::from_seed(near_crypto::KeyType::ED25519, seed)
+                .public_key(),
+            access_key: near_primitives::views::AccessKeyView { nonce, permission },
+        }
+    }
+
+    fn block_access_keys(
+        block_height: near_primitives::types::BlockHeight,
+        keys: Vec<near_primitives::views::AccessKeyInfoView>,
+    ) -> BlockAccessKeys {
+        BlockAccessKeys {
+            block_height,
+            block_hash: near_primitives::hash::CryptoHash::default(),
+            keys,
+        }
+    }
+
+    #[test]
+    fn access_key_diff_between_blocks() {
+        let full_access = near_primitives::views::AccessKeyPermissionView::FullAccess;
+        let function_call = near_primitives::views::AccessKeyPermissionView::FunctionCall {
+            allowance: None,
+            receiver_id: "app.near".to_string(),
+            method_names: vec![],
+        };
+        let from = block_access_keys(
+            1,
+            vec![
+                access_key("kept", 5, full_access.clone()),
+                access_key("removed", 1, full_access.clone()),
+                access_key("used", 10, function_call.clone()),
+                access_key("downgraded", 3, full_access.clone()),
+            ],
+        );
+        let to = block_access_keys(
+            2,
+            vec![
+                access_key("kept", 5, full_access.clone()),
+                access_key("used", 12, function_call.clone()),
+                access_key("downgraded", 3, function_call.clone()),
+                access_key("added", 100, full_access.clone()),
+            ],
+        );
+        let diff = access_key_diff(&"alice.near".parse().unwrap(), from, to);
+
+        assert_eq!(
+            diff.added,
+            vec![access_key_state(&access_key(
+                "added",
+                100,
+                full_access.clone()
+            ))]
+        );
+        assert_eq!(
+            diff.removed,
+            vec![access_key_state(&access_key(
+                "removed",
+                1,
+                full_access.clone()
+            ))]
+        );
+        assert_eq!(diff.changed.len(), 2);
+        let used = diff
+            .changed
+            .iter()
+            .find(|changed| changed.from.nonce == 10)
+            .unwrap();
+        assert_eq!(used.to.nonce, 12);
+        let downgraded = diff
+            .changed
+            .iter()
+            .find(|changed| changed.from.nonce == 3)
+            .unwrap();
+        assert_eq!(downgraded.to.permission, function_call);
+
+        let json = serde_json::to_value(&diff).unwrap();
+        assert_eq!(json["from"]["block_height"], 1);
+        assert!(json["from"].get("keys").is_none());
+        assert_eq!(json["added"][0]["nonce"], 100);
+    }
+}
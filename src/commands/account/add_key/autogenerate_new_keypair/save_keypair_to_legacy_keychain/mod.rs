@@ -58,7 +58,7 @@ impl From<SaveKeypairToLegacyKeychainContext> for crate::commands::ActionContext
 
         let on_before_sending_transaction_callback: crate::transaction_signature_options::OnBeforeSendingTransactionCallback =
             std::sync::Arc::new({
-                let credentials_home_dir = item.global_context.config.credentials_home_dir.clone();
+                let config = item.global_context.config.clone();
 
                 move |transaction, network_config| {
                     let account_id = match transaction {
@@ -72,7 +72,7 @@ impl From<SaveKeypairToLegacyKeychainContext> for crate::commands::ActionContext
                     let key_pair_properties_buf = serde_json::to_string(&item.key_pair_properties)?;
                     crate::common::save_access_key_to_legacy_keychain(
                         network_config.clone(),
-                        credentials_home_dir.clone(),
+                        config.credentials_dir(network_config),
                         &key_pair_properties_buf,
                         &item.key_pair_properties.public_key_str,
                         account_id.as_ref(),
@@ -49,6 +49,7 @@ impl AddKeyCommand {
 
 #[derive(Debug, Clone, EnumDiscriminants, interactive_clap::InteractiveClap)]
 #[interactive_clap(context = AddKeyCommandContext)]
+#[interactive_clap(skip_default_from_cli)]
 #[strum_discriminants(derive(EnumMessage, EnumIter))]
 /// Select a permission that you want to add to the access key:
 pub enum AccessKeyPermission {
@@ -64,6 +65,107 @@ pub enum AccessKeyPermission {
     GrantFunctionCallAccess(self::access_key_type::FunctionCallType),
 }
 
+impl interactive_clap::FromCli for AccessKeyPermission {
+    type FromCliContext = AddKeyCommandContext;
+    type FromCliError = color_eyre::eyre::Error;
+
+    fn from_cli(
+        mut optional_clap_variant: Option<<Self as interactive_clap::ToCli>::CliVariant>,
+        context: Self::FromCliContext,
+    ) -> interactive_clap::ResultFromCli<
+        <Self as interactive_clap::ToCli>::CliVariant,
+        Self::FromCliError,
+    >
+    where
+        Self: Sized + interactive_clap::ToCli,
+    {
+        loop {
+            let cli_inner_args = match optional_clap_variant.take() {
+                Some(CliAccessKeyPermission::GrantFullAccess(inner_cli_args)) => map_result_from_cli(
+                    <self::access_key_type::FullAccessType as interactive_clap::FromCli>::from_cli(
+                        Some(inner_cli_args),
+                        context.clone(),
+                    ),
+                    CliAccessKeyPermission::GrantFullAccess,
+                ),
+                Some(CliAccessKeyPermission::GrantFunctionCallAccess(inner_cli_args)) => {
+                    map_result_from_cli(
+                        <self::access_key_type::FunctionCallType as interactive_clap::FromCli>::from_cli(
+                            Some(inner_cli_args),
+                            context.clone(),
+                        ),
+                        CliAccessKeyPermission::GrantFunctionCallAccess,
+                    )
+                }
+                None => match Self::choose_permission(context.clone()) {
+                    interactive_clap::ResultFromCli::Ok(cli_args) => {
+                        optional_clap_variant = Some(cli_args);
+                        continue;
+                    }
+                    result => return result,
+                },
+            };
+            return match cli_inner_args {
+                interactive_clap::ResultFromCli::Back => continue,
+                result => result,
+            };
+        }
+    }
+}
+
+fn map_result_from_cli<T, U>(
+    result: interactive_clap::ResultFromCli<T, color_eyre::eyre::Error>,
+    f: impl Fn(T) -> U,
+) -> interactive_clap::ResultFromCli<U, color_eyre::eyre::Error> {
+    match result {
+        interactive_clap::ResultFromCli::Ok(cli_args) => {
+            interactive_clap::ResultFromCli::Ok(f(cli_args))
+        }
+        interactive_clap::ResultFromCli::Cancel(cli_args) => {
+            interactive_clap::ResultFromCli::Cancel(cli_args.map(f))
+        }
+        interactive_clap::ResultFromCli::Back => interactive_clap::ResultFromCli::Back,
+        interactive_clap::ResultFromCli::Err(cli_args, err) => {
+            interactive_clap::ResultFromCli::Err(cli_args.map(f), err)
+        }
+    }
+}
+
+impl AccessKeyPermission {
+    /// Without a TTY, the permission is chosen from a plain numbered list (see [`crate::common::select`]).
+    pub fn choose_permission(
+        context: AddKeyCommandContext,
+    ) -> interactive_clap::ResultFromCli<CliAccessKeyPermission, color_eyre::eyre::Error> {
+        use strum::IntoEnumIterator;
+
+        if crate::common::is_interactive_terminal() {
+            return Self::choose_variant(context);
+        }
+        match crate::common::select(
+            "Select a permission that you want to add to the access key:",
+            AccessKeyPermissionDiscriminants::iter()
+                .map(|variant| variant.get_message().unwrap_or_default())
+                .collect(),
+        ) {
+            Ok(Some(message)) => {
+                let variant = AccessKeyPermissionDiscriminants::iter()
+                    .find(|variant| variant.get_message() == Some(message))
+                    .expect("The message belongs to one of the variants");
+                interactive_clap::ResultFromCli::Ok(match variant {
+                    AccessKeyPermissionDiscriminants::GrantFullAccess => {
+                        CliAccessKeyPermission::GrantFullAccess(Default::default())
+                    }
+                    AccessKeyPermissionDiscriminants::GrantFunctionCallAccess => {
+                        CliAccessKeyPermission::GrantFunctionCallAccess(Default::default())
+                    }
+                })
+            }
+            Ok(None) => interactive_clap::ResultFromCli::Cancel(None),
+            Err(err) => interactive_clap::ResultFromCli::Err(None, err),
+        }
+    }
+}
+
 #[derive(Debug, Clone, EnumDiscriminants, interactive_clap::InteractiveClap)]
 #[interactive_clap(context = self::access_key_type::AccessTypeContext)]
 #[strum_discriminants(derive(EnumMessage, EnumIter))]
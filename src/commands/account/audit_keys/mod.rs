@@ -0,0 +1,334 @@
+use color_eyre::eyre::WrapErr;
+use prettytable::Table;
+
+use crate::common::RpcQueryResponseExt;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = AuditKeysContext)]
+pub struct AuditKeys {
+    #[interactive_clap(long)]
+    /// What is the path to the file with the list of accounts to audit (one account ID per line)?
+    accounts_file: crate::types::path_buf::PathBuf,
+    /// Public keys that must not be present on any of the accounts (e.g. keys of ex-employees)
+    #[interactive_clap(long_vec_multiple_opt)]
+    flag_key: Vec<crate::types::public_key::PublicKey>,
+    /// Public keys that are known to be valid
+    #[interactive_clap(long_vec_multiple_opt)]
+    expected_key: Vec<crate::types::public_key::PublicKey>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Report format: table (default), csv or json
+    format: Option<AuditReportFormat>,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network::Network,
+}
+
+#[derive(Clone)]
+pub struct AuditKeysContext(crate::network::NetworkContext);
+
+impl AuditKeysContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<AuditKeys as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let accounts_file_content = std::fs::read_to_string(&scope.accounts_file)
+            .wrap_err_with(|| format!("Failed to read the file: {}", scope.accounts_file))?;
+        let accounts = parse_accounts_file(&accounts_file_content);
+        if accounts.is_empty() {
+            return Err(color_eyre::eyre::eyre!(
+                "There are no accounts listed in the file: {}",
+                scope.accounts_file
+            ));
+        }
+        let key_lists = KeyLists {
+            flagged: scope
+                .flag_key
+                .iter()
+                .cloned()
+                .map(near_crypto::PublicKey::from)
+                .collect(),
+            expected: scope
+                .expected_key
+                .iter()
+                .cloned()
+                .map(near_crypto::PublicKey::from)
+                .collect(),
+        };
+        let format = scope.format.unwrap_or_default();
+        let interacting_with_account_ids = accounts
+            .iter()
+            .filter_map(|account| account.parse().ok())
+            .collect();
+
+        let on_after_getting_network_callback: crate::network::OnAfterGettingNetworkCallback =
+            std::sync::Arc::new(move |network_config| {
+                let report = get_audit_report(network_config, &accounts, &key_lists)?;
+                display_audit_report(&report, format)?;
+
+                let flagged_keys_count = report
+                    .iter()
+                    .filter(|row| row.status == Some(KeyStatus::Flagged))
+                    .count();
+                if flagged_keys_count > 0 {
+                    return Err(color_eyre::eyre::eyre!(
+                        "Found {flagged_keys_count} flagged access key(s)"
+                    ));
+                }
+                Ok(())
+            });
+
+        Ok(Self(crate::network::NetworkContext {
+            config: previous_context.config,
+            interacting_with_account_ids,
+            on_after_getting_network_callback,
+        }))
+    }
+}
+
+impl From<AuditKeysContext> for crate::network::NetworkContext {
+    fn from(item: AuditKeysContext) -> Self {
+        item.0
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, strum_macros::EnumString)]
+#[strum(serialize_all = "snake_case")]
+pub enum AuditReportFormat {
+    #[default]
+    Table,
+    Csv,
+    Json,
+}
+
+impl std::fmt::Display for AuditReportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuditReportFormat::Table => write!(f, "table"),
+            AuditReportFormat::Csv => write!(f, "csv"),
+            AuditReportFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+impl interactive_clap::ToCli for AuditReportFormat {
+    type CliVariant = AuditReportFormat;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+enum KeyStatus {
+    Expected,
+    Unknown,
+    Flagged,
+}
+
+impl std::fmt::Display for KeyStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeyStatus::Expected => write!(f, "expected"),
+            KeyStatus::Unknown => write!(f, "unknown"),
+            KeyStatus::Flagged => write!(f, "flagged"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct KeyLists {
+    flagged: Vec<near_crypto::PublicKey>,
+    expected: Vec<near_crypto::PublicKey>,
+}
+
+impl KeyLists {
+    fn key_status(&self, public_key: &near_crypto::PublicKey) -> KeyStatus {
+        if self.flagged.contains(public_key) {
+            KeyStatus::Flagged
+        } else if self.expected.contains(public_key) {
+            KeyStatus::Expected
+        } else {
+            KeyStatus::Unknown
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+struct AuditReportRow {
+    account_id: String,
+    public_key: Option<String>,
+    permission: Option<String>,
+    status: Option<KeyStatus>,
+    error: Option<String>,
+}
+
+/// Account IDs are listed one per line; empty lines and lines starting with `#` are ignored.
+fn parse_accounts_file(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(String::from)
+        .collect()
+}
+
+#[tracing::instrument(name = "Getting the access keys of the accounts ...", skip_all)]
+fn get_audit_report(
+    network_config: &crate::config::NetworkConfig,
+    accounts: &[String],
+    key_lists: &KeyLists,
+) -> color_eyre::eyre::Result<Vec<AuditReportRow>> {
+    let concurrency = 10;
+    let json_rpc_client = network_config.json_rpc_client();
+    let access_key_lists = crate::common::run_concurrently(accounts, concurrency, |account| {
+        let json_rpc_client = &json_rpc_client;
+        async move {
+            let access_key_list = async {
+                let account_id: near_primitives::types::AccountId =
+                    account.parse().wrap_err("The account ID is not valid")?;
+                crate::rpc_rate_limit::call(
+                    json_rpc_client,
+                    near_jsonrpc_client::methods::query::RpcQueryRequest {
+                        block_reference: near_primitives::types::Finality::Final.into(),
+                        request: near_primitives::views::QueryRequest::ViewAccessKeyList {
+                            account_id,
+                        },
+                    },
+                )
+                .await
+                .wrap_err("Failed to fetch the access keys")?
+                .access_key_list_view()
+            }
+            .await;
+            (account, access_key_list)
+        }
+    })?;
+
+    let mut report = Vec::new();
+    for (account, access_key_list) in access_key_lists {
+        match access_key_list {
+            Ok(access_key_list) => {
+                report.extend(
+                    access_key_list
+                        .keys
+                        .iter()
+                        .map(|access_key| AuditReportRow {
+                            account_id: account.clone(),
+                            public_key: Some(access_key.public_key.to_string()),
+                            permission: Some(crate::common::access_key_permission_message(
+                                &access_key.access_key.permission,
+                            )),
+                            status: Some(key_lists.key_status(&access_key.public_key)),
+                            error: None,
+                        }),
+                );
+            }
+            Err(err) => report.push(AuditReportRow {
+                account_id: account.clone(),
+                public_key: None,
+                permission: None,
+                status: None,
+                error: Some(format!("{err:#}")),
+            }),
+        }
+    }
+    Ok(report)
+}
+
+fn display_audit_report(report: &[AuditReportRow], format: AuditReportFormat) -> crate::CliResult {
+    match format {
+        AuditReportFormat::Table => {
+            let mut table = Table::new();
+            table.set_titles(
+                prettytable::row![Fg=>"Account", "Public Key", "Permissions", "Status", "Error"],
+            );
+            for row in report {
+                let status_style = match row.status {
+                    Some(KeyStatus::Flagged) => "Fr",
+                    Some(KeyStatus::Unknown) => "Fy",
+                    _ => "",
+                };
+                table.add_row(prettytable::Row::new(vec![
+                    prettytable::Cell::new(&row.account_id),
+                    prettytable::Cell::new(row.public_key.as_deref().unwrap_or_default()),
+                    prettytable::Cell::new(row.permission.as_deref().unwrap_or_default()),
+                    prettytable::Cell::new(
+                        &row.status
+                            .map(|status| status.to_string())
+                            .unwrap_or_default(),
+                    )
+                    .style_spec(status_style),
+                    prettytable::Cell::new(row.error.as_deref().unwrap_or_default())
+                        .style_spec("Fr"),
+                ]));
+            }
+            table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+            table.printstd();
+        }
+        AuditReportFormat::Csv => {
+            println!("account_id,public_key,permission,status,error");
+            for row in report {
+                println!(
+                    "{},{},{},{},{}",
+                    csv_field(&row.account_id),
+                    csv_field(row.public_key.as_deref().unwrap_or_default()),
+                    csv_field(row.permission.as_deref().unwrap_or_default()),
+                    row.status
+                        .map(|status| status.to_string())
+                        .unwrap_or_default(),
+                    csv_field(row.error.as_deref().unwrap_or_default()),
+                );
+            }
+        }
+        AuditReportFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(report)?);
+        }
+    }
+    Ok(())
+}
+
+fn csv_field(value: &str) -> std::borrow::Cow<'_, str> {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\"")).into()
+    } else {
+        value.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_accounts_file_skips_comments_and_empty_lines() {
+        let content = "# team accounts\nalice.near\n\n  bob.near  \n#carol.near\n";
+        assert_eq!(parse_accounts_file(content), vec!["alice.near", "bob.near"]);
+    }
+
+    #[test]
+    fn key_status_prefers_flagged() {
+        let key =
+            near_crypto::SecretKey::from_seed(near_crypto::KeyType::ED25519, "a").public_key();
+        let other_key =
+            near_crypto::SecretKey::from_seed(near_crypto::KeyType::ED25519, "b").public_key();
+        let key_lists = KeyLists {
+            flagged: vec![key.clone()],
+            expected: vec![key.clone()],
+        };
+        assert_eq!(key_lists.key_status(&key), KeyStatus::Flagged);
+        assert_eq!(key_lists.key_status(&other_key), KeyStatus::Unknown);
+        let key_lists = KeyLists {
+            flagged: vec![],
+            expected: vec![key.clone()],
+        };
+        assert_eq!(key_lists.key_status(&key), KeyStatus::Expected);
+    }
+
+    #[test]
+    fn csv_field_escaping() {
+        assert_eq!(csv_field("alice.near"), "alice.near");
+        assert_eq!(
+            csv_field("only do [\"a\", \"b\"] function calls"),
+            "\"only do [\"\"a\"\", \"\"b\"\"] function calls\""
+        );
+    }
+}
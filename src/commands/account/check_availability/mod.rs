@@ -0,0 +1,188 @@
+use color_eyre::eyre::WrapErr;
+
+use crate::common::{CallResultExt, JsonRpcClientExt};
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = CheckAvailabilityContext)]
+pub struct CheckAvailability {
+    #[interactive_clap(long)]
+    /// What is the account ID you want to check (e.g. alice.near or alice.testnet)?
+    account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network::Network,
+}
+
+#[derive(Clone)]
+pub struct CheckAvailabilityContext(crate::network::NetworkContext);
+
+impl CheckAvailabilityContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<CheckAvailability as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let on_after_getting_network_callback: crate::network::OnAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let account_id: near_primitives::types::AccountId = scope.account_id.clone().into();
+
+                move |network_config| {
+                    let availability = if account_exists(network_config, &account_id)? {
+                        Availability::Taken
+                    } else if is_reserved_by_registrar(network_config, &account_id)? {
+                        Availability::Reserved
+                    } else {
+                        Availability::Available
+                    };
+                    eprintln!(
+                        "Account <{account_id}> {} on network <{}>.",
+                        availability.description(),
+                        network_config.network_name
+                    );
+                    println!("{}", availability.status());
+                    Ok(())
+                }
+            });
+
+        Ok(Self(crate::network::NetworkContext {
+            config: previous_context.config,
+            interacting_with_account_ids: vec![scope.account_id.clone().into()],
+            on_after_getting_network_callback,
+        }))
+    }
+}
+
+impl From<CheckAvailabilityContext> for crate::network::NetworkContext {
+    fn from(item: CheckAvailabilityContext) -> Self {
+        item.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Availability {
+    Available,
+    Taken,
+    /// The account does not exist, but the registrar does not allow to create it
+    Reserved,
+}
+
+impl Availability {
+    fn status(&self) -> &'static str {
+        match self {
+            Self::Available => "available",
+            Self::Taken | Self::Reserved => "taken",
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            Self::Available => "does not exist and is available",
+            Self::Taken => "already exists",
+            Self::Reserved => "does not exist, but the registrar reports that it is not available",
+        }
+    }
+}
+
+#[tracing::instrument(name = "Checking whether the account exists ...", skip_all)]
+fn account_exists(
+    network_config: &crate::config::NetworkConfig,
+    account_id: &near_primitives::types::AccountId,
+) -> color_eyre::eyre::Result<bool> {
+    match network_config
+        .json_rpc_client()
+        .blocking_call_view_account(account_id, near_primitives::types::Finality::Final.into())
+    {
+        Ok(_) => Ok(true),
+        Err(near_jsonrpc_client::errors::JsonRpcError::ServerError(
+            near_jsonrpc_client::errors::JsonRpcServerError::HandlerError(
+                near_jsonrpc_primitives::types::query::RpcQueryError::UnknownAccount { .. },
+            ),
+        )) => Ok(false),
+        Err(err) => Err(err).wrap_err_with(|| {
+            format!(
+                "Failed to fetch the account <{account_id}> on network <{}>",
+                network_config.network_name
+            )
+        }),
+    }
+}
+
+/// The registrar (`linkdrop_account_id` of the network) of the account: the account has to be
+/// a top-level account or a direct sub-account of the registrar.
+fn registrar_account_id<'a>(
+    network_config: &'a crate::config::NetworkConfig,
+    account_id: &near_primitives::types::AccountId,
+) -> Option<&'a near_primitives::types::AccountId> {
+    let registrar_account_id = network_config.linkdrop_account_id.as_ref()?;
+    let is_named_account = matches!(
+        account_id.get_account_type(),
+        near_primitives::account::id::AccountType::NamedAccount
+    );
+    (is_named_account && account_id.is_top_level()
+        || account_id.get_parent_account_id() == Some(registrar_account_id.as_ref()))
+    .then_some(registrar_account_id)
+}
+
+#[tracing::instrument(
+    name = "Checking the account availability with the registrar ...",
+    skip_all
+)]
+fn is_reserved_by_registrar(
+    network_config: &crate::config::NetworkConfig,
+    account_id: &near_primitives::types::AccountId,
+) -> color_eyre::eyre::Result<bool> {
+    let Some(registrar_account_id) = registrar_account_id(network_config, account_id) else {
+        return Ok(false);
+    };
+    let call_result = network_config
+        .json_rpc_client()
+        .blocking_call_view_function(
+            registrar_account_id,
+            "check_account_availability",
+            serde_json::to_vec(&serde_json::json!({ "account_id": account_id }))?,
+            near_primitives::types::Finality::Final.into(),
+        );
+    match call_result {
+        Ok(call_result) => Ok(!call_result.parse_result_from_json::<bool>()?),
+        // Most registrars do not implement the method, so only the account existence is checked
+        Err(err)
+            if err
+                .chain()
+                .any(|cause| cause.to_string().contains("MethodNotFound")) =>
+        {
+            tracing::info!(
+                "The registrar <{registrar_account_id}> does not implement 'check_account_availability'"
+            );
+            Ok(false)
+        }
+        Err(err) => Err(err).wrap_err_with(|| {
+            format!(
+                "Failed to fetch query for view method: 'check_account_availability' (contract <{registrar_account_id}> on network <{}>)",
+                network_config.network_name
+            )
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registrar_of_top_level_accounts() {
+        let config = crate::config::Config::default();
+        let mainnet = config.network_connection.get("mainnet").unwrap();
+        let registrar = |account_id: &str| {
+            registrar_account_id(mainnet, &account_id.parse().unwrap()).map(|id| id.to_string())
+        };
+        assert_eq!(registrar("alice.near").as_deref(), Some("near"));
+        assert_eq!(registrar("alice").as_deref(), Some("near"));
+        assert_eq!(registrar("bob.alice.near"), None);
+        assert_eq!(registrar("alice.testnet"), None);
+        assert_eq!(
+            registrar("bb4dc639b212e075a751685b26bdcea5920a504181ff2910e8549742127092a0"),
+            None
+        );
+        assert_eq!(Availability::Reserved.status(), "taken");
+    }
+}
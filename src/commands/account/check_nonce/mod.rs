@@ -0,0 +1,148 @@
+use color_eyre::eyre::WrapErr;
+
+use crate::common::{JsonRpcClientExt, RpcQueryResponseExt};
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = CheckNonceContext)]
+pub struct CheckNonce {
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_default_input_arg)]
+    /// Which account does the access key belong to?
+    account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    /// What is the public key of the access key?
+    public_key: crate::types::public_key::PublicKey,
+    #[interactive_clap(long)]
+    /// What nonce do you expect the access key to have (the nonce of the last transaction signed with it)?
+    expected_nonce: crate::types::nonce::Nonce,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network::Network,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NonceCheck {
+    Match,
+    /// More transactions were signed with the key than expected
+    Ahead {
+        gap: u64,
+    },
+    /// Fewer transactions were executed than expected
+    Behind {
+        gap: u64,
+    },
+}
+
+impl NonceCheck {
+    fn new(on_chain_nonce: u64, expected_nonce: u64) -> Self {
+        match on_chain_nonce.cmp(&expected_nonce) {
+            std::cmp::Ordering::Equal => Self::Match,
+            std::cmp::Ordering::Greater => Self::Ahead {
+                gap: on_chain_nonce - expected_nonce,
+            },
+            std::cmp::Ordering::Less => Self::Behind {
+                gap: expected_nonce - on_chain_nonce,
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for NonceCheck {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Match => write!(f, "The on-chain nonce matches the expected nonce."),
+            Self::Ahead { gap } => write!(
+                f,
+                "The on-chain nonce is {gap} ahead of the expected nonce: the key was used to sign transactions you did not count (another tool or machine uses it), and the transactions you sign with the expected nonces are dropped as invalid. Sign the next transaction with a nonce above the on-chain nonce."
+            ),
+            Self::Behind { gap } => write!(
+                f,
+                "The on-chain nonce is {gap} behind the expected nonce: transactions you signed have not been executed (yet). They may still be included, so re-signing the same actions with new nonces risks executing them twice; wait for them or check their status before retrying."
+            ),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct CheckNonceContext(crate::network::NetworkContext);
+
+impl CheckNonceContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<CheckNonce as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let on_after_getting_network_callback: crate::network::OnAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let account_id: near_primitives::types::AccountId = scope.account_id.clone().into();
+                let public_key: near_crypto::PublicKey = scope.public_key.clone().into();
+                let expected_nonce = scope.expected_nonce.0;
+
+                move |network_config| {
+                    let on_chain_nonce = network_config
+                        .json_rpc_client()
+                        .blocking_call_view_access_key(
+                            &account_id,
+                            &public_key,
+                            near_primitives::types::Finality::Final.into(),
+                        )
+                        .wrap_err_with(|| {
+                            format!(
+                                "Failed to fetch the access key {public_key} of <{account_id}> on network <{}>",
+                                network_config.network_name
+                            )
+                        })?
+                        .access_key_view()?
+                        .nonce;
+                    println!("On-chain nonce: {on_chain_nonce}");
+                    println!("Expected nonce: {expected_nonce}");
+                    let nonce_check = NonceCheck::new(on_chain_nonce, expected_nonce);
+                    if nonce_check == NonceCheck::Match {
+                        eprintln!("\n{nonce_check}");
+                        Ok(())
+                    } else {
+                        Err(color_eyre::eyre::eyre!("{nonce_check}"))
+                    }
+                }
+            });
+
+        Ok(Self(crate::network::NetworkContext {
+            config: previous_context.config,
+            interacting_with_account_ids: vec![scope.account_id.clone().into()],
+            on_after_getting_network_callback,
+        }))
+    }
+}
+
+impl From<CheckNonceContext> for crate::network::NetworkContext {
+    fn from(item: CheckNonceContext) -> Self {
+        item.0
+    }
+}
+
+impl CheckNonce {
+    pub fn input_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "Which account does the access key belong to?",
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nonce_gaps() {
+        assert_eq!(NonceCheck::new(42, 42), NonceCheck::Match);
+        assert_eq!(NonceCheck::new(45, 42), NonceCheck::Ahead { gap: 3 });
+        assert_eq!(NonceCheck::new(40, 42), NonceCheck::Behind { gap: 2 });
+        assert!(NonceCheck::new(45, 42).to_string().contains("dropped"));
+        assert!(NonceCheck::new(0, u64::MAX)
+            .to_string()
+            .contains("executing them twice"));
+    }
+}
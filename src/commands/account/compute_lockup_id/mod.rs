@@ -0,0 +1,64 @@
+/// The lockup master account of mainnet (the lockup accounts are its sub-accounts)
+const DEFAULT_LOCKUP_MASTER_ACCOUNT_ID: &str = "lockup.near";
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = ComputeLockupIdContext)]
+pub struct ComputeLockupId {
+    #[interactive_clap(long)]
+    /// What is the account ID of the lockup owner?
+    account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// The lockup master account (default: lockup.near)
+    lockup_master_account_id: Option<crate::types::account_id::AccountId>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ComputeLockupIdContext;
+
+impl ComputeLockupIdContext {
+    pub fn from_previous_context(
+        _previous_context: crate::GlobalContext,
+        scope: &<ComputeLockupId as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let lockup_master_account_id: near_primitives::types::AccountId =
+            match &scope.lockup_master_account_id {
+                Some(lockup_master_account_id) => lockup_master_account_id.clone().into(),
+                None => DEFAULT_LOCKUP_MASTER_ACCOUNT_ID.parse()?,
+            };
+        let lockup_account_id =
+            compute_lockup_account_id(&scope.account_id.clone().into(), &lockup_master_account_id)?;
+        eprintln!("\nThe lockup account ID of <{}> is:", scope.account_id);
+        println!("{lockup_account_id}");
+        Ok(Self)
+    }
+}
+
+/// The first 40 hex characters of the SHA-256 hash of the owner account ID, as a sub-account of the
+/// lockup master account (this is how the lockup factory names the lockup accounts).
+fn compute_lockup_account_id(
+    owner_account_id: &near_primitives::types::AccountId,
+    lockup_master_account_id: &near_primitives::types::AccountId,
+) -> color_eyre::eyre::Result<near_primitives::types::AccountId> {
+    let hash = near_primitives::hash::hash(owner_account_id.as_bytes());
+    Ok(format!("{}.{lockup_master_account_id}", &hex::encode(hash)[..40]).parse()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lockup_account_id_of_an_owner() {
+        assert_eq!(
+            compute_lockup_account_id(
+                &"alice.near".parse().unwrap(),
+                &"lockup.near".parse().unwrap()
+            )
+            .unwrap()
+            .as_str(),
+            "2dd5dda540767b3a1aa33544bcba38042f4df6de.lockup.near"
+        );
+    }
+}
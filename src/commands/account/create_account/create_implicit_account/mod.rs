@@ -67,10 +67,13 @@ impl SaveToFolder {
         eprintln!();
         Ok(Some(
             CustomType::new("Where to save the implicit account file?")
-                .with_starting_input(&format!(
-                    "{}/implicit",
-                    context.config.credentials_home_dir.to_string_lossy()
-                ))
+                .with_starting_input(
+                    &context
+                        .config
+                        .credentials_home_dir
+                        .join("implicit")
+                        .to_string_lossy(),
+                )
                 .prompt()?,
         ))
     }
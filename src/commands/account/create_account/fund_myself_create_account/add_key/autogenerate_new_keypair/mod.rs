@@ -88,7 +88,7 @@ impl SaveModeContext {
             std::sync::Arc::new({
                 let new_account_id = previous_context.account_properties.new_account_id.clone();
                 let key_pair_properties = previous_context.key_pair_properties.clone();
-                let credentials_home_dir = previous_context.global_context.config.credentials_home_dir.clone();
+                let config = previous_context.global_context.config.clone();
 
                 move |_transaction, network_config| {
                     match scope {
@@ -107,7 +107,7 @@ impl SaveModeContext {
                                 serde_json::to_string(&key_pair_properties)?;
                             crate::common::save_access_key_to_legacy_keychain(
                                 network_config.clone(),
-                                credentials_home_dir.clone(),
+                                config.credentials_dir(network_config),
                                 &key_pair_properties_buf,
                                 &key_pair_properties.public_key_str,
                                 new_account_id.as_ref(),
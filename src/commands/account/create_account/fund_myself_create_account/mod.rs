@@ -1,4 +1,4 @@
-use inquire::{CustomType, Select};
+use inquire::CustomType;
 
 use crate::commands::account::MIN_ALLOWED_TOP_LEVEL_ACCOUNT_LENGTH;
 
@@ -65,11 +65,11 @@ impl NewAccount {
                 )]
                 No,
             }
-            let select_choose_input =
-            Select::new("\nDo you want to check the existence of the specified account so that you don’t waste tokens with sending a transaction that won't succeed?",
+            let Some(select_choose_input) = crate::common::select("\nDo you want to check the existence of the specified account so that you don’t waste tokens with sending a transaction that won't succeed?",
                 vec![ConfirmOptions::Yes{account_id: new_account_id.clone()}, ConfirmOptions::No],
-                )
-                .prompt()?;
+                )? else {
+                return Ok(None);
+            };
             if let ConfirmOptions::Yes { account_id } = select_choose_input {
                 let network = crate::common::find_network_where_account_exist(
                     context,
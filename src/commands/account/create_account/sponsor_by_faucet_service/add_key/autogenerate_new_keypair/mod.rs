@@ -76,7 +76,7 @@ impl SaveModeContext {
             std::sync::Arc::new({
                 let new_account_id_str = previous_context.new_account_id.to_string();
                 let key_pair_properties = previous_context.key_pair_properties.clone();
-                let credentials_home_dir = previous_context.config.credentials_home_dir.clone();
+                let config = previous_context.config.clone();
 
                 move |network_config| {
                     match scope {
@@ -95,7 +95,7 @@ impl SaveModeContext {
                                 serde_json::to_string(&key_pair_properties)?;
                             crate::common::save_access_key_to_legacy_keychain(
                                 network_config.clone(),
-                                credentials_home_dir.clone(),
+                                config.credentials_dir(network_config),
                                 &key_pair_properties_buf,
                                 &key_pair_properties.public_key_str,
                                 &new_account_id_str,
@@ -135,9 +135,13 @@ fn print_account_creation_status(
                         );
                         eprintln!("New account <{new_account_id}> created successfully.\n{storage_message}\n");
                     }
-                    eprintln!("Transaction ID: {id}\nTo see the transaction in the transaction explorer, please open this url in your browser:\n{path}{id}\n",
-                        id=account_creation_transaction.transaction_outcome.id,
-                        path=network_config.explorer_transaction_url
+                    eprintln!(
+                        "Transaction ID: {id}\n{}\n",
+                        crate::common::explorer_transaction_message(
+                            network_config,
+                            &account_creation_transaction.transaction_outcome.id
+                        ),
+                        id = account_creation_transaction.transaction_outcome.id,
                     );
                 }
                 near_primitives::views::FinalExecutionStatus::NotStarted
@@ -0,0 +1,220 @@
+use std::str::FromStr;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = CreateNamedAccountContext)]
+pub struct CreateNamedAccount {
+    #[interactive_clap(long)]
+    /// What is the new account ID (e.g. alice.near or alice.testnet)?
+    new_account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_default_input_arg)]
+    /// Enter the amount of the NEAR tokens you want to fund the new account with:
+    initial_balance: crate::types::near_token::NearToken,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// The full access key of the new account (by default, a new key pair is generated and saved to the legacy keychain)
+    new_public_key: Option<crate::types::public_key::PublicKey>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the signer account ID?
+    signer_id: crate::types::account_id::AccountId,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network_for_transaction::NetworkForTransactionArgs,
+}
+
+#[derive(Clone)]
+pub struct CreateNamedAccountContext(crate::commands::ActionContext);
+
+impl CreateNamedAccountContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<CreateNamedAccount as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let new_account_id: near_primitives::types::AccountId = scope.new_account_id.clone().into();
+        let signer_id: near_primitives::types::AccountId = scope.signer_id.clone().into();
+        let initial_balance = scope.initial_balance;
+
+        let (public_key, key_pair_properties) = match scope.new_public_key.clone() {
+            Some(public_key) => (public_key.into(), None),
+            None => {
+                let key_pair_properties = crate::common::generate_keypair()?;
+                (
+                    near_crypto::PublicKey::from_str(&key_pair_properties.public_key_str)?,
+                    Some(key_pair_properties),
+                )
+            }
+        };
+
+        let get_prepopulated_transaction_after_getting_network_callback: crate::commands::GetPrepopulatedTransactionAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let new_account_id = new_account_id.clone();
+                let signer_id = signer_id.clone();
+                let offline = previous_context.offline;
+
+                move |network_config| {
+                    let registrar_account_id = registrar_account_id(network_config, &new_account_id)?;
+                    if !offline {
+                        ensure_account_does_not_exist(network_config, &new_account_id)?;
+                    }
+                    let args = serde_json::to_vec(&serde_json::json!({
+                        "new_account_id": new_account_id.to_string(),
+                        "new_public_key": public_key.to_string(),
+                    }))?;
+                    Ok(crate::commands::PrepopulatedTransaction {
+                        signer_id: signer_id.clone(),
+                        receiver_id: registrar_account_id,
+                        actions: vec![near_primitives::transaction::Action::FunctionCall(
+                            Box::new(near_primitives::transaction::FunctionCallAction {
+                                method_name: "create_account".to_string(),
+                                args,
+                                gas: crate::common::NearGas::from_tgas(30).as_gas(),
+                                deposit: initial_balance.as_yoctonear(),
+                            }),
+                        )],
+                    })
+                }
+            });
+
+        let on_before_sending_transaction_callback: crate::transaction_signature_options::OnBeforeSendingTransactionCallback =
+            std::sync::Arc::new({
+                let new_account_id = new_account_id.clone();
+                let config = previous_context.config.clone();
+
+                move |_signed_transaction, network_config| {
+                    let Some(key_pair_properties) = &key_pair_properties else {
+                        return Ok(String::new());
+                    };
+                    crate::common::save_access_key_to_legacy_keychain(
+                        network_config.clone(),
+                        config.credentials_dir(network_config),
+                        &serde_json::to_string(key_pair_properties)?,
+                        &key_pair_properties.public_key_str,
+                        new_account_id.as_ref(),
+                    )
+                }
+            });
+
+        let on_after_sending_transaction_callback: crate::transaction_signature_options::OnAfterSendingTransactionCallback =
+            std::sync::Arc::new({
+                let credentials_home_dir = previous_context.config.credentials_home_dir.clone();
+                let new_account_id = new_account_id.clone();
+
+                move |_outcome_view, _network_config| {
+                    crate::common::update_used_account_list_as_signer(
+                        &credentials_home_dir,
+                        &new_account_id,
+                    );
+                    Ok(())
+                }
+            });
+
+        Ok(Self(crate::commands::ActionContext {
+            global_context: previous_context,
+            interacting_with_account_ids: vec![signer_id, new_account_id],
+            get_prepopulated_transaction_after_getting_network_callback,
+            on_before_signing_callback: std::sync::Arc::new(
+                |_prepolulated_unsinged_transaction, _network_config| Ok(()),
+            ),
+            on_before_sending_transaction_callback,
+            on_after_sending_transaction_callback,
+        }))
+    }
+}
+
+impl From<CreateNamedAccountContext> for crate::commands::ActionContext {
+    fn from(item: CreateNamedAccountContext) -> Self {
+        item.0
+    }
+}
+
+impl CreateNamedAccount {
+    fn input_initial_balance(
+        _context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::near_token::NearToken>> {
+        eprintln!();
+        Ok(Some(
+            inquire::CustomType::new("Enter the amount of the NEAR tokens you want to fund the new account with (example: 10NEAR or 0.5near or 10000yoctonear):")
+                .with_starting_input("0.1 NEAR")
+                .prompt()?
+        ))
+    }
+
+    pub fn input_signer_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "What is the signer account ID?",
+        )
+    }
+}
+
+/// The registrar contract (`linkdrop_account_id` of the network, e.g. `near` on mainnet and
+/// `testnet` on testnet) creates its direct sub-accounts.
+fn registrar_account_id(
+    network_config: &crate::config::NetworkConfig,
+    new_account_id: &near_primitives::types::AccountId,
+) -> color_eyre::eyre::Result<near_primitives::types::AccountId> {
+    let registrar_account_id = network_config.linkdrop_account_id.clone().ok_or_else(|| {
+        color_eyre::eyre::eyre!(
+            "Named accounts cannot be created on network <{}> because a <linkdrop_account_id> is not specified in the configuration file.",
+            network_config.network_name
+        )
+    })?;
+    if new_account_id.get_parent_account_id() != Some(registrar_account_id.as_ref()) {
+        return Err(color_eyre::eyre::eyre!(
+            "<{new_account_id}> cannot be created by the registrar <{registrar_account_id}> on network <{}>; only its direct sub-accounts such as <name.{registrar_account_id}> can. Use `account create-account fund-myself` to create a sub-account of your own account.",
+            network_config.network_name
+        ));
+    }
+    Ok(registrar_account_id)
+}
+
+#[tracing::instrument(name = "Checking that the new account does not exist ...", skip_all)]
+fn ensure_account_does_not_exist(
+    network_config: &crate::config::NetworkConfig,
+    account_id: &near_primitives::types::AccountId,
+) -> crate::CliResult {
+    let account_state = tokio::runtime::Runtime::new()?.block_on(crate::common::get_account_state(
+        network_config,
+        account_id,
+        near_primitives::types::BlockReference::latest(),
+    ));
+    match account_state {
+        Ok(_) => Err(color_eyre::eyre::eyre!(
+            "Account <{account_id}> already exists on network <{}>",
+            network_config.network_name
+        )),
+        Err(near_jsonrpc_client::errors::JsonRpcError::ServerError(
+            near_jsonrpc_client::errors::JsonRpcServerError::HandlerError(
+                near_jsonrpc_primitives::types::query::RpcQueryError::UnknownAccount { .. },
+            ),
+        )) => Ok(()),
+        Err(err) => {
+            tracing::warn!(
+                "Failed to check whether <{account_id}> exists ({err}), creating it anyway"
+            );
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_direct_sub_accounts_of_the_registrar() {
+        let config = crate::config::Config::default();
+        let mainnet = config.network_connection.get("mainnet").unwrap();
+        let registrar = |account_id: &str| {
+            registrar_account_id(mainnet, &account_id.parse().unwrap()).map(|id| id.to_string())
+        };
+        assert_eq!(registrar("alice.near").unwrap(), "near");
+        assert!(registrar("bob.alice.near").is_err());
+        assert!(registrar("alice.testnet").is_err());
+        assert!(registrar("alice").is_err());
+    }
+}
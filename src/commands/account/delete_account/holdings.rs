@@ -0,0 +1,395 @@
+//! The assets beyond NEAR that are lost when an account is deleted: FT balances, NFTs, a deployed
+//! contract and delegated stake. Every probe is time-boxed, and a probe that fails or cannot run
+//! (e.g. the NFTs without a `fastnear_url`) is skipped with a warning. The staking pools are the
+//! ones of the watchlist and, with a `fastnear_url`, the ones FastNear knows.
+
+use color_eyre::eyre::WrapErr;
+use futures::StreamExt;
+
+use crate::common::{CallResultExt, RpcQueryResponseExt};
+
+const PROBE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+const CONCURRENCY: usize = 10;
+
+/// The FTs of the account as returned by FastNear (`/v1/account/{account_id}/ft`)
+#[derive(Debug, serde::Deserialize)]
+struct FastnearFtResponse {
+    tokens: Vec<FastnearToken>,
+}
+
+/// The NFT contracts of the account as returned by FastNear (`/v1/account/{account_id}/nft`)
+#[derive(Debug, serde::Deserialize)]
+struct FastnearNftResponse {
+    tokens: Vec<FastnearToken>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct FastnearToken {
+    contract_id: near_primitives::types::AccountId,
+}
+
+/// The staking pools of the account as returned by FastNear (`/v1/account/{account_id}/staking`)
+#[derive(Debug, serde::Deserialize)]
+struct FastnearStakingResponse {
+    pools: Vec<FastnearStakingPool>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct FastnearStakingPool {
+    pool_id: near_primitives::types::AccountId,
+}
+
+/// Describes the assets the account still holds, e.g. ["120 USDC", "3 NFTs", "a deployed contract"].
+#[tracing::instrument(name = "Checking what the account still holds ...", skip_all)]
+pub fn get_holdings(
+    config: &crate::config::Config,
+    network_config: &crate::config::NetworkConfig,
+    account_id: &near_primitives::types::AccountId,
+) -> color_eyre::eyre::Result<Vec<String>> {
+    let runtime = crate::common::multi_thread_runtime()?;
+    let probes = Probes {
+        json_rpc_client: network_config.json_rpc_client(),
+        http_client: reqwest::Client::builder().timeout(PROBE_TIMEOUT).build()?,
+        fastnear_url: network_config.fastnear_url.as_ref().map(|fastnear_url| {
+            crate::config::config_url::ConfigUrl::new(network_config, "fastnear_url", fastnear_url)
+        }),
+        network_name: &network_config.network_name,
+        watchlist_staking_pools: watchlist_staking_pools(config, network_config),
+        account_id,
+    };
+    Ok(runtime.block_on(async {
+        let (fts, nfts, contract, stake) = futures::join!(
+            time_boxed("the FT balances", probes.fts()),
+            time_boxed("the NFTs", probes.nfts()),
+            time_boxed("the contract", probes.contract()),
+            time_boxed("the delegated stake", probes.stake()),
+        );
+        [fts, nfts, contract, stake].into_iter().flatten().collect()
+    }))
+}
+
+pub fn holdings_message(
+    account_id: &near_primitives::types::AccountId,
+    holdings: &[String],
+) -> String {
+    format!(
+        "The account <{account_id}> still holds: {}.\nThese assets are lost when the account is deleted (only the NEAR balance goes to the beneficiary). Move them first, or use --force to delete the account anyway.",
+        holdings.join(", ")
+    )
+}
+
+/// The descriptions of the probe, or none if it failed or timed out.
+async fn time_boxed(
+    name: &str,
+    probe: impl std::future::Future<Output = color_eyre::eyre::Result<Vec<String>>>,
+) -> Vec<String> {
+    match tokio::time::timeout(PROBE_TIMEOUT, probe).await {
+        Ok(Ok(holdings)) => holdings,
+        Ok(Err(err)) => {
+            eprintln!("Warning: skipped the check of {name}: {err:#}");
+            vec![]
+        }
+        Err(_) => {
+            eprintln!("Warning: skipped the check of {name}: timed out after {PROBE_TIMEOUT:?}");
+            vec![]
+        }
+    }
+}
+
+struct Probes<'a> {
+    json_rpc_client: near_jsonrpc_client::JsonRpcClient,
+    http_client: reqwest::Client,
+    fastnear_url: Option<crate::config::config_url::ConfigUrl>,
+    network_name: &'a str,
+    watchlist_staking_pools: Vec<near_primitives::types::AccountId>,
+    account_id: &'a near_primitives::types::AccountId,
+}
+
+/// The watched accounts of the network that are staking pools (sub-accounts of the staking pools
+/// factory, e.g. `*.poolv1.near`).
+fn watchlist_staking_pools(
+    config: &crate::config::Config,
+    network_config: &crate::config::NetworkConfig,
+) -> Vec<near_primitives::types::AccountId> {
+    let Some(staking_pools_factory_account_id) = &network_config.staking_pools_factory_account_id
+    else {
+        return vec![];
+    };
+    config
+        .watchlist
+        .iter()
+        .filter(|watched_account| {
+            watched_account.network_name == network_config.network_name
+                && watched_account
+                    .account_id
+                    .is_sub_account_of(staking_pools_factory_account_id)
+        })
+        .map(|watched_account| watched_account.account_id.clone())
+        .collect()
+}
+
+impl Probes<'_> {
+    async fn fastnear<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+    ) -> color_eyre::eyre::Result<Option<T>> {
+        let Some(fastnear_url) = &self.fastnear_url else {
+            return Ok(None);
+        };
+        let response = self
+            .http_client
+            .get(fastnear_url.join(&format!("v1/account/{}/{path}", self.account_id))?)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .wrap_err("Unexpected response of FastNear")?;
+        Ok(Some(response))
+    }
+
+    async fn call_view_function<T: serde::de::DeserializeOwned>(
+        &self,
+        contract_account_id: &near_primitives::types::AccountId,
+        method_name: &str,
+        args: serde_json::Value,
+    ) -> color_eyre::eyre::Result<T> {
+        crate::rpc_rate_limit::call(
+            &self.json_rpc_client,
+            near_jsonrpc_client::methods::query::RpcQueryRequest {
+                block_reference: near_primitives::types::Finality::Final.into(),
+                request: near_primitives::views::QueryRequest::CallFunction {
+                    account_id: contract_account_id.clone(),
+                    method_name: method_name.to_string(),
+                    args: near_primitives::types::FunctionArgs::from(serde_json::to_vec(&args)?),
+                },
+            },
+        )
+        .await
+        .wrap_err_with(|| {
+            format!("Failed to fetch query for view method: '{method_name}' (contract <{contract_account_id}>)")
+        })?
+        .call_result()?
+        .parse_result_from_json()
+    }
+
+    /// The FT contracts from FastNear, or the built-in list of popular FT contracts
+    async fn fts(&self) -> color_eyre::eyre::Result<Vec<String>> {
+        let ft_contracts = match self.fastnear::<FastnearFtResponse>("ft").await? {
+            Some(response) => response
+                .tokens
+                .into_iter()
+                .map(|token| token.contract_id)
+                .collect(),
+            None => super::super::view_all_balances::get_popular_ft_contracts(self.network_name)?,
+        };
+        let block_reference = near_primitives::types::Finality::Final.into();
+        let ft_balances: Vec<_> = futures::stream::iter(&ft_contracts)
+            .map(|ft_contract_account_id| {
+                super::super::view_all_balances::get_ft_balance(
+                    &self.json_rpc_client,
+                    ft_contract_account_id,
+                    self.account_id,
+                    &block_reference,
+                )
+            })
+            .buffered(CONCURRENCY)
+            .collect()
+            .await;
+        Ok(ft_contracts
+            .iter()
+            .zip(ft_balances)
+            .filter_map(|(ft_contract_account_id, ft_balance)| match ft_balance {
+                Ok(ft_balance) => (ft_balance.amount() > 0).then(|| ft_balance.to_string()),
+                Err(err) => {
+                    eprintln!(
+                        "Warning: skipped the FT balance on <{ft_contract_account_id}>: {err:#}"
+                    );
+                    None
+                }
+            })
+            .collect())
+    }
+
+    /// The NFTs on the contracts from FastNear (NEP-181 `nft_supply_for_owner`)
+    async fn nfts(&self) -> color_eyre::eyre::Result<Vec<String>> {
+        let Some(response) = self.fastnear::<FastnearNftResponse>("nft").await? else {
+            color_eyre::eyre::bail!(
+                "there is no `fastnear_url` for network <{}> to find the NFT contracts",
+                self.network_name
+            );
+        };
+        let supplies: Vec<color_eyre::eyre::Result<String>> =
+            futures::stream::iter(&response.tokens)
+                .map(|token| {
+                    self.call_view_function(
+                        &token.contract_id,
+                        "nft_supply_for_owner",
+                        serde_json::json!({ "account_id": self.account_id }),
+                    )
+                })
+                .buffered(CONCURRENCY)
+                .collect()
+                .await;
+        let mut nfts_count: u128 = 0;
+        for (token, supply) in response.tokens.iter().zip(supplies) {
+            match supply.and_then(|supply| {
+                supply
+                    .parse::<u128>()
+                    .wrap_err_with(|| format!("Invalid NFT supply: {supply}"))
+            }) {
+                Ok(supply) => nfts_count = nfts_count.saturating_add(supply),
+                Err(err) => {
+                    eprintln!(
+                        "Warning: skipped the NFTs on <{}>: {err:#}",
+                        token.contract_id
+                    )
+                }
+            }
+        }
+        Ok(match nfts_count {
+            0 => vec![],
+            1 => vec!["1 NFT".to_string()],
+            nfts_count => vec![format!("{nfts_count} NFTs")],
+        })
+    }
+
+    async fn contract(&self) -> color_eyre::eyre::Result<Vec<String>> {
+        let account_view = crate::rpc_rate_limit::call(
+            &self.json_rpc_client,
+            near_jsonrpc_client::methods::query::RpcQueryRequest {
+                block_reference: near_primitives::types::Finality::Final.into(),
+                request: near_primitives::views::QueryRequest::ViewAccount {
+                    account_id: self.account_id.clone(),
+                },
+            },
+        )
+        .await
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch query ViewAccount for <{}>",
+                self.account_id
+            )
+        })?
+        .account_view()?;
+        Ok(
+            if account_view.code_hash == near_primitives::hash::CryptoHash::default() {
+                vec![]
+            } else {
+                vec!["a deployed contract".to_string()]
+            },
+        )
+    }
+
+    /// The staked and unstaked balances in the staking pools of the watchlist and from FastNear
+    async fn stake(&self) -> color_eyre::eyre::Result<Vec<String>> {
+        let mut pools = self.watchlist_staking_pools.clone();
+        match self.fastnear::<FastnearStakingResponse>("staking").await {
+            Ok(Some(response)) => pools.extend(response.pools.into_iter().map(|pool| pool.pool_id)),
+            Ok(None) if pools.is_empty() => color_eyre::eyre::bail!(
+                "there is no `fastnear_url` for network <{}> to find the staking pools, and no staking pool in the watchlist",
+                self.network_name
+            ),
+            Ok(None) => eprintln!(
+                "Warning: the delegated stake is only checked in the staking pools of the watchlist (there is no `fastnear_url` for network <{}>)",
+                self.network_name
+            ),
+            Err(err) if pools.is_empty() => return Err(err),
+            Err(err) => eprintln!(
+                "Warning: the delegated stake is only checked in the staking pools of the watchlist: {err:#}"
+            ),
+        }
+        pools.sort();
+        pools.dedup();
+        let balances: Vec<color_eyre::eyre::Result<String>> = futures::stream::iter(&pools)
+            .map(|pool_id| {
+                self.call_view_function(
+                    pool_id,
+                    "get_account_total_balance",
+                    serde_json::json!({ "account_id": self.account_id }),
+                )
+            })
+            .buffered(CONCURRENCY)
+            .collect()
+            .await;
+        let mut stake = vec![];
+        for (pool_id, balance) in pools.iter().zip(balances) {
+            match balance.and_then(|balance| {
+                balance
+                    .parse::<u128>()
+                    .wrap_err_with(|| format!("Invalid balance: {balance}"))
+            }) {
+                Ok(balance) => {
+                    let balance = near_token::NearToken::from_yoctonear(balance);
+                    if !balance.is_zero() {
+                        stake.push(format!("{balance} staked with <{pool_id}>"));
+                    }
+                }
+                Err(err) => eprintln!("Warning: skipped the stake in <{pool_id}>: {err:#}"),
+            }
+        }
+        Ok(stake)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fastnear_responses() {
+        let fts: FastnearFtResponse = serde_json::from_value(serde_json::json!({
+            "account_id": "alice.near",
+            "tokens": [{ "balance": "10", "contract_id": "usdt.tether-token.near", "last_update_block_height": 1 }]
+        }))
+        .unwrap();
+        assert_eq!(fts.tokens[0].contract_id.as_str(), "usdt.tether-token.near");
+        let nfts: FastnearNftResponse = serde_json::from_value(serde_json::json!({
+            "account_id": "alice.near",
+            "tokens": [{ "contract_id": "nft.example.near", "last_update_block_height": null }]
+        }))
+        .unwrap();
+        assert_eq!(nfts.tokens[0].contract_id.as_str(), "nft.example.near");
+        let staking: FastnearStakingResponse = serde_json::from_value(serde_json::json!({
+            "account_id": "alice.near",
+            "pools": [{ "last_update_block_height": null, "pool_id": "pool.poolv1.near" }]
+        }))
+        .unwrap();
+        assert_eq!(staking.pools[0].pool_id.as_str(), "pool.poolv1.near");
+    }
+
+    #[test]
+    fn staking_pools_of_the_watchlist() {
+        let mut config = crate::config::Config::default();
+        config.watchlist = ["pool.poolv1.near", "alice.near", "other.pool.f863973.m0"]
+            .into_iter()
+            .map(|account_id| crate::config::WatchedAccount {
+                account_id: account_id.parse().unwrap(),
+                network_name: "mainnet".to_string(),
+            })
+            .collect();
+        let network_config = config.network_connection["mainnet"].clone();
+        assert_eq!(
+            watchlist_staking_pools(&config, &network_config),
+            vec!["pool.poolv1.near"
+                .parse::<near_primitives::types::AccountId>()
+                .unwrap()]
+        );
+    }
+
+    #[test]
+    fn holdings_are_listed_in_the_message() {
+        let message = holdings_message(
+            &"alice.near".parse().unwrap(),
+            &[
+                "120 USDC".to_string(),
+                "3 NFTs".to_string(),
+                "a deployed contract".to_string(),
+            ],
+        );
+        assert!(message.starts_with(
+            "The account <alice.near> still holds: 120 USDC, 3 NFTs, a deployed contract.\n"
+        ));
+        assert!(message.ends_with("use --force to delete the account anyway."));
+    }
+}
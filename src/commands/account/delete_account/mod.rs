@@ -1,5 +1,6 @@
 use color_eyre::owo_colors::OwoColorize;
-use inquire::Select;
+
+mod holdings;
 
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = crate::GlobalContext)]
@@ -8,6 +9,9 @@ pub struct DeleteAccount {
     #[interactive_clap(skip_default_input_arg)]
     /// What Account ID to be deleted?
     account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    /// Delete the account even if it still holds FTs, NFTs, a contract or delegated stake
+    force: bool,
     #[interactive_clap(named_arg)]
     /// Enter the beneficiary ID to delete this account ID
     beneficiary: BeneficiaryAccount,
@@ -17,6 +21,7 @@ pub struct DeleteAccount {
 pub struct DeleteAccountContext {
     global_context: crate::GlobalContext,
     account_id: near_primitives::types::AccountId,
+    force: bool,
 }
 
 impl DeleteAccountContext {
@@ -27,6 +32,7 @@ impl DeleteAccountContext {
         Ok(Self {
             global_context: previous_context,
             account_id: scope.account_id.clone().into(),
+            force: scope.force,
         })
     }
 }
@@ -58,6 +64,7 @@ pub struct BeneficiaryAccount {
 pub struct BeneficiaryAccountContext {
     global_context: crate::GlobalContext,
     account_id: near_primitives::types::AccountId,
+    force: bool,
     beneficiary_account_id: near_primitives::types::AccountId,
 }
 
@@ -69,6 +76,7 @@ impl BeneficiaryAccountContext {
         Ok(Self {
             global_context: previous_context.global_context,
             account_id: previous_context.account_id,
+            force: previous_context.force,
             beneficiary_account_id: scope.beneficiary_account_id.clone().into(),
         })
     }
@@ -79,8 +87,19 @@ impl From<BeneficiaryAccountContext> for crate::commands::ActionContext {
         let get_prepopulated_transaction_after_getting_network_callback: crate::commands::GetPrepopulatedTransactionAfterGettingNetworkCallback =
             std::sync::Arc::new({
                 let account_id = item.account_id.clone();
+                let check_holdings = !item.force && !item.global_context.offline;
+                let config = item.global_context.config.clone();
 
-                move |_network_config| {
+                move |network_config| {
+                    if check_holdings {
+                        let holdings = self::holdings::get_holdings(&config, network_config, &account_id)?;
+                        if !holdings.is_empty() {
+                            return Err(color_eyre::eyre::eyre!(
+                                "{}",
+                                self::holdings::holdings_message(&account_id, &holdings)
+                            ));
+                        }
+                    }
                     Ok(crate::commands::PrepopulatedTransaction {
                         signer_id: account_id.clone(),
                         receiver_id: account_id.clone(),
@@ -146,11 +165,11 @@ impl BeneficiaryAccount {
                 #[display(fmt = "No, I know this account exists and want to continue.")]
                 No,
             }
-            let select_choose_input =
-                Select::new("\nDo you want to check the existence of the specified account so that you don't lose tokens?",
+            let Some(select_choose_input) = crate::common::select("\nDo you want to check the existence of the specified account so that you don't lose tokens?",
                     vec![ConfirmOptions::Yes{account_id: beneficiary_account_id.clone()}, ConfirmOptions::No],
-                    )
-                    .prompt()?;
+                    )? else {
+return Ok(None);
+};
             if let ConfirmOptions::Yes { account_id } = select_choose_input {
                 if crate::common::find_network_where_account_exist(
                     &context.global_context,
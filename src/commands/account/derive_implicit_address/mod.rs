@@ -0,0 +1,95 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = DeriveImplicitAddressContext)]
+pub struct DeriveImplicitAddress {
+    /// Enter the public key (ed25519 for a NEAR-implicit account, secp256k1 for an eth-implicit account):
+    public_key: crate::types::public_key::PublicKey,
+}
+
+#[derive(Debug, Clone)]
+pub struct DeriveImplicitAddressContext;
+
+impl DeriveImplicitAddressContext {
+    pub fn from_previous_context(
+        _previous_context: crate::GlobalContext,
+        scope: &<DeriveImplicitAddress as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let implicit_account_id = derive_implicit_account_id(&scope.public_key.clone().into());
+        eprintln!();
+        match crate::types::account_id::eth_address(&implicit_account_id) {
+            Some(eth_address) => {
+                eprintln!("Eth-implicit account ID: {implicit_account_id}");
+                eprintln!("ETH address: {eth_address}");
+            }
+            None => eprintln!("NEAR-implicit account ID: {implicit_account_id}"),
+        }
+        Ok(Self)
+    }
+}
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = PrintImplicitAddressContext)]
+pub struct PrintImplicitAddress {
+    #[interactive_clap(long)]
+    /// Enter the public key (ed25519 for a NEAR-implicit account, secp256k1 for an eth-implicit account):
+    public_key: crate::types::public_key::PublicKey,
+}
+
+#[derive(Debug, Clone)]
+pub struct PrintImplicitAddressContext;
+
+impl PrintImplicitAddressContext {
+    pub fn from_previous_context(
+        _previous_context: crate::GlobalContext,
+        scope: &<PrintImplicitAddress as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        // Only the account ID goes to stdout, e.g. to compare it with an expected one in a script
+        println!(
+            "{}",
+            derive_implicit_account_id(&scope.public_key.clone().into())
+        );
+        Ok(Self)
+    }
+}
+
+fn derive_implicit_account_id(
+    public_key: &near_crypto::PublicKey,
+) -> near_primitives::types::AccountId {
+    match public_key {
+        near_crypto::PublicKey::ED25519(public_key) => {
+            near_primitives::utils::derive_near_implicit_account_id(public_key)
+        }
+        near_crypto::PublicKey::SECP256K1(public_key) => {
+            near_primitives::utils::derive_eth_implicit_account_id(public_key)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derive_implicit_account_ids() {
+        let ed25519_public_key =
+            near_crypto::PublicKey::from_seed(near_crypto::KeyType::ED25519, "test");
+        assert_eq!(
+            derive_implicit_account_id(&ed25519_public_key).as_str(),
+            "bb4dc639b212e075a751685b26bdcea5920a504181ff2910e8549742127092a0"
+        );
+        let secp256k1_public_key =
+            near_crypto::PublicKey::from_seed(near_crypto::KeyType::SECP256K1, "test");
+        let eth_implicit_account_id = derive_implicit_account_id(&secp256k1_public_key);
+        assert!(matches!(
+            eth_implicit_account_id.get_account_type(),
+            near_primitives::account::id::AccountType::EthImplicitAccount
+        ));
+        assert!(crate::types::account_id::eth_address(&eth_implicit_account_id).is_some());
+        // The NEAR-implicit account ID is the lowercase hex of the key bytes
+        assert_eq!(
+            derive_implicit_account_id(&ed25519_public_key).as_str(),
+            hex::encode(ed25519_public_key.key_data())
+        );
+    }
+}
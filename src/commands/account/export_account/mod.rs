@@ -126,10 +126,9 @@ pub fn get_password_from_keychain(
 pub fn get_account_key_pair_from_legacy_keychain(
     network_config: &crate::config::NetworkConfig,
     account_id: &near_primitives::types::AccountId,
-    credentials_home_dir: &std::path::Path,
+    credentials_dir: &std::path::Path,
 ) -> color_eyre::eyre::Result<crate::transaction_signature_options::AccountKeyPair> {
-    let data_path =
-        get_account_key_pair_data_path(network_config, account_id, credentials_home_dir)?;
+    let data_path = get_account_key_pair_data_path(network_config, account_id, credentials_dir)?;
     let data = std::fs::read_to_string(&data_path).wrap_err("Access key file not found!")?;
     let account_key_pair: crate::transaction_signature_options::AccountKeyPair =
         serde_json::from_str(&data)
@@ -140,13 +139,13 @@ pub fn get_account_key_pair_from_legacy_keychain(
 fn get_account_key_pair_data_path(
     network_config: &crate::config::NetworkConfig,
     account_id: &near_primitives::types::AccountId,
-    credentials_home_dir: &std::path::Path,
+    credentials_dir: &std::path::Path,
 ) -> color_eyre::eyre::Result<std::path::PathBuf> {
     let check_if_seed_phrase_exists = false;
     get_account_properties_data_path(
         network_config,
         account_id,
-        credentials_home_dir,
+        credentials_dir,
         check_if_seed_phrase_exists,
     )
 }
@@ -158,11 +157,11 @@ fn get_account_key_pair_data_path(
 pub fn get_account_properties_data_path(
     network_config: &crate::config::NetworkConfig,
     account_id: &near_primitives::types::AccountId,
-    credentials_home_dir: &std::path::Path,
+    credentials_dir: &std::path::Path,
     check_if_seed_phrase_exists: bool,
 ) -> color_eyre::eyre::Result<std::path::PathBuf> {
     let file_name = format!("{}.json", account_id);
-    let mut path = std::path::PathBuf::from(credentials_home_dir);
+    let mut path = std::path::PathBuf::from(credentials_dir);
 
     let dir_name = network_config.network_name.clone();
     path.push(&dir_name);
@@ -191,7 +190,7 @@ pub fn get_account_properties_data_path(
         )
         .wrap_err_with(|| format!("Failed to fetch access KeyList for {}", account_id))?
         .access_key_list_view()?;
-    let mut path = std::path::PathBuf::from(credentials_home_dir);
+    let mut path = std::path::PathBuf::from(credentials_dir);
     path.push(dir_name);
     path.push(account_id.to_string());
     let mut data_path = std::path::PathBuf::new();
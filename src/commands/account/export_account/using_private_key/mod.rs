@@ -36,7 +36,7 @@ impl ExportAccountFromPrivateKeyContext {
                     let account_key_pair = super::get_account_key_pair_from_legacy_keychain(
                         network_config,
                         &account_id,
-                        &config.credentials_home_dir,
+                        &config.credentials_dir(network_config),
                     )
                     .wrap_err_with(|| {
                         format!("There are no access keys in keychain to export for account <{account_id}>.")
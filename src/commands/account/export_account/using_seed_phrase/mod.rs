@@ -40,7 +40,7 @@ impl ExportAccountFromSeedPhraseContext {
                     let data_path = get_seed_phrase_data_path(
                         network_config,
                         &account_id,
-                        &config.credentials_home_dir,
+                        &config.credentials_dir(network_config),
                     )?;
 
                     let data = std::fs::read_to_string(&data_path)
@@ -74,13 +74,13 @@ impl From<ExportAccountFromSeedPhraseContext> for crate::network::NetworkContext
 fn get_seed_phrase_data_path(
     network_config: &crate::config::NetworkConfig,
     account_id: &near_primitives::types::AccountId,
-    credentials_home_dir: &std::path::Path,
+    credentials_dir: &std::path::Path,
 ) -> color_eyre::eyre::Result<std::path::PathBuf> {
     let check_if_seed_phrase_exists = true;
     super::get_account_properties_data_path(
         network_config,
         account_id,
-        credentials_home_dir,
+        credentials_dir,
         check_if_seed_phrase_exists,
     )
 }
@@ -34,7 +34,7 @@ impl ExportAccountFromWebWalletContext {
                     let account_key_pair = super::get_account_key_pair_from_legacy_keychain(
                         network_config,
                         &account_id,
-                        &config.credentials_home_dir,
+                        &config.credentials_dir(network_config),
                     )?;
                     auto_import_secret_key(
                         network_config,
@@ -63,7 +63,12 @@ fn auto_import_secret_key(
     account_id: &near_primitives::types::AccountId,
     private_key: &near_crypto::SecretKey,
 ) -> crate::CliResult {
-    let mut url: url::Url = network_config.wallet_url.join("auto-import-secret-key")?;
+    let mut url: url::Url = crate::config::config_url::ConfigUrl::new(
+        network_config,
+        "wallet_url",
+        &network_config.wallet_url,
+    )
+    .join("auto-import-secret-key")?;
     let fragment = format!("{}/{}", account_id, private_key);
     url.set_fragment(Some(&fragment));
     eprintln!(
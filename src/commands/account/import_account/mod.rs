@@ -1,8 +1,11 @@
 #![allow(clippy::enum_variant_names, clippy::large_enum_variant)]
 use std::{str::FromStr, vec};
 
-use color_eyre::eyre::Context;
-use inquire::{CustomType, Select};
+use color_eyre::{
+    eyre::{Context, ContextCompat},
+    owo_colors::OwoColorize,
+};
+use inquire::CustomType;
 use strum::{EnumDiscriminants, EnumIter, EnumMessage};
 
 mod using_private_key;
@@ -40,23 +43,23 @@ pub enum ImportAccountActions {
 
 pub fn login(
     network_config: crate::config::NetworkConfig,
-    credentials_home_dir: std::path::PathBuf,
+    config: &crate::config::Config,
     key_pair_properties_buf: &str,
     public_key_str: &str,
     error_message: &str,
 ) -> crate::CliResult {
     let public_key: near_crypto::PublicKey = near_crypto::PublicKey::from_str(public_key_str)?;
 
-    let account_id = loop {
+    let (account_id, access_key) = loop {
         let account_id_from_cli = input_account_id()?;
         eprintln!();
-        if crate::common::verify_account_access_key(
+        if let Ok(access_key) = crate::common::verify_account_access_key(
             account_id_from_cli.clone(),
             public_key.clone(),
             network_config.clone(),
-        )
-        .is_err()
-        {
+        ) {
+            break (account_id_from_cli, Some(access_key));
+        } else {
             eprintln!("{}", error_message);
 
             #[derive(strum_macros::Display)]
@@ -66,29 +69,149 @@ pub fn login(
                 #[strum(to_string = "No, I want to save the access key information.")]
                 No,
             }
-            let select_choose_input = Select::new(
+            let select_choose_input = crate::common::select(
                 "Would you like to re-enter the account_id?",
                 vec![ConfirmOptions::Yes, ConfirmOptions::No],
-            )
-            .prompt()?;
+            )?
+            .ok_or_else(|| color_eyre::eyre::eyre!("The login was canceled"))?;
             if let ConfirmOptions::No = select_choose_input {
-                break account_id_from_cli;
+                break (account_id_from_cli, None);
             }
-        } else {
-            break account_id_from_cli;
         }
     };
-    crate::common::update_used_account_list_as_signer(&credentials_home_dir, &account_id);
-    save_access_key(
-        account_id,
+    crate::common::update_used_account_list_as_signer(&config.credentials_home_dir, &account_id);
+    let credentials_dir = config.credentials_dir(&network_config);
+    let network_name = network_config.network_name.clone();
+    let key_storage = save_access_key(
+        account_id.clone(),
         key_pair_properties_buf,
         public_key_str,
         network_config,
-        credentials_home_dir,
+        credentials_dir,
     )?;
+    eprintln!(
+        "\n{}",
+        login_details(
+            &account_id,
+            &public_key,
+            access_key.as_ref(),
+            &key_storage,
+            &network_name
+        )
+    );
+    if let Some(access_key) = &access_key {
+        if let Some(warning) = function_call_key_warning(&account_id, access_key) {
+            eprintln!("\n{}", warning.red().bold());
+        }
+        println!("{}", login_summary(&account_id, &public_key));
+    }
     Ok(())
 }
 
+/// Where the access key was saved
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum KeyStorage {
+    Keychain {
+        service_name: String,
+        item_name: String,
+    },
+    LegacyKeychain {
+        key_file: std::path::PathBuf,
+    },
+    /// `--no-keychain`: the key pair was only printed
+    NotSaved,
+}
+
+/// The closing block of the login: what the key can do, where it was saved and how to try it.
+fn login_details(
+    account_id: &near_primitives::types::AccountId,
+    public_key: &near_crypto::PublicKey,
+    access_key: Option<&near_primitives::views::AccessKeyView>,
+    key_storage: &KeyStorage,
+    network_name: &str,
+) -> String {
+    let permission = match access_key.map(|access_key| &access_key.permission) {
+        None => format!("unknown (the access key was not found on <{network_name}>)"),
+        Some(near_primitives::views::AccessKeyPermissionView::FullAccess) => {
+            "full access".to_string()
+        }
+        Some(near_primitives::views::AccessKeyPermissionView::FunctionCall {
+            allowance,
+            receiver_id,
+            method_names,
+        }) => format!(
+            "function call to <{receiver_id}> only ({}), allowance: {}",
+            if method_names.is_empty() {
+                "any method".to_string()
+            } else {
+                format!("methods: {}", method_names.join(", "))
+            },
+            allowance.map_or_else(
+                || "unlimited".to_string(),
+                |allowance| crate::types::near_token::NearToken::from_yoctonear(allowance)
+                    .to_string()
+            )
+        ),
+    };
+    let (storage, sign_with) = match key_storage {
+        KeyStorage::Keychain {
+            service_name,
+            item_name,
+        } => (
+            format!("the keychain (service \"{service_name}\", item \"{item_name}\")"),
+            "sign-with-keychain",
+        ),
+        KeyStorage::LegacyKeychain { key_file } => (
+            format!("the legacy keychain ({})", key_file.display()),
+            "sign-with-legacy-keychain",
+        ),
+        KeyStorage::NotSaved => (
+            "nowhere (--no-keychain), keep the printed key pair".to_string(),
+            "sign-with-plaintext-private-key",
+        ),
+    };
+    let test_command = match access_key.map(|access_key| &access_key.permission) {
+        Some(near_primitives::views::AccessKeyPermissionView::FunctionCall {
+            receiver_id,
+            method_names,
+            ..
+        }) => format!(
+            "near contract call-function as-transaction {receiver_id} {} json-args {{}} prepaid-gas '30 Tgas' attached-deposit '0 NEAR' sign-as {account_id} network-config {network_name} {sign_with} display",
+            method_names.first().map_or("<method-name>", String::as_str)
+        ),
+        _ => format!(
+            "near tokens {account_id} send-near {account_id} '0.001 NEAR' network-config {network_name} {sign_with} display"
+        ),
+    };
+    format!(
+        "Account:     {account_id}\nPublic key:  {public_key}\nPermission:  {permission}\nSaved to:    {storage}\nSign a test transaction (it is displayed, not sent):\n    {test_command}"
+    )
+}
+
+/// A function-call key can only call the methods of one contract (without a deposit), which is
+/// not what the users who signed in to send tokens or deploy contracts expect.
+fn function_call_key_warning(
+    account_id: &near_primitives::types::AccountId,
+    access_key: &near_primitives::views::AccessKeyView,
+) -> Option<String> {
+    let near_primitives::views::AccessKeyPermissionView::FunctionCall { receiver_id, .. } =
+        &access_key.permission
+    else {
+        return None;
+    };
+    Some(format!(
+        "WARNING: The imported key is NOT a full access key of <{account_id}>: it can only call the contract <{receiver_id}> without attaching a deposit. Sending tokens, staking, deploying contracts or managing keys fails with this key. To get full access, sign in again and approve full access in the wallet, or import a full access key."
+    ))
+}
+
+/// The sign-in summary in the format of the JS CLI (downstream tools look for this line).
+fn login_summary(
+    account_id: &near_primitives::types::AccountId,
+    public_key: &near_crypto::PublicKey,
+) -> String {
+    format!("Logged in as {account_id} with public key {public_key} successfully")
+}
+
 fn input_account_id() -> color_eyre::eyre::Result<near_primitives::types::AccountId> {
     Ok(CustomType::new("Enter account ID:").prompt()?)
 }
@@ -98,8 +221,18 @@ fn save_access_key(
     key_pair_properties_buf: &str,
     public_key_str: &str,
     network_config: crate::config::NetworkConfig,
-    credentials_home_dir: std::path::PathBuf,
-) -> crate::CliResult {
+    credentials_dir: std::path::PathBuf,
+) -> color_eyre::eyre::Result<KeyStorage> {
+    if crate::common::is_no_keychain() {
+        let storage_message = crate::common::save_access_key_to_keychain(
+            network_config,
+            key_pair_properties_buf,
+            public_key_str,
+            account_id.as_ref(),
+        )?;
+        eprintln!("{}", storage_message);
+        return Ok(KeyStorage::NotSaved);
+    }
     #[derive(strum_macros::Display)]
     enum SelectStorage {
         #[strum(to_string = "Store the access key in my keychain")]
@@ -109,15 +242,19 @@ fn save_access_key(
         )]
         SaveToLegacyKeychain,
     }
-    let selection = Select::new(
+    let selection = crate::common::select(
         "Select a keychain to save the access key to:",
         vec![
             SelectStorage::SaveToKeychain,
             SelectStorage::SaveToLegacyKeychain,
         ],
-    )
-    .prompt()?;
+    )?
+    .wrap_err("Saving the access key was cancelled")?;
     if let SelectStorage::SaveToKeychain = selection {
+        let key_storage = KeyStorage::Keychain {
+            service_name: format!("near-{}-{account_id}", network_config.network_name),
+            item_name: format!("{account_id}:{public_key_str}"),
+        };
         let storage_message = crate::common::save_access_key_to_keychain(
             network_config,
             key_pair_properties_buf,
@@ -131,17 +268,113 @@ fn save_access_key(
             )
         })?;
         eprintln!("{}", storage_message);
-        return Ok(());
+        return Ok(key_storage);
     }
 
+    let (key_file, _) = crate::common::legacy_keychain_file_paths(
+        &credentials_dir,
+        &network_config.network_name,
+        account_id.as_ref(),
+        public_key_str,
+    );
     let storage_message = crate::common::save_access_key_to_legacy_keychain(
         network_config,
-        credentials_home_dir,
+        credentials_dir,
         key_pair_properties_buf,
         public_key_str,
         account_id.as_ref(),
     )
     .wrap_err_with(|| format!("Failed to save a file with access key: {}", public_key_str))?;
     eprintln!("{}", storage_message);
-    Ok(())
+    Ok(KeyStorage::LegacyKeychain { key_file })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn login_summary_in_the_js_cli_format() {
+        assert_eq!(
+            login_summary(
+                &"alice.testnet".parse().unwrap(),
+                &"ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp"
+                    .parse()
+                    .unwrap()
+            ),
+            "Logged in as alice.testnet with public key ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp successfully"
+        );
+    }
+
+    #[test]
+    fn login_details_of_the_granted_key() {
+        let account_id: near_primitives::types::AccountId = "alice.testnet".parse().unwrap();
+        let public_key: near_crypto::PublicKey =
+            "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp"
+                .parse()
+                .unwrap();
+        let full_access = near_primitives::views::AccessKeyView {
+            nonce: 0,
+            permission: near_primitives::views::AccessKeyPermissionView::FullAccess,
+        };
+        let function_call = near_primitives::views::AccessKeyView {
+            nonce: 0,
+            permission: near_primitives::views::AccessKeyPermissionView::FunctionCall {
+                allowance: None,
+                receiver_id: "app.testnet".to_string(),
+                method_names: vec!["vote".to_string()],
+            },
+        };
+        let keychain = KeyStorage::Keychain {
+            service_name: "near-testnet-alice.testnet".to_string(),
+            item_name: format!("alice.testnet:{public_key}"),
+        };
+
+        let details = login_details(
+            &account_id,
+            &public_key,
+            Some(&full_access),
+            &keychain,
+            "testnet",
+        );
+        assert!(details.contains("Permission:  full access\n"), "{details}");
+        assert!(
+            details.contains("the keychain (service \"near-testnet-alice.testnet\""),
+            "{details}"
+        );
+        assert!(details.ends_with("near tokens alice.testnet send-near alice.testnet '0.001 NEAR' network-config testnet sign-with-keychain display"));
+        assert_eq!(function_call_key_warning(&account_id, &full_access), None);
+
+        let details = login_details(
+            &account_id,
+            &public_key,
+            Some(&function_call),
+            &KeyStorage::LegacyKeychain {
+                key_file: "key.json".into(),
+            },
+            "testnet",
+        );
+        assert!(details.contains(
+            "Permission:  function call to <app.testnet> only (methods: vote), allowance: unlimited"
+        ));
+        assert!(details.contains("Saved to:    the legacy keychain (key.json)"));
+        assert!(details.ends_with("near contract call-function as-transaction app.testnet vote json-args {} prepaid-gas '30 Tgas' attached-deposit '0 NEAR' sign-as alice.testnet network-config testnet sign-with-legacy-keychain display"));
+        assert!(function_call_key_warning(&account_id, &function_call)
+            .unwrap()
+            .contains("NOT a full access key"));
+
+        assert!(
+            login_details(&account_id, &public_key, None, &keychain, "testnet")
+                .contains("Permission:  unknown (the access key was not found on <testnet>)")
+        );
+        let details = login_details(
+            &account_id,
+            &public_key,
+            None,
+            &KeyStorage::NotSaved,
+            "testnet",
+        );
+        assert!(details.contains("Saved to:    nowhere (--no-keychain)"));
+        assert!(details.ends_with("network-config testnet sign-with-plaintext-private-key display"));
+    }
 }
@@ -35,7 +35,7 @@ impl LoginFromSeedPhraseContext {
                 move |network_config| {
                     super::login(
                         network_config.clone(),
-                        config.credentials_home_dir.clone(),
+                        &config,
                         &key_pair_properties_buf,
                         &key_pair_properties.public_key_str,
                         &format!("\nIt is currently not possible to verify the account access key on network <{}>.\nYou may have entered an incorrect account_id.\nYou have the option to reconfirm your account or save your access key information.\n",
@@ -22,7 +22,12 @@ impl LoginFromWebWalletContext {
                 move |network_config| {
                     let key_pair_properties: crate::common::KeyPairProperties =
                         crate::common::generate_keypair()?;
-                    let mut url: url::Url = network_config.wallet_url.join("login/")?;
+                    let mut url: url::Url = crate::config::config_url::ConfigUrl::new(
+                        network_config,
+                        "wallet_url",
+                        &network_config.wallet_url,
+                    )
+                    .join("login/")?;
                     url.query_pairs_mut()
                         .append_pair("title", "NEAR CLI")
                         .append_pair("public_key", &key_pair_properties.public_key_str);
@@ -39,7 +44,7 @@ impl LoginFromWebWalletContext {
                     let error_message = format!("\nIt is currently not possible to verify the account access key.\nYou may not be logged in to {} or you may have entered an incorrect account_id.\nYou have the option to reconfirm your account or save your access key information.\n", &url.as_str());
                     super::login(
                         network_config.clone(),
-                        config.credentials_home_dir.clone(),
+                        &config,
                         &key_pair_properties_buf,
                         &key_pair_properties.public_key_str,
                         &error_message,
@@ -0,0 +1,138 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = ListKeychainKeysContext)]
+pub struct ListKeychainKeys;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct KeychainKey {
+    network_name: String,
+    account_id: String,
+    public_key: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ListKeychainKeysContext;
+
+impl ListKeychainKeysContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        _scope: &<ListKeychainKeys as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let mut table = prettytable::Table::new();
+        table.set_titles(prettytable::row![Fg=>"Network", "Account", "Public Key", "Directory"]);
+        let mut keys_count = 0;
+        for (credentials_dir, network_configs) in previous_context.config.credentials_dirs() {
+            let network_names: std::collections::BTreeSet<&str> = network_configs
+                .iter()
+                .map(|network_config| network_config.network_name.as_str())
+                .collect();
+            for network_name in network_names {
+                for key in read_keychain_keys(&credentials_dir, network_name) {
+                    table.add_row(prettytable::row![
+                        key.network_name,
+                        key.account_id,
+                        key.public_key,
+                        credentials_dir.display()
+                    ]);
+                    keys_count += 1;
+                }
+            }
+        }
+
+        if keys_count == 0 {
+            eprintln!("\nThere are no access keys in the legacy keychain directories.");
+        } else {
+            eprintln!("\nAccess keys in the legacy keychain directories:");
+            table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+            table.printstd();
+        }
+        Ok(Self)
+    }
+}
+
+/// The keys are stored in `<network_name>/<account_id>/<public_key>.json` and the last saved key
+/// of an account is also in `<network_name>/<account_id>.json`.
+fn read_keychain_keys(
+    credentials_dir: &std::path::Path,
+    network_name: &str,
+) -> std::collections::BTreeSet<KeychainKey> {
+    let read_dir =
+        |dir: &std::path::Path| dir.read_dir().map(Iterator::flatten).into_iter().flatten();
+    let mut keys = std::collections::BTreeSet::new();
+    for entry in read_dir(&credentials_dir.join(network_name)) {
+        let path = entry.path();
+        let account_id = entry.file_name().to_string_lossy().to_string();
+        if path.is_dir() {
+            for key_entry in read_dir(&path) {
+                let key_path = key_entry.path();
+                if key_path
+                    .extension()
+                    .is_some_and(|extension| extension == "json")
+                {
+                    if let Some(file_stem) = key_path.file_stem() {
+                        keys.insert(KeychainKey {
+                            network_name: network_name.to_string(),
+                            account_id: account_id.clone(),
+                            public_key: file_stem.to_string_lossy().replacen('_', ":", 1),
+                        });
+                    }
+                }
+            }
+        } else if let Some(account_id) = account_id.strip_suffix(".json") {
+            let public_key = std::fs::read_to_string(&path)
+                .ok()
+                .and_then(|data| serde_json::from_str::<serde_json::Value>(&data).ok())
+                .and_then(|data| data.get("public_key")?.as_str().map(str::to_string));
+            if let Some(public_key) = public_key {
+                keys.insert(KeychainKey {
+                    network_name: network_name.to_string(),
+                    account_id: account_id.to_string(),
+                    public_key,
+                });
+            }
+        }
+    }
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keychain_keys_of_account_dirs_and_files() {
+        let credentials_dir = std::env::temp_dir().join("near-cli-test-list-keychain-keys");
+        let _ = std::fs::remove_dir_all(&credentials_dir);
+        let account_dir = credentials_dir.join("testnet").join("alice.testnet");
+        std::fs::create_dir_all(&account_dir).unwrap();
+        let public_key = "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp";
+        let key_file_content = serde_json::json!({ "public_key": public_key }).to_string();
+        std::fs::write(
+            account_dir.join(format!("{}.json", public_key.replace(':', "_"))),
+            &key_file_content,
+        )
+        .unwrap();
+        std::fs::write(
+            credentials_dir.join("testnet").join("alice.testnet.json"),
+            &key_file_content,
+        )
+        .unwrap();
+        std::fs::write(
+            credentials_dir.join("testnet").join("bob.testnet.json"),
+            "not a key file",
+        )
+        .unwrap();
+
+        let keys = read_keychain_keys(&credentials_dir, "testnet");
+        assert_eq!(
+            keys.into_iter().collect::<Vec<_>>(),
+            vec![KeychainKey {
+                network_name: "testnet".to_string(),
+                account_id: "alice.testnet".to_string(),
+                public_key: public_key.to_string(),
+            }]
+        );
+        assert!(read_keychain_keys(&credentials_dir, "mainnet").is_empty());
+        std::fs::remove_dir_all(&credentials_dir).unwrap();
+    }
+}
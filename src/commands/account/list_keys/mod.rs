@@ -1,8 +1,17 @@
-use color_eyre::eyre::Context;
+use color_eyre::eyre::{Context, ContextCompat};
+use tracing_indicatif::span_ext::IndicatifSpanExt;
 
 use crate::common::JsonRpcClientExt;
 use crate::common::RpcQueryResponseExt;
 
+mod signing_capability;
+mod watch;
+
+/// The number of parallel requests to the indexer
+const MAX_CONCURRENT_ORIGIN_REQUESTS: usize = 8;
+/// How often the keys are polled with `--watch`
+const DEFAULT_WATCH_INTERVAL_SECS: u64 = 10;
+
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = crate::GlobalContext)]
 #[interactive_clap(output_context = ViewListKeysContext)]
@@ -10,6 +19,28 @@ pub struct ViewListKeys {
     #[interactive_clap(skip_default_input_arg)]
     /// What Account ID do you need to view?
     account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    /// Show the transaction that added each key (requires the indexer of the network connection)
+    with_origin: bool,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// How many keys to show (default: all of them)
+    limit: Option<u64>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// How many keys to skip (default: 0)
+    offset: Option<u64>,
+    #[interactive_clap(long)]
+    /// Keep polling the keys and show the keys that are added or removed until Ctrl-C is pressed
+    watch: bool,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// How many seconds between the polls of --watch (default: 10)
+    watch_interval_secs: Option<u64>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Show only the keys that can sign this action: transfer or function-call:<contract>:<method>
+    for_action: Option<self::signing_capability::IntendedAction>,
     #[interactive_clap(named_arg)]
     /// Select network
     network_config: crate::network_view_at_block::NetworkViewAtBlockArgs,
@@ -25,9 +56,37 @@ impl ViewListKeysContext {
     ) -> color_eyre::eyre::Result<Self> {
         let on_after_getting_block_reference_callback: crate::network_view_at_block::OnAfterGettingBlockReferenceCallback = std::sync::Arc::new({
             let account_id: near_primitives::types::AccountId = scope.account_id.clone().into();
+            let with_origin = scope.with_origin;
+            let limit = scope.limit.map(usize::try_from).transpose()?;
+            let offset = usize::try_from(scope.offset.unwrap_or_default())?;
+            let for_action = scope.for_action.clone();
+            let watch_interval = scope.watch.then(|| {
+                std::time::Duration::from_secs(
+                    scope
+                        .watch_interval_secs
+                        .unwrap_or(DEFAULT_WATCH_INTERVAL_SECS)
+                        .max(1),
+                )
+            });
 
             move |network_config, block_reference| {
-                let access_key_list = network_config
+                let indexer_url = if with_origin {
+                    let indexer_url = network_config.indexer_url.as_ref().wrap_err_with(|| {
+                        format!(
+                            "--with-origin requires an indexer, but the network connection <{}> has no `indexer_url` (set it with `config edit-connection`)",
+                            network_config.network_name
+                        )
+                    })?;
+                    Some(crate::config::config_url::ConfigUrl::new(
+                        network_config,
+                        "indexer_url",
+                        indexer_url,
+                    ))
+                } else {
+                    None
+                };
+
+                let rpc_query_response = network_config
                     .json_rpc_client()
                     .blocking_call_view_access_key_list(
                         &account_id,
@@ -38,10 +97,75 @@ impl ViewListKeysContext {
                             "Failed to fetch query AccessKeyList for {}",
                             &account_id
                         )
-                    })?
-                    .access_key_list_view()?;
+                    })?;
+                let access_key_list = rpc_query_response.access_key_list_view()?;
 
-                crate::common::display_access_key_list(&access_key_list.keys);
+                let call_cost = self::signing_capability::estimate_call_cost(
+                    network_config,
+                    &account_id,
+                    for_action.as_ref(),
+                )?;
+                let indexed_keys: Vec<_> = access_key_list
+                    .keys
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, access_key)| {
+                        for_action.as_ref().map_or(true, |for_action| {
+                            for_action.can_be_signed_with(&access_key.access_key.permission, call_cost)
+                        })
+                    })
+                    .collect();
+                if let Some(for_action) = &for_action {
+                    eprintln!(
+                        "{} of the {} access keys of <{account_id}> can sign {for_action}.",
+                        indexed_keys.len(),
+                        access_key_list.keys.len()
+                    );
+                }
+
+                let page = page(indexed_keys.len(), offset, limit);
+                let page_keys = &indexed_keys[page.clone()];
+
+                let can_sign: Vec<String> = page_keys
+                    .iter()
+                    .map(|(_, access_key)| {
+                        self::signing_capability::can_sign_message(
+                            &access_key.access_key.permission,
+                            call_cost,
+                        )
+                    })
+                    .collect();
+                // Only the keys of the page are looked up in the indexer
+                let origins = indexer_url
+                    .map(|indexer_url| {
+                        get_access_key_origins(
+                            &indexer_url,
+                            &account_id,
+                            &page_keys.iter().map(|(_, access_key)| *access_key).collect::<Vec<_>>(),
+                        )
+                    })
+                    .transpose()?;
+                let mut extra_columns = vec![("Can sign", can_sign.as_slice())];
+                if let Some(origins) = &origins {
+                    extra_columns.push(("Origin", origins.as_slice()));
+                }
+                crate::common::display_access_keys(page_keys.iter().copied(), &extra_columns);
+                if page.len() < indexed_keys.len() {
+                    eprintln!("{}", page_message(&page, indexed_keys.len()));
+                }
+                crate::network_view_at_block::display_viewed_at_block(
+                    rpc_query_response.block_height,
+                    &rpc_query_response.block_hash,
+                );
+                if let Some(watch_interval) = watch_interval {
+                    // The next polls are at the final block, whatever block the list was viewed at
+                    self::watch::watch_access_keys(
+                        network_config,
+                        &account_id,
+                        access_key_list.keys,
+                        watch_interval,
+                    )?;
+                }
                 Ok(())
             }
         });
@@ -70,3 +194,182 @@ impl ViewListKeys {
         )
     }
 }
+
+/// The indexes of the keys on the page, within the `len` keys of the account.
+fn page(len: usize, offset: usize, limit: Option<usize>) -> std::ops::Range<usize> {
+    let start = offset.min(len);
+    let end = limit.map_or(len, |limit| start.saturating_add(limit).min(len));
+    start..end
+}
+
+fn page_message(page: &std::ops::Range<usize>, len: usize) -> String {
+    if page.is_empty() {
+        return format!(
+            "There are only {len} access keys, so there are none to show at this offset."
+        );
+    }
+    let mut message = format!(
+        "Shown access keys #{} - #{} of {len}.",
+        page.start + 1,
+        page.end
+    );
+    if page.end < len {
+        message.push_str(&format!(" Use --offset {} to see the next ones.", page.end));
+    }
+    message
+}
+
+/// The key as returned by the Nearblocks API (`/v1/keys/{public_key}`)
+#[derive(Debug, Clone, serde::Deserialize)]
+struct IndexerKeys {
+    keys: Vec<IndexerKey>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+struct IndexerKey {
+    account_id: String,
+    created: Option<IndexerKeyEvent>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+struct IndexerKeyEvent {
+    transaction_hash: Option<String>,
+    /// Nanoseconds, as a number or a string
+    block_timestamp: Option<serde_json::Value>,
+}
+
+#[tracing::instrument(
+    name = "Looking up the transactions that added the access keys ...",
+    skip_all
+)]
+fn get_access_key_origins(
+    indexer_url: &crate::config::config_url::ConfigUrl,
+    account_id: &near_primitives::types::AccountId,
+    access_keys: &[&near_primitives::views::AccessKeyInfoView],
+) -> color_eyre::eyre::Result<Vec<String>> {
+    let client = reqwest::Client::new();
+    let span = tracing::Span::current();
+    let completed = std::sync::atomic::AtomicUsize::new(0);
+    crate::common::run_concurrently(access_keys, MAX_CONCURRENT_ORIGIN_REQUESTS, |access_key| {
+        let client = &client;
+        let span = &span;
+        let completed = &completed;
+        async move {
+            let origin =
+                get_access_key_origin(client, indexer_url, account_id, &access_key.public_key)
+                    .await;
+            let completed = completed.fetch_add(1, std::sync::atomic::Ordering::Relaxed) + 1;
+            span.pb_set_message(&format!("{completed} of {} ...", access_keys.len()));
+            match origin {
+                Ok(Some(origin)) => origin,
+                Ok(None) => "unknown".to_string(),
+                Err(err) => format!("lookup failed: {err:#}"),
+            }
+        }
+    })
+}
+
+async fn get_access_key_origin(
+    client: &reqwest::Client,
+    indexer_url: &crate::config::config_url::ConfigUrl,
+    account_id: &near_primitives::types::AccountId,
+    public_key: &near_crypto::PublicKey,
+) -> color_eyre::eyre::Result<Option<String>> {
+    let indexer_keys: IndexerKeys = client
+        .get(indexer_url.join(&format!("v1/keys/{public_key}"))?)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await
+        .wrap_err("Unexpected response of the indexer")?;
+    Ok(describe_origin(&indexer_keys, account_id))
+}
+
+/// For example: "added 2023-04-02 by tx 9abQ1c…"
+fn describe_origin(
+    indexer_keys: &IndexerKeys,
+    account_id: &near_primitives::types::AccountId,
+) -> Option<String> {
+    let created = indexer_keys
+        .keys
+        .iter()
+        .find(|key| key.account_id == account_id.as_str())?
+        .created
+        .as_ref()?;
+    let transaction_hash = created.transaction_hash.as_ref()?;
+    let date = created
+        .block_timestamp
+        .as_ref()
+        .and_then(|timestamp| match timestamp {
+            serde_json::Value::Number(number) => number.as_u64(),
+            serde_json::Value::String(string) => string.parse().ok(),
+            _ => None,
+        })
+        .and_then(|timestamp_nanosec| {
+            chrono::DateTime::from_timestamp((timestamp_nanosec / 1_000_000_000) as i64, 0)
+        })
+        .map(|date_time| date_time.format("%Y-%m-%d").to_string());
+    let transaction = format!(
+        "tx {}…",
+        transaction_hash.chars().take(6).collect::<String>()
+    );
+    Some(match date {
+        Some(date) => format!("added {date} by {transaction}"),
+        None => format!("added by {transaction}"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pages_of_the_key_list() {
+        assert_eq!(page(120, 0, None), 0..120);
+        assert_eq!(page(120, 0, Some(50)), 0..50);
+        assert_eq!(page(120, 100, Some(50)), 100..120);
+        assert_eq!(page(120, 150, Some(50)), 120..120);
+        assert_eq!(page(120, 10, Some(usize::MAX)), 10..120);
+        assert_eq!(
+            page_message(&(0..50), 120),
+            "Shown access keys #1 - #50 of 120. Use --offset 50 to see the next ones."
+        );
+        assert_eq!(
+            page_message(&(100..120), 120),
+            "Shown access keys #101 - #120 of 120."
+        );
+        assert_eq!(
+            page_message(&(120..120), 120),
+            "There are only 120 access keys, so there are none to show at this offset."
+        );
+    }
+
+    #[test]
+    fn access_key_origin_from_indexer_response() {
+        let indexer_keys: IndexerKeys = serde_json::from_value(serde_json::json!({
+            "keys": [
+                {
+                    "public_key": "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp",
+                    "account_id": "other.near",
+                    "created": { "transaction_hash": "1111111", "block_timestamp": 1580000000000000000_u64 }
+                },
+                {
+                    "public_key": "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp",
+                    "account_id": "alice.near",
+                    "created": { "transaction_hash": "9abQ1cXf", "block_timestamp": "1680393600000000000" },
+                    "deleted": { "transaction_hash": null, "block_timestamp": null }
+                }
+            ]
+        }))
+        .unwrap();
+        assert_eq!(
+            describe_origin(&indexer_keys, &"alice.near".parse().unwrap()).as_deref(),
+            Some("added 2023-04-02 by tx 9abQ1c…")
+        );
+        assert_eq!(
+            describe_origin(&indexer_keys, &"bob.near".parse().unwrap()),
+            None
+        );
+    }
+}
@@ -0,0 +1,251 @@
+//! What each access key can sign in practice (`list-keys` "Can sign" column and `--for-action`).
+
+use color_eyre::eyre::WrapErr;
+use near_primitives::views::AccessKeyPermissionView;
+
+use crate::common::JsonRpcClientExt;
+
+/// The gas that the allowance estimate assumes for each function call
+const ESTIMATED_CALL_TGAS: u64 = 100;
+
+/// The action that `--for-action` looks for a key to sign: `transfer` or
+/// `function-call:<contract>:<method>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IntendedAction {
+    Transfer,
+    FunctionCall {
+        receiver_id: near_primitives::types::AccountId,
+        method_name: String,
+    },
+}
+
+impl std::fmt::Display for IntendedAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Transfer => write!(f, "transfer"),
+            Self::FunctionCall {
+                receiver_id,
+                method_name,
+            } => write!(f, "function-call:{receiver_id}:{method_name}"),
+        }
+    }
+}
+
+impl std::str::FromStr for IntendedAction {
+    type Err = color_eyre::eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "transfer" {
+            return Ok(Self::Transfer);
+        }
+        match s.split(':').collect::<Vec<_>>()[..] {
+            ["function-call", receiver_id, method_name] if !method_name.is_empty() => {
+                Ok(Self::FunctionCall {
+                    receiver_id: receiver_id.parse().wrap_err_with(|| {
+                        format!("Invalid contract account ID in --for-action '{s}'")
+                    })?,
+                    method_name: method_name.to_string(),
+                })
+            }
+            _ => color_eyre::eyre::bail!(
+                "Invalid action '{s}': expected transfer or function-call:<contract>:<method>"
+            ),
+        }
+    }
+}
+
+impl interactive_clap::ToCli for IntendedAction {
+    type CliVariant = IntendedAction;
+}
+
+impl IntendedAction {
+    /// Whether a key with the permission can sign the action (with no deposit), and pay for a
+    /// call with `call_cost` from its allowance.
+    pub fn can_be_signed_with(
+        &self,
+        permission: &AccessKeyPermissionView,
+        call_cost: near_primitives::types::Balance,
+    ) -> bool {
+        match (self, permission) {
+            (_, AccessKeyPermissionView::FullAccess) => true,
+            (Self::Transfer, AccessKeyPermissionView::FunctionCall { .. }) => false,
+            (
+                Self::FunctionCall {
+                    receiver_id,
+                    method_name,
+                },
+                AccessKeyPermissionView::FunctionCall {
+                    allowance,
+                    receiver_id: key_receiver_id,
+                    method_names,
+                },
+            ) => {
+                key_receiver_id == receiver_id.as_str()
+                    && (method_names.is_empty() || method_names.contains(method_name))
+                    && allowance.map_or(true, |allowance| allowance >= call_cost)
+            }
+        }
+    }
+}
+
+/// For example: "full access" or "calls to app.near only (allowance left ≈ 12 calls at 100 Tgas
+/// and current gas price)".
+pub fn can_sign_message(
+    permission: &AccessKeyPermissionView,
+    call_cost: near_primitives::types::Balance,
+) -> String {
+    match permission {
+        AccessKeyPermissionView::FullAccess => "full access".to_string(),
+        AccessKeyPermissionView::FunctionCall {
+            allowance,
+            receiver_id,
+            ..
+        } => match allowance {
+            None => format!("calls to {receiver_id} only (unlimited allowance)"),
+            Some(allowance) => format!(
+                "calls to {receiver_id} only (allowance left ≈ {} calls at {ESTIMATED_CALL_TGAS} Tgas and current gas price)",
+                allowance.checked_div(call_cost).unwrap_or_default()
+            ),
+        },
+    }
+}
+
+/// What a function call with `ESTIMATED_CALL_TGAS` costs the allowance: its fees and all its
+/// attached gas at the current gas price.
+#[tracing::instrument(name = "Estimating the cost of a function call ...", skip_all)]
+pub fn estimate_call_cost(
+    network_config: &crate::config::NetworkConfig,
+    account_id: &near_primitives::types::AccountId,
+    intended_action: Option<&IntendedAction>,
+) -> color_eyre::eyre::Result<near_primitives::types::Balance> {
+    let json_rpc_client = network_config.json_rpc_client();
+    let runtime_config = json_rpc_client
+        .blocking_call(
+            near_jsonrpc_client::methods::EXPERIMENTAL_protocol_config::RpcProtocolConfigRequest {
+                block_reference: near_primitives::types::Finality::Final.into(),
+            },
+        )
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch the protocol config of network <{}>",
+                network_config.network_name
+            )
+        })?
+        .runtime_config;
+    let gas_price = json_rpc_client
+        .blocking_call(
+            near_jsonrpc_client::methods::gas_price::RpcGasPriceRequest { block_id: None },
+        )
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch the gas price of network <{}>",
+                network_config.network_name
+            )
+        })?
+        .gas_price;
+    let (receiver_id, method_name) = match intended_action {
+        Some(IntendedAction::FunctionCall {
+            receiver_id,
+            method_name,
+        }) => (receiver_id.clone(), method_name.clone()),
+        _ => ("contract.near".parse()?, String::new()),
+    };
+    let gas = crate::network_for_transaction::fee_estimate::transaction_gas(
+        &runtime_config.transaction_costs,
+        account_id,
+        &receiver_id,
+        &[near_primitives::transaction::Action::FunctionCall(
+            Box::new(near_primitives::transaction::FunctionCallAction {
+                method_name,
+                args: vec![],
+                gas: crate::common::NearGas::from_tgas(ESTIMATED_CALL_TGAS).as_gas(),
+                deposit: 0,
+            }),
+        )],
+    );
+    Ok(u128::from(gas.burnt.saturating_add(gas.attached)).saturating_mul(gas_price))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CALL_COST: u128 = 10u128.pow(22);
+
+    fn function_call_permission(
+        allowance: Option<u128>,
+        methods: &[&str],
+    ) -> AccessKeyPermissionView {
+        AccessKeyPermissionView::FunctionCall {
+            allowance,
+            receiver_id: "app.near".to_string(),
+            method_names: methods.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    #[test]
+    fn intended_action_from_str() {
+        assert_eq!(
+            "transfer".parse::<IntendedAction>().unwrap(),
+            IntendedAction::Transfer
+        );
+        let action: IntendedAction = "function-call:app.near:vote".parse().unwrap();
+        assert_eq!(
+            action,
+            IntendedAction::FunctionCall {
+                receiver_id: "app.near".parse().unwrap(),
+                method_name: "vote".to_string()
+            }
+        );
+        assert_eq!(action.to_string(), "function-call:app.near:vote");
+        for invalid in [
+            "stake",
+            "function-call:app.near",
+            "function-call:app.near:",
+            "function-call:A B:vote",
+        ] {
+            assert!(invalid.parse::<IntendedAction>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn keys_that_can_sign_the_action() {
+        let vote: IntendedAction = "function-call:app.near:vote".parse().unwrap();
+        let full_access = AccessKeyPermissionView::FullAccess;
+        assert!(IntendedAction::Transfer.can_be_signed_with(&full_access, CALL_COST));
+        assert!(vote.can_be_signed_with(&full_access, CALL_COST));
+        assert!(!IntendedAction::Transfer
+            .can_be_signed_with(&function_call_permission(None, &[]), CALL_COST));
+        assert!(vote.can_be_signed_with(&function_call_permission(None, &[]), CALL_COST));
+        assert!(vote.can_be_signed_with(
+            &function_call_permission(Some(CALL_COST), &["vote", "unvote"]),
+            CALL_COST
+        ));
+        assert!(!vote.can_be_signed_with(&function_call_permission(None, &["unvote"]), CALL_COST));
+        assert!(!vote.can_be_signed_with(
+            &function_call_permission(Some(CALL_COST - 1), &[]),
+            CALL_COST
+        ));
+        let other_contract: IntendedAction = "function-call:other.near:vote".parse().unwrap();
+        assert!(!other_contract.can_be_signed_with(&function_call_permission(None, &[]), CALL_COST));
+    }
+
+    #[test]
+    fn can_sign_messages() {
+        assert_eq!(
+            can_sign_message(&AccessKeyPermissionView::FullAccess, CALL_COST),
+            "full access"
+        );
+        assert_eq!(
+            can_sign_message(
+                &function_call_permission(Some(25 * CALL_COST / 2), &[]),
+                CALL_COST
+            ),
+            "calls to app.near only (allowance left ≈ 12 calls at 100 Tgas and current gas price)"
+        );
+        assert_eq!(
+            can_sign_message(&function_call_permission(None, &["vote"]), CALL_COST),
+            "calls to app.near only (unlimited allowance)"
+        );
+    }
+}
@@ -0,0 +1,195 @@
+use color_eyre::eyre::WrapErr;
+use color_eyre::owo_colors::OwoColorize;
+
+use crate::common::RpcQueryResponseExt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum KeyChange {
+    Added(near_primitives::views::AccessKeyInfoView),
+    Removed(near_primitives::views::AccessKeyInfoView),
+}
+
+impl std::fmt::Display for KeyChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Added(access_key) => write!(
+                f,
+                "{}",
+                format!(
+                    "+ added   {} ({})",
+                    access_key.public_key,
+                    permission_description(&access_key.access_key.permission)
+                )
+                .green()
+            ),
+            Self::Removed(access_key) => write!(
+                f,
+                "{}",
+                format!(
+                    "- removed {} ({})",
+                    access_key.public_key,
+                    permission_description(&access_key.access_key.permission)
+                )
+                .red()
+            ),
+        }
+    }
+}
+
+fn permission_description(permission: &near_primitives::views::AccessKeyPermissionView) -> String {
+    match permission {
+        near_primitives::views::AccessKeyPermissionView::FullAccess => "full access".to_string(),
+        near_primitives::views::AccessKeyPermissionView::FunctionCall { receiver_id, .. } => {
+            format!("function call on {receiver_id}")
+        }
+    }
+}
+
+/// The keys added and removed between two lists of keys (compared by public key, so the changes of
+/// the nonce or the remaining allowance of a key are not reported).
+fn diff_access_keys(
+    old_keys: &[near_primitives::views::AccessKeyInfoView],
+    new_keys: &[near_primitives::views::AccessKeyInfoView],
+) -> Vec<KeyChange> {
+    let by_public_key = |keys: &[near_primitives::views::AccessKeyInfoView]| {
+        keys.iter()
+            .map(|access_key| (access_key.public_key.to_string(), access_key.clone()))
+            .collect::<std::collections::BTreeMap<_, _>>()
+    };
+    let old_keys = by_public_key(old_keys);
+    let new_keys = by_public_key(new_keys);
+    let removed = old_keys
+        .iter()
+        .filter(|(public_key, _)| !new_keys.contains_key(*public_key))
+        .map(|(_, access_key)| KeyChange::Removed(access_key.clone()));
+    let added = new_keys
+        .iter()
+        .filter(|(public_key, _)| !old_keys.contains_key(*public_key))
+        .map(|(_, access_key)| KeyChange::Added(access_key.clone()));
+    removed.chain(added).collect()
+}
+
+/// Polls the access keys of the account until Ctrl-C is pressed, then prints a summary.
+pub fn watch_access_keys(
+    network_config: &crate::config::NetworkConfig,
+    account_id: &near_primitives::types::AccountId,
+    keys: Vec<near_primitives::views::AccessKeyInfoView>,
+    interval: std::time::Duration,
+) -> crate::CliResult {
+    let runtime = crate::common::multi_thread_runtime()?;
+    let observed_changes = runtime.block_on(poll_access_keys(
+        &network_config.json_rpc_client(),
+        account_id,
+        keys,
+        interval,
+    ));
+
+    eprintln!(
+        "\nObserved {} change(s) of the access keys of <{account_id}>.",
+        observed_changes.len()
+    );
+    for (time, change) in &observed_changes {
+        eprintln!("  [{}] {change}", time.format("%Y-%m-%d %H:%M:%S"));
+    }
+    Ok(())
+}
+
+async fn poll_access_keys(
+    json_rpc_client: &near_jsonrpc_client::JsonRpcClient,
+    account_id: &near_primitives::types::AccountId,
+    mut keys: Vec<near_primitives::views::AccessKeyInfoView>,
+    interval: std::time::Duration,
+) -> Vec<(chrono::DateTime<chrono::Local>, KeyChange)> {
+    eprintln!(
+        "\nWatching the access keys of <{account_id}> every {} seconds (press Ctrl-C to stop) ...",
+        interval.as_secs()
+    );
+    let mut observed_changes = Vec::new();
+    let mut ctrl_c = Box::pin(tokio::signal::ctrl_c());
+    loop {
+        let next_poll = Box::pin(tokio::time::sleep(interval));
+        if let futures::future::Either::Left(_) =
+            futures::future::select(&mut ctrl_c, next_poll).await
+        {
+            return observed_changes;
+        }
+
+        let new_keys = match get_access_keys(json_rpc_client, account_id).await {
+            Ok(new_keys) => new_keys,
+            Err(err) => {
+                eprintln!(
+                    "Warning: {err:#}. Retrying in {} seconds ...",
+                    interval.as_secs()
+                );
+                continue;
+            }
+        };
+        let now = chrono::Local::now();
+        for change in diff_access_keys(&keys, &new_keys) {
+            eprintln!("[{}] {change}", now.format("%Y-%m-%d %H:%M:%S"));
+            observed_changes.push((now, change));
+        }
+        keys = new_keys;
+    }
+}
+
+async fn get_access_keys(
+    json_rpc_client: &near_jsonrpc_client::JsonRpcClient,
+    account_id: &near_primitives::types::AccountId,
+) -> color_eyre::eyre::Result<Vec<near_primitives::views::AccessKeyInfoView>> {
+    Ok(crate::rpc_rate_limit::call(
+        json_rpc_client,
+        near_jsonrpc_client::methods::query::RpcQueryRequest {
+            block_reference: near_primitives::types::Finality::Final.into(),
+            request: near_primitives::views::QueryRequest::ViewAccessKeyList {
+                account_id: account_id.clone(),
+            },
+        },
+    )
+    .await
+    .wrap_err_with(|| format!("Failed to fetch query AccessKeyList for {account_id}"))?
+    .access_key_list_view()?
+    .keys)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn access_key(public_key: &str, nonce: u64) -> near_primitives::views::AccessKeyInfoView {
+        near_primitives::views::AccessKeyInfoView {
+            public_key: public_key.parse().unwrap(),
+            access_key: near_primitives::views::AccessKeyView {
+                nonce,
+                permission: near_primitives::views::AccessKeyPermissionView::FullAccess,
+            },
+        }
+    }
+
+    #[test]
+    fn added_and_removed_keys() {
+        let first = access_key("ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp", 1);
+        let second = access_key("ed25519:GMb8aJ5WykzqKKhTp3WYNsHtnJFZkPXcNAjJBamuJoqz", 1);
+        let third = access_key("ed25519:7QWuJ3Q2Xq5tDk5u1umxnKKbjCfkmS2dMAG4sAkZZGgk", 1);
+
+        assert_eq!(diff_access_keys(&[first.clone()], &[first.clone()]), []);
+        assert_eq!(
+            diff_access_keys(
+                &[first.clone()],
+                &[access_key(
+                    "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp",
+                    5
+                )]
+            ),
+            [],
+            "a used key is not a change"
+        );
+        assert_eq!(
+            diff_access_keys(
+                &[first.clone(), second.clone()],
+                &[third.clone(), first.clone()]
+            ),
+            [KeyChange::Removed(second), KeyChange::Added(third)]
+        );
+    }
+}
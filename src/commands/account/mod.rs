@@ -1,15 +1,35 @@
 use strum::{EnumDiscriminants, EnumIter, EnumMessage};
 
+mod access_key_diff;
 mod add_key;
+mod audit_keys;
+mod check_availability;
+mod check_nonce;
+mod compute_lockup_id;
 pub mod create_account;
+mod create_named;
 mod delete_account;
 mod delete_key;
-mod export_account;
+mod derive_implicit_address;
+pub mod export_account;
 mod import_account;
+mod list_keychain_keys;
 mod list_keys;
+mod monitor;
+mod print_key_file_path;
+mod refill_allowance;
+mod replace_all_keys;
+mod request_add_key_link;
 pub mod storage_management;
+mod transfer_all;
 pub mod update_social_profile;
+mod view_access_key_by_index;
+mod view_account_age;
 mod view_account_summary;
+mod view_all_balances;
+mod view_balance_history;
+mod view_deleted;
+mod view_storage_keys;
 
 pub const MIN_ALLOWED_TOP_LEVEL_ACCOUNT_LENGTH: usize = 32;
 
@@ -31,17 +51,67 @@ pub enum AccountActions {
     ))]
     /// View properties for an account
     ViewAccountSummary(self::view_account_summary::ViewAccountSummary),
+    #[strum_discriminants(strum(
+        message = "view-all-balances       - View NEAR and FT balances of an account"
+    ))]
+    /// View NEAR and FT balances of an account
+    ViewAllBalances(self::view_all_balances::ViewAllBalances),
+    #[strum_discriminants(strum(
+        message = "view-balance-history    - View the NEAR balance of an account over a range of blocks"
+    ))]
+    /// View the NEAR balance of an account over a range of blocks
+    ViewBalanceHistory(self::view_balance_history::ViewBalanceHistory),
+    #[strum_discriminants(strum(
+        message = "view-account-age        - View the block, the date and the age of the creation of an account"
+    ))]
+    /// View the block, the date and the age of the creation of an account
+    ViewAccountAge(self::view_account_age::ViewAccountAge),
+    #[strum_discriminants(strum(
+        message = "view-deleted            - Check whether an account that existed at a block was deleted since"
+    ))]
+    /// Check whether an account that existed at a block was deleted since
+    ViewDeleted(self::view_deleted::ViewDeleted),
+    #[strum_discriminants(strum(
+        message = "monitor                 - Print a live feed of the changes of an account"
+    ))]
+    /// Print a live feed of the changes of an account (balance, access keys, contract code, receipts)
+    Monitor(self::monitor::Monitor),
     #[strum_discriminants(strum(
         message = "import-account          - Import existing account (a.k.a. \"sign in\")"
     ))]
     /// Import existing account (a.k.a. "sign in")
     ImportAccount(self::import_account::ImportAccountCommand),
+    #[strum_discriminants(strum(
+        message = "derive-implicit-address - Derive the implicit account ID (NEAR or eth-implicit) of a public key"
+    ))]
+    /// Derive the implicit account ID (NEAR or eth-implicit) of a public key
+    DeriveImplicitAddress(self::derive_implicit_address::DeriveImplicitAddress),
+    #[strum_discriminants(strum(
+        message = "print-implicit-address  - Print only the implicit account ID of a public key (for scripts)"
+    ))]
+    /// Print only the implicit account ID of a public key (for scripts)
+    PrintImplicitAddress(self::derive_implicit_address::PrintImplicitAddress),
+    #[strum_discriminants(strum(
+        message = "compute-lockup-id       - Compute the lockup account ID of an account (without any RPC call)"
+    ))]
+    /// Compute the lockup account ID of an account (without any RPC call)
+    ComputeLockupId(self::compute_lockup_id::ComputeLockupId),
     #[strum_discriminants(strum(message = "export-account          - Export existing account"))]
     /// Export existing account
     ExportAccount(self::export_account::ExportAccount),
     #[strum_discriminants(strum(message = "create-account          - Create a new account"))]
     /// Create a new account
     CreateAccount(self::create_account::CreateAccount),
+    #[strum_discriminants(strum(
+        message = "create-named            - Create a named account (e.g. alice.near) with the registrar contract"
+    ))]
+    /// Create a named account (e.g. alice.near) with the registrar contract
+    CreateNamed(self::create_named::CreateNamedAccount),
+    #[strum_discriminants(strum(
+        message = "check-availability      - Check whether an account ID is still available"
+    ))]
+    /// Check whether an account ID is still available
+    CheckAvailability(self::check_availability::CheckAvailability),
     #[strum_discriminants(strum(
         message = "update-social-profile   - Update NEAR Social profile"
     ))]
@@ -50,21 +120,76 @@ pub enum AccountActions {
     #[strum_discriminants(strum(message = "delete-account          - Delete an account"))]
     /// Delete an account
     DeleteAccount(self::delete_account::DeleteAccount),
+    #[strum_discriminants(strum(
+        message = "transfer-all            - Transfer all the spendable balance of an account to another account"
+    ))]
+    /// Transfer all the spendable balance of an account to another account
+    TransferAll(self::transfer_all::TransferAll),
     #[strum_discriminants(strum(
         message = "list-keys               - View a list of access keys of an account"
     ))]
     /// View a list of access keys of an account
     ListKeys(self::list_keys::ViewListKeys),
+    #[strum_discriminants(strum(
+        message = "list-keychain-keys      - View the access keys saved in the legacy keychain directories"
+    ))]
+    /// View the access keys saved in the legacy keychain directories
+    ListKeychainKeys(self::list_keychain_keys::ListKeychainKeys),
+    #[strum_discriminants(strum(
+        message = "print-key-file-path     - Print the path of the key file of an account in the legacy keychain"
+    ))]
+    /// Print the path of the key file of an account in the legacy keychain
+    PrintKeyFilePath(self::print_key_file_path::PrintKeyFilePath),
+    #[strum_discriminants(strum(
+        message = "view-access-key-by-index - View an access key by its position in the list of keys"
+    ))]
+    /// View an access key by its position in the list of keys
+    ViewAccessKeyByIndex(self::view_access_key_by_index::ViewAccessKeyByIndex),
+    #[strum_discriminants(strum(
+        message = "check-nonce             - Compare the nonce of an access key with the expected nonce"
+    ))]
+    /// Compare the nonce of an access key with the expected nonce
+    CheckNonce(self::check_nonce::CheckNonce),
+    #[strum_discriminants(strum(
+        message = "audit-keys              - Audit access keys of many accounts against known keys"
+    ))]
+    /// Audit access keys of many accounts against known keys
+    AuditKeys(self::audit_keys::AuditKeys),
+    #[strum_discriminants(strum(
+        message = "access-key-diff         - View the access keys added, removed or changed between two blocks"
+    ))]
+    /// View the access keys added, removed or changed between two blocks
+    AccessKeyDiff(self::access_key_diff::AccessKeyDiff),
+    #[strum_discriminants(strum(
+        message = "view-storage-keys       - View the storage keys of a contract page by page"
+    ))]
+    /// View the storage keys of a contract page by page
+    ViewStorageKeys(self::view_storage_keys::ViewStorageKeys),
     #[strum_discriminants(strum(
         message = "add-key                 - Add an access key to an account"
     ))]
     /// Add an access key to an account
     AddKey(self::add_key::AddKeyCommand),
+    #[strum_discriminants(strum(
+        message = "request-add-key-link    - Generate a function-call access key and a wallet link to add it to an account"
+    ))]
+    /// Generate a function-call access key and a wallet link to add it to an account
+    RequestAddKeyLink(self::request_add_key_link::RequestAddKeyLink),
     #[strum_discriminants(strum(
         message = "delete-keys             - Delete access keys from an account"
     ))]
     /// Delete access keys from an account
     DeleteKeys(self::delete_key::DeleteKeysCommand),
+    #[strum_discriminants(strum(
+        message = "replace-all-keys        - Replace all the access keys of an account with one new full access key"
+    ))]
+    /// Replace all the access keys of an account with one new full access key
+    ReplaceAllKeys(self::replace_all_keys::ReplaceAllKeys),
+    #[strum_discriminants(strum(
+        message = "refill-allowance        - Change the allowance of a function-call access key"
+    ))]
+    /// Change the allowance of a function-call access key
+    RefillAllowance(self::refill_allowance::RefillAllowance),
     #[strum_discriminants(strum(
         message = "manage-storage-deposit  - Storage management: deposit, withdrawal, balance review"
     ))]
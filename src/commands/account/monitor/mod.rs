@@ -0,0 +1,657 @@
+use std::collections::HashSet;
+
+use color_eyre::eyre::WrapErr;
+
+use crate::common::RpcQueryResponseExt;
+
+/// How often the final block is polled (blocks are produced about every second).
+const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+/// The upper bound of the delay between the retries after RPC errors (e.g. rate limiting).
+const MAX_BACKOFF: std::time::Duration = std::time::Duration::from_secs(60);
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = MonitorContext)]
+pub struct Monitor {
+    #[interactive_clap(skip_default_input_arg)]
+    /// What Account ID do you want to monitor?
+    account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Output format: text (default) or ndjson (one JSON object per line)
+    output: Option<MonitorOutputFormat>,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network::Network,
+}
+
+#[derive(Clone)]
+pub struct MonitorContext(crate::network::NetworkContext);
+
+impl MonitorContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<Monitor as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let on_after_getting_network_callback: crate::network::OnAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let account_id: near_primitives::types::AccountId = scope.account_id.clone().into();
+                let output = scope.output.unwrap_or_default();
+
+                move |network_config| {
+                    tokio::runtime::Runtime::new()?.block_on(monitor_account(
+                        network_config,
+                        &account_id,
+                        output,
+                    ))
+                }
+            });
+
+        Ok(Self(crate::network::NetworkContext {
+            config: previous_context.config,
+            interacting_with_account_ids: vec![scope.account_id.clone().into()],
+            on_after_getting_network_callback,
+        }))
+    }
+}
+
+impl From<MonitorContext> for crate::network::NetworkContext {
+    fn from(item: MonitorContext) -> Self {
+        item.0
+    }
+}
+
+impl Monitor {
+    pub fn input_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_non_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "What Account ID do you want to monitor?",
+        )
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, strum_macros::EnumString)]
+#[strum(serialize_all = "snake_case")]
+pub enum MonitorOutputFormat {
+    #[default]
+    Text,
+    Ndjson,
+}
+
+impl std::fmt::Display for MonitorOutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MonitorOutputFormat::Text => write!(f, "text"),
+            MonitorOutputFormat::Ndjson => write!(f, "ndjson"),
+        }
+    }
+}
+
+impl interactive_clap::ToCli for MonitorOutputFormat {
+    type CliVariant = MonitorOutputFormat;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum MonitorEventKind {
+    BalanceChanged {
+        previous_amount: Option<String>,
+        amount: String,
+        locked: String,
+    },
+    AccountDeleted,
+    AccessKeyAdded {
+        public_key: String,
+        permission: String,
+    },
+    AccessKeyDeleted {
+        public_key: String,
+    },
+    ContractCodeUpdated {
+        code_hash: String,
+    },
+    ContractCodeDeleted,
+    IncomingReceipt,
+}
+
+impl std::fmt::Display for MonitorEventKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let near = |yoctonear: &str| {
+            near_token::NearToken::from_yoctonear(yoctonear.parse().unwrap_or_default())
+        };
+        match self {
+            Self::BalanceChanged {
+                previous_amount: Some(previous_amount),
+                amount,
+                ..
+            } => write!(
+                f,
+                "balance changed: {} -> {}",
+                near(previous_amount),
+                near(amount)
+            ),
+            Self::BalanceChanged {
+                previous_amount: None,
+                amount,
+                ..
+            } => write!(f, "account created with balance {}", near(amount)),
+            Self::AccountDeleted => write!(f, "account deleted"),
+            Self::AccessKeyAdded {
+                public_key,
+                permission,
+            } => write!(f, "access key added: {public_key} ({permission})"),
+            Self::AccessKeyDeleted { public_key } => {
+                write!(f, "access key deleted: {public_key}")
+            }
+            Self::ContractCodeUpdated { code_hash } => {
+                write!(f, "contract code updated (code hash {code_hash})")
+            }
+            Self::ContractCodeDeleted => write!(f, "contract code deleted"),
+            Self::IncomingReceipt => write!(f, "incoming receipt"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+struct MonitorEvent {
+    block_height: near_primitives::types::BlockHeight,
+    block_hash: String,
+    timestamp: String,
+    #[serde(flatten)]
+    kind: MonitorEventKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tx_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    receipt_id: Option<String>,
+}
+
+impl MonitorEvent {
+    fn display(&self, output: MonitorOutputFormat) -> color_eyre::eyre::Result<String> {
+        Ok(match output {
+            MonitorOutputFormat::Ndjson => serde_json::to_string(self)?,
+            MonitorOutputFormat::Text => {
+                let origin = match (&self.tx_hash, &self.receipt_id) {
+                    (Some(tx_hash), _) => format!(" (tx {tx_hash})"),
+                    (None, Some(receipt_id)) => format!(" (receipt {receipt_id})"),
+                    (None, None) => String::new(),
+                };
+                format!(
+                    "{} #{} {}{origin}",
+                    self.timestamp, self.block_height, self.kind
+                )
+            }
+        })
+    }
+}
+
+/// What is known about the account, to report only the actual changes (e.g. the access keys are
+/// updated with every transaction because of the nonce).
+#[derive(Debug, Default)]
+struct MonitorState {
+    account: Option<near_primitives::views::AccountView>,
+    access_keys: HashSet<near_crypto::PublicKey>,
+}
+
+impl MonitorState {
+    /// The events of the state changes of one block, in the order of the changes.
+    fn apply_changes(
+        &mut self,
+        changes: &[near_primitives::views::StateChangeWithCauseView],
+    ) -> Vec<(
+        MonitorEventKind,
+        Option<near_primitives::hash::CryptoHash>,
+        Option<near_primitives::hash::CryptoHash>,
+    )> {
+        let mut events = Vec::new();
+        let mut seen_receipts = HashSet::new();
+        for change in changes {
+            let (tx_hash, receipt_id) = match change.cause {
+                near_primitives::views::StateChangeCauseView::TransactionProcessing { tx_hash } => {
+                    (Some(tx_hash), None)
+                }
+                near_primitives::views::StateChangeCauseView::ActionReceiptProcessingStarted {
+                    receipt_hash,
+                }
+                | near_primitives::views::StateChangeCauseView::ReceiptProcessing {
+                    receipt_hash,
+                } => {
+                    if seen_receipts.insert(receipt_hash) {
+                        events.push((MonitorEventKind::IncomingReceipt, None, Some(receipt_hash)));
+                    }
+                    (None, Some(receipt_hash))
+                }
+                near_primitives::views::StateChangeCauseView::ActionReceiptGasReward {
+                    receipt_hash,
+                }
+                | near_primitives::views::StateChangeCauseView::PostponedReceipt { receipt_hash } => {
+                    (None, Some(receipt_hash))
+                }
+                _ => (None, None),
+            };
+            let kind = match &change.value {
+                near_primitives::views::StateChangeValueView::AccountUpdate { account, .. } => {
+                    let previous_account = self.account.replace(account.clone());
+                    if previous_account.as_ref().is_some_and(|previous_account| {
+                        previous_account.amount == account.amount
+                            && previous_account.locked == account.locked
+                    }) {
+                        continue;
+                    }
+                    MonitorEventKind::BalanceChanged {
+                        previous_amount: previous_account
+                            .map(|previous_account| previous_account.amount.to_string()),
+                        amount: account.amount.to_string(),
+                        locked: account.locked.to_string(),
+                    }
+                }
+                near_primitives::views::StateChangeValueView::AccountDeletion { .. } => {
+                    self.account = None;
+                    MonitorEventKind::AccountDeleted
+                }
+                near_primitives::views::StateChangeValueView::AccessKeyUpdate {
+                    public_key,
+                    access_key,
+                    ..
+                } => {
+                    if !self.access_keys.insert(public_key.clone()) {
+                        continue;
+                    }
+                    MonitorEventKind::AccessKeyAdded {
+                        public_key: public_key.to_string(),
+                        permission: crate::common::access_key_permission_message(
+                            &access_key.permission,
+                        ),
+                    }
+                }
+                near_primitives::views::StateChangeValueView::AccessKeyDeletion {
+                    public_key,
+                    ..
+                } => {
+                    self.access_keys.remove(public_key);
+                    MonitorEventKind::AccessKeyDeleted {
+                        public_key: public_key.to_string(),
+                    }
+                }
+                near_primitives::views::StateChangeValueView::ContractCodeUpdate {
+                    code, ..
+                } => MonitorEventKind::ContractCodeUpdated {
+                    code_hash: near_primitives::hash::CryptoHash::hash_bytes(code).to_string(),
+                },
+                near_primitives::views::StateChangeValueView::ContractCodeDeletion { .. } => {
+                    MonitorEventKind::ContractCodeDeleted
+                }
+                near_primitives::views::StateChangeValueView::DataUpdate { .. }
+                | near_primitives::views::StateChangeValueView::DataDeletion { .. } => continue,
+            };
+            events.push((kind, tx_hash, receipt_id));
+        }
+        events
+    }
+}
+
+async fn monitor_account(
+    network_config: &crate::config::NetworkConfig,
+    account_id: &near_primitives::types::AccountId,
+    output: MonitorOutputFormat,
+) -> crate::CliResult {
+    let json_rpc_client = network_config.json_rpc_client();
+    let mut state = get_initial_state(&json_rpc_client, account_id)
+        .await
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch the account <{account_id}> on network <{}>",
+                network_config.network_name
+            )
+        })?;
+    let mut next_block_height = get_final_block(&json_rpc_client).await?.header.height + 1;
+    eprintln!(
+        "Monitoring <{account_id}> on network <{}> from block #{next_block_height} (press Ctrl-C to stop) ...",
+        network_config.network_name
+    );
+
+    let mut backoff = POLL_INTERVAL;
+    loop {
+        match process_new_blocks(
+            &json_rpc_client,
+            account_id,
+            &mut state,
+            &mut next_block_height,
+            output,
+        )
+        .await
+        {
+            Ok(()) => {
+                backoff = POLL_INTERVAL;
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+            Err(err) => {
+                eprintln!(
+                    "Warning: {err:#}. Retrying in {} seconds ...",
+                    backoff.as_secs()
+                );
+                tokio::time::sleep(backoff).await;
+                backoff = std::cmp::min(backoff * 2, MAX_BACKOFF);
+            }
+        }
+    }
+}
+
+async fn get_initial_state(
+    json_rpc_client: &near_jsonrpc_client::JsonRpcClient,
+    account_id: &near_primitives::types::AccountId,
+) -> color_eyre::eyre::Result<MonitorState> {
+    let query = |request| {
+        crate::rpc_rate_limit::call(
+            json_rpc_client,
+            near_jsonrpc_client::methods::query::RpcQueryRequest {
+                block_reference: near_primitives::types::Finality::Final.into(),
+                request,
+            },
+        )
+    };
+    let account = match query(near_primitives::views::QueryRequest::ViewAccount {
+        account_id: account_id.clone(),
+    })
+    .await
+    {
+        Ok(response) => response.account_view()?,
+        Err(near_jsonrpc_client::errors::JsonRpcError::ServerError(
+            near_jsonrpc_client::errors::JsonRpcServerError::HandlerError(
+                near_jsonrpc_primitives::types::query::RpcQueryError::UnknownAccount { .. },
+            ),
+        )) => return Ok(MonitorState::default()),
+        Err(err) => return Err(err.into()),
+    };
+    let access_keys = query(near_primitives::views::QueryRequest::ViewAccessKeyList {
+        account_id: account_id.clone(),
+    })
+    .await?
+    .access_key_list_view()?
+    .keys
+    .into_iter()
+    .map(|access_key| access_key.public_key)
+    .collect();
+    Ok(MonitorState {
+        account: Some(account),
+        access_keys,
+    })
+}
+
+async fn get_final_block(
+    json_rpc_client: &near_jsonrpc_client::JsonRpcClient,
+) -> color_eyre::eyre::Result<near_primitives::views::BlockView> {
+    crate::rpc_rate_limit::call(
+        json_rpc_client,
+        near_jsonrpc_client::methods::block::RpcBlockRequest {
+            block_reference: near_primitives::types::Finality::Final.into(),
+        },
+    )
+    .await
+    .wrap_err("Failed to fetch the final block")
+}
+
+/// Prints the events of the blocks from `next_block_height` up to the final block.
+async fn process_new_blocks(
+    json_rpc_client: &near_jsonrpc_client::JsonRpcClient,
+    account_id: &near_primitives::types::AccountId,
+    state: &mut MonitorState,
+    next_block_height: &mut near_primitives::types::BlockHeight,
+    output: MonitorOutputFormat,
+) -> crate::CliResult {
+    let final_block_height = get_final_block(json_rpc_client).await?.header.height;
+    while *next_block_height <= final_block_height {
+        let block_height = *next_block_height;
+        let block = match crate::rpc_rate_limit::call(
+            json_rpc_client,
+            near_jsonrpc_client::methods::block::RpcBlockRequest {
+                block_reference: near_primitives::types::BlockReference::BlockId(
+                    near_primitives::types::BlockId::Height(block_height),
+                ),
+            },
+        )
+        .await
+        {
+            Ok(block) => Some(block),
+            // Not every height has a block
+            Err(near_jsonrpc_client::errors::JsonRpcError::ServerError(
+                near_jsonrpc_client::errors::JsonRpcServerError::HandlerError(
+                    near_jsonrpc_primitives::types::blocks::RpcBlockError::UnknownBlock { .. },
+                ),
+            )) => None,
+            Err(err) => {
+                return Err(err).wrap_err_with(|| format!("Failed to fetch block #{block_height}"))
+            }
+        };
+        if let Some(block) = block {
+            for event in get_block_events(json_rpc_client, account_id, state, &block).await? {
+                println!("{}", event.display(output)?);
+            }
+        }
+        *next_block_height += 1;
+    }
+    Ok(())
+}
+
+async fn get_block_events(
+    json_rpc_client: &near_jsonrpc_client::JsonRpcClient,
+    account_id: &near_primitives::types::AccountId,
+    state: &mut MonitorState,
+    block: &near_primitives::views::BlockView,
+) -> color_eyre::eyre::Result<Vec<MonitorEvent>> {
+    let block_reference = near_primitives::types::BlockReference::BlockId(
+        near_primitives::types::BlockId::Hash(block.header.hash),
+    );
+    // A cheap check whether the account was touched at all before fetching the changes themselves
+    let changed_kinds = crate::rpc_rate_limit::call(
+        json_rpc_client,
+        near_jsonrpc_client::methods::EXPERIMENTAL_changes_in_block::RpcStateChangesInBlockRequest {
+            block_reference: block_reference.clone(),
+        },
+    )
+    .await
+    .wrap_err_with(|| {
+        format!(
+            "Failed to fetch the changes in block #{}",
+            block.header.height
+        )
+    })?
+    .changes;
+    let is_touched = changed_kinds.iter().any(|kind| match kind {
+        near_primitives::views::StateChangeKindView::AccountTouched {
+            account_id: touched,
+        }
+        | near_primitives::views::StateChangeKindView::AccessKeyTouched {
+            account_id: touched,
+        }
+        | near_primitives::views::StateChangeKindView::ContractCodeTouched {
+            account_id: touched,
+        } => touched == account_id,
+        near_primitives::views::StateChangeKindView::DataTouched { .. } => false,
+    });
+    if !is_touched {
+        return Ok(Vec::new());
+    }
+
+    let mut changes = Vec::new();
+    for state_changes_request in [
+        near_primitives::views::StateChangesRequestView::AccountChanges {
+            account_ids: vec![account_id.clone()],
+        },
+        near_primitives::views::StateChangesRequestView::AllAccessKeyChanges {
+            account_ids: vec![account_id.clone()],
+        },
+        near_primitives::views::StateChangesRequestView::ContractCodeChanges {
+            account_ids: vec![account_id.clone()],
+        },
+    ] {
+        changes.extend(
+            crate::rpc_rate_limit::call(
+                json_rpc_client,
+                near_jsonrpc_client::methods::EXPERIMENTAL_changes::RpcStateChangesInBlockByTypeRequest {
+                    block_reference: block_reference.clone(),
+                    state_changes_request,
+                },
+            )
+            .await
+            .wrap_err_with(|| {
+                format!(
+                    "Failed to fetch the changes of <{account_id}> in block #{}",
+                    block.header.height
+                )
+            })?
+            .changes,
+        );
+    }
+
+    let timestamp = block_timestamp(block.header.timestamp);
+    Ok(state
+        .apply_changes(&changes)
+        .into_iter()
+        .map(|(kind, tx_hash, receipt_id)| MonitorEvent {
+            block_height: block.header.height,
+            block_hash: block.header.hash.to_string(),
+            timestamp: timestamp.clone(),
+            kind,
+            tx_hash: tx_hash.map(|tx_hash| tx_hash.to_string()),
+            receipt_id: receipt_id.map(|receipt_id| receipt_id.to_string()),
+        })
+        .collect())
+}
+
+fn block_timestamp(timestamp_nanosec: u64) -> String {
+    chrono::DateTime::from_timestamp(
+        (timestamp_nanosec / 1_000_000_000) as i64,
+        (timestamp_nanosec % 1_000_000_000) as u32,
+    )
+    .map(|date_time| date_time.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string())
+    .unwrap_or_else(|| timestamp_nanosec.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account_update(
+        amount: u128,
+        cause: near_primitives::views::StateChangeCauseView,
+    ) -> near_primitives::views::StateChangeWithCauseView {
+        near_primitives::views::StateChangeWithCauseView {
+            cause,
+            value: near_primitives::views::StateChangeValueView::AccountUpdate {
+                account_id: "alice.near".parse().unwrap(),
+                account: near_primitives::views::AccountView {
+                    amount,
+                    locked: 0,
+                    code_hash: near_primitives::hash::CryptoHash::default(),
+                    storage_usage: 182,
+                    storage_paid_at: 0,
+                },
+            },
+        }
+    }
+
+    #[test]
+    fn only_actual_changes_are_reported() {
+        let tx_hash = near_primitives::hash::CryptoHash::hash_bytes(b"tx");
+        let receipt_hash = near_primitives::hash::CryptoHash::hash_bytes(b"receipt");
+        let public_key = near_crypto::PublicKey::from_seed(near_crypto::KeyType::ED25519, "a");
+        let mut state = MonitorState::default();
+        let events = state.apply_changes(&[
+            account_update(
+                10,
+                near_primitives::views::StateChangeCauseView::TransactionProcessing { tx_hash },
+            ),
+            account_update(
+                10,
+                near_primitives::views::StateChangeCauseView::ReceiptProcessing { receipt_hash },
+            ),
+            account_update(
+                7,
+                near_primitives::views::StateChangeCauseView::ReceiptProcessing { receipt_hash },
+            ),
+            near_primitives::views::StateChangeWithCauseView {
+                cause: near_primitives::views::StateChangeCauseView::ReceiptProcessing {
+                    receipt_hash,
+                },
+                value: near_primitives::views::StateChangeValueView::AccessKeyUpdate {
+                    account_id: "alice.near".parse().unwrap(),
+                    public_key: public_key.clone(),
+                    access_key: near_primitives::views::AccessKeyView {
+                        nonce: 0,
+                        permission: near_primitives::views::AccessKeyPermissionView::FullAccess,
+                    },
+                },
+            },
+        ]);
+        assert_eq!(
+            events,
+            vec![
+                (
+                    MonitorEventKind::BalanceChanged {
+                        previous_amount: None,
+                        amount: "10".to_string(),
+                        locked: "0".to_string()
+                    },
+                    Some(tx_hash),
+                    None
+                ),
+                (MonitorEventKind::IncomingReceipt, None, Some(receipt_hash)),
+                (
+                    MonitorEventKind::BalanceChanged {
+                        previous_amount: Some("10".to_string()),
+                        amount: "7".to_string(),
+                        locked: "0".to_string()
+                    },
+                    None,
+                    Some(receipt_hash)
+                ),
+                (
+                    MonitorEventKind::AccessKeyAdded {
+                        public_key: public_key.to_string(),
+                        permission: "full access".to_string()
+                    },
+                    None,
+                    Some(receipt_hash)
+                ),
+            ]
+        );
+        // The nonce update of a known key is not reported
+        assert!(state
+            .apply_changes(&[near_primitives::views::StateChangeWithCauseView {
+                cause: near_primitives::views::StateChangeCauseView::TransactionProcessing {
+                    tx_hash
+                },
+                value: near_primitives::views::StateChangeValueView::AccessKeyUpdate {
+                    account_id: "alice.near".parse().unwrap(),
+                    public_key,
+                    access_key: near_primitives::views::AccessKeyView {
+                        nonce: 1,
+                        permission: near_primitives::views::AccessKeyPermissionView::FullAccess,
+                    },
+                },
+            }])
+            .is_empty());
+    }
+
+    #[test]
+    fn ndjson_event() {
+        let event = MonitorEvent {
+            block_height: 100,
+            block_hash: "hash".to_string(),
+            timestamp: block_timestamp(1_700_000_000_123_000_000),
+            kind: MonitorEventKind::ContractCodeDeleted,
+            tx_hash: None,
+            receipt_id: Some("receipt".to_string()),
+        };
+        assert_eq!(
+            event.display(MonitorOutputFormat::Ndjson).unwrap(),
+            r#"{"block_height":100,"block_hash":"hash","timestamp":"2023-11-14T22:13:20.123Z","event":"contract_code_deleted","receipt_id":"receipt"}"#
+        );
+        assert_eq!(
+            event.display(MonitorOutputFormat::Text).unwrap(),
+            "2023-11-14T22:13:20.123Z #100 contract code deleted (receipt receipt)"
+        );
+    }
+}
@@ -0,0 +1,122 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = PrintKeyFilePathContext)]
+pub struct PrintKeyFilePath {
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the account ID of the key file?
+    account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network::Network,
+}
+
+#[derive(Clone)]
+pub struct PrintKeyFilePathContext(crate::network::NetworkContext);
+
+impl PrintKeyFilePathContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<PrintKeyFilePath as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let on_after_getting_network_callback: crate::network::OnAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let config = previous_context.config.clone();
+                let account_id: near_primitives::types::AccountId = scope.account_id.clone().into();
+
+                move |network_config| {
+                    let key_file_paths = KeyFilePaths::new(
+                        &config.credentials_dir(network_config),
+                        &network_config.network_name,
+                        &account_id,
+                    );
+                    if key_file_paths.account_file.exists() {
+                        eprintln!(
+                            "The key file of <{account_id}> on network <{}> is:",
+                            network_config.network_name
+                        );
+                    } else {
+                        eprintln!(
+                            "There is no key file of <{account_id}> on network <{}> yet, it would be saved to:",
+                            network_config.network_name
+                        );
+                    }
+                    println!("{}", key_file_paths.account_file.display());
+                    eprintln!(
+                        "\nEach access key of the account is also saved as <public key>.json in {}",
+                        key_file_paths.keys_dir.display()
+                    );
+                    eprintln!("(only the legacy keychain uses files: the keys saved in the OS keychain are not there)");
+                    Ok(())
+                }
+            });
+
+        Ok(Self(crate::network::NetworkContext {
+            config: previous_context.config,
+            interacting_with_account_ids: vec![scope.account_id.clone().into()],
+            on_after_getting_network_callback,
+        }))
+    }
+}
+
+impl From<PrintKeyFilePathContext> for crate::network::NetworkContext {
+    fn from(item: PrintKeyFilePathContext) -> Self {
+        item.0
+    }
+}
+
+impl PrintKeyFilePath {
+    pub fn input_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "What is the account ID of the key file?",
+        )
+    }
+}
+
+/// The paths in the legacy keychain, see `crate::common::save_access_key_to_legacy_keychain`.
+#[derive(Debug, PartialEq, Eq)]
+struct KeyFilePaths {
+    /// `<network_name>/<account_id>.json`, with the last saved key of the account
+    account_file: std::path::PathBuf,
+    /// `<network_name>/<account_id>/`, with a `<public_key>.json` file for each key
+    keys_dir: std::path::PathBuf,
+}
+
+impl KeyFilePaths {
+    fn new(
+        credentials_dir: &std::path::Path,
+        network_name: &str,
+        account_id: &near_primitives::types::AccountId,
+    ) -> Self {
+        let network_dir = credentials_dir.join(network_name);
+        Self {
+            account_file: network_dir.join(format!("{account_id}.json")),
+            keys_dir: network_dir.join(account_id.as_str()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_file_paths_in_the_legacy_keychain() {
+        let key_file_paths = KeyFilePaths::new(
+            std::path::Path::new("/home/alice/.near-credentials"),
+            "testnet",
+            &"alice.testnet".parse().unwrap(),
+        );
+        assert_eq!(
+            key_file_paths.account_file,
+            std::path::Path::new("/home/alice/.near-credentials/testnet/alice.testnet.json")
+        );
+        assert_eq!(
+            key_file_paths.keys_dir,
+            std::path::Path::new("/home/alice/.near-credentials/testnet/alice.testnet")
+        );
+    }
+}
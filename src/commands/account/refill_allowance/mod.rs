@@ -0,0 +1,215 @@
+use color_eyre::eyre::WrapErr;
+
+use crate::common::{JsonRpcClientExt, RpcQueryResponseExt};
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = RefillAllowanceContext)]
+pub struct RefillAllowance {
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_default_input_arg)]
+    /// Which account does the function-call access key belong to?
+    account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    /// What is the public key of the function-call access key?
+    public_key: crate::types::public_key::PublicKey,
+    #[interactive_clap(long)]
+    /// What is the new allowance of the access key (example: 0.25NEAR or unlimited)?
+    new_allowance: crate::types::near_allowance::NearAllowance,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network_for_transaction::NetworkForTransactionArgs,
+}
+
+#[derive(Clone)]
+pub struct RefillAllowanceContext(crate::commands::ActionContext);
+
+impl RefillAllowanceContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<RefillAllowance as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let account_id: near_primitives::types::AccountId = scope.account_id.clone().into();
+        let public_key: near_crypto::PublicKey = scope.public_key.clone().into();
+        let new_allowance = scope.new_allowance;
+
+        let get_prepopulated_transaction_after_getting_network_callback: crate::commands::GetPrepopulatedTransactionAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let account_id = account_id.clone();
+                let public_key = public_key.clone();
+
+                move |network_config| {
+                    let access_key_view = network_config
+                        .json_rpc_client()
+                        .blocking_call_view_access_key(
+                            &account_id,
+                            &public_key,
+                            near_primitives::types::Finality::Final.into(),
+                        )
+                        .wrap_err_with(|| {
+                            format!(
+                                "Failed to fetch the access key {public_key} of <{account_id}> on network <{}>",
+                                network_config.network_name
+                            )
+                        })?
+                        .access_key_view()?;
+                    let (old_allowance, actions) =
+                        refill_allowance_actions(&public_key, access_key_view, new_allowance)?;
+                    eprintln!(
+                        "The allowance of the access key {public_key} will be changed from {old_allowance} to {new_allowance}."
+                    );
+                    Ok(crate::commands::PrepopulatedTransaction {
+                        signer_id: account_id.clone(),
+                        receiver_id: account_id.clone(),
+                        actions,
+                    })
+                }
+            });
+
+        let on_after_sending_transaction_callback: crate::transaction_signature_options::OnAfterSendingTransactionCallback = std::sync::Arc::new({
+            let account_id = account_id.clone();
+
+            move |outcome_view, _network_config| {
+                if let near_primitives::views::FinalExecutionStatus::SuccessValue(_) = outcome_view.status {
+                    eprintln!(
+                        "The access key {public_key} of <{account_id}> now has an allowance of {new_allowance}."
+                    );
+                }
+                Ok(())
+            }
+        });
+
+        Ok(Self(crate::commands::ActionContext {
+            global_context: previous_context,
+            interacting_with_account_ids: vec![account_id],
+            get_prepopulated_transaction_after_getting_network_callback,
+            on_before_signing_callback: std::sync::Arc::new(
+                |_prepolulated_unsinged_transaction, _network_config| Ok(()),
+            ),
+            on_before_sending_transaction_callback: std::sync::Arc::new(
+                |_signed_transaction, _network_config| Ok(String::new()),
+            ),
+            on_after_sending_transaction_callback,
+        }))
+    }
+}
+
+impl From<RefillAllowanceContext> for crate::commands::ActionContext {
+    fn from(item: RefillAllowanceContext) -> Self {
+        item.0
+    }
+}
+
+impl RefillAllowance {
+    pub fn input_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "Which account does the function-call access key belong to?",
+        )
+    }
+}
+
+/// The old allowance and the `DeleteKey` + `AddKey` actions that re-add the key with the same
+/// receiver and method names, but with the new allowance.
+fn refill_allowance_actions(
+    public_key: &near_crypto::PublicKey,
+    access_key_view: near_primitives::views::AccessKeyView,
+    new_allowance: crate::types::near_allowance::NearAllowance,
+) -> color_eyre::eyre::Result<(
+    crate::types::near_allowance::NearAllowance,
+    Vec<near_primitives::transaction::Action>,
+)> {
+    let near_primitives::views::AccessKeyPermissionView::FunctionCall {
+        allowance,
+        receiver_id,
+        method_names,
+    } = access_key_view.permission
+    else {
+        return Err(color_eyre::eyre::eyre!(
+            "The access key {public_key} is a full access key, it has no allowance to refill"
+        ));
+    };
+    let old_allowance = match allowance {
+        Some(allowance) => crate::types::near_allowance::NearAllowance::from_yoctonear(allowance),
+        None => crate::types::near_allowance::NearAllowance::from(None),
+    };
+    let actions = vec![
+        near_primitives::transaction::Action::DeleteKey(Box::new(
+            near_primitives::transaction::DeleteKeyAction {
+                public_key: public_key.clone(),
+            },
+        )),
+        near_primitives::transaction::Action::AddKey(Box::new(
+            near_primitives::transaction::AddKeyAction {
+                public_key: public_key.clone(),
+                access_key: near_primitives::account::AccessKey {
+                    nonce: access_key_view.nonce,
+                    permission: near_primitives::account::AccessKeyPermission::FunctionCall(
+                        near_primitives::account::FunctionCallPermission {
+                            allowance: new_allowance
+                                .optional_near_token()
+                                .map(|allowance| allowance.as_yoctonear()),
+                            receiver_id,
+                            method_names,
+                        },
+                    ),
+                },
+            },
+        )),
+    ];
+    Ok((old_allowance, actions))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_key_is_re_added_with_the_new_allowance() {
+        let public_key: near_crypto::PublicKey =
+            "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp"
+                .parse()
+                .unwrap();
+        let access_key_view = near_primitives::views::AccessKeyView {
+            nonce: 7,
+            permission: near_primitives::views::AccessKeyPermissionView::FunctionCall {
+                allowance: Some(1000),
+                receiver_id: "app.near".to_string(),
+                method_names: vec!["vote".to_string()],
+            },
+        };
+        let new_allowance: crate::types::near_allowance::NearAllowance =
+            "0.25 NEAR".parse().unwrap();
+        let (old_allowance, actions) =
+            refill_allowance_actions(&public_key, access_key_view, new_allowance).unwrap();
+        assert_eq!(old_allowance.to_string(), "1000 yoctoNEAR");
+        let [near_primitives::transaction::Action::DeleteKey(delete_key), near_primitives::transaction::Action::AddKey(add_key)] =
+            actions.as_slice()
+        else {
+            panic!("unexpected actions: {actions:?}");
+        };
+        assert_eq!(delete_key.public_key, public_key);
+        assert_eq!(add_key.public_key, public_key);
+        assert_eq!(
+            add_key.access_key,
+            near_primitives::account::AccessKey {
+                nonce: 7,
+                permission: near_primitives::account::AccessKeyPermission::FunctionCall(
+                    near_primitives::account::FunctionCallPermission {
+                        allowance: Some(250_000_000_000_000_000_000_000),
+                        receiver_id: "app.near".to_string(),
+                        method_names: vec!["vote".to_string()],
+                    }
+                ),
+            }
+        );
+
+        let full_access_key = near_primitives::views::AccessKeyView {
+            nonce: 7,
+            permission: near_primitives::views::AccessKeyPermissionView::FullAccess,
+        };
+        assert!(refill_allowance_actions(&public_key, full_access_key, new_allowance).is_err());
+    }
+}
@@ -0,0 +1,308 @@
+use color_eyre::eyre::WrapErr;
+
+use crate::common::{JsonRpcClientExt, RpcQueryResponseExt};
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = ReplaceAllKeysContext)]
+pub struct ReplaceAllKeys {
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_default_input_arg)]
+    /// Which account do you want to replace all the access keys of?
+    account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    /// What is the public key of the new access key (the only key left on the account)?
+    new_public_key: crate::types::public_key::PublicKey,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// The permission of the new access key (default: full; a function-call key would leave the account without a key able to manage its keys)
+    permission: Option<NewKeyPermission>,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network_for_transaction::NetworkForTransactionArgs,
+}
+
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, strum_macros::Display, strum_macros::EnumString,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum NewKeyPermission {
+    #[default]
+    Full,
+}
+
+impl interactive_clap::ToCli for NewKeyPermission {
+    type CliVariant = NewKeyPermission;
+}
+
+#[derive(Clone)]
+pub struct ReplaceAllKeysContext(crate::commands::ActionContext);
+
+impl ReplaceAllKeysContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<ReplaceAllKeys as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let account_id: near_primitives::types::AccountId = scope.account_id.clone().into();
+        let new_public_key: near_crypto::PublicKey = scope.new_public_key.clone().into();
+
+        let get_prepopulated_transaction_after_getting_network_callback: crate::commands::GetPrepopulatedTransactionAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let account_id = account_id.clone();
+                let new_public_key = new_public_key.clone();
+
+                move |network_config| {
+                    let access_keys = network_config
+                        .json_rpc_client()
+                        .blocking_call_view_access_key_list(
+                            &account_id,
+                            near_primitives::types::Finality::Final.into(),
+                        )
+                        .wrap_err_with(|| {
+                            format!(
+                                "Failed to fetch the access keys of <{account_id}> on network <{}>",
+                                network_config.network_name
+                            )
+                        })?
+                        .access_key_list_view()?
+                        .keys;
+                    let actions = replace_all_keys_actions(&access_keys, &new_public_key);
+                    let deleted_keys = deleted_public_keys(&actions)
+                        .filter(|public_key| **public_key != new_public_key)
+                        .map(|public_key| format!("  {public_key}"))
+                        .collect::<Vec<_>>();
+                    if deleted_keys.is_empty() {
+                        eprintln!("<{account_id}> has no other access keys to delete.");
+                    } else {
+                        eprintln!(
+                            "These access keys of <{account_id}> will be deleted:\n{}",
+                            deleted_keys.join("\n")
+                        );
+                    }
+                    eprintln!(
+                        "{new_public_key} will be the only access key of <{account_id}> (full access)."
+                    );
+                    Ok(crate::commands::PrepopulatedTransaction {
+                        signer_id: account_id.clone(),
+                        receiver_id: account_id.clone(),
+                        actions,
+                    })
+                }
+            });
+
+        let on_before_signing_callback: crate::commands::OnBeforeSigningCallback =
+            std::sync::Arc::new(|prepopulated_unsigned_transaction, _network_config| {
+                let signer_public_key = prepopulated_unsigned_transaction.public_key().clone();
+                move_signer_key_deletion_last(
+                    prepopulated_unsigned_transaction.actions_mut(),
+                    &signer_public_key,
+                );
+                Ok(())
+            });
+
+        let on_after_sending_transaction_callback: crate::transaction_signature_options::OnAfterSendingTransactionCallback = std::sync::Arc::new({
+            let account_id = account_id.clone();
+
+            move |outcome_view, _network_config| {
+                if let near_primitives::views::FinalExecutionStatus::SuccessValue(_) = outcome_view.status {
+                    eprintln!(
+                        "All the access keys of <{account_id}> were replaced with {new_public_key}."
+                    );
+                }
+                Ok(())
+            }
+        });
+
+        Ok(Self(crate::commands::ActionContext {
+            global_context: previous_context,
+            interacting_with_account_ids: vec![account_id],
+            get_prepopulated_transaction_after_getting_network_callback,
+            on_before_signing_callback,
+            on_before_sending_transaction_callback: std::sync::Arc::new(
+                |_signed_transaction, _network_config| Ok(String::new()),
+            ),
+            on_after_sending_transaction_callback,
+        }))
+    }
+}
+
+impl From<ReplaceAllKeysContext> for crate::commands::ActionContext {
+    fn from(item: ReplaceAllKeysContext) -> Self {
+        item.0
+    }
+}
+
+impl ReplaceAllKeys {
+    pub fn input_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "Which account do you want to replace all the access keys of?",
+        )
+    }
+}
+
+/// `AddKey` for the new full access key and `DeleteKey` for every other key, in one transaction
+/// so that the account is never left with both or neither. If the new key is already a
+/// function-call key of the account, it is deleted first and added again with full access.
+fn replace_all_keys_actions(
+    access_keys: &[near_primitives::views::AccessKeyInfoView],
+    new_public_key: &near_crypto::PublicKey,
+) -> Vec<near_primitives::transaction::Action> {
+    let delete_key = |public_key: &near_crypto::PublicKey| {
+        near_primitives::transaction::Action::DeleteKey(Box::new(
+            near_primitives::transaction::DeleteKeyAction {
+                public_key: public_key.clone(),
+            },
+        ))
+    };
+    let mut actions = Vec::new();
+    match access_keys
+        .iter()
+        .find(|access_key| access_key.public_key == *new_public_key)
+    {
+        Some(access_key)
+            if access_key.access_key.permission
+                == near_primitives::views::AccessKeyPermissionView::FullAccess => {}
+        existing_key => {
+            if existing_key.is_some() {
+                actions.push(delete_key(new_public_key));
+            }
+            actions.push(near_primitives::transaction::Action::AddKey(Box::new(
+                near_primitives::transaction::AddKeyAction {
+                    public_key: new_public_key.clone(),
+                    access_key: near_primitives::account::AccessKey {
+                        nonce: 0,
+                        permission: near_primitives::account::AccessKeyPermission::FullAccess,
+                    },
+                },
+            )));
+        }
+    }
+    actions.extend(
+        access_keys
+            .iter()
+            .filter(|access_key| access_key.public_key != *new_public_key)
+            .map(|access_key| delete_key(&access_key.public_key)),
+    );
+    actions
+}
+
+/// The signing key is deleted by the last action, after the new key is added.
+fn move_signer_key_deletion_last(
+    actions: &mut [near_primitives::transaction::Action],
+    signer_public_key: &near_crypto::PublicKey,
+) {
+    // The sort is stable, so the other actions keep their order
+    actions.sort_by_key(|action| {
+        matches!(
+            action,
+            near_primitives::transaction::Action::DeleteKey(delete_key)
+                if delete_key.public_key == *signer_public_key
+        )
+    });
+}
+
+fn deleted_public_keys(
+    actions: &[near_primitives::transaction::Action],
+) -> impl Iterator<Item = &near_crypto::PublicKey> {
+    actions.iter().filter_map(|action| match action {
+        near_primitives::transaction::Action::DeleteKey(delete_key) => Some(&delete_key.public_key),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn access_key(
+        public_key: &near_crypto::PublicKey,
+        permission: near_primitives::views::AccessKeyPermissionView,
+    ) -> near_primitives::views::AccessKeyInfoView {
+        near_primitives::views::AccessKeyInfoView {
+            public_key: public_key.clone(),
+            access_key: near_primitives::views::AccessKeyView {
+                nonce: 100,
+                permission,
+            },
+        }
+    }
+
+    fn public_key(seed: &str) -> near_crypto::PublicKey {
+        near_crypto::SecretKey::from_seed(near_crypto::KeyType::ED25519, seed).public_key()
+    }
+
+    #[test]
+    fn all_keys_are_replaced_and_the_signer_key_is_deleted_last() {
+        let [signer, other, new] = ["signer", "other", "new"].map(public_key);
+        let access_keys = [
+            access_key(
+                &signer,
+                near_primitives::views::AccessKeyPermissionView::FullAccess,
+            ),
+            access_key(
+                &other,
+                near_primitives::views::AccessKeyPermissionView::FunctionCall {
+                    allowance: None,
+                    receiver_id: "app.near".to_string(),
+                    method_names: vec![],
+                },
+            ),
+        ];
+        let mut actions = replace_all_keys_actions(&access_keys, &new);
+        move_signer_key_deletion_last(&mut actions, &signer);
+
+        assert!(matches!(
+            &actions[0],
+            near_primitives::transaction::Action::AddKey(add_key)
+                if add_key.public_key == new
+                    && add_key.access_key.permission
+                        == near_primitives::account::AccessKeyPermission::FullAccess
+        ));
+        assert_eq!(
+            deleted_public_keys(&actions).collect::<Vec<_>>(),
+            vec![&other, &signer]
+        );
+    }
+
+    #[test]
+    fn new_key_already_on_the_account() {
+        let [old, new] = ["old", "new"].map(public_key);
+        let full_access = [
+            access_key(
+                &old,
+                near_primitives::views::AccessKeyPermissionView::FullAccess,
+            ),
+            access_key(
+                &new,
+                near_primitives::views::AccessKeyPermissionView::FullAccess,
+            ),
+        ];
+        let actions = replace_all_keys_actions(&full_access, &new);
+        assert_eq!(actions.len(), 1);
+        assert_eq!(
+            deleted_public_keys(&actions).collect::<Vec<_>>(),
+            vec![&old]
+        );
+
+        let function_call = [access_key(
+            &new,
+            near_primitives::views::AccessKeyPermissionView::FunctionCall {
+                allowance: Some(1),
+                receiver_id: "app.near".to_string(),
+                method_names: vec![],
+            },
+        )];
+        let actions = replace_all_keys_actions(&function_call, &new);
+        assert_eq!(
+            deleted_public_keys(&actions).collect::<Vec<_>>(),
+            vec![&new]
+        );
+        assert!(matches!(
+            &actions[1],
+            near_primitives::transaction::Action::AddKey(_)
+        ));
+    }
+}
@@ -0,0 +1,255 @@
+use color_eyre::eyre::WrapErr;
+use tracing_indicatif::span_ext::IndicatifSpanExt;
+
+const DEFAULT_TIMEOUT_SECS: u64 = 300;
+const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = RequestAddKeyLinkContext)]
+pub struct RequestAddKeyLink {
+    #[interactive_clap(skip_default_input_arg)]
+    /// Which account should the new function-call access key be added to?
+    account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    /// Which contract should the access key be limited to?
+    contract: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// A comma-separated list of the method names the access key can call (default: any method)
+    methods: Option<crate::types::vec_string::VecString>,
+    #[interactive_clap(long)]
+    /// What is the allowance of the access key (example: 0.25NEAR or unlimited)?
+    allowance: crate::types::near_allowance::NearAllowance,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// How many seconds to wait for the key to be added (default: 300)
+    timeout_secs: Option<u64>,
+    #[interactive_clap(long)]
+    /// Save the key to the keychain even if it has not been added in time
+    keep_key_on_timeout: bool,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network::Network,
+}
+
+#[derive(Clone)]
+pub struct RequestAddKeyLinkContext(crate::network::NetworkContext);
+
+impl RequestAddKeyLinkContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<RequestAddKeyLink as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let on_after_getting_network_callback: crate::network::OnAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let account_id: near_primitives::types::AccountId = scope.account_id.clone().into();
+                let permission = near_primitives::account::FunctionCallPermission {
+                    allowance: scope
+                        .allowance
+                        .optional_near_token()
+                        .map(|allowance| allowance.as_yoctonear()),
+                    receiver_id: scope.contract.to_string(),
+                    method_names: scope.methods.clone().map(Into::into).unwrap_or_default(),
+                };
+                let timeout = std::time::Duration::from_secs(
+                    scope.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS),
+                );
+                let keep_key_on_timeout = scope.keep_key_on_timeout;
+
+                move |network_config| {
+                    let key_pair_properties = crate::common::generate_keypair()?;
+                    let public_key: near_crypto::PublicKey =
+                        key_pair_properties.public_key_str.parse()?;
+                    let url =
+                        add_key_link(network_config, &account_id, &public_key, permission.clone())?;
+                    eprintln!(
+                        "Open this URL in your wallet (e.g. on your phone) and approve the transaction that adds the access key {public_key} to <{account_id}>:\n {url}\n"
+                    );
+                    crate::qr_code::eprint_url("Or scan it with your phone:", &url);
+
+                    // The secret key is only stored once the key is on chain, so a timeout or
+                    // an interruption does not leave an unusable key in the keychain
+                    if !wait_for_access_key(network_config, &account_id, &public_key, timeout)? {
+                        if !keep_key_on_timeout {
+                            return Err(color_eyre::eyre::eyre!(
+                                "The access key {public_key} has not been added to <{account_id}> in {} seconds. The generated key pair was discarded (use --keep-key-on-timeout to save it anyway).",
+                                timeout.as_secs()
+                            ));
+                        }
+                        eprintln!(
+                            "WARNING: the access key {public_key} has not been added to <{account_id}> in {} seconds, it is saved anyway.",
+                            timeout.as_secs()
+                        );
+                    }
+
+                    let storage_message = crate::common::save_access_key_to_keychain(
+                        network_config.clone(),
+                        &serde_json::to_string(&key_pair_properties)?,
+                        &key_pair_properties.public_key_str,
+                        account_id.as_ref(),
+                    )
+                    .wrap_err_with(|| {
+                        format!("Failed to save the access key <{public_key}> to the keychain")
+                    })?;
+                    eprintln!("{storage_message}");
+                    Ok(())
+                }
+            });
+
+        Ok(Self(crate::network::NetworkContext {
+            config: previous_context.config,
+            interacting_with_account_ids: vec![scope.account_id.clone().into()],
+            on_after_getting_network_callback,
+        }))
+    }
+}
+
+impl From<RequestAddKeyLinkContext> for crate::network::NetworkContext {
+    fn from(item: RequestAddKeyLinkContext) -> Self {
+        item.0
+    }
+}
+
+impl RequestAddKeyLink {
+    pub fn input_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "Which account should the new function-call access key be added to?",
+        )
+    }
+}
+
+/// The `sign` URL of the wallet with the `AddKey` transaction. The wallet signs it with its own
+/// key, nonce and block hash, so only the receiver and the actions of the transaction matter.
+fn add_key_link(
+    network_config: &crate::config::NetworkConfig,
+    account_id: &near_primitives::types::AccountId,
+    public_key: &near_crypto::PublicKey,
+    permission: near_primitives::account::FunctionCallPermission,
+) -> color_eyre::eyre::Result<url::Url> {
+    let transaction = near_primitives::transaction::Transaction::V0(
+        near_primitives::transaction::TransactionV0 {
+            signer_id: account_id.clone(),
+            public_key: public_key.clone(),
+            nonce: 0,
+            receiver_id: account_id.clone(),
+            block_hash: Default::default(),
+            actions: vec![near_primitives::transaction::Action::AddKey(Box::new(
+                near_primitives::transaction::AddKeyAction {
+                    public_key: public_key.clone(),
+                    access_key: near_primitives::account::AccessKey {
+                        nonce: 0,
+                        permission: near_primitives::account::AccessKeyPermission::FunctionCall(
+                            permission,
+                        ),
+                    },
+                },
+            ))],
+        },
+    );
+    crate::transaction_signature_options::sign_with_wallet::wallet_sign_url(
+        network_config,
+        &[transaction],
+        None,
+    )
+}
+
+/// Whether the access key has been added to the account before the timeout.
+#[tracing::instrument(name = "Waiting for the access key to be added", skip_all)]
+fn wait_for_access_key(
+    network_config: &crate::config::NetworkConfig,
+    account_id: &near_primitives::types::AccountId,
+    public_key: &near_crypto::PublicKey,
+    timeout: std::time::Duration,
+) -> color_eyre::eyre::Result<bool> {
+    let started_at = std::time::Instant::now();
+    loop {
+        match crate::common::JsonRpcClientExt::blocking_call_view_access_key(
+            &network_config.json_rpc_client(),
+            account_id,
+            public_key,
+            near_primitives::types::Finality::Final.into(),
+        ) {
+            Ok(_) => return Ok(true),
+            Err(near_jsonrpc_client::errors::JsonRpcError::ServerError(
+                near_jsonrpc_client::errors::JsonRpcServerError::HandlerError(
+                    near_jsonrpc_primitives::types::query::RpcQueryError::UnknownAccessKey {
+                        ..
+                    },
+                ),
+            )) => {}
+            Err(near_jsonrpc_client::errors::JsonRpcError::ServerError(
+                near_jsonrpc_client::errors::JsonRpcServerError::HandlerError(
+                    near_jsonrpc_primitives::types::query::RpcQueryError::UnknownAccount { .. },
+                ),
+            )) => {
+                return Err(color_eyre::eyre::eyre!(
+                    "The account <{account_id}> does not exist on network <{}>",
+                    network_config.network_name
+                ));
+            }
+            Err(err) => tracing::warn!("Failed to fetch the access key {public_key}: {err}"),
+        }
+        if started_at.elapsed() > timeout {
+            return Ok(false);
+        }
+        tracing::Span::current().pb_set_message(&format!(
+            "{public_key} ({}s elapsed)",
+            started_at.elapsed().as_secs()
+        ));
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use near_primitives::borsh::BorshDeserialize;
+
+    use super::*;
+
+    #[test]
+    fn the_link_contains_the_add_key_transaction() {
+        let config = crate::config::Config::default();
+        let testnet = config.network_connection.get("testnet").unwrap();
+        let account_id: near_primitives::types::AccountId = "alice.testnet".parse().unwrap();
+        let public_key: near_crypto::PublicKey =
+            "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp"
+                .parse()
+                .unwrap();
+        let permission = near_primitives::account::FunctionCallPermission {
+            allowance: Some(250_000_000_000_000_000_000_000),
+            receiver_id: "app.testnet".to_string(),
+            method_names: vec!["a".to_string(), "b".to_string()],
+        };
+        let url = add_key_link(testnet, &account_id, &public_key, permission.clone()).unwrap();
+        assert!(
+            url.as_str()
+                .starts_with(&format!("{}sign?transactions=", testnet.wallet_url)),
+            "{url}"
+        );
+
+        let (_, transactions) = url.query_pairs().next().unwrap();
+        let transaction = near_primitives::transaction::Transaction::try_from_slice(
+            &near_primitives::serialize::from_base64(&transactions).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(transaction.receiver_id(), &account_id);
+        assert_eq!(
+            transaction.actions(),
+            [near_primitives::transaction::Action::AddKey(Box::new(
+                near_primitives::transaction::AddKeyAction {
+                    public_key,
+                    access_key: near_primitives::account::AccessKey {
+                        nonce: 0,
+                        permission: near_primitives::account::AccessKeyPermission::FunctionCall(
+                            permission
+                        ),
+                    },
+                }
+            ))]
+        );
+    }
+}
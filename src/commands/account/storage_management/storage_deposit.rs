@@ -1,5 +1,3 @@
-use inquire::Select;
-
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = super::ContractContext)]
 #[interactive_clap(output_context = DepositArgsContext)]
@@ -70,11 +68,13 @@ impl DepositArgs {
                     #[strum(to_string = "No, I want to use this account name.")]
                     No,
                 }
-                let select_choose_input = Select::new(
+                let Some(select_choose_input) = crate::common::select(
                     "Do you want to enter another receiver account id?",
                     vec![ConfirmOptions::Yes, ConfirmOptions::No],
-                )
-                .prompt()?;
+                )?
+                else {
+                    return Ok(None);
+                };
                 if let ConfirmOptions::No = select_choose_input {
                     return Ok(Some(receiver_account_id));
                 }
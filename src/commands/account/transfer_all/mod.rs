@@ -0,0 +1,209 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = TransferAllContext)]
+pub struct TransferAll {
+    #[interactive_clap(skip_default_input_arg)]
+    /// Which account do you want to drain?
+    sender_account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the receiver account ID?
+    receiver_account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network_for_transaction::NetworkForTransactionArgs,
+}
+
+#[derive(Clone)]
+pub struct TransferAllContext(crate::commands::ActionContext);
+
+impl TransferAllContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<TransferAll as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let sender_account_id: near_primitives::types::AccountId =
+            scope.sender_account_id.clone().into();
+        let receiver_account_id: near_primitives::types::AccountId =
+            scope.receiver_account_id.clone().into();
+        if sender_account_id == receiver_account_id {
+            return Err(color_eyre::eyre::eyre!(
+                "The sender and the receiver must be different accounts"
+            ));
+        }
+
+        let get_prepopulated_transaction_after_getting_network_callback: crate::commands::GetPrepopulatedTransactionAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let sender_account_id = sender_account_id.clone();
+                let receiver_account_id = receiver_account_id.clone();
+
+                move |network_config| {
+                    let account_transfer_allowance = tokio::runtime::Runtime::new()?.block_on(
+                        crate::common::get_account_transfer_allowance(
+                            network_config,
+                            sender_account_id.clone(),
+                            near_primitives::types::Finality::Final.into(),
+                        ),
+                    )?;
+                    let amount =
+                        transfer_amount(&sender_account_id, &account_transfer_allowance)?;
+                    eprintln!("{account_transfer_allowance}");
+                    eprintln!(
+                        "\nAll the spendable balance ({amount}) of <{sender_account_id}> will be transferred to <{receiver_account_id}>."
+                    );
+                    eprintln!(
+                        "Warning: <{sender_account_id}> will not be able to pay for new transactions after the transfer. To reclaim its name, delete the account (`account delete-account`)."
+                    );
+
+                    Ok(crate::commands::PrepopulatedTransaction {
+                        signer_id: sender_account_id.clone(),
+                        receiver_id: receiver_account_id.clone(),
+                        actions: vec![near_primitives::transaction::Action::Transfer(
+                            near_primitives::transaction::TransferAction {
+                                deposit: amount.as_yoctonear(),
+                            },
+                        )],
+                    })
+                }
+            });
+
+        Ok(Self(crate::commands::ActionContext {
+            global_context: previous_context,
+            interacting_with_account_ids: vec![sender_account_id, receiver_account_id],
+            get_prepopulated_transaction_after_getting_network_callback,
+            on_before_signing_callback: std::sync::Arc::new(
+                |_prepolulated_unsinged_transaction, _network_config| Ok(()),
+            ),
+            on_before_sending_transaction_callback: std::sync::Arc::new(
+                |_signed_transaction, _network_config| Ok(String::new()),
+            ),
+            on_after_sending_transaction_callback: std::sync::Arc::new(
+                |_outcome_view, _network_config| Ok(()),
+            ),
+        }))
+    }
+}
+
+/// The whole spendable balance of the sender: its transfer allowance, unless nothing is left.
+fn transfer_amount(
+    sender_account_id: &near_primitives::types::AccountId,
+    account_transfer_allowance: &crate::common::AccountTransferAllowance,
+) -> color_eyre::eyre::Result<near_token::NearToken> {
+    let amount = account_transfer_allowance.transfer_allowance();
+    if amount.is_zero() {
+        return Err(color_eyre::eyre::eyre!(
+            "<{sender_account_id}> has no spendable balance to transfer (the balance only covers the storage of the account and the transaction fee)"
+        ));
+    }
+    Ok(amount)
+}
+
+impl From<TransferAllContext> for crate::commands::ActionContext {
+    fn from(item: TransferAllContext) -> Self {
+        item.0
+    }
+}
+
+impl TransferAll {
+    pub fn input_sender_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "Which account do you want to drain?",
+        )
+    }
+
+    pub fn input_receiver_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_non_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "What is the receiver account ID?",
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 10^19 yoctoNEAR per byte, as on mainnet
+    const PER_BYTE: u128 = 10_000_000_000_000_000_000;
+    // The pessimistic transaction fee of get_account_transfer_allowance
+    const FEE: near_token::NearToken = near_token::NearToken::from_millinear(1);
+
+    fn transfer_amount_of(
+        amount: u128,
+        locked: u128,
+        code_hash: near_primitives::hash::CryptoHash,
+        storage_usage: u64,
+    ) -> color_eyre::eyre::Result<near_token::NearToken> {
+        let sender_account_id: near_primitives::types::AccountId = "alice.near".parse().unwrap();
+        let account_transfer_allowance = crate::common::AccountTransferAllowance::new(
+            sender_account_id.clone(),
+            &near_primitives::views::AccountView {
+                amount,
+                locked,
+                code_hash,
+                storage_usage,
+                storage_paid_at: 0,
+            },
+            PER_BYTE,
+            FEE,
+        );
+        transfer_amount(&sender_account_id, &account_transfer_allowance)
+    }
+
+    #[test]
+    fn a_zero_balance_has_nothing_to_transfer() {
+        let err = transfer_amount_of(0, 0, Default::default(), 182).unwrap_err();
+        assert!(
+            err.to_string().contains("has no spendable balance"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn the_locked_balance_covers_the_storage_stake() {
+        let amount =
+            transfer_amount_of(10u128.pow(24), 10u128.pow(25), Default::default(), 182).unwrap();
+        assert_eq!(amount.as_yoctonear(), 10u128.pow(24) - FEE.as_yoctonear());
+        // A locked balance below the storage stake covers only a part of it
+        let amount =
+            transfer_amount_of(10u128.pow(24), 100 * PER_BYTE, Default::default(), 182).unwrap();
+        assert_eq!(
+            amount.as_yoctonear(),
+            10u128.pow(24) - 82 * PER_BYTE - FEE.as_yoctonear()
+        );
+    }
+
+    #[test]
+    fn a_storage_stake_above_the_liquid_balance_has_nothing_to_transfer() {
+        assert!(transfer_amount_of(1_000 * PER_BYTE, 0, Default::default(), 2_000).is_err());
+        // What the storage stake leaves does not cover the fee
+        assert!(transfer_amount_of(
+            182 * PER_BYTE + FEE.as_yoctonear(),
+            0,
+            Default::default(),
+            182
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn the_fee_is_reserved_on_an_account_with_a_contract() {
+        // The code of a contract counts towards the storage usage of the account
+        let storage_usage = 182 + 300_000;
+        let amount = transfer_amount_of(
+            10u128.pow(25),
+            0,
+            near_primitives::hash::CryptoHash::hash_bytes(b"contract code"),
+            storage_usage,
+        )
+        .unwrap();
+        assert_eq!(
+            amount.as_yoctonear(),
+            10u128.pow(25) - u128::from(storage_usage) * PER_BYTE - FEE.as_yoctonear()
+        );
+    }
+}
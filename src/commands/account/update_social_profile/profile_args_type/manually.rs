@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use inquire::{CustomType, Select, Text};
+use inquire::{CustomType, Text};
 
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = super::super::UpdateSocialProfileContext)]
@@ -134,11 +134,13 @@ impl Manually {
             #[strum(to_string = "No, I don't want to enter a name for the account profile")]
             No,
         }
-        let select_choose_input = Select::new(
+        let Some(select_choose_input) = crate::common::select(
             "Do you want to enter a name for the account profile?",
             vec![ConfirmOptions::Yes, ConfirmOptions::No],
-        )
-        .prompt()?;
+        )?
+        else {
+            return Ok(None);
+        };
         if let ConfirmOptions::Yes = select_choose_input {
             Ok(Some(
                 Text::new("Enter a name for the account profile:").prompt()?,
@@ -159,11 +161,13 @@ impl Manually {
             #[strum(to_string = "No, I don't want to enter the URL of the account profile image")]
             No,
         }
-        let select_choose_input = Select::new(
+        let Some(select_choose_input) = crate::common::select(
             "Do you want to enter an account profile image URL?",
             vec![ConfirmOptions::Yes, ConfirmOptions::No],
-        )
-        .prompt()?;
+        )?
+        else {
+            return Ok(None);
+        };
         if let ConfirmOptions::Yes = select_choose_input {
             let url: crate::types::url::Url =
                 CustomType::new("What is the account profile image URL?").prompt()?;
@@ -186,11 +190,13 @@ impl Manually {
             )]
             No,
         }
-        let select_choose_input = Select::new(
+        let Some(select_choose_input) = crate::common::select(
             "Do you want to enter ipfs_cid for the account profile image?",
             vec![ConfirmOptions::Yes, ConfirmOptions::No],
-        )
-        .prompt()?;
+        )?
+        else {
+            return Ok(None);
+        };
         if let ConfirmOptions::Yes = select_choose_input {
             Ok(Some(
                 Text::new("Enter ipfs_cid for the account's profile image:").prompt()?,
@@ -215,11 +221,13 @@ impl Manually {
             )]
             No,
         }
-        let select_choose_input = Select::new(
+        let Some(select_choose_input) = crate::common::select(
             "Do you want to enter an account profile background image URL?",
             vec![ConfirmOptions::Yes, ConfirmOptions::No],
-        )
-        .prompt()?;
+        )?
+        else {
+            return Ok(None);
+        };
         if let ConfirmOptions::Yes = select_choose_input {
             let url: crate::types::url::Url =
                 CustomType::new("What is the account profile background image URL?").prompt()?;
@@ -244,11 +252,13 @@ impl Manually {
             )]
             No,
         }
-        let select_choose_input = Select::new(
+        let Some(select_choose_input) = crate::common::select(
             "Do you want to enter ipfs_cid for the account profile background image?",
             vec![ConfirmOptions::Yes, ConfirmOptions::No],
-        )
-        .prompt()?;
+        )?
+        else {
+            return Ok(None);
+        };
         if let ConfirmOptions::Yes = select_choose_input {
             Ok(Some(
                 Text::new("Enter ipfs_cid for the account profile background image:").prompt()?,
@@ -269,11 +279,13 @@ impl Manually {
             #[strum(to_string = "No, I don't want to enter a description for the account profile")]
             No,
         }
-        let select_choose_input = Select::new(
+        let Some(select_choose_input) = crate::common::select(
             "Do you want to enter a description for the account profile?",
             vec![ConfirmOptions::Yes, ConfirmOptions::No],
-        )
-        .prompt()?;
+        )?
+        else {
+            return Ok(None);
+        };
         if let ConfirmOptions::Yes = select_choose_input {
             Ok(Some(
                 Text::new("Enter a description for the account profile:").prompt()?,
@@ -296,11 +308,13 @@ impl Manually {
             )]
             No,
         }
-        let select_choose_input = Select::new(
+        let Some(select_choose_input) = crate::common::select(
             "Do you want to enter a Twitter nickname for the account profile?",
             vec![ConfirmOptions::Yes, ConfirmOptions::No],
-        )
-        .prompt()?;
+        )?
+        else {
+            return Ok(None);
+        };
         if let ConfirmOptions::Yes = select_choose_input {
             Ok(Some(
                 Text::new("Enter a Twitter nickname for the account profile:").prompt()?,
@@ -323,11 +337,13 @@ impl Manually {
             )]
             No,
         }
-        let select_choose_input = Select::new(
+        let Some(select_choose_input) = crate::common::select(
             "Do you want to enter a Github nickname for the account profile?",
             vec![ConfirmOptions::Yes, ConfirmOptions::No],
-        )
-        .prompt()?;
+        )?
+        else {
+            return Ok(None);
+        };
         if let ConfirmOptions::Yes = select_choose_input {
             Ok(Some(
                 Text::new("Enter a Github nickname for the account profile:").prompt()?,
@@ -352,11 +368,13 @@ impl Manually {
             )]
             No,
         }
-        let select_choose_input = Select::new(
+        let Some(select_choose_input) = crate::common::select(
             "Do you want to enter a Telegram nickname for the account profile?",
             vec![ConfirmOptions::Yes, ConfirmOptions::No],
-        )
-        .prompt()?;
+        )?
+        else {
+            return Ok(None);
+        };
         if let ConfirmOptions::Yes = select_choose_input {
             Ok(Some(
                 Text::new("Enter a Telegram nickname for the account profile:").prompt()?,
@@ -379,11 +397,13 @@ impl Manually {
             )]
             No,
         }
-        let select_choose_input = Select::new(
+        let Some(select_choose_input) = crate::common::select(
             "Do you want to enter the website URL for the account profile?",
             vec![ConfirmOptions::Yes, ConfirmOptions::No],
-        )
-        .prompt()?;
+        )?
+        else {
+            return Ok(None);
+        };
         if let ConfirmOptions::Yes = select_choose_input {
             let url: crate::types::url::Url =
                 CustomType::new("Enter the website URL for the account profile:").prompt()?;
@@ -404,11 +424,13 @@ impl Manually {
             #[strum(to_string = "No, I don't want to enter tags for an account profile")]
             No,
         }
-        let select_choose_input = Select::new(
+        let Some(select_choose_input) = crate::common::select(
             "Do you want to enter tags for the account profile?",
             vec![ConfirmOptions::Yes, ConfirmOptions::No],
-        )
-        .prompt()?;
+        )?
+        else {
+            return Ok(None);
+        };
         if let ConfirmOptions::Yes = select_choose_input {
             let tags: crate::types::vec_string::VecString =
                 CustomType::new("Enter a comma-separated list of tags for account profile:")
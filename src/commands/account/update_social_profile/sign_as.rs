@@ -2,7 +2,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use color_eyre::eyre::WrapErr;
-use inquire::{CustomType, Select};
+use inquire::CustomType;
 
 use crate::common::{CallResultExt, JsonRpcClientExt};
 
@@ -131,11 +131,13 @@ impl Signer {
                     #[strum(to_string = "No, I want to use this account name.")]
                     No,
                 }
-                let select_choose_input = Select::new(
+                let Some(select_choose_input) = crate::common::select(
                     "Do you want to enter another signer account id?",
                     vec![ConfirmOptions::Yes, ConfirmOptions::No],
-                )
-                .prompt()?;
+                )?
+                else {
+                    return Ok(None);
+                };
                 if let ConfirmOptions::No = select_choose_input {
                     return Ok(Some(signer_account_id));
                 }
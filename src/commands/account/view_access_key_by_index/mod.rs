@@ -0,0 +1,90 @@
+use color_eyre::eyre::Context;
+
+use crate::common::JsonRpcClientExt;
+use crate::common::RpcQueryResponseExt;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = ViewAccessKeyByIndexContext)]
+pub struct ViewAccessKeyByIndex {
+    #[interactive_clap(skip_default_input_arg)]
+    /// What Account ID do you need to view?
+    account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    /// What is the position of the access key in the list of keys (starting from 1)?
+    index: u64,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network_view_at_block::NetworkViewAtBlockArgs,
+}
+
+#[derive(Clone)]
+pub struct ViewAccessKeyByIndexContext(crate::network_view_at_block::ArgsForViewContext);
+
+impl ViewAccessKeyByIndexContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<ViewAccessKeyByIndex as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let on_after_getting_block_reference_callback: crate::network_view_at_block::OnAfterGettingBlockReferenceCallback = std::sync::Arc::new({
+            let account_id: near_primitives::types::AccountId = scope.account_id.clone().into();
+            let index = usize::try_from(scope.index)?;
+
+            move |network_config, block_reference| {
+                let rpc_query_response = network_config
+                    .json_rpc_client()
+                    .blocking_call_view_access_key_list(
+                        &account_id,
+                        block_reference.clone(),
+                    )
+                    .wrap_err_with(|| {
+                        format!(
+                            "Failed to fetch query AccessKeyList for {}",
+                            &account_id
+                        )
+                    })?;
+                let access_key_list = rpc_query_response.access_key_list_view()?;
+
+                let access_key = index
+                    .checked_sub(1)
+                    .and_then(|position| access_key_list.keys.get(position))
+                    .ok_or_else(|| {
+                        color_eyre::eyre::eyre!(
+                            "There is no access key #{index} on account <{account_id}> (the account has {} access keys)",
+                            access_key_list.keys.len()
+                        )
+                    })?;
+
+                crate::common::display_access_key(index - 1, access_key);
+                crate::network_view_at_block::display_viewed_at_block(
+                    rpc_query_response.block_height,
+                    &rpc_query_response.block_hash,
+                );
+                Ok(())
+            }
+        });
+
+        Ok(Self(crate::network_view_at_block::ArgsForViewContext {
+            config: previous_context.config,
+            interacting_with_account_ids: vec![scope.account_id.clone().into()],
+            on_after_getting_block_reference_callback,
+        }))
+    }
+}
+
+impl From<ViewAccessKeyByIndexContext> for crate::network_view_at_block::ArgsForViewContext {
+    fn from(item: ViewAccessKeyByIndexContext) -> Self {
+        item.0
+    }
+}
+
+impl ViewAccessKeyByIndex {
+    pub fn input_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_non_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "What Account ID do you need to view?",
+        )
+    }
+}
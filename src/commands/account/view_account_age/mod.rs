@@ -0,0 +1,298 @@
+use color_eyre::eyre::{ContextCompat, WrapErr};
+
+use super::view_balance_history::{bisect_change, get_balance_sample, BalanceSample};
+use crate::common::JsonRpcClientExt;
+
+/// How many heights after the first available block are tried (the chain skips some heights)
+const MAX_SKIPPED_HEIGHTS: u64 = 100;
+const NANOSECONDS_PER_DAY: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = ViewAccountAgeContext)]
+pub struct ViewAccountAge {
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_default_input_arg)]
+    /// What Account ID do you need to view the age of?
+    account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// An indexer with the Nearblocks API (default: the indexer_url of the network connection; without an indexer the block is found with a binary search on the RPC)
+    indexer_url: Option<crate::types::url::Url>,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network::Network,
+}
+
+#[derive(Clone)]
+pub struct ViewAccountAgeContext(crate::network::NetworkContext);
+
+impl ViewAccountAgeContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<ViewAccountAge as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let on_after_getting_network_callback: crate::network::OnAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let account_id: near_primitives::types::AccountId = scope.account_id.clone().into();
+                let indexer_url: Option<url::Url> = scope.indexer_url.clone().map(Into::into);
+
+                move |network_config| {
+                    let path = format!("v1/account/{account_id}/txns?order=asc&per_page=1");
+                    let transactions_url = match (&indexer_url, &network_config.indexer_url) {
+                        (Some(indexer_url), _) => Some(indexer_url.join(&path)?),
+                        (None, Some(indexer_url)) => Some(
+                            crate::config::config_url::ConfigUrl::new(
+                                network_config,
+                                "indexer_url",
+                                indexer_url,
+                            )
+                            .join(&path)?,
+                        ),
+                        (None, None) => None,
+                    };
+
+                    let creation = match transactions_url
+                        .map(|transactions_url| get_first_transaction(&transactions_url))
+                    {
+                        Some(Ok(Some(creation))) => creation,
+                        Some(Ok(None)) => {
+                            eprintln!("The indexer knows no transactions of <{account_id}>, searching for the creation block on the RPC ...");
+                            find_creation_block(network_config, &account_id)?
+                        }
+                        Some(Err(err)) => {
+                            eprintln!("The indexer lookup failed ({err:#}), searching for the creation block on the RPC ...");
+                            find_creation_block(network_config, &account_id)?
+                        }
+                        None => find_creation_block(network_config, &account_id)?,
+                    };
+                    display_account_age(&account_id, &creation, now_nanosec());
+                    Ok(())
+                }
+            });
+
+        Ok(Self(crate::network::NetworkContext {
+            config: previous_context.config,
+            interacting_with_account_ids: vec![scope.account_id.clone().into()],
+            on_after_getting_network_callback,
+        }))
+    }
+}
+
+impl From<ViewAccountAgeContext> for crate::network::NetworkContext {
+    fn from(item: ViewAccountAgeContext) -> Self {
+        item.0
+    }
+}
+
+impl ViewAccountAge {
+    pub fn input_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_non_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "What Account ID do you need to view the age of?",
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct AccountCreation {
+    block_height: near_primitives::types::BlockHeight,
+    timestamp_nanosec: u64,
+    /// The first transaction to or from the account (from the indexer)
+    transaction_hash: Option<String>,
+    /// The account already existed at the first block available on the RPC node (the genesis
+    /// or the oldest block that the node keeps), so it was created at that block or before
+    existed_at_first_block: bool,
+}
+
+#[tracing::instrument(name = "Looking up the first transaction of the account ...", skip_all)]
+fn get_first_transaction(
+    transactions_url: &url::Url,
+) -> color_eyre::eyre::Result<Option<AccountCreation>> {
+    let response: serde_json::Value = reqwest::blocking::get(transactions_url.clone())?
+        .error_for_status()?
+        .json()
+        .wrap_err("Unexpected response of the indexer")?;
+    Ok(parse_first_transaction(&response))
+}
+
+/// The first item of the `txns` of the Nearblocks API.
+fn parse_first_transaction(response: &serde_json::Value) -> Option<AccountCreation> {
+    let as_u64 = |value: &serde_json::Value| match value {
+        serde_json::Value::Number(number) => number.as_u64(),
+        serde_json::Value::String(string) => string.parse().ok(),
+        _ => None,
+    };
+    let transaction = response.get("txns")?.get(0)?;
+    Some(AccountCreation {
+        block_height: as_u64(transaction.get("block")?.get("block_height")?)?,
+        timestamp_nanosec: as_u64(transaction.get("block_timestamp")?)?,
+        transaction_hash: transaction
+            .get("transaction_hash")
+            .and_then(serde_json::Value::as_str)
+            .map(str::to_string),
+        existed_at_first_block: false,
+    })
+}
+
+/// The first block at which the account exists, found with a binary search between the first
+/// available block and the latest one (an account deleted and created again in between may be
+/// found at either creation).
+#[tracing::instrument(name = "Searching for the block of the account creation ...", skip_all)]
+fn find_creation_block(
+    network_config: &crate::config::NetworkConfig,
+    account_id: &near_primitives::types::AccountId,
+) -> color_eyre::eyre::Result<AccountCreation> {
+    let json_rpc_client = network_config.json_rpc_client();
+    let genesis_height = json_rpc_client
+        .blocking_call(
+            near_jsonrpc_client::methods::EXPERIMENTAL_protocol_config::RpcProtocolConfigRequest {
+                block_reference: near_primitives::types::Finality::Final.into(),
+            },
+        )
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch the protocol config of network <{}>",
+                network_config.network_name
+            )
+        })?
+        .genesis_height;
+    let sync_info = json_rpc_client
+        .blocking_call(near_jsonrpc_client::methods::status::RpcStatusRequest)
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch the status of the RPC node of network <{}>",
+                network_config.network_name
+            )
+        })?
+        .sync_info;
+    let first_height = sync_info
+        .earliest_block_height
+        .unwrap_or(genesis_height)
+        .max(genesis_height);
+
+    let first = get_balance_sample(
+        network_config,
+        account_id,
+        first_height,
+        first_height + MAX_SKIPPED_HEIGHTS,
+    )?
+    .wrap_err_with(|| {
+        format!(
+            "There is no block from #{first_height} on the RPC node, the archival RPC is required"
+        )
+    })?;
+    let latest = get_balance_sample(
+        network_config,
+        account_id,
+        sync_info.latest_block_height,
+        sync_info.latest_block_height,
+    )?
+    .wrap_err("Failed to fetch the latest block")?;
+    let (creation, existed_at_first_block) = match (first.balance, latest.balance) {
+        (_, None) => color_eyre::eyre::bail!(
+            "The account <{account_id}> does not exist on network <{}>",
+            network_config.network_name
+        ),
+        (Some(_), Some(_)) => (first, true),
+        (None, Some(_)) => (
+            bisect_change(first, latest, |height, last_height| {
+                get_balance_sample(network_config, account_id, height, last_height)
+            })?,
+            false,
+        ),
+    };
+    let BalanceSample {
+        height,
+        timestamp_nanosec,
+        ..
+    } = creation;
+    Ok(AccountCreation {
+        block_height: height,
+        timestamp_nanosec,
+        transaction_hash: None,
+        existed_at_first_block,
+    })
+}
+
+fn now_nanosec() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |duration| {
+            u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+        })
+}
+
+fn age_in_days(timestamp_nanosec: u64, now_nanosec: u64) -> u64 {
+    now_nanosec.saturating_sub(timestamp_nanosec) / NANOSECONDS_PER_DAY
+}
+
+fn display_account_age(
+    account_id: &near_primitives::types::AccountId,
+    creation: &AccountCreation,
+    now_nanosec: u64,
+) {
+    let date = chrono::DateTime::from_timestamp_nanos(
+        i64::try_from(creation.timestamp_nanosec).unwrap_or(i64::MAX),
+    )
+    .format("%Y-%m-%d %H:%M:%S UTC");
+    let age = age_in_days(creation.timestamp_nanosec, now_nanosec);
+    if creation.existed_at_first_block {
+        println!(
+            "<{account_id}> already existed at block #{} ({date}), the first block available on the RPC node: it is at least {age} days old",
+            creation.block_height
+        );
+        eprintln!("Use an archival RPC or an indexer to find the exact creation block.");
+    } else {
+        println!(
+            "<{account_id}> was created at block #{} ({date}), {age} days ago",
+            creation.block_height
+        );
+    }
+    if let Some(transaction_hash) = &creation.transaction_hash {
+        println!("First transaction: {transaction_hash}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_transaction_of_the_indexer() {
+        let response = serde_json::json!({
+            "cursor": "10",
+            "txns": [{
+                "transaction_hash": "9abQ1cXh7xLbJhXZ4y5y9Vt5wJi7nJDGYUS2yuqt7Lqs",
+                "block_timestamp": "1617264000000000000",
+                "block": { "block_height": 32_000_000 }
+            }]
+        });
+        assert_eq!(
+            parse_first_transaction(&response),
+            Some(AccountCreation {
+                block_height: 32_000_000,
+                timestamp_nanosec: 1_617_264_000_000_000_000,
+                transaction_hash: Some("9abQ1cXh7xLbJhXZ4y5y9Vt5wJi7nJDGYUS2yuqt7Lqs".to_string()),
+                existed_at_first_block: false,
+            })
+        );
+        assert_eq!(
+            parse_first_transaction(&serde_json::json!({ "txns": [] })),
+            None
+        );
+    }
+
+    #[test]
+    fn age_is_in_whole_days() {
+        let created = 1_617_264_000_000_000_000;
+        assert_eq!(
+            age_in_days(created, created + 3 * NANOSECONDS_PER_DAY - 1),
+            2
+        );
+        assert_eq!(age_in_days(created, created + 3 * NANOSECONDS_PER_DAY), 3);
+        assert_eq!(age_in_days(created, created - 1), 0);
+    }
+}
@@ -0,0 +1,237 @@
+use color_eyre::eyre::{ContextCompat, WrapErr};
+
+use crate::common::{CallResultExt, RpcQueryResponseExt};
+use crate::config::KnownContractKind;
+
+/// Code hashes of the widely deployed SputnikDAO contracts. The multisig contracts are built
+/// from source by each deployment, so there is no builtin hash for them: their code hashes are
+/// added with `known_contracts` in config.toml.
+const BUILTIN_KNOWN_CONTRACTS: &[(&str, KnownContractKind)] = &[
+    // SputnikDAO v2
+    (
+        "8RMeZ5cXDap6TENxaJKtigRYf3n139iHmTRe8ZUNey6N",
+        KnownContractKind::SputnikDao,
+    ),
+    // SputnikDAO v3
+    (
+        "783vth3Fg8MBBGGFmRqrytQCWBpYzUcmHoCq4Mo8QqF5",
+        KnownContractKind::SputnikDao,
+    ),
+];
+
+/// The enrichment is optional, so it must never hold up the account summary for long.
+const ENRICHMENT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Only the most recent proposals are checked for being active.
+const DAO_PROPOSALS_LIMIT: u64 = 100;
+
+pub fn known_contract_kind(
+    code_hash: &near_primitives::hash::CryptoHash,
+    known_contracts: &std::collections::BTreeMap<
+        near_primitives::hash::CryptoHash,
+        KnownContractKind,
+    >,
+) -> Option<KnownContractKind> {
+    known_contracts.get(code_hash).copied().or_else(|| {
+        BUILTIN_KNOWN_CONTRACTS
+            .iter()
+            .find(|(builtin_code_hash, _)| *builtin_code_hash == code_hash.to_string())
+            .map(|(_, kind)| *kind)
+    })
+}
+
+#[tracing::instrument(name = "Getting details of the known contract ...", skip_all)]
+pub fn get_known_contract_section(
+    json_rpc_client: &near_jsonrpc_client::JsonRpcClient,
+    contract_account_id: &near_primitives::types::AccountId,
+    kind: KnownContractKind,
+    block_reference: &near_primitives::types::BlockReference,
+) -> color_eyre::eyre::Result<crate::common::KnownContractSection> {
+    let runtime = crate::common::multi_thread_runtime()?;
+    let contract = KnownContract {
+        json_rpc_client,
+        contract_account_id,
+        block_reference,
+    };
+    let (title, rows) = runtime.block_on(async {
+        match kind {
+            KnownContractKind::Multisig => ("Multisig contract", contract.multisig_rows().await),
+            KnownContractKind::SputnikDao => ("SputnikDAO contract", contract.dao_rows().await),
+        }
+    });
+    Ok(crate::common::KnownContractSection {
+        title: title.to_string(),
+        rows,
+    })
+}
+
+struct KnownContract<'a> {
+    json_rpc_client: &'a near_jsonrpc_client::JsonRpcClient,
+    contract_account_id: &'a near_primitives::types::AccountId,
+    block_reference: &'a near_primitives::types::BlockReference,
+}
+
+impl KnownContract<'_> {
+    async fn call_view_function<T: serde::de::DeserializeOwned>(
+        &self,
+        method_name: &str,
+        args: serde_json::Value,
+    ) -> color_eyre::eyre::Result<T> {
+        let call_result = tokio::time::timeout(
+            ENRICHMENT_TIMEOUT,
+            crate::rpc_rate_limit::call(
+                self.json_rpc_client,
+                near_jsonrpc_client::methods::query::RpcQueryRequest {
+                    block_reference: self.block_reference.clone(),
+                    request: near_primitives::views::QueryRequest::CallFunction {
+                        account_id: self.contract_account_id.clone(),
+                        method_name: method_name.to_string(),
+                        args: near_primitives::types::FunctionArgs::from(serde_json::to_vec(
+                            &args,
+                        )?),
+                    },
+                },
+            ),
+        )
+        .await
+        .map_err(|_| color_eyre::eyre::eyre!("Timed out after {ENRICHMENT_TIMEOUT:?}"))?
+        .wrap_err_with(|| format!("Failed to fetch query for view method: '{method_name}'"))?
+        .call_result()?;
+        call_result.parse_result_from_json()
+    }
+
+    async fn multisig_rows(&self) -> Vec<(String, color_eyre::eyre::Result<String>)> {
+        let (num_confirmations, request_ids) = futures::join!(
+            self.call_view_function::<u32>("get_num_confirmations", serde_json::json!({})),
+            self.call_view_function::<Vec<u32>>("list_request_ids", serde_json::json!({})),
+        );
+        vec![
+            (
+                "Confirmations required".to_string(),
+                num_confirmations.map(|num_confirmations| num_confirmations.to_string()),
+            ),
+            (
+                "Pending requests".to_string(),
+                request_ids.map(|request_ids| request_ids.len().to_string()),
+            ),
+        ]
+    }
+
+    async fn dao_rows(&self) -> Vec<(String, color_eyre::eyre::Result<String>)> {
+        let (policy, active_proposals) = futures::join!(
+            self.call_view_function::<serde_json::Value>("get_policy", serde_json::json!({})),
+            self.dao_active_proposals(),
+        );
+        vec![
+            (
+                "Council members".to_string(),
+                policy.and_then(|policy| {
+                    let council_members = council_members(&policy)?;
+                    Ok(if council_members.is_empty() {
+                        "No council members".to_string()
+                    } else {
+                        council_members.join(", ")
+                    })
+                }),
+            ),
+            ("Active proposals".to_string(), active_proposals),
+        ]
+    }
+
+    async fn dao_active_proposals(&self) -> color_eyre::eyre::Result<String> {
+        let last_proposal_id: u64 = self
+            .call_view_function("get_last_proposal_id", serde_json::json!({}))
+            .await?;
+        let from_index = last_proposal_id.saturating_sub(DAO_PROPOSALS_LIMIT);
+        let proposals: Vec<serde_json::Value> = self
+            .call_view_function(
+                "get_proposals",
+                serde_json::json!({ "from_index": from_index, "limit": DAO_PROPOSALS_LIMIT }),
+            )
+            .await?;
+        let active_proposals_count = count_active_proposals(&proposals);
+        Ok(if from_index > 0 {
+            format!("{active_proposals_count} (among the last {DAO_PROPOSALS_LIMIT} proposals)")
+        } else {
+            active_proposals_count.to_string()
+        })
+    }
+}
+
+/// The council of a SputnikDAO is the group role named "council" in its policy.
+fn council_members(policy: &serde_json::Value) -> color_eyre::eyre::Result<Vec<String>> {
+    let roles = policy["roles"]
+        .as_array()
+        .wrap_err("The DAO policy has no roles")?;
+    Ok(roles
+        .iter()
+        .filter(|role| role["name"].as_str() == Some("council"))
+        .filter_map(|role| role["kind"]["Group"].as_array())
+        .flatten()
+        .filter_map(|member| member.as_str().map(str::to_string))
+        .collect())
+}
+
+fn count_active_proposals(proposals: &[serde_json::Value]) -> usize {
+    proposals
+        .iter()
+        .filter(|proposal| proposal["status"].as_str() == Some("InProgress"))
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_contract_kind_prefers_config_overrides() {
+        let sputnik_dao_v2_code_hash: near_primitives::hash::CryptoHash =
+            BUILTIN_KNOWN_CONTRACTS[0].0.parse().unwrap();
+        // Not a real multisig deployment, any code hash that is not builtin will do
+        let multisig_code_hash = near_primitives::hash::CryptoHash::hash_bytes(b"custom contract");
+        let mut known_contracts = std::collections::BTreeMap::new();
+        assert_eq!(
+            known_contract_kind(&sputnik_dao_v2_code_hash, &known_contracts),
+            Some(KnownContractKind::SputnikDao)
+        );
+        assert_eq!(
+            known_contract_kind(&multisig_code_hash, &known_contracts),
+            None
+        );
+
+        known_contracts.insert(multisig_code_hash, KnownContractKind::Multisig);
+        known_contracts.insert(sputnik_dao_v2_code_hash, KnownContractKind::Multisig);
+        assert_eq!(
+            known_contract_kind(&multisig_code_hash, &known_contracts),
+            Some(KnownContractKind::Multisig)
+        );
+        assert_eq!(
+            known_contract_kind(&sputnik_dao_v2_code_hash, &known_contracts),
+            Some(KnownContractKind::Multisig)
+        );
+    }
+
+    #[test]
+    fn dao_council_members_and_active_proposals() {
+        let policy = serde_json::json!({
+            "roles": [
+                { "name": "all", "kind": "Everyone" },
+                { "name": "council", "kind": { "Group": ["alice.near", "bob.near"] } },
+                { "name": "community", "kind": { "Group": ["carol.near"] } },
+            ]
+        });
+        assert_eq!(
+            council_members(&policy).unwrap(),
+            vec!["alice.near", "bob.near"]
+        );
+        assert!(council_members(&serde_json::json!({})).is_err());
+
+        let proposals = [
+            serde_json::json!({ "id": 0, "status": "Approved" }),
+            serde_json::json!({ "id": 1, "status": "InProgress" }),
+            serde_json::json!({ "id": 2, "status": "Expired" }),
+            serde_json::json!({ "id": 3, "status": "InProgress" }),
+        ];
+        assert_eq!(count_active_proposals(&proposals), 2);
+    }
+}
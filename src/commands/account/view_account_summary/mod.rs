@@ -4,6 +4,8 @@ use tracing_indicatif::span_ext::IndicatifSpanExt;
 
 use crate::common::{CallResultExt, JsonRpcClientExt, RpcQueryResponseExt};
 
+mod known_contracts;
+
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = crate::GlobalContext)]
 #[interactive_clap(output_context = ViewAccountSummaryContext)]
@@ -26,9 +28,10 @@ impl ViewAccountSummaryContext {
     ) -> color_eyre::eyre::Result<Self> {
         let on_after_getting_block_reference_callback: crate::network_view_at_block::OnAfterGettingBlockReferenceCallback = std::sync::Arc::new({
             let account_id: near_primitives::types::AccountId = scope.account_id.clone().into();
+            let known_contracts = previous_context.config.known_contracts.clone();
 
             move |network_config, block_reference| {
-                get_account_inquiry(&account_id, network_config, block_reference)
+                get_account_inquiry(&account_id, network_config, block_reference, &known_contracts)
             }
         });
         Ok(Self(crate::network_view_at_block::ArgsForViewContext {
@@ -61,6 +64,10 @@ fn get_account_inquiry(
     account_id: &near_primitives::types::AccountId,
     network_config: &crate::config::NetworkConfig,
     block_reference: &near_primitives::types::BlockReference,
+    known_contracts: &std::collections::BTreeMap<
+        near_primitives::hash::CryptoHash,
+        crate::config::KnownContractKind,
+    >,
 ) -> crate::CliResult {
     let json_rpc_client = network_config.json_rpc_client();
 
@@ -105,8 +112,15 @@ fn get_account_inquiry(
             .fastnear_url
             .as_ref()
             .and_then(|fastnear_url| {
-                crate::common::fetch_historically_delegated_staking_pools(fastnear_url, account_id)
-                    .ok()
+                crate::common::fetch_historically_delegated_staking_pools(
+                    &crate::config::config_url::ConfigUrl::new(
+                        network_config,
+                        "fastnear_url",
+                        fastnear_url,
+                    ),
+                    account_id,
+                )
+                .ok()
             });
     let validators = if let Some(validators) = historically_delegated_validators {
         Ok(validators)
@@ -121,9 +135,7 @@ fn get_account_inquiry(
         Ok(Default::default())
     };
 
-    let runtime = tokio::runtime::Builder::new_multi_thread()
-        .enable_all()
-        .build()?;
+    let runtime = crate::common::multi_thread_runtime()?;
     let concurrency = 10;
     let delegated_stake: color_eyre::Result<
         std::collections::BTreeMap<near_primitives::types::AccountId, near_token::NearToken>,
@@ -157,6 +169,26 @@ fn get_account_inquiry(
         .ok()
         .flatten();
 
+    let known_contract_section =
+        known_contracts::known_contract_kind(&account_view.code_hash, known_contracts).and_then(
+            |kind| {
+                known_contracts::get_known_contract_section(
+                    &json_rpc_client,
+                    account_id,
+                    kind,
+                    block_reference,
+                )
+                .map_err(|err| {
+                    tracing::warn!(
+                        "Failed to get details of the contract deployed on <{}>: {:#}",
+                        account_id,
+                        err
+                    );
+                })
+                .ok()
+            },
+        );
+
     crate::common::display_account_info(
         &rpc_query_response.block_hash,
         &rpc_query_response.block_height,
@@ -165,6 +197,7 @@ fn get_account_inquiry(
         &account_view,
         access_key_list.as_ref(),
         optional_account_profile.as_ref(),
+        known_contract_section.as_ref(),
     );
 
     Ok(())
@@ -181,8 +214,9 @@ async fn get_delegated_staked_balance(
     account_id: &near_primitives::types::AccountId,
 ) -> color_eyre::eyre::Result<near_token::NearToken> {
     tracing::Span::current().pb_set_message(staking_pool_account_id.as_str());
-    let account_staked_balance_response = json_rpc_client
-        .call(near_jsonrpc_client::methods::query::RpcQueryRequest {
+    let account_staked_balance_response = crate::rpc_rate_limit::call(
+        json_rpc_client,
+        near_jsonrpc_client::methods::query::RpcQueryRequest {
             block_reference: block_reference.clone(),
             request: near_primitives::views::QueryRequest::CallFunction {
                 account_id: staking_pool_account_id.clone(),
@@ -193,8 +227,9 @@ async fn get_delegated_staked_balance(
                     }),
                 )?),
             },
-        })
-        .await;
+        },
+    )
+    .await;
     match account_staked_balance_response {
         Ok(response) => Ok(near_token::NearToken::from_yoctonear(
             response
@@ -221,30 +256,21 @@ fn get_account_profile(
     network_config: &crate::config::NetworkConfig,
     block_reference: &near_primitives::types::BlockReference,
 ) -> color_eyre::Result<Option<near_socialdb_client::types::socialdb_types::AccountProfile>> {
-    if let Ok(contract_account_id) = network_config.get_near_social_account_id_from_network() {
-        let mut social_db = network_config
-            .json_rpc_client()
-            .blocking_call_view_function(
-                &contract_account_id,
-                "get",
-                serde_json::to_vec(&serde_json::json!({
-                    "keys": vec![format!("{account_id}/profile/**")],
-                }))?,
-                block_reference.clone(),
-            )
-            .wrap_err_with(|| {
-                format!("Failed to fetch query for view method: 'get {account_id}/profile/**' (contract <{}> on network <{}>)",
-                    contract_account_id,
-                    network_config.network_name
-                )
-            })?
-            .parse_result_from_json::<near_socialdb_client::types::socialdb_types::SocialDb>()
-            .wrap_err_with(|| {
-                format!("Failed to parse view function call return value for {account_id}/profile.")
-            })?;
-
-        Ok(social_db.accounts.remove(account_id))
-    } else {
-        Ok(None)
+    if network_config
+        .get_near_social_account_id_from_network()
+        .is_err()
+    {
+        return Ok(None);
     }
+    let data = crate::view_pagination::get_social_data(
+        network_config,
+        &[format!("{account_id}/profile/**")],
+        block_reference,
+        None,
+    )?;
+    let mut social_db: near_socialdb_client::types::socialdb_types::SocialDb =
+        serde_json::from_value(data).wrap_err_with(|| {
+            format!("Failed to parse view function call return value for {account_id}/profile.")
+        })?;
+    Ok(social_db.accounts.remove(account_id))
 }
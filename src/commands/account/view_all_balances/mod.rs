@@ -0,0 +1,244 @@
+use color_eyre::eyre::Context;
+use prettytable::Table;
+
+use crate::common::{CallResultExt, JsonRpcClientExt, RpcQueryResponseExt};
+
+const MAINNET_POPULAR_FT_CONTRACTS: &[&str] = &[
+    "wrap.near",
+    "usdt.tether-token.near",
+    "17208628f84f5d6ad33f0da3bbbeb27ffcb398eac501a31bd6ad2011e36133a1",
+    "meta-pool.near",
+    "linear-protocol.near",
+    "token.sweat",
+    "aurora",
+    "token.v2.ref-finance.near",
+];
+
+const TESTNET_POPULAR_FT_CONTRACTS: &[&str] = &["wrap.testnet", "usdc.fakes.testnet"];
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = ViewAllBalancesContext)]
+pub struct ViewAllBalances {
+    #[interactive_clap(skip_default_input_arg)]
+    /// What Account ID do you need to view?
+    account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Path to a file with the list of FT contracts (one account ID per line)
+    token_list_file: Option<crate::types::path_buf::PathBuf>,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network_view_at_block::NetworkViewAtBlockArgs,
+}
+
+#[derive(Clone)]
+pub struct ViewAllBalancesContext(crate::network_view_at_block::ArgsForViewContext);
+
+impl ViewAllBalancesContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<ViewAllBalances as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let token_list = scope
+            .token_list_file
+            .as_ref()
+            .map(|token_list_file| {
+                let token_list_file_content = std::fs::read_to_string(token_list_file)
+                    .wrap_err_with(|| format!("Failed to read the file: {token_list_file}"))?;
+                parse_token_list_file(&token_list_file_content)
+            })
+            .transpose()?;
+
+        let on_after_getting_block_reference_callback: crate::network_view_at_block::OnAfterGettingBlockReferenceCallback = std::sync::Arc::new({
+            let account_id: near_primitives::types::AccountId = scope.account_id.clone().into();
+
+            move |network_config, block_reference| {
+                let ft_contracts = match &token_list {
+                    Some(token_list) => token_list.clone(),
+                    None => get_popular_ft_contracts(&network_config.network_name)?,
+                };
+
+                let account_view = network_config
+                    .json_rpc_client()
+                    .blocking_call_view_account(&account_id, block_reference.clone())
+                    .wrap_err_with(|| {
+                        format!(
+                            "Failed to fetch query ViewAccount for account <{}> on network <{}>",
+                            account_id, network_config.network_name
+                        )
+                    })?
+                    .account_view()?;
+                let ft_balances = get_ft_balances(network_config, &account_id, &ft_contracts, block_reference)?;
+
+                display_all_balances(&account_id, &account_view, &ft_balances);
+                Ok(())
+            }
+        });
+        Ok(Self(crate::network_view_at_block::ArgsForViewContext {
+            config: previous_context.config,
+            interacting_with_account_ids: vec![scope.account_id.clone().into()],
+            on_after_getting_block_reference_callback,
+        }))
+    }
+}
+
+impl From<ViewAllBalancesContext> for crate::network_view_at_block::ArgsForViewContext {
+    fn from(item: ViewAllBalancesContext) -> Self {
+        item.0
+    }
+}
+
+impl ViewAllBalances {
+    pub fn input_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_non_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "What Account ID do you need to view?",
+        )
+    }
+}
+
+/// FT contracts are listed one per line; empty lines and lines starting with `#` are ignored.
+fn parse_token_list_file(
+    content: &str,
+) -> color_eyre::eyre::Result<Vec<near_primitives::types::AccountId>> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            line.parse()
+                .wrap_err_with(|| format!("Invalid FT contract account ID: {line}"))
+        })
+        .collect()
+}
+
+pub(super) fn get_popular_ft_contracts(
+    network_name: &str,
+) -> color_eyre::eyre::Result<Vec<near_primitives::types::AccountId>> {
+    let ft_contracts = match network_name {
+        "mainnet" => MAINNET_POPULAR_FT_CONTRACTS,
+        "testnet" => TESTNET_POPULAR_FT_CONTRACTS,
+        _ => color_eyre::eyre::bail!(
+            "There is no built-in list of FT contracts for the network <{network_name}>. Use --token-list-file to provide one."
+        ),
+    };
+    Ok(ft_contracts
+        .iter()
+        .map(|ft_contract| {
+            ft_contract
+                .parse()
+                .expect("Built-in FT contract IDs are valid")
+        })
+        .collect())
+}
+
+#[tracing::instrument(name = "Getting FT balances ...", skip_all)]
+fn get_ft_balances(
+    network_config: &crate::config::NetworkConfig,
+    account_id: &near_primitives::types::AccountId,
+    ft_contracts: &[near_primitives::types::AccountId],
+    block_reference: &near_primitives::types::BlockReference,
+) -> color_eyre::eyre::Result<
+    Vec<(
+        near_primitives::types::AccountId,
+        color_eyre::eyre::Result<crate::types::ft_properties::FungibleToken>,
+    )>,
+> {
+    let concurrency = 10;
+    let json_rpc_client = network_config.json_rpc_client();
+    crate::common::run_concurrently(ft_contracts, concurrency, |ft_contract_account_id| {
+        let json_rpc_client = &json_rpc_client;
+        async move {
+            let ft_balance = get_ft_balance(
+                json_rpc_client,
+                ft_contract_account_id,
+                account_id,
+                block_reference,
+            )
+            .await;
+            (ft_contract_account_id.clone(), ft_balance)
+        }
+    })
+}
+
+pub(super) async fn get_ft_balance(
+    json_rpc_client: &near_jsonrpc_client::JsonRpcClient,
+    ft_contract_account_id: &near_primitives::types::AccountId,
+    account_id: &near_primitives::types::AccountId,
+    block_reference: &near_primitives::types::BlockReference,
+) -> color_eyre::eyre::Result<crate::types::ft_properties::FungibleToken> {
+    let call_view_function = |method_name: &str, args: serde_json::Value| {
+        let method_name = method_name.to_string();
+        async move {
+            crate::rpc_rate_limit::call(
+                json_rpc_client,
+                near_jsonrpc_client::methods::query::RpcQueryRequest {
+                    block_reference: block_reference.clone(),
+                    request: near_primitives::views::QueryRequest::CallFunction {
+                        account_id: ft_contract_account_id.clone(),
+                        method_name: method_name.clone(),
+                        args: near_primitives::types::FunctionArgs::from(serde_json::to_vec(
+                            &args,
+                        )?),
+                    },
+                },
+            )
+            .await
+            .wrap_err_with(|| format!("Failed to fetch query for view method: '{method_name}'"))?
+            .call_result()
+        }
+    };
+
+    let (ft_metadata, ft_balance) = futures::join!(
+        call_view_function("ft_metadata", serde_json::json!({})),
+        call_view_function(
+            "ft_balance_of",
+            serde_json::json!({ "account_id": account_id })
+        ),
+    );
+    let crate::types::ft_properties::FtMetadata { decimals, symbol } =
+        ft_metadata?.parse_result_from_json()?;
+    let amount: String = ft_balance?.parse_result_from_json()?;
+    Ok(crate::types::ft_properties::FungibleToken::from_params_ft(
+        amount.parse::<u128>()?,
+        decimals,
+        symbol,
+    ))
+}
+
+fn display_all_balances(
+    account_id: &near_primitives::types::AccountId,
+    account_view: &near_primitives::views::AccountView,
+    ft_balances: &[(
+        near_primitives::types::AccountId,
+        color_eyre::eyre::Result<crate::types::ft_properties::FungibleToken>,
+    )],
+) {
+    let mut table = Table::new();
+    table.set_titles(prettytable::row![Fg=>"Token", "Contract", "Balance"]);
+    table.add_row(prettytable::row![
+        "NEAR",
+        "",
+        near_token::NearToken::from_yoctonear(account_view.amount)
+    ]);
+    for (ft_contract_account_id, ft_balance) in ft_balances {
+        match ft_balance {
+            Ok(ft_balance) => table.add_row(prettytable::row![
+                ft_balance.symbol(),
+                ft_contract_account_id,
+                ft_balance
+            ]),
+            Err(err) => table.add_row(prettytable::row![
+                "",
+                ft_contract_account_id,
+                Fr->format!("{err:#}")
+            ]),
+        };
+    }
+    table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    eprintln!("\nBalances of <{account_id}>:");
+    table.printstd();
+}
@@ -0,0 +1,377 @@
+use color_eyre::eyre::WrapErr;
+use prettytable::Table;
+
+use crate::common::{JsonRpcClientExt, RpcQueryResponseExt};
+
+const DEFAULT_SAMPLES: u64 = 20;
+const SPARKLINE_CHARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = ViewBalanceHistoryContext)]
+pub struct ViewBalanceHistory {
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_default_input_arg)]
+    /// What Account ID do you need to view the balance history of?
+    account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    /// What is the height of the first block?
+    from_block: crate::types::block_height::BlockHeight,
+    #[interactive_clap(long)]
+    /// What is the height of the last block?
+    to_block: crate::types::block_height::BlockHeight,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// How many evenly spaced blocks of the range are viewed (default: 20), each costs two RPC calls
+    samples: Option<u64>,
+    #[interactive_clap(long)]
+    /// Find the exact block of a balance change between the samples with a binary search (more RPC calls)
+    locate_changes: bool,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network::Network,
+}
+
+#[derive(Clone)]
+pub struct ViewBalanceHistoryContext(crate::network::NetworkContext);
+
+impl ViewBalanceHistoryContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<ViewBalanceHistory as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        if scope.from_block > scope.to_block {
+            return Err(color_eyre::eyre::eyre!(
+                "The first block height ({}) must not be greater than the last one ({})",
+                scope.from_block,
+                scope.to_block
+            ));
+        }
+        let samples = match scope.samples.unwrap_or(DEFAULT_SAMPLES) {
+            0 => {
+                return Err(color_eyre::eyre::eyre!(
+                    "The number of samples must be at least 1"
+                ))
+            }
+            samples => samples,
+        };
+
+        let on_after_getting_network_callback: crate::network::OnAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let account_id: near_primitives::types::AccountId = scope.account_id.clone().into();
+                let heights = sample_heights(scope.from_block.0, scope.to_block.0, samples);
+                let to_block = scope.to_block.0;
+                let locate_changes = scope.locate_changes;
+
+                move |network_config| {
+                    let mut history =
+                        get_balance_history(network_config, &account_id, &heights, to_block)?;
+                    if locate_changes {
+                        history = locate_balance_changes(network_config, &account_id, history)?;
+                    }
+                    display_balance_history(&account_id, &history);
+                    Ok(())
+                }
+            });
+
+        Ok(Self(crate::network::NetworkContext {
+            config: previous_context.config,
+            interacting_with_account_ids: vec![scope.account_id.clone().into()],
+            on_after_getting_network_callback,
+        }))
+    }
+}
+
+impl From<ViewBalanceHistoryContext> for crate::network::NetworkContext {
+    fn from(item: ViewBalanceHistoryContext) -> Self {
+        item.0
+    }
+}
+
+impl ViewBalanceHistory {
+    pub fn input_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_non_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "What Account ID do you need to view the balance history of?",
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct BalanceSample {
+    pub height: near_primitives::types::BlockHeight,
+    pub timestamp_nanosec: u64,
+    /// `None` if the account did not exist at the block
+    pub balance: Option<near_primitives::types::Balance>,
+}
+
+/// `samples` heights spread evenly over the range, with its first and last blocks.
+fn sample_heights(
+    from_block: near_primitives::types::BlockHeight,
+    to_block: near_primitives::types::BlockHeight,
+    samples: u64,
+) -> Vec<near_primitives::types::BlockHeight> {
+    if samples == 1 {
+        return vec![to_block];
+    }
+    let range = u128::from(to_block - from_block);
+    let mut heights: Vec<_> = (0..samples)
+        .map(|index| {
+            from_block
+                + u64::try_from(range * u128::from(index) / u128::from(samples - 1))
+                    .unwrap_or_default()
+        })
+        .collect();
+    heights.dedup();
+    heights
+}
+
+#[tracing::instrument(name = "Getting the balance history ...", skip_all)]
+fn get_balance_history(
+    network_config: &crate::config::NetworkConfig,
+    account_id: &near_primitives::types::AccountId,
+    heights: &[near_primitives::types::BlockHeight],
+    to_block: near_primitives::types::BlockHeight,
+) -> color_eyre::eyre::Result<Vec<BalanceSample>> {
+    let mut history: Vec<BalanceSample> = Vec::new();
+    for &height in heights {
+        let Some(sample) = get_balance_sample(network_config, account_id, height, to_block)? else {
+            continue;
+        };
+        // Several sample heights without a block may lead to the same next block
+        if history.last().map(|last| last.height) != Some(sample.height) {
+            history.push(sample);
+        }
+    }
+    Ok(history)
+}
+
+/// The balance at the first block from `height` to `last_height` (the chain skips some heights),
+/// `None` if there is no block.
+pub(super) fn get_balance_sample(
+    network_config: &crate::config::NetworkConfig,
+    account_id: &near_primitives::types::AccountId,
+    height: near_primitives::types::BlockHeight,
+    last_height: near_primitives::types::BlockHeight,
+) -> color_eyre::eyre::Result<Option<BalanceSample>> {
+    let json_rpc_client = network_config.json_rpc_client();
+    for height in height..=last_height {
+        let block = match json_rpc_client.blocking_call(
+            near_jsonrpc_client::methods::block::RpcBlockRequest {
+                block_reference: near_primitives::types::BlockReference::BlockId(
+                    near_primitives::types::BlockId::Height(height),
+                ),
+            },
+        ) {
+            Ok(block) => block,
+            Err(near_jsonrpc_client::errors::JsonRpcError::ServerError(
+                near_jsonrpc_client::errors::JsonRpcServerError::HandlerError(
+                    near_jsonrpc_primitives::types::blocks::RpcBlockError::UnknownBlock { .. },
+                ),
+            )) => continue,
+            Err(err) => {
+                return Err(err).wrap_err_with(|| {
+                    crate::common::old_block_request_error(
+                        format_args!("block #{height}"),
+                        network_config,
+                    )
+                })
+            }
+        };
+        let balance = match json_rpc_client.blocking_call_view_account(
+            account_id,
+            near_primitives::types::BlockReference::BlockId(near_primitives::types::BlockId::Hash(
+                block.header.hash,
+            )),
+        ) {
+            Ok(rpc_query_response) => Some(rpc_query_response.account_view()?.amount),
+            Err(near_jsonrpc_client::errors::JsonRpcError::ServerError(
+                near_jsonrpc_client::errors::JsonRpcServerError::HandlerError(
+                    near_jsonrpc_primitives::types::query::RpcQueryError::UnknownAccount { .. },
+                ),
+            )) => None,
+            Err(err) => {
+                return Err(err).wrap_err_with(|| {
+                    crate::common::old_block_request_error(
+                        format_args!(
+                            "query ViewAccount for <{account_id}> at block #{}",
+                            block.header.height
+                        ),
+                        network_config,
+                    )
+                })
+            }
+        };
+        return Ok(Some(BalanceSample {
+            height: block.header.height,
+            timestamp_nanosec: block.header.timestamp_nanosec,
+            balance,
+        }));
+    }
+    Ok(None)
+}
+
+/// Adds the block of a balance change between each two samples with different balances, found
+/// with a binary search (one change per interval, the first one for a single change).
+#[tracing::instrument(name = "Locating the balance changes ...", skip_all)]
+fn locate_balance_changes(
+    network_config: &crate::config::NetworkConfig,
+    account_id: &near_primitives::types::AccountId,
+    history: Vec<BalanceSample>,
+) -> color_eyre::eyre::Result<Vec<BalanceSample>> {
+    let mut located_history = Vec::with_capacity(history.len());
+    for (index, &sample) in history.iter().enumerate() {
+        if let Some(&previous) = index.checked_sub(1).and_then(|index| history.get(index)) {
+            if previous.balance != sample.balance {
+                let change = bisect_change(previous, sample, |height, last_height| {
+                    get_balance_sample(network_config, account_id, height, last_height)
+                })?;
+                if change.height != sample.height {
+                    located_history.push(change);
+                }
+            }
+        }
+        located_history.push(sample);
+    }
+    Ok(located_history)
+}
+
+/// The first block after `before` with a balance other than the balance of `before`, if the
+/// balance changed once up to `after`. `get_sample(height, last_height)` returns the first block
+/// from `height` to `last_height`.
+pub(super) fn bisect_change(
+    mut before: BalanceSample,
+    mut after: BalanceSample,
+    mut get_sample: impl FnMut(
+        near_primitives::types::BlockHeight,
+        near_primitives::types::BlockHeight,
+    ) -> color_eyre::eyre::Result<Option<BalanceSample>>,
+) -> color_eyre::eyre::Result<BalanceSample> {
+    // The blocks from `upper` to `after` (excluded) are known to be missing
+    let mut upper = after.height;
+    while upper - before.height > 1 {
+        let middle = before.height + (upper - before.height) / 2;
+        match get_sample(middle, upper - 1)? {
+            None => upper = middle,
+            Some(sample) if sample.balance == before.balance => before = sample,
+            Some(sample) => {
+                upper = sample.height;
+                after = sample;
+            }
+        }
+    }
+    Ok(after)
+}
+
+fn sparkline(history: &[BalanceSample]) -> String {
+    let balances = history.iter().filter_map(|sample| sample.balance);
+    let (Some(min), Some(max)) = (balances.clone().min(), balances.max()) else {
+        return String::new();
+    };
+    history
+        .iter()
+        .map(|sample| match sample.balance {
+            None => ' ',
+            Some(_) if max == min => SPARKLINE_CHARS[SPARKLINE_CHARS.len() / 2],
+            Some(balance) => {
+                let level = (balance - min) * (SPARKLINE_CHARS.len() as u128 - 1) / (max - min);
+                SPARKLINE_CHARS[usize::try_from(level).unwrap_or_default()]
+            }
+        })
+        .collect()
+}
+
+fn display_balance_history(
+    account_id: &near_primitives::types::AccountId,
+    history: &[BalanceSample],
+) {
+    let mut table = Table::new();
+    table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    table.set_titles(prettytable::row![Fg=>"Block height", "Timestamp (UTC)", "Balance"]);
+    for sample in history {
+        let timestamp = chrono::DateTime::from_timestamp_nanos(
+            i64::try_from(sample.timestamp_nanosec).unwrap_or(i64::MAX),
+        )
+        .format("%Y-%m-%d %H:%M:%S");
+        let balance = sample.balance.map_or_else(
+            || "account does not exist".to_string(),
+            |balance| crate::types::near_token::NearToken::from_yoctonear(balance).to_string(),
+        );
+        table.add_row(prettytable::row![sample.height, timestamp, balance]);
+    }
+    eprintln!("The NEAR balance of <{account_id}> (without the staked balance):");
+    table.printstd();
+    eprintln!("\n{}", sparkline(history));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(height: u64, balance: Option<u128>) -> BalanceSample {
+        BalanceSample {
+            height,
+            timestamp_nanosec: 0,
+            balance,
+        }
+    }
+
+    #[test]
+    fn sample_heights_are_evenly_spaced() {
+        assert_eq!(sample_heights(100, 200, 5), [100, 125, 150, 175, 200]);
+        assert_eq!(sample_heights(100, 200, 1), [200]);
+        assert_eq!(sample_heights(100, 102, 10), [100, 101, 102]);
+        assert_eq!(sample_heights(7, 7, 3), [7]);
+    }
+
+    #[test]
+    fn sparkline_scales_between_min_and_max() {
+        assert_eq!(
+            sparkline(&[
+                sample(1, Some(0)),
+                sample(2, Some(70)),
+                sample(3, None),
+                sample(4, Some(35)),
+            ]),
+            "▁█ ▄"
+        );
+        assert_eq!(sparkline(&[sample(1, Some(5)), sample(2, Some(5))]), "▅▅");
+        assert_eq!(sparkline(&[sample(1, None)]), "");
+    }
+
+    #[test]
+    fn binary_search_finds_the_block_of_the_change() {
+        // The balance changes at block 137, heights 150-160 have no block
+        let balance_at = |height: u64| Some(if height < 137 { 10 } else { 20 });
+        let mut calls = 0;
+        let change = bisect_change(
+            sample(100, Some(10)),
+            sample(200, Some(20)),
+            |height, last| {
+                calls += 1;
+                Ok((height..=last)
+                    .find(|height| !(150..=160).contains(height))
+                    .map(|height| sample(height, balance_at(height))))
+            },
+        )
+        .unwrap();
+        assert_eq!(change, sample(137, Some(20)));
+        assert!(calls <= 8, "{calls}");
+
+        // The change is at the first block after a gap
+        let balance_at = |height: u64| Some(if height < 150 { 10 } else { 20 });
+        let change = bisect_change(
+            sample(100, Some(10)),
+            sample(200, Some(20)),
+            |height, last| {
+                Ok((height..=last)
+                    .find(|height| !(150..=160).contains(height))
+                    .map(|height| sample(height, balance_at(height))))
+            },
+        )
+        .unwrap();
+        assert_eq!(change, sample(161, Some(20)));
+    }
+}
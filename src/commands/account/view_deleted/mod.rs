@@ -0,0 +1,170 @@
+use color_eyre::eyre::WrapErr;
+
+use crate::common::{JsonRpcClientExt, RpcQueryResponseExt};
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = ViewDeletedContext)]
+pub struct ViewDeleted {
+    #[interactive_clap(skip_default_input_arg)]
+    /// What Account ID do you need to check?
+    account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    /// At which block height did the account exist?
+    block_height: crate::types::block_height::BlockHeight,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network::Network,
+}
+
+#[derive(Clone)]
+pub struct ViewDeletedContext(crate::network::NetworkContext);
+
+impl ViewDeletedContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<ViewDeleted as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let on_after_getting_network_callback: crate::network::OnAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let account_id: near_primitives::types::AccountId = scope.account_id.clone().into();
+                let block_height = scope.block_height.0;
+
+                move |network_config| {
+                    let historical_account = get_account_view(
+                        network_config,
+                        &account_id,
+                        near_primitives::types::BlockReference::BlockId(
+                            near_primitives::types::BlockId::Height(block_height),
+                        ),
+                    )
+                    .wrap_err_with(|| {
+                        crate::common::old_block_request_error(
+                            format_args!("the account <{account_id}> at block #{block_height}"),
+                            network_config,
+                        )
+                    })?;
+                    let current_account = get_account_view(
+                        network_config,
+                        &account_id,
+                        near_primitives::types::Finality::Final.into(),
+                    )
+                    .wrap_err_with(|| {
+                        format!(
+                            "Failed to fetch the account <{account_id}> at the final block on network <{}>",
+                            network_config.network_name
+                        )
+                    })?;
+
+                    println!(
+                        "{}",
+                        deletion_status(
+                            &account_id,
+                            block_height,
+                            historical_account,
+                            current_account
+                        )
+                    );
+                    Ok(())
+                }
+            });
+
+        Ok(Self(crate::network::NetworkContext {
+            config: previous_context.config,
+            interacting_with_account_ids: vec![scope.account_id.clone().into()],
+            on_after_getting_network_callback,
+        }))
+    }
+}
+
+impl From<ViewDeletedContext> for crate::network::NetworkContext {
+    fn from(item: ViewDeletedContext) -> Self {
+        item.0
+    }
+}
+
+impl ViewDeleted {
+    pub fn input_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_non_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "What Account ID do you need to check?",
+        )
+    }
+}
+
+/// The account state at the block, `None` if the account does not exist there.
+fn get_account_view(
+    network_config: &crate::config::NetworkConfig,
+    account_id: &near_primitives::types::AccountId,
+    block_reference: near_primitives::types::BlockReference,
+) -> color_eyre::eyre::Result<Option<near_primitives::views::AccountView>> {
+    match network_config
+        .json_rpc_client()
+        .blocking_call_view_account(account_id, block_reference)
+    {
+        Ok(response) => response.account_view().map(Some),
+        Err(near_jsonrpc_client::errors::JsonRpcError::ServerError(
+            near_jsonrpc_client::errors::JsonRpcServerError::HandlerError(
+                near_jsonrpc_primitives::types::query::RpcQueryError::UnknownAccount { .. },
+            ),
+        )) => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+fn deletion_status(
+    account_id: &near_primitives::types::AccountId,
+    block_height: near_primitives::types::BlockHeight,
+    historical_account: Option<near_primitives::views::AccountView>,
+    current_account: Option<near_primitives::views::AccountView>,
+) -> String {
+    match (historical_account, current_account) {
+        (Some(historical_account), None) => format!(
+            "Account <{account_id}> was deleted after block #{block_height}.\nThe final known balance (at block #{block_height}) is {}.",
+            near_token::NearToken::from_yoctonear(historical_account.amount)
+        ),
+        (Some(_), Some(current_account)) => format!(
+            "Account <{account_id}> existed at block #{block_height} and still exists (if it was deleted in between, it has been re-created).\nThe current balance is {}.",
+            near_token::NearToken::from_yoctonear(current_account.amount)
+        ),
+        (None, Some(_)) => format!(
+            "Account <{account_id}> did not exist at block #{block_height}, it was created later and still exists."
+        ),
+        (None, None) => format!(
+            "Account <{account_id}> exists neither at block #{block_height} nor at the final block."
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account_view(amount: u128) -> near_primitives::views::AccountView {
+        near_primitives::views::AccountView {
+            amount,
+            locked: 0,
+            code_hash: near_primitives::hash::CryptoHash::default(),
+            storage_usage: 182,
+            storage_paid_at: 0,
+        }
+    }
+
+    #[test]
+    fn deleted_account_status() {
+        let account_id: near_primitives::types::AccountId = "alice.testnet".parse().unwrap();
+        let status = deletion_status(&account_id, 100, Some(account_view(10u128.pow(24))), None);
+        assert!(status.starts_with("Account <alice.testnet> was deleted after block #100."));
+        assert!(status.contains("1.00 NEAR"), "{status}");
+        assert!(deletion_status(
+            &account_id,
+            100,
+            Some(account_view(0)),
+            Some(account_view(0))
+        )
+        .contains("still exists"));
+        assert!(deletion_status(&account_id, 100, None, None).contains("exists neither"));
+    }
+}
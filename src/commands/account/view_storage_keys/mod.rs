@@ -0,0 +1,295 @@
+use color_eyre::eyre::Context;
+use prettytable::Table;
+
+use crate::common::{JsonRpcClientExt, RpcQueryResponseExt};
+
+const DEFAULT_LIMIT: u64 = 50;
+
+/// Every state record costs 40 bytes of storage on top of its key and value (`num_extra_bytes_record`).
+const STORAGE_BYTES_PER_RECORD: u64 = 40;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = ViewStorageKeysContext)]
+pub struct ViewStorageKeys {
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the contract account ID?
+    contract_account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Only view the keys that start with this string
+    prefix_utf8: Option<String>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// The maximum number of keys to view (default: 50)
+    limit: Option<u64>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// View the keys that follow this key (the cursor printed with the previous page)
+    start_key_base64: Option<crate::types::base64_bytes::Base64Bytes>,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network_view_at_block::NetworkViewAtBlockArgs,
+}
+
+#[derive(Clone)]
+pub struct ViewStorageKeysContext(crate::network_view_at_block::ArgsForViewContext);
+
+impl ViewStorageKeysContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<ViewStorageKeys as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let limit = scope.limit.unwrap_or(DEFAULT_LIMIT);
+        if limit == 0 {
+            return Err(color_eyre::eyre::eyre!("--limit must be greater than zero"));
+        }
+
+        let on_after_getting_block_reference_callback: crate::network_view_at_block::OnAfterGettingBlockReferenceCallback = std::sync::Arc::new({
+            let contract_account_id: near_primitives::types::AccountId = scope.contract_account_id.clone().into();
+            let prefix = scope.prefix_utf8.clone().unwrap_or_default();
+            let start_key = scope.start_key_base64.clone().map(|start_key| start_key.into_bytes());
+
+            move |network_config, block_reference| {
+                let account_view = network_config
+                    .json_rpc_client()
+                    .blocking_call_view_account(&contract_account_id, block_reference.clone())
+                    .wrap_err_with(|| {
+                        format!(
+                            "Failed to fetch query ViewAccount for account <{}> on network <{}>",
+                            contract_account_id, network_config.network_name
+                        )
+                    })?
+                    .account_view()?;
+                let state_items = get_state_items(&contract_account_id, &prefix, network_config, block_reference)?;
+                let page = storage_keys_page(&state_items, start_key.as_deref(), limit);
+
+                display_storage_keys(&contract_account_id, &prefix, &page);
+                match estimated_total_keys(account_view.storage_usage, &state_items) {
+                    Some(estimated_total_keys) => eprintln!(
+                        "\nThe account uses {} of storage, which is roughly {estimated_total_keys} keys in total (an upper bound, the storage also includes the contract code and access keys).",
+                        bytesize::ByteSize(account_view.storage_usage)
+                    ),
+                    None => eprintln!(
+                        "\nThe account uses {} of storage.",
+                        bytesize::ByteSize(account_view.storage_usage)
+                    ),
+                }
+                match page.next_start_key {
+                    Some(next_start_key) => eprintln!(
+                        "\nTo view the next page, run the same command with: --start-key-base64 {}",
+                        near_primitives::serialize::to_base64(next_start_key)
+                    ),
+                    None => eprintln!("\nThis is the last page."),
+                }
+                Ok(())
+            }
+        });
+        Ok(Self(crate::network_view_at_block::ArgsForViewContext {
+            config: previous_context.config,
+            interacting_with_account_ids: vec![scope.contract_account_id.clone().into()],
+            on_after_getting_block_reference_callback,
+        }))
+    }
+}
+
+impl From<ViewStorageKeysContext> for crate::network_view_at_block::ArgsForViewContext {
+    fn from(item: ViewStorageKeysContext) -> Self {
+        item.0
+    }
+}
+
+impl ViewStorageKeys {
+    pub fn input_contract_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_non_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "What is the contract account ID?",
+        )
+    }
+}
+
+#[tracing::instrument(name = "Obtaining the state keys of the contract ...", skip_all)]
+fn get_state_items(
+    contract_account_id: &near_primitives::types::AccountId,
+    prefix: &str,
+    network_config: &crate::config::NetworkConfig,
+    block_reference: &near_primitives::types::BlockReference,
+) -> color_eyre::eyre::Result<Vec<near_primitives::views::StateItem>> {
+    let query_view_method_response = network_config
+        .json_rpc_client()
+        .blocking_call(near_jsonrpc_client::methods::query::RpcQueryRequest {
+            block_reference: block_reference.clone(),
+            request: near_primitives::views::QueryRequest::ViewState {
+                account_id: contract_account_id.clone(),
+                prefix: near_primitives::types::StoreKey::from(prefix.as_bytes().to_vec()),
+                include_proof: false,
+            },
+        })
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch query ViewState for <{contract_account_id}> on network <{}> (if the state is too large, narrow it down with --prefix-utf8)",
+                network_config.network_name
+            )
+        })?;
+    if let near_jsonrpc_primitives::types::query::QueryResponseKind::ViewState(result) =
+        query_view_method_response.kind
+    {
+        Ok(result.values)
+    } else {
+        Err(color_eyre::Report::msg("Error call result".to_string()))
+    }
+}
+
+#[derive(Debug)]
+struct StorageKeysPage<'a> {
+    /// The position of the first key of the page among all the matching keys
+    offset: usize,
+    total_matching_keys: usize,
+    items: Vec<&'a near_primitives::views::StateItem>,
+    next_start_key: Option<&'a [u8]>,
+}
+
+fn storage_keys_page<'a>(
+    state_items: &'a [near_primitives::views::StateItem],
+    start_key: Option<&[u8]>,
+    limit: u64,
+) -> StorageKeysPage<'a> {
+    let mut sorted_items = state_items.iter().collect::<Vec<_>>();
+    sorted_items.sort_by(|a, b| a.key.as_slice().cmp(b.key.as_slice()));
+    let offset = start_key.map_or(0, |start_key| {
+        sorted_items.partition_point(|item| item.key.as_slice() <= start_key)
+    });
+    let items = sorted_items
+        .iter()
+        .skip(offset)
+        .take(usize::try_from(limit).unwrap_or(usize::MAX))
+        .copied()
+        .collect::<Vec<_>>();
+    let next_start_key = if offset + items.len() < sorted_items.len() {
+        items.last().map(|item| item.key.as_slice())
+    } else {
+        None
+    };
+    StorageKeysPage {
+        offset,
+        total_matching_keys: sorted_items.len(),
+        items,
+        next_start_key,
+    }
+}
+
+/// Extrapolates the number of keys from the average size of the fetched records.
+fn estimated_total_keys(
+    storage_usage: u64,
+    state_items: &[near_primitives::views::StateItem],
+) -> Option<u64> {
+    if state_items.is_empty() {
+        return None;
+    }
+    let fetched_bytes: u64 = state_items
+        .iter()
+        .map(|item| item.key.len() as u64 + item.value.len() as u64 + STORAGE_BYTES_PER_RECORD)
+        .sum();
+    let average_record_bytes = fetched_bytes.div_ceil(state_items.len() as u64);
+    Some(storage_usage / average_record_bytes)
+}
+
+fn display_storage_keys(
+    contract_account_id: &near_primitives::types::AccountId,
+    prefix: &str,
+    page: &StorageKeysPage,
+) {
+    let prefix_note = if prefix.is_empty() {
+        String::new()
+    } else {
+        format!(" starting with {prefix:?}")
+    };
+    if page.items.is_empty() {
+        eprintln!(
+            "\nNo storage keys{prefix_note} found in <{contract_account_id}> ({} matching keys in total).",
+            page.total_matching_keys
+        );
+        return;
+    }
+    let mut table = Table::new();
+    table.set_titles(prettytable::row![Fg=>"#", "Key", "Key (base64)", "Value size"]);
+    for (index, item) in page.items.iter().enumerate() {
+        table.add_row(prettytable::row![
+            page.offset + index + 1,
+            String::from_utf8(
+                item.key
+                    .iter()
+                    .flat_map(|b| std::ascii::escape_default(*b))
+                    .collect::<Vec<u8>>()
+            )
+            .unwrap_or_default(),
+            near_primitives::serialize::to_base64(item.key.as_slice()),
+            bytesize::ByteSize(item.value.len() as u64)
+        ]);
+    }
+    table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    eprintln!(
+        "\nStorage keys{prefix_note} of <{contract_account_id}> ({}-{} of {}):",
+        page.offset + 1,
+        page.offset + page.items.len(),
+        page.total_matching_keys
+    );
+    table.printstd();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_items(keys: &[&str]) -> Vec<near_primitives::views::StateItem> {
+        keys.iter()
+            .map(|key| near_primitives::views::StateItem {
+                key: key.as_bytes().to_vec().into(),
+                value: b"value".to_vec().into(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn storage_keys_pagination() {
+        let state_items = state_items(&["c", "a", "e", "b", "d"]);
+        let keys = |page: &StorageKeysPage| {
+            page.items
+                .iter()
+                .map(|item| String::from_utf8(item.key.to_vec()).unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        let first_page = storage_keys_page(&state_items, None, 2);
+        assert_eq!(keys(&first_page), vec!["a", "b"]);
+        assert_eq!(first_page.offset, 0);
+        assert_eq!(first_page.total_matching_keys, 5);
+        assert_eq!(first_page.next_start_key, Some(&b"b"[..]));
+
+        let second_page = storage_keys_page(&state_items, first_page.next_start_key, 2);
+        assert_eq!(keys(&second_page), vec!["c", "d"]);
+        assert_eq!(second_page.offset, 2);
+
+        let last_page = storage_keys_page(&state_items, second_page.next_start_key, 2);
+        assert_eq!(keys(&last_page), vec!["e"]);
+        assert_eq!(last_page.next_start_key, None);
+
+        // The cursor does not have to be an existing key
+        assert_eq!(
+            keys(&storage_keys_page(&state_items, Some(b"bb"), 10)),
+            vec!["c", "d", "e"]
+        );
+    }
+
+    #[test]
+    fn estimated_total_keys_from_storage_usage() {
+        // Each record is 1 (key) + 5 (value) + 40 bytes
+        assert_eq!(
+            estimated_total_keys(4600, &state_items(&["a", "b"])),
+            Some(100)
+        );
+        assert_eq!(estimated_total_keys(4600, &[]), None);
+    }
+}
@@ -0,0 +1,23 @@
+use strum::{EnumDiscriminants, EnumIter, EnumMessage};
+
+mod show;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(context = crate::GlobalContext)]
+pub struct AuditLogCommands {
+    #[interactive_clap(subcommand)]
+    audit_log_actions: AuditLogActions,
+}
+
+#[derive(Debug, EnumDiscriminants, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(context = crate::GlobalContext)]
+#[strum_discriminants(derive(EnumMessage, EnumIter))]
+#[non_exhaustive]
+/// What do you want to do with the audit log?
+pub enum AuditLogActions {
+    #[strum_discriminants(strum(
+        message = "show     - List the logged transactions and optionally verify the hash chain"
+    ))]
+    /// List the logged transactions and optionally verify the hash chain
+    Show(self::show::ShowAuditLog),
+}
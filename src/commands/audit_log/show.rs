@@ -0,0 +1,90 @@
+use color_eyre::eyre::WrapErr;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = ShowAuditLogContext)]
+pub struct ShowAuditLog {
+    #[interactive_clap(long)]
+    /// Check that no entry was changed, removed or inserted (the hash chain of the lines)
+    verify: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct ShowAuditLogContext;
+
+impl ShowAuditLogContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<ShowAuditLog as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        if !previous_context.config.audit_log {
+            eprintln!(
+                "The audit log is disabled, set `audit_log = true` in config.toml to enable it.\n"
+            );
+        }
+        let path = crate::audit_log::get_audit_log_path()?;
+        let audit_log = match std::fs::read_to_string(&path) {
+            Ok(audit_log) => audit_log,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                eprintln!(
+                    "There are no entries in the audit log ({}).",
+                    path.display()
+                );
+                return Ok(Self);
+            }
+            Err(err) => {
+                return Err(err)
+                    .wrap_err_with(|| format!("Failed to read file: {}", path.display()))
+            }
+        };
+
+        if scope.verify {
+            let (entries, broken) = crate::audit_log::verify(&audit_log);
+            entries.iter().for_each(display_entry);
+            if let Some((line_number, reason)) = broken {
+                color_eyre::eyre::bail!(
+                    "The audit log {} is broken at line {line_number}: {reason}",
+                    path.display()
+                );
+            }
+            eprintln!(
+                "\nThe hash chain of the {} entries is intact.",
+                entries.len()
+            );
+            if let Some(last_line) = audit_log.lines().rev().find(|line| !line.trim().is_empty()) {
+                eprintln!(
+                    "SHA-256 of the last line (keep it elsewhere to detect the removal of the latest entries): {}",
+                    crate::audit_log::line_sha256(last_line)
+                );
+            }
+        } else {
+            for (line_index, line) in audit_log.lines().enumerate() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                match serde_json::from_str::<crate::audit_log::AuditLogEntry>(line) {
+                    Ok(entry) => display_entry(&entry),
+                    Err(err) => eprintln!("Line {}: invalid entry: {err}", line_index + 1),
+                }
+            }
+        }
+        Ok(Self)
+    }
+}
+
+fn display_entry(entry: &crate::audit_log::AuditLogEntry) {
+    println!(
+        "{} {} <{}> -> <{}> {}: {}",
+        entry.timestamp,
+        entry.network,
+        entry.signer_id,
+        entry.receiver_id,
+        entry
+            .transaction_hash
+            .map_or_else(|| "(delegate action)".to_string(), |hash| hash.to_string()),
+        entry.outcome
+    );
+    for action in &entry.actions {
+        println!("    {action}");
+    }
+}
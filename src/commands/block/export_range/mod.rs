@@ -0,0 +1,201 @@
+use std::io::Write;
+
+use color_eyre::eyre::WrapErr;
+use futures::StreamExt;
+use tracing_indicatif::span_ext::IndicatifSpanExt;
+
+const DEFAULT_CONCURRENCY: u64 = 5;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = ExportRangeContext)]
+pub struct ExportRange {
+    #[interactive_clap(long)]
+    /// What is the height of the first block to export?
+    from_height: crate::types::block_height::BlockHeight,
+    #[interactive_clap(long)]
+    /// What is the height of the last block to export?
+    to_height: crate::types::block_height::BlockHeight,
+    #[interactive_clap(long)]
+    /// What is the path of the NDJSON file to write the blocks to?
+    output_file: crate::types::path_buf::PathBuf,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// How many blocks are fetched at the same time (default: 5)
+    concurrency: Option<u64>,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network::Network,
+}
+
+#[derive(Clone)]
+pub struct ExportRangeContext(crate::network::NetworkContext);
+
+impl ExportRangeContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<ExportRange as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        if scope.from_height > scope.to_height {
+            return Err(color_eyre::eyre::eyre!(
+                "The first block height ({}) must not be greater than the last one ({})",
+                scope.from_height,
+                scope.to_height
+            ));
+        }
+        let concurrency = match scope.concurrency.unwrap_or(DEFAULT_CONCURRENCY) {
+            0 => {
+                return Err(color_eyre::eyre::eyre!(
+                    "The concurrency must be at least 1"
+                ))
+            }
+            concurrency => usize::try_from(concurrency)?,
+        };
+
+        let on_after_getting_network_callback: crate::network::OnAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let heights = scope.from_height.0..=scope.to_height.0;
+                let output_file: std::path::PathBuf = scope.output_file.clone().into();
+
+                move |network_config| {
+                    let file = std::fs::File::create(&output_file).wrap_err_with(|| {
+                        format!("Failed to create the file: {}", output_file.display())
+                    })?;
+                    let summary = export_blocks(
+                        network_config,
+                        heights.clone(),
+                        concurrency,
+                        &mut std::io::BufWriter::new(file),
+                    )
+                    .wrap_err_with(|| {
+                        format!("Failed to export the blocks to {}", output_file.display())
+                    })?;
+                    eprintln!(
+                        "\nExported {} block(s) to {} ({} height(s) in the range have no block).",
+                        summary.exported_blocks,
+                        output_file.display(),
+                        summary.missing_blocks
+                    );
+                    Ok(())
+                }
+            });
+
+        Ok(Self(crate::network::NetworkContext {
+            config: previous_context.config,
+            interacting_with_account_ids: vec![],
+            on_after_getting_network_callback,
+        }))
+    }
+}
+
+impl From<ExportRangeContext> for crate::network::NetworkContext {
+    fn from(item: ExportRangeContext) -> Self {
+        item.0
+    }
+}
+
+/// One line of the exported file.
+#[derive(Debug, serde::Serialize)]
+struct ExportedBlock {
+    block: near_primitives::views::BlockView,
+    /// The chunks produced in the block (with their transactions and receipts)
+    chunks: Vec<near_primitives::views::ChunkView>,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct ExportSummary {
+    exported_blocks: u64,
+    /// Heights that were skipped by the chain
+    missing_blocks: u64,
+}
+
+#[tracing::instrument(name = "Exporting blocks ...", skip_all)]
+fn export_blocks(
+    network_config: &crate::config::NetworkConfig,
+    heights: std::ops::RangeInclusive<near_primitives::types::BlockHeight>,
+    concurrency: usize,
+    output: &mut impl Write,
+) -> color_eyre::eyre::Result<ExportSummary> {
+    let span = tracing::Span::current();
+    span.pb_set_style(
+        &indicatif::ProgressStyle::with_template(
+            "{spinner:.blue} {span_name} [{bar:40.cyan/blue}] {pos}/{len} blocks ({eta})",
+        )?
+        .progress_chars("=> "),
+    );
+    span.pb_set_length(heights.end() - heights.start() + 1);
+
+    let runtime = crate::common::multi_thread_runtime()?;
+    let json_rpc_client = network_config.json_rpc_client();
+    runtime.block_on(async {
+        let mut blocks = futures::stream::iter(heights)
+            .map(|height| get_block_with_chunks(&json_rpc_client, height))
+            .buffered(concurrency);
+        let mut summary = ExportSummary::default();
+        // The blocks are written in the order of their heights
+        while let Some(block) = blocks.next().await {
+            match block? {
+                Some(block) => {
+                    serde_json::to_writer(&mut *output, &block)?;
+                    output.write_all(b"\n")?;
+                    summary.exported_blocks += 1;
+                }
+                None => summary.missing_blocks += 1,
+            }
+            span.pb_inc(1);
+        }
+        output.flush()?;
+        Ok(summary)
+    })
+}
+
+/// The block at the height with all its chunks, `None` if there is no block at the height.
+async fn get_block_with_chunks(
+    json_rpc_client: &near_jsonrpc_client::JsonRpcClient,
+    height: near_primitives::types::BlockHeight,
+) -> color_eyre::eyre::Result<Option<ExportedBlock>> {
+    let block = match crate::rpc_rate_limit::call(
+        json_rpc_client,
+        near_jsonrpc_client::methods::block::RpcBlockRequest {
+            block_reference: near_primitives::types::BlockReference::BlockId(
+                near_primitives::types::BlockId::Height(height),
+            ),
+        },
+    )
+    .await
+    {
+        Ok(block) => block,
+        Err(near_jsonrpc_client::errors::JsonRpcError::ServerError(
+            near_jsonrpc_client::errors::JsonRpcServerError::HandlerError(
+                near_jsonrpc_primitives::types::blocks::RpcBlockError::UnknownBlock { .. },
+            ),
+        )) => return Ok(None),
+        Err(err) => {
+            return Err(err).wrap_err_with(|| format!("Failed to fetch block #{height}"));
+        }
+    };
+    // A shard without a new chunk in the block repeats its previous chunk header, which must not
+    // be exported twice
+    let new_chunks = block
+        .chunks
+        .iter()
+        .filter(|chunk_header| chunk_header.height_included == block.header.height);
+    let chunks = futures::future::try_join_all(new_chunks.map(|chunk_header| {
+        let chunk_hash = chunk_header.chunk_hash;
+        async move {
+            crate::rpc_rate_limit::call(
+                json_rpc_client,
+                near_jsonrpc_client::methods::chunk::RpcChunkRequest {
+                    chunk_reference:
+                        near_jsonrpc_client::methods::chunk::ChunkReference::ChunkHash {
+                            chunk_id: chunk_hash,
+                        },
+                },
+            )
+            .await
+            .wrap_err_with(|| format!("Failed to fetch chunk {chunk_hash} of block #{height}"))
+        }
+    }))
+    .await?;
+    Ok(Some(ExportedBlock { block, chunks }))
+}
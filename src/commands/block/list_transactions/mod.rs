@@ -0,0 +1,255 @@
+use color_eyre::eyre::WrapErr;
+
+use crate::common::JsonRpcClientExt;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = ListTransactionsContext)]
+pub struct ListTransactions {
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// The height of the block (default: the final block)
+    block_height: Option<crate::types::block_height::BlockHeight>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// The hash of the block, instead of --block-height
+    block_hash: Option<crate::types::crypto_hash::CryptoHash>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// List only the transactions of this shard
+    shard_id: Option<near_primitives::types::ShardId>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Output format: plaintext (default) or json
+    output: Option<crate::common::OutputFormat>,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network::Network,
+}
+
+#[derive(Clone)]
+pub struct ListTransactionsContext(crate::network::NetworkContext);
+
+impl ListTransactionsContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<ListTransactions as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let block_reference = match (scope.block_height, &scope.block_hash) {
+            (Some(_), Some(_)) => {
+                return Err(color_eyre::eyre::eyre!(
+                    "--block-height and --block-hash cannot be used together"
+                ))
+            }
+            (Some(block_height), None) => near_primitives::types::BlockReference::BlockId(
+                near_primitives::types::BlockId::Height(block_height.0),
+            ),
+            (None, Some(block_hash)) => near_primitives::types::BlockReference::BlockId(
+                near_primitives::types::BlockId::Hash((*block_hash).into()),
+            ),
+            (None, None) => near_primitives::types::Finality::Final.into(),
+        };
+
+        let on_after_getting_network_callback: crate::network::OnAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let shard_id = scope.shard_id;
+                let output = scope.output.clone().unwrap_or_default();
+
+                move |network_config| {
+                    let (block, transactions) =
+                        get_block_transactions(network_config, block_reference.clone(), shard_id)?;
+                    match output {
+                        crate::common::OutputFormat::Plaintext => {
+                            display_block_transactions(&block, &transactions, shard_id)
+                        }
+                        crate::common::OutputFormat::Json => {
+                            println!("{}", serde_json::to_string_pretty(&transactions)?)
+                        }
+                    }
+                    Ok(())
+                }
+            });
+
+        Ok(Self(crate::network::NetworkContext {
+            config: previous_context.config,
+            interacting_with_account_ids: vec![],
+            on_after_getting_network_callback,
+        }))
+    }
+}
+
+impl From<ListTransactionsContext> for crate::network::NetworkContext {
+    fn from(item: ListTransactionsContext) -> Self {
+        item.0
+    }
+}
+
+/// A transaction of the block, and one element of the JSON output.
+#[derive(Debug, Clone, serde::Serialize)]
+struct BlockTransaction {
+    hash: near_primitives::hash::CryptoHash,
+    shard_id: near_primitives::types::ShardId,
+    signer_id: near_primitives::types::AccountId,
+    receiver_id: near_primitives::types::AccountId,
+    actions: Vec<String>,
+}
+
+impl BlockTransaction {
+    fn new(
+        shard_id: near_primitives::types::ShardId,
+        transaction: &near_primitives::views::SignedTransactionView,
+    ) -> Self {
+        Self {
+            hash: transaction.hash,
+            shard_id,
+            signer_id: transaction.signer_id.clone(),
+            receiver_id: transaction.receiver_id.clone(),
+            actions: transaction.actions.iter().map(action_type).collect(),
+        }
+    }
+}
+
+/// The kind of the action, with the method name of function calls.
+fn action_type(action: &near_primitives::views::ActionView) -> String {
+    match action {
+        near_primitives::views::ActionView::CreateAccount => "CreateAccount".to_string(),
+        near_primitives::views::ActionView::DeployContract { .. } => "DeployContract".to_string(),
+        near_primitives::views::ActionView::FunctionCall { method_name, .. } => {
+            format!("FunctionCall({method_name})")
+        }
+        near_primitives::views::ActionView::Transfer { .. } => "Transfer".to_string(),
+        near_primitives::views::ActionView::Stake { .. } => "Stake".to_string(),
+        near_primitives::views::ActionView::AddKey { .. } => "AddKey".to_string(),
+        near_primitives::views::ActionView::DeleteKey { .. } => "DeleteKey".to_string(),
+        near_primitives::views::ActionView::DeleteAccount { .. } => "DeleteAccount".to_string(),
+        near_primitives::views::ActionView::Delegate { .. } => "Delegate".to_string(),
+    }
+}
+
+#[tracing::instrument(name = "Getting the transactions of the block ...", skip_all)]
+fn get_block_transactions(
+    network_config: &crate::config::NetworkConfig,
+    block_reference: near_primitives::types::BlockReference,
+    shard_id: Option<near_primitives::types::ShardId>,
+) -> color_eyre::eyre::Result<(near_primitives::views::BlockView, Vec<BlockTransaction>)> {
+    let json_rpc_client = network_config.json_rpc_client();
+    let block = json_rpc_client
+        .blocking_call(near_jsonrpc_client::methods::block::RpcBlockRequest { block_reference })
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch the block on network <{}>",
+                network_config.network_name
+            )
+        })?;
+    if let Some(shard_id) = shard_id {
+        if !block
+            .chunks
+            .iter()
+            .any(|chunk_header| chunk_header.shard_id == shard_id)
+        {
+            color_eyre::eyre::bail!(
+                "There is no shard {shard_id} in block #{} (it has {} shards)",
+                block.header.height,
+                block.chunks.len()
+            );
+        }
+    }
+    let mut transactions = Vec::new();
+    // A shard without a new chunk in the block repeats its previous chunk header, whose
+    // transactions belong to an earlier block
+    for chunk_header in block.chunks.iter().filter(|chunk_header| {
+        chunk_header.height_included == block.header.height
+            && shard_id.map_or(true, |shard_id| chunk_header.shard_id == shard_id)
+    }) {
+        let chunk = json_rpc_client
+            .blocking_call(near_jsonrpc_client::methods::chunk::RpcChunkRequest {
+                chunk_reference: near_jsonrpc_client::methods::chunk::ChunkReference::ChunkHash {
+                    chunk_id: chunk_header.chunk_hash,
+                },
+            })
+            .wrap_err_with(|| {
+                format!(
+                    "Failed to fetch chunk {} of block #{}",
+                    chunk_header.chunk_hash, block.header.height
+                )
+            })?;
+        transactions.extend(
+            chunk
+                .transactions
+                .iter()
+                .map(|transaction| BlockTransaction::new(chunk_header.shard_id, transaction)),
+        );
+    }
+    Ok((block, transactions))
+}
+
+fn display_block_transactions(
+    block: &near_primitives::views::BlockView,
+    transactions: &[BlockTransaction],
+    shard_id: Option<near_primitives::types::ShardId>,
+) {
+    let shards = match shard_id {
+        Some(shard_id) => format!("shard {shard_id}"),
+        None => format!("{} shards", block.chunks.len()),
+    };
+    eprintln!(
+        "Block #{} ({}), {shards}: {} transaction(s)",
+        block.header.height,
+        block.header.hash,
+        transactions.len()
+    );
+    if transactions.is_empty() {
+        return;
+    }
+    let mut table = prettytable::Table::new();
+    table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    table.set_titles(prettytable::row![Fg=>"Shard", "Hash", "Signer", "Receiver", "Actions"]);
+    for transaction in transactions {
+        table.add_row(prettytable::row![
+            transaction.shard_id,
+            transaction.hash,
+            transaction.signer_id,
+            transaction.receiver_id,
+            transaction.actions.join(", ")
+        ]);
+    }
+    table.printstd();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_transaction_with_action_types() {
+        let transaction: near_primitives::views::SignedTransactionView =
+            serde_json::from_value(serde_json::json!({
+                "signer_id": "alice.near",
+                "public_key": "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp",
+                "nonce": 1,
+                "receiver_id": "app.near",
+                "actions": [
+                    { "Transfer": { "deposit": "1" } },
+                    { "FunctionCall": { "method_name": "vote", "args": "e30=", "gas": 30000000000000_u64, "deposit": "0" } }
+                ],
+                "signature": "ed25519:3s1dvMqNDCByoMnDnkhB4GPjTSXCRt4nt3Af5n1RX8W7aJ2FC6MfRf5BNXZ52EBifNJnNVBsGvke6GRYuaEYJXt5",
+                "hash": "9FtHUFBQsZ2MG77K3x3MJ9wjX3UT8zE1TczCrhZEcG8U"
+            }))
+            .unwrap();
+        let block_transaction = BlockTransaction::new(3, &transaction);
+        assert_eq!(
+            block_transaction.actions,
+            ["Transfer", "FunctionCall(vote)"]
+        );
+        assert_eq!(
+            serde_json::to_value(&block_transaction).unwrap(),
+            serde_json::json!({
+                "hash": "9FtHUFBQsZ2MG77K3x3MJ9wjX3UT8zE1TczCrhZEcG8U",
+                "shard_id": 3,
+                "signer_id": "alice.near",
+                "receiver_id": "app.near",
+                "actions": ["Transfer", "FunctionCall(vote)"]
+            })
+        );
+    }
+}
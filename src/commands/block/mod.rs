@@ -0,0 +1,29 @@
+use strum::{EnumDiscriminants, EnumIter, EnumMessage};
+
+mod export_range;
+mod list_transactions;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(context = crate::GlobalContext)]
+pub struct BlockCommands {
+    #[interactive_clap(subcommand)]
+    block_actions: BlockActions,
+}
+
+#[derive(Debug, EnumDiscriminants, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(context = crate::GlobalContext)]
+#[strum_discriminants(derive(EnumMessage, EnumIter))]
+#[non_exhaustive]
+/// Choose action for blocks:
+pub enum BlockActions {
+    #[strum_discriminants(strum(
+        message = "export-range      - Export a range of blocks with their chunks to an NDJSON file"
+    ))]
+    /// Export a range of blocks with their chunks (and transactions) to an NDJSON file
+    ExportRange(self::export_range::ExportRange),
+    #[strum_discriminants(strum(
+        message = "list-transactions - List the transactions of a block with their signers, receivers and actions"
+    ))]
+    /// List the transactions of a block (in all its chunks) with their signers, receivers and actions
+    ListTransactions(self::list_transactions::ListTransactions),
+}
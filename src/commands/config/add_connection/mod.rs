@@ -1,4 +1,4 @@
-use inquire::{CustomType, Select};
+use inquire::CustomType;
 
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = crate::GlobalContext)]
@@ -43,6 +43,21 @@ pub struct AddNetworkConnection {
     #[interactive_clap(long)]
     #[interactive_clap(skip_default_input_arg)]
     coingecko_url: Option<crate::types::url::Url>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_default_input_arg)]
+    indexer_url: Option<crate::types::url::Url>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// The legacy keychain directory of the connection (default: credentials_home_dir)
+    credentials_dir: Option<crate::types::path_buf::PathBuf>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Client-side limit of RPC requests per second (default: 10 for the public endpoints, 0 disables the limit)
+    rpc_rate_limit_rps: Option<u64>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// A price oracle contract with `get_price_data` for `tokens view-near-price`
+    price_oracle_account_id: Option<crate::types::account_id::AccountId>,
 }
 
 #[derive(Debug, Clone)]
@@ -91,6 +106,19 @@ impl AddNetworkConnectionContext {
                     .coingecko_url
                     .clone()
                     .map(|coingecko_url| coingecko_url.into()),
+                indexer_url: scope
+                    .indexer_url
+                    .clone()
+                    .map(|indexer_url| indexer_url.into()),
+                credentials_dir: scope
+                    .credentials_dir
+                    .clone()
+                    .map(|credentials_dir| credentials_dir.into()),
+                rpc_rate_limit_rps: scope.rpc_rate_limit_rps.map(u32::try_from).transpose()?,
+                price_oracle_account_id: scope
+                    .price_oracle_account_id
+                    .clone()
+                    .map(|price_oracle_account_id| price_oracle_account_id.into()),
             },
         );
         eprintln!();
@@ -115,12 +143,11 @@ impl AddNetworkConnection {
             #[strum(to_string = "No, the RPC endpoint does not require API key")]
             No,
         }
-        let select_choose_input = Select::new(
+        let select_choose_input = crate::common::select(
             "Do you want to input an API key?",
             vec![ConfirmOptions::Yes, ConfirmOptions::No],
-        )
-        .prompt()?;
-        if let ConfirmOptions::Yes = select_choose_input {
+        )?;
+        if let Some(ConfirmOptions::Yes) = select_choose_input {
             let api_key: crate::types::api_key::ApiKey =
                 CustomType::new("Enter an API key").prompt()?;
             Ok(Some(api_key))
@@ -142,12 +169,11 @@ impl AddNetworkConnection {
             #[strum(to_string = "I dont know")]
             No,
         }
-        let select_choose_input = Select::new(
+        let select_choose_input = crate::common::select(
             "Is there a \"linkdrop\" program on this network?",
             vec![ConfirmOptions::Yes, ConfirmOptions::No],
-        )
-        .prompt()?;
-        if let ConfirmOptions::Yes = select_choose_input {
+        )?;
+        if let Some(ConfirmOptions::Yes) = select_choose_input {
             let account_id: crate::types::account_id::AccountId =
             CustomType::new("What is the name of the account that hosts the \"linkdrop\" program? (e.g. on mainnet it is near, and on testnet it is testnet)").prompt()?;
             Ok(Some(account_id))
@@ -169,12 +195,11 @@ impl AddNetworkConnection {
             )]
             No,
         }
-        let select_choose_input = Select::new(
+        let select_choose_input = crate::common::select(
             "Do you want to enter the NEAR Social DB contract account ID on this network?",
             vec![ConfirmOptions::Yes, ConfirmOptions::No],
-        )
-        .prompt()?;
-        if let ConfirmOptions::Yes = select_choose_input {
+        )?;
+        if let Some(ConfirmOptions::Yes) = select_choose_input {
             let account_id: crate::types::account_id::AccountId =
             CustomType::new("What is the name of the NEAR Social DB contract account ID (e.g. on mainnet it is social.near)").prompt()?;
             Ok(Some(account_id))
@@ -194,12 +219,11 @@ impl AddNetworkConnection {
             #[strum(to_string = "No, I don't want to enter the faucet URL")]
             No,
         }
-        let select_choose_input = Select::new(
+        let select_choose_input = crate::common::select(
             "Do you want to enter the faucet URL?",
             vec![ConfirmOptions::Yes, ConfirmOptions::No],
-        )
-        .prompt()?;
-        if let ConfirmOptions::Yes = select_choose_input {
+        )?;
+        if let Some(ConfirmOptions::Yes) = select_choose_input {
             let faucet_url: crate::types::url::Url =
                 CustomType::new("What is the faucet url?").prompt()?;
             Ok(Some(faucet_url))
@@ -219,12 +243,11 @@ impl AddNetworkConnection {
             #[strum(to_string = "No, I don't want to enter the relayer URL")]
             No,
         }
-        let select_choose_input = Select::new(
+        let select_choose_input = crate::common::select(
             "Do you want to enter the meta transaction relayer URL?",
             vec![ConfirmOptions::Yes, ConfirmOptions::No],
-        )
-        .prompt()?;
-        if let ConfirmOptions::Yes = select_choose_input {
+        )?;
+        if let Some(ConfirmOptions::Yes) = select_choose_input {
             let meta_transaction_relayer_url: crate::types::url::Url =
                 CustomType::new("What is the relayer url?").prompt()?;
             Ok(Some(meta_transaction_relayer_url))
@@ -244,12 +267,11 @@ impl AddNetworkConnection {
             #[strum(to_string = "No, I don't want to enter the fastnear API url")]
             No,
         }
-        let select_choose_input = Select::new(
+        let select_choose_input = crate::common::select(
             "Do you want to enter the fastnear API url?",
             vec![ConfirmOptions::Yes, ConfirmOptions::No],
-        )
-        .prompt()?;
-        if let ConfirmOptions::Yes = select_choose_input {
+        )?;
+        if let Some(ConfirmOptions::Yes) = select_choose_input {
             let stake_delegators_api: crate::types::url::Url =
                 CustomType::new("What is the fastnear API url?").prompt()?;
             Ok(Some(stake_delegators_api))
@@ -269,12 +291,11 @@ impl AddNetworkConnection {
             #[strum(to_string = "No, I don't want to enter the staking pools factory account ID")]
             No,
         }
-        let select_choose_input = Select::new(
+        let select_choose_input = crate::common::select(
             "Do you want to enter the staking pools factory account ID?",
             vec![ConfirmOptions::Yes, ConfirmOptions::No],
-        )
-        .prompt()?;
-        if let ConfirmOptions::Yes = select_choose_input {
+        )?;
+        if let Some(ConfirmOptions::Yes) = select_choose_input {
             let account_id: crate::types::account_id::AccountId =
                 CustomType::new("What is the staking pools factory account ID?").prompt()?;
             Ok(Some(account_id))
@@ -294,12 +315,11 @@ impl AddNetworkConnection {
             #[strum(to_string = "No, I don't want to enter the coingecko API url")]
             No,
         }
-        let select_choose_input = Select::new(
+        let select_choose_input = crate::common::select(
             "Do you want to enter the coingecko API url?",
             vec![ConfirmOptions::Yes, ConfirmOptions::No],
-        )
-        .prompt()?;
-        if let ConfirmOptions::Yes = select_choose_input {
+        )?;
+        if let Some(ConfirmOptions::Yes) = select_choose_input {
             let coingecko_api: crate::types::url::Url =
                 CustomType::new("What is the coingecko API url?")
                     .with_starting_input("https://api.coingecko.com/")
@@ -309,4 +329,30 @@ impl AddNetworkConnection {
             Ok(None)
         }
     }
+
+    fn input_indexer_url(
+        _context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::url::Url>> {
+        eprintln!();
+        #[derive(strum_macros::Display)]
+        enum ConfirmOptions {
+            #[strum(to_string = "Yes, I want to enter the indexer API url")]
+            Yes,
+            #[strum(to_string = "No, I don't want to enter the indexer API url")]
+            No,
+        }
+        let select_choose_input = crate::common::select(
+            "Do you want to enter the indexer API url (Nearblocks API)?",
+            vec![ConfirmOptions::Yes, ConfirmOptions::No],
+        )?;
+        if let Some(ConfirmOptions::Yes) = select_choose_input {
+            let indexer_url: crate::types::url::Url =
+                CustomType::new("What is the indexer API url?")
+                    .with_starting_input("https://api.nearblocks.io/")
+                    .prompt()?;
+            Ok(Some(indexer_url))
+        } else {
+            Ok(None)
+        }
+    }
 }
@@ -1,4 +1,4 @@
-use inquire::{Select, Text};
+use inquire::Text;
 
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = crate::GlobalContext)]
@@ -148,6 +148,34 @@ impl ParameterContext {
                     Some(scope.value.parse()?)
                 };
             }
+            "indexer_url" => {
+                network_config.indexer_url = if &scope.value == "null" {
+                    None
+                } else {
+                    Some(scope.value.parse()?)
+                };
+            }
+            "credentials_dir" => {
+                network_config.credentials_dir = if &scope.value == "null" {
+                    None
+                } else {
+                    Some(scope.value.parse()?)
+                };
+            }
+            "rpc_rate_limit_rps" => {
+                network_config.rpc_rate_limit_rps = if &scope.value == "null" {
+                    None
+                } else {
+                    Some(scope.value.parse()?)
+                };
+            }
+            "price_oracle_account_id" => {
+                network_config.price_oracle_account_id = if &scope.value == "null" {
+                    None
+                } else {
+                    Some(scope.value.parse()?)
+                };
+            }
             _ => {
                 return color_eyre::eyre::Result::Err(color_eyre::eyre::eyre!(
                     "Configuration key <{}> not found",
@@ -170,17 +198,10 @@ impl Parameter {
     fn input_key(context: &EditConnectionContext) -> color_eyre::eyre::Result<Option<String>> {
         let variants = context.network_config.get_fields()?;
 
-        let select_submit = Select::new("Which setting do you want to change?", variants).prompt();
-        match select_submit {
-            Ok(value) => Ok(Some(
-                value.split_once(':').expect("Internal error").0.to_string(),
-            )),
-            Err(
-                inquire::error::InquireError::OperationCanceled
-                | inquire::error::InquireError::OperationInterrupted,
-            ) => Ok(None),
-            Err(err) => Err(err.into()),
-        }
+        Ok(
+            crate::common::select("Which setting do you want to change?", variants)?
+                .map(|value| value.split_once(':').expect("Internal error").0.to_string()),
+        )
     }
 
     pub fn input_value(
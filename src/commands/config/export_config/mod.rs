@@ -0,0 +1,29 @@
+use color_eyre::eyre::WrapErr;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = ExportConfigContext)]
+pub struct ExportConfig {
+    #[interactive_clap(long)]
+    /// Where do you want to save the configuration bundle (example: team-config.json)?
+    out: crate::types::path_buf::PathBuf,
+}
+
+#[derive(Debug, Clone)]
+pub struct ExportConfigContext;
+
+impl ExportConfigContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<ExportConfig as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let bundle = crate::config::bundle::ConfigBundle::from_config(&previous_context.config);
+        std::fs::write(&scope.out, serde_json::to_string_pretty(&bundle)?)
+            .wrap_err_with(|| format!("Failed to write to file: {}", scope.out))?;
+        eprintln!(
+            "\nThe configuration bundle (network connections, defaults and watchlist, without credentials) was saved to {}",
+            scope.out
+        );
+        Ok(Self)
+    }
+}
@@ -0,0 +1,73 @@
+use color_eyre::eyre::WrapErr;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = ImportConfigContext)]
+pub struct ImportConfig {
+    /// What is the path to the configuration bundle (example: team-config.json)?
+    bundle_file: crate::types::path_buf::PathBuf,
+    #[interactive_clap(long)]
+    /// Keep the existing entries and add the new ones, skipping conflicting entries (default)
+    merge: bool,
+    #[interactive_clap(long)]
+    /// Replace the network connections, defaults and watchlist with the ones from the bundle
+    replace: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportConfigContext;
+
+impl ImportConfigContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<ImportConfig as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let mode = match (scope.merge, scope.replace) {
+            (true, true) => {
+                return Err(color_eyre::eyre::eyre!(
+                    "--merge and --replace cannot be used together"
+                ))
+            }
+            (_, true) => crate::config::bundle::ImportMode::Replace,
+            (_, false) => crate::config::bundle::ImportMode::Merge,
+        };
+        let bundle_json = std::fs::read_to_string(&scope.bundle_file)
+            .wrap_err_with(|| format!("Failed to read the file: {}", scope.bundle_file))?;
+        let bundle = crate::config::bundle::ConfigBundle::from_json(&bundle_json)
+            .wrap_err_with(|| format!("Invalid configuration bundle: {}", scope.bundle_file))?;
+
+        // The bundle replaces the global settings, not the ones of the applied profile
+        let mut config = previous_context.config.without_applied_profile();
+        let report = bundle.apply(&mut config, mode)?;
+
+        eprintln!();
+        for (title, entries) in [
+            ("Added", &report.added),
+            ("Updated", &report.updated),
+            ("Removed", &report.removed),
+            ("Skipped", &report.skipped),
+        ] {
+            for entry in entries {
+                eprintln!("{title}: {entry}");
+            }
+        }
+        if report.added.is_empty() && report.updated.is_empty() && report.removed.is_empty() {
+            eprintln!("\nThe configuration is already up to date");
+            return Ok(Self);
+        }
+
+        let config_toml = toml::to_string(&config.clone().into_latest_version())?;
+        let issues = super::validate_config::validate_config_toml(&config_toml);
+        if !issues.is_empty() {
+            eprintln!("\nValidating the imported configuration:");
+            if super::validate_config::print_issues(&issues) > 0 {
+                return Err(color_eyre::eyre::eyre!(
+                    "The imported configuration is invalid, nothing was changed"
+                ));
+            }
+        }
+        eprintln!();
+        config.write_config_toml()?;
+        Ok(Self)
+    }
+}
@@ -4,6 +4,13 @@ use strum::{EnumDiscriminants, EnumIter, EnumMessage};
 mod add_connection;
 mod delete_connection;
 mod edit_connection;
+mod export_config;
+mod import_config;
+mod profile;
+mod set_default_notify;
+mod set_default_wait_for_finality;
+mod validate_config;
+mod watchlist;
 
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(context = crate::GlobalContext)]
@@ -34,6 +41,41 @@ pub enum ConfigActions {
     ))]
     /// Delete a network connection
     DeleteConnection(self::delete_connection::DeleteNetworkConnection),
+    #[strum_discriminants(strum(
+        message = "export                 - Export network connections, defaults and watchlist to a shareable bundle"
+    ))]
+    /// Export network connections, defaults and watchlist to a shareable bundle
+    Export(self::export_config::ExportConfig),
+    #[strum_discriminants(strum(
+        message = "import                 - Import a configuration bundle"
+    ))]
+    /// Import a configuration bundle
+    Import(self::import_config::ImportConfig),
+    #[strum_discriminants(strum(
+        message = "validate               - Check the configuration file for common mistakes"
+    ))]
+    /// Check the configuration file for common mistakes
+    Validate(self::validate_config::ValidateConfig),
+    #[strum_discriminants(strum(
+        message = "set-default-wait-for-finality - Wait for the transaction finality after broadcasting by default"
+    ))]
+    /// Wait for the transaction finality after broadcasting by default
+    SetDefaultWaitForFinality(self::set_default_wait_for_finality::SetDefaultWaitForFinality),
+    #[strum_discriminants(strum(
+        message = "set-default-notify     - Choose when to show a desktop notification about the transaction completion"
+    ))]
+    /// Choose when to show a desktop notification about the transaction completion
+    SetDefaultNotify(self::set_default_notify::SetDefaultNotify),
+    #[strum_discriminants(strum(
+        message = "watchlist              - Manage the accounts watchlist"
+    ))]
+    /// Manage the accounts watchlist
+    Watchlist(self::watchlist::WatchlistCommands),
+    #[strum_discriminants(strum(
+        message = "profile                - Manage the profiles (e.g. work and personal settings)"
+    ))]
+    /// Manage the profiles (e.g. work and personal settings)
+    Profile(self::profile::ProfileCommands),
 }
 
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
@@ -0,0 +1,63 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = CreateProfileContext)]
+pub struct CreateProfile {
+    /// What is the name of the profile (e.g. work)?
+    name: String,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// The network connection offered first when the network is selected
+    default_network: Option<String>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// The legacy keychain directory of the profile (instead of `credentials_home_dir` and the `credentials_dir` of the network connections)
+    credentials_dir: Option<crate::types::path_buf::PathBuf>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Transactions on mainnet with a total outgoing value above this amount must be signed with Ledger
+    require_ledger_above: Option<crate::types::near_token::NearToken>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateProfileContext;
+
+impl CreateProfileContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<CreateProfile as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let mut config = previous_context.config;
+        crate::config::profiles::validate_profile_name(&scope.name)?;
+        if config.profiles.contains_key(&scope.name) {
+            color_eyre::eyre::bail!("Profile \"{}\" already exists in config.toml", scope.name);
+        }
+        if let Some(default_network) = &scope.default_network {
+            if !config.network_connection.contains_key(default_network) {
+                color_eyre::eyre::bail!(
+                    "Network connection \"{default_network}\" is not found in config.toml"
+                );
+            }
+        }
+        config.profiles.insert(
+            scope.name.clone(),
+            crate::config::profiles::ConfigProfile {
+                default_network: scope.default_network.clone(),
+                credentials_dir: scope.credentials_dir.clone().map(Into::into),
+                defaults: crate::config::profiles::ProfileDefaults {
+                    require_ledger_above: scope.require_ledger_above,
+                    ..Default::default()
+                },
+                watchlist: None,
+            },
+        );
+        eprintln!();
+        config.write_config_toml()?;
+        eprintln!(
+            "Profile \"{name}\" was successfully created. Use it with `--profile {name}`, `{}={name}` or `config profile use {name}`",
+            crate::config::profiles::NEAR_CLI_PROFILE,
+            name = scope.name
+        );
+        eprintln!("The other settings of the profile (`defaults` and `watchlist`) can be edited in [profiles.{}] of config.toml", scope.name);
+        Ok(Self)
+    }
+}
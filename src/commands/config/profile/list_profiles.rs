@@ -0,0 +1,41 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = ListProfilesContext)]
+pub struct ListProfiles;
+
+#[derive(Debug, Clone)]
+pub struct ListProfilesContext;
+
+impl ListProfilesContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        _scope: &<ListProfiles as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let config = previous_context.config;
+        if config.profiles.is_empty() {
+            eprintln!(
+                "There are no profiles in the config, create one with `config profile create`"
+            );
+            return Ok(Self);
+        }
+        let applied_profile_name = config
+            .applied_profile
+            .as_ref()
+            .map(|applied_profile| applied_profile.name.as_str());
+        for profile_name in config.profiles.keys() {
+            let mut notes = Vec::new();
+            if config.default_profile.as_ref() == Some(profile_name) {
+                notes.push("default");
+            }
+            if applied_profile_name == Some(profile_name.as_str()) {
+                notes.push("in use");
+            }
+            if notes.is_empty() {
+                println!("{profile_name}");
+            } else {
+                println!("{profile_name} ({})", notes.join(", "));
+            }
+        }
+        Ok(Self)
+    }
+}
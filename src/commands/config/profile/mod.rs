@@ -0,0 +1,50 @@
+use strum::{EnumDiscriminants, EnumIter, EnumMessage};
+
+mod create_profile;
+mod list_profiles;
+mod show_profile;
+mod use_profile;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(context = crate::GlobalContext)]
+pub struct ProfileCommands {
+    #[interactive_clap(subcommand)]
+    profile_actions: ProfileActions,
+}
+
+#[derive(Debug, EnumDiscriminants, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(context = crate::GlobalContext)]
+#[strum_discriminants(derive(EnumMessage, EnumIter))]
+#[non_exhaustive]
+/// What do you want to do with the config profiles?
+pub enum ProfileActions {
+    #[strum_discriminants(strum(message = "create   - Create a profile"))]
+    /// Create a profile
+    Create(self::create_profile::CreateProfile),
+    #[strum_discriminants(strum(message = "list     - Show a list of the profiles"))]
+    /// Show a list of the profiles
+    List(self::list_profiles::ListProfiles),
+    #[strum_discriminants(strum(message = "show     - Show the settings of a profile"))]
+    /// Show the settings of a profile
+    Show(self::show_profile::ShowProfile),
+    #[strum_discriminants(strum(message = "use      - Use a profile by default"))]
+    /// Use a profile by default
+    Use(self::use_profile::UseProfile),
+}
+
+fn input_profile_name(
+    context: &crate::GlobalContext,
+    message: &str,
+    with_global: bool,
+) -> color_eyre::eyre::Result<Option<String>> {
+    let mut profile_names = context.config.profile_names();
+    if profile_names.is_empty() {
+        color_eyre::eyre::bail!(
+            "There are no profiles in the config, create one with `config profile create`"
+        );
+    }
+    if with_global {
+        profile_names.push(crate::config::profiles::GLOBAL_PROFILE_NAME.to_string());
+    }
+    crate::common::select(message, profile_names)
+}
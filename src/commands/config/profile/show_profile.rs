@@ -0,0 +1,40 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = ShowProfileContext)]
+pub struct ShowProfile {
+    #[interactive_clap(skip_default_input_arg)]
+    /// What profile do you want to show?
+    name: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ShowProfileContext;
+
+impl ShowProfileContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<ShowProfile as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        // The loaded config has the settings of the applied profile, the stored one has them
+        // in the profile itself
+        let config = previous_context.config.without_applied_profile();
+        let profile = config.profiles.get(&scope.name).ok_or_else(|| {
+            color_eyre::eyre::eyre!("Profile \"{}\" is not found in config.toml", scope.name)
+        })?;
+        if *profile == crate::config::profiles::ConfigProfile::default() {
+            eprintln!(
+                "Profile \"{}\" overrides no settings, the global ones are used",
+                scope.name
+            );
+        } else {
+            print!("{}", toml::to_string(profile)?);
+        }
+        Ok(Self)
+    }
+}
+
+impl ShowProfile {
+    fn input_name(context: &crate::GlobalContext) -> color_eyre::eyre::Result<Option<String>> {
+        super::input_profile_name(context, "What profile do you want to show?", false)
+    }
+}
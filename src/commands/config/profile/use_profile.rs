@@ -0,0 +1,47 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = UseProfileContext)]
+pub struct UseProfile {
+    #[interactive_clap(skip_default_input_arg)]
+    /// What profile do you want to use by default ("global" for no profile)?
+    name: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct UseProfileContext;
+
+impl UseProfileContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<UseProfile as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let mut config = previous_context.config;
+        if scope.name == crate::config::profiles::GLOBAL_PROFILE_NAME {
+            config.default_profile = None;
+        } else if config.profiles.contains_key(&scope.name) {
+            config.default_profile = Some(scope.name.clone());
+        } else {
+            color_eyre::eyre::bail!("Profile \"{}\" is not found in config.toml", scope.name);
+        }
+        eprintln!();
+        config.write_config_toml()?;
+        if scope.name == crate::config::profiles::GLOBAL_PROFILE_NAME {
+            eprintln!("The global settings will be used by default");
+        } else {
+            eprintln!("Profile \"{}\" will be used by default", scope.name);
+        }
+        if let Ok(env_profile) = std::env::var(crate::config::profiles::NEAR_CLI_PROFILE) {
+            eprintln!(
+                "Note: {}={env_profile} is set, it takes precedence over the default profile",
+                crate::config::profiles::NEAR_CLI_PROFILE
+            );
+        }
+        Ok(Self)
+    }
+}
+
+impl UseProfile {
+    fn input_name(context: &crate::GlobalContext) -> color_eyre::eyre::Result<Option<String>> {
+        super::input_profile_name(context, "What profile do you want to use by default?", true)
+    }
+}
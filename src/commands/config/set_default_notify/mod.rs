@@ -0,0 +1,42 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = SetDefaultNotifyContext)]
+pub struct SetDefaultNotify {
+    /// When do you want to get a desktop notification about the transaction completion by default (always/long/never)?
+    notify: crate::notification::NotifyMode,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// The duration (in seconds) after which a transaction is notified with "long" (default: 20)
+    after_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SetDefaultNotifyContext;
+
+impl SetDefaultNotifyContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<SetDefaultNotify as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let mut config = previous_context.config;
+        config.notify = scope.notify;
+        if let Some(after_secs) = scope.after_secs {
+            config.notify_after_secs = after_secs;
+        }
+        let notify_after_secs = config.notify_after_secs;
+        eprintln!();
+        config.write_config_toml()?;
+        match scope.notify {
+            crate::notification::NotifyMode::Always => {
+                eprintln!("A desktop notification will be shown when a transaction completes")
+            }
+            crate::notification::NotifyMode::Long => eprintln!(
+                "A desktop notification will be shown when a transaction takes longer than {notify_after_secs} seconds"
+            ),
+            crate::notification::NotifyMode::Never => {
+                eprintln!("Desktop notifications will be shown only if `--notify` is passed")
+            }
+        }
+        Ok(Self)
+    }
+}
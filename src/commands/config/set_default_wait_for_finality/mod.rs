@@ -0,0 +1,35 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = SetDefaultWaitForFinalityContext)]
+pub struct SetDefaultWaitForFinality {
+    /// Do you want to wait for the transaction finality after broadcasting by default (true/false)?
+    wait_for_finality: BoolValue,
+}
+
+#[derive(Debug, Clone)]
+pub struct SetDefaultWaitForFinalityContext;
+
+impl SetDefaultWaitForFinalityContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<SetDefaultWaitForFinality as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let mut config = previous_context.config;
+        config.wait_for_finality = scope.wait_for_finality.0;
+        eprintln!();
+        config.write_config_toml()?;
+        if scope.wait_for_finality.0 {
+            eprintln!("Transactions will be watched until finality after broadcasting by default");
+        } else {
+            eprintln!("Transactions will be watched until finality only if `--watch-tx` is passed");
+        }
+        Ok(Self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::Display, derive_more::FromStr)]
+pub struct BoolValue(bool);
+
+impl interactive_clap::ToCli for BoolValue {
+    type CliVariant = BoolValue;
+}
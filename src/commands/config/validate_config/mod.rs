@@ -0,0 +1,255 @@
+use color_eyre::eyre::ContextCompat;
+
+/// The fields of a network connection that must be URLs
+const URL_FIELDS: &[&str] = &[
+    "rpc_url",
+    "wallet_url",
+    "explorer_transaction_url",
+    "faucet_url",
+    "meta_transaction_relayer_url",
+    "fastnear_url",
+    "coingecko_url",
+    "indexer_url",
+];
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = ValidateConfigContext)]
+pub struct ValidateConfig;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Issue {
+    Error(String),
+    Warning(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct ValidateConfigContext;
+
+impl ValidateConfigContext {
+    pub fn from_previous_context(
+        _previous_context: crate::GlobalContext,
+        _scope: &<ValidateConfig as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let mut path_config_toml =
+            dirs::config_dir().wrap_err("Impossible to get your config dir!")?;
+        path_config_toml.push("near-cli/config.toml");
+        let config_toml = std::fs::read_to_string(&path_config_toml).map_err(|err| {
+            color_eyre::eyre::eyre!("Failed to read the file {path_config_toml:?}: {err}")
+        })?;
+
+        let issues = validate_config_toml(&config_toml);
+        eprintln!("\nValidating {path_config_toml:?}:");
+        let errors = print_issues(&issues);
+        if errors > 0 {
+            return Err(color_eyre::eyre::eyre!(
+                "The configuration file has {errors} error(s)"
+            ));
+        }
+        Ok(Self)
+    }
+}
+
+pub(super) fn validate_config_toml(config_toml: &str) -> Vec<Issue> {
+    let mut issues = duplicate_network_connections(config_toml);
+    if !issues.is_empty() {
+        // The file cannot be parsed with duplicate tables
+        return issues;
+    }
+    let config_value = match toml::from_str::<toml::Table>(config_toml) {
+        Ok(config_value) => config_value,
+        Err(err) => {
+            issues.push(Issue::Error(format!("invalid TOML: {}", err.message())));
+            return issues;
+        }
+    };
+
+    match config_value.get("credentials_home_dir") {
+        Some(toml::Value::String(credentials_home_dir)) => issues.extend(
+            check_writable_dir("credentials_home_dir", credentials_home_dir.as_ref())
+                .map(Issue::Error),
+        ),
+        _ => issues.push(Issue::Error(
+            "credentials_home_dir: the path is missing".to_string(),
+        )),
+    }
+
+    match config_value.get("network_connection") {
+        Some(toml::Value::Table(network_connections)) => {
+            for (connection_name, network_config) in network_connections {
+                let Some(network_config) = network_config.as_table() else {
+                    issues.push(Issue::Error(format!(
+                        "network_connection.{connection_name}: expected a table"
+                    )));
+                    continue;
+                };
+                issues.extend(validate_network_connection(connection_name, network_config));
+            }
+        }
+        _ => issues.push(Issue::Error(
+            "network_connection: there are no network connections".to_string(),
+        )),
+    }
+
+    // The types of the other fields are only checked by deserializing the whole config
+    if issues
+        .iter()
+        .all(|issue| matches!(issue, Issue::Warning(_)))
+    {
+        if let Err(err) = crate::config::Config::parse_config_toml(config_toml) {
+            issues.push(Issue::Error(err.message().to_string()));
+        }
+    }
+    issues
+}
+
+/// Prints the issues and their counts, returns the number of errors.
+pub(super) fn print_issues(issues: &[Issue]) -> usize {
+    for issue in issues {
+        match issue {
+            Issue::Error(message) => eprintln!("  error:   {message}"),
+            Issue::Warning(message) => eprintln!("  warning: {message}"),
+        }
+    }
+    let errors = issues
+        .iter()
+        .filter(|issue| matches!(issue, Issue::Error(_)))
+        .count();
+    let warnings = issues.len() - errors;
+    eprintln!("\n{errors} error(s), {warnings} warning(s)");
+    errors
+}
+
+fn validate_network_connection(connection_name: &str, network_config: &toml::Table) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    if !network_config.contains_key("network_name") {
+        issues.push(Issue::Error(format!(
+            "network_connection.{connection_name}.network_name: the network name is missing"
+        )));
+    }
+    for field in URL_FIELDS {
+        let Some(value) = network_config.get(*field) else {
+            continue;
+        };
+        let path = format!("network_connection.{connection_name}.{field}");
+        match value.as_str().map(url::Url::parse) {
+            Some(Ok(url)) if !matches!(url.scheme(), "http" | "https") => {
+                issues.push(Issue::Warning(format!(
+                    "{path}: unexpected URL scheme \"{}\"",
+                    url.scheme()
+                )))
+            }
+            Some(Ok(_)) => {}
+            Some(Err(err)) => issues.push(Issue::Error(format!("{path}: invalid URL ({err})"))),
+            None => issues.push(Issue::Error(format!("{path}: expected a URL string"))),
+        }
+    }
+    if let Some(toml::Value::String(credentials_dir)) = network_config.get("credentials_dir") {
+        issues.extend(
+            check_writable_dir(
+                &format!("network_connection.{connection_name}.credentials_dir"),
+                credentials_dir.as_ref(),
+            )
+            .map(Issue::Warning),
+        );
+    }
+    issues
+}
+
+/// Duplicated `[network_connection.<name>]` tables.
+fn duplicate_network_connections(config_toml: &str) -> Vec<Issue> {
+    let mut connection_names = std::collections::BTreeMap::<&str, usize>::new();
+    for line in config_toml.lines() {
+        if let Some(connection_name) = line
+            .trim()
+            .strip_prefix("[network_connection.")
+            .and_then(|rest| rest.strip_suffix(']'))
+        {
+            *connection_names
+                .entry(connection_name.trim().trim_matches('"'))
+                .or_default() += 1;
+        }
+    }
+    connection_names
+        .into_iter()
+        .filter(|(_, count)| *count > 1)
+        .map(|(connection_name, count)| {
+            Issue::Error(format!(
+                "network_connection.{connection_name}: the network connection is defined {count} times"
+            ))
+        })
+        .collect()
+}
+
+fn check_writable_dir(field: &str, dir: &std::path::Path) -> Option<String> {
+    if !dir.is_dir() {
+        return Some(format!("{field}: the directory {dir:?} does not exist"));
+    }
+    let probe_file = dir.join(format!(".near-cli-write-check-{}", std::process::id()));
+    match std::fs::write(&probe_file, b"") {
+        Ok(()) => {
+            let _ = std::fs::remove_file(&probe_file);
+            None
+        }
+        Err(err) => Some(format!(
+            "{field}: the directory {dir:?} is not writable ({err})"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_validation_issues() {
+        let credentials_home_dir = std::env::temp_dir();
+        let valid_config = format!(
+            r#"version = "2"
+credentials_home_dir = {credentials_home_dir:?}
+
+[network_connection.mainnet]
+network_name = "mainnet"
+rpc_url = "https://rpc.mainnet.near.org/"
+wallet_url = "https://app.mynearwallet.com/"
+explorer_transaction_url = "https://explorer.near.org/transactions/"
+"#
+        );
+        assert_eq!(validate_config_toml(&valid_config), vec![]);
+
+        let invalid_config = valid_config
+            .replace("https://rpc.mainnet.near.org/", "rpc.mainnet.near.org")
+            .replace("https://app.mynearwallet.com/", "ftp://wallet.near.org/")
+            + "\n[network_connection.mainnet]\nnetwork_name = \"mainnet\"\n";
+        assert_eq!(
+            validate_config_toml(&invalid_config),
+            vec![Issue::Error(
+                "network_connection.mainnet: the network connection is defined 2 times".to_string()
+            )]
+        );
+
+        let invalid_config = valid_config
+            .replace("https://rpc.mainnet.near.org/", "rpc.mainnet.near.org")
+            .replace("https://app.mynearwallet.com/", "ftp://wallet.near.org/")
+            .replace(
+                &format!("{credentials_home_dir:?}"),
+                "\"/nonexistent/near-credentials\"",
+            );
+        assert_eq!(
+            validate_config_toml(&invalid_config),
+            vec![
+                Issue::Error(
+                    "credentials_home_dir: the directory \"/nonexistent/near-credentials\" does not exist"
+                        .to_string()
+                ),
+                Issue::Error(
+                    "network_connection.mainnet.rpc_url: invalid URL (relative URL without a base)"
+                        .to_string()
+                ),
+                Issue::Warning(
+                    "network_connection.mainnet.wallet_url: unexpected URL scheme \"ftp\"".to_string()
+                ),
+            ]
+        );
+    }
+}
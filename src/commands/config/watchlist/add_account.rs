@@ -0,0 +1,64 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = AddAccountToWatchlistContext)]
+pub struct AddAccountToWatchlist {
+    #[interactive_clap(skip_default_input_arg)]
+    /// What Account ID do you want to add to the watchlist?
+    account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the name of the network?
+    network: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct AddAccountToWatchlistContext;
+
+impl AddAccountToWatchlistContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<AddAccountToWatchlist as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let mut config = previous_context.config;
+        if !config.network_connection.contains_key(&scope.network) {
+            return Err(color_eyre::eyre::eyre!(
+                "Network connection \"{}\" is not found in config.toml",
+                &scope.network
+            ));
+        }
+        let watched_account = crate::config::WatchedAccount {
+            account_id: scope.account_id.clone().into(),
+            network_name: scope.network.clone(),
+        };
+        if config.watchlist.contains(&watched_account) {
+            eprintln!(
+                "\nAccount <{}> on network <{}> is already in the watchlist",
+                watched_account.account_id, watched_account.network_name
+            );
+            return Ok(Self);
+        }
+        config.watchlist.push(watched_account);
+        eprintln!();
+        config.write_config_toml()?;
+        eprintln!(
+            "Account <{}> on network <{}> was successfully added to the watchlist",
+            &scope.account_id, &scope.network
+        );
+        Ok(Self)
+    }
+}
+
+impl AddAccountToWatchlist {
+    pub fn input_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_non_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "What Account ID do you want to add to the watchlist?",
+        )
+    }
+
+    fn input_network(context: &crate::GlobalContext) -> color_eyre::eyre::Result<Option<String>> {
+        crate::common::input_network_name(&context.config, &[])
+    }
+}
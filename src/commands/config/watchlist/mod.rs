@@ -0,0 +1,25 @@
+use strum::{EnumDiscriminants, EnumIter, EnumMessage};
+
+mod add_account;
+mod remove_account;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(context = crate::GlobalContext)]
+pub struct WatchlistCommands {
+    #[interactive_clap(subcommand)]
+    watchlist_actions: WatchlistActions,
+}
+
+#[derive(Debug, EnumDiscriminants, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(context = crate::GlobalContext)]
+#[strum_discriminants(derive(EnumMessage, EnumIter))]
+#[non_exhaustive]
+/// What do you want to do with the accounts watchlist?
+pub enum WatchlistActions {
+    #[strum_discriminants(strum(message = "add      - Add an account to the watchlist"))]
+    /// Add an account to the watchlist
+    Add(self::add_account::AddAccountToWatchlist),
+    #[strum_discriminants(strum(message = "remove   - Remove an account from the watchlist"))]
+    /// Remove an account from the watchlist
+    Remove(self::remove_account::RemoveAccountFromWatchlist),
+}
@@ -0,0 +1,74 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = RemoveAccountFromWatchlistContext)]
+pub struct RemoveAccountFromWatchlist {
+    #[interactive_clap(skip_default_input_arg)]
+    /// What Account ID do you want to remove from the watchlist?
+    account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the name of the network?
+    network: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct RemoveAccountFromWatchlistContext;
+
+impl RemoveAccountFromWatchlistContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<RemoveAccountFromWatchlist as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let mut config = previous_context.config;
+        let account_id: near_primitives::types::AccountId = scope.account_id.clone().into();
+        let watchlist_len = config.watchlist.len();
+        config.watchlist.retain(|watched_account| {
+            watched_account.account_id != account_id
+                || watched_account.network_name != scope.network
+        });
+        if config.watchlist.len() == watchlist_len {
+            return Err(color_eyre::eyre::eyre!(
+                "Account <{}> on network <{}> is not in the watchlist",
+                account_id,
+                &scope.network
+            ));
+        }
+        eprintln!();
+        config.write_config_toml()?;
+        eprintln!(
+            "Account <{}> on network <{}> was successfully removed from the watchlist",
+            account_id, &scope.network
+        );
+        Ok(Self)
+    }
+}
+
+impl RemoveAccountFromWatchlist {
+    fn input_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        let account_ids = context
+            .config
+            .watchlist
+            .iter()
+            .map(|watched_account| watched_account.account_id.to_string())
+            .collect::<std::collections::BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        if account_ids.is_empty() {
+            return Err(color_eyre::eyre::eyre!("The watchlist is empty"));
+        }
+        let Some(account_id) = crate::common::select(
+            "What Account ID do you want to remove from the watchlist?",
+            account_ids,
+        )?
+        else {
+            return Ok(None);
+        };
+        Ok(Some(account_id.parse()?))
+    }
+
+    fn input_network(context: &crate::GlobalContext) -> color_eyre::eyre::Result<Option<String>> {
+        crate::common::input_network_name(&context.config, &[])
+    }
+}
@@ -0,0 +1,446 @@
+//! The check of a function call against the ABI of the contract, if it has one: the function must
+//! exist with the kind of the call, and the JSON arguments must match the schemas of its
+//! parameters. A mismatch is only a warning (the ABI can be outdated).
+
+use serde_json::Value;
+
+/// Prints a warning for each mismatch between the call and the ABI of the contract.
+pub fn warn_on_abi_mismatch(
+    network_config: &crate::config::NetworkConfig,
+    block_reference: &near_primitives::types::BlockReference,
+    contract_account_id: &near_primitives::types::AccountId,
+    function_name: &str,
+    function_args: &[u8],
+    function_kind: near_abi::AbiFunctionKind,
+) {
+    let abi_root = match tokio::runtime::Runtime::new().unwrap().block_on(
+        super::super::inspect::get_contract_abi(
+            &network_config.json_rpc_client(),
+            block_reference,
+            contract_account_id,
+        ),
+    ) {
+        Ok(abi_root) => abi_root,
+        Err(err) => {
+            tracing::debug!("The call is not checked against the contract ABI: {err}");
+            return;
+        }
+    };
+    for mismatch in abi_mismatches(&abi_root, function_name, function_args, function_kind) {
+        eprintln!("WARNING: {mismatch} (according to the ABI of <{contract_account_id}>)");
+    }
+}
+
+fn abi_mismatches(
+    abi_root: &near_abi::AbiRoot,
+    function_name: &str,
+    function_args: &[u8],
+    function_kind: near_abi::AbiFunctionKind,
+) -> Vec<String> {
+    let Some(function) = abi_root
+        .body
+        .functions
+        .iter()
+        .find(|function| function.name == function_name)
+    else {
+        let function_names = abi_root
+            .body
+            .functions
+            .iter()
+            .filter(|function| function.kind == function_kind)
+            .map(|function| function.name.as_str())
+            .collect::<Vec<_>>();
+        return vec![format!(
+            "The contract has no function '{function_name}' (its {} functions are: {})",
+            kind_name(&function_kind),
+            function_names.join(", ")
+        )];
+    };
+
+    let mut mismatches = Vec::new();
+    match (&function.kind, &function_kind) {
+        (near_abi::AbiFunctionKind::Call, near_abi::AbiFunctionKind::View) => mismatches.push(format!(
+            "'{function_name}' is a change function, a read-only call of it fails if it changes the state"
+        )),
+        (near_abi::AbiFunctionKind::View, near_abi::AbiFunctionKind::Call) => mismatches.push(format!(
+            "'{function_name}' is a read-only function, it can be called without a transaction (and without gas fees)"
+        )),
+        _ => {}
+    }
+    // The Borsh arguments are not checked
+    let near_abi::AbiParameters::Json { args: parameters } = &function.params else {
+        return mismatches;
+    };
+    let args = if function_args.is_empty() {
+        Value::Object(serde_json::Map::new())
+    } else {
+        match serde_json::from_slice::<Value>(function_args) {
+            Ok(args) => args,
+            Err(_) => {
+                mismatches.push(format!(
+                    "The arguments of '{function_name}' must be a JSON object"
+                ));
+                return mismatches;
+            }
+        }
+    };
+    let Value::Object(args) = args else {
+        mismatches.push(format!(
+            "The arguments of '{function_name}' must be a JSON object, not {}",
+            json_type_name(&args)
+        ));
+        return mismatches;
+    };
+
+    let definitions = serde_json::to_value(&abi_root.body.root_schema)
+        .ok()
+        .and_then(|root_schema| root_schema.get("definitions").cloned())
+        .unwrap_or(Value::Null);
+    let parameter_names = parameters
+        .iter()
+        .map(|parameter| parameter.name.as_str())
+        .collect::<Vec<_>>();
+    for arg_name in args.keys() {
+        if !parameter_names.contains(&arg_name.as_str()) {
+            mismatches.push(format!(
+                "'{function_name}' has no parameter '{arg_name}' (its parameters are: {})",
+                parameter_names.join(", ")
+            ));
+        }
+    }
+    for parameter in parameters {
+        let Ok(schema) = serde_json::to_value(&parameter.type_schema) else {
+            continue;
+        };
+        let result = match args.get(&parameter.name) {
+            // The optional parameters accept null
+            None => validate(&schema, &Value::Null, &definitions, "")
+                .map_err(|_| "the argument is missing".to_string()),
+            Some(value) => validate(&schema, value, &definitions, ""),
+        };
+        if let Err(err) = result {
+            mismatches.push(format!(
+                "The argument '{}' of '{function_name}' does not match its schema: {err}",
+                parameter.name
+            ));
+        }
+    }
+    mismatches
+}
+
+fn kind_name(function_kind: &near_abi::AbiFunctionKind) -> &'static str {
+    match function_kind {
+        near_abi::AbiFunctionKind::View => "read-only",
+        near_abi::AbiFunctionKind::Call => "change",
+    }
+}
+
+fn json_type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(number) if number.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn matches_type(type_name: &str, value: &Value) -> bool {
+    match type_name {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => {
+            value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|n| n.fract() == 0.0)
+        }
+        _ => true,
+    }
+}
+
+/// The subset of the JSON Schema that the ABI generator (schemars) uses: types, `$ref` to the
+/// definitions of the ABI, enums and constants, `anyOf`/`oneOf`/`allOf`, object properties and
+/// array items. Formats and numeric ranges are not checked.
+fn validate(schema: &Value, value: &Value, definitions: &Value, path: &str) -> Result<(), String> {
+    let at = |path: &str| {
+        if path.is_empty() {
+            String::new()
+        } else {
+            format!(" at '{path}'")
+        }
+    };
+    let schema = match schema {
+        Value::Bool(false) => return Err(format!("no value is allowed{}", at(path))),
+        Value::Object(schema) => schema,
+        _ => return Ok(()),
+    };
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        // The references outside the definitions are not resolved
+        if let Some(definition) = reference
+            .strip_prefix("#/definitions/")
+            .and_then(|name| definitions.get(name))
+        {
+            validate(definition, value, definitions, path)?;
+        }
+    }
+    if let Some(types) = schema.get("type") {
+        let types: Vec<&str> = match types {
+            Value::String(type_name) => vec![type_name.as_str()],
+            Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+            _ => vec![],
+        };
+        if !types.is_empty() && !types.iter().any(|type_name| matches_type(type_name, value)) {
+            return Err(format!(
+                "expected {}{}, got {}",
+                types.join(" or "),
+                at(path),
+                json_type_name(value)
+            ));
+        }
+    }
+    if let Some(Value::Array(allowed_values)) = schema.get("enum") {
+        if !allowed_values.contains(value) {
+            return Err(format!(
+                "expected one of {}{}, got {value}",
+                allowed_values
+                    .iter()
+                    .map(Value::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                at(path)
+            ));
+        }
+    }
+    if let Some(constant) = schema.get("const") {
+        if constant != value {
+            return Err(format!("expected {constant}{}, got {value}", at(path)));
+        }
+    }
+    for keyword in ["anyOf", "oneOf"] {
+        if let Some(Value::Array(subschemas)) = schema.get(keyword) {
+            if !subschemas
+                .iter()
+                .any(|subschema| validate(subschema, value, definitions, path).is_ok())
+            {
+                return Err(format!(
+                    "{value} matches none of the allowed variants{}",
+                    at(path)
+                ));
+            }
+        }
+    }
+    if let Some(Value::Array(subschemas)) = schema.get("allOf") {
+        for subschema in subschemas {
+            validate(subschema, value, definitions, path)?;
+        }
+    }
+    match value {
+        Value::Object(object) => {
+            let properties = schema.get("properties").and_then(Value::as_object);
+            if let Some(Value::Array(required)) = schema.get("required") {
+                for name in required.iter().filter_map(Value::as_str) {
+                    if !object.contains_key(name) {
+                        return Err(format!("the field '{path}.{name}' is missing"));
+                    }
+                }
+            }
+            for (name, field_value) in object {
+                let field_path = format!("{path}.{name}");
+                match properties.and_then(|properties| properties.get(name)) {
+                    Some(field_schema) => {
+                        validate(field_schema, field_value, definitions, &field_path)?
+                    }
+                    None if schema.get("additionalProperties") == Some(&Value::Bool(false)) => {
+                        return Err(format!("the field '{field_path}' is not allowed"))
+                    }
+                    None => {
+                        if let Some(additional_properties) = schema.get("additionalProperties") {
+                            validate(additional_properties, field_value, definitions, &field_path)?;
+                        }
+                    }
+                }
+            }
+        }
+        Value::Array(items) => match schema.get("items") {
+            Some(Value::Array(item_schemas)) => {
+                for (index, (item_schema, item)) in item_schemas.iter().zip(items).enumerate() {
+                    validate(item_schema, item, definitions, &format!("{path}[{index}]"))?;
+                }
+            }
+            Some(item_schema) => {
+                for (index, item) in items.iter().enumerate() {
+                    validate(item_schema, item, definitions, &format!("{path}[{index}]"))?;
+                }
+            }
+            None => {}
+        },
+        _ => {}
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn abi_root() -> near_abi::AbiRoot {
+        serde_json::from_value(serde_json::json!({
+            "schema_version": "0.4.0",
+            "metadata": {},
+            "body": {
+                "functions": [
+                    {
+                        "name": "ft_transfer",
+                        "kind": "call",
+                        "modifiers": ["payable"],
+                        "params": {
+                            "serialization_type": "json",
+                            "args": [
+                                { "name": "receiver_id", "type_schema": { "$ref": "#/definitions/AccountId" } },
+                                { "name": "amount", "type_schema": { "$ref": "#/definitions/U128" } },
+                                { "name": "memo", "type_schema": { "type": ["string", "null"] } }
+                            ]
+                        }
+                    },
+                    {
+                        "name": "ft_balance_of",
+                        "kind": "view",
+                        "params": {
+                            "serialization_type": "json",
+                            "args": [
+                                { "name": "account_id", "type_schema": { "$ref": "#/definitions/AccountId" } }
+                            ]
+                        }
+                    },
+                    {
+                        "name": "set_config",
+                        "kind": "call",
+                        "params": {
+                            "serialization_type": "json",
+                            "args": [
+                                { "name": "config", "type_schema": { "$ref": "#/definitions/Config" } }
+                            ]
+                        }
+                    }
+                ],
+                "root_schema": {
+                    "$schema": "http://json-schema.org/draft-07/schema#",
+                    "title": "String",
+                    "type": "string",
+                    "definitions": {
+                        "AccountId": { "type": "string" },
+                        "U128": { "type": "string" },
+                        "Config": {
+                            "type": "object",
+                            "required": ["fee"],
+                            "properties": {
+                                "fee": { "type": "integer", "format": "uint32", "minimum": 0.0 },
+                                "mode": { "type": "string", "enum": ["fast", "safe"] },
+                                "owners": { "type": "array", "items": { "$ref": "#/definitions/AccountId" } }
+                            },
+                            "additionalProperties": false
+                        }
+                    }
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    fn mismatches(
+        function_name: &str,
+        args: Value,
+        function_kind: near_abi::AbiFunctionKind,
+    ) -> Vec<String> {
+        abi_mismatches(
+            &abi_root(),
+            function_name,
+            &serde_json::to_vec(&args).unwrap(),
+            function_kind,
+        )
+    }
+
+    #[test]
+    fn calls_matching_the_abi() {
+        assert!(mismatches(
+            "ft_transfer",
+            serde_json::json!({ "receiver_id": "bob.near", "amount": "1000" }),
+            near_abi::AbiFunctionKind::Call
+        )
+        .is_empty());
+        assert!(mismatches(
+            "set_config",
+            serde_json::json!({ "config": { "fee": 5, "mode": "safe", "owners": ["alice.near"] } }),
+            near_abi::AbiFunctionKind::Call
+        )
+        .is_empty());
+        assert!(abi_mismatches(
+            &abi_root(),
+            "ft_balance_of",
+            br#"{"account_id":"bob.near"}"#,
+            near_abi::AbiFunctionKind::View
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn calls_not_matching_the_abi() {
+        assert_eq!(
+            mismatches("ft_transfr", serde_json::json!({}), near_abi::AbiFunctionKind::Call),
+            ["The contract has no function 'ft_transfr' (its change functions are: ft_transfer, set_config)"]
+        );
+        assert_eq!(
+            mismatches(
+                "ft_transfer",
+                serde_json::json!({ "receiver_id": "bob.near", "amount": 1000, "msg": "" }),
+                near_abi::AbiFunctionKind::Call
+            ),
+            [
+                "'ft_transfer' has no parameter 'msg' (its parameters are: receiver_id, amount, memo)",
+                "The argument 'amount' of 'ft_transfer' does not match its schema: expected string, got integer"
+            ]
+        );
+        assert_eq!(
+            mismatches("ft_transfer", serde_json::json!({ "amount": "1" }), near_abi::AbiFunctionKind::Call),
+            ["The argument 'receiver_id' of 'ft_transfer' does not match its schema: the argument is missing"]
+        );
+        assert!(mismatches(
+            "ft_balance_of",
+            serde_json::json!({ "account_id": "bob.near" }),
+            near_abi::AbiFunctionKind::Call
+        )[0]
+        .contains("read-only function"));
+        assert!(mismatches(
+            "ft_balance_of",
+            serde_json::json!([1]),
+            near_abi::AbiFunctionKind::Call
+        )[1]
+        .contains("must be a JSON object, not array"));
+
+        let config_mismatch = |config: Value| {
+            mismatches(
+                "set_config",
+                serde_json::json!({ "config": config }),
+                near_abi::AbiFunctionKind::Call,
+            )
+        };
+        assert_eq!(
+            config_mismatch(serde_json::json!({ "fee": 1, "mode": "slow" })),
+            ["The argument 'config' of 'set_config' does not match its schema: expected one of \"fast\", \"safe\" at '.mode', got \"slow\""]
+        );
+        assert!(config_mismatch(serde_json::json!({ "mode": "fast" }))[0]
+            .ends_with("the field '.fee' is missing"));
+        assert!(
+            config_mismatch(serde_json::json!({ "fee": 1, "owners": [1] }))[0]
+                .ends_with("expected string at '.owners[0]', got integer")
+        );
+        assert!(
+            config_mismatch(serde_json::json!({ "fee": 1, "extra": true }))[0]
+                .ends_with("the field '.extra' is not allowed")
+        );
+    }
+}
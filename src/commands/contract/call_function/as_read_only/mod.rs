@@ -1,7 +1,7 @@
 use color_eyre::eyre::Context;
 
 use crate::common::CallResultExt;
-use crate::common::JsonRpcClientExt;
+use crate::common::{JsonRpcClientExt, RpcQueryResponseExt};
 
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = crate::GlobalContext)]
@@ -10,6 +10,9 @@ pub struct CallFunctionView {
     #[interactive_clap(skip_default_input_arg)]
     /// What is the contract account ID?
     contract_account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long_vec_multiple_opt)]
+    /// A function argument as name=value (a string) or name:=value (raw JSON), e.g. --arg receiver_id=alice.near --arg amount:=5 (instead of the function arguments type and the arguments)
+    arg: Vec<super::call_function_args_type::key_value_args::FunctionArg>,
     #[interactive_clap(subargs)]
     /// Select function
     function: Function,
@@ -19,6 +22,7 @@ pub struct CallFunctionView {
 pub struct CallFunctionViewContext {
     global_context: crate::GlobalContext,
     contract_account_id: near_primitives::types::AccountId,
+    key_value_function_args: Option<String>,
 }
 
 impl CallFunctionViewContext {
@@ -29,6 +33,9 @@ impl CallFunctionViewContext {
         Ok(Self {
             global_context: previous_context,
             contract_account_id: scope.contract_account_id.clone().into(),
+            key_value_function_args: super::call_function_args_type::key_value_function_args(
+                &scope.arg,
+            )?,
         })
     }
 }
@@ -55,8 +62,12 @@ pub struct Function {
     #[interactive_clap(skip_default_input_arg)]
     /// How do you want to pass the function call arguments?
     function_args_type: super::call_function_args_type::FunctionArgsType,
+    #[interactive_clap(skip_default_input_arg)]
     /// Enter the arguments to this function:
-    function_args: String,
+    function_args: super::call_function_args_type::FunctionArgs,
+    #[interactive_clap(long)]
+    /// Parse the JSON arguments leniently (comments, trailing commas, single quotes and unquoted keys are allowed)
+    args_json5: bool,
     #[interactive_clap(named_arg)]
     /// Select network
     network_config: crate::network_view_at_block::NetworkViewAtBlockArgs,
@@ -70,14 +81,20 @@ impl FunctionContext {
         previous_context: CallFunctionViewContext,
         scope: &<Function as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        super::call_function_args_type::ensure_no_conflicting_function_args(
+            previous_context.key_value_function_args.as_deref(),
+            &scope.function_args_type,
+            &scope.function_args.args,
+        )?;
         let on_after_getting_block_reference_callback: crate::network_view_at_block::OnAfterGettingBlockReferenceCallback = std::sync::Arc::new({
             let function_args = scope.function_args.clone();
             let function_args_type = scope.function_args_type.clone();
+            let args_json5 = scope.args_json5;
             let account_id: near_primitives::types::AccountId = previous_context.contract_account_id.clone();
             let function_name = scope.function_name.clone();
 
             move |network_config, block_reference| {
-                call_view_function(network_config, &account_id, &function_name, function_args.clone(), function_args_type.clone(), block_reference)
+                call_view_function(network_config, &account_id, &function_name, function_args.clone(), function_args_type.clone(), args_json5, block_reference)
             }
         });
 
@@ -97,11 +114,25 @@ impl From<FunctionContext> for crate::network_view_at_block::ArgsForViewContext
 
 impl Function {
     fn input_function_args_type(
-        _context: &CallFunctionViewContext,
+        context: &CallFunctionViewContext,
     ) -> color_eyre::eyre::Result<Option<super::call_function_args_type::FunctionArgsType>> {
+        if context.key_value_function_args.is_some() {
+            return Ok(Some(
+                super::call_function_args_type::FunctionArgsType::JsonArgs,
+            ));
+        }
         super::call_function_args_type::input_function_args_type()
     }
 
+    fn input_function_args(
+        context: &CallFunctionViewContext,
+    ) -> color_eyre::eyre::Result<Option<super::call_function_args_type::FunctionArgs>> {
+        if let Some(key_value_function_args) = &context.key_value_function_args {
+            return Ok(Some(key_value_function_args.clone().into()));
+        }
+        super::call_function_args_type::input_function_args()
+    }
+
     fn input_function_name(
         context: &CallFunctionViewContext,
     ) -> color_eyre::eyre::Result<Option<String>> {
@@ -114,20 +145,45 @@ fn call_view_function(
     network_config: &crate::config::NetworkConfig,
     account_id: &near_primitives::types::AccountId,
     function_name: &str,
-    function_args: String,
+    function_args: super::call_function_args_type::FunctionArgs,
     function_args_type: super::call_function_args_type::FunctionArgsType,
+    args_json5: bool,
     block_reference: &near_primitives::types::BlockReference,
 ) -> crate::CliResult {
-    let args = super::call_function_args_type::function_args(function_args, function_args_type)?;
-    let call_result = network_config
+    let args = super::call_function_args_type::function_args(
+        function_args,
+        function_args_type,
+        args_json5,
+    )?;
+    super::abi_check::warn_on_abi_mismatch(
+        network_config,
+        block_reference,
+        account_id,
+        function_name,
+        &args,
+        near_abi::AbiFunctionKind::View,
+    );
+    let rpc_query_response = network_config
         .json_rpc_client()
-        .blocking_call_view_function(account_id, function_name, args, block_reference.clone())
+        .blocking_call(near_jsonrpc_client::methods::query::RpcQueryRequest {
+            block_reference: block_reference.clone(),
+            request: near_primitives::views::QueryRequest::CallFunction {
+                account_id: account_id.clone(),
+                method_name: function_name.to_owned(),
+                args: near_primitives::types::FunctionArgs::from(args),
+            },
+        })
         .wrap_err_with(|| {
             format!(
                 "Failed to fetch query for read-only function call: '{}' (contract <{}> on network <{}>)",
                 function_name, account_id, network_config.network_name
             )
         })?;
+    crate::network_view_at_block::display_viewed_at_block(
+        rpc_query_response.block_height,
+        &rpc_query_response.block_hash,
+    );
+    let call_result = rpc_query_response.call_result()?;
     call_result.print_logs();
     eprintln!("Result:");
     if call_result.result.is_empty() {
@@ -7,6 +7,9 @@ pub struct CallFunction {
     #[interactive_clap(skip_default_input_arg)]
     /// What is the contract account ID?
     contract_account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long_vec_multiple_opt)]
+    /// A function argument as name=value (a string) or name:=value (raw JSON), e.g. --arg receiver_id=alice.near --arg amount:=5 (instead of the function arguments type and the arguments)
+    arg: Vec<super::call_function_args_type::key_value_args::FunctionArg>,
     #[interactive_clap(subargs)]
     /// Select function
     function: Function,
@@ -16,6 +19,7 @@ pub struct CallFunction {
 pub struct CallFunctionContext {
     global_context: crate::GlobalContext,
     contract_account_id: near_primitives::types::AccountId,
+    key_value_function_args: Option<String>,
 }
 
 impl CallFunctionContext {
@@ -26,6 +30,9 @@ impl CallFunctionContext {
         Ok(Self {
             global_context: previous_context,
             contract_account_id: scope.contract_account_id.clone().into(),
+            key_value_function_args: super::call_function_args_type::key_value_function_args(
+                &scope.arg,
+            )?,
         })
     }
 }
@@ -52,8 +59,12 @@ pub struct Function {
     #[interactive_clap(skip_default_input_arg)]
     /// How do you want to pass the function call arguments?
     function_args_type: super::call_function_args_type::FunctionArgsType,
+    #[interactive_clap(skip_default_input_arg)]
     /// Enter the arguments to this function:
-    function_args: String,
+    function_args: super::call_function_args_type::FunctionArgs,
+    #[interactive_clap(long)]
+    /// Parse the JSON arguments leniently (comments, trailing commas, single quotes and unquoted keys are allowed)
+    args_json5: bool,
     #[interactive_clap(named_arg)]
     /// Enter gas for function call
     prepaid_gas: PrepaidGas,
@@ -72,9 +83,15 @@ impl FunctionContext {
         previous_context: CallFunctionContext,
         scope: &<Function as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        super::call_function_args_type::ensure_no_conflicting_function_args(
+            previous_context.key_value_function_args.as_deref(),
+            &scope.function_args_type,
+            &scope.function_args.args,
+        )?;
         let function_args = super::call_function_args_type::function_args(
             scope.function_args.clone(),
             scope.function_args_type.clone(),
+            scope.args_json5,
         )?;
         Ok(Self {
             global_context: previous_context.global_context,
@@ -87,11 +104,25 @@ impl FunctionContext {
 
 impl Function {
     fn input_function_args_type(
-        _context: &CallFunctionContext,
+        context: &CallFunctionContext,
     ) -> color_eyre::eyre::Result<Option<super::call_function_args_type::FunctionArgsType>> {
+        if context.key_value_function_args.is_some() {
+            return Ok(Some(
+                super::call_function_args_type::FunctionArgsType::JsonArgs,
+            ));
+        }
         super::call_function_args_type::input_function_args_type()
     }
 
+    fn input_function_args(
+        context: &CallFunctionContext,
+    ) -> color_eyre::eyre::Result<Option<super::call_function_args_type::FunctionArgs>> {
+        if let Some(key_value_function_args) = &context.key_value_function_args {
+            return Ok(Some(key_value_function_args.clone().into()));
+        }
+        super::call_function_args_type::input_function_args()
+    }
+
     fn input_function_name(
         context: &CallFunctionContext,
     ) -> color_eyre::eyre::Result<Option<String>> {
@@ -256,8 +287,19 @@ impl From<SignerAccountIdContext> for crate::commands::ActionContext {
             std::sync::Arc::new({
                 let signer_account_id = item.signer_account_id.clone();
                 let receiver_account_id = item.contract_account_id.clone();
+                let offline = item.global_context.offline;
 
-                move |_network_config| {
+                move |network_config| {
+                    if !offline {
+                        super::abi_check::warn_on_abi_mismatch(
+                            network_config,
+                            &near_primitives::types::Finality::Final.into(),
+                            &receiver_account_id,
+                            &item.function_name,
+                            &item.function_args,
+                            near_abi::AbiFunctionKind::Call,
+                        );
+                    }
                     Ok(crate::commands::PrepopulatedTransaction {
                         signer_id: signer_account_id.clone(),
                         receiver_id: receiver_account_id.clone(),
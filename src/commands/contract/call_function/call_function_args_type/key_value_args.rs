@@ -0,0 +1,201 @@
+//! Function call arguments passed as individual `--arg` flags.
+//!
+//! `name=value` sets a string and `name:=value` sets a raw JSON value, so
+//! `--arg receiver=alice.near --arg amount:=5` builds `{"receiver":"alice.near","amount":5}`.
+//! Dots in the name build nested objects (`--arg msg.action=stake`). A backslash escapes the
+//! next character of the name, e.g. `--arg 'a\.b=1'` sets the `a.b` key itself.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionArg {
+    path: Vec<String>,
+    value: serde_json::Value,
+}
+
+impl interactive_clap::ToCli for FunctionArg {
+    type CliVariant = FunctionArg;
+}
+
+impl std::str::FromStr for FunctionArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut path = vec![String::new()];
+        let mut chars = s.char_indices();
+        let (is_raw_json, value) = loop {
+            match chars.next() {
+                Some((_, '\\')) => match chars.next() {
+                    Some((_, escaped)) => path
+                        .last_mut()
+                        .expect("The path is not empty")
+                        .push(escaped),
+                    None => {
+                        return Err(format!("The argument <{s}> ends with an escape character"))
+                    }
+                },
+                Some((_, '.')) => path.push(String::new()),
+                Some((position, ':')) if s[position + 1..].starts_with('=') => {
+                    break (true, &s[position + 2..]);
+                }
+                Some((position, '=')) => break (false, &s[position + 1..]),
+                Some((_, c)) => path.last_mut().expect("The path is not empty").push(c),
+                None => {
+                    return Err(format!(
+                        "The argument <{s}> must be in the form name=value or name:=json"
+                    ))
+                }
+            }
+        };
+        if path.iter().any(String::is_empty) {
+            return Err(format!("The argument <{s}> has an empty name"));
+        }
+        let value = if is_raw_json {
+            serde_json::from_str(value).map_err(|err| {
+                format!("The value of the argument <{s}> is not valid JSON: {err}")
+            })?
+        } else {
+            serde_json::Value::String(value.to_string())
+        };
+        Ok(Self { path, value })
+    }
+}
+
+impl std::fmt::Display for FunctionArg {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let path = self
+            .path
+            .iter()
+            .map(|key| {
+                key.chars().fold(String::new(), |mut escaped, c| {
+                    if matches!(c, '\\' | '.' | ':' | '=') {
+                        escaped.push('\\');
+                    }
+                    escaped.push(c);
+                    escaped
+                })
+            })
+            .collect::<Vec<_>>()
+            .join(".");
+        match &self.value {
+            serde_json::Value::String(value) => write!(f, "{path}={value}"),
+            value => write!(f, "{path}:={value}"),
+        }
+    }
+}
+
+/// Builds the JSON object of the arguments (a name cannot be set twice).
+pub fn to_json(function_args: &[FunctionArg]) -> Result<serde_json::Value, String> {
+    let mut args = serde_json::Map::new();
+    for function_arg in function_args {
+        let (name, parents) = function_arg
+            .path
+            .split_last()
+            .expect("The path is not empty");
+        let mut object = &mut args;
+        for (depth, key) in parents.iter().enumerate() {
+            object = match object
+                .entry(key.clone())
+                .or_insert_with(|| serde_json::Value::Object(Default::default()))
+            {
+                serde_json::Value::Object(object) => object,
+                _ => {
+                    return Err(format!(
+                        "The argument <{}> is already set to a value that is not an object",
+                        function_arg.path[..=depth].join(".")
+                    ))
+                }
+            };
+        }
+        if object
+            .insert(name.clone(), function_arg.value.clone())
+            .is_some()
+        {
+            return Err(format!(
+                "The argument <{}> is set more than once",
+                function_arg.path.join(".")
+            ));
+        }
+    }
+    Ok(serde_json::Value::Object(args))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(function_args: &[&str]) -> Result<String, String> {
+        let function_args = function_args
+            .iter()
+            .map(|function_arg| function_arg.parse())
+            .collect::<Result<Vec<FunctionArg>, _>>()?;
+        to_json(&function_args).map(|args| args.to_string())
+    }
+
+    #[test]
+    fn strings_and_raw_json_values() {
+        assert_eq!(
+            args(&["amount:=5", "receiver=alice.near"]).unwrap(),
+            r#"{"amount":5,"receiver":"alice.near"}"#
+        );
+        assert_eq!(
+            args(&["a=5", "b=true", "c=", "d==x", "e=a:=b"]).unwrap(),
+            r#"{"a":"5","b":"true","c":"","d":"=x","e":"a:=b"}"#
+        );
+        assert_eq!(
+            args(&[
+                "a:=true",
+                "b:=null",
+                "c:=[1,\"x\"]",
+                "d:={\"k\":1.5}",
+                "e:=\"5\""
+            ])
+            .unwrap(),
+            r#"{"a":true,"b":null,"c":[1,"x"],"d":{"k":1.5},"e":"5"}"#
+        );
+        assert_eq!(args(&[]).unwrap(), "{}");
+    }
+
+    #[test]
+    fn nested_objects_and_escaping() {
+        assert_eq!(
+            args(&["msg.action=stake", "msg.amount:=1", "receiver_id=pool.near"]).unwrap(),
+            r#"{"msg":{"action":"stake","amount":1},"receiver_id":"pool.near"}"#
+        );
+        assert_eq!(
+            args(&[r"a\.b=1", r"c\:d=2", r"e\=f=3", r"g\\h=4"]).unwrap(),
+            r#"{"a.b":"1","c:d":"2","e=f":"3","g\\h":"4"}"#
+        );
+        assert_eq!(
+            args(&["msg.inner.deep:=[]"]).unwrap(),
+            r#"{"msg":{"inner":{"deep":[]}}}"#
+        );
+        assert_eq!(args(&["a:@=1"]).unwrap(), r#"{"a:@":"1"}"#);
+    }
+
+    #[test]
+    fn invalid_arguments() {
+        assert!(args(&["amount"]).is_err());
+        assert!(args(&["=5"]).is_err());
+        assert!(args(&["a..b=5"]).is_err());
+        assert!(args(&["a.=5"]).is_err());
+        assert!(args(&[r"a\"]).is_err());
+        assert!(args(&["amount:=five"]).is_err());
+        assert!(args(&["a=1", "a=2"]).is_err());
+        assert!(args(&["a=1", "a.b=2"]).is_err());
+        assert!(args(&["a.b=2", "a=1"]).is_err());
+    }
+
+    #[test]
+    fn display_round_trip() {
+        for function_arg in [
+            "receiver=alice.near",
+            "amount:=5",
+            "msg.action=stake",
+            r"a\.b:={}",
+            r"c\:d=e:=f",
+        ] {
+            let parsed: FunctionArg = function_arg.parse().unwrap();
+            assert_eq!(parsed.to_string(), function_arg);
+            assert_eq!(parsed.to_string().parse::<FunctionArg>().unwrap(), parsed);
+        }
+    }
+}
@@ -0,0 +1,381 @@
+//! A lenient (JSON5-ish) JSON parser for the function call arguments.
+//!
+//! On top of strict JSON it accepts `//` and `/* */` comments, trailing commas,
+//! single-quoted strings, unquoted object keys and a leading `+` in numbers, which
+//! covers most of the arguments copy-pasted from JavaScript code.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LenientJsonError {
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl std::fmt::Display for LenientJsonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{} at line {} column {}",
+            self.message, self.line, self.column
+        )
+    }
+}
+
+impl std::error::Error for LenientJsonError {}
+
+pub fn parse(input: &str) -> Result<serde_json::Value, LenientJsonError> {
+    let mut parser = Parser {
+        chars: input.chars().collect(),
+        position: 0,
+    };
+    parser.skip_whitespace_and_comments()?;
+    let value = parser.parse_value()?;
+    parser.skip_whitespace_and_comments()?;
+    if parser.peek().is_some() {
+        return Err(parser.error("Unexpected trailing characters"));
+    }
+    Ok(value)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += 1;
+        Some(c)
+    }
+
+    fn error(&self, message: &str) -> LenientJsonError {
+        let consumed = &self.chars[..self.position.min(self.chars.len())];
+        let line = consumed.iter().filter(|c| **c == '\n').count() + 1;
+        let column = consumed.iter().rev().take_while(|c| **c != '\n').count() + 1;
+        LenientJsonError {
+            message: message.to_string(),
+            line,
+            column,
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), LenientJsonError> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.position += 1;
+                Ok(())
+            }
+            Some(c) => Err(self.error(&format!("Expected '{expected}', found '{c}'"))),
+            None => Err(self.error(&format!("Expected '{expected}', found end of input"))),
+        }
+    }
+
+    fn skip_whitespace_and_comments(&mut self) -> Result<(), LenientJsonError> {
+        loop {
+            match (self.peek(), self.chars.get(self.position + 1)) {
+                (Some(c), _) if c.is_whitespace() => self.position += 1,
+                (Some('/'), Some('/')) => {
+                    while !matches!(self.peek(), None | Some('\n')) {
+                        self.position += 1;
+                    }
+                }
+                (Some('/'), Some('*')) => {
+                    let comment_start = self.position;
+                    self.position += 2;
+                    loop {
+                        match self.next() {
+                            Some('*') if self.peek() == Some('/') => {
+                                self.position += 1;
+                                break;
+                            }
+                            Some(_) => {}
+                            None => {
+                                self.position = comment_start;
+                                return Err(self.error("Unterminated block comment"));
+                            }
+                        }
+                    }
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<serde_json::Value, LenientJsonError> {
+        match self.peek() {
+            Some('{') => self.parse_object(),
+            Some('[') => self.parse_array(),
+            Some(quote @ ('"' | '\'')) => Ok(serde_json::Value::String(self.parse_string(quote)?)),
+            Some(c) if c == '-' || c == '+' || c == '.' || c.is_ascii_digit() => {
+                self.parse_number()
+            }
+            Some(c) if is_identifier_start(c) => {
+                let start = self.position;
+                let identifier = self.parse_identifier();
+                match identifier.as_str() {
+                    "true" => Ok(serde_json::Value::Bool(true)),
+                    "false" => Ok(serde_json::Value::Bool(false)),
+                    "null" => Ok(serde_json::Value::Null),
+                    _ => {
+                        self.position = start;
+                        Err(self.error(&format!("Unexpected identifier '{identifier}'")))
+                    }
+                }
+            }
+            Some(c) => Err(self.error(&format!("Unexpected character '{c}'"))),
+            None => Err(self.error("Unexpected end of input")),
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<serde_json::Value, LenientJsonError> {
+        self.expect('{')?;
+        let mut object = serde_json::Map::new();
+        loop {
+            self.skip_whitespace_and_comments()?;
+            if self.peek() == Some('}') {
+                self.position += 1;
+                return Ok(serde_json::Value::Object(object));
+            }
+            let key = match self.peek() {
+                Some(quote @ ('"' | '\'')) => self.parse_string(quote)?,
+                Some(c) if is_identifier_start(c) => self.parse_identifier(),
+                Some(c) => return Err(self.error(&format!("Expected an object key, found '{c}'"))),
+                None => return Err(self.error("Unterminated object")),
+            };
+            self.skip_whitespace_and_comments()?;
+            self.expect(':')?;
+            self.skip_whitespace_and_comments()?;
+            let value = self.parse_value()?;
+            object.insert(key, value);
+            self.skip_whitespace_and_comments()?;
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some('}') => {}
+                Some(c) => return Err(self.error(&format!("Expected ',' or '}}', found '{c}'"))),
+                None => return Err(self.error("Unterminated object")),
+            }
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<serde_json::Value, LenientJsonError> {
+        self.expect('[')?;
+        let mut array = Vec::new();
+        loop {
+            self.skip_whitespace_and_comments()?;
+            if self.peek() == Some(']') {
+                self.position += 1;
+                return Ok(serde_json::Value::Array(array));
+            }
+            array.push(self.parse_value()?);
+            self.skip_whitespace_and_comments()?;
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some(']') => {}
+                Some(c) => return Err(self.error(&format!("Expected ',' or ']', found '{c}'"))),
+                None => return Err(self.error("Unterminated array")),
+            }
+        }
+    }
+
+    fn parse_identifier(&mut self) -> String {
+        let mut identifier = String::new();
+        while let Some(c) = self.peek() {
+            if !(is_identifier_start(c) || c.is_ascii_digit()) {
+                break;
+            }
+            identifier.push(c);
+            self.position += 1;
+        }
+        identifier
+    }
+
+    fn parse_string(&mut self, quote: char) -> Result<String, LenientJsonError> {
+        let string_start = self.position;
+        self.expect(quote)?;
+        let mut string = String::new();
+        loop {
+            match self.next() {
+                Some(c) if c == quote => return Ok(string),
+                Some('\\') => match self.next() {
+                    Some('n') => string.push('\n'),
+                    Some('t') => string.push('\t'),
+                    Some('r') => string.push('\r'),
+                    Some('b') => string.push('\u{8}'),
+                    Some('f') => string.push('\u{c}'),
+                    Some('0') => string.push('\0'),
+                    // A backslash at the end of a line continues the string on the next line
+                    Some('\n') => {}
+                    Some('u') => string.push(self.parse_unicode_escape()?),
+                    Some(c @ ('"' | '\'' | '\\' | '/')) => string.push(c),
+                    Some(c) => {
+                        self.position -= 1;
+                        return Err(self.error(&format!("Invalid escape sequence '\\{c}'")));
+                    }
+                    None => break,
+                },
+                Some('\n') => {
+                    self.position -= 1;
+                    return Err(self.error("Unescaped line break in a string"));
+                }
+                Some(c) => string.push(c),
+                None => break,
+            }
+        }
+        self.position = string_start;
+        Err(self.error("Unterminated string"))
+    }
+
+    fn parse_hex4(&mut self) -> Result<u32, LenientJsonError> {
+        let start = self.position;
+        let digits: String = (0..4).filter_map(|_| self.next()).collect();
+        u32::from_str_radix(&digits, 16).map_err(|_| {
+            self.position = start;
+            self.error("Invalid unicode escape sequence")
+        })
+    }
+
+    fn parse_unicode_escape(&mut self) -> Result<char, LenientJsonError> {
+        let start = self.position;
+        let high = self.parse_hex4()?;
+        let code_point = if (0xD800..0xDC00).contains(&high) {
+            if self.next() != Some('\\') || self.next() != Some('u') {
+                self.position = start;
+                return Err(self.error("Unpaired surrogate in a unicode escape sequence"));
+            }
+            let low = self.parse_hex4()?;
+            if !(0xDC00..0xE000).contains(&low) {
+                self.position = start;
+                return Err(self.error("Unpaired surrogate in a unicode escape sequence"));
+            }
+            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+        } else {
+            high
+        };
+        char::from_u32(code_point).ok_or_else(|| {
+            self.position = start;
+            self.error("Invalid unicode escape sequence")
+        })
+    }
+
+    fn parse_number(&mut self) -> Result<serde_json::Value, LenientJsonError> {
+        let start = self.position;
+        if self.peek() == Some('+') {
+            self.position += 1;
+        }
+        let number_start = self.position;
+        while let Some(c) = self.peek() {
+            if !(c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E')) {
+                break;
+            }
+            self.position += 1;
+        }
+        let number: String = self.chars[number_start..self.position].iter().collect();
+        // JSON5 allows a leading or trailing decimal point, strict JSON does not
+        let number = if let Some(fraction) = number.strip_prefix('.') {
+            format!("0.{fraction}")
+        } else if let Some(fraction) = number.strip_prefix("-.") {
+            format!("-0.{fraction}")
+        } else {
+            number
+        };
+        let number = match number.strip_suffix('.') {
+            Some(integer) => integer.to_string(),
+            None => number,
+        };
+        serde_json::from_str::<serde_json::Number>(&number)
+            .map(serde_json::Value::Number)
+            .map_err(|_| {
+                self.position = start;
+                self.error(&format!("Invalid number '{number}'"))
+            })
+    }
+}
+
+fn is_identifier_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_' || c == '$'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn canonical(input: &str) -> String {
+        serde_json::to_string(&parse(input).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn strict_json_is_unchanged() {
+        let input = r#"{"receiver_id": "bob.near", "amount": "100", "memo": null, "msg": ["a", 1, -2.5e3, true]}"#;
+        assert_eq!(
+            parse(input).unwrap(),
+            serde_json::from_str::<serde_json::Value>(input).unwrap()
+        );
+    }
+
+    #[test]
+    fn messy_js_inputs() {
+        let corpus = [
+            (
+                "{receiver_id: 'bob.near', amount: '100',}",
+                r#"{"amount":"100","receiver_id":"bob.near"}"#,
+            ),
+            (
+                "{\n  // the token to transfer\n  \"token_id\": \"42\", /* inline */\n}",
+                r#"{"token_id":"42"}"#,
+            ),
+            ("['a', 'b', 'c',]", r#"["a","b","c"]"#),
+            (
+                "{'it\\'s': \"a \\\"quote\\\"\"}",
+                r#"{"it's":"a \"quote\""}"#,
+            ),
+            (
+                "{n: +1, f: .5, g: 2., h: -.25}",
+                r#"{"f":0.5,"g":2,"h":-0.25,"n":1}"#,
+            ),
+            (
+                "{$ref: true, _private: false, v2: null}",
+                r#"{"$ref":true,"_private":false,"v2":null}"#,
+            ),
+            ("{emoji: '\\uD83D\\uDE00'}", r#"{"emoji":"😀"}"#),
+            ("{'a': 'line \\\ncontinued'}", r#"{"a":"line continued"}"#),
+            ("  {}  // nothing", "{}"),
+        ];
+        for (input, expected) in corpus {
+            assert_eq!(canonical(input), expected, "input: {input}");
+        }
+    }
+
+    #[test]
+    fn canonical_form_is_deterministic() {
+        assert_eq!(
+            canonical("{b: 1, a: {d: 2, c: 3}}"),
+            canonical("{a: {c: 3, d: 2}, b: 1}")
+        );
+    }
+
+    #[test]
+    fn invalid_inputs_report_position() {
+        let corpus = [
+            ("{\"a\": 1,\n \"b\" 2}", 2, 6),
+            ("{\"a\": undefined}", 1, 7),
+            ("[1, 2", 1, 6),
+            ("{\"a\": 'unterminated}", 1, 7),
+            ("{\"a\": 1} extra", 1, 10),
+            ("/* never closed", 1, 1),
+            ("{a: 1.2.3}", 1, 5),
+        ];
+        for (input, line, column) in corpus {
+            let err = parse(input).unwrap_err();
+            assert_eq!(
+                (err.line, err.column),
+                (line, column),
+                "input: {input}, error: {err}"
+            );
+        }
+    }
+}
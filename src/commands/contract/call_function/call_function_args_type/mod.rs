@@ -1,9 +1,11 @@
 use std::str::FromStr;
 
 use color_eyre::eyre::Context;
-use inquire::Select;
 use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
 
+pub mod key_value_args;
+mod lenient_json;
+
 #[derive(Debug, EnumDiscriminants, Clone, clap::ValueEnum)]
 #[strum_discriminants(derive(EnumMessage, EnumIter))]
 /// How do you want to pass the function call arguments?
@@ -65,13 +67,49 @@ impl std::fmt::Display for FunctionArgsTypeDiscriminants {
     }
 }
 
+/// The function arguments, and whether they were typed at the prompt: as JSON arguments those are
+/// parsed leniently (and the normalized form is confirmed) even without `--args-json5`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionArgs {
+    pub args: String,
+    pub entered_interactively: bool,
+}
+
+impl From<String> for FunctionArgs {
+    fn from(args: String) -> Self {
+        Self {
+            args,
+            entered_interactively: false,
+        }
+    }
+}
+
+impl std::str::FromStr for FunctionArgs {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(s.to_string().into())
+    }
+}
+
+impl std::fmt::Display for FunctionArgs {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.args)
+    }
+}
+
+impl interactive_clap::ToCli for FunctionArgs {
+    type CliVariant = FunctionArgs;
+}
+
 pub fn input_function_args_type() -> color_eyre::eyre::Result<Option<FunctionArgsType>> {
     let variants = FunctionArgsTypeDiscriminants::iter().collect::<Vec<_>>();
-    let selected = Select::new(
+    let Some(selected) = crate::common::select(
         "How would you like to pass the function arguments?",
         variants,
-    )
-    .prompt()?;
+    )?
+    else {
+        return Ok(None);
+    };
     match selected {
         FunctionArgsTypeDiscriminants::JsonArgs => Ok(Some(FunctionArgsType::JsonArgs)),
         FunctionArgsTypeDiscriminants::TextArgs => Ok(Some(FunctionArgsType::TextArgs)),
@@ -80,15 +118,87 @@ pub fn input_function_args_type() -> color_eyre::eyre::Result<Option<FunctionArg
     }
 }
 
+pub fn input_function_args() -> color_eyre::eyre::Result<Option<FunctionArgs>> {
+    let args: String =
+        inquire::CustomType::new("Enter the arguments to this function:").prompt()?;
+    Ok(Some(FunctionArgs {
+        args,
+        entered_interactively: true,
+    }))
+}
+
+/// The arguments of the `--arg` flags as a JSON object (`None` if there are no such flags).
+pub fn key_value_function_args(
+    function_args: &[self::key_value_args::FunctionArg],
+) -> color_eyre::eyre::Result<Option<String>> {
+    if function_args.is_empty() {
+        return Ok(None);
+    }
+    let args =
+        self::key_value_args::to_json(function_args).map_err(color_eyre::eyre::Error::msg)?;
+    Ok(Some(args.to_string()))
+}
+
+/// When the `--arg` flags are used, the arguments type and the arguments are filled in from them,
+/// so anything else passed on the command line is a conflict.
+pub fn ensure_no_conflicting_function_args(
+    key_value_function_args: Option<&str>,
+    function_args_type: &FunctionArgsType,
+    function_args: &str,
+) -> crate::CliResult {
+    match key_value_function_args {
+        Some(key_value_function_args)
+            if !matches!(function_args_type, FunctionArgsType::JsonArgs)
+                || function_args != key_value_function_args =>
+        {
+            Err(color_eyre::eyre::eyre!(
+                "The --arg flags cannot be combined with the <{function_args_type}> function arguments"
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// The JSON arguments are strict unless `--args-json5` is given or they were typed at the prompt
+/// (then rejecting the normalized form prompts for the arguments again).
 pub fn function_args(
-    args: String,
+    function_args: FunctionArgs,
     function_args_type: FunctionArgsType,
+    args_json5: bool,
 ) -> color_eyre::eyre::Result<Vec<u8>> {
+    let FunctionArgs {
+        args,
+        entered_interactively,
+    } = function_args;
+    if args_json5 && !matches!(function_args_type, FunctionArgsType::JsonArgs) {
+        return Err(color_eyre::eyre::eyre!(
+            "--args-json5 only applies to the <json-args> function arguments"
+        ));
+    }
     match function_args_type {
         super::call_function_args_type::FunctionArgsType::JsonArgs => {
+            if let Ok(data_json) = serde_json::Value::from_str(&args) {
+                return serde_json::to_vec(&data_json).wrap_err("Internal error!");
+            }
+            if !args_json5 && !entered_interactively {
+                let err = serde_json::Value::from_str(&args).unwrap_err();
+                return Err(color_eyre::eyre::Report::new(err).wrap_err(
+                    "Data not in JSON format! (use --args-json5 to allow comments, trailing commas and single quotes)",
+                ));
+            }
             let data_json =
-                serde_json::Value::from_str(&args).wrap_err("Data not in JSON format!")?;
-            serde_json::to_vec(&data_json).wrap_err("Internal error!")
+                self::lenient_json::parse(&args).wrap_err("Data not in JSON format!")?;
+            let normalized_args = serde_json::to_string(&data_json).wrap_err("Internal error!")?;
+            eprintln!("\nThe arguments were normalized to strict JSON:\n{normalized_args}\n");
+            if entered_interactively && !confirm_normalized_args()? {
+                let Some(function_args) = input_function_args()? else {
+                    return Err(color_eyre::eyre::eyre!(
+                        "The function arguments were not entered"
+                    ));
+                };
+                return self::function_args(function_args, function_args_type, args_json5);
+            }
+            Ok(normalized_args.into_bytes())
         }
         super::call_function_args_type::FunctionArgsType::TextArgs => Ok(args.into_bytes()),
         super::call_function_args_type::FunctionArgsType::Base64Args => {
@@ -103,3 +213,51 @@ pub fn function_args(
         }
     }
 }
+
+fn confirm_normalized_args() -> color_eyre::eyre::Result<bool> {
+    #[derive(strum_macros::Display, PartialEq)]
+    enum ConfirmOptions {
+        #[strum(to_string = "Yes, use the normalized arguments.")]
+        Yes,
+        #[strum(to_string = "No, I want to fix the arguments.")]
+        No,
+    }
+    let select_choose_input = crate::common::select(
+        "Do you want to use the normalized arguments?",
+        vec![ConfirmOptions::Yes, ConfirmOptions::No],
+    )?;
+    Ok(select_choose_input == Some(ConfirmOptions::Yes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_args_are_strict_without_args_json5() {
+        let args = "{'token_id': '42', /* the id */ }".to_string();
+        assert!(function_args(args.clone().into(), FunctionArgsType::JsonArgs, false).is_err());
+        assert_eq!(
+            function_args(args.clone().into(), FunctionArgsType::JsonArgs, true).unwrap(),
+            br#"{"token_id":"42"}"#
+        );
+        assert!(function_args(args.into(), FunctionArgsType::TextArgs, true).is_err());
+        assert_eq!(
+            function_args(
+                r#"{"a": 1}"#.to_string().into(),
+                FunctionArgsType::JsonArgs,
+                false
+            )
+            .unwrap(),
+            br#"{"a":1}"#
+        );
+        // The arguments given on the command line are not the ones typed at the prompt
+        assert_eq!(
+            "{'a': 1}".parse::<FunctionArgs>().unwrap(),
+            FunctionArgs {
+                args: "{'a': 1}".to_string(),
+                entered_interactively: false,
+            }
+        );
+    }
+}
@@ -1,6 +1,7 @@
-use inquire::{Select, Text};
+use inquire::Text;
 use strum::{EnumDiscriminants, EnumIter, EnumMessage};
 
+mod abi_check;
 mod as_read_only;
 mod as_transaction;
 pub mod call_function_args_type;
@@ -79,9 +80,7 @@ fn input_function_name(
                 .map(|function| function.name)
                 .collect::<Vec<String>>();
             if !function_names.is_empty() {
-                return Ok(Some(
-                    Select::new(message, function_names).prompt()?.to_string(),
-                ));
+                return crate::common::select(message, function_names);
             }
         }
     }
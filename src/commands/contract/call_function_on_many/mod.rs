@@ -0,0 +1,300 @@
+use color_eyre::eyre::WrapErr;
+
+use crate::common::{CallResultExt, RpcQueryResponseExt};
+
+const DEFAULT_CONCURRENCY: u64 = 10;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = CallFunctionOnManyContext)]
+pub struct CallFunctionOnMany {
+    #[interactive_clap(long)]
+    /// What is the path of the file with the contract account IDs (one per line)?
+    contracts_file: crate::types::path_buf::PathBuf,
+    #[interactive_clap(long)]
+    /// What is the name of the view method?
+    method: String,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// The JSON arguments of the view method (default: {})
+    args: Option<crate::types::json::Json>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Print a table of this field of the results instead of the JSON, e.g. .proposal_bond or .roles.0.name
+    extract: Option<String>,
+    #[interactive_clap(long)]
+    /// Fail if the call failed on any contract
+    strict: bool,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// How many contracts are called at the same time (default: 10)
+    concurrency: Option<u64>,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network_view_at_block::NetworkViewAtBlockArgs,
+}
+
+#[derive(Clone)]
+pub struct CallFunctionOnManyContext(crate::network_view_at_block::ArgsForViewContext);
+
+impl CallFunctionOnManyContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<CallFunctionOnMany as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let contracts_file_content = std::fs::read_to_string(&scope.contracts_file)
+            .wrap_err_with(|| format!("Failed to read the file: {}", scope.contracts_file))?;
+        let contract_account_ids = parse_contracts_file(&contracts_file_content)?;
+        let args = match &scope.args {
+            Some(args) => args.try_into_bytes()?,
+            None => b"{}".to_vec(),
+        };
+        let concurrency = match scope.concurrency.unwrap_or(DEFAULT_CONCURRENCY) {
+            0 => {
+                return Err(color_eyre::eyre::eyre!(
+                    "The concurrency must be at least 1"
+                ))
+            }
+            concurrency => usize::try_from(concurrency)?,
+        };
+
+        let on_after_getting_block_reference_callback: crate::network_view_at_block::OnAfterGettingBlockReferenceCallback = std::sync::Arc::new({
+            let method = scope.method.clone();
+            let extract = scope.extract.clone();
+            let strict = scope.strict;
+
+            move |network_config, block_reference| {
+                let results = call_view_function_on_many(
+                    network_config,
+                    &contract_account_ids,
+                    &method,
+                    &args,
+                    block_reference,
+                    concurrency,
+                )?;
+                let errors: Vec<(&near_primitives::types::AccountId, &String)> = results
+                    .iter()
+                    .filter_map(|(contract_account_id, result)| {
+                        result.as_ref().err().map(|err| (contract_account_id, err))
+                    })
+                    .collect();
+
+                match &extract {
+                    None => println!("{}", serde_json::to_string_pretty(&combined_json(&results))?),
+                    Some(path) => {
+                        let mut table = prettytable::Table::new();
+                        table.set_titles(prettytable::row![Fg=>"Contract", path]);
+                        for (contract_account_id, result) in &results {
+                            if let Ok(value) = result {
+                                table.add_row(prettytable::row![
+                                    contract_account_id,
+                                    extract_field(value, path)
+                                        .map_or_else(|| "(missing)".to_string(), display_value)
+                                ]);
+                            }
+                        }
+                        table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+                        table.printstd();
+                        if !errors.is_empty() {
+                            eprintln!("\nErrors:");
+                            for (contract_account_id, err) in &errors {
+                                eprintln!("  <{contract_account_id}>: {err}");
+                            }
+                        }
+                    }
+                }
+
+                if strict && !errors.is_empty() {
+                    return Err(color_eyre::eyre::eyre!(
+                        "The call of '{method}' failed on {} of {} contracts",
+                        errors.len(),
+                        results.len()
+                    ));
+                }
+                Ok(())
+            }
+        });
+
+        Ok(Self(crate::network_view_at_block::ArgsForViewContext {
+            config: previous_context.config,
+            interacting_with_account_ids: vec![],
+            on_after_getting_block_reference_callback,
+        }))
+    }
+}
+
+impl From<CallFunctionOnManyContext> for crate::network_view_at_block::ArgsForViewContext {
+    fn from(item: CallFunctionOnManyContext) -> Self {
+        item.0
+    }
+}
+
+/// Contracts are listed one per line; empty lines and lines starting with `#` are ignored.
+fn parse_contracts_file(
+    content: &str,
+) -> color_eyre::eyre::Result<Vec<near_primitives::types::AccountId>> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            line.parse()
+                .wrap_err_with(|| format!("Invalid contract account ID: {line}"))
+        })
+        .collect()
+}
+
+type CallResults = Vec<(
+    near_primitives::types::AccountId,
+    Result<serde_json::Value, String>,
+)>;
+
+#[tracing::instrument(name = "Calling the view method on the contracts ...", skip_all)]
+fn call_view_function_on_many(
+    network_config: &crate::config::NetworkConfig,
+    contract_account_ids: &[near_primitives::types::AccountId],
+    method: &str,
+    args: &[u8],
+    block_reference: &near_primitives::types::BlockReference,
+    concurrency: usize,
+) -> color_eyre::eyre::Result<CallResults> {
+    let json_rpc_client = network_config.json_rpc_client();
+    crate::common::run_concurrently(contract_account_ids, concurrency, |contract_account_id| {
+        let json_rpc_client = &json_rpc_client;
+        async move {
+            let result = call_view_function(
+                json_rpc_client,
+                contract_account_id,
+                method,
+                args,
+                block_reference,
+            )
+            .await
+            .map_err(|err| format!("{err:#}"));
+            (contract_account_id.clone(), result)
+        }
+    })
+}
+
+async fn call_view_function(
+    json_rpc_client: &near_jsonrpc_client::JsonRpcClient,
+    contract_account_id: &near_primitives::types::AccountId,
+    method: &str,
+    args: &[u8],
+    block_reference: &near_primitives::types::BlockReference,
+) -> color_eyre::eyre::Result<serde_json::Value> {
+    let call_result = crate::rpc_rate_limit::call(
+        json_rpc_client,
+        near_jsonrpc_client::methods::query::RpcQueryRequest {
+            block_reference: block_reference.clone(),
+            request: near_primitives::views::QueryRequest::CallFunction {
+                account_id: contract_account_id.clone(),
+                method_name: method.to_string(),
+                args: near_primitives::types::FunctionArgs::from(args.to_vec()),
+            },
+        },
+    )
+    .await
+    .wrap_err_with(|| format!("Failed to fetch query for view method: '{method}'"))?
+    .call_result()?;
+    if call_result.result.is_empty() {
+        return Ok(serde_json::Value::Null);
+    }
+    if let Ok(json_result) = call_result.parse_result_from_json::<serde_json::Value>() {
+        return Ok(json_result);
+    }
+    String::from_utf8(call_result.result)
+        .map(serde_json::Value::String)
+        .wrap_err("The returned value is not printable (binary data)")
+}
+
+/// `{"results": {<contract>: <result>}, "errors": {<contract>: <error>}}`
+fn combined_json(results: &CallResults) -> serde_json::Value {
+    let mut successes = serde_json::Map::new();
+    let mut errors = serde_json::Map::new();
+    for (contract_account_id, result) in results {
+        match result {
+            Ok(value) => successes.insert(contract_account_id.to_string(), value.clone()),
+            Err(err) => errors.insert(
+                contract_account_id.to_string(),
+                serde_json::Value::String(err.clone()),
+            ),
+        };
+    }
+    serde_json::json!({ "results": successes, "errors": errors })
+}
+
+/// The field at the dot-separated path (numbers index arrays), e.g. `.roles.0.name`.
+fn extract_field<'a>(value: &'a serde_json::Value, path: &str) -> Option<&'a serde_json::Value> {
+    path.trim_start_matches('.')
+        .split('.')
+        .filter(|segment| !segment.is_empty())
+        .try_fold(value, |value, segment| match value {
+            serde_json::Value::Array(array) => array.get(segment.parse::<usize>().ok()?),
+            value => value.get(segment),
+        })
+}
+
+fn display_value(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(string) => string.clone(),
+        value => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_fields_of_the_results() {
+        let policy = serde_json::json!({
+            "proposal_bond": "100000000000000000000000",
+            "roles": [{ "name": "council", "permissions": ["*:*"] }]
+        });
+        assert_eq!(
+            extract_field(&policy, ".proposal_bond")
+                .map(display_value)
+                .as_deref(),
+            Some("100000000000000000000000")
+        );
+        assert_eq!(
+            extract_field(&policy, ".roles.0.name"),
+            Some(&serde_json::json!("council"))
+        );
+        assert_eq!(
+            extract_field(&policy, ".roles.0.permissions")
+                .map(display_value)
+                .as_deref(),
+            Some(r#"["*:*"]"#)
+        );
+        assert_eq!(extract_field(&policy, ".roles.1.name"), None);
+        assert_eq!(extract_field(&policy, "."), Some(&policy));
+    }
+
+    #[test]
+    fn failures_are_in_the_errors_section() {
+        let results: CallResults = vec![
+            (
+                "a.sputnik-dao.near".parse().unwrap(),
+                Ok(serde_json::json!({ "bond": 1 })),
+            ),
+            (
+                "b.sputnik-dao.near".parse().unwrap(),
+                Err("MethodNotFound".to_string()),
+            ),
+        ];
+        assert_eq!(
+            combined_json(&results),
+            serde_json::json!({
+                "results": { "a.sputnik-dao.near": { "bond": 1 } },
+                "errors": { "b.sputnik-dao.near": "MethodNotFound" }
+            })
+        );
+        assert_eq!(
+            parse_contracts_file("# DAOs\na.sputnik-dao.near\n\n b.sputnik-dao.near \n").unwrap(),
+            ["a.sputnik-dao.near", "b.sputnik-dao.near"]
+                .map(|id| id.parse::<near_primitives::types::AccountId>().unwrap())
+        );
+    }
+}
@@ -11,8 +11,12 @@ pub struct CallFunctionAction {
     /// How do you want to pass the function call arguments?
     function_args_type:
         super::super::super::call_function::call_function_args_type::FunctionArgsType,
+    #[interactive_clap(skip_default_input_arg)]
     /// Enter the arguments to this function:
-    function_args: String,
+    function_args: super::super::super::call_function::call_function_args_type::FunctionArgs,
+    #[interactive_clap(long)]
+    /// Parse the JSON arguments leniently (comments, trailing commas, single quotes and unquoted keys are allowed)
+    args_json5: bool,
     #[interactive_clap(named_arg)]
     /// Enter gas for function call
     prepaid_gas: PrepaidGas,
@@ -24,6 +28,7 @@ pub struct CallFunctionActionContext {
     receiver_account_id: near_primitives::types::AccountId,
     signer_account_id: near_primitives::types::AccountId,
     code: Vec<u8>,
+    skip_if_same_hash: bool,
     function_name: String,
     function_args: Vec<u8>,
 }
@@ -37,12 +42,14 @@ impl CallFunctionActionContext {
             super::super::super::call_function::call_function_args_type::function_args(
                 scope.function_args.clone(),
                 scope.function_args_type.clone(),
+                scope.args_json5,
             )?;
         Ok(Self {
             global_context: previous_context.global_context,
             receiver_account_id: previous_context.receiver_account_id,
             signer_account_id: previous_context.signer_account_id,
             code: previous_context.code,
+            skip_if_same_hash: previous_context.skip_if_same_hash,
             function_name: scope.function_name.clone(),
             function_args,
         })
@@ -57,6 +64,14 @@ impl CallFunctionAction {
     > {
         super::super::super::call_function::call_function_args_type::input_function_args_type()
     }
+
+    fn input_function_args(
+        _context: &super::super::ContractFileContext,
+    ) -> color_eyre::eyre::Result<
+        Option<super::super::super::call_function::call_function_args_type::FunctionArgs>,
+    > {
+        super::super::super::call_function::call_function_args_type::input_function_args()
+    }
 }
 
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
@@ -77,6 +92,7 @@ pub struct PrepaidGasContext {
     receiver_account_id: near_primitives::types::AccountId,
     signer_account_id: near_primitives::types::AccountId,
     code: Vec<u8>,
+    skip_if_same_hash: bool,
     function_name: String,
     function_args: Vec<u8>,
     gas: crate::common::NearGas,
@@ -92,6 +108,7 @@ impl PrepaidGasContext {
             receiver_account_id: previous_context.receiver_account_id,
             signer_account_id: previous_context.signer_account_id,
             code: previous_context.code,
+            skip_if_same_hash: previous_context.skip_if_same_hash,
             function_name: previous_context.function_name,
             function_args: previous_context.function_args,
             gas: scope.gas,
@@ -149,8 +166,33 @@ impl DepositContext {
             std::sync::Arc::new({
                 let signer_account_id = previous_context.signer_account_id.clone();
                 let receiver_account_id = previous_context.receiver_account_id.clone();
-
-                move |_network_config| {
+                let check_code_hash =
+                    previous_context.skip_if_same_hash && !previous_context.global_context.offline;
+                let check_storage_cost = !previous_context.global_context.offline;
+
+                move |network_config| {
+                    if check_code_hash
+                        && super::super::is_code_deployed(
+                            network_config,
+                            &receiver_account_id,
+                            &previous_context.code,
+                        )?
+                    {
+                        // A transaction without actions is not sent
+                        return Ok(crate::commands::PrepopulatedTransaction {
+                            signer_id: signer_account_id.clone(),
+                            receiver_id: receiver_account_id.clone(),
+                            actions: vec![],
+                        });
+                    }
+                    if check_storage_cost {
+                        super::super::storage_cost::check_storage_cost(
+                            network_config,
+                            &receiver_account_id,
+                            &previous_context.code,
+                            deposit,
+                        )?;
+                    }
                     Ok(crate::commands::PrepopulatedTransaction {
                         signer_id: signer_account_id.clone(),
                         receiver_id: receiver_account_id.clone(),
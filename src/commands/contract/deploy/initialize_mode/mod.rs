@@ -37,6 +37,7 @@ impl NoInitializeContext {
             receiver_account_id: previous_context.receiver_account_id,
             signer_account_id: previous_context.signer_account_id,
             code: previous_context.code,
+            skip_if_same_hash: previous_context.skip_if_same_hash,
         }))
     }
 }
@@ -47,8 +48,32 @@ impl From<NoInitializeContext> for crate::commands::ActionContext {
             std::sync::Arc::new({
                 let signer_account_id = item.0.signer_account_id.clone();
                 let receiver_account_id = item.0.receiver_account_id.clone();
+                let check_code_hash = item.0.skip_if_same_hash && !item.0.global_context.offline;
+                let check_storage_cost = !item.0.global_context.offline;
 
-                move |_network_config| {
+                move |network_config| {
+                    if check_code_hash
+                        && super::is_code_deployed(
+                            network_config,
+                            &receiver_account_id,
+                            &item.0.code,
+                        )?
+                    {
+                        // A transaction without actions is not sent
+                        return Ok(crate::commands::PrepopulatedTransaction {
+                            signer_id: signer_account_id.clone(),
+                            receiver_id: receiver_account_id.clone(),
+                            actions: vec![],
+                        });
+                    }
+                    if check_storage_cost {
+                        super::storage_cost::check_storage_cost(
+                            network_config,
+                            &receiver_account_id,
+                            &item.0.code,
+                            crate::types::near_token::NearToken::from_yoctonear(0),
+                        )?;
+                    }
                     Ok(crate::commands::PrepopulatedTransaction {
                         signer_id: signer_account_id.clone(),
                         receiver_id: receiver_account_id.clone(),
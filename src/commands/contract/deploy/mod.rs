@@ -1,6 +1,7 @@
 use color_eyre::eyre::Context;
 
 pub mod initialize_mode;
+mod storage_cost;
 
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = crate::GlobalContext)]
@@ -51,6 +52,9 @@ impl Contract {
 pub struct ContractFile {
     /// What is a file location of the contract?
     pub file_path: crate::types::path_buf::PathBuf,
+    #[interactive_clap(long)]
+    /// Do not deploy when the same code is already deployed (compares the code hashes)
+    pub skip_if_same_hash: bool,
     #[interactive_clap(subcommand)]
     initialize: self::initialize_mode::InitializeMode,
 }
@@ -61,6 +65,7 @@ pub struct ContractFileContext {
     pub receiver_account_id: near_primitives::types::AccountId,
     pub signer_account_id: near_primitives::types::AccountId,
     pub code: Vec<u8>,
+    pub skip_if_same_hash: bool,
 }
 
 impl ContractFileContext {
@@ -76,6 +81,30 @@ impl ContractFileContext {
             receiver_account_id: previous_context.receiver_account_id,
             signer_account_id: previous_context.signer_account_id,
             code,
+            skip_if_same_hash: scope.skip_if_same_hash,
         })
     }
 }
+
+/// With `--skip-if-same-hash`: whether the deployed code has the same hash (SHA-256) as the code
+/// to deploy, in which case nothing is sent.
+pub fn is_code_deployed(
+    network_config: &crate::config::NetworkConfig,
+    account_id: &near_primitives::types::AccountId,
+    code: &[u8],
+) -> color_eyre::eyre::Result<bool> {
+    let code_hash = near_primitives::hash::CryptoHash::hash_bytes(code);
+    let deployed_code_hash = super::upgrade::get_code_hash(
+        network_config,
+        account_id,
+        near_primitives::types::Finality::Final.into(),
+    )?;
+    if deployed_code_hash == code_hash {
+        eprintln!("\nContract is already up to date: <{account_id}> has the same code deployed (code hash: {code_hash})");
+        return Ok(true);
+    }
+    eprintln!(
+        "\nThe code hash of <{account_id}> will change from {deployed_code_hash} to {code_hash}"
+    );
+    Ok(false)
+}
@@ -0,0 +1,222 @@
+use color_eyre::eyre::WrapErr;
+
+use crate::common::{JsonRpcClientExt, RpcQueryResponseExt};
+use crate::types::near_token::NearToken;
+
+/// The storage staked by the contract account before and after the deployment: the account must
+/// hold (with its staked NEAR) the storage of its state, including the code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeployStorageCost {
+    pub storage_usage: near_primitives::types::StorageUsage,
+    pub current_code_size: u64,
+    pub new_code_size: u64,
+    pub storage_amount_per_byte: near_primitives::types::Balance,
+    /// The liquid and the staked NEAR of the account (minus the deposit of the initialize call)
+    pub available_balance: near_primitives::types::Balance,
+}
+
+impl DeployStorageCost {
+    pub fn storage_usage_after_deploy(&self) -> near_primitives::types::StorageUsage {
+        self.storage_usage
+            .saturating_sub(self.current_code_size)
+            .saturating_add(self.new_code_size)
+    }
+
+    /// The NEAR that the account must hold for its storage after the deployment.
+    pub fn required_balance(&self) -> NearToken {
+        NearToken::from_yoctonear(
+            u128::from(self.storage_usage_after_deploy())
+                .saturating_mul(self.storage_amount_per_byte),
+        )
+    }
+
+    /// How much the storage stake grows (positive) or shrinks (negative) with the new code.
+    pub fn cost_change(&self) -> i128 {
+        let bytes = i128::from(self.new_code_size) - i128::from(self.current_code_size);
+        bytes.saturating_mul(i128::try_from(self.storage_amount_per_byte).unwrap_or(i128::MAX))
+    }
+
+    pub fn shortfall(&self) -> Option<NearToken> {
+        let required_balance = self.required_balance().as_yoctonear();
+        (required_balance > self.available_balance)
+            .then(|| NearToken::from_yoctonear(required_balance - self.available_balance))
+    }
+}
+
+impl std::fmt::Display for DeployStorageCost {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let cost_change = self.cost_change();
+        let cost_change_abs = NearToken::from_yoctonear(cost_change.unsigned_abs());
+        writeln!(
+            f,
+            "Storage cost of the code: {} bytes -> {} bytes ({})",
+            self.current_code_size,
+            self.new_code_size,
+            match cost_change.signum() {
+                1 => format!("{cost_change_abs} more to hold"),
+                -1 => format!("{cost_change_abs} freed"),
+                _ => "no change".to_string(),
+            }
+        )?;
+        write!(
+            f,
+            "The account must hold {} for {} bytes of storage, it has {}",
+            self.required_balance(),
+            self.storage_usage_after_deploy(),
+            NearToken::from_yoctonear(self.available_balance)
+        )
+    }
+}
+
+/// Prints the storage cost of the deployment and fails with the exact shortfall if the account
+/// cannot hold the new code (instead of the `LackBalanceForState` error of the runtime).
+#[tracing::instrument(name = "Estimating the storage cost of the deployment ...", skip_all)]
+pub fn check_storage_cost(
+    network_config: &crate::config::NetworkConfig,
+    account_id: &near_primitives::types::AccountId,
+    code: &[u8],
+    outgoing_deposit: NearToken,
+) -> crate::CliResult {
+    let json_rpc_client = network_config.json_rpc_client();
+    let account_view = match json_rpc_client
+        .blocking_call_view_account(account_id, near_primitives::types::Finality::Final.into())
+    {
+        Ok(rpc_query_response) => rpc_query_response.account_view()?,
+        // The account is created in the same transaction (or the signing fails anyway)
+        Err(near_jsonrpc_client::errors::JsonRpcError::ServerError(
+            near_jsonrpc_client::errors::JsonRpcServerError::HandlerError(
+                near_jsonrpc_primitives::types::query::RpcQueryError::UnknownAccount { .. },
+            ),
+        )) => return Ok(()),
+        Err(err) => {
+            return Err(err).wrap_err_with(|| {
+                format!(
+                    "Failed to fetch query ViewAccount for account <{account_id}> on network <{}>",
+                    network_config.network_name
+                )
+            })
+        }
+    };
+    let current_code_size =
+        if account_view.code_hash == near_primitives::hash::CryptoHash::default() {
+            0
+        } else {
+            get_code_size(&json_rpc_client, network_config, account_id)?
+        };
+    let storage_amount_per_byte = json_rpc_client
+        .blocking_call(
+            near_jsonrpc_client::methods::EXPERIMENTAL_protocol_config::RpcProtocolConfigRequest {
+                block_reference: near_primitives::types::Finality::Final.into(),
+            },
+        )
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch the protocol config of network <{}>",
+                network_config.network_name
+            )
+        })?
+        .runtime_config
+        .storage_amount_per_byte;
+
+    let storage_cost = DeployStorageCost {
+        storage_usage: account_view.storage_usage,
+        current_code_size,
+        new_code_size: u64::try_from(code.len())?,
+        storage_amount_per_byte,
+        available_balance: account_view
+            .amount
+            .saturating_add(account_view.locked)
+            .saturating_sub(outgoing_deposit.as_yoctonear()),
+    };
+    eprintln!("\n{storage_cost}");
+    if let Some(shortfall) = storage_cost.shortfall() {
+        color_eyre::eyre::bail!(
+            "<{account_id}> does not have enough NEAR to store the new code: {shortfall} more is needed (the deployment would fail with LackBalanceForState)"
+        );
+    }
+    Ok(())
+}
+
+fn get_code_size(
+    json_rpc_client: &near_jsonrpc_client::JsonRpcClient,
+    network_config: &crate::config::NetworkConfig,
+    account_id: &near_primitives::types::AccountId,
+) -> color_eyre::eyre::Result<u64> {
+    let view_code_response = json_rpc_client
+        .blocking_call(near_jsonrpc_client::methods::query::RpcQueryRequest {
+            block_reference: near_primitives::types::Finality::Final.into(),
+            request: near_primitives::views::QueryRequest::ViewCode {
+                account_id: account_id.clone(),
+            },
+        })
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch query ViewCode for <{account_id}> on network <{}>",
+                network_config.network_name
+            )
+        })?;
+    if let near_jsonrpc_primitives::types::query::QueryResponseKind::ViewCode(contract_code_view) =
+        view_code_response.kind
+    {
+        Ok(u64::try_from(contract_code_view.code.len())?)
+    } else {
+        color_eyre::eyre::bail!("Received unexpected query kind in response to a ViewCode query")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 10^19 yoctoNEAR per byte, as on mainnet
+    const PER_BYTE: u128 = 10_000_000_000_000_000_000;
+
+    fn storage_cost(
+        storage_usage: u64,
+        current_code_size: u64,
+        new_code_size: u64,
+        available_balance: u128,
+    ) -> DeployStorageCost {
+        DeployStorageCost {
+            storage_usage,
+            current_code_size,
+            new_code_size,
+            storage_amount_per_byte: PER_BYTE,
+            available_balance,
+        }
+    }
+
+    #[test]
+    fn first_deployment() {
+        let cost = storage_cost(182, 0, 100_000, 2 * 10u128.pow(24));
+        assert_eq!(cost.storage_usage_after_deploy(), 100_182);
+        assert_eq!(cost.cost_change(), 100_000 * PER_BYTE as i128);
+        assert_eq!(cost.required_balance().as_yoctonear(), 100_182 * PER_BYTE);
+        assert_eq!(cost.shortfall(), None);
+    }
+
+    #[test]
+    fn shortfall_is_exact() {
+        let cost = storage_cost(182, 0, 300_000, 10u128.pow(24));
+        assert_eq!(
+            cost.shortfall(),
+            Some(NearToken::from_yoctonear(
+                300_182 * PER_BYTE - 10u128.pow(24)
+            ))
+        );
+        assert_eq!(
+            storage_cost(182, 0, 300_000, 300_182 * PER_BYTE).shortfall(),
+            None
+        );
+    }
+
+    #[test]
+    fn replacing_with_smaller_code_frees_storage() {
+        let cost = storage_cost(200_500, 200_000, 50_000, 6 * 10u128.pow(23));
+        assert_eq!(cost.storage_usage_after_deploy(), 50_500);
+        assert_eq!(cost.cost_change(), -150_000 * PER_BYTE as i128);
+        // The account could not hold the old code, but it can hold the new one
+        assert_eq!(cost.shortfall(), None);
+        assert!(cost.to_string().contains("freed"), "{cost}");
+    }
+}
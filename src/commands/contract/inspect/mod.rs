@@ -383,14 +383,16 @@ async fn get_account_view(
 ) -> color_eyre::eyre::Result<near_primitives::views::AccountView> {
     tracing::Span::current().pb_set_message(&format!("{account_id} ..."));
     for _ in 0..5 {
-        let account_view_response = json_rpc_client
-            .call(near_jsonrpc_client::methods::query::RpcQueryRequest {
+        let account_view_response = crate::rpc_rate_limit::call(
+            json_rpc_client,
+            near_jsonrpc_client::methods::query::RpcQueryRequest {
                 block_reference: block_reference.clone(),
                 request: near_primitives::views::QueryRequest::ViewAccount {
                     account_id: account_id.clone(),
                 },
-            })
-            .await;
+            },
+        )
+        .await;
 
         if let Err(near_jsonrpc_client::errors::JsonRpcError::TransportError(_)) =
             &account_view_response
@@ -421,14 +423,16 @@ async fn get_access_keys(
 ) -> color_eyre::eyre::Result<Vec<near_primitives::views::AccessKeyInfoView>> {
     tracing::Span::current().pb_set_message(&format!("{account_id} access keys ..."));
     for _ in 0..5 {
-        let access_keys_response = json_rpc_client
-            .call(near_jsonrpc_client::methods::query::RpcQueryRequest {
+        let access_keys_response = crate::rpc_rate_limit::call(
+            json_rpc_client,
+            near_jsonrpc_client::methods::query::RpcQueryRequest {
                 block_reference: block_reference.clone(),
                 request: near_primitives::views::QueryRequest::ViewAccessKeyList {
                     account_id: account_id.clone(),
                 },
-            })
-            .await;
+            },
+        )
+        .await;
 
         if let Err(near_jsonrpc_client::errors::JsonRpcError::TransportError(_)) =
             &access_keys_response
@@ -473,16 +477,18 @@ async fn get_contract_source_metadata(
 ) -> Result<self::contract_metadata::ContractSourceMetadata, FetchContractSourceMetadataError> {
     let mut retries_left = (0..5).rev();
     loop {
-        let contract_source_metadata_response = json_rpc_client
-            .call(near_jsonrpc_client::methods::query::RpcQueryRequest {
+        let contract_source_metadata_response = crate::rpc_rate_limit::call(
+            json_rpc_client,
+            near_jsonrpc_client::methods::query::RpcQueryRequest {
                 block_reference: block_reference.clone(),
                 request: near_primitives::views::QueryRequest::CallFunction {
                     account_id: account_id.clone(),
                     method_name: "contract_source_metadata".to_owned(),
                     args: near_primitives::types::FunctionArgs::from(vec![]),
                 },
-            })
-            .await;
+            },
+        )
+        .await;
 
         match contract_source_metadata_response {
             Err(near_jsonrpc_client::errors::JsonRpcError::TransportError(_))
@@ -540,16 +546,18 @@ pub async fn get_contract_abi(
 ) -> Result<near_abi::AbiRoot, FetchAbiError> {
     let mut retries_left = (0..5).rev();
     loop {
-        let contract_abi_response = json_rpc_client
-            .call(near_jsonrpc_client::methods::query::RpcQueryRequest {
+        let contract_abi_response = crate::rpc_rate_limit::call(
+            json_rpc_client,
+            near_jsonrpc_client::methods::query::RpcQueryRequest {
                 block_reference: block_reference.clone(),
                 request: near_primitives::views::QueryRequest::CallFunction {
                     account_id: account_id.clone(),
                     method_name: "__contract_abi".to_owned(),
                     args: near_primitives::types::FunctionArgs::from(vec![]),
                 },
-            })
-            .await;
+            },
+        )
+        .await;
 
         match contract_abi_response {
             Err(near_jsonrpc_client::errors::JsonRpcError::TransportError(_))
@@ -565,7 +573,8 @@ pub async fn get_contract_abi(
                     },
                 ),
             )) if vm_error.contains("MethodNotFound") => {
-                return Err(FetchAbiError::AbiNotSupported);
+                return get_contract_abi_from_storage(json_rpc_client, block_reference, account_id)
+                    .await;
             }
             Err(err) => {
                 return Err(FetchAbiError::RpcError(err));
@@ -589,3 +598,45 @@ pub async fn get_contract_abi(
         tokio::time::sleep(std::time::Duration::from_millis(100)).await;
     }
 }
+
+/// The storage key of the ABI of the contracts without the `__contract_abi` function (NEP-451)
+const ABI_STORAGE_KEY: &[u8] = b"__abi";
+
+/// The ABI stored under the `__abi` key of the contract state (zstd-compressed or plain JSON).
+async fn get_contract_abi_from_storage(
+    json_rpc_client: &near_jsonrpc_client::JsonRpcClient,
+    block_reference: &BlockReference,
+    account_id: &near_primitives::types::AccountId,
+) -> Result<near_abi::AbiRoot, FetchAbiError> {
+    // The state of the contract can be too large to be viewed, then the ABI is not available
+    let Ok(view_state_response) = crate::rpc_rate_limit::call(
+        json_rpc_client,
+        near_jsonrpc_client::methods::query::RpcQueryRequest {
+            block_reference: block_reference.clone(),
+            request: near_primitives::views::QueryRequest::ViewState {
+                account_id: account_id.clone(),
+                prefix: near_primitives::types::StoreKey::from(ABI_STORAGE_KEY.to_vec()),
+                include_proof: false,
+            },
+        },
+    )
+    .await
+    else {
+        return Err(FetchAbiError::AbiNotSupported);
+    };
+    let near_jsonrpc_primitives::types::query::QueryResponseKind::ViewState(view_state_result) =
+        view_state_response.kind
+    else {
+        return Err(FetchAbiError::AbiNotSupported);
+    };
+    let abi = view_state_result
+        .values
+        .into_iter()
+        .find(|state_item| state_item.key.as_slice() == ABI_STORAGE_KEY)
+        .ok_or(FetchAbiError::AbiNotSupported)?
+        .value;
+    let abi = zstd::decode_all(abi.as_slice()).unwrap_or_else(|_| abi.to_vec());
+    serde_json::from_slice::<near_abi::AbiRoot>(&abi)
+        .wrap_err("Failed to parse NEAR ABI schema (the '__abi' storage key)")
+        .map_err(FetchAbiError::AbiUnknownFormat)
+}
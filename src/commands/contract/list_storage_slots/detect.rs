@@ -0,0 +1,166 @@
+//! Heuristics that guess how a storage key or value was serialized by the contract.
+//!
+//! near-sdk contracts store JSON (with `#[serde]` state) or borsh (the default), and the keys
+//! of the collections are a short prefix followed by the borsh-serialized key, e.g. `m` +
+//! `0a000000alice.near`. Borsh has no type tags, so only the common shapes are recognized:
+//! strings, integers and vectors of fixed-size items.
+
+/// Values longer than this are shortened in the output
+const MAX_DISPLAYED_CHARS: usize = 64;
+/// The longest collection prefix that is recognized before a borsh string key
+const MAX_KEY_PREFIX_LEN: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, strum_macros::Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum SlotFormat {
+    Json,
+    Borsh,
+    Utf8,
+    Hex,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetectedSlot {
+    pub format: SlotFormat,
+    /// The decoded content, e.g. `u128 1000000` or `"alice.near"`
+    pub display: String,
+}
+
+pub fn detect(bytes: &[u8]) -> DetectedSlot {
+    let detected = |format, display: String| DetectedSlot {
+        format,
+        display: shorten(&display),
+    };
+    if let Ok(value) = serde_json::from_slice::<serde_json::Value>(bytes) {
+        return detected(SlotFormat::Json, value.to_string());
+    }
+    if let Some(text) = printable_utf8(bytes) {
+        return detected(SlotFormat::Utf8, format!("{text:?}"));
+    }
+    if let Some(display) = detect_borsh(bytes) {
+        return detected(SlotFormat::Borsh, display);
+    }
+    detected(SlotFormat::Hex, hex::encode(bytes))
+}
+
+fn printable_utf8(bytes: &[u8]) -> Option<&str> {
+    let text = std::str::from_utf8(bytes).ok()?;
+    (!text.is_empty() && text.chars().all(|c| !c.is_control() || c.is_whitespace())).then_some(text)
+}
+
+fn detect_borsh(bytes: &[u8]) -> Option<String> {
+    if let Some(text) = borsh_string(bytes) {
+        return Some(format!("string {text:?}"));
+    }
+    // A collection key: a short prefix and the borsh-serialized key
+    if let Some((prefix, text)) = (1..=MAX_KEY_PREFIX_LEN.min(bytes.len())).find_map(|len| {
+        let (prefix, rest) = bytes.split_at(len);
+        Some((printable_utf8(prefix)?, borsh_string(rest)?))
+    }) {
+        return Some(format!("prefix {prefix:?} + string {text:?}"));
+    }
+    match bytes.len() {
+        1 => return Some(format!("u8 {}", bytes[0])),
+        2 => {
+            return Some(format!(
+                "u16 {}",
+                u16::from_le_bytes(bytes.try_into().ok()?)
+            ))
+        }
+        4 => {
+            return Some(format!(
+                "u32 {}",
+                u32::from_le_bytes(bytes.try_into().ok()?)
+            ))
+        }
+        8 => {
+            return Some(format!(
+                "u64 {}",
+                u64::from_le_bytes(bytes.try_into().ok()?)
+            ))
+        }
+        16 => {
+            return Some(format!(
+                "u128 {}",
+                u128::from_le_bytes(bytes.try_into().ok()?)
+            ))
+        }
+        _ => {}
+    }
+    // A vector: the number of items and the items of the same size
+    let (len, items) = bytes.split_first_chunk::<4>()?;
+    let len = usize::try_from(u32::from_le_bytes(*len)).ok()?;
+    if len > 0 && !items.is_empty() && items.len() % len == 0 {
+        return Some(format!("vec of {len} items of {} bytes", items.len() / len));
+    }
+    None
+}
+
+fn borsh_string(bytes: &[u8]) -> Option<&str> {
+    let (len, text) = bytes.split_first_chunk::<4>()?;
+    if usize::try_from(u32::from_le_bytes(*len)).ok()? != text.len() {
+        return None;
+    }
+    printable_utf8(text)
+}
+
+fn shorten(display: &str) -> String {
+    if display.chars().count() <= MAX_DISPLAYED_CHARS {
+        return display.to_string();
+    }
+    let shortened: String = display.chars().take(MAX_DISPLAYED_CHARS - 1).collect();
+    format!("{shortened}…")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn borsh_string_bytes(prefix: &[u8], text: &str) -> Vec<u8> {
+        let mut bytes = prefix.to_vec();
+        bytes.extend(u32::try_from(text.len()).unwrap().to_le_bytes());
+        bytes.extend(text.as_bytes());
+        bytes
+    }
+
+    #[test]
+    fn formats_are_detected() {
+        assert_eq!(
+            detect(br#"{"owner_id":"alice.near"}"#),
+            DetectedSlot {
+                format: SlotFormat::Json,
+                display: r#"{"owner_id":"alice.near"}"#.to_string()
+            }
+        );
+        assert_eq!(detect(b"STATE").format, SlotFormat::Utf8);
+        assert_eq!(
+            detect(&borsh_string_bytes(b"", "alice.near")).display,
+            r#"string "alice.near""#
+        );
+        assert_eq!(
+            detect(&borsh_string_bytes(b"m", "alice.near")).display,
+            r#"prefix "m" + string "alice.near""#
+        );
+        assert_eq!(
+            detect(&10u128.pow(24).to_le_bytes()).display,
+            "u128 1000000000000000000000000"
+        );
+        let mut vec_bytes = 3u32.to_le_bytes().to_vec();
+        vec_bytes.extend([7; 96]);
+        assert_eq!(detect(&vec_bytes).display, "vec of 3 items of 32 bytes");
+        assert_eq!(
+            detect(&[0xff, 0x00, 0xfe]),
+            DetectedSlot {
+                format: SlotFormat::Hex,
+                display: "ff00fe".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn long_values_are_shortened() {
+        let detected = detect("a".repeat(100).as_bytes());
+        assert_eq!(detected.display.chars().count(), MAX_DISPLAYED_CHARS);
+        assert!(detected.display.ends_with('…'));
+    }
+}
@@ -0,0 +1,216 @@
+use prettytable::Table;
+
+mod detect;
+
+const DEFAULT_LIMIT: u64 = 100;
+/// The width of the longest bar of the histogram
+const HISTOGRAM_WIDTH: usize = 40;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = ListStorageSlotsContext)]
+pub struct ListStorageSlots {
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the contract account ID?
+    contract_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// The maximum number of storage slots to list (default: 100)
+    limit: Option<u64>,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network_view_at_block::NetworkViewAtBlockArgs,
+}
+
+#[derive(Clone)]
+pub struct ListStorageSlotsContext(crate::network_view_at_block::ArgsForViewContext);
+
+impl ListStorageSlotsContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<ListStorageSlots as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let limit = scope.limit.unwrap_or(DEFAULT_LIMIT);
+        if limit == 0 {
+            return Err(color_eyre::eyre::eyre!("--limit must be greater than zero"));
+        }
+
+        let on_after_getting_block_reference_callback: crate::network_view_at_block::OnAfterGettingBlockReferenceCallback = std::sync::Arc::new({
+            let contract_id: near_primitives::types::AccountId = scope.contract_id.clone().into();
+
+            move |network_config, block_reference| {
+                let query_view_method_response = super::view_storage::output_format::get_contract_state(
+                    &contract_id,
+                    near_primitives::types::StoreKey::from(Vec::new()),
+                    network_config,
+                    block_reference.clone(),
+                )?;
+                let near_jsonrpc_primitives::types::query::QueryResponseKind::ViewState(result) =
+                    query_view_method_response.kind
+                else {
+                    return Err(color_eyre::Report::msg("Error call result".to_string()));
+                };
+                crate::network_view_at_block::display_viewed_at_block(
+                    query_view_method_response.block_height,
+                    &query_view_method_response.block_hash,
+                );
+                let total_slots = result.values.len();
+                let slots = storage_slots(result.values, limit);
+                if slots.is_empty() {
+                    eprintln!("\n<{contract_id}> has no storage slots.");
+                    return Ok(());
+                }
+                display_storage_slots(&slots);
+                display_histogram(&slots);
+                if slots.len() < total_slots {
+                    eprintln!(
+                        "\nListed {} of {total_slots} storage slots, pass a greater --limit to list more.",
+                        slots.len()
+                    );
+                }
+                Ok(())
+            }
+        });
+        Ok(Self(crate::network_view_at_block::ArgsForViewContext {
+            config: previous_context.config,
+            interacting_with_account_ids: vec![scope.contract_id.clone().into()],
+            on_after_getting_block_reference_callback,
+        }))
+    }
+}
+
+impl From<ListStorageSlotsContext> for crate::network_view_at_block::ArgsForViewContext {
+    fn from(item: ListStorageSlotsContext) -> Self {
+        item.0
+    }
+}
+
+impl ListStorageSlots {
+    pub fn input_contract_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_non_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "What is the contract account ID?",
+        )
+    }
+}
+
+#[derive(Debug)]
+struct StorageSlot {
+    key: detect::DetectedSlot,
+    value: detect::DetectedSlot,
+    value_size: usize,
+}
+
+/// The first `limit` slots in key order, grouped by the format of their values.
+fn storage_slots(
+    mut state_items: Vec<near_primitives::views::StateItem>,
+    limit: u64,
+) -> Vec<StorageSlot> {
+    state_items.sort_by(|a, b| a.key.as_slice().cmp(b.key.as_slice()));
+    let mut slots = state_items
+        .into_iter()
+        .take(usize::try_from(limit).unwrap_or(usize::MAX))
+        .map(|item| StorageSlot {
+            key: detect::detect(&item.key),
+            value: detect::detect(&item.value),
+            value_size: item.value.len(),
+        })
+        .collect::<Vec<_>>();
+    // The sort is stable, so the slots of a group stay in key order
+    slots.sort_by_key(|slot| slot.value.format);
+    slots
+}
+
+fn display_storage_slots(slots: &[StorageSlot]) {
+    let mut table = Table::new();
+    table.set_titles(
+        prettytable::row![Fg=>"Value format", "Key", "Key format", "Value", "Value size"],
+    );
+    for slot in slots {
+        table.add_row(prettytable::row![
+            slot.value.format,
+            slot.key.display,
+            slot.key.format,
+            slot.value.display,
+            bytesize::ByteSize(slot.value_size as u64)
+        ]);
+    }
+    table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    table.printstd();
+}
+
+fn display_histogram(slots: &[StorageSlot]) {
+    eprintln!("\nValue formats:");
+    for (format, count) in histogram(slots) {
+        eprintln!(
+            "  {format:<6} {:<HISTOGRAM_WIDTH$} {count}",
+            bar(count, slots.len())
+        );
+    }
+}
+
+fn histogram(slots: &[StorageSlot]) -> Vec<(detect::SlotFormat, usize)> {
+    let mut histogram = std::collections::BTreeMap::new();
+    for slot in slots {
+        *histogram.entry(slot.value.format).or_insert(0) += 1;
+    }
+    histogram.into_iter().collect()
+}
+
+fn bar(count: usize, total: usize) -> String {
+    // Every non-empty group gets at least one block
+    "█".repeat((count * HISTOGRAM_WIDTH).div_ceil(total.max(1)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slots_are_grouped_by_value_format() {
+        let state_items = [
+            ("c", b"\xff\xfe\xfd".to_vec()),
+            ("a", br#"{"x":1}"#.to_vec()),
+            ("STATE", 7u64.to_le_bytes().to_vec()),
+            ("b", br#"[1,2]"#.to_vec()),
+        ]
+        .into_iter()
+        .map(|(key, value)| near_primitives::views::StateItem {
+            key: key.as_bytes().to_vec().into(),
+            value: value.into(),
+        })
+        .collect::<Vec<_>>();
+
+        let slots = storage_slots(state_items.clone(), 100);
+        assert_eq!(
+            slots
+                .iter()
+                .map(|slot| slot.key.display.as_str())
+                .collect::<Vec<_>>(),
+            vec![r#""a""#, r#""b""#, r#""STATE""#, r#""c""#]
+        );
+        assert_eq!(
+            histogram(&slots),
+            vec![
+                (detect::SlotFormat::Json, 2),
+                (detect::SlotFormat::Borsh, 1),
+                (detect::SlotFormat::Hex, 1)
+            ]
+        );
+        assert_eq!(bar(2, 4).chars().count(), HISTOGRAM_WIDTH / 2);
+        assert_eq!(bar(1, 1000).chars().count(), 1);
+
+        // The limit applies in key order, before the grouping
+        let slots = storage_slots(state_items, 2);
+        assert_eq!(
+            slots
+                .iter()
+                .map(|slot| slot.key.display.as_str())
+                .collect::<Vec<_>>(),
+            vec![r#""a""#, r#""STATE""#]
+        );
+    }
+}
@@ -1,10 +1,16 @@
 use strum::{EnumDiscriminants, EnumIter, EnumMessage};
 
 pub mod call_function;
+mod call_function_on_many;
 pub mod deploy;
 mod download_abi;
 mod download_wasm;
 mod inspect;
+mod list_storage_slots;
+mod set_admin_key;
+mod upgrade;
+mod view_json;
+mod view_metadata;
 mod view_storage;
 
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
@@ -25,14 +31,39 @@ pub enum ContractActions {
     ))]
     /// Execute function (contract method)
     CallFunction(self::call_function::CallFunctionCommands),
+    #[strum_discriminants(strum(
+        message = "call-function-on-many - Call a view method on many contracts"
+    ))]
+    /// Call a view method on many contracts
+    CallFunctionOnMany(self::call_function_on_many::CallFunctionOnMany),
     #[strum_discriminants(strum(message = "deploy          - Add a new contract code"))]
     /// Add a contract code
     Deploy(self::deploy::Contract),
+    #[strum_discriminants(strum(
+        message = "upgrade         - Redeploy a contract code with an optional state migration"
+    ))]
+    /// Redeploy a contract code with an optional state migration
+    Upgrade(self::upgrade::ContractUpgrade),
+    #[strum_discriminants(strum(
+        message = "set-admin-key   - Rotate the admin key of a contract with its key rotation method"
+    ))]
+    /// Rotate the admin key of a contract with its key rotation method
+    SetAdminKey(self::set_admin_key::SetAdminKey),
     #[strum_discriminants(strum(
         message = "inspect         - Get a list of available function names"
     ))]
     /// Get a list of available function names
     Inspect(self::inspect::Contract),
+    #[strum_discriminants(strum(
+        message = "view-json       - Call a view function and print its JSON result, optionally filtered"
+    ))]
+    /// Call a view function and print its JSON result, optionally filtered
+    ViewJson(self::view_json::ViewJson),
+    #[strum_discriminants(strum(
+        message = "view-metadata   - View the metadata of a contract (NFT, FT or contract metadata standard)"
+    ))]
+    /// View the metadata of a contract (NFT, FT or contract metadata standard)
+    ViewMetadata(self::view_metadata::ViewMetadata),
     #[strum_discriminants(strum(message = "download-abi    - Download contract ABI"))]
     /// Download contract ABI
     DownloadAbi(self::download_abi::Contract),
@@ -42,4 +73,9 @@ pub enum ContractActions {
     #[strum_discriminants(strum(message = "view-storage    - View contract storage state"))]
     /// View contract storage state
     ViewStorage(self::view_storage::ViewStorage),
+    #[strum_discriminants(strum(
+        message = "list-storage-slots - List the storage slots with the detected format of their keys and values"
+    ))]
+    /// List the storage slots with the detected format of their keys and values
+    ListStorageSlots(self::list_storage_slots::ListStorageSlots),
 }
@@ -0,0 +1,191 @@
+/// The argument name of the new key if neither --arg-name nor --args-json is given
+const DEFAULT_ARG_NAME: &str = "new_key";
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = SetAdminKeyContext)]
+pub struct SetAdminKey {
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the contract account ID?
+    contract_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    /// What is the new admin public key?
+    new_key: crate::types::public_key::PublicKey,
+    #[interactive_clap(long)]
+    /// What is the name of the contract method that sets the admin key (e.g. set_admin_key)?
+    method_name: String,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// The name of the method argument with the new key (default: new_key)
+    arg_name: Option<String>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// The JSON arguments of the method, instead of {"<arg-name>": "<new-key>"}
+    args_json: Option<crate::types::json::Json>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// The deposit attached to the call, e.g. 1 yoctoNEAR for methods that require it (default: 0 NEAR)
+    deposit: Option<crate::types::near_token::NearToken>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Gas for the call (default: 30 TeraGas)
+    gas: Option<crate::common::NearGas>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the account ID of the current admin that signs the call?
+    signer_account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network_for_transaction::NetworkForTransactionArgs,
+}
+
+#[derive(Clone)]
+pub struct SetAdminKeyContext(crate::commands::ActionContext);
+
+impl SetAdminKeyContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<SetAdminKey as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let new_key: near_crypto::PublicKey = scope.new_key.clone().into();
+        let args = function_args(
+            &new_key,
+            scope.arg_name.as_deref(),
+            scope.args_json.as_ref(),
+        )?;
+        if scope.args_json.is_some()
+            && !String::from_utf8_lossy(&args).contains(&new_key.to_string())
+        {
+            eprintln!("\nWarning: the --args-json arguments do not contain the new key {new_key}");
+        }
+        let function_call = near_primitives::transaction::FunctionCallAction {
+            method_name: scope.method_name.clone(),
+            args,
+            gas: scope
+                .gas
+                .unwrap_or(near_gas::NearGas::from_tgas(30))
+                .as_gas(),
+            deposit: scope.deposit.unwrap_or_default().as_yoctonear(),
+        };
+
+        let get_prepopulated_transaction_after_getting_network_callback: crate::commands::GetPrepopulatedTransactionAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let signer_account_id: near_primitives::types::AccountId =
+                    scope.signer_account_id.clone().into();
+                let contract_account_id: near_primitives::types::AccountId =
+                    scope.contract_id.clone().into();
+
+                move |_network_config| {
+                    Ok(crate::commands::PrepopulatedTransaction {
+                        signer_id: signer_account_id.clone(),
+                        receiver_id: contract_account_id.clone(),
+                        actions: vec![near_primitives::transaction::Action::FunctionCall(
+                            Box::new(function_call.clone()),
+                        )],
+                    })
+                }
+            });
+
+        let on_after_sending_transaction_callback: crate::transaction_signature_options::OnAfterSendingTransactionCallback = std::sync::Arc::new({
+            let contract_account_id: near_primitives::types::AccountId =
+                scope.contract_id.clone().into();
+            let method_name = scope.method_name.clone();
+
+            move |outcome_view, _network_config| {
+                if let near_primitives::views::FinalExecutionStatus::SuccessValue(_) = outcome_view.status {
+                    eprintln!(
+                        "The admin key of <{contract_account_id}> has been set to {new_key} by '{method_name}'"
+                    );
+                }
+                Ok(())
+            }
+        });
+
+        Ok(Self(crate::commands::ActionContext {
+            global_context: previous_context,
+            interacting_with_account_ids: vec![
+                scope.signer_account_id.clone().into(),
+                scope.contract_id.clone().into(),
+            ],
+            get_prepopulated_transaction_after_getting_network_callback,
+            on_before_signing_callback: std::sync::Arc::new(
+                |_prepolulated_unsinged_transaction, _network_config| Ok(()),
+            ),
+            on_before_sending_transaction_callback: std::sync::Arc::new(
+                |_signed_transaction, _network_config| Ok(String::new()),
+            ),
+            on_after_sending_transaction_callback,
+        }))
+    }
+}
+
+impl From<SetAdminKeyContext> for crate::commands::ActionContext {
+    fn from(item: SetAdminKeyContext) -> Self {
+        item.0
+    }
+}
+
+impl SetAdminKey {
+    pub fn input_contract_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_non_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "What is the contract account ID?",
+        )
+    }
+
+    pub fn input_signer_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "What is the account ID of the current admin that signs the call?",
+        )
+    }
+}
+
+/// The `args_json` as is, or `{"<arg_name>": "<new_key>"}`.
+fn function_args(
+    new_key: &near_crypto::PublicKey,
+    arg_name: Option<&str>,
+    args_json: Option<&crate::types::json::Json>,
+) -> color_eyre::eyre::Result<Vec<u8>> {
+    match (args_json, arg_name) {
+        (Some(_), Some(_)) => Err(color_eyre::eyre::eyre!(
+            "--arg-name cannot be used together with --args-json"
+        )),
+        (Some(args_json), None) => args_json.try_into_bytes(),
+        (None, arg_name) => Ok(serde_json::to_vec(&serde_json::json!({
+            arg_name.unwrap_or(DEFAULT_ARG_NAME): new_key,
+        }))?),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_formatted_and_overridden_args() {
+        let new_key: near_crypto::PublicKey =
+            "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp"
+                .parse()
+                .unwrap();
+        assert_eq!(
+            function_args(&new_key, None, None).unwrap(),
+            br#"{"new_key":"ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp"}"#
+        );
+        assert_eq!(
+            function_args(&new_key, Some("admin_pk"), None).unwrap(),
+            br#"{"admin_pk":"ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp"}"#
+        );
+        let args_json: crate::types::json::Json = r#"{"key":{"pk":"abc"}}"#.parse().unwrap();
+        assert_eq!(
+            function_args(&new_key, None, Some(&args_json)).unwrap(),
+            br#"{"key":{"pk":"abc"}}"#
+        );
+        assert!(function_args(&new_key, Some("admin_pk"), Some(&args_json)).is_err());
+    }
+}
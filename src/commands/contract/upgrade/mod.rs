@@ -0,0 +1,189 @@
+use color_eyre::eyre::Context;
+
+use crate::common::{JsonRpcClientExt, RpcQueryResponseExt};
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = ContractUpgradeContext)]
+pub struct ContractUpgrade {
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the contract account ID?
+    contract_account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    /// What is a file location of the new contract code?
+    wasm_file: crate::types::path_buf::PathBuf,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// The state migration method to call right after the code is deployed
+    migrate_method: Option<String>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// JSON arguments of the state migration method (default: {})
+    migrate_args_json: Option<crate::types::json::Json>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Gas for the state migration method call (default: 100 TeraGas)
+    migrate_gas: Option<crate::common::NearGas>,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network_for_transaction::NetworkForTransactionArgs,
+}
+
+#[derive(Clone)]
+pub struct ContractUpgradeContext(crate::commands::ActionContext);
+
+impl ContractUpgradeContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<ContractUpgrade as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let code = std::fs::read(&scope.wasm_file).wrap_err_with(|| {
+            format!("Failed to open or read the file: {:?}.", &scope.wasm_file.0)
+        })?;
+        let migrate_function_call = match &scope.migrate_method {
+            Some(migrate_method) => Some(near_primitives::transaction::FunctionCallAction {
+                method_name: migrate_method.clone(),
+                args: match &scope.migrate_args_json {
+                    Some(migrate_args) => migrate_args.try_into_bytes()?,
+                    None => b"{}".to_vec(),
+                },
+                gas: scope
+                    .migrate_gas
+                    .unwrap_or(near_gas::NearGas::from_tgas(100))
+                    .as_gas(),
+                deposit: 0,
+            }),
+            None if scope.migrate_args_json.is_some() || scope.migrate_gas.is_some() => {
+                return Err(color_eyre::eyre::eyre!(
+                    "--migrate-args-json and --migrate-gas can only be used together with --migrate-method"
+                ));
+            }
+            None => None,
+        };
+        let new_code_hash = near_primitives::hash::CryptoHash::hash_bytes(&code);
+        let previous_code_hash: std::sync::Arc<
+            std::sync::Mutex<Option<near_primitives::hash::CryptoHash>>,
+        > = Default::default();
+
+        let get_prepopulated_transaction_after_getting_network_callback: crate::commands::GetPrepopulatedTransactionAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let contract_account_id: near_primitives::types::AccountId =
+                    scope.contract_account_id.clone().into();
+                let offline = previous_context.offline;
+                let previous_code_hash = previous_code_hash.clone();
+
+                move |network_config| {
+                    if !offline {
+                        let code_hash = get_code_hash(
+                            network_config,
+                            &contract_account_id,
+                            near_primitives::types::Finality::Final.into(),
+                        )?;
+                        if code_hash == new_code_hash {
+                            eprintln!("\nWarning: the code of <{contract_account_id}> is already identical to the one being deployed (code hash: {code_hash})");
+                        } else {
+                            eprintln!("\nThe code hash of <{contract_account_id}> will change from {code_hash} to {new_code_hash}");
+                        }
+                        *previous_code_hash.lock().expect("Internal error") = Some(code_hash);
+                    }
+
+                    let mut actions = vec![near_primitives::transaction::Action::DeployContract(
+                        near_primitives::transaction::DeployContractAction { code: code.clone() },
+                    )];
+                    if let Some(migrate_function_call) = &migrate_function_call {
+                        actions.push(near_primitives::transaction::Action::FunctionCall(Box::new(
+                            migrate_function_call.clone(),
+                        )));
+                    }
+                    Ok(crate::commands::PrepopulatedTransaction {
+                        signer_id: contract_account_id.clone(),
+                        receiver_id: contract_account_id.clone(),
+                        actions,
+                    })
+                }
+            });
+
+        let on_after_sending_transaction_callback: crate::transaction_signature_options::OnAfterSendingTransactionCallback = std::sync::Arc::new({
+            let contract_account_id: near_primitives::types::AccountId =
+                scope.contract_account_id.clone().into();
+
+            move |outcome_view, network_config| {
+                if let near_primitives::views::FinalExecutionStatus::SuccessValue(_) = outcome_view.status {
+                    // The final block may not include the upgrade yet: the code hash is read
+                    // at the block where the receipt with the deployment was executed
+                    let block_reference = match outcome_view.receipts_outcome.first() {
+                        Some(receipt_outcome) => near_primitives::types::BlockReference::BlockId(
+                            near_primitives::types::BlockId::Hash(receipt_outcome.block_hash),
+                        ),
+                        None => near_primitives::types::Finality::None.into(),
+                    };
+                    let code_hash =
+                        get_code_hash(network_config, &contract_account_id, block_reference)?;
+                    if code_hash != new_code_hash {
+                        return Err(color_eyre::eyre::eyre!(
+                            "The upgrade of <{contract_account_id}> could not be confirmed: the deployed code hash is {code_hash}, but {new_code_hash} was expected"
+                        ));
+                    }
+                    match *previous_code_hash.lock().expect("Internal error") {
+                        Some(previous_code_hash) => eprintln!(
+                            "The contract <{contract_account_id}> has been successfully upgraded (code hash: {previous_code_hash} -> {code_hash})"
+                        ),
+                        None => eprintln!(
+                            "The contract <{contract_account_id}> has been successfully upgraded (code hash: {code_hash})"
+                        ),
+                    }
+                }
+                Ok(())
+            }
+        });
+
+        Ok(Self(crate::commands::ActionContext {
+            global_context: previous_context,
+            interacting_with_account_ids: vec![scope.contract_account_id.clone().into()],
+            get_prepopulated_transaction_after_getting_network_callback,
+            on_before_signing_callback: std::sync::Arc::new(
+                |_prepolulated_unsinged_transaction, _network_config| Ok(()),
+            ),
+            on_before_sending_transaction_callback: std::sync::Arc::new(
+                |_signed_transaction, _network_config| Ok(String::new()),
+            ),
+            on_after_sending_transaction_callback,
+        }))
+    }
+}
+
+impl From<ContractUpgradeContext> for crate::commands::ActionContext {
+    fn from(item: ContractUpgradeContext) -> Self {
+        item.0
+    }
+}
+
+impl ContractUpgrade {
+    pub fn input_contract_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "What is the contract account ID?",
+        )
+    }
+}
+
+#[tracing::instrument(name = "Getting the deployed code hash ...", skip_all)]
+pub fn get_code_hash(
+    network_config: &crate::config::NetworkConfig,
+    contract_account_id: &near_primitives::types::AccountId,
+    block_reference: near_primitives::types::BlockReference,
+) -> color_eyre::eyre::Result<near_primitives::hash::CryptoHash> {
+    Ok(network_config
+        .json_rpc_client()
+        .blocking_call_view_account(contract_account_id, block_reference)
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch query ViewAccount for account <{}> on network <{}>",
+                contract_account_id, network_config.network_name
+            )
+        })?
+        .account_view()?
+        .code_hash)
+}
@@ -0,0 +1,297 @@
+//! The subset of the `jq` filters that selects parts of a JSON value: paths (`.a.b`, `.["a b"]`,
+//! `.[0]`, `.[-1]`), the iteration `.[]`, the `length` and `keys` functions and pipes (`|`).
+//!
+//! It is not `jq` (nor jaq, which is not a dependency of near CLI): the other functions,
+//! operators, slices, optional paths (`?`) and literals are rejected with an error that lists
+//! what is supported. Without `--jq-filter`, `view-json` prints the whole result as JSON, so a
+//! full `jq` filter can be applied with `near contract view-json ... | jq '<filter>'`.
+
+use serde_json::Value;
+
+/// What the error of an unsupported filter lists
+const SUPPORTED_FILTERS: &str =
+    "paths such as .a.b, .[\"a b\"], .[0] and .[-1], the iteration .[], length, keys and |";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Step {
+    Field(String),
+    Index(i64),
+    Iterate,
+    Length,
+    Keys,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonFilter {
+    /// The stages separated by pipes, each stage a path or a function
+    stages: Vec<Vec<Step>>,
+}
+
+impl std::str::FromStr for JsonFilter {
+    type Err = color_eyre::eyre::Report;
+
+    fn from_str(filter: &str) -> Result<Self, Self::Err> {
+        parse_filter(filter).map_err(|err| {
+            color_eyre::eyre::eyre!(
+                "{err}. Only a subset of the jq filters is supported ({SUPPORTED_FILTERS}); for the others, leave out --jq-filter and pipe the output into jq"
+            )
+        })
+    }
+}
+
+fn parse_filter(filter: &str) -> color_eyre::eyre::Result<JsonFilter> {
+    let mut chars = filter.chars().peekable();
+    let mut stages = Vec::new();
+    loop {
+        skip_whitespace(&mut chars);
+        stages.push(parse_stage(&mut chars, filter)?);
+        skip_whitespace(&mut chars);
+        match chars.next() {
+            None => break,
+            Some('|') => {}
+            Some(c) => color_eyre::eyre::bail!("Unexpected '{c}' in the filter '{filter}'"),
+        }
+    }
+    Ok(JsonFilter { stages })
+}
+
+impl JsonFilter {
+    /// The values the filter produces from the input, as `jq` prints them.
+    pub fn apply(&self, input: Value) -> color_eyre::eyre::Result<Vec<Value>> {
+        let mut values = vec![input];
+        for step in self.stages.iter().flatten() {
+            let mut next_values = Vec::new();
+            for value in values {
+                next_values.extend(apply_step(step, value)?);
+            }
+            values = next_values;
+        }
+        Ok(values)
+    }
+}
+
+type Chars<'a> = std::iter::Peekable<std::str::Chars<'a>>;
+
+fn skip_whitespace(chars: &mut Chars) {
+    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+fn read_identifier(chars: &mut Chars) -> String {
+    std::iter::from_fn(|| chars.next_if(|c| is_identifier_char(*c))).collect()
+}
+
+/// A JSON string literal, with escapes
+fn read_string(chars: &mut Chars, filter: &str) -> color_eyre::eyre::Result<String> {
+    let mut literal = String::from(chars.next().unwrap_or('"'));
+    loop {
+        match chars.next() {
+            Some('\\') => {
+                literal.push('\\');
+                literal.extend(chars.next());
+            }
+            Some('"') => {
+                literal.push('"');
+                break;
+            }
+            Some(c) => literal.push(c),
+            None => color_eyre::eyre::bail!("Unterminated string in the filter '{filter}'"),
+        }
+    }
+    serde_json::from_str(&literal).map_err(|err| {
+        color_eyre::eyre::eyre!("Invalid string {literal} in the filter '{filter}': {err}")
+    })
+}
+
+fn parse_stage(chars: &mut Chars, filter: &str) -> color_eyre::eyre::Result<Vec<Step>> {
+    if chars.peek().copied().is_some_and(is_identifier_char) {
+        return match read_identifier(chars).as_str() {
+            "length" => Ok(vec![Step::Length]),
+            "keys" => Ok(vec![Step::Keys]),
+            function => color_eyre::eyre::bail!(
+                "The function '{function}' of the filter '{filter}' is not supported"
+            ),
+        };
+    }
+    if chars.next() != Some('.') {
+        color_eyre::eyre::bail!("Expected a path starting with '.' in the filter '{filter}'");
+    }
+    let mut steps = Vec::new();
+    let mut after_dot = true;
+    loop {
+        match chars.peek().copied() {
+            Some(c) if after_dot && (c.is_ascii_alphabetic() || c == '_') => {
+                steps.push(Step::Field(read_identifier(chars)));
+            }
+            Some('"') if after_dot => steps.push(Step::Field(read_string(chars, filter)?)),
+            Some('[') => {
+                chars.next();
+                skip_whitespace(chars);
+                match chars.peek().copied() {
+                    Some(']') => steps.push(Step::Iterate),
+                    Some('"') => steps.push(Step::Field(read_string(chars, filter)?)),
+                    _ => {
+                        let mut index = String::from_iter(chars.next_if_eq(&'-'));
+                        index.extend(std::iter::from_fn(|| chars.next_if(char::is_ascii_digit)));
+                        steps.push(Step::Index(index.parse().map_err(|_| {
+                            color_eyre::eyre::eyre!(
+                                "Expected an index, a string or ']' after '[' in the filter '{filter}'"
+                            )
+                        })?));
+                    }
+                }
+                skip_whitespace(chars);
+                if chars.next() != Some(']') {
+                    color_eyre::eyre::bail!("Expected ']' in the filter '{filter}'");
+                }
+            }
+            Some('.') if !after_dot => {
+                chars.next();
+                after_dot = true;
+                continue;
+            }
+            _ => break,
+        }
+        after_dot = false;
+    }
+    if after_dot && !steps.is_empty() {
+        color_eyre::eyre::bail!("Expected a field name after '.' in the filter '{filter}'");
+    }
+    Ok(steps)
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn apply_step(step: &Step, value: Value) -> color_eyre::eyre::Result<Vec<Value>> {
+    Ok(match (step, value) {
+        (Step::Field(_) | Step::Index(_), Value::Null) => vec![Value::Null],
+        (Step::Field(field), Value::Object(mut object)) => {
+            vec![object.remove(field).unwrap_or(Value::Null)]
+        }
+        (Step::Index(index), Value::Array(mut array)) => {
+            let position = if *index < 0 {
+                i64::try_from(array.len())?.checked_add(*index)
+            } else {
+                Some(*index)
+            };
+            let value = position
+                .and_then(|position| usize::try_from(position).ok())
+                .filter(|position| *position < array.len())
+                .map_or(Value::Null, |position| array.swap_remove(position));
+            vec![value]
+        }
+        (Step::Iterate, Value::Array(array)) => array,
+        (Step::Iterate, Value::Object(object)) => {
+            object.into_iter().map(|(_, value)| value).collect()
+        }
+        (Step::Length, Value::Null) => vec![Value::from(0)],
+        (Step::Length, Value::Number(number)) => vec![number
+            .as_i64()
+            .map(|number| Value::from(number.unsigned_abs()))
+            .or_else(|| number.as_f64().map(|number| Value::from(number.abs())))
+            .unwrap_or(Value::Number(number))],
+        (Step::Length, Value::String(string)) => vec![Value::from(string.chars().count())],
+        (Step::Length, Value::Array(array)) => vec![Value::from(array.len())],
+        (Step::Length, Value::Object(object)) => vec![Value::from(object.len())],
+        (Step::Keys, Value::Object(object)) => {
+            let mut keys: Vec<String> = object.into_iter().map(|(key, _)| key).collect();
+            keys.sort();
+            vec![Value::from(keys)]
+        }
+        (Step::Keys, Value::Array(array)) => vec![Value::from_iter(0..array.len())],
+        (step, value) => {
+            let operation = match step {
+                Step::Field(field) => format!("get the field \"{field}\""),
+                Step::Index(index) => format!("get the index {index}"),
+                Step::Iterate => "iterate over".to_string(),
+                Step::Length => "get the length".to_string(),
+                Step::Keys => "get the keys".to_string(),
+            };
+            color_eyre::eyre::bail!("Cannot {operation} of {} {value}", type_name(&value))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(filter: &str, input: Value) -> Vec<Value> {
+        filter.parse::<JsonFilter>().unwrap().apply(input).unwrap()
+    }
+
+    #[test]
+    fn paths_and_iteration() {
+        let input = serde_json::json!({
+            "owner_id": "alice.near",
+            "tokens": [{ "token_id": "1" }, { "token_id": "2", "metadata": { "title": "Two" } }],
+            "a key": true
+        });
+        assert_eq!(apply(".", input.clone()), [input.clone()]);
+        assert_eq!(apply(".owner_id", input.clone()), ["alice.near"]);
+        assert_eq!(apply(".tokens[0].token_id", input.clone()), ["1"]);
+        assert_eq!(apply(".tokens.[-1].metadata.title", input.clone()), ["Two"]);
+        assert_eq!(apply(".tokens[5]", input.clone()), [Value::Null]);
+        assert_eq!(apply(".missing.field", input.clone()), [Value::Null]);
+        assert_eq!(apply(r#".["a key"]"#, input.clone()), [true]);
+        assert_eq!(apply(r#"."a key""#, input.clone()), [true]);
+        assert_eq!(apply(".tokens[] | .token_id", input.clone()), ["1", "2"]);
+        assert_eq!(
+            apply(".tokens[].metadata", input.clone()),
+            [Value::Null, serde_json::json!({ "title": "Two" })]
+        );
+        assert_eq!(apply(".tokens | length", input.clone()), [2]);
+        assert_eq!(
+            apply("keys", input.clone()),
+            [serde_json::json!(["a key", "owner_id", "tokens"])]
+        );
+        assert_eq!(apply(".owner_id | length", input), [10]);
+    }
+
+    #[test]
+    fn invalid_filters() {
+        for filter in [
+            "owner_id",
+            ".a.",
+            ".[x]",
+            ".[0",
+            ".a b",
+            "select(.a)",
+            r#".["a"#,
+            ".[1:2]",
+            ".a?",
+            ".a, .b",
+            "{a: .a}",
+        ] {
+            assert!(filter.parse::<JsonFilter>().is_err(), "{filter}");
+        }
+        let err = "map(.a)".parse::<JsonFilter>().unwrap_err().to_string();
+        assert!(
+            err.starts_with("The function 'map' of the filter 'map(.a)' is not supported. Only a subset of the jq filters is supported"),
+            "{err}"
+        );
+        let err = ".a"
+            .parse::<JsonFilter>()
+            .unwrap()
+            .apply(serde_json::json!([1]))
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Cannot get the field \"a\" of array [1]");
+        assert!(".[]"
+            .parse::<JsonFilter>()
+            .unwrap()
+            .apply(serde_json::json!("text"))
+            .is_err());
+    }
+}
@@ -0,0 +1,106 @@
+use color_eyre::eyre::WrapErr;
+
+use crate::common::{CallResultExt, JsonRpcClientExt};
+
+mod json_filter;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = ViewJsonContext)]
+pub struct ViewJson {
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the contract account ID?
+    contract_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    /// What is the name of the view method?
+    method: String,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// The JSON arguments of the view method (default: {})
+    args_json: Option<crate::types::json::Json>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// A filter of the result in a subset of the jq syntax: paths ('.owner_id', '.[0].token_id'), '.[]', 'length', 'keys' and '|' (no other jq functions, operators, slices or '?')
+    jq_filter: Option<String>,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network_view_at_block::NetworkViewAtBlockArgs,
+}
+
+#[derive(Clone)]
+pub struct ViewJsonContext(crate::network_view_at_block::ArgsForViewContext);
+
+impl ViewJsonContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<ViewJson as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let args = match &scope.args_json {
+            Some(args_json) => args_json.try_into_bytes()?,
+            None => b"{}".to_vec(),
+        };
+        let json_filter = scope
+            .jq_filter
+            .as_deref()
+            .map(str::parse::<self::json_filter::JsonFilter>)
+            .transpose()?;
+
+        let on_after_getting_block_reference_callback: crate::network_view_at_block::OnAfterGettingBlockReferenceCallback = std::sync::Arc::new({
+            let contract_account_id: near_primitives::types::AccountId = scope.contract_id.clone().into();
+            let method = scope.method.clone();
+
+            move |network_config, block_reference| {
+                let call_result = network_config
+                    .json_rpc_client()
+                    .blocking_call_view_function(
+                        &contract_account_id,
+                        &method,
+                        args.clone(),
+                        block_reference.clone(),
+                    )
+                    .wrap_err_with(|| {
+                        format!(
+                            "Failed to fetch query for read-only function call: '{method}' (contract <{contract_account_id}> on network <{}>)",
+                            network_config.network_name
+                        )
+                    })?;
+                call_result.print_logs();
+                let result: serde_json::Value = call_result
+                    .parse_result_from_json()
+                    .wrap_err_with(|| format!("The result of '{method}' is not JSON"))?;
+                let values = match &json_filter {
+                    Some(json_filter) => json_filter.apply(result)?,
+                    None => vec![result],
+                };
+                for value in values {
+                    println!("{}", serde_json::to_string_pretty(&value)?);
+                }
+                Ok(())
+            }
+        });
+
+        Ok(Self(crate::network_view_at_block::ArgsForViewContext {
+            config: previous_context.config,
+            interacting_with_account_ids: vec![scope.contract_id.clone().into()],
+            on_after_getting_block_reference_callback,
+        }))
+    }
+}
+
+impl From<ViewJsonContext> for crate::network_view_at_block::ArgsForViewContext {
+    fn from(item: ViewJsonContext) -> Self {
+        item.0
+    }
+}
+
+impl ViewJson {
+    pub fn input_contract_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_non_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "What is the contract account ID?",
+        )
+    }
+}
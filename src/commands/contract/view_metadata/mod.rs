@@ -0,0 +1,113 @@
+use color_eyre::eyre::WrapErr;
+
+use crate::common::{CallResultExt, JsonRpcClientExt, RpcQueryResponseExt};
+
+/// The view methods of the metadata standards, in the order they are tried
+const METADATA_METHODS: [&str; 3] = ["nft_metadata", "ft_metadata", "contract_metadata"];
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = ViewMetadataContext)]
+pub struct ViewMetadata {
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the contract account ID?
+    contract_id: crate::types::account_id::AccountId,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network_view_at_block::NetworkViewAtBlockArgs,
+}
+
+#[derive(Clone)]
+pub struct ViewMetadataContext(crate::network_view_at_block::ArgsForViewContext);
+
+impl ViewMetadataContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<ViewMetadata as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let on_after_getting_block_reference_callback: crate::network_view_at_block::OnAfterGettingBlockReferenceCallback = std::sync::Arc::new({
+            let contract_account_id: near_primitives::types::AccountId = scope.contract_id.clone().into();
+
+            move |network_config, block_reference| {
+                if let Some((method_name, metadata)) =
+                    get_metadata(network_config, &contract_account_id, block_reference)
+                {
+                    eprintln!("Metadata of <{contract_account_id}> ('{method_name}'):");
+                    println!("{}", serde_json::to_string_pretty(&metadata)?);
+                    return Ok(());
+                }
+
+                let account_view = network_config
+                    .json_rpc_client()
+                    .blocking_call_view_account(&contract_account_id, block_reference.clone())
+                    .wrap_err_with(|| {
+                        format!(
+                            "Failed to fetch query ViewAccount for <{contract_account_id}> on network <{}>",
+                            network_config.network_name
+                        )
+                    })?
+                    .account_view()?;
+                eprintln!(
+                    "<{contract_account_id}> does not implement any of the metadata methods ({}).",
+                    METADATA_METHODS.join(", ")
+                );
+                if account_view.code_hash == near_primitives::hash::CryptoHash::default() {
+                    eprintln!("There is no contract deployed to <{contract_account_id}>.");
+                } else {
+                    eprintln!("Contract code hash:");
+                    println!("{}", account_view.code_hash);
+                }
+                Ok(())
+            }
+        });
+
+        Ok(Self(crate::network_view_at_block::ArgsForViewContext {
+            config: previous_context.config,
+            interacting_with_account_ids: vec![scope.contract_id.clone().into()],
+            on_after_getting_block_reference_callback,
+        }))
+    }
+}
+
+impl From<ViewMetadataContext> for crate::network_view_at_block::ArgsForViewContext {
+    fn from(item: ViewMetadataContext) -> Self {
+        item.0
+    }
+}
+
+impl ViewMetadata {
+    pub fn input_contract_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_non_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "What is the contract account ID?",
+        )
+    }
+}
+
+/// The first metadata method that succeeds and its result.
+#[tracing::instrument(name = "Getting the contract metadata ...", skip_all)]
+fn get_metadata(
+    network_config: &crate::config::NetworkConfig,
+    contract_account_id: &near_primitives::types::AccountId,
+    block_reference: &near_primitives::types::BlockReference,
+) -> Option<(&'static str, serde_json::Value)> {
+    let json_rpc_client = network_config.json_rpc_client();
+    METADATA_METHODS.into_iter().find_map(|method_name| {
+        json_rpc_client
+            .blocking_call_view_function(
+                contract_account_id,
+                method_name,
+                b"{}".to_vec(),
+                block_reference.clone(),
+            )
+            .and_then(|call_result| call_result.parse_result_from_json::<serde_json::Value>())
+            .inspect_err(|err| {
+                tracing::debug!("'{method_name}' of <{contract_account_id}> failed: {err:#}")
+            })
+            .ok()
+            .map(|metadata| (method_name, metadata))
+    })
+}
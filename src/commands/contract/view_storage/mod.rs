@@ -1,5 +1,5 @@
 mod keys_to_view;
-mod output_format;
+pub(super) mod output_format;
 
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = crate::GlobalContext)]
@@ -24,6 +24,10 @@ impl AsTextContext {
             move |network_config, block_reference| {
                 let query_view_method_response =
                     super::get_contract_state(&contract_account_id, prefix.clone(), network_config, block_reference.clone())?;
+                crate::network_view_at_block::display_viewed_at_block(
+                    query_view_method_response.block_height,
+                    &query_view_method_response.block_hash,
+                );
 
                 if let near_jsonrpc_primitives::types::query::QueryResponseKind::ViewState(result) =
                     query_view_method_response.kind
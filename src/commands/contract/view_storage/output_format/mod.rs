@@ -30,20 +30,33 @@ pub fn get_contract_state(
     network_config: &crate::config::NetworkConfig,
     block_reference: near_primitives::types::BlockReference,
 ) -> color_eyre::eyre::Result<near_jsonrpc_client::methods::query::RpcQueryResponse> {
-    network_config
-        .json_rpc_client()
-        .blocking_call(near_jsonrpc_client::methods::query::RpcQueryRequest {
-            block_reference,
+    match network_config.json_rpc_client().blocking_call(
+        near_jsonrpc_client::methods::query::RpcQueryRequest {
+            block_reference: block_reference.clone(),
             request: near_primitives::views::QueryRequest::ViewState {
                 account_id: contract_account_id.clone(),
-                prefix,
+                prefix: prefix.clone(),
                 include_proof: false,
             },
-        })
-        .wrap_err_with(|| {
+        },
+    ) {
+        Err(near_jsonrpc_client::errors::JsonRpcError::ServerError(
+            near_jsonrpc_client::errors::JsonRpcServerError::HandlerError(
+                near_jsonrpc_primitives::types::query::RpcQueryError::TooLargeContractState {
+                    ..
+                },
+            ),
+        )) => crate::view_pagination::get_contract_state_in_parts(
+            network_config,
+            contract_account_id,
+            prefix.as_ref(),
+            &block_reference,
+        ),
+        response => response.wrap_err_with(|| {
             format!(
                 "Failed to fetch query ViewState for <{contract_account_id}> on network <{}>",
                 network_config.network_name
             )
-        })
+        }),
+    }
 }
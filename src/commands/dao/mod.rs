@@ -0,0 +1,36 @@
+use strum::{EnumDiscriminants, EnumIter, EnumMessage};
+
+mod policy;
+mod proposals;
+mod propose_transfer;
+mod vote;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(context = crate::GlobalContext)]
+pub struct DaoCommands {
+    #[interactive_clap(subcommand)]
+    dao_actions: DaoActions,
+}
+
+#[derive(Debug, EnumDiscriminants, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(context = crate::GlobalContext)]
+#[strum_discriminants(derive(EnumMessage, EnumIter))]
+#[non_exhaustive]
+/// Сhoose action for SputnikDAO:
+pub enum DaoActions {
+    #[strum_discriminants(strum(
+        message = "proposals         - View the active proposals of a DAO"
+    ))]
+    /// View the active proposals of a DAO
+    Proposals(self::proposals::ViewProposals),
+    #[strum_discriminants(strum(
+        message = "propose-transfer  - Submit a proposal to transfer NEAR from a DAO"
+    ))]
+    /// Submit a proposal to transfer NEAR from a DAO
+    ProposeTransfer(self::propose_transfer::ProposeTransfer),
+    #[strum_discriminants(strum(
+        message = "vote              - Vote to approve or reject a DAO proposal"
+    ))]
+    /// Vote to approve or reject a DAO proposal
+    Vote(self::vote::Vote),
+}
@@ -0,0 +1,251 @@
+use color_eyre::eyre::WrapErr;
+
+use crate::common::{CallResultExt, JsonRpcClientExt};
+
+/// The policy labels of SputnikDAO proposal kinds (`ProposalKind::to_policy_label` in the contract).
+const PROPOSAL_KIND_LABELS: &[(&str, &str)] = &[
+    ("ChangeConfig", "config"),
+    ("ChangePolicy", "policy"),
+    ("AddMemberToRole", "add_member_to_role"),
+    ("RemoveMemberFromRole", "remove_member_from_role"),
+    ("FunctionCall", "call"),
+    ("UpgradeSelf", "upgrade_self"),
+    ("UpgradeRemote", "upgrade_remote"),
+    ("Transfer", "transfer"),
+    ("SetStakingContract", "set_vote_token"),
+    ("AddBounty", "add_bounty"),
+    ("BountyDone", "bounty_done"),
+    ("Vote", "vote"),
+    ("FactoryInfoUpdate", "factory_info_update"),
+    ("ChangePolicyAddOrUpdateRole", "policy_add_or_update_role"),
+    ("ChangePolicyRemoveRole", "policy_remove_role"),
+    (
+        "ChangePolicyUpdateDefaultVotePolicy",
+        "policy_update_default_vote_policy",
+    ),
+    ("ChangePolicyUpdateParameters", "policy_update_parameters"),
+];
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct Policy {
+    pub roles: Vec<Role>,
+    proposal_bond: String,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct Role {
+    pub name: String,
+    pub kind: RoleKind,
+    pub permissions: Vec<String>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub enum RoleKind {
+    Everyone,
+    /// Token holders with at least the given balance
+    Member(String),
+    Group(Vec<near_primitives::types::AccountId>),
+}
+
+impl Policy {
+    pub fn proposal_bond(&self) -> color_eyre::eyre::Result<near_token::NearToken> {
+        Ok(near_token::NearToken::from_yoctonear(
+            self.proposal_bond
+                .parse()
+                .wrap_err("The proposal bond in the DAO policy is not a valid amount")?,
+        ))
+    }
+
+    /// Names of the roles of the account that allow the action on the proposals of the given kind.
+    fn permitted_roles(
+        &self,
+        account_id: &near_primitives::types::AccountId,
+        proposal_kind_label: &str,
+        action: &str,
+    ) -> Vec<&str> {
+        self.roles
+            .iter()
+            .filter(|role| match &role.kind {
+                RoleKind::Everyone => true,
+                RoleKind::Member(_) => false,
+                RoleKind::Group(members) => members.contains(account_id),
+            })
+            .filter(|role| {
+                role.permissions
+                    .iter()
+                    .any(|permission| permission_allows(permission, proposal_kind_label, action))
+            })
+            .map(|role| role.name.as_str())
+            .collect()
+    }
+
+    /// The contract is the final judge, so a missing permission is only reported as a warning.
+    pub fn warn_if_not_permitted(
+        &self,
+        account_id: &near_primitives::types::AccountId,
+        proposal_kind_label: &str,
+        action: &str,
+    ) {
+        if !self
+            .permitted_roles(account_id, proposal_kind_label, action)
+            .is_empty()
+        {
+            return;
+        }
+        let token_weighted_roles = self
+            .roles
+            .iter()
+            .filter(|role| {
+                role.permissions
+                    .iter()
+                    .any(|permission| permission_allows(permission, proposal_kind_label, action))
+            })
+            .filter_map(|role| match &role.kind {
+                RoleKind::Member(min_balance) => Some(format!(
+                    "{} (with at least {min_balance} of the vote token)",
+                    role.name
+                )),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        if token_weighted_roles.is_empty() {
+            eprintln!(
+                "\nWarning: <{account_id}> has no role in the DAO policy that permits \"{proposal_kind_label}:{action}\", the transaction is likely to fail."
+            );
+        } else {
+            eprintln!(
+                "\nWarning: <{account_id}> is not in a group permitted to \"{proposal_kind_label}:{action}\", it is only permitted to token holders of the roles: {}",
+                token_weighted_roles.join(", ")
+            );
+        }
+    }
+}
+
+/// Permissions are `<proposal kind label>:<action>`, where both parts may be `*`.
+fn permission_allows(permission: &str, proposal_kind_label: &str, action: &str) -> bool {
+    match permission.split_once(':') {
+        Some((permission_kind, permission_action)) => {
+            (permission_kind == "*" || permission_kind == proposal_kind_label)
+                && (permission_action == "*" || permission_action == action)
+        }
+        None => false,
+    }
+}
+
+/// The kind of a proposal is serialized either as a string (`"Vote"`) or as a single-key object.
+pub fn proposal_kind_name(kind: &serde_json::Value) -> String {
+    match kind {
+        serde_json::Value::String(name) => name.clone(),
+        serde_json::Value::Object(fields) => fields.keys().next().cloned().unwrap_or_default(),
+        _ => String::new(),
+    }
+}
+
+pub fn proposal_kind_label(kind: &serde_json::Value) -> String {
+    let name = proposal_kind_name(kind);
+    PROPOSAL_KIND_LABELS
+        .iter()
+        .find(|(kind_name, _)| *kind_name == name)
+        .map_or_else(|| name.to_lowercase(), |(_, label)| label.to_string())
+}
+
+#[tracing::instrument(name = "Getting the DAO policy ...", skip_all)]
+pub fn get_policy(
+    network_config: &crate::config::NetworkConfig,
+    dao_account_id: &near_primitives::types::AccountId,
+    block_reference: near_primitives::types::BlockReference,
+) -> color_eyre::eyre::Result<Policy> {
+    network_config
+        .json_rpc_client()
+        .blocking_call_view_function(dao_account_id, "get_policy", vec![], block_reference)
+        .wrap_err_with(|| {
+            format!("Failed to fetch the policy of the DAO <{dao_account_id}>, is it a SputnikDAO contract?")
+        })?
+        .parse_result_from_json()
+        .wrap_err_with(|| format!("Failed to parse the policy of the DAO <{dao_account_id}>"))
+}
+
+#[tracing::instrument(name = "Getting the DAO proposal ...", skip_all)]
+pub fn get_proposal(
+    network_config: &crate::config::NetworkConfig,
+    dao_account_id: &near_primitives::types::AccountId,
+    proposal_id: u64,
+) -> color_eyre::eyre::Result<serde_json::Value> {
+    network_config
+        .json_rpc_client()
+        .blocking_call_view_function(
+            dao_account_id,
+            "get_proposal",
+            serde_json::to_vec(&serde_json::json!({ "id": proposal_id }))?,
+            near_primitives::types::Finality::Final.into(),
+        )
+        .wrap_err_with(|| {
+            format!("Failed to fetch the proposal #{proposal_id} of the DAO <{dao_account_id}>")
+        })?
+        .parse_result_from_json()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn policy_permitted_roles() {
+        let policy: Policy = serde_json::from_value(serde_json::json!({
+            "roles": [
+                { "name": "all", "kind": "Everyone", "permissions": ["*:AddProposal"], "vote_policy": {} },
+                {
+                    "name": "council",
+                    "kind": { "Group": ["alice.near", "bob.near"] },
+                    "permissions": ["*:Finalize", "transfer:VoteApprove", "transfer:VoteReject"],
+                    "vote_policy": {}
+                },
+                { "name": "holders", "kind": { "Member": "1000" }, "permissions": ["*:*"], "vote_policy": {} },
+            ],
+            "default_vote_policy": {},
+            "proposal_bond": "100000000000000000000000",
+            "proposal_period": "604800000000000",
+            "bounty_bond": "100000000000000000000000",
+            "bounty_forgiveness_period": "86400000000000"
+        }))
+        .unwrap();
+        let alice: near_primitives::types::AccountId = "alice.near".parse().unwrap();
+        let carol: near_primitives::types::AccountId = "carol.near".parse().unwrap();
+
+        assert_eq!(
+            policy.proposal_bond().unwrap(),
+            near_token::NearToken::from_millinear(100)
+        );
+        assert_eq!(
+            policy.permitted_roles(&alice, "transfer", "VoteApprove"),
+            vec!["council"]
+        );
+        assert!(policy
+            .permitted_roles(&alice, "call", "VoteApprove")
+            .is_empty());
+        assert!(policy
+            .permitted_roles(&carol, "transfer", "VoteApprove")
+            .is_empty());
+        assert_eq!(
+            policy.permitted_roles(&carol, "transfer", "AddProposal"),
+            vec!["all"]
+        );
+    }
+
+    #[test]
+    fn proposal_kind_labels() {
+        assert_eq!(
+            proposal_kind_label(&serde_json::json!({ "Transfer": { "token_id": "" } })),
+            "transfer"
+        );
+        assert_eq!(
+            proposal_kind_label(&serde_json::json!({ "FunctionCall": {} })),
+            "call"
+        );
+        assert_eq!(proposal_kind_label(&serde_json::json!("Vote")), "vote");
+        assert_eq!(
+            proposal_kind_name(&serde_json::json!({ "AddMemberToRole": {} })),
+            "AddMemberToRole"
+        );
+    }
+}
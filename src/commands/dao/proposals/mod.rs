@@ -0,0 +1,163 @@
+use color_eyre::eyre::Context;
+use prettytable::Table;
+use tracing_indicatif::span_ext::IndicatifSpanExt;
+
+use crate::common::{CallResultExt, JsonRpcClientExt};
+
+const PROPOSALS_PAGE_SIZE: u64 = 100;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = ViewProposalsContext)]
+pub struct ViewProposals {
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the DAO account ID?
+    dao_account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network_view_at_block::NetworkViewAtBlockArgs,
+}
+
+#[derive(Clone)]
+pub struct ViewProposalsContext(crate::network_view_at_block::ArgsForViewContext);
+
+impl ViewProposalsContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<ViewProposals as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let on_after_getting_block_reference_callback: crate::network_view_at_block::OnAfterGettingBlockReferenceCallback = std::sync::Arc::new({
+            let dao_account_id: near_primitives::types::AccountId = scope.dao_account_id.clone().into();
+
+            move |network_config, block_reference| {
+                let proposals = get_proposals(network_config, &dao_account_id, block_reference)?;
+                let active_proposals = proposals
+                    .iter()
+                    .filter(|proposal| proposal["status"].as_str() == Some("InProgress"))
+                    .collect::<Vec<_>>();
+                if active_proposals.is_empty() {
+                    eprintln!("\nThe DAO <{dao_account_id}> has no active proposals.");
+                    return Ok(());
+                }
+                display_proposals(&dao_account_id, &active_proposals);
+                Ok(())
+            }
+        });
+        Ok(Self(crate::network_view_at_block::ArgsForViewContext {
+            config: previous_context.config,
+            interacting_with_account_ids: vec![scope.dao_account_id.clone().into()],
+            on_after_getting_block_reference_callback,
+        }))
+    }
+}
+
+impl From<ViewProposalsContext> for crate::network_view_at_block::ArgsForViewContext {
+    fn from(item: ViewProposalsContext) -> Self {
+        item.0
+    }
+}
+
+impl ViewProposals {
+    pub fn input_dao_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_non_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "What is the DAO account ID?",
+        )
+    }
+}
+
+#[tracing::instrument(name = "Getting the DAO proposals ...", skip_all)]
+fn get_proposals(
+    network_config: &crate::config::NetworkConfig,
+    dao_account_id: &near_primitives::types::AccountId,
+    block_reference: &near_primitives::types::BlockReference,
+) -> color_eyre::eyre::Result<Vec<serde_json::Value>> {
+    let json_rpc_client = network_config.json_rpc_client();
+    let last_proposal_id: u64 = json_rpc_client
+        .blocking_call_view_function(
+            dao_account_id,
+            "get_last_proposal_id",
+            vec![],
+            block_reference.clone(),
+        )
+        .wrap_err_with(|| {
+            format!("Failed to fetch the proposals of the DAO <{dao_account_id}>, is it a SputnikDAO contract?")
+        })?
+        .parse_result_from_json()?;
+
+    let mut proposals = Vec::new();
+    for from_index in (0..last_proposal_id).step_by(PROPOSALS_PAGE_SIZE as usize) {
+        tracing::Span::current().pb_set_message(&format!("{from_index} of {last_proposal_id} ..."));
+        let page: Vec<serde_json::Value> = json_rpc_client
+            .blocking_call_view_function(
+                dao_account_id,
+                "get_proposals",
+                serde_json::to_vec(&serde_json::json!({
+                    "from_index": from_index,
+                    "limit": PROPOSALS_PAGE_SIZE,
+                }))?,
+                block_reference.clone(),
+            )
+            .wrap_err("Failed to fetch query for view method: 'get_proposals'")?
+            .parse_result_from_json()?;
+        proposals.extend(page);
+    }
+    Ok(proposals)
+}
+
+/// Counts of the `Approve`, `Reject` and `Remove` votes of a proposal.
+fn vote_counts(proposal: &serde_json::Value) -> (usize, usize, usize) {
+    let Some(votes) = proposal["votes"].as_object() else {
+        return (0, 0, 0);
+    };
+    let count = |vote: &str| votes.values().filter(|value| *value == vote).count();
+    (count("Approve"), count("Reject"), count("Remove"))
+}
+
+fn display_proposals(
+    dao_account_id: &near_primitives::types::AccountId,
+    proposals: &[&serde_json::Value],
+) {
+    let mut table = Table::new();
+    table.set_titles(prettytable::row![Fg=>"ID", "Kind", "Proposer", "Approve", "Reject", "Remove", "Description"]);
+    for proposal in proposals {
+        let (approve, reject, remove) = vote_counts(proposal);
+        table.add_row(prettytable::row![
+            proposal["id"],
+            super::policy::proposal_kind_name(&proposal["kind"]),
+            proposal["proposer"].as_str().unwrap_or_default(),
+            Fg->approve,
+            Fr->reject,
+            remove,
+            proposal["description"].as_str().unwrap_or_default()
+        ]);
+    }
+    table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    eprintln!(
+        "\nActive proposals of the DAO <{dao_account_id}> ({}):",
+        proposals.len()
+    );
+    table.printstd();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proposal_vote_counts() {
+        let proposal = serde_json::json!({
+            "id": 7,
+            "votes": {
+                "alice.near": "Approve",
+                "bob.near": "Reject",
+                "carol.near": "Approve",
+                "dave.near": "Remove",
+            }
+        });
+        assert_eq!(vote_counts(&proposal), (2, 1, 1));
+        assert_eq!(vote_counts(&serde_json::json!({ "id": 8 })), (0, 0, 0));
+    }
+}
@@ -0,0 +1,184 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = ProposeTransferContext)]
+pub struct ProposeTransfer {
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the DAO account ID?
+    dao_account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the receiver account ID of the transfer?
+    receiver: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    /// How many NEAR Tokens do you want to transfer from the DAO? (example: 10NEAR or 0.5near or 10000yoctonear)
+    amount: crate::types::near_token::NearToken,
+    #[interactive_clap(long)]
+    /// Enter the description of the proposal:
+    description: String,
+    #[interactive_clap(named_arg)]
+    /// What is the signer account ID?
+    sign_as: SignerAccountId,
+}
+
+#[derive(Clone)]
+pub struct ProposeTransferContext {
+    global_context: crate::GlobalContext,
+    dao_account_id: near_primitives::types::AccountId,
+    receiver_account_id: near_primitives::types::AccountId,
+    amount: crate::types::near_token::NearToken,
+    description: String,
+}
+
+impl ProposeTransferContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<ProposeTransfer as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        Ok(Self {
+            global_context: previous_context,
+            dao_account_id: scope.dao_account_id.clone().into(),
+            receiver_account_id: scope.receiver.clone().into(),
+            amount: scope.amount,
+            description: scope.description.clone(),
+        })
+    }
+}
+
+impl ProposeTransfer {
+    pub fn input_dao_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_non_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "What is the DAO account ID?",
+        )
+    }
+
+    pub fn input_receiver(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_non_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "What is the receiver account ID of the transfer?",
+        )
+    }
+}
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = ProposeTransferContext)]
+#[interactive_clap(output_context = SignerAccountIdContext)]
+pub struct SignerAccountId {
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the signer account ID?
+    signer_account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network_for_transaction::NetworkForTransactionArgs,
+}
+
+#[derive(Clone)]
+pub struct SignerAccountIdContext(crate::commands::ActionContext);
+
+impl SignerAccountIdContext {
+    pub fn from_previous_context(
+        previous_context: ProposeTransferContext,
+        scope: &<SignerAccountId as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let get_prepopulated_transaction_after_getting_network_callback: crate::commands::GetPrepopulatedTransactionAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let signer_account_id: near_primitives::types::AccountId =
+                    scope.signer_account_id.clone().into();
+                let previous_context = previous_context.clone();
+
+                move |network_config| {
+                    let policy = super::policy::get_policy(
+                        network_config,
+                        &previous_context.dao_account_id,
+                        near_primitives::types::Finality::Final.into(),
+                    )?;
+                    policy.warn_if_not_permitted(&signer_account_id, "transfer", "AddProposal");
+                    let proposal_bond = policy.proposal_bond()?;
+                    eprintln!(
+                        "\nThe proposal bond of {} will be attached (it is refunded once the proposal is finalized).",
+                        crate::types::near_token::NearToken::from(proposal_bond)
+                    );
+
+                    Ok(crate::commands::PrepopulatedTransaction {
+                        signer_id: signer_account_id.clone(),
+                        receiver_id: previous_context.dao_account_id.clone(),
+                        actions: vec![near_primitives::transaction::Action::FunctionCall(
+                            Box::new(near_primitives::transaction::FunctionCallAction {
+                                method_name: "add_proposal".to_string(),
+                                args: serde_json::to_vec(&serde_json::json!({
+                                    "proposal": {
+                                        "description": previous_context.description,
+                                        "kind": {
+                                            "Transfer": {
+                                                "token_id": "",
+                                                "receiver_id": previous_context.receiver_account_id,
+                                                "amount": previous_context.amount.as_yoctonear().to_string(),
+                                            }
+                                        }
+                                    }
+                                }))?,
+                                gas: crate::common::NearGas::from_tgas(100).as_gas(),
+                                deposit: proposal_bond.as_yoctonear(),
+                            }),
+                        )],
+                    })
+                }
+            });
+
+        let on_after_sending_transaction_callback: crate::transaction_signature_options::OnAfterSendingTransactionCallback = std::sync::Arc::new({
+            let dao_account_id = previous_context.dao_account_id.clone();
+
+            move |outcome_view, _network_config| {
+                if let near_primitives::views::FinalExecutionStatus::SuccessValue(result) = &outcome_view.status {
+                    match serde_json::from_slice::<u64>(result) {
+                        Ok(proposal_id) => eprintln!(
+                            "The transfer proposal #{proposal_id} has been successfully added to the DAO <{dao_account_id}>."
+                        ),
+                        Err(_) => eprintln!(
+                            "The transfer proposal has been successfully added to the DAO <{dao_account_id}>."
+                        ),
+                    }
+                }
+                Ok(())
+            }
+        });
+
+        Ok(Self(crate::commands::ActionContext {
+            global_context: previous_context.global_context,
+            interacting_with_account_ids: vec![
+                scope.signer_account_id.clone().into(),
+                previous_context.dao_account_id,
+                previous_context.receiver_account_id,
+            ],
+            get_prepopulated_transaction_after_getting_network_callback,
+            on_before_signing_callback: std::sync::Arc::new(
+                |_prepolulated_unsinged_transaction, _network_config| Ok(()),
+            ),
+            on_before_sending_transaction_callback: std::sync::Arc::new(
+                |_signed_transaction, _network_config| Ok(String::new()),
+            ),
+            on_after_sending_transaction_callback,
+        }))
+    }
+}
+
+impl From<SignerAccountIdContext> for crate::commands::ActionContext {
+    fn from(item: SignerAccountIdContext) -> Self {
+        item.0
+    }
+}
+
+impl SignerAccountId {
+    pub fn input_signer_account_id(
+        context: &ProposeTransferContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_signer_account_id_from_used_account_list(
+            &context.global_context.config.credentials_home_dir,
+            "What is the signer account ID?",
+        )
+    }
+}
@@ -0,0 +1,221 @@
+use strum::IntoEnumIterator;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = VoteContext)]
+pub struct Vote {
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the DAO account ID?
+    dao_account_id: crate::types::account_id::AccountId,
+    /// What is the ID of the proposal?
+    proposal_id: u64,
+    #[interactive_clap(skip_default_input_arg)]
+    /// How do you vote (approve or reject)?
+    vote: VoteAction,
+    #[interactive_clap(named_arg)]
+    /// What is the signer account ID?
+    sign_as: SignerAccountId,
+}
+
+#[derive(Clone)]
+pub struct VoteContext {
+    global_context: crate::GlobalContext,
+    dao_account_id: near_primitives::types::AccountId,
+    proposal_id: u64,
+    vote: VoteAction,
+}
+
+impl VoteContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<Vote as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        Ok(Self {
+            global_context: previous_context,
+            dao_account_id: scope.dao_account_id.clone().into(),
+            proposal_id: scope.proposal_id,
+            vote: scope.vote,
+        })
+    }
+}
+
+impl Vote {
+    pub fn input_dao_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_non_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "What is the DAO account ID?",
+        )
+    }
+
+    pub fn input_vote(
+        _context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<VoteAction>> {
+        crate::common::select(
+            "How do you vote (approve or reject)?",
+            VoteAction::iter().collect(),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum_macros::EnumString, strum_macros::EnumIter)]
+#[strum(serialize_all = "snake_case")]
+pub enum VoteAction {
+    Approve,
+    Reject,
+}
+
+impl std::fmt::Display for VoteAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VoteAction::Approve => write!(f, "approve"),
+            VoteAction::Reject => write!(f, "reject"),
+        }
+    }
+}
+
+impl interactive_clap::ToCli for VoteAction {
+    type CliVariant = VoteAction;
+}
+
+impl VoteAction {
+    /// The name of the action in the DAO contract (`act_proposal`) and its policy permissions
+    fn contract_action(&self) -> &'static str {
+        match self {
+            VoteAction::Approve => "VoteApprove",
+            VoteAction::Reject => "VoteReject",
+        }
+    }
+}
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = VoteContext)]
+#[interactive_clap(output_context = SignerAccountIdContext)]
+pub struct SignerAccountId {
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the signer account ID?
+    signer_account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network_for_transaction::NetworkForTransactionArgs,
+}
+
+#[derive(Clone)]
+pub struct SignerAccountIdContext(crate::commands::ActionContext);
+
+impl SignerAccountIdContext {
+    pub fn from_previous_context(
+        previous_context: VoteContext,
+        scope: &<SignerAccountId as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let get_prepopulated_transaction_after_getting_network_callback: crate::commands::GetPrepopulatedTransactionAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let signer_account_id: near_primitives::types::AccountId =
+                    scope.signer_account_id.clone().into();
+                let previous_context = previous_context.clone();
+
+                move |network_config| {
+                    let proposal = super::policy::get_proposal(
+                        network_config,
+                        &previous_context.dao_account_id,
+                        previous_context.proposal_id,
+                    )?;
+                    if let Some(status) = proposal["status"].as_str() {
+                        if status != "InProgress" {
+                            eprintln!(
+                                "\nWarning: the proposal #{} is not active anymore (status: {status}).",
+                                previous_context.proposal_id
+                            );
+                        }
+                    }
+                    let policy = super::policy::get_policy(
+                        network_config,
+                        &previous_context.dao_account_id,
+                        near_primitives::types::Finality::Final.into(),
+                    )?;
+                    policy.warn_if_not_permitted(
+                        &signer_account_id,
+                        &super::policy::proposal_kind_label(&proposal["kind"]),
+                        previous_context.vote.contract_action(),
+                    );
+
+                    Ok(crate::commands::PrepopulatedTransaction {
+                        signer_id: signer_account_id.clone(),
+                        receiver_id: previous_context.dao_account_id.clone(),
+                        actions: vec![near_primitives::transaction::Action::FunctionCall(
+                            Box::new(near_primitives::transaction::FunctionCallAction {
+                                method_name: "act_proposal".to_string(),
+                                // The proposal kind guards against voting on a proposal that has been replaced (SputnikDAO v3)
+                                args: serde_json::to_vec(&serde_json::json!({
+                                    "id": previous_context.proposal_id,
+                                    "action": previous_context.vote.contract_action(),
+                                    "proposal": proposal["kind"],
+                                }))?,
+                                // An approval may execute the proposal, so all the gas is attached
+                                gas: crate::common::NearGas::from_tgas(300).as_gas(),
+                                deposit: 0,
+                            }),
+                        )],
+                    })
+                }
+            });
+
+        let on_after_sending_transaction_callback: crate::transaction_signature_options::OnAfterSendingTransactionCallback = std::sync::Arc::new({
+            let signer_account_id: near_primitives::types::AccountId = scope.signer_account_id.clone().into();
+            let previous_context = previous_context.clone();
+
+            move |outcome_view, network_config| {
+                if let near_primitives::views::FinalExecutionStatus::SuccessValue(_) = outcome_view.status {
+                    eprintln!(
+                        "<{signer_account_id}> has successfully voted to {} the proposal #{} of the DAO <{}>.",
+                        previous_context.vote, previous_context.proposal_id, previous_context.dao_account_id
+                    );
+                    if let Ok(proposal) = super::policy::get_proposal(
+                        network_config,
+                        &previous_context.dao_account_id,
+                        previous_context.proposal_id,
+                    ) {
+                        if let Some(status) = proposal["status"].as_str() {
+                            eprintln!("The proposal status is now: {status}");
+                        }
+                    }
+                }
+                Ok(())
+            }
+        });
+
+        Ok(Self(crate::commands::ActionContext {
+            global_context: previous_context.global_context,
+            interacting_with_account_ids: vec![
+                scope.signer_account_id.clone().into(),
+                previous_context.dao_account_id,
+            ],
+            get_prepopulated_transaction_after_getting_network_callback,
+            on_before_signing_callback: std::sync::Arc::new(
+                |_prepolulated_unsinged_transaction, _network_config| Ok(()),
+            ),
+            on_before_sending_transaction_callback: std::sync::Arc::new(
+                |_signed_transaction, _network_config| Ok(String::new()),
+            ),
+            on_after_sending_transaction_callback,
+        }))
+    }
+}
+
+impl From<SignerAccountIdContext> for crate::commands::ActionContext {
+    fn from(item: SignerAccountIdContext) -> Self {
+        item.0
+    }
+}
+
+impl SignerAccountId {
+    pub fn input_signer_account_id(
+        context: &VoteContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_signer_account_id_from_used_account_list(
+            &context.global_context.config.credentials_home_dir,
+            "What is the signer account ID?",
+        )
+    }
+}
@@ -0,0 +1,87 @@
+/// How long an extension may take to describe itself
+const DESCRIBE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = ListExtensionsContext)]
+pub struct ListExtensions;
+
+#[derive(Debug, Clone)]
+pub struct ListExtensionsContext;
+
+impl ListExtensionsContext {
+    pub fn from_previous_context(
+        _previous_context: crate::GlobalContext,
+        _scope: &<ListExtensions as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let extensions = crate::common::find_extensions();
+        if extensions.is_empty() {
+            eprintln!(
+                "No extensions found: an extension is an executable `{}<name>` on PATH, run as `near <name>`.",
+                crate::common::EXTENSION_PREFIX
+            );
+            return Ok(Self);
+        }
+        let mut table = prettytable::Table::new();
+        table.set_format(*prettytable::format::consts::FORMAT_NO_COLSEP);
+        table.set_titles(prettytable::row![Fg->"command", Fg->"description", Fg->"executable"]);
+        for (name, path) in extensions {
+            table.add_row(prettytable::row![
+                Fy->format!("near {name}"),
+                describe_extension(&path).unwrap_or_default(),
+                path.display()
+            ]);
+        }
+        table.printstd();
+        Ok(Self)
+    }
+}
+
+/// The first line that the extension prints with `--near-ext-describe`.
+fn describe_extension(path: &std::path::Path) -> Option<String> {
+    let mut child = std::process::Command::new(path)
+        .arg("--near-ext-describe")
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .ok()?;
+    let started_at = std::time::Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => break,
+            Ok(None) if started_at.elapsed() < DESCRIBE_TIMEOUT => {
+                std::thread::sleep(std::time::Duration::from_millis(20));
+            }
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+        }
+    }
+    let output = child.wait_with_output().ok()?;
+    first_line(&String::from_utf8_lossy(&output.stdout))
+}
+
+fn first_line(output: &str) -> Option<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(ToString::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn description_is_the_first_line() {
+        assert_eq!(
+            first_line("\n  List NFTs on a marketplace \nUsage: ...\n").as_deref(),
+            Some("List NFTs on a marketplace")
+        );
+        assert_eq!(first_line(" \n"), None);
+    }
+}
@@ -1,5 +1,7 @@
 use strum::{EnumDiscriminants, EnumIter, EnumMessage};
 
+mod list;
+#[cfg(feature = "self-update")]
 pub mod self_update;
 
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
@@ -15,7 +17,13 @@ pub struct ExtensionsCommands {
 #[non_exhaustive]
 /// What do you want to do with a near CLI?
 pub enum ExtensionsActions {
+    #[cfg(feature = "self-update")]
     #[strum_discriminants(strum(message = "self-update             - Self update near CLI"))]
     /// Self update near CLI
     SelfUpdate(self::self_update::SelfUpdateCommand),
+    #[strum_discriminants(strum(
+        message = "list                    - List the extensions (near-cli-* executables on PATH)"
+    ))]
+    /// List the extensions (near-cli-* executables on PATH)
+    List(self::list::ListExtensions),
 }
@@ -2,13 +2,19 @@
 use strum::{EnumDiscriminants, EnumIter, EnumMessage};
 
 pub mod account;
+mod audit_log;
+mod block;
 mod config;
 pub mod contract;
+mod dao;
+mod network;
+mod receipt;
+mod social;
 mod staking;
 mod tokens;
 pub mod transaction;
+mod watchlist;
 
-#[cfg(feature = "self-update")]
 pub mod extensions;
 
 #[derive(Debug, EnumDiscriminants, Clone, interactive_clap::InteractiveClap)]
@@ -36,15 +42,43 @@ pub enum TopLevelCommand {
     ))]
     /// Use this for contract actions: call function, deploy, download wasm, inspect storage
     Contract(self::contract::ContractCommands),
+    #[strum_discriminants(strum(
+        message = "dao         - Manage SputnikDAO proposals: view, propose, vote"
+    ))]
+    /// Use this for SputnikDAO actions: view proposals, submit a transfer proposal, vote
+    Dao(self::dao::DaoCommands),
+    #[strum_discriminants(strum(
+        message = "social      - Manage NEAR Social data: get, set, follow, unfollow"
+    ))]
+    /// Use this for NEAR Social actions: view and store data, follow and unfollow accounts
+    Social(self::social::SocialCommands),
     #[strum_discriminants(strum(message = "transaction - Operate transactions"))]
     /// Use this to construct transactions or view a transaction status.
     Transaction(self::transaction::TransactionCommands),
+    #[strum_discriminants(strum(message = "receipt     - View receipts"))]
+    /// Use this to view a receipt and its execution outcome.
+    Receipt(self::receipt::ReceiptCommands),
+    #[strum_discriminants(strum(message = "block       - Export blocks"))]
+    /// Use this to export blocks with their chunks and transactions.
+    Block(self::block::BlockCommands),
+    #[strum_discriminants(strum(message = "network     - View the status of the RPC node"))]
+    /// Use this to troubleshoot the connectivity of the RPC node of a network.
+    Network(self::network::NetworkCommands),
     #[strum_discriminants(strum(
         message = "config      - Manage connections in a configuration file (config.toml)"
     ))]
     /// Use this to manage connections in a configuration file (config.toml).
     Config(self::config::ConfigCommands),
-    #[cfg(feature = "self-update")]
+    #[strum_discriminants(strum(
+        message = "watchlist   - View a dashboard of the accounts from the watchlist"
+    ))]
+    /// Use this to view balances and access keys of the accounts from the watchlist
+    Watchlist(self::watchlist::Watchlist),
+    #[strum_discriminants(strum(
+        message = "audit-log   - View the local audit log of the signed transactions"
+    ))]
+    /// Use this to view and verify the audit log of the transactions signed or sent by near CLI
+    AuditLog(self::audit_log::AuditLogCommands),
     #[strum_discriminants(strum(message = "extension   - Manage near CLI and extensions"))]
     /// Use this to manage near CLI and extensions
     Extensions(self::extensions::ExtensionsCommands),
@@ -0,0 +1,23 @@
+use strum::{EnumDiscriminants, EnumIter, EnumMessage};
+
+mod node_status;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(context = crate::GlobalContext)]
+pub struct NetworkCommands {
+    #[interactive_clap(subcommand)]
+    network_actions: NetworkActions,
+}
+
+#[derive(Debug, EnumDiscriminants, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(context = crate::GlobalContext)]
+#[strum_discriminants(derive(EnumMessage, EnumIter))]
+#[non_exhaustive]
+/// What do you want to know about the network?
+pub enum NetworkActions {
+    #[strum_discriminants(strum(
+        message = "node-status    - View the version, sync status and peers of the RPC node"
+    ))]
+    /// View the version, sync status and peers of the RPC node
+    NodeStatus(self::node_status::NodeStatus),
+}
@@ -0,0 +1,188 @@
+use color_eyre::eyre::WrapErr;
+
+use crate::common::JsonRpcClientExt;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = NodeStatusContext)]
+pub struct NodeStatus {
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Query this node instead of the RPC server of the network connection
+    rpc_url: Option<crate::types::url::Url>,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network::Network,
+}
+
+#[derive(Clone)]
+pub struct NodeStatusContext(crate::network::NetworkContext);
+
+impl NodeStatusContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<NodeStatus as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let on_after_getting_network_callback: crate::network::OnAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let rpc_url: Option<url::Url> = scope.rpc_url.clone().map(Into::into);
+
+                move |network_config| {
+                    let mut network_config = network_config.clone();
+                    if let Some(rpc_url) = &rpc_url {
+                        // The API key of the connection is not sent to another node
+                        network_config.rpc_url = rpc_url.clone();
+                        network_config.rpc_api_key = None;
+                    }
+                    let json_rpc_client = network_config.json_rpc_client();
+                    let status = json_rpc_client
+                        .blocking_call(near_jsonrpc_client::methods::status::RpcStatusRequest)
+                        .wrap_err_with(|| {
+                            format!(
+                                "Failed to fetch the status of the node <{}>",
+                                network_config.rpc_url
+                            )
+                        })?;
+                    let peers = json_rpc_client
+                        .blocking_call(
+                            near_jsonrpc_client::methods::network_info::RpcNetworkInfoRequest,
+                        )
+                        .map(|network_info| network_info.num_active_peers)
+                        .map_err(|err| {
+                            tracing::warn!("Failed to fetch the network info of the node: {err}");
+                        })
+                        .ok();
+
+                    eprintln!("\nThe status of the node <{}>:\n", network_config.rpc_url);
+                    let mut table = prettytable::Table::new();
+                    table.set_format(*prettytable::format::consts::FORMAT_NO_COLSEP);
+                    for (name, value) in node_status_rows(&status, peers) {
+                        table.add_row(prettytable::row![Fg->name, Fy->value]);
+                    }
+                    table.printstd();
+                    Ok(())
+                }
+            });
+
+        Ok(Self(crate::network::NetworkContext {
+            config: previous_context.config,
+            interacting_with_account_ids: vec![],
+            on_after_getting_network_callback,
+        }))
+    }
+}
+
+impl From<NodeStatusContext> for crate::network::NetworkContext {
+    fn from(item: NodeStatusContext) -> Self {
+        item.0
+    }
+}
+
+fn node_status_rows(
+    status: &near_primitives::views::StatusResponse,
+    peers: Option<usize>,
+) -> Vec<(&'static str, String)> {
+    let sync_info = &status.sync_info;
+    vec![
+        ("Chain ID", status.chain_id.clone()),
+        (
+            "Node version",
+            format!(
+                "{} (build {})",
+                status.version.version, status.version.build
+            ),
+        ),
+        (
+            "Protocol version",
+            if status.protocol_version == status.latest_protocol_version {
+                status.protocol_version.to_string()
+            } else {
+                format!(
+                    "{} (the node supports up to {})",
+                    status.protocol_version, status.latest_protocol_version
+                )
+            },
+        ),
+        (
+            "Sync status",
+            if sync_info.syncing {
+                "syncing".to_string()
+            } else {
+                "synced".to_string()
+            },
+        ),
+        (
+            "Latest block height",
+            sync_info.latest_block_height.to_string(),
+        ),
+        ("Latest block hash", sync_info.latest_block_hash.to_string()),
+        ("Latest block time", sync_info.latest_block_time.to_string()),
+        (
+            "Validator account",
+            status
+                .validator_account_id
+                .as_ref()
+                .map_or_else(|| "not a validator".to_string(), ToString::to_string),
+        ),
+        (
+            "Peer connections",
+            peers.map_or_else(|| "unknown".to_string(), |peers| peers.to_string()),
+        ),
+        ("Uptime", format!("{} seconds", status.uptime_sec)),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_of_the_node_status() {
+        let status: near_primitives::views::StatusResponse =
+            serde_json::from_value(serde_json::json!({
+                "version": { "version": "2.5.0", "build": "2.5.0-rc.1", "rustc_version": "1.84.0" },
+                "chain_id": "testnet",
+                "protocol_version": 73,
+                "latest_protocol_version": 74,
+                "rpc_addr": "0.0.0.0:3030",
+                "validators": [],
+                "sync_info": {
+                    "latest_block_hash": "11111111111111111111111111111111",
+                    "latest_block_height": 187_000_000,
+                    "latest_state_root": "11111111111111111111111111111111",
+                    "latest_block_time": "2025-01-01T00:00:00.000000000Z",
+                    "syncing": true,
+                    "earliest_block_hash": null,
+                    "earliest_block_height": null,
+                    "earliest_block_time": null,
+                    "epoch_id": "11111111111111111111111111111111",
+                    "epoch_start_height": null
+                },
+                "validator_account_id": "pool.poolv1.near",
+                "validator_public_key": null,
+                "node_public_key": "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp",
+                "node_key": null,
+                "uptime_sec": 42,
+                "genesis_hash": "11111111111111111111111111111111",
+                "detailed_debug_status": null
+            }))
+            .unwrap();
+
+        let rows = node_status_rows(&status, Some(12));
+        let value = |name| {
+            rows.iter()
+                .find(|(row_name, _)| *row_name == name)
+                .map(|(_, value)| value.as_str())
+                .unwrap()
+        };
+        assert_eq!(value("Node version"), "2.5.0 (build 2.5.0-rc.1)");
+        assert_eq!(value("Protocol version"), "73 (the node supports up to 74)");
+        assert_eq!(value("Sync status"), "syncing");
+        assert_eq!(value("Latest block height"), "187000000");
+        assert_eq!(value("Validator account"), "pool.poolv1.near");
+        assert_eq!(value("Peer connections"), "12");
+
+        let rows = node_status_rows(&status, None);
+        assert!(rows.contains(&("Peer connections", "unknown".to_string())));
+    }
+}
@@ -0,0 +1,21 @@
+use strum::{EnumDiscriminants, EnumIter, EnumMessage};
+
+mod view;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(context = crate::GlobalContext)]
+pub struct ReceiptCommands {
+    #[interactive_clap(subcommand)]
+    receipt_actions: ReceiptActions,
+}
+
+#[derive(Debug, EnumDiscriminants, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(context = crate::GlobalContext)]
+#[strum_discriminants(derive(EnumMessage, EnumIter))]
+#[non_exhaustive]
+/// Сhoose action for receipt:
+pub enum ReceiptActions {
+    #[strum_discriminants(strum(message = "view    - View a receipt and its execution outcome"))]
+    /// View a receipt and its execution outcome
+    View(self::view::ReceiptInfo),
+}
@@ -0,0 +1,298 @@
+use color_eyre::eyre::Context;
+use tracing_indicatif::span_ext::IndicatifSpanExt;
+
+use crate::common::JsonRpcClientExt;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = ReceiptInfoContext)]
+pub struct ReceiptInfo {
+    #[interactive_clap(long)]
+    /// Enter the ID of the receipt you need to view:
+    receipt_id: crate::types::crypto_hash::CryptoHash,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network::Network,
+}
+
+#[derive(Clone)]
+pub struct ReceiptInfoContext(crate::network::NetworkContext);
+
+impl ReceiptInfoContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<ReceiptInfo as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let on_after_getting_network_callback: crate::network::OnAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let receipt_id: near_primitives::hash::CryptoHash = scope.receipt_id.into();
+
+                move |network_config| {
+                    let receipt_view = get_receipt(network_config, receipt_id)?;
+                    let execution_outcome = get_receipt_execution_outcome(
+                        network_config,
+                        receipt_id,
+                        &receipt_view.receiver_id,
+                    );
+                    print_receipt(&receipt_view);
+                    match execution_outcome {
+                        Ok(execution_outcome) => print_execution_outcome(&execution_outcome)?,
+                        Err(err) => {
+                            eprintln!("\nThe receipt has not been executed yet or its execution outcome is not available:\n  {err:#}");
+                        }
+                    }
+                    Ok(())
+                }
+            });
+
+        Ok(Self(crate::network::NetworkContext {
+            config: previous_context.config,
+            interacting_with_account_ids: vec![],
+            on_after_getting_network_callback,
+        }))
+    }
+}
+
+impl From<ReceiptInfoContext> for crate::network::NetworkContext {
+    fn from(item: ReceiptInfoContext) -> Self {
+        item.0
+    }
+}
+
+#[tracing::instrument(name = "Getting information about receipt", skip_all)]
+fn get_receipt(
+    network_config: &crate::config::NetworkConfig,
+    receipt_id: near_primitives::hash::CryptoHash,
+) -> color_eyre::eyre::Result<near_primitives::views::ReceiptView> {
+    tracing::Span::current().pb_set_message(&format!("{receipt_id} ..."));
+    network_config
+        .json_rpc_client()
+        .blocking_call(
+            near_jsonrpc_client::methods::EXPERIMENTAL_receipt::RpcReceiptRequest {
+                receipt_reference: near_jsonrpc_primitives::types::receipts::ReceiptReference {
+                    receipt_id,
+                },
+            },
+        )
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch the receipt <{receipt_id}> on network <{}>",
+                network_config.network_name
+            )
+        })
+}
+
+#[tracing::instrument(name = "Getting the execution outcome of the receipt ...", skip_all)]
+fn get_receipt_execution_outcome(
+    network_config: &crate::config::NetworkConfig,
+    receipt_id: near_primitives::hash::CryptoHash,
+    receiver_id: &near_primitives::types::AccountId,
+) -> color_eyre::eyre::Result<near_primitives::views::ExecutionOutcomeView> {
+    let json_rpc_client = network_config.json_rpc_client();
+    let final_block = json_rpc_client
+        .blocking_call(near_jsonrpc_client::methods::block::RpcBlockRequest {
+            block_reference: near_primitives::types::Finality::Final.into(),
+        })
+        .wrap_err("Failed to fetch the final block")?;
+    Ok(json_rpc_client
+        .blocking_call(
+            near_jsonrpc_client::methods::light_client_proof::RpcLightClientExecutionProofRequest {
+                id: near_primitives::types::TransactionOrReceiptId::Receipt {
+                    receipt_id,
+                    receiver_id: receiver_id.clone(),
+                },
+                light_client_head: final_block.header.hash,
+            },
+        )
+        .wrap_err("Failed to fetch the execution outcome")?
+        .outcome_proof
+        .outcome)
+}
+
+fn print_receipt(receipt_view: &near_primitives::views::ReceiptView) {
+    eprintln!("\nReceipt:        {}", receipt_view.receipt_id);
+    match &receipt_view.receipt {
+        near_primitives::views::ReceiptEnumView::Action {
+            signer_id,
+            signer_public_key,
+            gas_price,
+            output_data_receivers,
+            input_data_ids,
+            actions,
+            is_promise_yield,
+        } => {
+            eprintln!(
+                "Type:           action{}",
+                if *is_promise_yield {
+                    " (promise yield)"
+                } else {
+                    ""
+                }
+            );
+            eprintln!("Predecessor:    {}", receipt_view.predecessor_id);
+            eprintln!("Receiver:       {}", receipt_view.receiver_id);
+            eprintln!("Signer:         {signer_id} ({signer_public_key})");
+            eprintln!(
+                "Gas price:      {} per gas unit",
+                crate::types::near_token::NearToken::from_yoctonear(*gas_price)
+            );
+            for input_data_id in input_data_ids {
+                eprintln!("Input data:     {input_data_id}");
+            }
+            for output_data_receiver in output_data_receivers {
+                eprintln!(
+                    "Output data:    {} -> <{}>",
+                    output_data_receiver.data_id, output_data_receiver.receiver_id
+                );
+            }
+            eprintln!("Actions:");
+            for action in actions {
+                eprintln!("  - {}", action_description(action));
+            }
+        }
+        near_primitives::views::ReceiptEnumView::Data {
+            data_id,
+            data,
+            is_promise_resume,
+        } => {
+            eprintln!(
+                "Type:           data{}",
+                if *is_promise_resume {
+                    " (promise resume)"
+                } else {
+                    ""
+                }
+            );
+            eprintln!("Predecessor:    {}", receipt_view.predecessor_id);
+            eprintln!("Receiver:       {}", receipt_view.receiver_id);
+            eprintln!("Data ID:        {data_id}");
+            match data {
+                Some(data) => eprintln!("Data:           {}", printable_value(data)),
+                None => eprintln!("Data:           none (the promise has failed)"),
+            }
+        }
+    }
+}
+
+fn action_description(action: &near_primitives::views::ActionView) -> String {
+    match action {
+        near_primitives::views::ActionView::CreateAccount => "create account".to_string(),
+        // The RPC returns the hash of the deployed code instead of the code itself
+        near_primitives::views::ActionView::DeployContract { code } => {
+            match near_primitives::hash::CryptoHash::try_from(code.as_slice()) {
+                Ok(code_hash) => format!("deploy contract (code hash: {code_hash})"),
+                Err(_) => "deploy contract".to_string(),
+            }
+        }
+        near_primitives::views::ActionView::FunctionCall {
+            method_name,
+            args,
+            gas,
+            deposit,
+        } => format!(
+            "function call \"{method_name}\" with {} attached gas and {} deposit, args: {}",
+            crate::common::NearGas::from_gas(*gas),
+            crate::types::near_token::NearToken::from_yoctonear(*deposit),
+            printable_value(args)
+        ),
+        near_primitives::views::ActionView::Transfer { deposit } => format!(
+            "transfer {}",
+            crate::types::near_token::NearToken::from_yoctonear(*deposit)
+        ),
+        near_primitives::views::ActionView::Stake { stake, public_key } => format!(
+            "stake {} with the public key {public_key}",
+            crate::types::near_token::NearToken::from_yoctonear(*stake)
+        ),
+        near_primitives::views::ActionView::AddKey {
+            public_key,
+            access_key,
+        } => format!(
+            "add key {public_key} ({})",
+            crate::common::access_key_permission_message(&access_key.permission)
+        ),
+        near_primitives::views::ActionView::DeleteKey { public_key } => {
+            format!("delete key {public_key}")
+        }
+        near_primitives::views::ActionView::DeleteAccount { beneficiary_id } => {
+            format!("delete account (beneficiary: <{beneficiary_id}>)")
+        }
+        near_primitives::views::ActionView::Delegate {
+            delegate_action,
+            signature: _,
+        } => format!(
+            "delegate {} action(s) of <{}> to <{}>",
+            delegate_action.actions.len(),
+            delegate_action.sender_id,
+            delegate_action.receiver_id
+        ),
+    }
+}
+
+fn print_execution_outcome(
+    execution_outcome: &near_primitives::views::ExecutionOutcomeView,
+) -> crate::CliResult {
+    eprintln!("\nExecution outcome:");
+    eprintln!("Executor:       {}", execution_outcome.executor_id);
+    eprintln!(
+        "Gas burned:     {}",
+        crate::common::NearGas::from_gas(execution_outcome.gas_burnt)
+    );
+    eprintln!(
+        "Tokens burned:  {}",
+        crate::types::near_token::NearToken::from_yoctonear(execution_outcome.tokens_burnt)
+    );
+    for receipt_id in &execution_outcome.receipt_ids {
+        eprintln!("New receipt:    {receipt_id}");
+    }
+    if execution_outcome.logs.is_empty() {
+        eprintln!("Logs:           none");
+    } else {
+        eprintln!("Logs:");
+        for log in &execution_outcome.logs {
+            eprintln!("  {log}");
+        }
+    }
+    match &execution_outcome.status {
+        near_primitives::views::ExecutionStatusView::Unknown => {
+            eprintln!("Status:         unknown (the execution is pending)");
+        }
+        near_primitives::views::ExecutionStatusView::Failure(tx_execution_error) => {
+            eprintln!("Status:         failure: {tx_execution_error}");
+        }
+        near_primitives::views::ExecutionStatusView::SuccessReceiptId(receipt_id) => {
+            eprintln!(
+                "Status:         success, the result is produced by the receipt {receipt_id}"
+            );
+        }
+        near_primitives::views::ExecutionStatusView::SuccessValue(bytes_result) => {
+            eprintln!("Status:         success");
+            eprintln!("--- Result -------------------------");
+            if bytes_result.is_empty() {
+                eprintln!("Empty result");
+            } else if let Ok(json_result) =
+                serde_json::from_slice::<serde_json::Value>(bytes_result)
+            {
+                println!("{}", serde_json::to_string_pretty(&json_result)?);
+            } else if let Ok(string_result) = String::from_utf8(bytes_result.clone()) {
+                println!("{string_result}");
+            } else {
+                eprintln!("The returned value is not printable (binary data)");
+            }
+            eprintln!("------------------------------------");
+        }
+    }
+    Ok(())
+}
+
+/// Arguments and data are shown as JSON or text when possible, otherwise only their size is shown.
+fn printable_value(bytes: &[u8]) -> String {
+    if bytes.is_empty() {
+        "empty".to_string()
+    } else if let Ok(json_value) = serde_json::from_slice::<serde_json::Value>(bytes) {
+        json_value.to_string()
+    } else if let Ok(string_value) = std::str::from_utf8(bytes) {
+        format!("{string_value:?}")
+    } else {
+        format!("{} bytes of binary data", bytes.len())
+    }
+}
@@ -0,0 +1,187 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = FollowContext)]
+pub struct Follow {
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is your account ID?
+    account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the account ID you want to follow?
+    followed_account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(named_arg)]
+    /// What is the signer account ID?
+    sign_as: super::sign_as::Signer,
+}
+
+#[derive(Clone)]
+pub struct FollowContext(super::sign_as::SocialDataContext);
+
+impl FollowContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<Follow as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let account_id: near_primitives::types::AccountId = scope.account_id.clone().into();
+        let followed_account_id: near_primitives::types::AccountId =
+            scope.followed_account_id.clone().into();
+
+        Ok(Self(super::sign_as::SocialDataContext {
+            global_context: previous_context,
+            success_message: format!("<{account_id}> now follows <{followed_account_id}>."),
+            data: follow_data(&followed_account_id, FollowAction::Follow)?,
+            account_id,
+            permission_key: "graph".to_string(),
+            requires_storage_deposit: true,
+        }))
+    }
+}
+
+impl From<FollowContext> for super::sign_as::SocialDataContext {
+    fn from(item: FollowContext) -> Self {
+        item.0
+    }
+}
+
+impl Follow {
+    pub fn input_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "What is your account ID?",
+        )
+    }
+
+    pub fn input_followed_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_non_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "What is the account ID you want to follow?",
+        )
+    }
+}
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = UnfollowContext)]
+pub struct Unfollow {
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is your account ID?
+    account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the account ID you want to stop following?
+    followed_account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(named_arg)]
+    /// What is the signer account ID?
+    sign_as: super::sign_as::Signer,
+}
+
+#[derive(Clone)]
+pub struct UnfollowContext(super::sign_as::SocialDataContext);
+
+impl UnfollowContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<Unfollow as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let account_id: near_primitives::types::AccountId = scope.account_id.clone().into();
+        let followed_account_id: near_primitives::types::AccountId =
+            scope.followed_account_id.clone().into();
+
+        Ok(Self(super::sign_as::SocialDataContext {
+            global_context: previous_context,
+            success_message: format!("<{account_id}> no longer follows <{followed_account_id}>."),
+            data: follow_data(&followed_account_id, FollowAction::Unfollow)?,
+            account_id,
+            permission_key: "graph".to_string(),
+            requires_storage_deposit: false,
+        }))
+    }
+}
+
+impl From<UnfollowContext> for super::sign_as::SocialDataContext {
+    fn from(item: UnfollowContext) -> Self {
+        item.0
+    }
+}
+
+impl Unfollow {
+    pub fn input_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "What is your account ID?",
+        )
+    }
+
+    pub fn input_followed_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_non_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "What is the account ID you want to stop following?",
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FollowAction {
+    Follow,
+    Unfollow,
+}
+
+/// The same data as near.social writes: the follow edge in the graph (removed with `null`),
+/// and the index entries that feed the activity and notifications of the followed account.
+fn follow_data(
+    followed_account_id: &near_primitives::types::AccountId,
+    action: FollowAction,
+) -> color_eyre::eyre::Result<serde_json::Value> {
+    let (edge, action_type) = match action {
+        FollowAction::Follow => (serde_json::json!(""), "follow"),
+        FollowAction::Unfollow => (serde_json::Value::Null, "unfollow"),
+    };
+    Ok(serde_json::json!({
+        "graph": {
+            "follow": {
+                followed_account_id.to_string(): edge,
+            }
+        },
+        "index": {
+            "graph": serde_json::to_string(&serde_json::json!({
+                "key": "follow",
+                "value": { "type": action_type, "accountId": followed_account_id },
+            }))?,
+            "notify": serde_json::to_string(&serde_json::json!({
+                "key": followed_account_id,
+                "value": { "type": action_type },
+            }))?,
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn follow_and_unfollow_data() {
+        let bob: near_primitives::types::AccountId = "bob.near".parse().unwrap();
+
+        let follow = follow_data(&bob, FollowAction::Follow).unwrap();
+        assert_eq!(follow["graph"]["follow"]["bob.near"], "");
+        assert!(super::super::sign_as::validate_social_data(&follow).is_ok());
+        let notify: serde_json::Value =
+            serde_json::from_str(follow["index"]["notify"].as_str().unwrap()).unwrap();
+        assert_eq!(notify["key"], "bob.near");
+        assert_eq!(notify["value"]["type"], "follow");
+
+        let unfollow = follow_data(&bob, FollowAction::Unfollow).unwrap();
+        assert!(unfollow["graph"]["follow"]["bob.near"].is_null());
+        assert!(unfollow["index"]["graph"]
+            .as_str()
+            .unwrap()
+            .contains("\"unfollow\""));
+    }
+}
@@ -0,0 +1,74 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = SocialGetContext)]
+pub struct SocialGet {
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the account ID whose data you want to view?
+    account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    /// Enter the key to view (for example: profile/name, profile/** or graph/follow/*):
+    key: String,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// How many keys to read per request (by default the data is read at once and only split when it is too large)
+    max_page_size: Option<u64>,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network_view_at_block::NetworkViewAtBlockArgs,
+}
+
+#[derive(Clone)]
+pub struct SocialGetContext(crate::network_view_at_block::ArgsForViewContext);
+
+impl SocialGetContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<SocialGet as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let on_after_getting_block_reference_callback: crate::network_view_at_block::OnAfterGettingBlockReferenceCallback = std::sync::Arc::new({
+            let account_id: near_primitives::types::AccountId = scope.account_id.clone().into();
+            let key = scope.key.trim_matches('/').to_string();
+            let max_page_size = scope.max_page_size;
+
+            move |network_config, block_reference| {
+                let full_key = format!("{account_id}/{key}");
+                let data = crate::view_pagination::get_social_data(
+                    network_config,
+                    std::slice::from_ref(&full_key),
+                    block_reference,
+                    max_page_size,
+                )?;
+
+                if data.as_object().is_some_and(|data| data.is_empty()) {
+                    eprintln!("\nThere is no data for the key <{full_key}>.");
+                } else {
+                    eprintln!("\nData for the key <{full_key}>:");
+                    println!("{}", serde_json::to_string_pretty(&data)?);
+                }
+                Ok(())
+            }
+        });
+        Ok(Self(crate::network_view_at_block::ArgsForViewContext {
+            config: previous_context.config,
+            interacting_with_account_ids: vec![scope.account_id.clone().into()],
+            on_after_getting_block_reference_callback,
+        }))
+    }
+}
+
+impl From<SocialGetContext> for crate::network_view_at_block::ArgsForViewContext {
+    fn from(item: SocialGetContext) -> Self {
+        item.0
+    }
+}
+
+impl SocialGet {
+    pub fn input_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_non_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "What is the account ID whose data you want to view?",
+        )
+    }
+}
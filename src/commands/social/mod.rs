@@ -0,0 +1,35 @@
+use strum::{EnumDiscriminants, EnumIter, EnumMessage};
+
+mod follow;
+mod get;
+mod set;
+mod sign_as;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(context = crate::GlobalContext)]
+pub struct SocialCommands {
+    #[interactive_clap(subcommand)]
+    social_actions: SocialActions,
+}
+
+#[derive(Debug, EnumDiscriminants, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(context = crate::GlobalContext)]
+#[strum_discriminants(derive(EnumMessage, EnumIter))]
+#[non_exhaustive]
+/// Сhoose action for NEAR Social:
+pub enum SocialActions {
+    #[strum_discriminants(strum(message = "get       - View the data of an account by its key"))]
+    /// View the data of an account by its key
+    Get(self::get::SocialGet),
+    #[strum_discriminants(strum(
+        message = "set       - Store a JSON value under a key of an account"
+    ))]
+    /// Store a JSON value under a key of an account
+    Set(self::set::SocialSet),
+    #[strum_discriminants(strum(message = "follow    - Follow another account"))]
+    /// Follow another account
+    Follow(self::follow::Follow),
+    #[strum_discriminants(strum(message = "unfollow  - Stop following another account"))]
+    /// Stop following another account
+    Unfollow(self::follow::Unfollow),
+}
@@ -0,0 +1,64 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = SocialSetContext)]
+pub struct SocialSet {
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the account ID whose data you want to change?
+    account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    /// Enter the key to store the value under (for example: profile/name):
+    key: String,
+    #[interactive_clap(long)]
+    /// Enter the JSON value to store (for example: "Alice" or {"name": "Alice"}):
+    value: crate::types::json::Json,
+    #[interactive_clap(named_arg)]
+    /// What is the signer account ID?
+    sign_as: super::sign_as::Signer,
+}
+
+#[derive(Clone)]
+pub struct SocialSetContext(super::sign_as::SocialDataContext);
+
+impl SocialSetContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<SocialSet as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let account_id: near_primitives::types::AccountId = scope.account_id.clone().into();
+        let key = scope.key.trim_matches('/').to_string();
+        if key.is_empty() {
+            color_eyre::eyre::bail!("The key must not be empty");
+        }
+        let mut data: serde_json::Value = scope.value.clone().into();
+        super::sign_as::validate_social_data(&data)?;
+        near_socialdb_client::social_db_data_from_key(&key, &mut data);
+
+        Ok(Self(super::sign_as::SocialDataContext {
+            global_context: previous_context,
+            success_message: format!(
+                "The value of <{account_id}/{key}> has been successfully updated."
+            ),
+            account_id,
+            data,
+            permission_key: key,
+            requires_storage_deposit: true,
+        }))
+    }
+}
+
+impl From<SocialSetContext> for super::sign_as::SocialDataContext {
+    fn from(item: SocialSetContext) -> Self {
+        item.0
+    }
+}
+
+impl SocialSet {
+    pub fn input_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "What is the account ID whose data you want to change?",
+        )
+    }
+}
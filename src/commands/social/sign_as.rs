@@ -0,0 +1,236 @@
+use color_eyre::eyre::WrapErr;
+use inquire::CustomType;
+
+use crate::common::{CallResultExt, JsonRpcClientExt};
+
+/// The data to store in the SocialDB contract on behalf of an account.
+#[derive(Clone)]
+pub struct SocialDataContext {
+    pub global_context: crate::GlobalContext,
+    pub account_id: near_primitives::types::AccountId,
+    /// The data of the account, e.g. `{"profile": {"name": "Alice"}}`
+    pub data: serde_json::Value,
+    /// The key the signer must be allowed to write to, relative to the account
+    pub permission_key: String,
+    /// Removing values frees the storage, so it does not require a storage deposit
+    pub requires_storage_deposit: bool,
+    pub success_message: String,
+}
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = SocialDataContext)]
+#[interactive_clap(output_context = SignerContext)]
+pub struct Signer {
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the signer account ID?
+    signer_account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network_for_transaction::NetworkForTransactionArgs,
+}
+
+#[derive(Clone)]
+pub struct SignerContext(crate::commands::ActionContext);
+
+impl SignerContext {
+    pub fn from_previous_context(
+        previous_context: SocialDataContext,
+        scope: &<Signer as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let signer_account_id: near_primitives::types::AccountId =
+            scope.signer_account_id.clone().into();
+
+        let get_prepopulated_transaction_after_getting_network_callback: crate::commands::GetPrepopulatedTransactionAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let signer_account_id = signer_account_id.clone();
+                let previous_context = previous_context.clone();
+
+                move |network_config| {
+                    let contract_account_id = network_config.get_near_social_account_id_from_network()?;
+                    let deposit = if previous_context.requires_storage_deposit {
+                        let remote_data = get_remote_data(
+                            network_config,
+                            &contract_account_id,
+                            &previous_context.account_id,
+                            &previous_context.data,
+                        )?;
+                        tokio::runtime::Runtime::new()?.block_on(near_socialdb_client::required_deposit(
+                            &network_config.json_rpc_client(),
+                            &contract_account_id,
+                            &previous_context.account_id,
+                            &previous_context.data,
+                            remote_data.as_ref(),
+                        ))?
+                    } else {
+                        near_token::NearToken::from_yoctonear(0)
+                    };
+
+                    Ok(crate::commands::PrepopulatedTransaction {
+                        signer_id: signer_account_id.clone(),
+                        receiver_id: contract_account_id,
+                        actions: vec![near_primitives::transaction::Action::FunctionCall(
+                            Box::new(near_primitives::transaction::FunctionCallAction {
+                                method_name: "set".to_string(),
+                                args: serde_json::to_vec(&serde_json::json!({
+                                    "data": {
+                                        previous_context.account_id.to_string(): previous_context.data,
+                                    }
+                                }))?,
+                                gas: crate::common::NearGas::from_tgas(300).as_gas(),
+                                deposit: deposit.as_yoctonear(),
+                            }),
+                        )],
+                    })
+                }
+            });
+
+        let on_before_signing_callback: crate::commands::OnBeforeSigningCallback =
+            std::sync::Arc::new({
+                let signer_account_id = signer_account_id.clone();
+                let account_id = previous_context.account_id.clone();
+                let permission_key = previous_context.permission_key.clone();
+
+                move |prepopulated_unsigned_transaction, network_config| {
+                    let json_rpc_client = network_config.json_rpc_client();
+                    let public_key = prepopulated_unsigned_transaction.public_key().clone();
+                    let receiver_id = prepopulated_unsigned_transaction.receiver_id().clone();
+
+                    if let Some(near_primitives::transaction::Action::FunctionCall(action)) =
+                        prepopulated_unsigned_transaction.actions_mut().get_mut(0)
+                    {
+                        action.deposit = tokio::runtime::Runtime::new()?
+                            .block_on(near_socialdb_client::get_deposit(
+                                &json_rpc_client,
+                                &signer_account_id,
+                                &public_key,
+                                &account_id,
+                                &permission_key,
+                                &receiver_id,
+                                near_token::NearToken::from_yoctonear(action.deposit),
+                            ))?
+                            .as_yoctonear();
+                        Ok(())
+                    } else {
+                        color_eyre::eyre::bail!("Unexpected action to change the SocialDB data");
+                    }
+                }
+            });
+
+        let on_after_sending_transaction_callback: crate::transaction_signature_options::OnAfterSendingTransactionCallback = std::sync::Arc::new({
+            let success_message = previous_context.success_message.clone();
+
+            move |outcome_view, _network_config| {
+                if let near_primitives::views::FinalExecutionStatus::SuccessValue(_) = outcome_view.status {
+                    eprintln!("\n{success_message}");
+                }
+                Ok(())
+            }
+        });
+
+        Ok(Self(crate::commands::ActionContext {
+            global_context: previous_context.global_context,
+            interacting_with_account_ids: vec![signer_account_id, previous_context.account_id],
+            get_prepopulated_transaction_after_getting_network_callback,
+            on_before_signing_callback,
+            on_before_sending_transaction_callback: std::sync::Arc::new(
+                |_signed_transaction, _network_config| Ok(String::new()),
+            ),
+            on_after_sending_transaction_callback,
+        }))
+    }
+}
+
+impl From<SignerContext> for crate::commands::ActionContext {
+    fn from(item: SignerContext) -> Self {
+        item.0
+    }
+}
+
+impl Signer {
+    fn input_signer_account_id(
+        context: &SocialDataContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        Ok(Some(
+            CustomType::new("What is the signer account ID?")
+                .with_default(context.account_id.clone().into())
+                .prompt()?,
+        ))
+    }
+}
+
+/// Full paths of the values in the data, e.g. `profile/name` for `{"profile": {"name": "Alice"}}`.
+fn leaf_keys(data: &serde_json::Value, prefix: &str) -> Vec<String> {
+    match data {
+        serde_json::Value::Object(object) if !object.is_empty() => object
+            .iter()
+            .flat_map(|(key, value)| leaf_keys(value, &format!("{prefix}/{key}")))
+            .collect(),
+        _ => vec![prefix.to_string()],
+    }
+}
+
+/// The currently stored values at the same keys as the data, to only pay for the new storage.
+#[tracing::instrument(name = "Getting the current SocialDB data ...", skip_all)]
+fn get_remote_data(
+    network_config: &crate::config::NetworkConfig,
+    contract_account_id: &near_primitives::types::AccountId,
+    account_id: &near_primitives::types::AccountId,
+    data: &serde_json::Value,
+) -> color_eyre::eyre::Result<Option<serde_json::Value>> {
+    let mut remote_data: serde_json::Value = network_config
+        .json_rpc_client()
+        .blocking_call_view_function(
+            contract_account_id,
+            "get",
+            serde_json::to_vec(&serde_json::json!({
+                "keys": leaf_keys(data, account_id.as_str()),
+            }))?,
+            near_primitives::types::Finality::Final.into(),
+        )
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch query for view method: 'get' (contract <{}> on network <{}>)",
+                contract_account_id, network_config.network_name
+            )
+        })?
+        .parse_result_from_json()?;
+    Ok(remote_data
+        .get_mut(account_id.as_str())
+        .map(serde_json::Value::take))
+}
+
+/// SocialDB stores strings and objects only; the storage estimation relies on it.
+pub fn validate_social_data(data: &serde_json::Value) -> color_eyre::eyre::Result<()> {
+    match data {
+        serde_json::Value::Object(object) => object.values().try_for_each(validate_social_data),
+        serde_json::Value::String(_) => Ok(()),
+        _ => color_eyre::eyre::bail!(
+            "SocialDB values must be strings or objects, use a JSON string for other values (for example: '\"{data}\"')"
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn social_data_leaf_keys_and_validation() {
+        let data = serde_json::json!({
+            "profile": { "name": "Alice", "linktree": { "github": "alice" } },
+            "graph": { "follow": { "bob.near": "" } },
+        });
+        let mut keys = leaf_keys(&data, "alice.near");
+        keys.sort();
+        assert_eq!(
+            keys,
+            vec![
+                "alice.near/graph/follow/bob.near",
+                "alice.near/profile/linktree/github",
+                "alice.near/profile/name",
+            ]
+        );
+        assert!(validate_social_data(&data).is_ok());
+        assert!(validate_social_data(&serde_json::json!({ "profile": { "age": 30 } })).is_err());
+    }
+}
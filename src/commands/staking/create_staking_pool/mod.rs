@@ -0,0 +1,297 @@
+use color_eyre::eyre::{ContextCompat, WrapErr};
+
+use crate::common::{CallResultExt, JsonRpcClientExt, RpcQueryResponseExt};
+
+/// The staking pool factory rejects deposits below 30 NEAR (the storage of the pool contract).
+const MIN_STAKING_POOL_DEPOSIT: near_token::NearToken = near_token::NearToken::from_near(30);
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = CreateStakingPoolContext)]
+pub struct CreateStakingPool {
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the owner account ID of the new staking pool?
+    owner_account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    /// What is the staking pool ID (the pool account will be <pool-id>.<factory-account-id>)?
+    pool_id: String,
+    #[interactive_clap(long)]
+    /// Enter the validator public key used for staking:
+    public_key: crate::types::public_key::PublicKey,
+    #[interactive_clap(long)]
+    /// Enter the reward fee of the pool as a fraction (example: 5/100):
+    reward_fee: RewardFeeFraction,
+    #[interactive_clap(long)]
+    /// Enter the amount to attach to the new staking pool (at least 30 NEAR, example: 30NEAR):
+    amount: crate::types::near_token::NearToken,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network_for_transaction::NetworkForTransactionArgs,
+}
+
+#[derive(Clone)]
+pub struct CreateStakingPoolContext(crate::commands::ActionContext);
+
+impl CreateStakingPoolContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<CreateStakingPool as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        if scope.amount.0 < MIN_STAKING_POOL_DEPOSIT {
+            return Err(color_eyre::eyre::eyre!(
+                "The attached amount ({}) is less than the minimum deposit required by the staking pool factory ({})",
+                scope.amount,
+                crate::types::near_token::NearToken::from(MIN_STAKING_POOL_DEPOSIT)
+            ));
+        }
+
+        let get_prepopulated_transaction_after_getting_network_callback: crate::commands::GetPrepopulatedTransactionAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let owner_account_id: near_primitives::types::AccountId =
+                    scope.owner_account_id.clone().into();
+                let pool_id = scope.pool_id.clone();
+                let public_key: near_crypto::PublicKey = scope.public_key.clone().into();
+                let reward_fee = scope.reward_fee;
+                let amount = scope.amount;
+
+                move |network_config| {
+                    let factory_account_id = get_staking_pools_factory_account_id(network_config)?;
+                    get_staking_pool_account_id(&pool_id, &factory_account_id)?;
+
+                    Ok(crate::commands::PrepopulatedTransaction {
+                        signer_id: owner_account_id.clone(),
+                        receiver_id: factory_account_id,
+                        actions: vec![near_primitives::transaction::Action::FunctionCall(
+                            Box::new(near_primitives::transaction::FunctionCallAction {
+                                method_name: "create_staking_pool".to_string(),
+                                args: serde_json::to_vec(&serde_json::json!({
+                                    "staking_pool_id": pool_id,
+                                    "owner_id": owner_account_id,
+                                    "stake_public_key": public_key,
+                                    "reward_fee_fraction": reward_fee,
+                                }))?,
+                                gas: crate::common::NearGas::from_tgas(300).as_gas(),
+                                deposit: amount.as_yoctonear(),
+                            }),
+                        )],
+                    })
+                }
+            });
+
+        let on_after_sending_transaction_callback: crate::transaction_signature_options::OnAfterSendingTransactionCallback = std::sync::Arc::new({
+            let pool_id = scope.pool_id.clone();
+
+            move |outcome_view, network_config| {
+                if let near_primitives::views::FinalExecutionStatus::SuccessValue(result) = &outcome_view.status {
+                    let staking_pool_account_id = get_staking_pool_account_id(
+                        &pool_id,
+                        &get_staking_pools_factory_account_id(network_config)?,
+                    )?;
+                    if serde_json::from_slice::<bool>(result).unwrap_or(true) {
+                        display_staking_pool_details(network_config, &staking_pool_account_id)?;
+                    } else {
+                        return Err(color_eyre::eyre::eyre!(
+                            "The staking pool factory failed to create <{staking_pool_account_id}> (the attached deposit has been refunded)"
+                        ));
+                    }
+                }
+                Ok(())
+            }
+        });
+
+        Ok(Self(crate::commands::ActionContext {
+            global_context: previous_context,
+            interacting_with_account_ids: vec![scope.owner_account_id.clone().into()],
+            get_prepopulated_transaction_after_getting_network_callback,
+            on_before_signing_callback: std::sync::Arc::new(
+                |_prepolulated_unsinged_transaction, _network_config| Ok(()),
+            ),
+            on_before_sending_transaction_callback: std::sync::Arc::new(
+                |_signed_transaction, _network_config| Ok(String::new()),
+            ),
+            on_after_sending_transaction_callback,
+        }))
+    }
+}
+
+impl From<CreateStakingPoolContext> for crate::commands::ActionContext {
+    fn from(item: CreateStakingPoolContext) -> Self {
+        item.0
+    }
+}
+
+impl CreateStakingPool {
+    pub fn input_owner_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "What is the owner account ID of the new staking pool?",
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct RewardFeeFraction {
+    numerator: u32,
+    denominator: u32,
+}
+
+impl std::fmt::Display for RewardFeeFraction {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}/{}", self.numerator, self.denominator)
+    }
+}
+
+impl std::str::FromStr for RewardFeeFraction {
+    type Err = color_eyre::eyre::ErrReport;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (numerator, denominator) = s
+            .split_once('/')
+            .wrap_err("The reward fee must be a fraction, e.g. 5/100")?;
+        let numerator: u32 = numerator
+            .trim()
+            .parse()
+            .wrap_err("The numerator of the reward fee is not a valid number")?;
+        let denominator: u32 = denominator
+            .trim()
+            .parse()
+            .wrap_err("The denominator of the reward fee is not a valid number")?;
+        if denominator == 0 {
+            color_eyre::eyre::bail!("The denominator of the reward fee must not be zero");
+        }
+        if numerator > denominator {
+            color_eyre::eyre::bail!("The reward fee must not exceed 100% (the numerator must not be greater than the denominator)");
+        }
+        Ok(Self {
+            numerator,
+            denominator,
+        })
+    }
+}
+
+impl interactive_clap::ToCli for RewardFeeFraction {
+    type CliVariant = RewardFeeFraction;
+}
+
+fn get_staking_pools_factory_account_id(
+    network_config: &crate::config::NetworkConfig,
+) -> color_eyre::eyre::Result<near_primitives::types::AccountId> {
+    network_config
+        .staking_pools_factory_account_id
+        .clone()
+        .wrap_err_with(|| {
+            format!(
+                "The staking pools factory account ID is not set for the network <{}>. You can set it with: near config edit-connection {} --key staking_pools_factory_account_id --value <factory-account-id>",
+                network_config.network_name, network_config.network_name
+            )
+        })
+}
+
+fn get_staking_pool_account_id(
+    pool_id: &str,
+    factory_account_id: &near_primitives::types::AccountId,
+) -> color_eyre::eyre::Result<near_primitives::types::AccountId> {
+    if pool_id.contains('.') {
+        color_eyre::eyre::bail!(
+            "The staking pool ID <{pool_id}> must not contain dots, the factory account ID is appended automatically"
+        );
+    }
+    format!("{pool_id}.{factory_account_id}")
+        .parse()
+        .wrap_err_with(|| format!("The staking pool ID <{pool_id}> is not valid"))
+}
+
+#[tracing::instrument(name = "Verifying the new staking pool ...", skip_all)]
+fn display_staking_pool_details(
+    network_config: &crate::config::NetworkConfig,
+    staking_pool_account_id: &near_primitives::types::AccountId,
+) -> crate::CliResult {
+    let json_rpc_client = network_config.json_rpc_client();
+    let account_view = json_rpc_client
+        .blocking_call_view_account(
+            staking_pool_account_id,
+            near_primitives::types::Finality::Final.into(),
+        )
+        .wrap_err_with(|| {
+            format!(
+                "The staking pool <{staking_pool_account_id}> was not found on the network <{}>",
+                network_config.network_name
+            )
+        })?
+        .account_view()?;
+    let owner_id: near_primitives::types::AccountId = json_rpc_client
+        .blocking_call_view_function(
+            staking_pool_account_id,
+            "get_owner_id",
+            vec![],
+            near_primitives::types::Finality::Final.into(),
+        )
+        .wrap_err("Failed to fetch the owner of the staking pool")?
+        .parse_result_from_json()?;
+    let reward_fee_fraction: serde_json::Value = json_rpc_client
+        .blocking_call_view_function(
+            staking_pool_account_id,
+            "get_reward_fee_fraction",
+            vec![],
+            near_primitives::types::Finality::Final.into(),
+        )
+        .wrap_err("Failed to fetch the reward fee of the staking pool")?
+        .parse_result_from_json()?;
+
+    eprintln!("\nThe staking pool <{staking_pool_account_id}> has been successfully created:");
+    eprintln!("  Owner:        {owner_id}");
+    eprintln!(
+        "  Reward fee:   {}/{}",
+        reward_fee_fraction["numerator"], reward_fee_fraction["denominator"]
+    );
+    eprintln!(
+        "  Balance:      {}",
+        near_token::NearToken::from_yoctonear(account_view.amount)
+    );
+    eprintln!(
+        "  Locked:       {}",
+        near_token::NearToken::from_yoctonear(account_view.locked)
+    );
+    eprintln!("  Code hash:    {}", account_view.code_hash);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn reward_fee_fraction_from_str() {
+        assert_eq!(
+            RewardFeeFraction::from_str("5/100").unwrap(),
+            RewardFeeFraction {
+                numerator: 5,
+                denominator: 100
+            }
+        );
+        assert_eq!(
+            RewardFeeFraction::from_str(" 1 / 10 ").unwrap().to_string(),
+            "1/10"
+        );
+        assert!(RewardFeeFraction::from_str("5").is_err());
+        assert!(RewardFeeFraction::from_str("5/0").is_err());
+        assert!(RewardFeeFraction::from_str("101/100").is_err());
+        assert!(RewardFeeFraction::from_str("-1/100").is_err());
+    }
+
+    #[test]
+    fn staking_pool_account_id() {
+        let factory_account_id: near_primitives::types::AccountId = "poolv1.near".parse().unwrap();
+        assert_eq!(
+            get_staking_pool_account_id("mypool", &factory_account_id)
+                .unwrap()
+                .as_str(),
+            "mypool.poolv1.near"
+        );
+        assert!(get_staking_pool_account_id("my.pool", &factory_account_id).is_err());
+        assert!(get_staking_pool_account_id("MyPool", &factory_account_id).is_err());
+    }
+}
@@ -1,6 +1,9 @@
 use strum::{EnumDiscriminants, EnumIter, EnumMessage};
 
+mod create_staking_pool;
 pub mod delegate;
+mod propose;
+mod seat_price;
 mod validator_list;
 
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
@@ -21,7 +24,22 @@ pub enum StakingType {
     ))]
     /// View the list of validators to delegate
     ValidatorList(self::validator_list::ValidatorList),
+    #[strum_discriminants(strum(
+        message = "seat-price       - View the current and the next epoch validator seat price"
+    ))]
+    /// View the current and the next epoch validator seat price
+    SeatPrice(self::seat_price::SeatPrice),
     #[strum_discriminants(strum(message = "delegation       - Delegation management"))]
     /// Delegation management
     Delegation(self::delegate::StakeDelegation),
+    #[strum_discriminants(strum(
+        message = "create-staking-pool - Create a new staking pool with the staking pool factory"
+    ))]
+    /// Create a new staking pool with the staking pool factory
+    CreateStakingPool(self::create_staking_pool::CreateStakingPool),
+    #[strum_discriminants(strum(
+        message = "propose          - Propose the stake or ping a staking pool with the validator key of the node"
+    ))]
+    /// Propose the stake or ping a staking pool with the validator key of the node
+    Propose(self::propose::Propose),
 }
@@ -0,0 +1,376 @@
+use color_eyre::eyre::WrapErr;
+
+use crate::common::JsonRpcClientExt;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = ProposeContext)]
+pub struct Propose {
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the validator account ID (or the staking pool account ID with --ping)?
+    account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    /// What is the location of the validator key file of the node (path/to/validator_key.json)?
+    validator_key_file: crate::types::path_buf::PathBuf,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// The amount to stake (example: 10000 NEAR)
+    amount: Option<crate::types::near_token::NearToken>,
+    #[interactive_clap(long)]
+    /// Submit the periodic `ping` call to the staking pool instead of staking
+    ping: bool,
+    #[interactive_clap(long)]
+    /// Proceed even if the account ID of the validator key file does not match the account
+    force: bool,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: Network,
+}
+
+/// The key file of a node (`validator_key.json`), as written by neard
+#[derive(Debug, Clone, serde::Deserialize)]
+struct ValidatorKeyFile {
+    account_id: near_primitives::types::AccountId,
+    public_key: near_crypto::PublicKey,
+    #[serde(alias = "private_key")]
+    secret_key: near_crypto::SecretKey,
+}
+
+#[derive(Clone)]
+pub struct ProposeContext {
+    global_context: crate::GlobalContext,
+    validator_key_file: crate::types::path_buf::PathBuf,
+    validator_public_key: near_crypto::PublicKey,
+    prepopulated_transaction: crate::commands::PrepopulatedTransaction,
+}
+
+impl ProposeContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<Propose as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let account_id: near_primitives::types::AccountId = scope.account_id.clone().into();
+        let validator_key = read_validator_key_file(scope.validator_key_file.as_ref())?;
+        if validator_key.account_id != account_id {
+            if !scope.force {
+                return Err(color_eyre::eyre::eyre!(
+                    "The validator key file belongs to <{}>, not to <{account_id}> (use --force to proceed anyway)",
+                    validator_key.account_id
+                ));
+            }
+            eprintln!(
+                "\nWarning: the validator key file belongs to <{}>, not to <{account_id}>",
+                validator_key.account_id
+            );
+        }
+
+        let prepopulated_transaction = match (scope.ping, &scope.amount) {
+            (true, Some(_)) => {
+                return Err(color_eyre::eyre::eyre!(
+                    "--ping and --amount cannot be used together"
+                ))
+            }
+            (false, None) => {
+                return Err(color_eyre::eyre::eyre!(
+                    "Either --amount (to propose the stake) or --ping (to ping the staking pool) is required"
+                ))
+            }
+            (true, None) => crate::commands::PrepopulatedTransaction {
+                signer_id: validator_key.account_id.clone(),
+                receiver_id: account_id,
+                actions: vec![near_primitives::transaction::Action::FunctionCall(
+                    Box::new(near_primitives::transaction::FunctionCallAction {
+                        method_name: "ping".to_string(),
+                        args: serde_json::to_vec(&serde_json::json!({}))?,
+                        gas: crate::common::NearGas::from_tgas(100).as_gas(),
+                        deposit: 0,
+                    }),
+                )],
+            },
+            (false, Some(amount)) => crate::commands::PrepopulatedTransaction {
+                signer_id: account_id.clone(),
+                receiver_id: account_id,
+                actions: vec![near_primitives::transaction::Action::Stake(Box::new(
+                    near_primitives::transaction::StakeAction {
+                        stake: amount.as_yoctonear(),
+                        public_key: validator_key.public_key.clone(),
+                    },
+                ))],
+            },
+        };
+
+        Ok(Self {
+            global_context: previous_context,
+            validator_key_file: scope.validator_key_file.clone(),
+            validator_public_key: validator_key.public_key,
+            prepopulated_transaction,
+        })
+    }
+}
+
+impl From<ProposeContext> for crate::commands::ActionContext {
+    fn from(item: ProposeContext) -> Self {
+        let offline = item.global_context.offline;
+        let validator_public_key = item.validator_public_key;
+        let prepopulated_transaction = item.prepopulated_transaction;
+        Self {
+            global_context: item.global_context,
+            interacting_with_account_ids: vec![
+                prepopulated_transaction.signer_id.clone(),
+                prepopulated_transaction.receiver_id.clone(),
+            ],
+            get_prepopulated_transaction_after_getting_network_callback: std::sync::Arc::new(
+                move |network_config| {
+                    if !offline {
+                        check_validator_access_key(
+                            network_config,
+                            &prepopulated_transaction.signer_id,
+                            &validator_public_key,
+                        )?;
+                    }
+                    Ok(prepopulated_transaction.clone())
+                },
+            ),
+            on_before_signing_callback: std::sync::Arc::new(
+                |_prepolulated_unsigned_transaction, _network_config| Ok(()),
+            ),
+            on_before_sending_transaction_callback: std::sync::Arc::new(
+                |_signed_transaction, _network_config| Ok(String::new()),
+            ),
+            on_after_sending_transaction_callback: std::sync::Arc::new(
+                |_outcome_view, _network_config| Ok(()),
+            ),
+        }
+    }
+}
+
+/// The signing fails with a less helpful error if the validator key is not an access key of the
+/// signer, so it is checked first.
+fn check_validator_access_key(
+    network_config: &crate::config::NetworkConfig,
+    signer_id: &near_primitives::types::AccountId,
+    validator_public_key: &near_crypto::PublicKey,
+) -> crate::CliResult {
+    network_config
+        .json_rpc_client()
+        .blocking_call_view_access_key(
+            signer_id,
+            validator_public_key,
+            near_primitives::types::Finality::Final.into(),
+        )
+        .wrap_err_with(|| {
+            format!(
+                "The validator key {validator_public_key} must be an access key of <{signer_id}> on network <{}> (add it with `near account add-key`)",
+                network_config.network_name
+            )
+        })?;
+    Ok(())
+}
+
+impl Propose {
+    pub fn input_account_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_non_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "What is the validator account ID (or the staking pool account ID with --ping)?",
+        )
+    }
+}
+
+fn read_validator_key_file(path: &std::path::Path) -> color_eyre::eyre::Result<ValidatorKeyFile> {
+    let data = std::fs::read_to_string(path)
+        .wrap_err_with(|| format!("Failed to read the validator key file: {}", path.display()))?;
+    let validator_key: ValidatorKeyFile = serde_json::from_str(&data).wrap_err_with(|| {
+        format!(
+            "The file {} is not a validator key file (expected account_id, public_key and secret_key)",
+            path.display()
+        )
+    })?;
+    if validator_key.secret_key.public_key() != validator_key.public_key {
+        color_eyre::eyre::bail!(
+            "The secret key in {} does not match its public key {}",
+            path.display(),
+            validator_key.public_key
+        );
+    }
+    Ok(validator_key)
+}
+
+/// The network, the transaction is signed with the validator key file
+/// (`sign-with-access-key-file`) through the checks of the other transactions.
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = ProposeContext)]
+#[interactive_clap(output_context = crate::commands::ActionContext)]
+#[interactive_clap(skip_default_from_cli)]
+pub struct Network {
+    /// What is the name of the network?
+    #[interactive_clap(skip_default_input_arg)]
+    network_name: String,
+    #[interactive_clap(subcommand)]
+    submit: crate::transaction_signature_options::Submit,
+}
+
+impl interactive_clap::FromCli for Network {
+    type FromCliContext = ProposeContext;
+    type FromCliError = color_eyre::eyre::Error;
+
+    fn from_cli(
+        optional_clap_variant: Option<<Network as interactive_clap::ToCli>::CliVariant>,
+        context: Self::FromCliContext,
+    ) -> interactive_clap::ResultFromCli<
+        <Self as interactive_clap::ToCli>::CliVariant,
+        Self::FromCliError,
+    >
+    where
+        Self: Sized + interactive_clap::ToCli,
+    {
+        let mut clap_variant = optional_clap_variant.unwrap_or_default();
+
+        if clap_variant.network_name.is_none() {
+            clap_variant.network_name = match Self::input_network_name(&context) {
+                Ok(Some(network_name)) => Some(network_name),
+                Ok(None) => return interactive_clap::ResultFromCli::Cancel(Some(clap_variant)),
+                Err(err) => return interactive_clap::ResultFromCli::Err(Some(clap_variant), err),
+            };
+        }
+        let network_args = match network_args(
+            clap_variant.network_name.as_deref().unwrap_or_default(),
+            &context.validator_key_file,
+            clap_variant.submit.as_ref(),
+        ) {
+            Ok(network_args) => network_args,
+            Err(err) => return interactive_clap::ResultFromCli::Err(Some(clap_variant), err),
+        };
+
+        let submit =
+            |network_args: crate::network_for_transaction::CliNetworkForTransactionArgs| {
+                match network_args.transaction_signature_options {
+                    Some(
+                        crate::transaction_signature_options::CliSignWith::SignWithAccessKeyFile(
+                            sign_access_key_file,
+                        ),
+                    ) => sign_access_key_file.submit,
+                    _ => None,
+                }
+            };
+        match <crate::network_for_transaction::NetworkForTransactionArgs as interactive_clap::FromCli>::from_cli(
+            Some(network_args),
+            context.into(),
+        ) {
+            interactive_clap::ResultFromCli::Ok(network_args) => {
+                clap_variant.submit = submit(network_args);
+                interactive_clap::ResultFromCli::Ok(clap_variant)
+            }
+            interactive_clap::ResultFromCli::Cancel(network_args) => {
+                clap_variant.submit = network_args.and_then(submit);
+                interactive_clap::ResultFromCli::Cancel(Some(clap_variant))
+            }
+            interactive_clap::ResultFromCli::Back => interactive_clap::ResultFromCli::Back,
+            interactive_clap::ResultFromCli::Err(network_args, err) => {
+                clap_variant.submit = network_args.and_then(submit);
+                interactive_clap::ResultFromCli::Err(Some(clap_variant), err)
+            }
+        }
+    }
+}
+
+/// `network-config <network> sign-with-access-key-file <validator key file> [<submit>]`: the
+/// validator key file has the format of an access key file.
+fn network_args(
+    network_name: &str,
+    validator_key_file: &crate::types::path_buf::PathBuf,
+    submit: Option<
+        &<crate::transaction_signature_options::Submit as interactive_clap::ToCli>::CliVariant,
+    >,
+) -> color_eyre::eyre::Result<crate::network_for_transaction::CliNetworkForTransactionArgs> {
+    let args = [
+        "network-config".to_string(),
+        network_name.to_string(),
+        "sign-with-access-key-file".to_string(),
+        validator_key_file.to_string(),
+    ]
+    .into_iter()
+    .chain(
+        submit
+            .map(|submit| {
+                interactive_clap::ToCliArgs::to_cli_args(submit)
+                    .into_iter()
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default(),
+    );
+    <crate::network_for_transaction::CliNetworkForTransactionArgs as clap::Parser>::try_parse_from(
+        args,
+    )
+    .map_err(|err| color_eyre::eyre::eyre!("{err}"))
+}
+
+impl Network {
+    fn input_network_name(context: &ProposeContext) -> color_eyre::eyre::Result<Option<String>> {
+        crate::common::input_network_name(
+            &context.global_context.config,
+            &[
+                context.prepopulated_transaction.signer_id.clone(),
+                context.prepopulated_transaction.receiver_id.clone(),
+            ],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_node_validator_key_file() {
+        let secret_key =
+            near_crypto::SecretKey::from_seed(near_crypto::KeyType::ED25519, "validator");
+        let path = std::env::temp_dir().join(format!(
+            "near-cli-test-{}-validator_key.json",
+            std::process::id()
+        ));
+        std::fs::write(
+            &path,
+            serde_json::json!({
+                "account_id": "pool.poolv1.near",
+                "public_key": secret_key.public_key(),
+                "secret_key": secret_key,
+            })
+            .to_string(),
+        )
+        .unwrap();
+        let validator_key = read_validator_key_file(&path).unwrap();
+        assert_eq!(validator_key.account_id.as_str(), "pool.poolv1.near");
+        assert_eq!(validator_key.public_key, secret_key.public_key());
+        // The transaction is signed with the file by sign-with-access-key-file
+        let account_key_pair: crate::transaction_signature_options::AccountKeyPair =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(account_key_pair.private_key, secret_key);
+        let network_args = network_args(
+            "mainnet",
+            &crate::types::path_buf::PathBuf(path.clone()),
+            None,
+        )
+        .unwrap();
+        assert!(matches!(
+            network_args.transaction_signature_options,
+            Some(crate::transaction_signature_options::CliSignWith::SignWithAccessKeyFile(_))
+        ));
+
+        let other_public_key =
+            near_crypto::SecretKey::from_seed(near_crypto::KeyType::ED25519, "other").public_key();
+        std::fs::write(
+            &path,
+            serde_json::json!({
+                "account_id": "pool.poolv1.near",
+                "public_key": other_public_key,
+                "secret_key": secret_key,
+            })
+            .to_string(),
+        )
+        .unwrap();
+        assert!(read_validator_key_file(&path).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
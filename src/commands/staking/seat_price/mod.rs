@@ -0,0 +1,242 @@
+use color_eyre::eyre::WrapErr;
+
+use crate::common::JsonRpcClientExt;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = SeatPriceContext)]
+pub struct SeatPrice {
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Show how much additional stake the account needs to get a seat
+    account_id: Option<crate::types::account_id::AccountId>,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network::Network,
+}
+
+#[derive(Clone)]
+pub struct SeatPriceContext(crate::network::NetworkContext);
+
+impl SeatPriceContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<SeatPrice as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let account_id: Option<near_primitives::types::AccountId> =
+            scope.account_id.clone().map(Into::into);
+        let on_after_getting_network_callback: crate::network::OnAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let account_id = account_id.clone();
+                move |network_config| display_seat_price(network_config, account_id.as_ref())
+            });
+        Ok(Self(crate::network::NetworkContext {
+            config: previous_context.config,
+            interacting_with_account_ids: account_id.into_iter().collect(),
+            on_after_getting_network_callback,
+        }))
+    }
+}
+
+impl From<SeatPriceContext> for crate::network::NetworkContext {
+    fn from(item: SeatPriceContext) -> Self {
+        item.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SeatPrices {
+    current: Option<near_primitives::types::Balance>,
+    next: Option<near_primitives::types::Balance>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum AccountSeat {
+    /// The account is in the validator set of the next epoch
+    NextValidator {
+        stake: near_primitives::types::Balance,
+    },
+    /// The account needs more stake than the seat price to be selected
+    Missing {
+        stake: near_primitives::types::Balance,
+        additional_stake: near_primitives::types::Balance,
+    },
+}
+
+type Stakes = [(
+    near_primitives::types::AccountId,
+    near_primitives::types::Balance,
+)];
+
+/// The seat price is the smallest stake in a validator set.
+fn seat_prices(current_validators: &Stakes, next_validators: &Stakes) -> SeatPrices {
+    let min_stake = |validators: &Stakes| validators.iter().map(|(_, stake)| *stake).min();
+    SeatPrices {
+        current: min_stake(current_validators),
+        next: min_stake(next_validators),
+    }
+}
+
+/// The stake of the account is taken from its proposal first, then from the current validator set.
+fn account_seat(
+    account_id: &near_primitives::types::AccountId,
+    next_seat_price: near_primitives::types::Balance,
+    current_validators: &Stakes,
+    next_validators: &Stakes,
+    current_proposals: &Stakes,
+) -> AccountSeat {
+    let find_stake = |validators: &Stakes| {
+        validators
+            .iter()
+            .find(|(validator_id, _)| validator_id == account_id)
+            .map(|(_, stake)| *stake)
+    };
+    if let Some(stake) = find_stake(next_validators) {
+        return AccountSeat::NextValidator { stake };
+    }
+    let stake = find_stake(current_proposals)
+        .or_else(|| find_stake(current_validators))
+        .unwrap_or_default();
+    AccountSeat::Missing {
+        stake,
+        additional_stake: next_seat_price.saturating_sub(stake),
+    }
+}
+
+#[tracing::instrument(name = "Getting the epoch validators information ...", skip_all)]
+fn get_epoch_validator_info(
+    network_config: &crate::config::NetworkConfig,
+) -> color_eyre::eyre::Result<near_primitives::views::EpochValidatorInfo> {
+    network_config
+        .json_rpc_client()
+        .blocking_call(
+            &near_jsonrpc_client::methods::validators::RpcValidatorRequest {
+                epoch_reference: near_primitives::types::EpochReference::Latest,
+            },
+        )
+        .wrap_err("Failed to get epoch validators information request.")
+}
+
+fn display_seat_price(
+    network_config: &crate::config::NetworkConfig,
+    account_id: Option<&near_primitives::types::AccountId>,
+) -> crate::CliResult {
+    let epoch_validator_info = get_epoch_validator_info(network_config)?;
+    let current_validators = epoch_validator_info
+        .current_validators
+        .iter()
+        .map(|validator| (validator.account_id.clone(), validator.stake))
+        .collect::<Vec<_>>();
+    let next_validators = epoch_validator_info
+        .next_validators
+        .iter()
+        .map(|validator| (validator.account_id.clone(), validator.stake))
+        .collect::<Vec<_>>();
+    let current_proposals = epoch_validator_info
+        .current_proposals
+        .into_iter()
+        .map(|proposal| proposal.into_validator_stake().account_and_stake())
+        .collect::<Vec<_>>();
+
+    let seat_prices = seat_prices(&current_validators, &next_validators);
+    let display_seat_price = |seat_price: Option<near_primitives::types::Balance>| {
+        seat_price.map_or_else(
+            || "unknown (the validator set is empty)".to_string(),
+            |seat_price| near_token::NearToken::from_yoctonear(seat_price).to_string(),
+        )
+    };
+    eprintln!(
+        "\nEpoch #{} (started at block #{}) on network <{}>:",
+        epoch_validator_info.epoch_height,
+        epoch_validator_info.epoch_start_height,
+        network_config.network_name
+    );
+    eprintln!(
+        "  Current seat price:       {} ({} validators)",
+        display_seat_price(seat_prices.current),
+        current_validators.len()
+    );
+    eprintln!(
+        "  Next epoch seat price:    {} ({} validators)",
+        display_seat_price(seat_prices.next),
+        next_validators.len()
+    );
+
+    if let Some(account_id) = account_id {
+        match account_seat(
+            account_id,
+            seat_prices.next.unwrap_or_default(),
+            &current_validators,
+            &next_validators,
+            &current_proposals,
+        ) {
+            AccountSeat::NextValidator { stake } => eprintln!(
+                "\n<{account_id}> is in the validator set of the next epoch with a stake of {}",
+                near_token::NearToken::from_yoctonear(stake)
+            ),
+            AccountSeat::Missing {
+                stake,
+                additional_stake,
+            } => eprintln!(
+                "\n<{account_id}> has a stake of {} and needs at least {} more to get a seat in the next epoch",
+                near_token::NearToken::from_yoctonear(stake),
+                near_token::NearToken::from_yoctonear(additional_stake)
+            ),
+        }
+    }
+    eprintln!("\nThe seat price of the epoch after the next one depends on the proposals until the end of the current epoch.");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seat_prices_and_additional_stake() {
+        let stakes = |stakes: &[(&str, u128)]| {
+            stakes
+                .iter()
+                .map(|(account_id, stake)| (account_id.parse().unwrap(), *stake))
+                .collect::<Vec<_>>()
+        };
+        let current_validators = stakes(&[("a.near", 300), ("b.near", 100), ("c.near", 200)]);
+        let next_validators = stakes(&[("a.near", 300), ("c.near", 250)]);
+        let current_proposals = stakes(&[("b.near", 120)]);
+
+        let prices = seat_prices(&current_validators, &next_validators);
+        assert_eq!(
+            prices,
+            SeatPrices {
+                current: Some(100),
+                next: Some(250)
+            }
+        );
+        assert_eq!(seat_prices(&[], &[]).next, None);
+
+        let seat = |account_id: &str| {
+            account_seat(
+                &account_id.parse().unwrap(),
+                250,
+                &current_validators,
+                &next_validators,
+                &current_proposals,
+            )
+        };
+        assert_eq!(seat("c.near"), AccountSeat::NextValidator { stake: 250 });
+        assert_eq!(
+            seat("b.near"),
+            AccountSeat::Missing {
+                stake: 120,
+                additional_stake: 130
+            }
+        );
+        assert_eq!(
+            seat("d.near"),
+            AccountSeat::Missing {
+                stake: 0,
+                additional_stake: 250
+            }
+        );
+    }
+}
@@ -62,7 +62,12 @@ fn display_validators_info(network_config: &crate::config::NetworkConfig) -> cra
     }
     table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
     table.printstd();
-    let validators_url: url::Url = network_config.wallet_url.join("staking/validators")?;
+    let validators_url: url::Url = crate::config::config_url::ConfigUrl::new(
+        network_config,
+        "wallet_url",
+        &network_config.wallet_url,
+    )
+    .join("staking/validators")?;
     eprintln!(
         "This is not a complete list of validators. To see the full list of validators visit Explorer:\n{}\n",
         &validators_url.as_str()
@@ -0,0 +1,226 @@
+use color_eyre::eyre::{Context, ContextCompat};
+use inquire::CustomType;
+use serde_json::json;
+
+use crate::common::{CallResultExt, JsonRpcClientExt};
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = super::TokensCommandsContext)]
+#[interactive_clap(output_context = ApproveFtCommandContext)]
+pub struct ApproveFtCommand {
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the ft-contract account ID?
+    ft_contract_account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the account ID allowed to spend the tokens?
+    spender: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_default_input_arg)]
+    /// Enter an FT amount to approve:
+    amount: crate::types::ft_properties::FungibleToken,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network_for_transaction::NetworkForTransactionArgs,
+}
+
+#[derive(Clone)]
+pub struct ApproveFtCommandContext(crate::commands::ActionContext);
+
+impl ApproveFtCommandContext {
+    pub fn from_previous_context(
+        previous_context: super::TokensCommandsContext,
+        scope: &<ApproveFtCommand as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let owner_account_id = previous_context.owner_account_id.clone();
+        let ft_contract_account_id: near_primitives::types::AccountId =
+            scope.ft_contract_account_id.clone().into();
+        let spender_account_id: near_primitives::types::AccountId = scope.spender.clone().into();
+
+        let get_prepopulated_transaction_after_getting_network_callback: crate::commands::GetPrepopulatedTransactionAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let owner_account_id = owner_account_id.clone();
+                let ft_contract_account_id = ft_contract_account_id.clone();
+                let spender_account_id = spender_account_id.clone();
+                let amount = scope.amount.clone();
+
+                move |network_config| {
+                    let ft_metadata = crate::types::ft_properties::params_ft_metadata(
+                        ft_contract_account_id.clone(),
+                        network_config,
+                        near_primitives::types::Finality::Final.into(),
+                    )?;
+                    let amount = amount.normalize(&ft_metadata)?;
+
+                    let balance = get_ft_balance(network_config, &ft_contract_account_id, &owner_account_id)?;
+                    if balance < amount.amount() {
+                        eprintln!(
+                            "\nWarning: <{owner_account_id}> only has {} in <{ft_contract_account_id}>, which is less than the approved amount.",
+                            crate::types::ft_properties::FungibleToken::from_params_ft(balance, ft_metadata.decimals, ft_metadata.symbol.clone())
+                        );
+                    }
+
+                    match get_ft_allowance(network_config, &ft_contract_account_id, &owner_account_id, &spender_account_id) {
+                        Ok(allowance) => {
+                            let allowance = crate::types::ft_properties::FungibleToken::from_params_ft(
+                                allowance,
+                                ft_metadata.decimals,
+                                ft_metadata.symbol.clone(),
+                            );
+                            eprintln!("\nThe current allowance of <{spender_account_id}> is {allowance}, it will be set to {amount}.");
+                        }
+                        Err(err) if is_method_not_found(&err) => {
+                            eprintln!(
+                                "\nWarning: the contract <{ft_contract_account_id}> does not implement the FT approval extension ('ft_allowance' / 'ft_approve'), the transaction is likely to fail."
+                            );
+                        }
+                        Err(err) => return Err(err),
+                    }
+
+                    Ok(crate::commands::PrepopulatedTransaction {
+                        signer_id: owner_account_id.clone(),
+                        receiver_id: ft_contract_account_id.clone(),
+                        actions: vec![near_primitives::transaction::Action::FunctionCall(
+                            Box::new(near_primitives::transaction::FunctionCallAction {
+                                method_name: "ft_approve".to_string(),
+                                args: serde_json::to_vec(&json!({
+                                    "spender_id": spender_account_id.to_string(),
+                                    "amount": amount.amount().to_string(),
+                                }))?,
+                                gas: crate::common::NearGas::from_tgas(100).as_gas(),
+                                // Like the other state-changing FT methods, the approval requires exactly one yoctoNEAR
+                                deposit: 1,
+                            }),
+                        )],
+                    })
+                }
+            });
+
+        let on_after_sending_transaction_callback: crate::transaction_signature_options::OnAfterSendingTransactionCallback = std::sync::Arc::new({
+            let owner_account_id = owner_account_id.clone();
+            let ft_contract_account_id = ft_contract_account_id.clone();
+            let spender_account_id = spender_account_id.clone();
+
+            move |outcome_view, _network_config| {
+                if let near_primitives::views::FinalExecutionStatus::SuccessValue(_) = outcome_view.status {
+                    eprintln!(
+                        "<{owner_account_id}> has successfully approved <{spender_account_id}> to spend its tokens (FT-contract: {ft_contract_account_id}).",
+                    );
+                }
+                Ok(())
+            }
+        });
+
+        Ok(Self(crate::commands::ActionContext {
+            global_context: previous_context.global_context,
+            interacting_with_account_ids: vec![
+                ft_contract_account_id,
+                owner_account_id,
+                spender_account_id,
+            ],
+            get_prepopulated_transaction_after_getting_network_callback,
+            on_before_signing_callback: std::sync::Arc::new(
+                |_prepolulated_unsinged_transaction, _network_config| Ok(()),
+            ),
+            on_before_sending_transaction_callback: std::sync::Arc::new(
+                |_signed_transaction, _network_config| Ok(String::new()),
+            ),
+            on_after_sending_transaction_callback,
+        }))
+    }
+}
+
+impl From<ApproveFtCommandContext> for crate::commands::ActionContext {
+    fn from(item: ApproveFtCommandContext) -> Self {
+        item.0
+    }
+}
+
+impl ApproveFtCommand {
+    pub fn input_ft_contract_account_id(
+        context: &super::TokensCommandsContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_non_signer_account_id_from_used_account_list(
+            &context.global_context.config.credentials_home_dir,
+            "What is the ft-contract account ID?",
+        )
+    }
+
+    pub fn input_spender(
+        context: &super::TokensCommandsContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_non_signer_account_id_from_used_account_list(
+            &context.global_context.config.credentials_home_dir,
+            "What is the account ID allowed to spend the tokens?",
+        )
+    }
+
+    pub fn input_amount(
+        _context: &super::TokensCommandsContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::ft_properties::FungibleToken>> {
+        eprintln!();
+        Ok(Some(
+            CustomType::<crate::types::ft_properties::FungibleToken>::new(
+                "Enter an FT amount to approve (example: 10 USDT or 0.5 USDT):",
+            )
+            .with_formatter(&|ft| ft.to_string())
+            .prompt()?,
+        ))
+    }
+}
+
+#[tracing::instrument(name = "Getting the FT balance of the owner ...", skip_all)]
+pub(super) fn get_ft_balance(
+    network_config: &crate::config::NetworkConfig,
+    ft_contract_account_id: &near_primitives::types::AccountId,
+    owner_account_id: &near_primitives::types::AccountId,
+) -> color_eyre::eyre::Result<u128> {
+    let amount: String = network_config
+        .json_rpc_client()
+        .blocking_call_view_function(
+            ft_contract_account_id,
+            "ft_balance_of",
+            serde_json::to_vec(&json!({ "account_id": owner_account_id }))?,
+            near_primitives::types::Finality::Final.into(),
+        )
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch query for view method: 'ft_balance_of' (contract <{}> on network <{}>)",
+                ft_contract_account_id, network_config.network_name
+            )
+        })?
+        .parse_result_from_json()?;
+    amount
+        .parse()
+        .wrap_err("The FT balance is not a valid amount")
+}
+
+#[tracing::instrument(name = "Getting the current FT allowance ...", skip_all)]
+fn get_ft_allowance(
+    network_config: &crate::config::NetworkConfig,
+    ft_contract_account_id: &near_primitives::types::AccountId,
+    owner_account_id: &near_primitives::types::AccountId,
+    spender_account_id: &near_primitives::types::AccountId,
+) -> color_eyre::eyre::Result<u128> {
+    let allowance: Option<String> = network_config
+        .json_rpc_client()
+        .blocking_call_view_function(
+            ft_contract_account_id,
+            "ft_allowance",
+            serde_json::to_vec(&json!({
+                "owner_id": owner_account_id,
+                "spender_id": spender_account_id,
+            }))?,
+            near_primitives::types::Finality::Final.into(),
+        )?
+        .parse_result_from_json()?;
+    allowance
+        .map_or(Ok(0), |allowance| allowance.parse())
+        .ok()
+        .wrap_err("The FT allowance is not a valid amount")
+}
+
+fn is_method_not_found(err: &color_eyre::eyre::Error) -> bool {
+    err.chain()
+        .any(|cause| cause.to_string().contains("MethodNotFound"))
+}
@@ -0,0 +1,653 @@
+use color_eyre::eyre::WrapErr;
+use near_primitives::transaction::TransactionV0;
+
+use crate::common::{CallResultExt, JsonRpcClientExt, RpcQueryResponseExt};
+
+const DEFAULT_CONCURRENCY: u64 = 3;
+const FT_TRANSFER_GAS: near_gas::NearGas = near_gas::NearGas::from_tgas(30);
+/// The same deposit as `send-ft` for the receivers that are not registered with the contract
+const STORAGE_DEPOSIT: near_token::NearToken = near_token::NearToken::from_millinear(100);
+/// How many times the transfers rejected because of the nonce order are signed again
+const MAX_NONCE_RETRIES: usize = 3;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = super::TokensCommandsContext)]
+#[interactive_clap(output_context = BatchTransferFtContext)]
+pub struct BatchTransferFt {
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the ft-contract account ID?
+    contract: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    /// What is the path of the JSON file with the transfers ([{"receiver_id": ..., "amount": "10 USDT", "memo": ...}])?
+    transfers_file: crate::types::path_buf::PathBuf,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// How many transactions are sent at the same time (default: 3)
+    concurrency: Option<u64>,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network::Network,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TransferEntry {
+    receiver_id: near_primitives::types::AccountId,
+    /// e.g. "10 USDT" or "0.5 USDT", as for `send-ft`
+    amount: String,
+    memo: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct PlannedTransfer {
+    receiver_id: near_primitives::types::AccountId,
+    amount: crate::types::ft_properties::FungibleToken,
+    memo: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct TransferOutcome {
+    signed_transaction: Option<near_primitives::transaction::SignedTransaction>,
+    result: Result<(), String>,
+    /// The transaction was rejected before its execution because a transaction with a greater
+    /// nonce was included first, so it can be signed again
+    invalid_nonce: bool,
+    /// The outcome in the audit log
+    audit_log_outcome: String,
+}
+
+#[derive(Clone)]
+pub struct BatchTransferFtContext(crate::network::NetworkContext);
+
+impl BatchTransferFtContext {
+    pub fn from_previous_context(
+        previous_context: super::TokensCommandsContext,
+        scope: &<BatchTransferFt as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        if previous_context.global_context.offline {
+            color_eyre::eyre::bail!("Sending FT transfers is not possible offline");
+        }
+        let transfers_file_content = std::fs::read_to_string(&scope.transfers_file)
+            .wrap_err_with(|| format!("Failed to read the file: {}", scope.transfers_file))?;
+        let transfer_entries = parse_transfers_file(&transfers_file_content)
+            .wrap_err_with(|| format!("Invalid transfers file: {}", scope.transfers_file))?;
+        let concurrency = match scope.concurrency.unwrap_or(DEFAULT_CONCURRENCY) {
+            0 => {
+                return Err(color_eyre::eyre::eyre!(
+                    "The concurrency must be at least 1"
+                ))
+            }
+            concurrency => usize::try_from(concurrency)?,
+        };
+        let signer_id = previous_context.owner_account_id.clone();
+        let contract_id: near_primitives::types::AccountId = scope.contract.clone().into();
+
+        let on_after_getting_network_callback: crate::network::OnAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let global_context = previous_context.global_context.clone();
+                let signer_id = signer_id.clone();
+                let contract_id = contract_id.clone();
+
+                move |network_config| {
+                    let ft_metadata = crate::types::ft_properties::params_ft_metadata(
+                        contract_id.clone(),
+                        network_config,
+                        near_primitives::types::Finality::Final.into(),
+                    )?;
+                    let transfers = plan_transfers(&transfer_entries, &ft_metadata)?;
+                    let total = transfers
+                        .iter()
+                        .try_fold(0u128, |total, transfer| {
+                            total.checked_add(transfer.amount.amount())
+                        })
+                        .ok_or_else(|| color_eyre::eyre::eyre!("The total amount overflows"))?;
+                    eprintln!(
+                        "Sending {} transfers of {} in total from <{signer_id}> (FT contract <{contract_id}>) on network <{}>, {concurrency} at a time.",
+                        transfers.len(),
+                        crate::types::ft_properties::FungibleToken::from_params_ft(
+                            total,
+                            ft_metadata.decimals,
+                            ft_metadata.symbol.clone()
+                        ),
+                        network_config.network_name
+                    );
+
+                    let key_pair =
+                        get_signer_key_pair(&global_context.config, network_config, &signer_id)?;
+                    let transfers_actions =
+                        prepare_transfers(network_config, &contract_id, &transfers, concurrency)?;
+                    if !check_batch(
+                        &global_context,
+                        network_config,
+                        &signer_id,
+                        &contract_id,
+                        &transfers_actions,
+                    )? {
+                        return Ok(());
+                    }
+                    let outcomes = send_transfers(
+                        &global_context.config,
+                        network_config,
+                        &signer_id,
+                        &key_pair,
+                        &contract_id,
+                        transfers_actions,
+                        concurrency,
+                    )?;
+                    display_results(&transfers, &outcomes);
+
+                    let failed = outcomes
+                        .iter()
+                        .filter(|outcome| outcome.result.is_err())
+                        .count();
+                    if failed > 0 {
+                        color_eyre::eyre::bail!(
+                            "{failed} of {} transfers failed (the failed ones can be sent again with a transfers file of only them)",
+                            outcomes.len()
+                        );
+                    }
+                    eprintln!("\nAll the {} transfers succeeded.", outcomes.len());
+                    Ok(())
+                }
+            });
+
+        Ok(Self(crate::network::NetworkContext {
+            config: previous_context.global_context.config,
+            interacting_with_account_ids: vec![contract_id, signer_id],
+            on_after_getting_network_callback,
+        }))
+    }
+}
+
+impl From<BatchTransferFtContext> for crate::network::NetworkContext {
+    fn from(item: BatchTransferFtContext) -> Self {
+        item.0
+    }
+}
+
+impl BatchTransferFt {
+    pub fn input_contract(
+        context: &super::TokensCommandsContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_non_signer_account_id_from_used_account_list(
+            &context.global_context.config.credentials_home_dir,
+            "What is the ft-contract account ID?",
+        )
+    }
+}
+
+fn parse_transfers_file(content: &str) -> color_eyre::eyre::Result<Vec<TransferEntry>> {
+    let transfer_entries: Vec<TransferEntry> = serde_json::from_str(content)?;
+    if transfer_entries.is_empty() {
+        color_eyre::eyre::bail!("There are no transfers in the file");
+    }
+    Ok(transfer_entries)
+}
+
+/// The transfers with the amounts in the smallest units of the token.
+fn plan_transfers(
+    transfer_entries: &[TransferEntry],
+    ft_metadata: &crate::types::ft_properties::FtMetadata,
+) -> color_eyre::eyre::Result<Vec<PlannedTransfer>> {
+    let mut errors = Vec::new();
+    let mut transfers = Vec::new();
+    for (index, transfer_entry) in transfer_entries.iter().enumerate() {
+        match transfer_entry
+            .amount
+            .parse::<crate::types::ft_properties::FungibleToken>()
+            .map_err(|err| color_eyre::eyre::eyre!(err))
+            .and_then(|amount| amount.normalize(ft_metadata))
+        {
+            Ok(amount) => transfers.push(PlannedTransfer {
+                receiver_id: transfer_entry.receiver_id.clone(),
+                amount,
+                memo: transfer_entry.memo.clone(),
+            }),
+            Err(err) => errors.push(format!(
+                "transfers[{index}].amount: \"{}\": {err} (expected e.g. \"10 {}\")",
+                transfer_entry.amount, ft_metadata.symbol
+            )),
+        }
+    }
+    if !errors.is_empty() {
+        color_eyre::eyre::bail!("The transfers are invalid:\n  {}", errors.join("\n  "));
+    }
+    Ok(transfers)
+}
+
+/// The key of the signer from the keychain, then from the legacy keychain.
+fn get_signer_key_pair(
+    config: &crate::config::Config,
+    network_config: &crate::config::NetworkConfig,
+    signer_id: &near_primitives::types::AccountId,
+) -> color_eyre::eyre::Result<crate::transaction_signature_options::AccountKeyPair> {
+    crate::commands::account::export_account::get_account_key_pair_from_keychain(
+        network_config,
+        signer_id,
+    )
+    .or_else(|_| {
+        crate::commands::account::export_account::get_account_key_pair_from_legacy_keychain(
+            network_config,
+            signer_id,
+            &config.credentials_dir(network_config),
+        )
+    })
+    .wrap_err_with(|| {
+        format!(
+            "There is no full access key of <{signer_id}> for network <{}> in the keychain or in the legacy keychain",
+            network_config.network_name
+        )
+    })
+}
+
+/// The actions of each transfer, or why they could not be prepared, with the registration of the
+/// receivers checked `concurrency` at a time.
+#[tracing::instrument(name = "Checking the registration of the receivers ...", skip_all)]
+fn prepare_transfers(
+    network_config: &crate::config::NetworkConfig,
+    contract_id: &near_primitives::types::AccountId,
+    transfers: &[PlannedTransfer],
+    concurrency: usize,
+) -> color_eyre::eyre::Result<Vec<Result<Vec<near_primitives::transaction::Action>, String>>> {
+    let json_rpc_client = network_config.json_rpc_client();
+    crate::common::run_concurrently(transfers, concurrency, |transfer| {
+        let json_rpc_client = &json_rpc_client;
+        async move {
+            let is_registered =
+                is_registered(json_rpc_client, contract_id, &transfer.receiver_id).await?;
+            let actions = transfer_actions(transfer, is_registered)?;
+            crate::tx_builder::validate_actions(&actions)?;
+            Ok(actions)
+        }
+    })
+    .map(|results| {
+        results
+            .into_iter()
+            .map(|result: color_eyre::eyre::Result<_>| result.map_err(|err| format!("{err:#}")))
+            .collect()
+    })
+}
+
+/// The checks of the transaction pipeline on the whole batch before anything is signed: the
+/// protocol version, the fee estimate (`--fee-estimate`), the `require_ledger_above` policy and
+/// `--yes-below`, with the outgoing value of all the transfers together. Returns `false` if
+/// nothing is to be sent (`--fee-estimate-only`).
+fn check_batch(
+    global_context: &crate::GlobalContext,
+    network_config: &crate::config::NetworkConfig,
+    signer_id: &near_primitives::types::AccountId,
+    contract_id: &near_primitives::types::AccountId,
+    transfers_actions: &[Result<Vec<near_primitives::transaction::Action>, String>],
+) -> color_eyre::eyre::Result<bool> {
+    let prepopulated_transactions = transfers_actions
+        .iter()
+        .flatten()
+        .map(|actions| crate::commands::PrepopulatedTransaction {
+            signer_id: signer_id.clone(),
+            receiver_id: contract_id.clone(),
+            actions: actions.clone(),
+        })
+        .collect::<Vec<_>>();
+    if prepopulated_transactions.is_empty() {
+        return Ok(true);
+    }
+
+    crate::network_for_transaction::protocol_version::check_protocol_version(
+        network_config,
+        global_context.strict_protocol_check,
+    )?;
+    if global_context.fee_estimate {
+        let fee_estimate = crate::network_for_transaction::fee_estimate::estimate_batch_fee(
+            network_config,
+            &prepopulated_transactions,
+        )?;
+        eprintln!(
+            "\nThe fee of the {} transactions together:\n{fee_estimate}\n",
+            prepopulated_transactions.len()
+        );
+        if global_context.fee_estimate_only {
+            println!("{}", fee_estimate.max_fee().as_yoctonear());
+            return Ok(false);
+        }
+    }
+
+    let batch = crate::commands::PrepopulatedTransaction {
+        signer_id: signer_id.clone(),
+        receiver_id: contract_id.clone(),
+        actions: prepopulated_transactions
+            .into_iter()
+            .flat_map(|prepopulated_transaction| prepopulated_transaction.actions)
+            .collect(),
+    };
+    // The transfers are signed with the key from the keychain, so a batch that has to be signed
+    // with Ledger is rejected
+    crate::transaction_signature_options::signing_policy::enforce_require_ledger_policy(
+        global_context,
+        network_config,
+        &batch,
+        &mut Some(
+            crate::transaction_signature_options::CliSignWith::SignWithKeychain(Default::default()),
+        ),
+    )?;
+    crate::transaction_signature_options::signing_policy::enforce_yes_below_policy(
+        global_context,
+        network_config,
+        &batch,
+    )?;
+    Ok(true)
+}
+
+/// Signs one transaction per transfer with consecutive nonces and sends them `concurrency` at a
+/// time. The transfers rejected because a transaction with a greater nonce was included first
+/// were not executed, so they are signed again with new nonces.
+#[tracing::instrument(name = "Sending the FT transfers ...", skip_all)]
+fn send_transfers(
+    config: &crate::config::Config,
+    network_config: &crate::config::NetworkConfig,
+    signer_id: &near_primitives::types::AccountId,
+    key_pair: &crate::transaction_signature_options::AccountKeyPair,
+    contract_id: &near_primitives::types::AccountId,
+    transfers_actions: Vec<Result<Vec<near_primitives::transaction::Action>, String>>,
+    concurrency: usize,
+) -> color_eyre::eyre::Result<Vec<TransferOutcome>> {
+    let json_rpc_client = network_config.json_rpc_client();
+    let mut outcomes: Vec<Option<TransferOutcome>> = vec![None; transfers_actions.len()];
+    let mut pending = Vec::new();
+    for (index, actions) in transfers_actions.into_iter().enumerate() {
+        match actions {
+            Ok(actions) => pending.push((index, actions)),
+            Err(err) => {
+                outcomes[index] = Some(TransferOutcome {
+                    signed_transaction: None,
+                    result: Err(err),
+                    invalid_nonce: false,
+                    audit_log_outcome: String::new(),
+                })
+            }
+        }
+    }
+    for retry in 0..=MAX_NONCE_RETRIES {
+        if pending.is_empty() {
+            break;
+        }
+        let rpc_query_response = json_rpc_client
+            .blocking_call_view_access_key(
+                signer_id,
+                &key_pair.public_key,
+                near_primitives::types::Finality::Final.into(),
+            )
+            .wrap_err_with(|| {
+                format!(
+                    "Failed to fetch the access key {} of <{signer_id}>",
+                    key_pair.public_key
+                )
+            })?;
+        let access_key_nonce = rpc_query_response.access_key_view()?.nonce;
+        let block_hash = rpc_query_response.block_hash;
+        let nonces = (0..pending.len() as u64)
+            .map(|position| {
+                crate::tx_builder::next_nonce(access_key_nonce.saturating_add(position))
+            })
+            .collect::<color_eyre::eyre::Result<Vec<_>>>()?;
+
+        let round_outcomes = crate::common::run_concurrently(
+            pending.iter().zip(nonces),
+            concurrency,
+            |((_, actions), nonce)| {
+                let unsigned_transaction =
+                    near_primitives::transaction::Transaction::V0(TransactionV0 {
+                        public_key: key_pair.public_key.clone(),
+                        block_hash,
+                        nonce,
+                        signer_id: signer_id.clone(),
+                        receiver_id: contract_id.clone(),
+                        actions: actions.clone(),
+                    });
+                let signature = key_pair
+                    .private_key
+                    .sign(unsigned_transaction.get_hash_and_size().0.as_ref());
+                send_transfer(
+                    &json_rpc_client,
+                    near_primitives::transaction::SignedTransaction::new(
+                        signature,
+                        unsigned_transaction,
+                    ),
+                )
+            },
+        )?;
+
+        let mut rejected = Vec::new();
+        for ((index, actions), outcome) in pending.into_iter().zip(round_outcomes) {
+            if let Some(signed_transaction) = &outcome.signed_transaction {
+                crate::audit_log::record(
+                    config,
+                    network_config,
+                    signed_transaction.into(),
+                    outcome.audit_log_outcome.clone(),
+                );
+            }
+            if outcome.invalid_nonce && retry < MAX_NONCE_RETRIES {
+                rejected.push((index, actions));
+            } else {
+                outcomes[index] = Some(outcome);
+            }
+        }
+        if !rejected.is_empty() {
+            eprintln!(
+                "{} transfers were rejected because of the order of the nonces, they are signed again.",
+                rejected.len()
+            );
+        }
+        pending = rejected;
+    }
+    Ok(outcomes.into_iter().flatten().collect())
+}
+
+async fn send_transfer(
+    json_rpc_client: &near_jsonrpc_client::JsonRpcClient,
+    signed_transaction: near_primitives::transaction::SignedTransaction,
+) -> TransferOutcome {
+    crate::deadline::record_transaction_hash(signed_transaction.get_hash());
+    let response = crate::rpc_rate_limit::call(
+        json_rpc_client,
+        near_jsonrpc_client::methods::broadcast_tx_commit::RpcBroadcastTxCommitRequest {
+            signed_transaction: signed_transaction.clone(),
+        },
+    )
+    .await;
+    let (result, invalid_nonce, audit_log_outcome) = match response {
+        Ok(outcome_view) => {
+            let audit_log_outcome =
+                crate::audit_log::sent_transaction_outcome(&Ok(outcome_view.clone()));
+            match outcome_view.status {
+                near_primitives::views::FinalExecutionStatus::SuccessValue(_) => {
+                    (Ok(()), false, audit_log_outcome)
+                }
+                near_primitives::views::FinalExecutionStatus::Failure(err) => {
+                    (Err(err.to_string()), false, audit_log_outcome)
+                }
+                near_primitives::views::FinalExecutionStatus::NotStarted
+                | near_primitives::views::FinalExecutionStatus::Started => (
+                    Err("The transaction is not executed yet".to_string()),
+                    false,
+                    audit_log_outcome,
+                ),
+            }
+        }
+        Err(err) => {
+            let invalid_nonce = matches!(
+                &err,
+                near_jsonrpc_client::errors::JsonRpcError::ServerError(
+                    near_jsonrpc_client::errors::JsonRpcServerError::HandlerError(
+                        near_jsonrpc_client::methods::broadcast_tx_commit::RpcTransactionError::InvalidTransaction {
+                            context: near_primitives::errors::InvalidTxError::InvalidNonce { .. },
+                        },
+                    ),
+                )
+            );
+            let rejected = matches!(
+                &err,
+                near_jsonrpc_client::errors::JsonRpcError::ServerError(
+                    near_jsonrpc_client::errors::JsonRpcServerError::HandlerError(
+                        near_jsonrpc_client::methods::broadcast_tx_commit::RpcTransactionError::InvalidTransaction { .. },
+                    ),
+                )
+            );
+            let message = match crate::common::rpc_transaction_error(&err) {
+                Ok(message) if !message.is_empty() => message,
+                Ok(_) => err.to_string(),
+                Err(report) => format!("{report:#}"),
+            };
+            let report = if rejected {
+                color_eyre::Report::new(crate::audit_log::RejectedTransaction(message.clone()))
+            } else {
+                color_eyre::eyre::eyre!("{message}")
+            };
+            let audit_log_outcome = crate::audit_log::sent_transaction_outcome(&Err(report));
+            (Err(message), invalid_nonce, audit_log_outcome)
+        }
+    };
+    TransferOutcome {
+        signed_transaction: Some(signed_transaction),
+        result,
+        invalid_nonce,
+        audit_log_outcome,
+    }
+}
+
+async fn is_registered(
+    json_rpc_client: &near_jsonrpc_client::JsonRpcClient,
+    contract_id: &near_primitives::types::AccountId,
+    receiver_id: &near_primitives::types::AccountId,
+) -> color_eyre::eyre::Result<bool> {
+    let call_result = crate::rpc_rate_limit::call(
+        json_rpc_client,
+        near_jsonrpc_client::methods::query::RpcQueryRequest {
+            block_reference: near_primitives::types::Finality::Final.into(),
+            request: near_primitives::views::QueryRequest::CallFunction {
+                account_id: contract_id.clone(),
+                method_name: "storage_balance_of".to_string(),
+                args: near_primitives::types::FunctionArgs::from(serde_json::to_vec(
+                    &serde_json::json!({ "account_id": receiver_id }),
+                )?),
+            },
+        },
+    )
+    .await
+    .wrap_err("Failed to fetch query for view method: 'storage_balance_of'")?
+    .call_result()?;
+    Ok(!call_result
+        .parse_result_from_json::<serde_json::Value>()?
+        .is_null())
+}
+
+/// `ft_transfer` with 1 yoctoNEAR, after a `storage_deposit` if the receiver is not registered
+/// with the contract (as `send-ft` does).
+fn transfer_actions(
+    transfer: &PlannedTransfer,
+    is_registered: bool,
+) -> color_eyre::eyre::Result<Vec<near_primitives::transaction::Action>> {
+    let mut ft_transfer_args = serde_json::json!({
+        "receiver_id": transfer.receiver_id,
+        "amount": transfer.amount.amount().to_string(),
+    });
+    if let Some(memo) = &transfer.memo {
+        ft_transfer_args["memo"] = serde_json::Value::String(memo.clone());
+    }
+    let mut actions = Vec::new();
+    if !is_registered {
+        actions.push(near_primitives::transaction::Action::FunctionCall(
+            Box::new(near_primitives::transaction::FunctionCallAction {
+                method_name: "storage_deposit".to_string(),
+                args: serde_json::to_vec(
+                    &serde_json::json!({ "account_id": transfer.receiver_id }),
+                )?,
+                gas: FT_TRANSFER_GAS.as_gas(),
+                deposit: STORAGE_DEPOSIT.as_yoctonear(),
+            }),
+        ));
+    }
+    actions.push(near_primitives::transaction::Action::FunctionCall(
+        Box::new(near_primitives::transaction::FunctionCallAction {
+            method_name: "ft_transfer".to_string(),
+            args: serde_json::to_vec(&ft_transfer_args)?,
+            gas: FT_TRANSFER_GAS.as_gas(),
+            deposit: 1,
+        }),
+    ));
+    Ok(actions)
+}
+
+fn display_results(transfers: &[PlannedTransfer], outcomes: &[TransferOutcome]) {
+    let mut table = prettytable::Table::new();
+    table.set_titles(
+        prettytable::row![Fg=>"#", "Receiver", "Amount", "Status", "Transaction hash / error"],
+    );
+    for (index, (transfer, outcome)) in transfers.iter().zip(outcomes).enumerate() {
+        let transaction_hash = outcome
+            .signed_transaction
+            .as_ref()
+            .map(|signed_transaction| signed_transaction.get_hash().to_string());
+        let (status, details) = match &outcome.result {
+            Ok(()) => ("succeeded", transaction_hash.unwrap_or_default()),
+            Err(err) => (
+                "failed",
+                match transaction_hash {
+                    Some(transaction_hash) => format!("{transaction_hash}: {err}"),
+                    None => err.clone(),
+                },
+            ),
+        };
+        table.add_row(prettytable::row![
+            index + 1,
+            transfer.receiver_id,
+            transfer.amount,
+            status,
+            details
+        ]);
+    }
+    table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    table.printstd();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transfers_are_planned_in_the_smallest_units() {
+        let ft_metadata = crate::types::ft_properties::FtMetadata {
+            symbol: "USDT".to_string(),
+            decimals: 6,
+        };
+        let transfer_entries = parse_transfers_file(
+            r#"[
+                {"receiver_id": "alice.near", "amount": "10 USDT", "memo": "airdrop"},
+                {"receiver_id": "bob.near", "amount": "0.5 usdt"}
+            ]"#,
+        )
+        .unwrap();
+        let transfers = plan_transfers(&transfer_entries, &ft_metadata).unwrap();
+        assert_eq!(transfers[0].amount.amount(), 10_000_000);
+        assert_eq!(transfers[1].amount.amount(), 500_000);
+
+        let actions = transfer_actions(&transfers[0], true).unwrap();
+        let [near_primitives::transaction::Action::FunctionCall(ft_transfer)] = actions.as_slice()
+        else {
+            panic!("expected only ft_transfer, got {actions:?}");
+        };
+        assert_eq!(ft_transfer.deposit, 1);
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&ft_transfer.args).unwrap(),
+            serde_json::json!({"receiver_id": "alice.near", "amount": "10000000", "memo": "airdrop"})
+        );
+        assert_eq!(transfer_actions(&transfers[1], false).unwrap().len(), 2);
+
+        let invalid =
+            parse_transfers_file(r#"[{"receiver_id": "bob.near", "amount": "1 NEAR"}]"#).unwrap();
+        assert!(plan_transfers(&invalid, &ft_metadata).is_err());
+        assert!(parse_transfers_file("[]").is_err());
+        assert!(parse_transfers_file(r#"[{"receiver_id": "bob.near"}]"#).is_err());
+    }
+}
@@ -0,0 +1,391 @@
+use std::io::IsTerminal;
+
+use color_eyre::eyre::Context;
+use inquire::CustomType;
+use serde_json::json;
+
+use crate::common::{CallResultExt, JsonRpcClientExt};
+
+/// Tokens sent to the all-zeros implicit account cannot be spent by anyone since there is no
+/// private key for it.
+const DEAD_ACCOUNT_ID: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = super::TokensCommandsContext)]
+#[interactive_clap(output_context = BurnFtCommandContext)]
+pub struct BurnFtCommand {
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the ft-contract account ID?
+    ft_contract_account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_default_input_arg)]
+    /// Enter an FT amount to burn:
+    amount: crate::types::ft_properties::FungibleToken,
+    #[interactive_clap(long)]
+    /// Burn the tokens without asking for a confirmation (burning cannot be undone)
+    yes: bool,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network_for_transaction::NetworkForTransactionArgs,
+}
+
+#[derive(Clone)]
+pub struct BurnFtCommandContext(crate::commands::ActionContext);
+
+impl BurnFtCommandContext {
+    pub fn from_previous_context(
+        previous_context: super::TokensCommandsContext,
+        scope: &<BurnFtCommand as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let owner_account_id = previous_context.owner_account_id.clone();
+        let ft_contract_account_id: near_primitives::types::AccountId =
+            scope.ft_contract_account_id.clone().into();
+
+        let get_prepopulated_transaction_after_getting_network_callback: crate::commands::GetPrepopulatedTransactionAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let owner_account_id = owner_account_id.clone();
+                let ft_contract_account_id = ft_contract_account_id.clone();
+                let amount = scope.amount.clone();
+                let yes = scope.yes;
+
+                move |network_config| {
+                    let ft_metadata = crate::types::ft_properties::params_ft_metadata(
+                        ft_contract_account_id.clone(),
+                        network_config,
+                        near_primitives::types::Finality::Final.into(),
+                    )
+                    .wrap_err_with(|| {
+                        format!("<{ft_contract_account_id}> does not look like a NEP-141 fungible token contract")
+                    })?;
+                    let amount = amount.normalize(&ft_metadata)?;
+
+                    let balance = super::approve_ft::get_ft_balance(
+                        network_config,
+                        &ft_contract_account_id,
+                        &owner_account_id,
+                    )?;
+                    if balance < amount.amount() {
+                        return Err(color_eyre::eyre::eyre!(
+                            "<{owner_account_id}> only has {} in <{ft_contract_account_id}>, which is less than {amount}",
+                            crate::types::ft_properties::FungibleToken::from_params_ft(
+                                balance,
+                                ft_metadata.decimals,
+                                ft_metadata.symbol.clone()
+                            )
+                        ));
+                    }
+
+                    let burn_method = if has_ft_burn_method(network_config, &ft_contract_account_id)? {
+                        eprintln!("\n{amount} will be burned with 'ft_burn' of <{ft_contract_account_id}>.");
+                        BurnMethod::FtBurn
+                    } else {
+                        eprintln!(
+                            "\nThe contract <{ft_contract_account_id}> does not implement 'ft_burn', so {amount} will be sent to the dead account <{DEAD_ACCOUNT_ID}> instead."
+                        );
+                        let dead_account_id: near_primitives::types::AccountId =
+                            DEAD_ACCOUNT_ID.parse()?;
+                        BurnMethod::TransferToDeadAccount {
+                            needs_storage_deposit: !is_registered(
+                                network_config,
+                                &ft_contract_account_id,
+                                &dead_account_id,
+                            )?,
+                        }
+                    };
+
+                    if !yes {
+                        confirm_burn(&amount)?;
+                    }
+
+                    Ok(crate::commands::PrepopulatedTransaction {
+                        signer_id: owner_account_id.clone(),
+                        receiver_id: ft_contract_account_id.clone(),
+                        actions: burn_actions(burn_method, amount.amount())?,
+                    })
+                }
+            });
+
+        let on_after_sending_transaction_callback: crate::transaction_signature_options::OnAfterSendingTransactionCallback = std::sync::Arc::new({
+            let owner_account_id = owner_account_id.clone();
+            let ft_contract_account_id = ft_contract_account_id.clone();
+
+            move |outcome_view, network_config| {
+                if let near_primitives::views::FinalExecutionStatus::SuccessValue(_) = outcome_view.status {
+                    eprintln!(
+                        "<{owner_account_id}> has successfully burned its tokens (FT-contract: {ft_contract_account_id}).",
+                    );
+                    let ft_metadata = crate::types::ft_properties::params_ft_metadata(
+                        ft_contract_account_id.clone(),
+                        network_config,
+                        near_primitives::types::Finality::Final.into(),
+                    )?;
+                    let balance = super::approve_ft::get_ft_balance(
+                        network_config,
+                        &ft_contract_account_id,
+                        &owner_account_id,
+                    )?;
+                    eprintln!(
+                        "The remaining balance of <{owner_account_id}> is {}.",
+                        crate::types::ft_properties::FungibleToken::from_params_ft(
+                            balance,
+                            ft_metadata.decimals,
+                            ft_metadata.symbol
+                        )
+                    );
+                }
+                Ok(())
+            }
+        });
+
+        Ok(Self(crate::commands::ActionContext {
+            global_context: previous_context.global_context,
+            interacting_with_account_ids: vec![ft_contract_account_id, owner_account_id],
+            get_prepopulated_transaction_after_getting_network_callback,
+            on_before_signing_callback: std::sync::Arc::new(
+                |_prepolulated_unsinged_transaction, _network_config| Ok(()),
+            ),
+            on_before_sending_transaction_callback: std::sync::Arc::new(
+                |_signed_transaction, _network_config| Ok(String::new()),
+            ),
+            on_after_sending_transaction_callback,
+        }))
+    }
+}
+
+impl From<BurnFtCommandContext> for crate::commands::ActionContext {
+    fn from(item: BurnFtCommandContext) -> Self {
+        item.0
+    }
+}
+
+impl BurnFtCommand {
+    pub fn input_ft_contract_account_id(
+        context: &super::TokensCommandsContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_non_signer_account_id_from_used_account_list(
+            &context.global_context.config.credentials_home_dir,
+            "What is the ft-contract account ID?",
+        )
+    }
+
+    pub fn input_amount(
+        _context: &super::TokensCommandsContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::ft_properties::FungibleToken>> {
+        eprintln!();
+        Ok(Some(
+            CustomType::<crate::types::ft_properties::FungibleToken>::new(
+                "Enter an FT amount to burn (example: 10 USDT or 0.5 USDT):",
+            )
+            .with_formatter(&|ft| ft.to_string())
+            .prompt()?,
+        ))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BurnMethod {
+    FtBurn,
+    TransferToDeadAccount { needs_storage_deposit: bool },
+}
+
+fn burn_actions(
+    burn_method: BurnMethod,
+    amount: u128,
+) -> color_eyre::eyre::Result<Vec<near_primitives::transaction::Action>> {
+    let function_call = |method_name: &str, args: serde_json::Value, deposit: u128| {
+        color_eyre::eyre::Ok(near_primitives::transaction::Action::FunctionCall(
+            Box::new(near_primitives::transaction::FunctionCallAction {
+                method_name: method_name.to_string(),
+                args: serde_json::to_vec(&args)?,
+                gas: crate::common::NearGas::from_tgas(100).as_gas(),
+                deposit,
+            }),
+        ))
+    };
+    match burn_method {
+        // Like the other state-changing FT methods, the burn requires exactly one yoctoNEAR
+        BurnMethod::FtBurn => Ok(vec![function_call(
+            "ft_burn",
+            json!({ "amount": amount.to_string() }),
+            1,
+        )?]),
+        BurnMethod::TransferToDeadAccount {
+            needs_storage_deposit,
+        } => {
+            let mut actions = Vec::new();
+            if needs_storage_deposit {
+                actions.push(function_call(
+                    "storage_deposit",
+                    json!({ "account_id": DEAD_ACCOUNT_ID, "registration_only": true }),
+                    near_token::NearToken::from_millinear(100).as_yoctonear(),
+                )?);
+            }
+            actions.push(function_call(
+                "ft_transfer",
+                json!({
+                    "receiver_id": DEAD_ACCOUNT_ID,
+                    "amount": amount.to_string(),
+                    "memo": "burn",
+                }),
+                1,
+            )?);
+            Ok(actions)
+        }
+    }
+}
+
+#[tracing::instrument(
+    name = "Checking whether the contract implements 'ft_burn' ...",
+    skip_all
+)]
+fn has_ft_burn_method(
+    network_config: &crate::config::NetworkConfig,
+    ft_contract_account_id: &near_primitives::types::AccountId,
+) -> color_eyre::eyre::Result<bool> {
+    let code = match network_config
+        .json_rpc_client()
+        .blocking_call(near_jsonrpc_client::methods::query::RpcQueryRequest {
+            block_reference: near_primitives::types::Finality::Final.into(),
+            request: near_primitives::views::QueryRequest::ViewCode {
+                account_id: ft_contract_account_id.clone(),
+            },
+        })
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch query ViewCode for <{}> on network <{}>",
+                ft_contract_account_id, network_config.network_name
+            )
+        })?
+        .kind
+    {
+        near_jsonrpc_primitives::types::query::QueryResponseKind::ViewCode(code) => code.code,
+        _ => return Err(color_eyre::eyre::eyre!("Received unexpected query kind")),
+    };
+    exports_function(&code, "ft_burn").wrap_err_with(|| {
+        format!("Could not parse WebAssembly binary of the contract <{ft_contract_account_id}>.")
+    })
+}
+
+pub(super) fn exports_function(code: &[u8], function_name: &str) -> color_eyre::eyre::Result<bool> {
+    for payload in wasmparser::Parser::new(0).parse_all(code) {
+        if let wasmparser::Payload::ExportSection(export_section) = payload? {
+            for export in export_section {
+                let export = export?;
+                if export.kind == wasmparser::ExternalKind::Func && export.name == function_name {
+                    return Ok(true);
+                }
+            }
+        }
+    }
+    Ok(false)
+}
+
+#[tracing::instrument(
+    name = "Checking the storage registration of the dead account ...",
+    skip_all
+)]
+fn is_registered(
+    network_config: &crate::config::NetworkConfig,
+    ft_contract_account_id: &near_primitives::types::AccountId,
+    account_id: &near_primitives::types::AccountId,
+) -> color_eyre::eyre::Result<bool> {
+    let storage_balance: serde_json::Value = network_config
+        .json_rpc_client()
+        .blocking_call_view_function(
+            ft_contract_account_id,
+            "storage_balance_of",
+            serde_json::to_vec(&json!({ "account_id": account_id }))?,
+            near_primitives::types::Finality::Final.into(),
+        )
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch query for view method: 'storage_balance_of' (contract <{}> on network <{}>)",
+                ft_contract_account_id, network_config.network_name
+            )
+        })?
+        .parse_result_from_json()?;
+    Ok(!storage_balance.is_null())
+}
+
+fn confirm_burn(amount: &crate::types::ft_properties::FungibleToken) -> crate::CliResult {
+    if !std::io::stdin().is_terminal() {
+        return Err(color_eyre::eyre::eyre!(
+            "Burning tokens cannot be undone; pass --yes to burn {amount} non-interactively"
+        ));
+    }
+    #[derive(strum_macros::Display, PartialEq)]
+    enum ConfirmOptions {
+        #[strum(to_string = "Yes, burn the tokens.")]
+        Yes,
+        #[strum(to_string = "No, keep the tokens.")]
+        No,
+    }
+    let select_choose_input = crate::common::select(
+        &format!("Burning cannot be undone. Do you want to burn {amount}?"),
+        vec![ConfirmOptions::Yes, ConfirmOptions::No],
+    )?;
+    if select_choose_input != Some(ConfirmOptions::Yes) {
+        return Err(color_eyre::eyre::eyre!("The burn was cancelled"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn method_names(actions: &[near_primitives::transaction::Action]) -> Vec<String> {
+        actions
+            .iter()
+            .map(|action| match action {
+                near_primitives::transaction::Action::FunctionCall(function_call) => {
+                    function_call.method_name.clone()
+                }
+                _ => panic!("Unexpected action"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn burn_actions_by_method() {
+        assert_eq!(
+            method_names(&burn_actions(BurnMethod::FtBurn, 5).unwrap()),
+            vec!["ft_burn"]
+        );
+        assert_eq!(
+            method_names(
+                &burn_actions(
+                    BurnMethod::TransferToDeadAccount {
+                        needs_storage_deposit: true
+                    },
+                    5
+                )
+                .unwrap()
+            ),
+            vec!["storage_deposit", "ft_transfer"]
+        );
+        assert_eq!(
+            method_names(
+                &burn_actions(
+                    BurnMethod::TransferToDeadAccount {
+                        needs_storage_deposit: false
+                    },
+                    5
+                )
+                .unwrap()
+            ),
+            vec!["ft_transfer"]
+        );
+        assert!(DEAD_ACCOUNT_ID
+            .parse::<near_primitives::types::AccountId>()
+            .is_ok());
+    }
+
+    #[test]
+    fn exports_function_of_empty_module() {
+        // The smallest valid WebAssembly module: the magic number and the version
+        let code = b"\0asm\x01\0\0\0";
+        assert!(!exports_function(code, "ft_burn").unwrap());
+        assert!(exports_function(b"not wasm", "ft_burn").is_err());
+    }
+}
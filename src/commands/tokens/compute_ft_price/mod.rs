@@ -0,0 +1,278 @@
+use color_eyre::eyre::Context;
+use serde_json::json;
+
+use crate::common::CallResultExt;
+use crate::common::JsonRpcClientExt;
+
+/// The share of the swapped amount that is used to quote the spot price (1/1000).
+const SPOT_PRICE_AMOUNT_DIVISOR: u128 = 1000;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = super::TokensCommandsContext)]
+#[interactive_clap(output_context = ComputeFtPriceContext)]
+pub struct ComputeFtPrice {
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the ft-contract account ID of the token you want to swap?
+    from_contract: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the ft-contract account ID of the token you want to receive?
+    to_contract: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    /// Enter an amount FT to swap (example: 10 wNEAR or 0.5 USDC):
+    amount: crate::types::ft_properties::FungibleToken,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the DEX contract account ID (example: v2.ref-finance.near)?
+    dex_contract: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// The pool ID to quote with `get_return` (if omitted, `get_estimate_swap_output` is used)
+    pool_id: Option<u64>,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network_view_at_block::NetworkViewAtBlockArgs,
+}
+
+#[derive(Clone)]
+pub struct ComputeFtPriceContext(crate::network_view_at_block::ArgsForViewContext);
+
+impl ComputeFtPriceContext {
+    pub fn from_previous_context(
+        previous_context: super::TokensCommandsContext,
+        scope: &<ComputeFtPrice as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let on_after_getting_block_reference_callback: crate::network_view_at_block::OnAfterGettingBlockReferenceCallback = std::sync::Arc::new({
+            let from_contract: near_primitives::types::AccountId = scope.from_contract.clone().into();
+            let to_contract: near_primitives::types::AccountId = scope.to_contract.clone().into();
+            let dex_contract: near_primitives::types::AccountId = scope.dex_contract.clone().into();
+            let amount = scope.amount.clone();
+            let pool_id = scope.pool_id;
+
+            move |network_config, block_reference| {
+                let from_metadata = crate::types::ft_properties::params_ft_metadata(
+                    from_contract.clone(),
+                    network_config,
+                    block_reference.clone(),
+                )?;
+                let to_metadata = crate::types::ft_properties::params_ft_metadata(
+                    to_contract.clone(),
+                    network_config,
+                    block_reference.clone(),
+                )?;
+                let amount_in = amount.normalize(&from_metadata)?.amount();
+                let swap = SwapQuoteRequest {
+                    dex_contract: &dex_contract,
+                    pool_id,
+                    from_contract: &from_contract,
+                    to_contract: &to_contract,
+                };
+
+                let amount_out = get_swap_output(network_config, &swap, amount_in, block_reference.clone())?;
+                let spot_amount_in = (amount_in / SPOT_PRICE_AMOUNT_DIVISOR).max(1);
+                let spot_amount_out = get_swap_output(network_config, &swap, spot_amount_in, block_reference.clone())?;
+
+                let amount_in = crate::types::ft_properties::FungibleToken::from_params_ft(
+                    amount_in,
+                    from_metadata.decimals,
+                    from_metadata.symbol.clone(),
+                );
+                let amount_out = crate::types::ft_properties::FungibleToken::from_params_ft(
+                    amount_out,
+                    to_metadata.decimals,
+                    to_metadata.symbol.clone(),
+                );
+                let spot_amount_in = crate::types::ft_properties::FungibleToken::from_params_ft(
+                    spot_amount_in,
+                    from_metadata.decimals,
+                    from_metadata.symbol.clone(),
+                );
+                let spot_amount_out = crate::types::ft_properties::FungibleToken::from_params_ft(
+                    spot_amount_out,
+                    to_metadata.decimals,
+                    to_metadata.symbol.clone(),
+                );
+
+                eprintln!("\nSwapping {amount_in} on <{dex_contract}> is expected to return {amount_out}");
+                match exchange_rate(&amount_in, &amount_out) {
+                    Some(rate) => eprintln!(
+                        "Exchange rate: 1 {} = {rate:.6} {}",
+                        from_metadata.symbol, to_metadata.symbol
+                    ),
+                    None => eprintln!("Exchange rate: unavailable"),
+                }
+                match price_impact_percent(&amount_in, &amount_out, &spot_amount_in, &spot_amount_out) {
+                    Some(price_impact) => eprintln!("Price impact: {price_impact:.2}%"),
+                    None => eprintln!("Price impact: unavailable (the pool has no liquidity for this pair)"),
+                }
+                Ok(())
+            }
+        });
+        Ok(Self(crate::network_view_at_block::ArgsForViewContext {
+            config: previous_context.global_context.config,
+            on_after_getting_block_reference_callback,
+            interacting_with_account_ids: vec![
+                scope.dex_contract.clone().into(),
+                scope.from_contract.clone().into(),
+                scope.to_contract.clone().into(),
+            ],
+        }))
+    }
+}
+
+impl From<ComputeFtPriceContext> for crate::network_view_at_block::ArgsForViewContext {
+    fn from(item: ComputeFtPriceContext) -> Self {
+        item.0
+    }
+}
+
+impl ComputeFtPrice {
+    pub fn input_from_contract(
+        context: &super::TokensCommandsContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_non_signer_account_id_from_used_account_list(
+            &context.global_context.config.credentials_home_dir,
+            "What is the ft-contract account ID of the token you want to swap?",
+        )
+    }
+
+    pub fn input_to_contract(
+        context: &super::TokensCommandsContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_non_signer_account_id_from_used_account_list(
+            &context.global_context.config.credentials_home_dir,
+            "What is the ft-contract account ID of the token you want to receive?",
+        )
+    }
+
+    pub fn input_dex_contract(
+        context: &super::TokensCommandsContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_non_signer_account_id_from_used_account_list(
+            &context.global_context.config.credentials_home_dir,
+            "What is the DEX contract account ID (example: v2.ref-finance.near)?",
+        )
+    }
+}
+
+struct SwapQuoteRequest<'a> {
+    dex_contract: &'a near_primitives::types::AccountId,
+    pool_id: Option<u64>,
+    from_contract: &'a near_primitives::types::AccountId,
+    to_contract: &'a near_primitives::types::AccountId,
+}
+
+#[tracing::instrument(name = "Getting the expected swap output ...", skip_all)]
+fn get_swap_output(
+    network_config: &crate::config::NetworkConfig,
+    swap: &SwapQuoteRequest,
+    amount_in: u128,
+    block_reference: near_primitives::types::BlockReference,
+) -> color_eyre::eyre::Result<u128> {
+    let (method_name, args) = match swap.pool_id {
+        Some(pool_id) => (
+            "get_return",
+            json!({
+                "pool_id": pool_id,
+                "token_in": swap.from_contract,
+                "amount_in": amount_in.to_string(),
+                "token_out": swap.to_contract,
+            }),
+        ),
+        None => (
+            "get_estimate_swap_output",
+            json!({
+                "token_in": swap.from_contract,
+                "amount_in": amount_in.to_string(),
+                "token_out": swap.to_contract,
+            }),
+        ),
+    };
+    let amount_out: String = network_config
+        .json_rpc_client()
+        .blocking_call_view_function(
+            swap.dex_contract,
+            method_name,
+            serde_json::to_vec(&args)?,
+            block_reference,
+        )
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch query for view method: '{method_name}' (contract <{}> on network <{}>)",
+                swap.dex_contract, network_config.network_name
+            )
+        })?
+        .parse_result_from_json()?;
+    amount_out
+        .parse::<u128>()
+        .wrap_err_with(|| format!("The DEX contract returned an invalid amount: {amount_out}"))
+}
+
+fn to_f64(token: &crate::types::ft_properties::FungibleToken) -> f64 {
+    token.amount() as f64 / 10f64.powi(token.decimals().into())
+}
+
+/// How many `amount_out` tokens are received for one `amount_in` token.
+fn exchange_rate(
+    amount_in: &crate::types::ft_properties::FungibleToken,
+    amount_out: &crate::types::ft_properties::FungibleToken,
+) -> Option<f64> {
+    if amount_in.amount() == 0 {
+        return None;
+    }
+    Some(to_f64(amount_out) / to_f64(amount_in))
+}
+
+/// Compares the exchange rate of the swap with the spot rate (quoted with a tiny amount).
+fn price_impact_percent(
+    amount_in: &crate::types::ft_properties::FungibleToken,
+    amount_out: &crate::types::ft_properties::FungibleToken,
+    spot_amount_in: &crate::types::ft_properties::FungibleToken,
+    spot_amount_out: &crate::types::ft_properties::FungibleToken,
+) -> Option<f64> {
+    let rate = exchange_rate(amount_in, amount_out)?;
+    let spot_rate = exchange_rate(spot_amount_in, spot_amount_out)?;
+    if spot_rate == 0.0 {
+        return None;
+    }
+    Some(((1.0 - rate / spot_rate) * 100.0).max(0.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ft_properties::FungibleToken;
+
+    #[test]
+    fn exchange_rate_respects_decimals() {
+        let amount_in = FungibleToken::from_params_ft(2 * 10u128.pow(24), 24, "wNEAR".into());
+        let amount_out = FungibleToken::from_params_ft(10_000_000, 6, "USDC".into());
+        assert_eq!(exchange_rate(&amount_in, &amount_out), Some(5.0));
+        assert_eq!(
+            exchange_rate(
+                &FungibleToken::from_params_ft(0, 24, "wNEAR".into()),
+                &amount_out
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn price_impact_against_spot_rate() {
+        let amount_in = FungibleToken::from_params_ft(1000, 0, "A".into());
+        let amount_out = FungibleToken::from_params_ft(900, 0, "B".into());
+        let spot_amount_in = FungibleToken::from_params_ft(1, 0, "A".into());
+        let spot_amount_out = FungibleToken::from_params_ft(1, 0, "B".into());
+        let price_impact =
+            price_impact_percent(&amount_in, &amount_out, &spot_amount_in, &spot_amount_out)
+                .unwrap();
+        assert!((price_impact - 10.0).abs() < 1e-9);
+
+        let no_liquidity = FungibleToken::from_params_ft(0, 0, "B".into());
+        assert_eq!(
+            price_impact_percent(&amount_in, &amount_out, &spot_amount_in, &no_liquidity),
+            None
+        );
+    }
+}
@@ -1,11 +1,18 @@
 use strum::{EnumDiscriminants, EnumIter, EnumMessage};
 
+mod approve_ft;
+mod batch_transfer_ft;
+mod burn_ft;
+mod compute_ft_price;
 mod send_ft;
 mod send_near;
 mod send_nft;
 mod view_ft_balance;
 mod view_near_balance;
+mod view_near_price;
 mod view_nft_assets;
+mod view_nft_metadata;
+mod view_nft_supply;
 
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = crate::GlobalContext)]
@@ -63,6 +70,21 @@ pub enum TokensActions {
     ))]
     /// The transfer is carried out in FT tokens
     SendFt(self::send_ft::SendFtCommand),
+    #[strum_discriminants(strum(
+        message = "batch-transfer-ft - Send FT tokens to many receivers from a JSON file (e.g. for airdrops)"
+    ))]
+    /// Send FT tokens to many receivers from a JSON file (e.g. for airdrops)
+    BatchTransferFt(self::batch_transfer_ft::BatchTransferFt),
+    #[strum_discriminants(strum(
+        message = "approve-ft        - Allow another account to spend your FT tokens"
+    ))]
+    /// Allow another account to spend your FT tokens
+    ApproveFt(self::approve_ft::ApproveFtCommand),
+    #[strum_discriminants(strum(
+        message = "burn-ft           - Burn FT tokens (this cannot be undone)"
+    ))]
+    /// Burn FT tokens (this cannot be undone)
+    BurnFt(self::burn_ft::BurnFtCommand),
     #[strum_discriminants(strum(
         message = "send-nft          - The transfer is carried out in NFT tokens"
     ))]
@@ -71,10 +93,30 @@ pub enum TokensActions {
     #[strum_discriminants(strum(message = "view-near-balance - View the balance of Near tokens"))]
     /// View the balance of Near tokens
     ViewNearBalance(self::view_near_balance::ViewNearBalance),
+    #[strum_discriminants(strum(
+        message = "view-near-price   - View the USD price of NEAR from an oracle or a DEX"
+    ))]
+    /// View the USD price of NEAR from an oracle or a DEX
+    ViewNearPrice(self::view_near_price::ViewNearPrice),
     #[strum_discriminants(strum(message = "view-ft-balance   - View the balance of FT tokens"))]
     /// View the balance of FT tokens
     ViewFtBalance(self::view_ft_balance::ViewFtBalance),
     #[strum_discriminants(strum(message = "view-nft-assets   - View the balance of NFT tokens"))]
     /// View the balance of NFT tokens
     ViewNftAssets(self::view_nft_assets::ViewNftAssets),
+    #[strum_discriminants(strum(
+        message = "view-nft-metadata - View the metadata of an NFT collection and its tokens"
+    ))]
+    /// View the metadata of an NFT collection and its tokens
+    ViewNftMetadata(self::view_nft_metadata::ViewNftMetadata),
+    #[strum_discriminants(strum(
+        message = "view-nft-supply   - View the number of NFT tokens of a collection and of an owner"
+    ))]
+    /// View the number of NFT tokens of a collection and of an owner
+    ViewNftSupply(self::view_nft_supply::ViewNftSupply),
+    #[strum_discriminants(strum(
+        message = "compute-ft-price  - Compute the expected output of an FT swap on a DEX"
+    ))]
+    /// Compute the expected output of an FT swap on a DEX
+    ComputeFtPrice(self::compute_ft_price::ComputeFtPrice),
 }
@@ -1,3 +1,12 @@
+use color_eyre::eyre::WrapErr;
+
+use crate::common::JsonRpcClientExt;
+
+mod send_all;
+
+/// The method that receivers of NEAR transfers with a memo (e.g. exchange deposit accounts) expose
+const TRANSFER_WITH_MEMO_METHOD_NAME: &str = "transfer_with_memo";
+
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = super::TokensCommandsContext)]
 #[interactive_clap(output_context = SendNearCommandContext)]
@@ -5,8 +14,12 @@ pub struct SendNearCommand {
     #[interactive_clap(skip_default_input_arg)]
     /// What is the receiver account ID?
     receiver_account_id: crate::types::account_id::AccountId,
-    /// How many NEAR Tokens do you want to transfer? (example: 10NEAR or 0.5near or 10000yoctonear)
-    amount_in_near: crate::types::near_token::NearToken,
+    /// How many NEAR Tokens do you want to transfer? (example: 10NEAR or 0.5near or 10000yoctonear, or all)
+    amount_in_near: crate::types::transfer_amount::TransferAmount,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// The memo for the receiver, e.g. a deposit memo of an exchange
+    memo: Option<String>,
     #[interactive_clap(named_arg)]
     /// Select network
     network_config: crate::network_for_transaction::NetworkForTransactionArgs,
@@ -17,7 +30,8 @@ pub struct SendNearCommandContext {
     global_context: crate::GlobalContext,
     signer_account_id: near_primitives::types::AccountId,
     receiver_account_id: near_primitives::types::AccountId,
-    amount_in_near: crate::types::near_token::NearToken,
+    amount_in_near: crate::types::transfer_amount::TransferAmount,
+    memo: Option<String>,
 }
 
 impl SendNearCommandContext {
@@ -30,6 +44,7 @@ impl SendNearCommandContext {
             signer_account_id: previous_context.owner_account_id,
             receiver_account_id: scope.receiver_account_id.clone().into(),
             amount_in_near: scope.amount_in_near,
+            memo: scope.memo.clone(),
         })
     }
 }
@@ -41,16 +56,58 @@ impl From<SendNearCommandContext> for crate::commands::ActionContext {
                 let signer_account_id = item.signer_account_id.clone();
                 let receiver_account_id = item.receiver_account_id.clone();
 
-                move |_network_config| {
-                    Ok(crate::commands::PrepopulatedTransaction {
-                        signer_id: signer_account_id.clone(),
-                        receiver_id: receiver_account_id.clone(),
-                        actions: vec![near_primitives::transaction::Action::Transfer(
-                            near_primitives::transaction::TransferAction {
-                                deposit: item.amount_in_near.as_yoctonear(),
-                            },
-                        )],
-                    })
+                let global_context = item.global_context.clone();
+                let memo = item.memo.clone();
+
+                move |network_config| {
+                    if memo.is_some() {
+                        // A Transfer action cannot carry the memo, and it must never be dropped
+                        if global_context.offline {
+                            return Err(color_eyre::eyre::eyre!(
+                                "The NEAR transfer with a memo is not possible in offline mode: it is only sent if the receiver <{receiver_account_id}> exposes '{TRANSFER_WITH_MEMO_METHOD_NAME}', which cannot be checked offline."
+                            ));
+                        }
+                        if !exports_transfer_with_memo(network_config, &receiver_account_id)? {
+                            return Err(color_eyre::eyre::eyre!(
+                                "Native NEAR transfers cannot carry a memo, and the receiver <{receiver_account_id}> does not expose '{TRANSFER_WITH_MEMO_METHOD_NAME}' to accept it.\nNothing was sent. Check the deposit instructions of the receiver (e.g. exchanges often give a dedicated deposit account instead of a memo), or send the tokens without --memo."
+                            ));
+                        }
+                        eprintln!(
+                            "The receiver <{receiver_account_id}> exposes '{TRANSFER_WITH_MEMO_METHOD_NAME}', so the NEAR tokens are attached to a call of it with the memo."
+                        );
+                    }
+                    let transaction_with_amount = |amount: crate::types::near_token::NearToken| {
+                        let action = match &memo {
+                            None => near_primitives::transaction::Action::Transfer(
+                                near_primitives::transaction::TransferAction {
+                                    deposit: amount.as_yoctonear(),
+                                },
+                            ),
+                            Some(memo) => transfer_with_memo_action(amount, memo)?,
+                        };
+                        Ok::<_, color_eyre::eyre::Report>(crate::commands::PrepopulatedTransaction {
+                            signer_id: signer_account_id.clone(),
+                            receiver_id: receiver_account_id.clone(),
+                            actions: vec![action],
+                        })
+                    };
+                    let amount = match item.amount_in_near {
+                        crate::types::transfer_amount::TransferAmount::Exact(amount) => amount,
+                        crate::types::transfer_amount::TransferAmount::All => {
+                            if global_context.offline {
+                                return Err(color_eyre::eyre::eyre!(
+                                    "Sending all the NEAR is not possible in offline mode: the amount depends on the balance, the storage usage and the gas price on the network."
+                                ));
+                            }
+                            send_all::sendable_amount(
+                                network_config,
+                                &transaction_with_amount(
+                                    crate::types::near_token::NearToken::from_yoctonear(0),
+                                )?,
+                            )?
+                        }
+                    };
+                    transaction_with_amount(amount)
                 }
             });
 
@@ -81,3 +138,78 @@ impl SendNearCommand {
         )
     }
 }
+
+fn transfer_with_memo_action(
+    amount: crate::types::near_token::NearToken,
+    memo: &str,
+) -> color_eyre::eyre::Result<near_primitives::transaction::Action> {
+    Ok(near_primitives::transaction::Action::FunctionCall(
+        Box::new(near_primitives::transaction::FunctionCallAction {
+            method_name: TRANSFER_WITH_MEMO_METHOD_NAME.to_string(),
+            args: serde_json::to_vec(&serde_json::json!({ "memo": memo }))?,
+            gas: crate::common::NearGas::from_tgas(30).as_gas(),
+            deposit: amount.as_yoctonear(),
+        }),
+    ))
+}
+
+#[tracing::instrument(
+    name = "Checking whether the receiver accepts transfers with a memo ...",
+    skip_all
+)]
+fn exports_transfer_with_memo(
+    network_config: &crate::config::NetworkConfig,
+    receiver_account_id: &near_primitives::types::AccountId,
+) -> color_eyre::eyre::Result<bool> {
+    let query_response = match network_config.json_rpc_client().blocking_call(
+        near_jsonrpc_client::methods::query::RpcQueryRequest {
+            block_reference: near_primitives::types::Finality::Final.into(),
+            request: near_primitives::views::QueryRequest::ViewCode {
+                account_id: receiver_account_id.clone(),
+            },
+        },
+    ) {
+        Ok(query_response) => query_response,
+        Err(near_jsonrpc_client::errors::JsonRpcError::ServerError(
+            near_jsonrpc_client::errors::JsonRpcServerError::HandlerError(
+                near_jsonrpc_client::methods::query::RpcQueryError::NoContractCode { .. },
+            ),
+        )) => return Ok(false),
+        Err(err) => {
+            return Err(err).wrap_err_with(|| {
+                format!(
+                    "Failed to fetch query ViewCode for <{}> on network <{}>",
+                    receiver_account_id, network_config.network_name
+                )
+            })
+        }
+    };
+    let near_jsonrpc_primitives::types::query::QueryResponseKind::ViewCode(code) =
+        query_response.kind
+    else {
+        return Err(color_eyre::eyre::eyre!("Received unexpected query kind"));
+    };
+    super::burn_ft::exports_function(&code.code, TRANSFER_WITH_MEMO_METHOD_NAME).wrap_err_with(
+        || format!("Could not parse WebAssembly binary of the contract <{receiver_account_id}>."),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memo_is_passed_to_transfer_with_memo() {
+        let action = transfer_with_memo_action(
+            crate::types::near_token::NearToken::from_yoctonear(5),
+            "104937",
+        )
+        .unwrap();
+        let near_primitives::transaction::Action::FunctionCall(function_call) = action else {
+            panic!("Expected a function call, got {action:?}");
+        };
+        assert_eq!(function_call.method_name, "transfer_with_memo");
+        assert_eq!(function_call.args, br#"{"memo":"104937"}"#);
+        assert_eq!(function_call.deposit, 5);
+    }
+}
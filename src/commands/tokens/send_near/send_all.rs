@@ -0,0 +1,266 @@
+use color_eyre::eyre::WrapErr;
+
+use crate::common::{JsonRpcClientExt, RpcQueryResponseExt};
+use crate::types::near_token::NearToken;
+
+/// How much the gas price may grow before the transaction is included (the fee is reserved at
+/// this multiple of the current gas price, the unused part stays on the account)
+const GAS_PRICE_MULTIPLIER: u128 = 2;
+
+/// The fee reserved for a transaction that burns and attaches `gas`: the unused part stays on
+/// the account.
+pub fn fee_reserve(
+    gas: near_primitives::types::Gas,
+    gas_price: near_primitives::types::Balance,
+) -> near_token::NearToken {
+    near_token::NearToken::from_yoctonear(
+        u128::from(gas)
+            .saturating_mul(gas_price)
+            .saturating_mul(GAS_PRICE_MULTIPLIER),
+    )
+}
+
+/// The NEAR to attach, or why nothing can be sent: the transfer allowance of the account, with
+/// the fee reserve as its pessimistic transaction fee.
+pub fn drain_amount(
+    allowance: &crate::common::AccountTransferAllowance,
+) -> color_eyre::eyre::Result<NearToken> {
+    let liquid_storage_stake = allowance.liquid_storage_stake();
+    if liquid_storage_stake >= allowance.account_liquid_balance() {
+        color_eyre::eyre::bail!(
+            "Nothing can be sent: the storage stake of {} bytes ({}{}) takes the whole balance of {}",
+            allowance.storage_usage(),
+            NearToken(allowance.storage_stake()),
+            if allowance.account_locked_balance().is_zero() {
+                String::new()
+            } else {
+                format!(
+                    ", {} of it not covered by the locked balance",
+                    NearToken(liquid_storage_stake)
+                )
+            },
+            NearToken(allowance.account_liquid_balance())
+        );
+    }
+    let transfer_allowance = allowance.transfer_allowance();
+    if transfer_allowance.is_zero() {
+        color_eyre::eyre::bail!(
+            "Nothing can be sent: the {} left after the storage stake of {} bytes do not cover the fee reserve of {} (the transfer fee at {GAS_PRICE_MULTIPLIER} times the gas price)",
+            NearToken(
+                allowance
+                    .account_liquid_balance()
+                    .saturating_sub(liquid_storage_stake)
+            ),
+            allowance.storage_usage(),
+            NearToken(allowance.pessimistic_transaction_fee())
+        );
+    }
+    Ok(NearToken(transfer_allowance))
+}
+
+/// The balance, the storage stake and the fee reserve that the sent amount is computed from
+struct Breakdown<'a>(&'a crate::common::AccountTransferAllowance);
+
+impl std::fmt::Display for Breakdown<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "  {:<44} {}",
+            "balance:",
+            NearToken(self.0.account_liquid_balance())
+        )?;
+        writeln!(
+            f,
+            "  {:<44} {}",
+            format!("- storage stake ({} bytes):", self.0.storage_usage()),
+            NearToken(self.0.liquid_storage_stake())
+        )?;
+        write!(
+            f,
+            "  {:<44} {}",
+            format!("- fee reserve ({GAS_PRICE_MULTIPLIER} x gas price):"),
+            NearToken(self.0.pessimistic_transaction_fee())
+        )
+    }
+}
+
+/// The amount that `send-near all` attaches to the transfer: everything but the storage stake and
+/// the fee reserve of the transaction (built with a zero deposit).
+#[tracing::instrument(name = "Computing the amount to send ...", skip_all)]
+pub fn sendable_amount(
+    network_config: &crate::config::NetworkConfig,
+    prepopulated_transaction: &crate::commands::PrepopulatedTransaction,
+) -> color_eyre::eyre::Result<NearToken> {
+    let json_rpc_client = network_config.json_rpc_client();
+    let signer_id = &prepopulated_transaction.signer_id;
+    let account_view = json_rpc_client
+        .blocking_call_view_account(signer_id, near_primitives::types::Finality::Final.into())
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch query ViewAccount for account <{signer_id}> on network <{}>",
+                network_config.network_name
+            )
+        })?
+        .account_view()?;
+    let runtime_config = json_rpc_client
+        .blocking_call(
+            near_jsonrpc_client::methods::EXPERIMENTAL_protocol_config::RpcProtocolConfigRequest {
+                block_reference: near_primitives::types::Finality::Final.into(),
+            },
+        )
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch the protocol config of network <{}>",
+                network_config.network_name
+            )
+        })?
+        .runtime_config;
+    let gas_price = json_rpc_client
+        .blocking_call(
+            near_jsonrpc_client::methods::gas_price::RpcGasPriceRequest { block_id: None },
+        )
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch the gas price of network <{}>",
+                network_config.network_name
+            )
+        })?
+        .gas_price;
+    let gas = crate::network_for_transaction::fee_estimate::transaction_gas(
+        &runtime_config.transaction_costs,
+        signer_id,
+        &prepopulated_transaction.receiver_id,
+        &prepopulated_transaction.actions,
+    );
+
+    let allowance = crate::common::AccountTransferAllowance::new(
+        signer_id.clone(),
+        &account_view,
+        runtime_config.storage_amount_per_byte,
+        fee_reserve(gas.burnt.saturating_add(gas.attached), gas_price),
+    );
+    let breakdown = Breakdown(&allowance);
+    let sendable_amount = drain_amount(&allowance)
+        .wrap_err_with(|| format!("<{signer_id}> cannot send all its NEAR:\n{breakdown}"))?;
+    eprintln!(
+        "Sending all the NEAR of <{signer_id}>:\n{breakdown}\n  {:<44} {sendable_amount}",
+        "= sent:"
+    );
+    Ok(sendable_amount)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 10^19 yoctoNEAR per byte and 10^8 yoctoNEAR per gas, as on mainnet
+    const PER_BYTE: u128 = 10_000_000_000_000_000_000;
+    const GAS_PRICE: u128 = 100_000_000;
+    // The send and execution fees of a transfer to another account
+    const TRANSFER_GAS: u64 = 223_182_562_500;
+
+    fn allowance(
+        amount: u128,
+        locked: u128,
+        storage_usage: u64,
+        gas: near_primitives::types::Gas,
+        gas_price: near_primitives::types::Balance,
+    ) -> crate::common::AccountTransferAllowance {
+        crate::common::AccountTransferAllowance::new(
+            "alice.near".parse().unwrap(),
+            &near_primitives::views::AccountView {
+                amount,
+                locked,
+                code_hash: near_primitives::hash::CryptoHash::default(),
+                storage_usage,
+                storage_paid_at: 0,
+            },
+            PER_BYTE,
+            fee_reserve(gas, gas_price),
+        )
+    }
+
+    fn drain(
+        amount: u128,
+        locked: u128,
+        storage_usage: u64,
+    ) -> color_eyre::eyre::Result<NearToken> {
+        drain_amount(&allowance(
+            amount,
+            locked,
+            storage_usage,
+            TRANSFER_GAS,
+            GAS_PRICE,
+        ))
+    }
+
+    #[test]
+    fn sends_everything_but_the_storage_stake_and_the_fee() {
+        assert_eq!(
+            drain(10u128.pow(24), 0, 182).unwrap().as_yoctonear(),
+            10u128.pow(24) - 182 * PER_BYTE - 2 * u128::from(TRANSFER_GAS) * GAS_PRICE
+        );
+        // The locked balance of a validator holds the storage stake
+        assert_eq!(
+            drain(10u128.pow(24), 10u128.pow(25), 182)
+                .unwrap()
+                .as_yoctonear(),
+            10u128.pow(24) - 2 * u128::from(TRANSFER_GAS) * GAS_PRICE
+        );
+    }
+
+    #[test]
+    fn explains_why_nothing_can_be_sent() {
+        let err = drain(100_000 * PER_BYTE, 0, 100_000).unwrap_err();
+        assert!(
+            err.to_string().contains("storage stake of 100000 bytes"),
+            "{err}"
+        );
+        let err = drain(182 * PER_BYTE + 1_000, 0, 182).unwrap_err();
+        assert!(
+            err.to_string().contains("do not cover the fee reserve"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn never_leaves_the_account_below_its_storage_stake() {
+        // A deterministic pseudo-random sweep over balances, locked balances and storage usages
+        let mut seed: u64 = 0x853c_49e6_748f_ea9b;
+        let mut next = |modulus: u128| {
+            seed = seed
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            u128::from(seed >> 11) * u128::from(seed >> 13) % modulus
+        };
+        for _ in 0..10_000 {
+            let amount = next(10u128.pow(26));
+            let locked = if next(4) == 0 {
+                next(10u128.pow(26))
+            } else {
+                0
+            };
+            let storage_usage = u64::try_from(next(5_000_000)).unwrap();
+            let gas = u64::try_from(next(300 * 10u128.pow(12))).unwrap();
+            let gas_price = next(10u128.pow(10));
+            let balance = allowance(amount, locked, storage_usage, gas, gas_price);
+            let actual_fee = u128::from(gas) * gas_price;
+            match drain_amount(&balance) {
+                Ok(sent) => {
+                    let sent = sent.as_yoctonear();
+                    assert!(sent > 0, "{balance:?}");
+                    // Even with the gas price doubled before the inclusion
+                    let left = amount - sent - 2 * actual_fee;
+                    assert!(
+                        left + locked >= balance.storage_stake().as_yoctonear(),
+                        "{balance:?}"
+                    );
+                }
+                Err(_) => assert!(
+                    amount <= balance.liquid_storage_stake().as_yoctonear() + 2 * actual_fee,
+                    "{balance:?}"
+                ),
+            }
+        }
+    }
+}
@@ -2,7 +2,7 @@ use color_eyre::eyre::Context;
 use serde_json::json;
 
 use crate::common::CallResultExt;
-use crate::common::JsonRpcClientExt;
+use crate::common::{JsonRpcClientExt, RpcQueryResponseExt};
 
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = super::TokensCommandsContext)]
@@ -11,11 +11,57 @@ pub struct ViewFtBalance {
     #[interactive_clap(skip_default_input_arg)]
     /// What is the ft-contract account ID?
     ft_contract_account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Output format: plaintext (default) or json
+    output: Option<crate::common::OutputFormat>,
     #[interactive_clap(named_arg)]
     /// Select network
     network_config: crate::network_view_at_block::NetworkViewAtBlockArgs,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+struct FtBalance {
+    account_id: near_primitives::types::AccountId,
+    ft_contract_account_id: near_primitives::types::AccountId,
+    block_height: near_primitives::types::BlockHeight,
+    block_hash: near_primitives::hash::CryptoHash,
+    /// The balance in the smallest units of the token
+    amount: String,
+    decimals: u8,
+    symbol: String,
+    /// The balance with the decimals of the token, e.g. "12.5 USDC"
+    formatted: String,
+}
+
+impl FtBalance {
+    fn new(
+        account_id: near_primitives::types::AccountId,
+        ft_contract_account_id: near_primitives::types::AccountId,
+        block_height: near_primitives::types::BlockHeight,
+        block_hash: near_primitives::hash::CryptoHash,
+        amount: u128,
+        ft_metadata: crate::types::ft_properties::FtMetadata,
+    ) -> Self {
+        let formatted = crate::types::ft_properties::FungibleToken::from_params_ft(
+            amount,
+            ft_metadata.decimals,
+            ft_metadata.symbol.clone(),
+        )
+        .to_string();
+        Self {
+            account_id,
+            ft_contract_account_id,
+            block_height,
+            block_hash,
+            amount: amount.to_string(),
+            decimals: ft_metadata.decimals,
+            symbol: ft_metadata.symbol,
+            formatted,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct ViewFtBalanceContext(crate::network_view_at_block::ArgsForViewContext);
 
@@ -28,28 +74,55 @@ impl ViewFtBalanceContext {
             let owner_account_id = previous_context.owner_account_id.clone();
             let ft_contract_account_id: near_primitives::types::AccountId =
                 scope.ft_contract_account_id.clone().into();
+            let output = scope.output.clone().unwrap_or_default();
 
             move |network_config, block_reference| {
-                let crate::types::ft_properties::FtMetadata { decimals, symbol } = crate::types::ft_properties::params_ft_metadata(
-                    ft_contract_account_id.clone(),
-                    network_config,
-                    block_reference.clone(),
-                )?;
                 let args = serde_json::to_vec(&json!({
                     "account_id": owner_account_id.to_string(),
                     }))?;
-                let call_result = get_ft_balance(network_config, &ft_contract_account_id, args, block_reference.clone())?;
+                let rpc_query_response = get_ft_balance(network_config, &ft_contract_account_id, args.clone(), block_reference.clone())?;
+                // The metadata and the registration are viewed at the same block as the balance
+                let block_reference = near_primitives::types::BlockReference::BlockId(
+                    near_primitives::types::BlockId::Hash(rpc_query_response.block_hash),
+                );
+                let call_result = rpc_query_response.call_result()?;
                 call_result.print_logs();
                 let amount: String = call_result.parse_result_from_json()?;
-                let fungible_token = crate::types::ft_properties::FungibleToken::from_params_ft(
+                let ft_metadata = crate::types::ft_properties::params_ft_metadata(
+                    ft_contract_account_id.clone(),
+                    network_config,
+                    block_reference.clone(),
+                )?;
+                if !is_registered(network_config, &ft_contract_account_id, args, block_reference) {
+                    return Err(color_eyre::eyre::eyre!(
+                        "<{owner_account_id}> is not registered with the FT contract <{ft_contract_account_id}> at block #{}",
+                        rpc_query_response.block_height
+                    ));
+                }
+                let ft_balance = FtBalance::new(
+                    owner_account_id.clone(),
+                    ft_contract_account_id.clone(),
+                    rpc_query_response.block_height,
+                    rpc_query_response.block_hash,
                     amount.parse::<u128>()?,
-                    decimals,
-                    symbol
+                    ft_metadata,
                 );
 
-                eprintln!(
-                    "\n<{owner_account_id}> account has {fungible_token}  (FT-contract: {ft_contract_account_id})"
-                );
+                match output {
+                    crate::common::OutputFormat::Plaintext => {
+                        crate::network_view_at_block::display_viewed_at_block(
+                            ft_balance.block_height,
+                            &ft_balance.block_hash,
+                        );
+                        eprintln!(
+                            "\n<{owner_account_id}> account has {}  (FT-contract: {ft_contract_account_id})\nRaw amount: {} (decimals: {})",
+                            ft_balance.formatted, ft_balance.amount, ft_balance.decimals
+                        );
+                    }
+                    crate::common::OutputFormat::Json => {
+                        println!("{}", serde_json::to_string_pretty(&ft_balance)?)
+                    }
+                }
                 Ok(())
             }
         });
@@ -87,19 +160,81 @@ fn get_ft_balance(
     ft_contract_account_id: &near_primitives::types::AccountId,
     args: Vec<u8>,
     block_reference: near_primitives::types::BlockReference,
-) -> color_eyre::eyre::Result<near_primitives::views::CallResult> {
+) -> color_eyre::eyre::Result<near_jsonrpc_primitives::types::query::RpcQueryResponse> {
+    network_config
+        .json_rpc_client()
+        .blocking_call(near_jsonrpc_client::methods::query::RpcQueryRequest {
+            block_reference,
+            request: near_primitives::views::QueryRequest::CallFunction {
+                account_id: ft_contract_account_id.clone(),
+                method_name: "ft_balance_of".to_string(),
+                args: near_primitives::types::FunctionArgs::from(args),
+            },
+        })
+        .wrap_err_with(|| {
+            crate::common::old_block_request_error(
+                format_args!(
+                    "query for view method 'ft_balance_of' of contract <{ft_contract_account_id}>"
+                ),
+                network_config,
+            )
+        })
+}
+
+/// Whether the account is registered with the token (NEP-145 `storage_balance_of` is not null).
+/// The tokens without the storage management standard are assumed to register every account.
+fn is_registered(
+    network_config: &crate::config::NetworkConfig,
+    ft_contract_account_id: &near_primitives::types::AccountId,
+    args: Vec<u8>,
+    block_reference: near_primitives::types::BlockReference,
+) -> bool {
     network_config
         .json_rpc_client()
         .blocking_call_view_function(
             ft_contract_account_id,
-            "ft_balance_of",
+            "storage_balance_of",
             args,
             block_reference,
         )
-        .wrap_err_with(||{
-            format!("Failed to fetch query for view method: 'ft_balance_of' (contract <{}> on network <{}>)",
-                ft_contract_account_id,
-                network_config.network_name
-            )
+        .ok()
+        .and_then(|call_result| {
+            call_result
+                .parse_result_from_json::<serde_json::Value>()
+                .ok()
         })
+        .map_or(true, |storage_balance| !storage_balance.is_null())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ft_balance_in_json() {
+        let ft_balance = FtBalance::new(
+            "alice.near".parse().unwrap(),
+            "usdc.near".parse().unwrap(),
+            135_000_000,
+            near_primitives::hash::CryptoHash::default(),
+            12_500_000,
+            crate::types::ft_properties::FtMetadata {
+                symbol: "USDC".to_string(),
+                decimals: 6,
+            },
+        );
+        assert_eq!(
+            serde_json::to_value(&ft_balance).unwrap(),
+            serde_json::json!({
+                "account_id": "alice.near",
+                "ft_contract_account_id": "usdc.near",
+                "block_height": 135_000_000,
+                "block_hash": "11111111111111111111111111111111",
+                "amount": "12500000",
+                "decimals": 6,
+                "symbol": "USDC",
+                "formatted": "12.5 USDC"
+            })
+        );
+    }
 }
@@ -1,32 +1,92 @@
+use color_eyre::eyre::{ContextCompat, WrapErr};
+
+use crate::common::{JsonRpcClientExt, RpcQueryResponseExt};
+
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = super::TokensCommandsContext)]
 #[interactive_clap(output_context = ViewNearBalanceContext)]
 pub struct ViewNearBalance {
+    #[interactive_clap(long)]
+    /// Print only the available balance as a bare number in NEAR, for scripts
+    bare: bool,
+    #[interactive_clap(long)]
+    /// Print the total balance (including the locked balance) instead of the available balance (implies --bare)
+    total: bool,
+    #[interactive_clap(long)]
+    /// Print the balance in yoctoNEAR instead of NEAR (implies --bare)
+    yocto: bool,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Output format: plaintext (default) or json (all the balances in yoctoNEAR)
+    output: Option<crate::common::OutputFormat>,
     #[interactive_clap(named_arg)]
     /// Select network
     network_config: crate::network_view_at_block::NetworkViewAtBlockArgs,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+struct NearBalance {
+    account_id: near_primitives::types::AccountId,
+    block_height: near_primitives::types::BlockHeight,
+    total: near_token::NearToken,
+    locked: near_token::NearToken,
+    storage_stake: near_token::NearToken,
+    /// The total balance minus the locked balance and the part of the storage stake that is not
+    /// covered by the locked balance
+    available: near_token::NearToken,
+}
+
 #[derive(Clone)]
 pub struct ViewNearBalanceContext(crate::network_view_at_block::ArgsForViewContext);
 
 impl ViewNearBalanceContext {
     pub fn from_previous_context(
         previous_context: super::TokensCommandsContext,
-        _scope: &<ViewNearBalance as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+        scope: &<ViewNearBalance as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
         let on_after_getting_block_reference_callback: crate::network_view_at_block::OnAfterGettingBlockReferenceCallback = std::sync::Arc::new({
             let owner_account_id = previous_context.owner_account_id.clone();
+            let total = scope.total;
+            let yocto = scope.yocto;
+            let bare = scope.bare || total || yocto;
+            let output = scope.output.clone().unwrap_or_default();
 
             move |network_config, block_reference| {
-                let account_transfer_allowance = tokio::runtime::Runtime::new()
-                .unwrap()
-                .block_on(crate::common::get_account_transfer_allowance(
+                if !bare && matches!(output, crate::common::OutputFormat::Plaintext) {
+                    let account_transfer_allowance = crate::common::multi_thread_runtime()?
+                        .block_on(crate::common::get_account_transfer_allowance(
+                            network_config,
+                            owner_account_id.clone(),
+                            block_reference.clone(),
+                        ))?;
+                    eprintln!("{account_transfer_allowance}");
+                    return Ok(());
+                }
+                let need_storage_stake =
+                    !total || matches!(output, crate::common::OutputFormat::Json);
+                let near_balance = get_near_balance(
                     network_config,
-                    owner_account_id.clone(),
-                    block_reference.clone(),
-                ))?;
-                eprintln!("{account_transfer_allowance}");
+                    &owner_account_id,
+                    block_reference,
+                    need_storage_stake,
+                )?;
+                match output {
+                    crate::common::OutputFormat::Plaintext => {
+                        let balance = if total {
+                            near_balance.total
+                        } else {
+                            near_balance.available
+                        };
+                        if yocto {
+                            println!("{}", balance.as_yoctonear());
+                        } else {
+                            println!("{}", format_near(balance));
+                        }
+                    }
+                    crate::common::OutputFormat::Json => {
+                        println!("{}", serde_json::to_string_pretty(&near_balance)?)
+                    }
+                }
                 Ok(())
             }
         });
@@ -44,3 +104,227 @@ impl From<ViewNearBalanceContext> for crate::network_view_at_block::ArgsForViewC
         item.0
     }
 }
+
+/// A single `ViewAccount` query, plus the cached storage price (only if the storage stake is needed).
+fn get_near_balance(
+    network_config: &crate::config::NetworkConfig,
+    account_id: &near_primitives::types::AccountId,
+    block_reference: &near_primitives::types::BlockReference,
+    need_storage_stake: bool,
+) -> color_eyre::eyre::Result<NearBalance> {
+    let json_rpc_client = network_config.json_rpc_client();
+    let rpc_query_response = json_rpc_client
+        .blocking_call_view_account(account_id, block_reference.clone())
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch query ViewAccount for account <{account_id}> on network <{}>",
+                network_config.network_name
+            )
+        })?;
+    let block_height = rpc_query_response.block_height;
+    let account_view = rpc_query_response.account_view()?;
+    let storage_amount_per_byte = if need_storage_stake {
+        get_storage_amount_per_byte(network_config, &json_rpc_client, block_reference)?
+    } else {
+        0
+    };
+    Ok(near_balance(
+        account_id.clone(),
+        block_height,
+        &account_view,
+        storage_amount_per_byte,
+    ))
+}
+
+/// The storage price has not changed since the genesis of the networks, so the protocol config is
+/// fetched at most once a day per RPC server.
+const STORAGE_PRICE_CACHE_FILE_NAME: &str = "storage-prices.json";
+const STORAGE_PRICE_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(24 * 60 * 60);
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+struct CachedStoragePrice {
+    storage_amount_per_byte: near_token::NearToken,
+    fetched_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// The cached storage prices by the RPC URL
+type StoragePriceCache = std::collections::BTreeMap<String, CachedStoragePrice>;
+
+fn get_storage_amount_per_byte(
+    network_config: &crate::config::NetworkConfig,
+    json_rpc_client: &near_jsonrpc_client::JsonRpcClient,
+    block_reference: &near_primitives::types::BlockReference,
+) -> color_eyre::eyre::Result<near_primitives::types::Balance> {
+    let cache_path = get_storage_price_cache_path();
+    let mut cache: StoragePriceCache = cache_path
+        .as_ref()
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|cache_buf| serde_json::from_str(&cache_buf).ok())
+        .unwrap_or_default();
+    let rpc_url = network_config.rpc_url.to_string();
+    if let Some(storage_amount_per_byte) = fresh_storage_price(&cache, &rpc_url, chrono::Utc::now())
+    {
+        return Ok(storage_amount_per_byte);
+    }
+    let storage_amount_per_byte = json_rpc_client
+        .blocking_call(
+            near_jsonrpc_client::methods::EXPERIMENTAL_protocol_config::RpcProtocolConfigRequest {
+                block_reference: block_reference.clone(),
+            },
+        )
+        .wrap_err("Failed to fetch the protocol config")?
+        .runtime_config
+        .storage_amount_per_byte;
+    cache.insert(
+        rpc_url,
+        CachedStoragePrice {
+            storage_amount_per_byte: near_token::NearToken::from_yoctonear(storage_amount_per_byte),
+            fetched_at: chrono::Utc::now(),
+        },
+    );
+    if let Err(err) = cache_path.and_then(|path| write_storage_price_cache(&path, &cache)) {
+        tracing::warn!("Failed to cache the storage price: {err:#}");
+    }
+    Ok(storage_amount_per_byte)
+}
+
+fn fresh_storage_price(
+    cache: &StoragePriceCache,
+    rpc_url: &str,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Option<near_primitives::types::Balance> {
+    cache
+        .get(rpc_url)
+        .filter(|cached| {
+            (now - cached.fetched_at)
+                .to_std()
+                .is_ok_and(|age| age < STORAGE_PRICE_CACHE_TTL)
+        })
+        .map(|cached| cached.storage_amount_per_byte.as_yoctonear())
+}
+
+fn get_storage_price_cache_path() -> color_eyre::eyre::Result<std::path::PathBuf> {
+    let mut path = dirs::cache_dir().wrap_err("Impossible to get your cache dir!")?;
+    path.push("near-cli");
+    path.push(STORAGE_PRICE_CACHE_FILE_NAME);
+    Ok(path)
+}
+
+fn write_storage_price_cache(
+    path: &std::path::Path,
+    cache: &StoragePriceCache,
+) -> crate::CliResult {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(cache)?)
+        .wrap_err_with(|| format!("Failed to write to file: {}", path.display()))
+}
+
+fn near_balance(
+    account_id: near_primitives::types::AccountId,
+    block_height: near_primitives::types::BlockHeight,
+    account_view: &near_primitives::views::AccountView,
+    storage_amount_per_byte: near_primitives::types::Balance,
+) -> NearBalance {
+    let storage_stake = u128::from(account_view.storage_usage) * storage_amount_per_byte;
+    let liquid_storage_stake = storage_stake.saturating_sub(account_view.locked);
+    NearBalance {
+        account_id,
+        block_height,
+        total: near_token::NearToken::from_yoctonear(account_view.amount + account_view.locked),
+        locked: near_token::NearToken::from_yoctonear(account_view.locked),
+        storage_stake: near_token::NearToken::from_yoctonear(storage_stake),
+        available: near_token::NearToken::from_yoctonear(
+            account_view.amount.saturating_sub(liquid_storage_stake),
+        ),
+    }
+}
+
+/// The exact amount in NEAR without the unit, e.g. `12.5` (the NearToken display is rounded).
+fn format_near(amount: near_token::NearToken) -> String {
+    const YOCTONEAR_PER_NEAR: u128 = 10u128.pow(24);
+    let yoctonear = amount.as_yoctonear();
+    let fraction = yoctonear % YOCTONEAR_PER_NEAR;
+    if fraction == 0 {
+        format!("{}", yoctonear / YOCTONEAR_PER_NEAR)
+    } else {
+        let fraction = format!("{fraction:024}");
+        format!(
+            "{}.{}",
+            yoctonear / YOCTONEAR_PER_NEAR,
+            fraction.trim_end_matches('0')
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn available_balance_and_exact_format() {
+        let account_view = near_primitives::views::AccountView {
+            amount: near_token::NearToken::from_near(10).as_yoctonear(),
+            locked: near_token::NearToken::from_millinear(10).as_yoctonear(),
+            code_hash: Default::default(),
+            storage_usage: 10_000,
+            storage_paid_at: 0,
+        };
+        let balance = near_balance(
+            "alice.near".parse().unwrap(),
+            1,
+            &account_view,
+            10u128.pow(19),
+        );
+        assert_eq!(
+            balance.storage_stake,
+            near_token::NearToken::from_millinear(100)
+        );
+        assert_eq!(balance.total, near_token::NearToken::from_millinear(10_010));
+        assert_eq!(
+            balance.available,
+            near_token::NearToken::from_millinear(9_910)
+        );
+
+        assert_eq!(format_near(balance.available), "9.91");
+        assert_eq!(format_near(near_token::NearToken::from_near(3)), "3");
+        assert_eq!(
+            format_near(near_token::NearToken::from_yoctonear(1)),
+            "0.000000000000000000000001"
+        );
+    }
+
+    #[test]
+    fn storage_price_is_cached_for_a_day() {
+        let fetched_at = chrono::Utc::now();
+        let cache = StoragePriceCache::from([(
+            "https://rpc.testnet.near.org/".to_string(),
+            CachedStoragePrice {
+                storage_amount_per_byte: near_token::NearToken::from_yoctonear(10u128.pow(19)),
+                fetched_at,
+            },
+        )]);
+        assert_eq!(
+            fresh_storage_price(
+                &cache,
+                "https://rpc.testnet.near.org/",
+                fetched_at + chrono::Duration::hours(23)
+            ),
+            Some(10u128.pow(19))
+        );
+        assert_eq!(
+            fresh_storage_price(
+                &cache,
+                "https://rpc.testnet.near.org/",
+                fetched_at + chrono::Duration::hours(25)
+            ),
+            None
+        );
+        assert_eq!(
+            fresh_storage_price(&cache, "https://rpc.mainnet.near.org/", fetched_at),
+            None
+        );
+    }
+}
@@ -0,0 +1,354 @@
+use color_eyre::eyre::WrapErr;
+use serde_json::json;
+
+use crate::common::{CallResultExt, JsonRpcClientExt};
+
+/// The NEAR/USD price feed of Pyth
+const PYTH_NEAR_USD_PRICE_IDENTIFIER: &str =
+    "c415de8d2eba7db216527dff4b60e8f3a5311c740dadb233e13e12547e226750";
+/// The wNEAR/USDt pool of Ref Finance on mainnet
+const REF_FINANCE_CONTRACT: &str = "v2.ref-finance.near";
+const REF_FINANCE_NEAR_USDT_POOL_ID: u64 = 3879;
+const REF_FINANCE_USDT_CONTRACT: &str = "usdt.tether-token.near";
+const USDT_DECIMALS: u32 = 6;
+const NEAR_DECIMALS: u32 = 24;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = super::TokensCommandsContext)]
+#[interactive_clap(output_context = ViewNearPriceContext)]
+pub struct ViewNearPrice {
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Where to get the price: oracle (default if `price_oracle_account_id` is configured), ref-finance or pyth
+    source: Option<PriceSource>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Output format: plaintext (default) or json (the raw response of the contract)
+    output: Option<crate::common::OutputFormat>,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network::Network,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum_macros::EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum PriceSource {
+    /// The price oracle contract of the network connection (`get_price_data`)
+    Oracle,
+    /// The spot price of the wNEAR/USDt pool
+    RefFinance,
+    /// The NEAR/USD feed of the Pyth contract
+    Pyth,
+}
+
+impl std::fmt::Display for PriceSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PriceSource::Oracle => write!(f, "oracle"),
+            PriceSource::RefFinance => write!(f, "ref-finance"),
+            PriceSource::Pyth => write!(f, "pyth"),
+        }
+    }
+}
+
+impl interactive_clap::ToCli for PriceSource {
+    type CliVariant = PriceSource;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct NearPrice {
+    usd: f64,
+    confidence: Option<f64>,
+    timestamp: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl std::fmt::Display for NearPrice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "1 NEAR = ${:.4}", self.usd)?;
+        if let Some(confidence) = self.confidence {
+            write!(f, " ± ${confidence:.4}")?;
+        }
+        if let Some(timestamp) = self.timestamp {
+            write!(f, " (as of {})", timestamp.format("%Y-%m-%d %H:%M:%S UTC"))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct ViewNearPriceContext(crate::network::NetworkContext);
+
+impl ViewNearPriceContext {
+    pub fn from_previous_context(
+        previous_context: super::TokensCommandsContext,
+        scope: &<ViewNearPrice as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let on_after_getting_network_callback: crate::network::OnAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let source = scope.source;
+                let output = scope.output.clone().unwrap_or_default();
+
+                move |network_config| {
+                    let source =
+                        source.unwrap_or(if network_config.price_oracle_account_id.is_some() {
+                            PriceSource::Oracle
+                        } else {
+                            PriceSource::RefFinance
+                        });
+                    let (contract_account_id, method_name, args) =
+                        price_request(network_config, source)?;
+                    let response: serde_json::Value = network_config
+                        .json_rpc_client()
+                        .blocking_call_view_function(
+                            &contract_account_id,
+                            method_name,
+                            serde_json::to_vec(&args)?,
+                            near_primitives::types::Finality::Final.into(),
+                        )
+                        .wrap_err_with(|| {
+                            format!(
+                                "Failed to fetch query for view method: '{method_name}' (contract <{contract_account_id}> on network <{}>)",
+                                network_config.network_name
+                            )
+                        })?
+                        .parse_result_from_json()?;
+                    match output {
+                        crate::common::OutputFormat::Plaintext => {
+                            let near_price = match source {
+                                PriceSource::Oracle => parse_oracle_price(&response)?,
+                                PriceSource::RefFinance => parse_ref_finance_price(&response)?,
+                                PriceSource::Pyth => parse_pyth_price(&response)?,
+                            };
+                            eprintln!("\nThe price from {source} <{contract_account_id}>:");
+                            println!("{near_price}");
+                        }
+                        crate::common::OutputFormat::Json => {
+                            println!("{}", serde_json::to_string_pretty(&response)?);
+                        }
+                    }
+                    Ok(())
+                }
+            });
+
+        Ok(Self(crate::network::NetworkContext {
+            config: previous_context.global_context.config,
+            interacting_with_account_ids: vec![previous_context.owner_account_id],
+            on_after_getting_network_callback,
+        }))
+    }
+}
+
+impl From<ViewNearPriceContext> for crate::network::NetworkContext {
+    fn from(item: ViewNearPriceContext) -> Self {
+        item.0
+    }
+}
+
+/// The contract, the view method and its arguments that return the price.
+fn price_request(
+    network_config: &crate::config::NetworkConfig,
+    source: PriceSource,
+) -> color_eyre::eyre::Result<(
+    near_primitives::types::AccountId,
+    &'static str,
+    serde_json::Value,
+)> {
+    // The contracts are deployed under the top-level account of the network (near, testnet)
+    let top_level_account_id = network_config.linkdrop_account_id.as_ref();
+    match source {
+        PriceSource::Oracle => {
+            let price_oracle_account_id = network_config.price_oracle_account_id.clone().ok_or_else(|| {
+                color_eyre::eyre::eyre!(
+                    "There is no price oracle for network <{network_name}>, set it with: near config edit-connection {network_name} --key price_oracle_account_id --value <ACCOUNT_ID>",
+                    network_name = network_config.network_name
+                )
+            })?;
+            let wrap_account_id = top_level_account_id
+                .map(|top_level_account_id| format!("wrap.{top_level_account_id}"))
+                .ok_or_else(|| {
+                    color_eyre::eyre::eyre!(
+                        "The wNEAR contract of network <{}> is unknown (there is no linkdrop_account_id)",
+                        network_config.network_name
+                    )
+                })?;
+            Ok((
+                price_oracle_account_id,
+                "get_price_data",
+                json!({ "asset_ids": [wrap_account_id] }),
+            ))
+        }
+        PriceSource::RefFinance => {
+            if top_level_account_id.map(|account_id| account_id.as_str()) != Some("near") {
+                color_eyre::eyre::bail!(
+                    "The Ref Finance price is available only on mainnet, use `--source oracle` or `--source pyth` for network <{}>",
+                    network_config.network_name
+                );
+            }
+            Ok((
+                REF_FINANCE_CONTRACT.parse()?,
+                "get_return",
+                json!({
+                    "pool_id": REF_FINANCE_NEAR_USDT_POOL_ID,
+                    "token_in": "wrap.near",
+                    "amount_in": 10u128.pow(NEAR_DECIMALS).to_string(),
+                    "token_out": REF_FINANCE_USDT_CONTRACT,
+                }),
+            ))
+        }
+        PriceSource::Pyth => {
+            let top_level_account_id = top_level_account_id.ok_or_else(|| {
+                color_eyre::eyre::eyre!(
+                    "The Pyth contract of network <{}> is unknown (there is no linkdrop_account_id)",
+                    network_config.network_name
+                )
+            })?;
+            Ok((
+                format!("pyth-oracle.{top_level_account_id}").parse()?,
+                "get_price_unsafe",
+                json!({ "price_identifier": PYTH_NEAR_USD_PRICE_IDENTIFIER }),
+            ))
+        }
+    }
+}
+
+/// `get_price_data` returns the price of one yocto-unit of the asset as `multiplier / 10^decimals`.
+fn parse_oracle_price(response: &serde_json::Value) -> color_eyre::eyre::Result<NearPrice> {
+    #[derive(serde::Deserialize)]
+    struct PriceData {
+        timestamp: String,
+        prices: Vec<AssetPrice>,
+    }
+    #[derive(serde::Deserialize)]
+    struct AssetPrice {
+        price: Option<Price>,
+    }
+    #[derive(serde::Deserialize)]
+    struct Price {
+        multiplier: String,
+        decimals: u32,
+    }
+
+    let price_data: PriceData = serde_json::from_value(response.clone())
+        .wrap_err("The price oracle returned an unexpected response")?;
+    let price = price_data
+        .prices
+        .into_iter()
+        .find_map(|asset_price| asset_price.price)
+        .ok_or_else(|| color_eyre::eyre::eyre!("The price oracle has no price for wNEAR"))?;
+    let multiplier = price
+        .multiplier
+        .parse::<f64>()
+        .wrap_err("The price oracle returned an invalid multiplier")?;
+    let timestamp_nanos = price_data
+        .timestamp
+        .parse::<i64>()
+        .wrap_err("The price oracle returned an invalid timestamp")?;
+    Ok(NearPrice {
+        usd: multiplier * 10f64.powi(NEAR_DECIMALS as i32 - price.decimals as i32),
+        confidence: None,
+        timestamp: Some(chrono::DateTime::from_timestamp_nanos(timestamp_nanos)),
+    })
+}
+
+/// `get_return` returns the USDt received for 1 NEAR.
+fn parse_ref_finance_price(response: &serde_json::Value) -> color_eyre::eyre::Result<NearPrice> {
+    let amount_out = response
+        .as_str()
+        .and_then(|amount_out| amount_out.parse::<u128>().ok())
+        .ok_or_else(|| {
+            color_eyre::eyre::eyre!("Ref Finance returned an invalid amount: {response}")
+        })?;
+    Ok(NearPrice {
+        usd: amount_out as f64 / 10f64.powi(USDT_DECIMALS as i32),
+        confidence: None,
+        timestamp: None,
+    })
+}
+
+/// The Pyth price and its confidence interval are `price * 10^expo` and `conf * 10^expo`.
+fn parse_pyth_price(response: &serde_json::Value) -> color_eyre::eyre::Result<NearPrice> {
+    #[derive(serde::Deserialize)]
+    struct Price {
+        price: String,
+        conf: String,
+        expo: i32,
+        publish_time: i64,
+    }
+
+    if response.is_null() {
+        color_eyre::eyre::bail!("Pyth has no NEAR/USD price");
+    }
+    let price: Price = serde_json::from_value(response.clone())
+        .wrap_err("Pyth returned an unexpected response")?;
+    let scale = 10f64.powi(price.expo);
+    Ok(NearPrice {
+        usd: price
+            .price
+            .parse::<f64>()
+            .wrap_err("Pyth returned an invalid price")?
+            * scale,
+        confidence: Some(
+            price
+                .conf
+                .parse::<f64>()
+                .wrap_err("Pyth returned an invalid confidence interval")?
+                * scale,
+        ),
+        timestamp: chrono::DateTime::from_timestamp(price.publish_time, 0),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_price(near_price: &NearPrice, usd: f64) {
+        assert!((near_price.usd - usd).abs() < 1e-9, "{near_price:?}");
+    }
+
+    #[test]
+    fn oracle_price() {
+        let near_price = parse_oracle_price(&json!({
+            "timestamp": "1735689600000000000",
+            "recency_duration_sec": 90,
+            "prices": [{ "asset_id": "wrap.near", "price": { "multiplier": "36550", "decimals": 28 } }]
+        }))
+        .unwrap();
+        assert_price(&near_price, 3.655);
+        assert_eq!(
+            near_price.to_string(),
+            "1 NEAR = $3.6550 (as of 2025-01-01 00:00:00 UTC)"
+        );
+        assert!(parse_oracle_price(&json!({
+            "timestamp": "1735689600000000000",
+            "prices": [{ "asset_id": "wrap.near", "price": null }]
+        }))
+        .is_err());
+    }
+
+    #[test]
+    fn ref_finance_price() {
+        assert_price(
+            &parse_ref_finance_price(&json!("3655012")).unwrap(),
+            3.655012,
+        );
+        assert!(parse_ref_finance_price(&json!(3655012)).is_err());
+    }
+
+    #[test]
+    fn pyth_price() {
+        let near_price = parse_pyth_price(&json!({
+            "price": "365501234",
+            "conf": "210000",
+            "expo": -8,
+            "publish_time": 1735689600
+        }))
+        .unwrap();
+        assert_price(&near_price, 3.65501234);
+        assert_eq!(
+            near_price.to_string(),
+            "1 NEAR = $3.6550 ± $0.0021 (as of 2025-01-01 00:00:00 UTC)"
+        );
+        assert!(parse_pyth_price(&serde_json::Value::Null).is_err());
+    }
+}
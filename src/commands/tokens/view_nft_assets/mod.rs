@@ -1,9 +1,3 @@
-use color_eyre::eyre::Context;
-use serde_json::json;
-
-use crate::common::CallResultExt;
-use crate::common::JsonRpcClientExt;
-
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = super::TokensCommandsContext)]
 #[interactive_clap(output_context = ViewNftAssetsContext)]
@@ -11,6 +5,10 @@ pub struct ViewNftAssets {
     #[interactive_clap(skip_default_input_arg)]
     /// What is the nft-contract account ID?
     nft_contract_account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// How many tokens to read per request (by default the contract's default page is read and only split when it is too large)
+    max_page_size: Option<u64>,
     #[interactive_clap(named_arg)]
     /// Select network
     network_config: crate::network_view_at_block::NetworkViewAtBlockArgs,
@@ -28,17 +26,19 @@ impl ViewNftAssetsContext {
             let owner_account_id = previous_context.owner_account_id.clone();
             let nft_contract_account_id: near_primitives::types::AccountId =
                 scope.nft_contract_account_id.clone().into();
+            let max_page_size = scope.max_page_size;
 
             move |network_config, block_reference| {
-                let args = serde_json::to_vec(&json!({
-                        "account_id": owner_account_id.to_string(),
-                    }))?;
-                let call_result = get_nft_balance(network_config, &nft_contract_account_id, args, block_reference.clone())?;
-                call_result.print_logs();
-                let serde_call_result: serde_json::Value = call_result.parse_result_from_json()?;
+                let tokens = crate::view_pagination::get_nft_tokens_for_owner(
+                    network_config,
+                    &nft_contract_account_id,
+                    &owner_account_id,
+                    block_reference,
+                    max_page_size,
+                )?;
 
                 eprintln!("\n{} account has NFT tokens:", owner_account_id);
-                eprintln!("{}", serde_json::to_string_pretty(&serde_call_result)?);
+                eprintln!("{}", serde_json::to_string_pretty(&tokens)?);
                 Ok(())
             }
         });
@@ -69,26 +69,3 @@ impl ViewNftAssets {
         )
     }
 }
-
-#[tracing::instrument(name = "Getting NFT balance ...", skip_all)]
-fn get_nft_balance(
-    network_config: &crate::config::NetworkConfig,
-    nft_contract_account_id: &near_primitives::types::AccountId,
-    args: Vec<u8>,
-    block_reference: near_primitives::types::BlockReference,
-) -> color_eyre::eyre::Result<near_primitives::views::CallResult> {
-    network_config
-        .json_rpc_client()
-        .blocking_call_view_function(
-            nft_contract_account_id,
-            "nft_tokens_for_owner",
-            args,
-            block_reference,
-        )
-        .wrap_err_with(||{
-            format!("Failed to fetch query for view method: 'nft_tokens_for_owner' (contract <{}> on network <{}>)",
-                nft_contract_account_id,
-                network_config.network_name
-        )
-})
-}
@@ -0,0 +1,302 @@
+use color_eyre::eyre::{Context, ContextCompat};
+use prettytable::Table;
+use serde_json::json;
+
+use crate::common::{CallResultExt, JsonRpcClientExt};
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = super::TokensCommandsContext)]
+#[interactive_clap(output_context = ViewNftMetadataContext)]
+pub struct ViewNftMetadata {
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the nft-contract account ID?
+    nft_contract_account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Also view the metadata of the token with this ID
+    token_id: Option<String>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Output format: plaintext (default) or json
+    output: Option<crate::common::OutputFormat>,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network_view_at_block::NetworkViewAtBlockArgs,
+}
+
+/// NEP-177 contract metadata
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct NftContractMetadata {
+    spec: String,
+    name: String,
+    symbol: String,
+    icon: Option<String>,
+    base_uri: Option<String>,
+    reference: Option<String>,
+    reference_hash: Option<String>,
+}
+
+/// NEP-177 token metadata
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct NftTokenMetadata {
+    title: Option<String>,
+    description: Option<String>,
+    media: Option<String>,
+    media_hash: Option<String>,
+    copies: Option<u64>,
+    issued_at: Option<String>,
+    expires_at: Option<String>,
+    starts_at: Option<String>,
+    updated_at: Option<String>,
+    extra: Option<String>,
+    reference: Option<String>,
+    reference_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct NftToken {
+    token_id: String,
+    owner_id: near_primitives::types::AccountId,
+    metadata: Option<NftTokenMetadata>,
+}
+
+/// The JSON output (`--output json`) is this structure serialized as is.
+#[derive(Debug, Clone, serde::Serialize)]
+struct NftMetadataReport {
+    contract_metadata: NftContractMetadata,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    token: Option<NftToken>,
+}
+
+#[derive(Clone)]
+pub struct ViewNftMetadataContext(crate::network_view_at_block::ArgsForViewContext);
+
+impl ViewNftMetadataContext {
+    pub fn from_previous_context(
+        previous_context: super::TokensCommandsContext,
+        scope: &<ViewNftMetadata as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let on_after_getting_block_reference_callback: crate::network_view_at_block::OnAfterGettingBlockReferenceCallback = std::sync::Arc::new({
+            let nft_contract_account_id: near_primitives::types::AccountId =
+                scope.nft_contract_account_id.clone().into();
+            let token_id = scope.token_id.clone();
+            let output = scope.output.clone().unwrap_or_default();
+
+            move |network_config, block_reference| {
+                let contract_metadata: NftContractMetadata = call_nft_view_method(
+                    network_config,
+                    &nft_contract_account_id,
+                    "nft_metadata",
+                    json!({}),
+                    block_reference,
+                )?;
+                let token = token_id
+                    .as_ref()
+                    .map(|token_id| {
+                        call_nft_view_method::<Option<NftToken>>(
+                            network_config,
+                            &nft_contract_account_id,
+                            "nft_token",
+                            json!({ "token_id": token_id }),
+                            block_reference,
+                        )?
+                        .wrap_err_with(|| {
+                            format!("The token <{token_id}> does not exist in <{nft_contract_account_id}>")
+                        })
+                    })
+                    .transpose()?;
+
+                let report = NftMetadataReport {
+                    contract_metadata,
+                    token,
+                };
+                match output {
+                    crate::common::OutputFormat::Plaintext => {
+                        display_nft_metadata(&nft_contract_account_id, &report)
+                    }
+                    crate::common::OutputFormat::Json => {
+                        println!("{}", serde_json::to_string_pretty(&report)?)
+                    }
+                }
+                Ok(())
+            }
+        });
+        Ok(Self(crate::network_view_at_block::ArgsForViewContext {
+            config: previous_context.global_context.config,
+            on_after_getting_block_reference_callback,
+            interacting_with_account_ids: vec![
+                scope.nft_contract_account_id.clone().into(),
+                previous_context.owner_account_id,
+            ],
+        }))
+    }
+}
+
+impl From<ViewNftMetadataContext> for crate::network_view_at_block::ArgsForViewContext {
+    fn from(item: ViewNftMetadataContext) -> Self {
+        item.0
+    }
+}
+
+impl ViewNftMetadata {
+    pub fn input_nft_contract_account_id(
+        context: &super::TokensCommandsContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_non_signer_account_id_from_used_account_list(
+            &context.global_context.config.credentials_home_dir,
+            "What is the nft-contract account ID?",
+        )
+    }
+}
+
+#[tracing::instrument(name = "Getting NFT metadata ...", skip_all)]
+fn call_nft_view_method<T: serde::de::DeserializeOwned>(
+    network_config: &crate::config::NetworkConfig,
+    nft_contract_account_id: &near_primitives::types::AccountId,
+    method_name: &str,
+    args: serde_json::Value,
+    block_reference: &near_primitives::types::BlockReference,
+) -> color_eyre::eyre::Result<T> {
+    network_config
+        .json_rpc_client()
+        .blocking_call_view_function(
+            nft_contract_account_id,
+            method_name,
+            serde_json::to_vec(&args)?,
+            block_reference.clone(),
+        )
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch query for view method: '{method_name}' (contract <{}> on network <{}>)",
+                nft_contract_account_id, network_config.network_name
+            )
+        })?
+        .parse_result_from_json()
+        .wrap_err_with(|| {
+            format!("The result of '{method_name}' does not match the NEP-177 metadata standard")
+        })
+}
+
+/// Icons are often inlined as (long) data URLs, which are only summarized in the table.
+fn display_icon(icon: &str) -> String {
+    if icon.starts_with("data:") {
+        let media_type = icon
+            .trim_start_matches("data:")
+            .split([';', ','])
+            .next()
+            .unwrap_or_default();
+        format!("<inline {media_type} data URL, {} bytes>", icon.len())
+    } else {
+        icon.to_string()
+    }
+}
+
+fn display_nft_metadata(
+    nft_contract_account_id: &near_primitives::types::AccountId,
+    report: &NftMetadataReport,
+) {
+    let optional = |value: &Option<String>| value.clone().unwrap_or_default();
+    let metadata = &report.contract_metadata;
+
+    let mut table = Table::new();
+    table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    table.set_titles(prettytable::row![Fg=>"NFT collection", nft_contract_account_id]);
+    table.add_row(prettytable::row!["Name", metadata.name]);
+    table.add_row(prettytable::row!["Symbol", metadata.symbol]);
+    table.add_row(prettytable::row!["Spec", metadata.spec]);
+    table.add_row(prettytable::row![
+        "Icon",
+        metadata
+            .icon
+            .as_deref()
+            .map(display_icon)
+            .unwrap_or_default()
+    ]);
+    table.add_row(prettytable::row!["Base URI", optional(&metadata.base_uri)]);
+    table.add_row(prettytable::row![
+        "Reference",
+        optional(&metadata.reference)
+    ]);
+    table.add_row(prettytable::row![
+        "Reference hash",
+        optional(&metadata.reference_hash)
+    ]);
+    table.printstd();
+
+    let Some(token) = &report.token else {
+        return;
+    };
+    let mut table = Table::new();
+    table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    table.set_titles(prettytable::row![Fg=>"Token", token.token_id]);
+    table.add_row(prettytable::row!["Owner", token.owner_id]);
+    match &token.metadata {
+        Some(token_metadata) => {
+            for (title, value) in [
+                ("Title", &token_metadata.title),
+                ("Description", &token_metadata.description),
+                ("Media", &token_metadata.media),
+                ("Media hash", &token_metadata.media_hash),
+                ("Issued at", &token_metadata.issued_at),
+                ("Expires at", &token_metadata.expires_at),
+                ("Starts at", &token_metadata.starts_at),
+                ("Updated at", &token_metadata.updated_at),
+                ("Extra", &token_metadata.extra),
+                ("Reference", &token_metadata.reference),
+                ("Reference hash", &token_metadata.reference_hash),
+            ] {
+                table.add_row(prettytable::row![title, optional(value)]);
+            }
+            table.add_row(prettytable::row![
+                "Copies",
+                token_metadata
+                    .copies
+                    .map(|copies| copies.to_string())
+                    .unwrap_or_default()
+            ]);
+        }
+        None => {
+            table.add_row(prettytable::row!["Metadata", "(none)"]);
+        }
+    }
+    println!();
+    table.printstd();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_nft_metadata() {
+        let contract_metadata: NftContractMetadata = serde_json::from_value(json!({
+            "spec": "nft-1.0.0",
+            "name": "Example",
+            "symbol": "EXMPL",
+            "icon": "data:image/svg+xml;base64,PHN2Zz4=",
+            "base_uri": null,
+            "reference": null,
+            "reference_hash": null,
+        }))
+        .unwrap();
+        assert_eq!(contract_metadata.symbol, "EXMPL");
+        assert_eq!(
+            display_icon(contract_metadata.icon.as_deref().unwrap()),
+            "<inline image/svg+xml data URL, 34 bytes>"
+        );
+        assert_eq!(
+            display_icon("https://example.com/icon.png"),
+            "https://example.com/icon.png"
+        );
+
+        let token: Option<NftToken> = serde_json::from_value(json!({
+            "token_id": "1",
+            "owner_id": "alice.near",
+            "metadata": { "title": "First", "copies": 1 },
+        }))
+        .unwrap();
+        let token_metadata = token.unwrap().metadata.unwrap();
+        assert_eq!(token_metadata.title.as_deref(), Some("First"));
+        assert_eq!(token_metadata.copies, Some(1));
+    }
+}
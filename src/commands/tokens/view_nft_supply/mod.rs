@@ -0,0 +1,139 @@
+use color_eyre::eyre::Context;
+use serde_json::json;
+
+use crate::common::{CallResultExt, JsonRpcClientExt};
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = super::TokensCommandsContext)]
+#[interactive_clap(output_context = ViewNftSupplyContext)]
+pub struct ViewNftSupply {
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the nft-contract account ID?
+    nft_contract_account_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Also count the NFT tokens of this account
+    account_id: Option<crate::types::account_id::AccountId>,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network_view_at_block::NetworkViewAtBlockArgs,
+}
+
+#[derive(Clone)]
+pub struct ViewNftSupplyContext(crate::network_view_at_block::ArgsForViewContext);
+
+impl ViewNftSupplyContext {
+    pub fn from_previous_context(
+        previous_context: super::TokensCommandsContext,
+        scope: &<ViewNftSupply as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let on_after_getting_block_reference_callback: crate::network_view_at_block::OnAfterGettingBlockReferenceCallback = std::sync::Arc::new({
+            let nft_contract_account_id: near_primitives::types::AccountId =
+                scope.nft_contract_account_id.clone().into();
+            let account_id: Option<near_primitives::types::AccountId> =
+                scope.account_id.clone().map(Into::into);
+
+            move |network_config, block_reference| {
+                let total_supply = get_nft_supply(
+                    network_config,
+                    &nft_contract_account_id,
+                    "nft_total_supply",
+                    json!({}),
+                    block_reference,
+                )?;
+                println!("Total supply of <{nft_contract_account_id}>: {total_supply} NFT tokens");
+                if let Some(account_id) = &account_id {
+                    let supply_for_owner = get_nft_supply(
+                        network_config,
+                        &nft_contract_account_id,
+                        "nft_supply_for_owner",
+                        json!({ "account_id": account_id }),
+                        block_reference,
+                    )?;
+                    println!("Owned by <{account_id}>: {supply_for_owner} NFT tokens");
+                }
+                Ok(())
+            }
+        });
+        Ok(Self(crate::network_view_at_block::ArgsForViewContext {
+            config: previous_context.global_context.config,
+            on_after_getting_block_reference_callback,
+            interacting_with_account_ids: std::iter::once(
+                scope.nft_contract_account_id.clone().into(),
+            )
+            .chain(scope.account_id.clone().map(Into::into))
+            .collect(),
+        }))
+    }
+}
+
+impl From<ViewNftSupplyContext> for crate::network_view_at_block::ArgsForViewContext {
+    fn from(item: ViewNftSupplyContext) -> Self {
+        item.0
+    }
+}
+
+impl ViewNftSupply {
+    pub fn input_nft_contract_account_id(
+        context: &super::TokensCommandsContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_non_signer_account_id_from_used_account_list(
+            &context.global_context.config.credentials_home_dir,
+            "What is the nft-contract account ID?",
+        )
+    }
+}
+
+#[tracing::instrument(name = "Getting NFT supply ...", skip_all)]
+fn get_nft_supply(
+    network_config: &crate::config::NetworkConfig,
+    nft_contract_account_id: &near_primitives::types::AccountId,
+    method_name: &str,
+    args: serde_json::Value,
+    block_reference: &near_primitives::types::BlockReference,
+) -> color_eyre::eyre::Result<u128> {
+    let supply: serde_json::Value = network_config
+        .json_rpc_client()
+        .blocking_call_view_function(
+            nft_contract_account_id,
+            method_name,
+            serde_json::to_vec(&args)?,
+            block_reference.clone(),
+        )
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch query for view method: '{method_name}' (contract <{}> on network <{}>), the contract may not implement the NEP-181 enumeration",
+                nft_contract_account_id, network_config.network_name
+            )
+        })?
+        .parse_result_from_json()?;
+    parse_supply(&supply).wrap_err_with(|| {
+        format!("The result of '{method_name}' is not a NEP-181 supply (a U128 string): {supply}")
+    })
+}
+
+/// NEP-181 returns the supply as a `U128` string, some contracts return a number.
+fn parse_supply(supply: &serde_json::Value) -> color_eyre::eyre::Result<u128> {
+    match supply {
+        serde_json::Value::String(supply) => Ok(supply.parse()?),
+        serde_json::Value::Number(supply) => supply
+            .as_u64()
+            .map(u128::from)
+            .ok_or_else(|| color_eyre::eyre::eyre!("Negative or fractional supply")),
+        _ => color_eyre::eyre::bail!("Expected a string or a number"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn supply_is_a_u128_string_or_a_number() {
+        assert_eq!(parse_supply(&json!("12345")).unwrap(), 12345);
+        assert_eq!(parse_supply(&json!(7)).unwrap(), 7);
+        assert!(parse_supply(&json!("-1")).is_err());
+        assert!(parse_supply(&json!(1.5)).is_err());
+        assert!(parse_supply(&json!(null)).is_err());
+    }
+}
@@ -26,8 +26,7 @@ impl AddAccessKeyActionContext {
                 access_key,
             },
         ));
-        let mut actions = previous_context.actions;
-        actions.push(action);
+        let actions = crate::tx_builder::append_action(previous_context.actions, action)?;
         Ok(Self(
             super::super::super::super::ConstructTransactionContext {
                 global_context: previous_context.global_context,
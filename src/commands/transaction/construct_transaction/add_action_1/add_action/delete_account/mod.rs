@@ -1,5 +1,4 @@
 use color_eyre::owo_colors::OwoColorize;
-use inquire::Select;
 
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = super::super::super::ConstructTransactionContext)]
@@ -25,8 +24,7 @@ impl DeleteAccountActionContext {
         let action = near_primitives::transaction::Action::DeleteAccount(
             near_primitives::transaction::DeleteAccountAction { beneficiary_id },
         );
-        let mut actions = previous_context.actions;
-        actions.push(action);
+        let actions = crate::tx_builder::append_action(previous_context.actions, action)?;
         Ok(Self(super::super::super::ConstructTransactionContext {
             global_context: previous_context.global_context,
             signer_account_id: previous_context.signer_account_id,
@@ -78,11 +76,11 @@ impl DeleteAccountAction {
                 #[display(fmt = "No, I know this account exists and want to continue.")]
                 No,
             }
-            let select_choose_input =
-                Select::new("\nDo you want to check the existence of the specified account so that you don't lose tokens?",
+            let Some(select_choose_input) = crate::common::select("\nDo you want to check the existence of the specified account so that you don't lose tokens?",
                     vec![ConfirmOptions::Yes{account_id: beneficiary_account_id.clone()}, ConfirmOptions::No],
-                    )
-                    .prompt()?;
+                    )? else {
+return Ok(None);
+};
             if let ConfirmOptions::Yes { account_id } = select_choose_input {
                 if crate::common::find_network_where_account_exist(
                     &context.global_context,
@@ -12,8 +12,12 @@ pub struct FunctionCallAction {
     /// How do you want to pass the function call arguments?
     function_args_type:
         crate::commands::contract::call_function::call_function_args_type::FunctionArgsType,
+    #[interactive_clap(skip_default_input_arg)]
     /// Enter the arguments to this function:
-    function_args: String,
+    function_args: crate::commands::contract::call_function::call_function_args_type::FunctionArgs,
+    #[interactive_clap(long)]
+    /// Parse the JSON arguments leniently (comments, trailing commas, single quotes and unquoted keys are allowed)
+    args_json5: bool,
     #[interactive_clap(named_arg)]
     /// Enter gas for function call
     prepaid_gas: PrepaidGas,
@@ -38,6 +42,7 @@ impl FunctionCallActionContext {
             crate::commands::contract::call_function::call_function_args_type::function_args(
                 scope.function_args.clone(),
                 scope.function_args_type.clone(),
+                scope.args_json5,
             )?;
         Ok(Self {
             global_context: previous_context.global_context,
@@ -60,6 +65,14 @@ impl FunctionCallAction {
         )
     }
 
+    fn input_function_args(
+        _context: &super::super::super::ConstructTransactionContext,
+    ) -> color_eyre::eyre::Result<
+        Option<crate::commands::contract::call_function::call_function_args_type::FunctionArgs>,
+    > {
+        crate::commands::contract::call_function::call_function_args_type::input_function_args()
+    }
+
     fn input_function_name(
         context: &super::super::super::ConstructTransactionContext,
     ) -> color_eyre::eyre::Result<Option<String>> {
@@ -161,8 +174,7 @@ impl DepositContext {
                 deposit: scope.deposit.clone().as_yoctonear(),
             },
         ));
-        let mut actions = previous_context.actions;
-        actions.push(action);
+        let actions = crate::tx_builder::append_action(previous_context.actions, action)?;
         Ok(Self(super::super::super::ConstructTransactionContext {
             global_context: previous_context.global_context,
             signer_account_id: previous_context.signer_account_id,
@@ -34,8 +34,7 @@ impl ContractFileContext {
         let action = near_primitives::transaction::Action::DeployContract(
             near_primitives::transaction::DeployContractAction { code },
         );
-        let mut actions = previous_context.actions;
-        actions.push(action);
+        let actions = crate::tx_builder::append_action(previous_context.actions, action)?;
         Ok(Self(super::super::super::ConstructTransactionContext {
             global_context: previous_context.global_context,
             signer_account_id: previous_context.signer_account_id,
@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use inquire::{CustomType, Select, Text};
+use inquire::{CustomType, Text};
 
 #[derive(Debug, Clone)]
 pub struct AccessKeyPermissionContext {
@@ -108,11 +108,12 @@ impl FunctionCallType {
             #[strum(to_string = "No, I allow it to call any functions on the specified contract")]
             No,
         }
-        let select_choose_input = Select::new(
+        let Some(select_choose_input) = crate::common::select(
             "Would you like the access key to be valid exclusively for calling specific functions on the contract?",
             vec![ConfirmOptions::Yes, ConfirmOptions::No],
-        )
-        .prompt()?;
+        )? else {
+return Ok(None);
+};
         if let ConfirmOptions::Yes = select_choose_input {
             let mut input_function_names =
                     Text::new("Enter a comma-separated list of function names that will be allowed to be called in a transaction signed by this access key:")
@@ -17,8 +17,7 @@ impl CreateAccountActionContext {
         let action = near_primitives::transaction::Action::CreateAccount(
             near_primitives::transaction::CreateAccountAction {},
         );
-        let mut actions = previous_context.actions;
-        actions.push(action);
+        let actions = crate::tx_builder::append_action(previous_context.actions, action)?;
         Ok(Self(super::super::super::ConstructTransactionContext {
             global_context: previous_context.global_context,
             signer_account_id: previous_context.signer_account_id,
@@ -21,8 +21,7 @@ impl TransferActionContext {
                 deposit: scope.amount_in_near.as_yoctonear(),
             },
         );
-        let mut actions = previous_context.actions;
-        actions.push(action);
+        let actions = crate::tx_builder::append_action(previous_context.actions, action)?;
         Ok(Self(super::super::super::ConstructTransactionContext {
             global_context: previous_context.global_context,
             signer_account_id: previous_context.signer_account_id,
@@ -4,6 +4,9 @@ pub mod add_action_3;
 pub mod add_action_last;
 pub mod skip_action;
 
+/// The receiver account ID that stands for the sender, e.g. for AddKey or DeployContract actions
+const SELF_RECEIVER: &str = "self";
+
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = crate::GlobalContext)]
 #[interactive_clap(output_context = ConstructTransactionContext)]
@@ -12,7 +15,7 @@ pub struct ConstructTransaction {
     /// What is the sender account ID?
     pub sender_account_id: crate::types::account_id::AccountId,
     #[interactive_clap(skip_default_input_arg)]
-    /// What is the receiver account ID?
+    /// What is the receiver account ID ("self" for the sender account)?
     pub receiver_account_id: crate::types::account_id::AccountId,
     #[interactive_clap(subcommand)]
     pub next_actions: self::add_action_1::NextAction,
@@ -31,10 +34,16 @@ impl ConstructTransactionContext {
         previous_context: crate::GlobalContext,
         scope: &<ConstructTransaction as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let signer_account_id: near_primitives::types::AccountId =
+            scope.sender_account_id.clone().into();
+        let receiver_account_id = resolve_receiver_account_id(
+            &signer_account_id,
+            scope.receiver_account_id.clone().into(),
+        );
         Ok(Self {
             global_context: previous_context,
-            signer_account_id: scope.sender_account_id.clone().into(),
-            receiver_account_id: scope.receiver_account_id.clone().into(),
+            signer_account_id,
+            receiver_account_id,
             actions: vec![],
         })
     }
@@ -55,7 +64,38 @@ impl ConstructTransaction {
     ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
         crate::common::input_non_signer_account_id_from_used_account_list(
             &context.config.credentials_home_dir,
-            "What is the receiver account ID?",
+            "What is the receiver account ID (\"self\" for the sender account)?",
         )
     }
 }
+
+/// The `receiver_account_id`, or the signer for [`SELF_RECEIVER`] (it is kept as "self" in the
+/// console command, so the command stays the same).
+fn resolve_receiver_account_id(
+    signer_account_id: &near_primitives::types::AccountId,
+    receiver_account_id: near_primitives::types::AccountId,
+) -> near_primitives::types::AccountId {
+    if receiver_account_id.as_str() == SELF_RECEIVER {
+        signer_account_id.clone()
+    } else {
+        receiver_account_id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn self_receiver_is_the_signer() {
+        let signer_account_id: near_primitives::types::AccountId = "alice.near".parse().unwrap();
+        assert_eq!(
+            resolve_receiver_account_id(&signer_account_id, "self".parse().unwrap()),
+            signer_account_id
+        );
+        assert_eq!(
+            resolve_receiver_account_id(&signer_account_id, "bob.near".parse().unwrap()).as_str(),
+            "bob.near"
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use color_eyre::eyre::WrapErr;
+
+mod template;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = GenerateFromTemplateContext)]
+pub struct GenerateFromTemplate {
+    #[interactive_clap(long)]
+    /// What is the path to the template file (YAML or JSON, example: transfer.yaml)?
+    template_file: crate::types::path_buf::PathBuf,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Write the borsh-serialized transaction to this file (default: print it as base64)
+    output_file: Option<crate::types::path_buf::PathBuf>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GenerateFromTemplateContext;
+
+impl GenerateFromTemplateContext {
+    pub fn from_previous_context(
+        _previous_context: crate::GlobalContext,
+        scope: &<GenerateFromTemplate as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let template_path: std::path::PathBuf = scope.template_file.clone().into();
+        let template_content = std::fs::read_to_string(&template_path)
+            .wrap_err_with(|| format!("Failed to read the file: {}", template_path.display()))?;
+        let template = template::TransactionTemplate::from_yaml(&template_content)
+            .wrap_err_with(|| format!("Invalid template: {}", template_path.display()))?;
+        let template_dir = template_path
+            .parent()
+            .unwrap_or_else(|| std::path::Path::new(""));
+        let transaction = template::to_transaction(&template, template_dir)?;
+
+        if template.nonce.is_none() || template.block_hash.is_none() {
+            eprintln!(
+                "WARNING: the template has no nonce or block_hash, so they are left empty and must be set before the transaction is signed."
+            );
+        }
+        eprintln!(
+            "Transaction hash to sign:\n{}\n",
+            hex::encode(transaction.get_hash_and_size().0)
+        );
+        match &scope.output_file {
+            Some(output_file) => {
+                let output_path: std::path::PathBuf = output_file.clone().into();
+                std::fs::write(&output_path, near_primitives::borsh::to_vec(&transaction)?)
+                    .wrap_err_with(|| {
+                        format!(
+                            "Failed to write the transaction to: {}",
+                            output_path.display()
+                        )
+                    })?;
+                eprintln!(
+                    "The unsigned transaction (borsh) is written to: {}",
+                    output_path.display()
+                );
+            }
+            None => println!(
+                "{}",
+                crate::types::transaction::TransactionAsBase64::from(transaction)
+            ),
+        }
+        Ok(Self)
+    }
+}
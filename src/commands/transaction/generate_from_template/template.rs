@@ -0,0 +1,273 @@
+use super::super::run_manifest::manifest::{validate_action, ManifestAction};
+
+/// The `$schema` of the templates this version of near CLI understands
+pub const SCHEMA_V1: &str = "near-cli-rs/transaction-template/v1";
+
+/// A transaction described with human-friendly actions, e.g.:
+///
+/// ```yaml
+/// $schema: near-cli-rs/transaction-template/v1
+/// signer: alice.testnet
+/// receiver: contract.alice.testnet
+/// public_key: ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp
+/// actions:
+///   - { type: transfer, amount: 1 NEAR }
+///   - { type: function_call, method: new, args: { owner_id: alice.testnet } }
+/// ```
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TransactionTemplate {
+    #[serde(rename = "$schema")]
+    pub schema: String,
+    pub signer: String,
+    pub receiver: String,
+    /// The access key of the signer that will sign the transaction
+    pub public_key: String,
+    #[serde(default)]
+    pub nonce: Option<u64>,
+    #[serde(default)]
+    pub block_hash: Option<String>,
+    pub actions: Vec<TemplateAction>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum TemplateAction {
+    CreateAccount,
+    Deploy {
+        /// The path is relative to the template file
+        wasm_file: std::path::PathBuf,
+    },
+    FunctionCall {
+        method: String,
+        #[serde(default)]
+        args: Option<serde_json::Value>,
+        #[serde(default)]
+        gas: Option<String>,
+        #[serde(default)]
+        deposit: Option<String>,
+    },
+    Transfer {
+        amount: String,
+    },
+    DeleteAccount {
+        beneficiary: String,
+    },
+}
+
+impl TransactionTemplate {
+    pub fn from_yaml(template: &str) -> color_eyre::eyre::Result<Self> {
+        // JSON is a subset of YAML, so JSON templates are accepted as well
+        let template: Self =
+            serde_yaml::from_str(template).map_err(|err| color_eyre::eyre::eyre!("{err}"))?;
+        if template.schema != SCHEMA_V1 {
+            color_eyre::eyre::bail!(
+                "$schema: unsupported template schema \"{}\" (supported: \"{SCHEMA_V1}\")",
+                template.schema
+            );
+        }
+        Ok(template)
+    }
+}
+
+/// Converts the template to an unsigned transaction; every problem is reported with the path of
+/// the offending field, e.g. `actions[1].amount`.
+pub fn to_transaction(
+    template: &TransactionTemplate,
+    template_dir: &std::path::Path,
+) -> color_eyre::eyre::Result<near_primitives::transaction::Transaction> {
+    let mut errors = Vec::new();
+    let mut parse = |path: &str, value: &str| {
+        let parsed = value.parse::<near_primitives::types::AccountId>();
+        if let Err(err) = &parsed {
+            errors.push(format!("{path}: invalid account ID <{value}>: {err}"));
+        }
+        parsed.ok()
+    };
+    let signer_id = parse("signer", &template.signer);
+    let receiver_id = parse("receiver", &template.receiver);
+    let public_key = template
+        .public_key
+        .parse::<near_crypto::PublicKey>()
+        .map_err(|err| errors.push(format!("public_key: {err}")))
+        .ok();
+    let block_hash = template
+        .block_hash
+        .as_deref()
+        .map_or(Ok(Default::default()), str::parse)
+        .map_err(|err| errors.push(format!("block_hash: {err}")))
+        .ok();
+
+    if template.actions.is_empty() {
+        errors.push("actions: at least one action is required".to_string());
+    }
+    let mut actions = Vec::new();
+    for (index, action) in template.actions.iter().enumerate() {
+        match to_action(action, &format!("actions[{index}]"), template_dir) {
+            Ok(action) => actions.push(action),
+            Err(action_errors) => errors.extend(action_errors),
+        }
+    }
+    if let Err(err) = crate::tx_builder::validate_actions(&actions) {
+        errors.push(format!("actions: {err}"));
+    }
+
+    match (signer_id, receiver_id, public_key, block_hash) {
+        (Some(signer_id), Some(receiver_id), Some(public_key), Some(block_hash))
+            if errors.is_empty() =>
+        {
+            Ok(near_primitives::transaction::Transaction::V0(
+                near_primitives::transaction::TransactionV0 {
+                    signer_id,
+                    public_key,
+                    nonce: template.nonce.unwrap_or_default(),
+                    receiver_id,
+                    block_hash,
+                    actions,
+                },
+            ))
+        }
+        _ => color_eyre::eyre::bail!("The template is invalid:\n  {}", errors.join("\n  ")),
+    }
+}
+
+fn to_action(
+    action: &TemplateAction,
+    path: &str,
+    template_dir: &std::path::Path,
+) -> Result<near_primitives::transaction::Action, Vec<String>> {
+    // The actions shared with the manifests of `run-manifest` are checked the same way
+    let manifest_action = match action {
+        TemplateAction::CreateAccount => {
+            return Ok(near_primitives::transaction::Action::CreateAccount(
+                near_primitives::transaction::CreateAccountAction {},
+            ))
+        }
+        TemplateAction::DeleteAccount { beneficiary } => {
+            return beneficiary
+                .parse()
+                .map(|beneficiary_id| {
+                    near_primitives::transaction::Action::DeleteAccount(
+                        near_primitives::transaction::DeleteAccountAction { beneficiary_id },
+                    )
+                })
+                .map_err(|err| {
+                    vec![format!(
+                        "{path}.beneficiary: invalid account ID <{beneficiary}>: {err}"
+                    )]
+                });
+        }
+        TemplateAction::Deploy { wasm_file } => ManifestAction::Deploy {
+            wasm_file: wasm_file.clone(),
+        },
+        TemplateAction::FunctionCall {
+            method,
+            args,
+            gas,
+            deposit,
+        } => ManifestAction::FunctionCall {
+            method_name: method.clone(),
+            args: args.clone(),
+            gas: gas.clone(),
+            deposit: deposit.clone(),
+        },
+        TemplateAction::Transfer { amount } => ManifestAction::Transfer {
+            amount: amount.clone(),
+        },
+    };
+    validate_action(&manifest_action, path, template_dir)
+        .map(|(action, _description)| action)
+        .map_err(|errors| errors.into_iter().map(rename_manifest_path).collect())
+}
+
+/// The manifest errors name the fields of the manifest (`actions[0].function_call.method_name`),
+/// while the template has them at the level of the action (`actions[0].method`).
+fn rename_manifest_path(error: String) -> String {
+    ["deploy.", "transfer."]
+        .into_iter()
+        .fold(error, |error, prefix| error.replacen(prefix, "", 1))
+        .replacen("function_call.method_name", "method", 1)
+        .replacen("function_call.", "", 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn template_is_converted_to_a_transaction() {
+        let template = TransactionTemplate::from_yaml(
+            r#"
+$schema: near-cli-rs/transaction-template/v1
+signer: alice.testnet
+receiver: app.alice.testnet
+public_key: ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp
+nonce: 7
+actions:
+  - type: create_account
+  - { type: transfer, amount: 1 NEAR }
+  - type: function_call
+    method: new
+    args: { owner_id: alice.testnet }
+    deposit: 0.1 NEAR
+"#,
+        )
+        .unwrap();
+        let transaction = to_transaction(&template, std::path::Path::new(".")).unwrap();
+        assert_eq!(transaction.signer_id().as_str(), "alice.testnet");
+        assert_eq!(transaction.receiver_id().as_str(), "app.alice.testnet");
+        assert_eq!(transaction.nonce(), 7);
+        assert_eq!(
+            transaction.actions()[1],
+            near_primitives::transaction::Action::Transfer(
+                near_primitives::transaction::TransferAction {
+                    deposit: 10u128.pow(24)
+                }
+            )
+        );
+        match &transaction.actions()[2] {
+            near_primitives::transaction::Action::FunctionCall(function_call) => {
+                assert_eq!(function_call.method_name, "new");
+                assert_eq!(function_call.args, br#"{"owner_id":"alice.testnet"}"#);
+                assert_eq!(function_call.deposit, 10u128.pow(23));
+            }
+            action => panic!("Unexpected action: {action:?}"),
+        }
+    }
+
+    #[test]
+    fn template_errors_point_at_the_field() {
+        let err = TransactionTemplate::from_yaml(
+            "$schema: near-cli-rs/transaction-template/v2\nsigner: a\nreceiver: b\npublic_key: c\nactions: []\n",
+        )
+        .unwrap_err();
+        assert!(err.to_string().starts_with("$schema: unsupported"), "{err}");
+
+        let template = TransactionTemplate::from_yaml(
+            r#"{
+  "$schema": "near-cli-rs/transaction-template/v1",
+  "signer": "alice.testnet",
+  "receiver": "invalid account",
+  "public_key": "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp",
+  "actions": [
+    { "type": "delete_account", "beneficiary": "bob.testnet" },
+    { "type": "transfer", "amount": "1 NEAR" },
+    { "type": "transfer", "amount": "1" },
+    { "type": "function_call", "method": "" }
+  ]
+}"#,
+        )
+        .unwrap();
+        let err = to_transaction(&template, std::path::Path::new("."))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("receiver: invalid account ID"), "{err}");
+        assert!(err.contains("actions[2].amount:"), "{err}");
+        assert!(
+            err.contains("actions[3].method: must not be empty"),
+            "{err}"
+        );
+        assert!(err.contains("actions: DeleteAccount"), "{err}");
+        assert!(!err.contains("signer"), "{err}");
+    }
+}
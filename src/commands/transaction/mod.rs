@@ -2,12 +2,16 @@
 use strum::{EnumDiscriminants, EnumIter, EnumMessage};
 
 pub mod construct_transaction;
+mod generate_from_template;
 mod print_transaction;
 mod reconstruct_transaction;
+mod run_manifest;
 mod send_meta_transaction;
 mod send_signed_transaction;
 pub mod sign_transaction;
+mod token_flows;
 mod view_status;
+mod wait_for_finality;
 
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(context = crate::GlobalContext)]
@@ -27,6 +31,16 @@ pub enum TransactionActions {
     ))]
     /// Execute function (contract method)
     ViewStatus(self::view_status::TransactionInfo),
+    #[strum_discriminants(strum(
+        message = "wait-for-finality        - Wait until a sent transaction reaches finality"
+    ))]
+    /// Wait until a sent transaction reaches finality
+    WaitForFinality(self::wait_for_finality::WaitForFinality),
+    #[strum_discriminants(strum(
+        message = "token-flows              - View where NEAR and fungible tokens went in a transaction"
+    ))]
+    /// View where NEAR and fungible tokens went in a transaction
+    TokenFlows(self::token_flows::TokenFlows),
     #[strum_discriminants(strum(
         message = "reconstruct-transaction  - Use any existing transaction from the chain to construct NEAR CLI command (helpful tool for re-submitting similar transactions)"
     ))]
@@ -37,6 +51,16 @@ pub enum TransactionActions {
     ))]
     /// Construct a new transaction
     ConstructTransaction(self::construct_transaction::ConstructTransaction),
+    #[strum_discriminants(strum(
+        message = "run-manifest             - Execute the transactions declared in a YAML or JSON manifest"
+    ))]
+    /// Execute the transactions declared in a YAML or JSON manifest
+    RunManifest(self::run_manifest::RunManifest),
+    #[strum_discriminants(strum(
+        message = "generate-from-template   - Generate an unsigned transaction from a YAML or JSON template"
+    ))]
+    /// Generate an unsigned transaction from a YAML or JSON template
+    GenerateFromTemplate(self::generate_from_template::GenerateFromTemplate),
     #[strum_discriminants(strum(
         message = "sign-transaction         - Sign previously prepared unsigned transaction"
     ))]
@@ -209,7 +209,8 @@ fn action_transformation(
                 add_action::call_function::CliFunctionCallAction {
                     function_name: Some(function_call_action.method_name),
                     function_args_type: Some(crate::commands::contract::call_function::call_function_args_type::FunctionArgsType::TextArgs),
-                    function_args: Some(String::from_utf8(function_call_action.args)?),
+                    function_args: Some(String::from_utf8(function_call_action.args)?.into()),
+                    args_json5: false,
                     prepaid_gas: Some(add_action::call_function::ClapNamedArgPrepaidGasForFunctionCallAction::PrepaidGas(
                         add_action::call_function::CliPrepaidGas {
                             gas: Some(near_gas::NearGas::from_gas(function_call_action.gas)),
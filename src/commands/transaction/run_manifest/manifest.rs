@@ -0,0 +1,391 @@
+use std::str::FromStr;
+
+use clap::Parser;
+use color_eyre::eyre::WrapErr;
+
+/// The default gas attached to the function calls
+const DEFAULT_FUNCTION_CALL_GAS_TGAS: u64 = 100;
+const DEFAULT_SIGN_WITH: &str = "sign-with-keychain";
+
+/// A declarative description of the transactions to execute, e.g.:
+///
+/// ```yaml
+/// network: testnet
+/// signer: alice.testnet
+/// transactions:
+///   - receiver: contract.alice.testnet
+///     actions:
+///       - deploy: { wasm_file: ./contract.wasm }
+///       - function_call: { method_name: new, args: { owner_id: alice.testnet } }
+///   - receiver: bob.testnet
+///     actions:
+///       - transfer: { amount: 1 NEAR }
+/// ```
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    pub network: String,
+    /// The default signer of the transactions
+    pub signer: String,
+    /// The signing command with its arguments, as on the command line (default: sign-with-keychain)
+    #[serde(default)]
+    pub sign_with: Option<String>,
+    pub transactions: Vec<ManifestTransaction>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestTransaction {
+    #[serde(default)]
+    pub signer: Option<String>,
+    pub receiver: String,
+    /// Each action is a map with a single key, e.g. `transfer: { amount: 1 NEAR }`
+    #[serde(with = "serde_yaml::with::singleton_map_recursive")]
+    pub actions: Vec<ManifestAction>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum ManifestAction {
+    Deploy {
+        /// The path is relative to the manifest file
+        wasm_file: std::path::PathBuf,
+    },
+    FunctionCall {
+        method_name: String,
+        #[serde(default)]
+        args: Option<serde_json::Value>,
+        #[serde(default)]
+        gas: Option<String>,
+        #[serde(default)]
+        deposit: Option<String>,
+    },
+    Transfer {
+        amount: String,
+    },
+}
+
+impl Manifest {
+    pub fn from_yaml(manifest: &str) -> color_eyre::eyre::Result<Self> {
+        // JSON is a subset of YAML, so JSON manifests are accepted as well
+        serde_yaml::from_str(manifest).map_err(|err| color_eyre::eyre::eyre!("{err}"))
+    }
+}
+
+/// A manifest checked against the configuration, ready to be executed
+#[derive(Debug, Clone)]
+pub struct Plan {
+    pub network_name: String,
+    pub network_args: crate::network_for_transaction::CliNetworkForTransactionArgs,
+    pub transactions: Vec<PlannedTransaction>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PlannedTransaction {
+    pub signer_id: near_primitives::types::AccountId,
+    pub receiver_id: near_primitives::types::AccountId,
+    pub actions: Vec<near_primitives::transaction::Action>,
+    pub action_descriptions: Vec<String>,
+}
+
+/// Checks the whole manifest before anything is sent; every problem is reported with the path of
+/// the offending field, e.g. `transactions[1].actions[0].transfer.amount`.
+pub fn validate(
+    manifest: &Manifest,
+    manifest_dir: &std::path::Path,
+    config: &crate::config::Config,
+) -> color_eyre::eyre::Result<Plan> {
+    let mut errors = Vec::new();
+
+    if !config.network_connection.contains_key(&manifest.network) {
+        errors.push(format!(
+            "network: unknown network <{}> (available: {})",
+            manifest.network,
+            config
+                .network_connection
+                .keys()
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+    let default_signer_id = parse_account_id("signer", &manifest.signer, &mut errors);
+
+    let network_args = match network_args(manifest) {
+        Ok(network_args) => Some(network_args),
+        Err(err) => {
+            errors.push(format!("sign_with: {err}"));
+            None
+        }
+    };
+
+    if manifest.transactions.is_empty() {
+        errors.push("transactions: at least one transaction is required".to_string());
+    }
+    let mut transactions = Vec::new();
+    for (transaction_index, transaction) in manifest.transactions.iter().enumerate() {
+        let path = format!("transactions[{transaction_index}]");
+        let signer_id = match &transaction.signer {
+            Some(signer) => parse_account_id(&format!("{path}.signer"), signer, &mut errors),
+            None => default_signer_id.clone(),
+        };
+        let receiver_id = parse_account_id(
+            &format!("{path}.receiver"),
+            &transaction.receiver,
+            &mut errors,
+        );
+
+        if transaction.actions.is_empty() {
+            errors.push(format!("{path}.actions: at least one action is required"));
+        }
+        let mut actions = Vec::new();
+        let mut action_descriptions = Vec::new();
+        for (action_index, action) in transaction.actions.iter().enumerate() {
+            let path = format!("{path}.actions[{action_index}]");
+            match validate_action(action, &path, manifest_dir) {
+                Ok((action, description)) => {
+                    actions.push(action);
+                    action_descriptions.push(description);
+                }
+                Err(action_errors) => errors.extend(action_errors),
+            }
+        }
+        if let Err(err) = crate::tx_builder::validate_actions(&actions) {
+            errors.push(format!("{path}.actions: {err}"));
+        }
+
+        if let (Some(signer_id), Some(receiver_id)) = (signer_id, receiver_id) {
+            transactions.push(PlannedTransaction {
+                signer_id,
+                receiver_id,
+                actions,
+                action_descriptions,
+            });
+        }
+    }
+
+    match network_args {
+        Some(network_args) if errors.is_empty() => Ok(Plan {
+            network_name: manifest.network.clone(),
+            network_args,
+            transactions,
+        }),
+        _ => color_eyre::eyre::bail!("The manifest is invalid:\n  {}", errors.join("\n  ")),
+    }
+}
+
+fn network_args(
+    manifest: &Manifest,
+) -> color_eyre::eyre::Result<crate::network_for_transaction::CliNetworkForTransactionArgs> {
+    let sign_with = shell_words::split(manifest.sign_with.as_deref().unwrap_or(DEFAULT_SIGN_WITH))?;
+    if !sign_with
+        .first()
+        .is_some_and(|command| command.starts_with("sign-with-"))
+    {
+        color_eyre::eyre::bail!("expected a sign-with-* command, e.g. \"{DEFAULT_SIGN_WITH}\"");
+    }
+    let args = std::iter::once("network-config".to_string())
+        .chain(std::iter::once(manifest.network.clone()))
+        .chain(sign_with)
+        .chain(std::iter::once("send".to_string()));
+    crate::network_for_transaction::CliNetworkForTransactionArgs::try_parse_from(args).map_err(
+        |err| {
+            let message = err.to_string();
+            color_eyre::eyre::eyre!(
+                "{}",
+                message
+                    .lines()
+                    .next()
+                    .unwrap_or_default()
+                    .trim_start_matches("error: ")
+            )
+        },
+    )
+}
+
+fn parse_account_id(
+    path: &str,
+    account_id: &str,
+    errors: &mut Vec<String>,
+) -> Option<near_primitives::types::AccountId> {
+    match account_id.parse() {
+        Ok(account_id) => Some(account_id),
+        Err(err) => {
+            errors.push(format!("{path}: invalid account ID <{account_id}>: {err}"));
+            None
+        }
+    }
+}
+
+pub fn validate_action(
+    action: &ManifestAction,
+    path: &str,
+    manifest_dir: &std::path::Path,
+) -> Result<(near_primitives::transaction::Action, String), Vec<String>> {
+    match action {
+        ManifestAction::Deploy { wasm_file } => {
+            let wasm_path = manifest_dir.join(wasm_file);
+            let code = std::fs::read(&wasm_path).map_err(|err| {
+                vec![format!(
+                    "{path}.deploy.wasm_file: failed to read {}: {err}",
+                    wasm_path.display()
+                )]
+            })?;
+            let description = format!("deploy {} ({} bytes)", wasm_file.display(), code.len());
+            Ok((
+                near_primitives::transaction::Action::DeployContract(
+                    near_primitives::transaction::DeployContractAction { code },
+                ),
+                description,
+            ))
+        }
+        ManifestAction::FunctionCall {
+            method_name,
+            args,
+            gas,
+            deposit,
+        } => {
+            let mut errors = Vec::new();
+            let path = format!("{path}.function_call");
+            if method_name.is_empty() {
+                errors.push(format!("{path}.method_name: must not be empty"));
+            }
+            let gas = match gas {
+                Some(gas) => near_gas::NearGas::from_str(gas)
+                    .map_err(|err| format!("{path}.gas: {err:?}"))
+                    .and_then(|gas| {
+                        if gas > near_gas::NearGas::from_tgas(300) {
+                            Err(format!("{path}.gas: must not exceed 300 TeraGas"))
+                        } else {
+                            Ok(gas)
+                        }
+                    }),
+                None => Ok(near_gas::NearGas::from_tgas(DEFAULT_FUNCTION_CALL_GAS_TGAS)),
+            }
+            .map_err(|err| errors.push(err))
+            .ok();
+            let deposit = parse_near_token(&format!("{path}.deposit"), deposit.as_deref())
+                .map_err(|err| errors.push(err))
+                .ok();
+            let args = args
+                .as_ref()
+                .map_or(Ok(Vec::new()), serde_json::to_vec)
+                .map_err(|err| errors.push(format!("{path}.args: {err}")))
+                .ok();
+
+            match (gas, deposit, args) {
+                (Some(gas), Some(deposit), Some(args)) if errors.is_empty() => {
+                    let description = format!(
+                        "call {method_name}({}) with {gas} and a deposit of {deposit}",
+                        String::from_utf8_lossy(&args)
+                    );
+                    Ok((
+                        near_primitives::transaction::Action::FunctionCall(Box::new(
+                            near_primitives::transaction::FunctionCallAction {
+                                method_name: method_name.clone(),
+                                args,
+                                gas: gas.as_gas(),
+                                deposit: deposit.as_yoctonear(),
+                            },
+                        )),
+                        description,
+                    ))
+                }
+                _ => Err(errors),
+            }
+        }
+        ManifestAction::Transfer { amount } => {
+            let amount = parse_near_token(&format!("{path}.transfer.amount"), Some(amount))
+                .map_err(|err| vec![err])?;
+            Ok((
+                near_primitives::transaction::Action::Transfer(
+                    near_primitives::transaction::TransferAction {
+                        deposit: amount.as_yoctonear(),
+                    },
+                ),
+                format!("transfer {amount}"),
+            ))
+        }
+    }
+}
+
+fn parse_near_token(
+    path: &str,
+    amount: Option<&str>,
+) -> Result<crate::types::near_token::NearToken, String> {
+    match amount {
+        Some(amount) => crate::types::near_token::NearToken::from_str(amount)
+            .wrap_err("expected an amount like \"1 NEAR\" or \"0.5 NEAR\"")
+            .map_err(|err| format!("{path}: {err}")),
+        None => Ok(crate::types::near_token::NearToken::from_yoctonear(0)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schema_errors_point_at_the_field() {
+        let err = Manifest::from_yaml(
+            "network: testnet\nsigner: alice.testnet\ntransactions:\n  - receiver: bob.testnet\n    actions:\n      - transfer: { amont: 1 NEAR }\n",
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("transactions[0].actions[0].transfer: unknown field `amont`"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn validate_manifest() {
+        let manifest = Manifest::from_yaml(
+            r#"
+network: testnet
+signer: alice.testnet
+transactions:
+  - receiver: bob.testnet
+    actions:
+      - transfer: { amount: 1 NEAR }
+      - function_call:
+          method_name: new
+          args: { owner_id: alice.testnet }
+  - signer: bob.testnet
+    receiver: "invalid account"
+    actions:
+      - transfer: { amount: 1 }
+      - deploy: { wasm_file: missing.wasm }
+"#,
+        )
+        .unwrap();
+        let config = crate::config::Config::default();
+        let err = validate(&manifest, std::path::Path::new("/nonexistent"), &config)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("transactions[1].receiver: invalid account ID"));
+        assert!(err.contains("transactions[1].actions[0].transfer.amount"));
+        assert!(err.contains("transactions[1].actions[1].deploy.wasm_file"));
+        assert!(!err.contains("transactions[0]"));
+
+        let manifest = Manifest {
+            transactions: manifest.transactions[..1].to_vec(),
+            ..manifest
+        };
+        let plan = validate(&manifest, std::path::Path::new("."), &config).unwrap();
+        assert_eq!(plan.network_name, "testnet");
+        let transaction = &plan.transactions[0];
+        assert_eq!(transaction.signer_id.as_str(), "alice.testnet");
+        assert_eq!(transaction.actions.len(), 2);
+        match &transaction.actions[1] {
+            near_primitives::transaction::Action::FunctionCall(function_call) => {
+                assert_eq!(function_call.args, br#"{"owner_id":"alice.testnet"}"#);
+                assert_eq!(
+                    function_call.gas,
+                    near_gas::NearGas::from_tgas(DEFAULT_FUNCTION_CALL_GAS_TGAS).as_gas()
+                );
+            }
+            action => panic!("Unexpected action: {action:?}"),
+        }
+    }
+}
@@ -0,0 +1,222 @@
+use color_eyre::eyre::WrapErr;
+
+pub(super) mod manifest;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = RunManifestContext)]
+pub struct RunManifest {
+    /// What is the path to the manifest file (YAML or JSON, example: deploy.yaml)?
+    manifest_file: crate::types::path_buf::PathBuf,
+    #[interactive_clap(long)]
+    /// Continue with the next transactions when a transaction fails
+    keep_going: bool,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Where to write the results (default: <manifest>.results.json next to the manifest)
+    results_file: Option<crate::types::path_buf::PathBuf>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+enum TransactionStatus {
+    Succeeded,
+    Failed,
+    Skipped,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+struct TransactionResult {
+    index: usize,
+    signer_id: near_primitives::types::AccountId,
+    receiver_id: near_primitives::types::AccountId,
+    status: TransactionStatus,
+    transaction_hash: Option<near_primitives::hash::CryptoHash>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+struct RunResults {
+    manifest: std::path::PathBuf,
+    network: String,
+    transactions: Vec<TransactionResult>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RunManifestContext;
+
+impl RunManifestContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<RunManifest as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let manifest_path: std::path::PathBuf = scope.manifest_file.clone().into();
+        let manifest_content = std::fs::read_to_string(&manifest_path)
+            .wrap_err_with(|| format!("Failed to read the file: {}", manifest_path.display()))?;
+        let manifest = manifest::Manifest::from_yaml(&manifest_content)
+            .wrap_err_with(|| format!("Invalid manifest: {}", manifest_path.display()))?;
+        let manifest_dir = manifest_path
+            .parent()
+            .unwrap_or_else(|| std::path::Path::new(""));
+        let plan = manifest::validate(&manifest, manifest_dir, &previous_context.config)?;
+        let results_path = scope.results_file.clone().map_or_else(
+            || manifest_path.with_extension("results.json"),
+            std::path::PathBuf::from,
+        );
+
+        print_plan(&plan);
+
+        let mut results = RunResults {
+            manifest: manifest_path,
+            network: plan.network_name.clone(),
+            transactions: Vec::new(),
+        };
+        let mut stopped = false;
+        for (index, transaction) in plan.transactions.iter().enumerate() {
+            let (status, transaction_hash, error) = if stopped {
+                (TransactionStatus::Skipped, None, None)
+            } else {
+                eprintln!(
+                    "\nTransaction #{}/{}: <{}> -> <{}>",
+                    index + 1,
+                    plan.transactions.len(),
+                    transaction.signer_id,
+                    transaction.receiver_id
+                );
+                let (transaction_hash, outcome) =
+                    execute_transaction(&previous_context, &plan, transaction);
+                match outcome {
+                    Ok(()) => (TransactionStatus::Succeeded, transaction_hash, None),
+                    Err(err) => {
+                        eprintln!("\nTransaction #{} failed: {err:#}", index + 1);
+                        stopped = !scope.keep_going;
+                        (
+                            TransactionStatus::Failed,
+                            transaction_hash,
+                            Some(format!("{err:#}")),
+                        )
+                    }
+                }
+            };
+            results.transactions.push(TransactionResult {
+                index,
+                signer_id: transaction.signer_id.clone(),
+                receiver_id: transaction.receiver_id.clone(),
+                status,
+                transaction_hash,
+                error,
+            });
+        }
+
+        std::fs::write(&results_path, serde_json::to_string_pretty(&results)?).wrap_err_with(
+            || format!("Failed to write the results to: {}", results_path.display()),
+        )?;
+        eprintln!("\nThe results are written to: {}", results_path.display());
+
+        print_summary(&results)?;
+        Ok(Self)
+    }
+}
+
+fn print_plan(plan: &manifest::Plan) {
+    eprintln!("\nExecution plan on network <{}>:", plan.network_name);
+    for (index, transaction) in plan.transactions.iter().enumerate() {
+        eprintln!(
+            "  #{} <{}> -> <{}>",
+            index + 1,
+            transaction.signer_id,
+            transaction.receiver_id
+        );
+        for description in &transaction.action_descriptions {
+            eprintln!("       - {description}");
+        }
+    }
+}
+
+/// Sends the transaction through the same network selection, signing and sending steps as the
+/// interactive commands, with the answers taken from the manifest.
+fn execute_transaction(
+    global_context: &crate::GlobalContext,
+    plan: &manifest::Plan,
+    transaction: &manifest::PlannedTransaction,
+) -> (
+    Option<near_primitives::hash::CryptoHash>,
+    color_eyre::eyre::Result<()>,
+) {
+    let transaction_hash = std::sync::Arc::new(std::sync::Mutex::new(None));
+
+    let action_context = crate::commands::ActionContext {
+        global_context: global_context.clone(),
+        interacting_with_account_ids: vec![
+            transaction.signer_id.clone(),
+            transaction.receiver_id.clone(),
+        ],
+        get_prepopulated_transaction_after_getting_network_callback: std::sync::Arc::new({
+            let prepopulated_transaction = crate::commands::PrepopulatedTransaction {
+                signer_id: transaction.signer_id.clone(),
+                receiver_id: transaction.receiver_id.clone(),
+                actions: transaction.actions.clone(),
+            };
+            move |_network_config| Ok(prepopulated_transaction.clone())
+        }),
+        on_before_signing_callback: std::sync::Arc::new(
+            |_prepolulated_unsinged_transaction, _network_config| Ok(()),
+        ),
+        on_before_sending_transaction_callback: std::sync::Arc::new({
+            let transaction_hash = transaction_hash.clone();
+            move |signed_transaction_or_signed_delegate_action, _network_config| {
+                if let crate::transaction_signature_options::SignedTransactionOrSignedDelegateAction::SignedTransaction(
+                    signed_transaction,
+                ) = signed_transaction_or_signed_delegate_action
+                {
+                    // The hash is not known before the wallet signs the transaction
+                    if signed_transaction.signature
+                        == near_crypto::Signature::empty(near_crypto::KeyType::ED25519)
+                    {
+                        return Ok(String::new());
+                    }
+                    *transaction_hash.lock().expect("Unexpected poisoned lock") =
+                        Some(signed_transaction.get_hash());
+                }
+                Ok(String::new())
+            }
+        }),
+        on_after_sending_transaction_callback: std::sync::Arc::new(
+            |_outcome_view, _network_config| Ok(()),
+        ),
+    };
+
+    let outcome = match <crate::network_for_transaction::NetworkForTransactionArgs as interactive_clap::FromCli>::from_cli(
+        Some(plan.network_args.clone()),
+        action_context,
+    ) {
+        interactive_clap::ResultFromCli::Ok(_) => Ok(()),
+        interactive_clap::ResultFromCli::Err(_, err) => Err(err),
+        interactive_clap::ResultFromCli::Cancel(_) | interactive_clap::ResultFromCli::Back => {
+            Err(color_eyre::eyre::eyre!("The transaction was cancelled"))
+        }
+    };
+    let transaction_hash = *transaction_hash.lock().expect("Unexpected poisoned lock");
+    (transaction_hash, outcome)
+}
+
+fn print_summary(results: &RunResults) -> crate::CliResult {
+    let count = |expected: fn(&TransactionStatus) -> bool| {
+        results
+            .transactions
+            .iter()
+            .filter(|result| expected(&result.status))
+            .count()
+    };
+    let succeeded = count(|status| matches!(status, TransactionStatus::Succeeded));
+    let failed = count(|status| matches!(status, TransactionStatus::Failed));
+    let skipped = count(|status| matches!(status, TransactionStatus::Skipped));
+    eprintln!("\n{succeeded} succeeded, {failed} failed, {skipped} skipped");
+    if failed > 0 {
+        color_eyre::eyre::bail!(
+            "{failed} of {} transactions failed",
+            results.transactions.len()
+        );
+    }
+    Ok(())
+}
@@ -1,5 +1,3 @@
-use inquire::Select;
-
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = super::SendMetaTransactionContext)]
 #[interactive_clap(output_context = RelayerAccountIdContext)]
@@ -103,11 +101,13 @@ impl RelayerAccountId {
                     #[strum(to_string = "No, I want to use this account name.")]
                     No,
                 }
-                let select_choose_input = Select::new(
+                let Some(select_choose_input) = crate::common::select(
                     "Do you want to enter another relayer account id?",
                     vec![ConfirmOptions::Yes, ConfirmOptions::No],
-                )
-                .prompt()?;
+                )?
+                else {
+                    return Ok(None);
+                };
                 if let ConfirmOptions::No = select_choose_input {
                     return Ok(Some(relayer_account_id));
                 }
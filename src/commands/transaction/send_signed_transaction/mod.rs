@@ -13,7 +13,7 @@ pub struct SignedTransaction {
 
 #[derive(Debug, Clone)]
 pub struct SignedTransactionContext {
-    config: crate::config::Config,
+    global_context: crate::GlobalContext,
     signed_transaction: near_primitives::transaction::SignedTransaction,
 }
 
@@ -23,7 +23,7 @@ impl SignedTransactionContext {
         scope: &<SignedTransaction as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
         Ok(Self {
-            config: previous_context.config,
+            global_context: previous_context,
             signed_transaction: scope.signed_action.inner.clone(),
         })
     }
@@ -14,6 +14,7 @@ pub struct Network {
 
 #[derive(Debug, Clone)]
 pub struct NetworkContext {
+    global_context: crate::GlobalContext,
     signed_transaction: near_primitives::transaction::SignedTransaction,
     network_config: crate::config::NetworkConfig,
 }
@@ -24,6 +25,7 @@ impl NetworkContext {
         scope: &<Network as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
         let network_config = previous_context
+            .global_context
             .config
             .network_connection
             .get(&scope.network_name)
@@ -31,6 +33,7 @@ impl NetworkContext {
             .clone();
 
         Ok(Self {
+            global_context: previous_context.global_context,
             signed_transaction: previous_context.signed_transaction,
             network_config,
         })
@@ -42,37 +45,76 @@ impl Network {
         context: &super::SignedTransactionContext,
     ) -> color_eyre::eyre::Result<Option<String>> {
         crate::common::input_network_name(
-            &context.config,
+            &context.global_context.config,
             &[context.signed_transaction.transaction.receiver_id().clone()],
         )
     }
 }
 
 #[derive(Debug, EnumDiscriminants, Clone, interactive_clap::InteractiveClap)]
-#[interactive_clap(input_context = NetworkContext)]
-#[interactive_clap(output_context = SubmitContext)]
+#[interactive_clap(context = NetworkContext)]
 #[strum_discriminants(derive(EnumMessage, EnumIter))]
 /// How would you like to proceed?
 pub enum Submit {
     #[strum_discriminants(strum(message = "send - Send the transaction to the network"))]
-    Send,
+    /// Send the transaction to the network
+    Send(Send),
+}
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = NetworkContext)]
+#[interactive_clap(output_context = SendContext)]
+pub struct Send {
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Warn if the block hash of the transaction is older than this number of blocks (default: 80)
+    max_block_lag: Option<u64>,
+    #[interactive_clap(long)]
+    /// Refuse to send the transaction if its block hash is older than --max-block-lag
+    strict: bool,
 }
 
 #[derive(Debug, Clone)]
-pub struct SubmitContext;
+pub struct SendContext;
 
-impl SubmitContext {
+impl SendContext {
     #[tracing::instrument(name = "Sending transaction ...", skip_all)]
     pub fn from_previous_context(
         previous_context: NetworkContext,
-        _scope: &<Submit as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
-    ) -> crate::CliResult {
-        let transaction_info =
-            crate::transaction_signature_options::send::sending_signed_transaction(
-                &previous_context.network_config,
-                &previous_context.signed_transaction,
-            )?;
+        scope: &<Send as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        crate::notification::with_transaction_completion_notification(
+            &previous_context.global_context,
+            previous_context.signed_transaction.get_hash(),
+            || {
+                crate::transaction_signature_options::send::check_block_hash_age(
+                    &previous_context.network_config,
+                    *previous_context.signed_transaction.transaction.block_hash(),
+                    scope.max_block_lag.unwrap_or(
+                        crate::transaction_signature_options::send::DEFAULT_MAX_BLOCK_LAG,
+                    ),
+                    scope.strict,
+                )?;
+
+                let transaction_info =
+                    crate::transaction_signature_options::send::sending_signed_transaction(
+                        &previous_context.network_config,
+                        &previous_context.signed_transaction,
+                    );
+                crate::audit_log::record(
+                    &previous_context.global_context.config,
+                    &previous_context.network_config,
+                    (&previous_context.signed_transaction).into(),
+                    crate::audit_log::sent_transaction_outcome(&transaction_info),
+                );
+                let transaction_info = transaction_info?;
 
-        crate::common::print_transaction_status(&transaction_info, &previous_context.network_config)
+                crate::common::print_transaction_status(
+                    &transaction_info,
+                    &previous_context.network_config,
+                )
+            },
+        )?;
+        Ok(Self)
     }
 }
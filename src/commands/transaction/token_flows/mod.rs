@@ -0,0 +1,419 @@
+use color_eyre::eyre::{ContextCompat, WrapErr};
+use prettytable::Table;
+use tracing_indicatif::span_ext::IndicatifSpanExt;
+
+use crate::common::JsonRpcClientExt;
+
+/// Gas refunds and refunds of failed receipts are sent by the protocol on behalf of this account.
+const SYSTEM_ACCOUNT_ID: &str = "system";
+const MINT_LABEL: &str = "(mint)";
+const BURN_LABEL: &str = "(burn)";
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = TokenFlowsContext)]
+pub struct TokenFlows {
+    /// Enter the hash of the transaction you need to analyze:
+    transaction_hash: crate::types::crypto_hash::CryptoHash,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network::Network,
+}
+
+#[derive(Clone)]
+pub struct TokenFlowsContext(crate::network::NetworkContext);
+
+impl TokenFlowsContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<TokenFlows as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let on_after_getting_network_callback: crate::network::OnAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let tx_hash: near_primitives::hash::CryptoHash = scope.transaction_hash.into();
+
+                move |network_config| {
+                    let outcome = get_transaction_outcome_with_receipts(network_config, tx_hash)?;
+                    let mut flows = near_flows(&outcome);
+                    flows.extend(ft_flows(&outcome.final_outcome.receipts_outcome));
+
+                    let ft_metadata = get_ft_metadata(network_config, &flows);
+                    display_token_flows(&tx_hash, &flows, &ft_metadata);
+                    let total_tokens_burnt: near_primitives::types::Balance =
+                        std::iter::once(&outcome.final_outcome.transaction_outcome)
+                            .chain(&outcome.final_outcome.receipts_outcome)
+                            .map(|outcome| outcome.outcome.tokens_burnt)
+                            .sum();
+                    eprintln!(
+                        "\nGas fees burnt: {} (paid by <{}>, unused prepaid gas is returned with the refunds from <{SYSTEM_ACCOUNT_ID}>)",
+                        near_token::NearToken::from_yoctonear(total_tokens_burnt),
+                        outcome.final_outcome.transaction.signer_id
+                    );
+                    Ok(())
+                }
+            });
+
+        Ok(Self(crate::network::NetworkContext {
+            config: previous_context.config,
+            interacting_with_account_ids: vec![],
+            on_after_getting_network_callback,
+        }))
+    }
+}
+
+impl From<TokenFlowsContext> for crate::network::NetworkContext {
+    fn from(item: TokenFlowsContext) -> Self {
+        item.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Asset {
+    Near,
+    FungibleToken(near_primitives::types::AccountId),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Flow {
+    asset: Asset,
+    from: String,
+    to: String,
+    amount: u128,
+}
+
+#[tracing::instrument(name = "Getting the receipts of the transaction", skip_all)]
+fn get_transaction_outcome_with_receipts(
+    network_config: &crate::config::NetworkConfig,
+    tx_hash: near_primitives::hash::CryptoHash,
+) -> color_eyre::eyre::Result<near_primitives::views::FinalExecutionOutcomeWithReceiptView> {
+    tracing::Span::current().pb_set_message(&format!("{tx_hash} ..."));
+    let response = network_config
+        .json_rpc_client()
+        .blocking_call(
+            near_jsonrpc_client::methods::EXPERIMENTAL_tx_status::RpcTransactionStatusRequest {
+                transaction_info:
+                    near_jsonrpc_client::methods::EXPERIMENTAL_tx_status::TransactionInfo::TransactionId {
+                        tx_hash,
+                        sender_account_id: "near".parse::<near_primitives::types::AccountId>()?,
+                    },
+                wait_until: near_primitives::views::TxExecutionStatus::Final,
+            },
+        )
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch query for view transaction on network <{}>",
+                network_config.network_name
+            )
+        })?;
+    match response
+        .final_execution_outcome
+        .wrap_err("The transaction has not been executed yet")?
+    {
+        near_primitives::views::FinalExecutionOutcomeViewEnum::FinalExecutionOutcomeWithReceipt(
+            outcome,
+        ) => Ok(outcome),
+        near_primitives::views::FinalExecutionOutcomeViewEnum::FinalExecutionOutcome(_) => {
+            Err(color_eyre::eyre::eyre!(
+                "The RPC server did not return the receipts of the transaction"
+            ))
+        }
+    }
+}
+
+fn attached_deposit(action: &near_primitives::views::ActionView) -> u128 {
+    match action {
+        near_primitives::views::ActionView::Transfer { deposit }
+        | near_primitives::views::ActionView::FunctionCall { deposit, .. } => *deposit,
+        _ => 0,
+    }
+}
+
+/// NEAR moved by the deposits of the actions, including the refunds sent by the system.
+fn near_flows(outcome: &near_primitives::views::FinalExecutionOutcomeWithReceiptView) -> Vec<Flow> {
+    let transaction = &outcome.final_outcome.transaction;
+    let mut flows = Vec::new();
+    // The receipt the transaction was converted to is normally among the receipts, otherwise the
+    // deposits are taken from the transaction itself
+    let converted_receipt_id = outcome
+        .final_outcome
+        .transaction_outcome
+        .outcome
+        .receipt_ids
+        .first();
+    if !outcome
+        .receipts
+        .iter()
+        .any(|receipt| Some(&receipt.receipt_id) == converted_receipt_id)
+    {
+        flows.extend(transaction.actions.iter().map(|action| Flow {
+            asset: Asset::Near,
+            from: transaction.signer_id.to_string(),
+            to: transaction.receiver_id.to_string(),
+            amount: attached_deposit(action),
+        }));
+    }
+    for receipt in &outcome.receipts {
+        if let near_primitives::views::ReceiptEnumView::Action { actions, .. } = &receipt.receipt {
+            flows.extend(actions.iter().map(|action| Flow {
+                asset: Asset::Near,
+                from: receipt.predecessor_id.to_string(),
+                to: receipt.receiver_id.to_string(),
+                amount: attached_deposit(action),
+            }));
+        }
+    }
+    flows.retain(|flow| flow.amount > 0);
+    flows
+}
+
+/// FT movements from the NEP-141 events (`EVENT_JSON:` logs) of the contracts.
+fn ft_flows(receipts_outcome: &[near_primitives::views::ExecutionOutcomeWithIdView]) -> Vec<Flow> {
+    receipts_outcome
+        .iter()
+        .flat_map(|receipt_outcome| {
+            receipt_outcome.outcome.logs.iter().flat_map(|log| {
+                parse_ft_event(log)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|(from, to, amount)| Flow {
+                        asset: Asset::FungibleToken(receipt_outcome.outcome.executor_id.clone()),
+                        from,
+                        to,
+                        amount,
+                    })
+            })
+        })
+        .collect()
+}
+
+/// Returns the (from, to, amount) movements of an `ft_transfer`, `ft_mint` or `ft_burn` event,
+/// or `None` if the log is not a valid NEP-141 event.
+fn parse_ft_event(log: &str) -> Option<Vec<(String, String, u128)>> {
+    let event: serde_json::Value =
+        serde_json::from_str(log.strip_prefix("EVENT_JSON:")?.trim()).ok()?;
+    if event["standard"].as_str()? != "nep141" {
+        return None;
+    }
+    let event_name = event["event"].as_str()?;
+    event["data"]
+        .as_array()?
+        .iter()
+        .map(|data| {
+            let amount = data["amount"].as_str()?.parse::<u128>().ok()?;
+            let (from, to) = match event_name {
+                "ft_transfer" => (
+                    data["old_owner_id"].as_str()?.to_string(),
+                    data["new_owner_id"].as_str()?.to_string(),
+                ),
+                "ft_mint" => (
+                    MINT_LABEL.to_string(),
+                    data["owner_id"].as_str()?.to_string(),
+                ),
+                "ft_burn" => (
+                    data["owner_id"].as_str()?.to_string(),
+                    BURN_LABEL.to_string(),
+                ),
+                _ => return None,
+            };
+            Some((from, to, amount))
+        })
+        .collect()
+}
+
+/// Total amount of every asset moved from one account to another.
+fn sum_flows(flows: &[Flow]) -> std::collections::BTreeMap<(&Asset, &str, &str), u128> {
+    let mut totals = std::collections::BTreeMap::new();
+    for flow in flows {
+        *totals
+            .entry((&flow.asset, flow.from.as_str(), flow.to.as_str()))
+            .or_insert(0) += flow.amount;
+    }
+    totals
+}
+
+/// Net change of every asset per account (outgoing flows are negative).
+fn net_flows(flows: &[Flow]) -> std::collections::BTreeMap<(&Asset, &str), i128> {
+    let mut net = std::collections::BTreeMap::new();
+    for flow in flows {
+        let amount = i128::try_from(flow.amount).unwrap_or(i128::MAX);
+        *net.entry((&flow.asset, flow.from.as_str())).or_insert(0) -= amount;
+        *net.entry((&flow.asset, flow.to.as_str())).or_insert(0) += amount;
+    }
+    net.retain(|_, amount| *amount != 0);
+    net
+}
+
+fn get_ft_metadata(
+    network_config: &crate::config::NetworkConfig,
+    flows: &[Flow],
+) -> std::collections::BTreeMap<
+    near_primitives::types::AccountId,
+    crate::types::ft_properties::FtMetadata,
+> {
+    flows
+        .iter()
+        .filter_map(|flow| match &flow.asset {
+            Asset::FungibleToken(ft_contract_account_id) => Some(ft_contract_account_id),
+            Asset::Near => None,
+        })
+        .collect::<std::collections::BTreeSet<_>>()
+        .into_iter()
+        .filter_map(|ft_contract_account_id| {
+            crate::types::ft_properties::params_ft_metadata(
+                ft_contract_account_id.clone(),
+                network_config,
+                near_primitives::types::Finality::Final.into(),
+            )
+            .ok()
+            .map(|ft_metadata| (ft_contract_account_id.clone(), ft_metadata))
+        })
+        .collect()
+}
+
+fn format_amount(
+    asset: &Asset,
+    amount: u128,
+    ft_metadata: &std::collections::BTreeMap<
+        near_primitives::types::AccountId,
+        crate::types::ft_properties::FtMetadata,
+    >,
+) -> String {
+    match asset {
+        Asset::Near => near_token::NearToken::from_yoctonear(amount).to_string(),
+        Asset::FungibleToken(ft_contract_account_id) => {
+            match ft_metadata.get(ft_contract_account_id) {
+                Some(crate::types::ft_properties::FtMetadata { symbol, decimals }) => {
+                    crate::types::ft_properties::FungibleToken::from_params_ft(
+                        amount,
+                        *decimals,
+                        symbol.clone(),
+                    )
+                    .to_string()
+                }
+                None => format!("{amount} (raw units of <{ft_contract_account_id}>)"),
+            }
+        }
+    }
+}
+
+fn asset_name(asset: &Asset) -> String {
+    match asset {
+        Asset::Near => "NEAR".to_string(),
+        Asset::FungibleToken(ft_contract_account_id) => ft_contract_account_id.to_string(),
+    }
+}
+
+fn display_token_flows(
+    tx_hash: &near_primitives::hash::CryptoHash,
+    flows: &[Flow],
+    ft_metadata: &std::collections::BTreeMap<
+        near_primitives::types::AccountId,
+        crate::types::ft_properties::FtMetadata,
+    >,
+) {
+    if flows.is_empty() {
+        eprintln!("\nThe transaction {tx_hash} did not move any NEAR or fungible tokens.");
+        return;
+    }
+
+    let mut table = Table::new();
+    table.set_titles(prettytable::row![Fg=>"Asset", "From", "To", "Amount"]);
+    for ((asset, from, to), amount) in sum_flows(flows) {
+        table.add_row(prettytable::row![
+            asset_name(asset),
+            from,
+            to,
+            format_amount(asset, amount, ft_metadata)
+        ]);
+    }
+    table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    eprintln!("\nToken flows of the transaction {tx_hash}:");
+    table.printstd();
+
+    let mut table = Table::new();
+    table.set_titles(prettytable::row![Fg=>"Asset", "Account", "Net change"]);
+    for ((asset, account), amount) in net_flows(flows) {
+        let formatted_amount = format_amount(asset, amount.unsigned_abs(), ft_metadata);
+        if amount < 0 {
+            table.add_row(
+                prettytable::row![asset_name(asset), account, Fr->format!("-{formatted_amount}")],
+            );
+        } else {
+            table.add_row(
+                prettytable::row![asset_name(asset), account, Fg->format!("+{formatted_amount}")],
+            );
+        }
+    }
+    table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    eprintln!("\nNet flows per account:");
+    table.printstd();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_ft_events() {
+        assert_eq!(
+            parse_ft_event(
+                r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_transfer","data":[{"old_owner_id":"alice.near","new_owner_id":"bob.near","amount":"100"},{"old_owner_id":"bob.near","new_owner_id":"carol.near","amount":"7","memo":"tip"}]}"#
+            ),
+            Some(vec![
+                ("alice.near".to_string(), "bob.near".to_string(), 100),
+                ("bob.near".to_string(), "carol.near".to_string(), 7),
+            ])
+        );
+        assert_eq!(
+            parse_ft_event(
+                r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_burn","data":[{"owner_id":"alice.near","amount":"5"}]}"#
+            ),
+            Some(vec![("alice.near".to_string(), BURN_LABEL.to_string(), 5)])
+        );
+        // Not FT events or not valid event JSON
+        assert_eq!(
+            parse_ft_event("Transfer 100 from alice.near to bob.near"),
+            None
+        );
+        assert_eq!(parse_ft_event("EVENT_JSON:{not json"), None);
+        assert_eq!(
+            parse_ft_event(
+                r#"EVENT_JSON:{"standard":"nep171","version":"1.0.0","event":"nft_mint","data":[{"owner_id":"alice.near","token_ids":["1"]}]}"#
+            ),
+            None
+        );
+        assert_eq!(
+            parse_ft_event(
+                r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_transfer","data":[{"old_owner_id":"alice.near","amount":"1"}]}"#
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn sum_and_net_flows() {
+        let flow = |asset: &Asset, from: &str, to: &str, amount| Flow {
+            asset: asset.clone(),
+            from: from.to_string(),
+            to: to.to_string(),
+            amount,
+        };
+        let usdt = Asset::FungibleToken("usdt.near".parse().unwrap());
+        let flows = vec![
+            flow(&Asset::Near, "alice.near", "dex.near", 10),
+            flow(&Asset::Near, "alice.near", "dex.near", 5),
+            flow(&Asset::Near, SYSTEM_ACCOUNT_ID, "alice.near", 2),
+            flow(&usdt, "dex.near", "alice.near", 30),
+        ];
+
+        let totals = sum_flows(&flows);
+        assert_eq!(totals[&(&Asset::Near, "alice.near", "dex.near")], 15);
+        assert_eq!(totals.len(), 3);
+
+        let net = net_flows(&flows);
+        assert_eq!(net[&(&Asset::Near, "alice.near")], -13);
+        assert_eq!(net[&(&Asset::Near, "dex.near")], 15);
+        assert_eq!(net[&(&Asset::Near, SYSTEM_ACCOUNT_ID)], -2);
+        assert_eq!(net[&(&usdt, "alice.near")], 30);
+    }
+}
@@ -0,0 +1,73 @@
+const DEFAULT_TIMEOUT_SECS: u64 = 60;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = WaitForFinalityContext)]
+pub struct WaitForFinality {
+    #[interactive_clap(long)]
+    /// What is the hash of the transaction to wait for?
+    transaction_hash: crate::types::crypto_hash::CryptoHash,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the signer account ID of the transaction?
+    signer_id: crate::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// How many seconds to wait for the finality (default: 60)
+    timeout_secs: Option<u64>,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: crate::network::Network,
+}
+
+#[derive(Clone)]
+pub struct WaitForFinalityContext(crate::network::NetworkContext);
+
+impl WaitForFinalityContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        scope: &<WaitForFinality as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let on_after_getting_network_callback: crate::network::OnAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let transaction_hash: near_primitives::hash::CryptoHash =
+                    scope.transaction_hash.into();
+                let signer_id: near_primitives::types::AccountId = scope.signer_id.clone().into();
+                let timeout = std::time::Duration::from_secs(
+                    scope.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS),
+                );
+
+                move |network_config| {
+                    crate::transaction_signature_options::send::wait_for_transaction_finality(
+                        network_config,
+                        transaction_hash,
+                        &signer_id,
+                        timeout,
+                    )
+                }
+            });
+
+        Ok(Self(crate::network::NetworkContext {
+            config: previous_context.config,
+            interacting_with_account_ids: vec![scope.signer_id.clone().into()],
+            on_after_getting_network_callback,
+        }))
+    }
+}
+
+impl From<WaitForFinalityContext> for crate::network::NetworkContext {
+    fn from(item: WaitForFinalityContext) -> Self {
+        item.0
+    }
+}
+
+impl WaitForFinality {
+    pub fn input_signer_id(
+        context: &crate::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+        crate::common::input_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "What is the signer account ID of the transaction?",
+        )
+    }
+}
@@ -0,0 +1,361 @@
+use color_eyre::eyre::{ContextCompat, WrapErr};
+use prettytable::Table;
+
+use crate::common::RpcQueryResponseExt;
+
+const WATCHLIST_SNAPSHOT_FILE_NAME: &str = "watchlist.json";
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::GlobalContext)]
+#[interactive_clap(output_context = WatchlistContext)]
+pub struct Watchlist;
+
+#[derive(Debug, Clone)]
+pub struct WatchlistContext;
+
+impl WatchlistContext {
+    pub fn from_previous_context(
+        previous_context: crate::GlobalContext,
+        _scope: &<Watchlist as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let config = previous_context.config;
+        if config.watchlist.is_empty() {
+            eprintln!(
+                "\nThe watchlist is empty. You can add an account to it with the command: near config watchlist add <account-id> --network <network-name>"
+            );
+            return Ok(Self);
+        }
+
+        let previous_snapshot = read_watchlist_snapshot();
+        let watchlist_state = get_watchlist_state(&config)?;
+
+        display_watchlist(&watchlist_state, &previous_snapshot);
+
+        let snapshot = watchlist_state
+            .iter()
+            .filter_map(|(watched_account, state, _)| match state {
+                Ok(state) => Some(WatchlistSnapshotEntry {
+                    watched_account: watched_account.clone(),
+                    state: *state,
+                }),
+                Err(_) => previous_snapshot
+                    .iter()
+                    .find(|entry| &entry.watched_account == watched_account)
+                    .cloned(),
+            })
+            .collect::<Vec<_>>();
+        if let Err(err) = write_watchlist_snapshot(&snapshot) {
+            eprintln!("Failed to save the watchlist state for the next run: {err:#}");
+        }
+        Ok(Self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+struct WatchedAccountState {
+    balance: near_token::NearToken,
+    access_keys_count: usize,
+}
+
+/// The last transaction of the account, from the indexer of the network connection
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum LastActivity {
+    /// The network connection has no `indexer_url`
+    NoIndexer,
+    NoTransactions,
+    At {
+        timestamp_nanosec: u64,
+    },
+    LookupFailed(String),
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct WatchlistSnapshotEntry {
+    #[serde(flatten)]
+    watched_account: crate::config::WatchedAccount,
+    #[serde(flatten)]
+    state: WatchedAccountState,
+}
+
+fn get_watchlist_snapshot_path() -> color_eyre::eyre::Result<std::path::PathBuf> {
+    let mut path = dirs::data_dir().wrap_err("Impossible to get your data dir!")?;
+    path.push("near-cli");
+    path.push(WATCHLIST_SNAPSHOT_FILE_NAME);
+    Ok(path)
+}
+
+fn read_watchlist_snapshot() -> Vec<WatchlistSnapshotEntry> {
+    get_watchlist_snapshot_path()
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|snapshot_buf| serde_json::from_str(&snapshot_buf).ok())
+        .unwrap_or_default()
+}
+
+fn write_watchlist_snapshot(snapshot: &[WatchlistSnapshotEntry]) -> crate::CliResult {
+    let path = get_watchlist_snapshot_path()?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, serde_json::to_string_pretty(snapshot)?)
+        .wrap_err_with(|| format!("Failed to write to file: {}", path.display()))
+}
+
+#[tracing::instrument(name = "Getting the state of the watchlist accounts ...", skip_all)]
+fn get_watchlist_state(
+    config: &crate::config::Config,
+) -> color_eyre::eyre::Result<
+    Vec<(
+        crate::config::WatchedAccount,
+        color_eyre::eyre::Result<WatchedAccountState>,
+        LastActivity,
+    )>,
+> {
+    let concurrency = 10;
+    let client = reqwest::Client::new();
+    crate::common::run_concurrently(
+        config.watchlist.iter().cloned(),
+        concurrency,
+        |watched_account| {
+            let client = &client;
+            async move {
+                let (state, last_activity) = futures::join!(
+                    get_watched_account_state(config, &watched_account),
+                    get_last_activity(client, config, &watched_account),
+                );
+                (watched_account, state, last_activity)
+            }
+        },
+    )
+}
+
+async fn get_last_activity(
+    client: &reqwest::Client,
+    config: &crate::config::Config,
+    watched_account: &crate::config::WatchedAccount,
+) -> LastActivity {
+    // A missing network connection is already reported by the state of the account
+    let Some(network_config) = config.network_connection.get(&watched_account.network_name) else {
+        return LastActivity::NoIndexer;
+    };
+    let Some(indexer_url) = &network_config.indexer_url else {
+        return LastActivity::NoIndexer;
+    };
+    let response = async {
+        let transactions_url =
+            crate::config::config_url::ConfigUrl::new(network_config, "indexer_url", indexer_url)
+                .join(&format!(
+                "v1/account/{}/txns?order=desc&per_page=1",
+                watched_account.account_id
+            ))?;
+        client
+            .get(transactions_url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<serde_json::Value>()
+            .await
+            .wrap_err("Unexpected response of the indexer")
+    }
+    .await;
+    match response {
+        Ok(response) => parse_last_activity(&response),
+        Err(err) => LastActivity::LookupFailed(format!("{err:#}")),
+    }
+}
+
+/// The first item of the `txns` of the Nearblocks API, the most recent one in the descending order.
+fn parse_last_activity(response: &serde_json::Value) -> LastActivity {
+    let Some(transactions) = response.get("txns").and_then(serde_json::Value::as_array) else {
+        return LastActivity::LookupFailed("Unexpected response of the indexer".to_string());
+    };
+    let Some(transaction) = transactions.first() else {
+        return LastActivity::NoTransactions;
+    };
+    let timestamp_nanosec = match transaction.get("block_timestamp") {
+        Some(serde_json::Value::Number(number)) => number.as_u64(),
+        Some(serde_json::Value::String(string)) => string.parse().ok(),
+        _ => None,
+    };
+    match timestamp_nanosec {
+        Some(timestamp_nanosec) => LastActivity::At { timestamp_nanosec },
+        None => LastActivity::LookupFailed(
+            "The indexer returned a transaction without a block timestamp".to_string(),
+        ),
+    }
+}
+
+async fn get_watched_account_state(
+    config: &crate::config::Config,
+    watched_account: &crate::config::WatchedAccount,
+) -> color_eyre::eyre::Result<WatchedAccountState> {
+    let network_config = config
+        .network_connection
+        .get(&watched_account.network_name)
+        .wrap_err_with(|| {
+            format!(
+                "Network connection \"{}\" is not found in config.toml",
+                watched_account.network_name
+            )
+        })?;
+    let json_rpc_client = network_config.json_rpc_client();
+
+    let account_view = crate::rpc_rate_limit::call(
+        &json_rpc_client,
+        near_jsonrpc_client::methods::query::RpcQueryRequest {
+            block_reference: near_primitives::types::Finality::Final.into(),
+            request: near_primitives::views::QueryRequest::ViewAccount {
+                account_id: watched_account.account_id.clone(),
+            },
+        },
+    )
+    .await
+    .wrap_err("Failed to fetch the account")?
+    .account_view()?;
+    let access_key_list = crate::rpc_rate_limit::call(
+        &json_rpc_client,
+        near_jsonrpc_client::methods::query::RpcQueryRequest {
+            block_reference: near_primitives::types::Finality::Final.into(),
+            request: near_primitives::views::QueryRequest::ViewAccessKeyList {
+                account_id: watched_account.account_id.clone(),
+            },
+        },
+    )
+    .await
+    .wrap_err("Failed to fetch the access keys")?
+    .access_key_list_view()?;
+
+    Ok(WatchedAccountState {
+        balance: near_token::NearToken::from_yoctonear(account_view.amount),
+        access_keys_count: access_key_list.keys.len(),
+    })
+}
+
+fn display_watchlist(
+    watchlist_state: &[(
+        crate::config::WatchedAccount,
+        color_eyre::eyre::Result<WatchedAccountState>,
+        LastActivity,
+    )],
+    previous_snapshot: &[WatchlistSnapshotEntry],
+) {
+    let mut table = Table::new();
+    table.set_titles(
+        prettytable::row![Fg=>"#", "Account", "Network", "Balance", "Access keys", "Last activity"],
+    );
+
+    for (index, (watched_account, state, last_activity)) in watchlist_state.iter().enumerate() {
+        let previous_state = previous_snapshot
+            .iter()
+            .find(|entry| &entry.watched_account == watched_account)
+            .map(|entry| entry.state);
+        let (balance_cell, access_keys_cell) = match state {
+            Ok(state) => (
+                balance_cell(state.balance, previous_state.map(|state| state.balance)),
+                access_keys_cell(
+                    state.access_keys_count,
+                    previous_state.map(|state| state.access_keys_count),
+                ),
+            ),
+            Err(err) => (
+                prettytable::Cell::new(&format!("{err:#}")).style_spec("Fr"),
+                prettytable::Cell::new(""),
+            ),
+        };
+        table.add_row(prettytable::Row::new(vec![
+            prettytable::Cell::new(&(index + 1).to_string()).style_spec("Fg"),
+            prettytable::Cell::new(watched_account.account_id.as_str()),
+            prettytable::Cell::new(&watched_account.network_name),
+            balance_cell,
+            access_keys_cell,
+            last_activity_cell(last_activity),
+        ]));
+    }
+
+    table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    eprintln!();
+    table.printstd();
+}
+
+fn balance_cell(
+    balance: near_token::NearToken,
+    previous_balance: Option<near_token::NearToken>,
+) -> prettytable::Cell {
+    let balance_str = crate::types::near_token::NearToken::from(balance).to_string();
+    match previous_balance {
+        Some(previous_balance) if previous_balance < balance => prettytable::Cell::new(&format!(
+            "{balance_str} (+{})",
+            crate::types::near_token::NearToken::from(balance.saturating_sub(previous_balance))
+        ))
+        .style_spec("Fy"),
+        Some(previous_balance) if previous_balance > balance => prettytable::Cell::new(&format!(
+            "{balance_str} (-{})",
+            crate::types::near_token::NearToken::from(previous_balance.saturating_sub(balance))
+        ))
+        .style_spec("Fy"),
+        _ => prettytable::Cell::new(&balance_str),
+    }
+}
+
+fn access_keys_cell(
+    access_keys_count: usize,
+    previous_access_keys_count: Option<usize>,
+) -> prettytable::Cell {
+    match previous_access_keys_count {
+        Some(previous_count) if previous_count != access_keys_count => {
+            prettytable::Cell::new(&format!(
+                "{access_keys_count} ({:+})",
+                access_keys_count as i64 - previous_count as i64
+            ))
+            .style_spec("Fy")
+        }
+        _ => prettytable::Cell::new(&access_keys_count.to_string()),
+    }
+}
+
+fn last_activity_cell(last_activity: &LastActivity) -> prettytable::Cell {
+    match last_activity {
+        LastActivity::NoIndexer => prettytable::Cell::new("no indexer"),
+        LastActivity::NoTransactions => prettytable::Cell::new("no transactions"),
+        LastActivity::At { timestamp_nanosec } => prettytable::Cell::new(
+            &chrono::DateTime::from_timestamp((timestamp_nanosec / 1_000_000_000) as i64, 0)
+                .map_or_else(
+                    || timestamp_nanosec.to_string(),
+                    |date_time| date_time.format("%Y-%m-%d %H:%M UTC").to_string(),
+                ),
+        ),
+        LastActivity::LookupFailed(err) => {
+            prettytable::Cell::new(&format!("lookup failed: {err}")).style_spec("Fr")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn last_activity_from_the_indexer() {
+        let response = serde_json::json!({
+            "txns": [{ "transaction_hash": "9abQ1c", "block_timestamp": "1700000000000000000" }]
+        });
+        assert_eq!(
+            parse_last_activity(&response),
+            LastActivity::At {
+                timestamp_nanosec: 1_700_000_000_000_000_000
+            }
+        );
+        assert_eq!(
+            last_activity_cell(&parse_last_activity(&response)).get_content(),
+            "2023-11-14 22:13 UTC"
+        );
+        assert_eq!(
+            parse_last_activity(&serde_json::json!({ "txns": [] })),
+            LastActivity::NoTransactions
+        );
+        assert!(matches!(
+            parse_last_activity(&serde_json::json!({ "error": "rate limited" })),
+            LastActivity::LookupFailed(_)
+        ));
+    }
+}
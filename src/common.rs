@@ -6,7 +6,7 @@ use std::str::FromStr;
 
 use color_eyre::eyre::{ContextCompat, WrapErr};
 use color_eyre::owo_colors::OwoColorize;
-use futures::{StreamExt, TryStreamExt};
+use futures::StreamExt;
 use prettytable::Table;
 use rust_decimal::prelude::FromPrimitive;
 use tracing_indicatif::span_ext::IndicatifSpanExt;
@@ -51,6 +51,10 @@ impl std::fmt::Display for OutputFormat {
     }
 }
 
+impl interactive_clap::ToCli for OutputFormat {
+    type CliVariant = OutputFormat;
+}
+
 #[derive(Debug, Clone)]
 pub struct BlockHashAsBase58 {
     pub inner: near_primitives::hash::CryptoHash,
@@ -127,6 +131,7 @@ pub struct AccountTransferAllowance {
     account_id: near_primitives::types::AccountId,
     account_liquid_balance: near_token::NearToken,
     account_locked_balance: near_token::NearToken,
+    storage_usage: near_primitives::types::StorageUsage,
     storage_stake: near_token::NearToken,
     pessimistic_transaction_fee: near_token::NearToken,
 }
@@ -144,6 +149,45 @@ impl std::fmt::Display for AccountTransferAllowance {
 }
 
 impl AccountTransferAllowance {
+    pub fn new(
+        account_id: near_primitives::types::AccountId,
+        account_view: &near_primitives::views::AccountView,
+        storage_amount_per_byte: near_primitives::types::Balance,
+        pessimistic_transaction_fee: near_token::NearToken,
+    ) -> Self {
+        Self {
+            account_id,
+            account_liquid_balance: near_token::NearToken::from_yoctonear(account_view.amount),
+            account_locked_balance: near_token::NearToken::from_yoctonear(account_view.locked),
+            storage_usage: account_view.storage_usage,
+            storage_stake: near_token::NearToken::from_yoctonear(
+                u128::from(account_view.storage_usage).saturating_mul(storage_amount_per_byte),
+            ),
+            pessimistic_transaction_fee,
+        }
+    }
+
+    pub fn account_liquid_balance(&self) -> near_token::NearToken {
+        self.account_liquid_balance
+    }
+
+    pub fn account_locked_balance(&self) -> near_token::NearToken {
+        self.account_locked_balance
+    }
+
+    pub fn storage_usage(&self) -> near_primitives::types::StorageUsage {
+        self.storage_usage
+    }
+
+    pub fn storage_stake(&self) -> near_token::NearToken {
+        self.storage_stake
+    }
+
+    pub fn pessimistic_transaction_fee(&self) -> near_token::NearToken {
+        self.pessimistic_transaction_fee
+    }
+
+    /// The part of the storage stake that the locked balance does not cover.
     pub fn liquid_storage_stake(&self) -> near_token::NearToken {
         self.storage_stake
             .saturating_sub(self.account_locked_balance)
@@ -173,6 +217,7 @@ pub async fn get_account_transfer_allowance(
                 account_id,
                 account_liquid_balance: near_token::NearToken::from_near(0),
                 account_locked_balance: near_token::NearToken::from_near(0),
+                storage_usage: 0,
                 storage_stake: near_token::NearToken::from_near(0),
                 pessimistic_transaction_fee: near_token::NearToken::from_near(0),
             });
@@ -190,30 +235,26 @@ pub async fn get_account_transfer_allowance(
             ));
         }
     };
-    let storage_amount_per_byte = network_config
-        .json_rpc_client()
-        .call(
-            near_jsonrpc_client::methods::EXPERIMENTAL_protocol_config::RpcProtocolConfigRequest {
-                block_reference: near_primitives::types::Finality::Final.into(),
-            },
-        )
-        .await
-        .wrap_err("RpcError")?
-        .runtime_config
-        .storage_amount_per_byte;
+    let storage_amount_per_byte = crate::rpc_rate_limit::call(
+        &network_config.json_rpc_client(),
+        near_jsonrpc_client::methods::EXPERIMENTAL_protocol_config::RpcProtocolConfigRequest {
+            block_reference: near_primitives::types::Finality::Final.into(),
+        },
+    )
+    .await
+    .wrap_err("RpcError")?
+    .runtime_config
+    .storage_amount_per_byte;
 
-    Ok(AccountTransferAllowance {
+    Ok(AccountTransferAllowance::new(
         account_id,
-        account_liquid_balance: near_token::NearToken::from_yoctonear(account_view.amount),
-        account_locked_balance: near_token::NearToken::from_yoctonear(account_view.locked),
-        storage_stake: near_token::NearToken::from_yoctonear(
-            u128::from(account_view.storage_usage) * storage_amount_per_byte,
-        ),
+        &account_view,
+        storage_amount_per_byte,
         // pessimistic_transaction_fee = 10^21 - this value is set temporarily
         // In the future, its value will be calculated by the function: fn tx_cost(...)
         // https://github.com/near/nearcore/blob/8a377fda0b4ce319385c463f1ae46e4b0b29dcd9/runtime/runtime/src/config.rs#L178-L232
-        pessimistic_transaction_fee: near_token::NearToken::from_millinear(1),
-    })
+        near_token::NearToken::from_millinear(1),
+    ))
 }
 
 #[tracing::instrument(name = "Account access key verification ...", skip_all)]
@@ -322,12 +363,11 @@ pub fn ask_if_different_account_id_wanted() -> color_eyre::eyre::Result<bool> {
         #[strum(to_string = "No, I want to keep using this name for account ID.")]
         No,
     }
-    let select_choose_input = Select::new(
+    let select_choose_input = select(
         "Do you want to enter a different name for the new account ID?",
         vec![ConfirmOptions::Yes, ConfirmOptions::No],
-    )
-    .prompt()?;
-    Ok(select_choose_input == ConfirmOptions::Yes)
+    )?;
+    Ok(select_choose_input == Some(ConfirmOptions::Yes))
 }
 
 #[tracing::instrument(name = "Getting account status information for", skip_all)]
@@ -443,8 +483,7 @@ async fn view_account(
         );
     }
 
-    json_rpc_client
-        .call(query_view_method_request)
+    crate::rpc_rate_limit::call(json_rpc_client, query_view_method_request)
         .await
         .inspect_err(|err| match err {
             near_jsonrpc_client::errors::JsonRpcError::TransportError(transport_error) => {
@@ -485,13 +524,12 @@ fn need_check_account(message: String) -> bool {
         #[strum(to_string = "No, I want to skip the check and use the specified account ID.")]
         No,
     }
-    let select_choose_input = Select::new(
+    let select_choose_input = select(
         &format!("{message}\nDo you want to try again?"),
         vec![ConfirmOptions::Yes, ConfirmOptions::No],
     )
-    .prompt()
-    .unwrap_or(ConfirmOptions::Yes);
-    select_choose_input == ConfirmOptions::Yes
+    .unwrap_or(Some(ConfirmOptions::Yes));
+    select_choose_input != Some(ConfirmOptions::No)
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -505,6 +543,78 @@ pub struct KeyPairProperties {
     pub secret_keypair_str: String,
 }
 
+impl KeyPairProperties {
+    pub fn public_key(&self) -> color_eyre::eyre::Result<near_crypto::PublicKey> {
+        self.public_key_str
+            .parse()
+            .wrap_err_with(|| format!("Invalid public key: {}", self.public_key_str))
+    }
+
+    pub fn secret_key(&self) -> color_eyre::eyre::Result<near_crypto::SecretKey> {
+        self.secret_keypair_str
+            .parse()
+            .wrap_err("Invalid private key of the key pair")
+    }
+
+    pub fn key_type(&self) -> color_eyre::eyre::Result<near_crypto::KeyType> {
+        Ok(self.public_key()?.key_type())
+    }
+}
+
+/// The public properties of the key pair; the seed phrase and the private key are not printed.
+impl std::fmt::Display for KeyPairProperties {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Implicit account ID: {}", self.implicit_account_id)?;
+        writeln!(f, "Public key:          {}", self.public_key_str)?;
+        write!(f, "HD path:             {}", self.seed_phrase_hd_path)
+    }
+}
+
+/// The key files of the legacy keychain (`{"account_id", "public_key", "private_key"}`, or
+/// `"secret_key"` in the oldest ones), as well as the files with all the properties.
+impl TryFrom<serde_json::Value> for KeyPairProperties {
+    type Error = color_eyre::eyre::Report;
+
+    fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {
+        if let Ok(key_pair_properties) = serde_json::from_value::<Self>(value.clone()) {
+            return Ok(key_pair_properties);
+        }
+        let field = |name: &str| value.get(name).and_then(serde_json::Value::as_str);
+        let secret_key: near_crypto::SecretKey = field("private_key")
+            .or_else(|| field("secret_key"))
+            .wrap_err("The key file has no \"private_key\"")?
+            .parse()
+            .wrap_err("The key file has an invalid \"private_key\"")?;
+        let public_key = secret_key.public_key();
+        if let Some(public_key_str) = field("public_key") {
+            let file_public_key: near_crypto::PublicKey = public_key_str
+                .parse()
+                .wrap_err("The key file has an invalid \"public_key\"")?;
+            if file_public_key != public_key {
+                color_eyre::eyre::bail!(
+                    "The \"public_key\" of the key file ({file_public_key}) does not belong to its \"private_key\" (its public key is {public_key})"
+                );
+            }
+        }
+        let implicit_account_id = match &public_key {
+            near_crypto::PublicKey::ED25519(public_key) => {
+                near_primitives::utils::derive_near_implicit_account_id(public_key)
+            }
+            near_crypto::PublicKey::SECP256K1(public_key) => {
+                near_primitives::utils::derive_eth_implicit_account_id(public_key)
+            }
+        };
+        Ok(Self {
+            // The key was not derived from a seed phrase, the default HD path is kept
+            seed_phrase_hd_path: "m/44'/397'/0'".parse()?,
+            master_seed_phrase: String::new(),
+            implicit_account_id,
+            public_key_str: public_key.to_string(),
+            secret_keypair_str: secret_key.to_string(),
+        })
+    }
+}
+
 pub fn get_key_pair_properties_from_seed_phrase(
     seed_phrase_hd_path: crate::types::slip10::BIP32Path,
     master_seed_phrase: String,
@@ -1203,7 +1313,9 @@ pub fn convert_invalid_tx_error_to_cli_result(
     }
 }
 
-fn get_near_usd_exchange_rate(coingecko_url: &url::Url) -> color_eyre::Result<f64> {
+fn get_near_usd_exchange_rate(
+    coingecko_url: &crate::config::config_url::ConfigUrl,
+) -> color_eyre::Result<f64> {
     #[derive(serde::Deserialize)]
     struct CoinGeckoResponse {
         near: CoinGeckoNearData,
@@ -1253,20 +1365,18 @@ pub fn print_transaction_status(
     transaction_info: &near_primitives::views::FinalExecutionOutcomeView,
     network_config: &crate::config::NetworkConfig,
 ) -> crate::CliResult {
-    let near_usd_exchange_rate: Option<Result<f64, color_eyre::eyre::Error>> = network_config
-        .coingecko_url
-        .as_ref()
-        .map(get_near_usd_exchange_rate);
+    let near_usd_exchange_rate: Option<Result<f64, color_eyre::eyre::Error>> =
+        network_config.coingecko_url.as_ref().map(|coingecko_url| {
+            get_near_usd_exchange_rate(&crate::config::config_url::ConfigUrl::new(
+                network_config,
+                "coingecko_url",
+                coingecko_url,
+            ))
+        });
 
     eprintln!("\n--- Logs ---------------------------"); // "\n" - required for correct display after {span_name}
 
-    let mut total_gas_burnt = transaction_info.transaction_outcome.outcome.gas_burnt;
-    let mut total_tokens_burnt = transaction_info.transaction_outcome.outcome.tokens_burnt;
-
     for receipt in transaction_info.receipts_outcome.iter() {
-        total_gas_burnt += receipt.outcome.gas_burnt;
-        total_tokens_burnt += receipt.outcome.tokens_burnt;
-
         if receipt.outcome.logs.is_empty() {
             eprintln!("Logs [{}]:   No logs", receipt.outcome.executor_id);
         } else {
@@ -1308,13 +1418,17 @@ pub fn print_transaction_status(
         }
     };
 
+    let fee_summary = match get_transaction_receipts(transaction_info, network_config) {
+        Some(outcome) => {
+            crate::fee_summary::FeeSummary::new(&outcome.final_outcome, Some(&outcome.receipts))
+        }
+        None => crate::fee_summary::FeeSummary::new(transaction_info, None),
+    };
     eprintln!();
-    eprintln!("Gas burned: {}", NearGas::from_gas(total_gas_burnt));
     eprintln!(
-        "Transaction fee: {}{}",
-        crate::types::near_token::NearToken::from_yoctonear(total_tokens_burnt),
+        "Fee paid: {fee_summary}{}",
         match near_usd_exchange_rate {
-            Some(Ok(exchange_rate)) => calculate_usd_amount(total_tokens_burnt, exchange_rate).map_or_else(
+            Some(Ok(exchange_rate)) => calculate_usd_amount(fee_summary.tokens_burnt, exchange_rate).map_or_else(
                 || format!(" (USD equivalent is too big to be displayed, using ${:.2} USD/NEAR exchange rate)", exchange_rate),
                 |amount| format!(" (approximately ${:.8} USD, using ${:.2} USD/NEAR exchange rate)", amount, exchange_rate)
             ),
@@ -1323,20 +1437,98 @@ pub fn print_transaction_status(
         }
     );
 
-    eprintln!("Transaction ID: {id}\nTo see the transaction in the transaction explorer, please open this url in your browser:\n{path}{id}\n",
-        id=transaction_info.transaction_outcome.id,
-        path=network_config.explorer_transaction_url
+    eprintln!(
+        "Transaction ID: {id}\n{}\n",
+        explorer_transaction_message(network_config, &transaction_info.transaction_outcome.id),
+        id = transaction_info.transaction_outcome.id,
     );
 
     return_value
 }
 
+/// The outcome of the executed transaction with its receipts (to tell the refunds in the fee),
+/// `None` if the RPC server does not return them. It does not wait for the refunds: when they are
+/// not executed yet, the fee is printed without them.
+#[tracing::instrument(name = "Getting the refunds of the transaction ...", skip_all)]
+fn get_transaction_receipts(
+    transaction_info: &near_primitives::views::FinalExecutionOutcomeView,
+    network_config: &crate::config::NetworkConfig,
+) -> Option<near_primitives::views::FinalExecutionOutcomeWithReceiptView> {
+    let response = network_config
+        .json_rpc_client()
+        .blocking_call(
+            near_jsonrpc_client::methods::EXPERIMENTAL_tx_status::RpcTransactionStatusRequest {
+                transaction_info:
+                    near_jsonrpc_client::methods::EXPERIMENTAL_tx_status::TransactionInfo::TransactionId {
+                        tx_hash: transaction_info.transaction_outcome.id,
+                        sender_account_id: transaction_info.transaction.signer_id.clone(),
+                    },
+                // Already reached by the sent transaction, so this does not wait
+                wait_until: near_primitives::views::TxExecutionStatus::ExecutedOptimistic,
+            },
+        )
+        .inspect_err(|err| tracing::warn!("Failed to fetch the receipts of the transaction: {err}"))
+        .ok()?;
+    match response.final_execution_outcome? {
+        near_primitives::views::FinalExecutionOutcomeViewEnum::FinalExecutionOutcomeWithReceipt(
+            outcome,
+        ) => Some(outcome),
+        near_primitives::views::FinalExecutionOutcomeViewEnum::FinalExecutionOutcome(_) => None,
+    }
+}
+
+/// The explorer link to the transaction, or why it cannot be built from the config.
+pub fn explorer_transaction_message(
+    network_config: &crate::config::NetworkConfig,
+    transaction_hash: &CryptoHash,
+) -> String {
+    match crate::config::config_url::ConfigUrl::new(
+        network_config,
+        "explorer_transaction_url",
+        &network_config.explorer_transaction_url,
+    )
+    .transaction(&transaction_hash.to_string())
+    {
+        Ok(url) => format!("To see the transaction in the transaction explorer, please open this url in your browser:\n{url}"),
+        Err(err) => format!("The link to the transaction explorer is not available. {err}"),
+    }
+}
+
+/// Set with `--no-keychain`: the access keys are printed instead of being saved.
+static NO_KEYCHAIN: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+pub fn set_no_keychain(no_keychain: bool) {
+    NO_KEYCHAIN.store(no_keychain, std::sync::atomic::Ordering::Relaxed);
+}
+
+pub fn is_no_keychain() -> bool {
+    NO_KEYCHAIN.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+/// Prints the key pair to stdout (with `--no-keychain`) instead of writing it anywhere.
+fn print_unsaved_access_key(key_pair_properties_buf: &str) -> color_eyre::eyre::Result<String> {
+    let key_pair_properties = serde_json::from_str::<serde_json::Value>(key_pair_properties_buf)
+        .and_then(|value| serde_json::to_string_pretty(&value))
+        .unwrap_or_else(|_| key_pair_properties_buf.to_string());
+    println!("{key_pair_properties}");
+    eprintln!(
+        "{}",
+        "WARNING: --no-keychain: the access key printed above is NOT saved in the keychain or on disk. Copy it now, otherwise it will be lost."
+            .red()
+            .bold()
+    );
+    Ok("The data for the access key is not saved (--no-keychain)".to_string())
+}
+
 pub fn save_access_key_to_keychain(
     network_config: crate::config::NetworkConfig,
     key_pair_properties_buf: &str,
     public_key_str: &str,
     account_id: &str,
 ) -> color_eyre::eyre::Result<String> {
+    if is_no_keychain() {
+        return print_unsaved_access_key(key_pair_properties_buf);
+    }
     let service_name = std::borrow::Cow::Owned(format!(
         "near-{}-{}",
         network_config.network_name, account_id
@@ -1350,28 +1542,74 @@ pub fn save_access_key_to_keychain(
     Ok("The data for the access key is saved in the keychain".to_string())
 }
 
+/// The name of the legacy keychain file of an access key: `ed25519_<key>.json` (`:` is not
+/// allowed in Windows file names).
+pub fn key_file_name(public_key_str: &str) -> String {
+    format!("{}.json", public_key_str.replace(':', "_"))
+}
+
+/// The legacy keychain files of an access key: `<network_name>/<account_id>/<key>.json` and
+/// `<network_name>/<account_id>.json`, joined with the separator of the platform.
+pub fn legacy_keychain_file_paths(
+    credentials_dir: &std::path::Path,
+    network_name: &str,
+    account_id: &str,
+    public_key_str: &str,
+) -> (std::path::PathBuf, std::path::PathBuf) {
+    let network_dir = credentials_dir.join(network_name);
+    (
+        network_dir
+            .join(account_id)
+            .join(key_file_name(public_key_str)),
+        network_dir.join(format!("{account_id}.json")),
+    )
+}
+
+/// Creates (or truncates) a file with secrets that only the current user can read: with the
+/// `0600` mode on Unix; on Windows, the file inherits the ACL of the (user profile) directory and
+/// it is not shared with other processes while it is written.
+pub fn create_credential_file(path: &std::path::Path) -> std::io::Result<std::fs::File> {
+    let mut open_options = OpenOptions::new();
+    open_options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        open_options.mode(0o600);
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::OpenOptionsExt;
+        open_options.share_mode(0);
+    }
+    open_options.open(path)
+}
+
 pub fn save_access_key_to_legacy_keychain(
     network_config: crate::config::NetworkConfig,
-    credentials_home_dir: std::path::PathBuf,
+    credentials_dir: std::path::PathBuf,
     key_pair_properties_buf: &str,
     public_key_str: &str,
     account_id: &str,
 ) -> color_eyre::eyre::Result<String> {
-    let dir_name = network_config.network_name.as_str();
-    let file_with_key_name: std::path::PathBuf =
-        format!("{}.json", public_key_str.replace(':', "_")).into();
-    let mut path_with_key_name = std::path::PathBuf::from(&credentials_home_dir);
-    path_with_key_name.push(dir_name);
-    path_with_key_name.push(account_id);
-    std::fs::create_dir_all(&path_with_key_name)?;
-    path_with_key_name.push(file_with_key_name);
+    if is_no_keychain() {
+        return print_unsaved_access_key(key_pair_properties_buf);
+    }
+    let (path_with_key_name, path_with_account_name) = legacy_keychain_file_paths(
+        &credentials_dir,
+        &network_config.network_name,
+        account_id,
+        public_key_str,
+    );
+    if let Some(account_dir) = path_with_key_name.parent() {
+        std::fs::create_dir_all(account_dir)?;
+    }
     let message_1 = if path_with_key_name.exists() {
         format!(
             "The file: {} already exists! Therefore it was not overwritten.",
             &path_with_key_name.display()
         )
     } else {
-        std::fs::File::create(&path_with_key_name)
+        create_credential_file(&path_with_key_name)
             .wrap_err_with(|| format!("Failed to create file: {:?}", path_with_key_name))?
             .write(key_pair_properties_buf.as_bytes())
             .wrap_err_with(|| format!("Failed to write to file: {:?}", path_with_key_name))?;
@@ -1381,10 +1619,6 @@ pub fn save_access_key_to_legacy_keychain(
         )
     };
 
-    let file_with_account_name: std::path::PathBuf = format!("{}.json", account_id).into();
-    let mut path_with_account_name = std::path::PathBuf::from(&credentials_home_dir);
-    path_with_account_name.push(dir_name);
-    path_with_account_name.push(file_with_account_name);
     if path_with_account_name.exists() {
         Ok(format!(
             "{}\nThe file: {} already exists! Therefore it was not overwritten.",
@@ -1392,7 +1626,7 @@ pub fn save_access_key_to_legacy_keychain(
             &path_with_account_name.display()
         ))
     } else {
-        std::fs::File::create(&path_with_account_name)
+        create_credential_file(&path_with_account_name)
             .wrap_err_with(|| format!("Failed to create file: {:?}", path_with_account_name))?
             .write(key_pair_properties_buf.as_bytes())
             .wrap_err_with(|| format!("Failed to write to file: {:?}", path_with_account_name))?;
@@ -1404,51 +1638,147 @@ pub fn save_access_key_to_legacy_keychain(
     }
 }
 
-pub fn try_external_subcommand_execution(error: clap::Error) -> CliResult {
-    let (subcommand, args) = {
-        let mut args = std::env::args().skip(1);
-        let subcommand = args
-            .next()
-            .ok_or_else(|| color_eyre::eyre::eyre!("subcommand is not provided"))?;
-        (subcommand, args.collect::<Vec<String>>())
-    };
+/// `extension_args` are the arguments from the name of the extension on (after the top-level
+/// flags), and `config` is loaded with the profile of the top-level flags.
+pub fn try_external_subcommand_execution(
+    error: clap::Error,
+    extension_args: &[String],
+    config: &crate::config::Config,
+) -> CliResult {
+    let (subcommand, args) = extension_args
+        .split_first()
+        .ok_or_else(|| color_eyre::eyre::eyre!("subcommand is not provided"))?;
     let is_top_level_command_known = crate::commands::TopLevelCommandDiscriminants::iter()
         .map(|x| format!("{:?}", &x).to_lowercase())
-        .any(|x| x == subcommand);
+        .any(|x| x == *subcommand);
     if is_top_level_command_known {
         error.exit()
     }
-    let subcommand_exe = format!("near-{}{}", subcommand, std::env::consts::EXE_SUFFIX);
+    // `near-<subcommand>` is the name of the extensions before `near-cli-<subcommand>`
+    let subcommand_exes = [EXTENSION_PREFIX, "near-"]
+        .map(|prefix| format!("{prefix}{subcommand}{}", std::env::consts::EXE_SUFFIX));
 
-    let path = path_directories()
-        .iter()
-        .map(|dir| dir.join(&subcommand_exe))
-        .find(|file| is_executable(file));
+    let path = subcommand_exes.iter().find_map(|subcommand_exe| {
+        path_directories()
+            .iter()
+            .map(|dir| dir.join(subcommand_exe))
+            .find(|file| is_executable(file))
+    });
 
     let command = path.ok_or_else(|| {
         color_eyre::eyre::eyre!(
             "{} command or {} extension does not exist",
             subcommand,
-            subcommand_exe
+            subcommand_exes[0]
         )
     })?;
 
-    let err = match cargo_util::ProcessBuilder::new(command)
-        .args(&args)
-        .exec_replace()
-    {
+    let mut process_builder = cargo_util::ProcessBuilder::new(command);
+    process_builder
+        .args(args)
+        .env_remove(crate::config::env_file::NEAR_PRIVATE_KEY);
+    for (key, value) in extension_env(config) {
+        process_builder.env(key, value);
+    }
+    let err = match process_builder.exec_replace() {
         Ok(()) => return Ok(()),
         Err(e) => e,
     };
 
     if let Some(perr) = err.downcast_ref::<cargo_util::ProcessError>() {
         if let Some(code) = perr.code {
-            return Err(color_eyre::eyre::eyre!("perror occurred, code: {}", code));
+            std::process::exit(code);
         }
     }
     Err(color_eyre::eyre::eyre!(err))
 }
 
+/// The executables `near-cli-<name>` on PATH run as `near <name>`.
+pub const EXTENSION_PREFIX: &str = "near-cli-";
+
+/// The context passed to the extensions in environment variables (never the secrets: the
+/// private key of `NEAR_PRIVATE_KEY` is removed from their environment).
+fn extension_env(config: &crate::config::Config) -> Vec<(&'static str, String)> {
+    let mut env = Vec::new();
+    if let Some(config_toml_path) = crate::config::Config::config_toml_path() {
+        env.push((
+            "NEAR_CLI_CONFIG_PATH",
+            config_toml_path.display().to_string(),
+        ));
+    }
+    if let Some(applied_profile) = &config.applied_profile {
+        env.push((
+            crate::config::profiles::NEAR_CLI_PROFILE,
+            applied_profile.name.clone(),
+        ));
+    }
+    // The network that the commands of near CLI would offer first
+    let network_name = std::env::var("NEAR_NETWORK")
+        .or_else(|_| std::env::var("NEAR_ENV"))
+        .ok()
+        .filter(|network_name| config.network_connection.contains_key(network_name))
+        .or_else(|| {
+            config
+                .default_network
+                .clone()
+                .filter(|network_name| config.network_connection.contains_key(network_name))
+        })
+        .or_else(|| {
+            config
+                .network_connection
+                .contains_key("mainnet")
+                .then(|| "mainnet".to_string())
+        })
+        .or_else(|| config.network_names().into_iter().next());
+    if let Some(network_config) = network_name
+        .as_ref()
+        .and_then(|network_name| config.network_connection.get(network_name))
+    {
+        env.push(("NEAR_CLI_NETWORK", network_config.network_name.clone()));
+        env.push(("NEAR_CLI_RPC_URL", network_config.rpc_url.to_string()));
+    }
+    let signer_account_id = crate::config::env_file::var::<near_primitives::types::AccountId>(
+        crate::config::env_file::NEAR_ACCOUNT_ID,
+    )
+    .ok()
+    .flatten()
+    .or_else(|| {
+        get_used_account_list(&config.credentials_home_dir)
+            .into_iter()
+            .find(|used_account| used_account.used_as_signer)
+            .map(|used_account| used_account.account_id)
+    });
+    if let Some(signer_account_id) = signer_account_id {
+        env.push(("NEAR_CLI_SIGNER_ACCOUNT_ID", signer_account_id.to_string()));
+    }
+    env
+}
+
+/// The extensions on PATH (name and executable), the first one on PATH wins for a name.
+pub fn find_extensions() -> Vec<(String, std::path::PathBuf)> {
+    let mut extensions = std::collections::BTreeMap::new();
+    for dir in path_directories() {
+        let Ok(entries) = dir.read_dir() else {
+            continue;
+        };
+        for entry in entries.filter_map(Result::ok) {
+            let file_name = entry.file_name().to_string_lossy().into_owned();
+            let Some(name) = file_name.strip_prefix(EXTENSION_PREFIX).map(|name| {
+                name.strip_suffix(std::env::consts::EXE_SUFFIX)
+                    .unwrap_or(name)
+            }) else {
+                continue;
+            };
+            if !name.is_empty() && is_executable(entry.path()) {
+                extensions
+                    .entry(name.to_string())
+                    .or_insert_with(|| entry.path());
+            }
+        }
+    }
+    extensions.into_iter().collect()
+}
+
 fn is_executable<P: AsRef<std::path::Path>>(path: P) -> bool {
     #[cfg(target_family = "unix")]
     {
@@ -1543,14 +1873,16 @@ pub fn input_staking_pool_validator_account_id(
                 .cloned()
                 .collect())
         })
-        .with_validator(|account_id_str: &str| {
-            match near_primitives::types::AccountId::validate(account_id_str) {
+        .with_validator(
+            |account_id_str: &str| match crate::types::account_id::AccountId::from_str(
+                account_id_str,
+            ) {
                 Ok(_) => Ok(inquire::validator::Validation::Valid),
                 Err(err) => Ok(inquire::validator::Validation::Invalid(
                     inquire::validator::ErrorMessage::Custom(format!("Invalid account ID: {err}")),
                 )),
-            }
-        })
+            },
+        )
         .prompt()
     {
         Ok(value) => value,
@@ -1583,6 +1915,45 @@ pub struct RewardFeeFraction {
     pub denominator: u32,
 }
 
+/// The runtime of the commands that make many requests at the same time.
+pub fn multi_thread_runtime() -> color_eyre::eyre::Result<tokio::runtime::Runtime> {
+    Ok(tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()?)
+}
+
+/// Runs `task` on every item, at most `concurrency` at a time, and returns the results in the
+/// order of the items.
+pub fn run_concurrently<I, F, Fut>(
+    items: I,
+    concurrency: usize,
+    task: F,
+) -> color_eyre::eyre::Result<Vec<Fut::Output>>
+where
+    I: IntoIterator,
+    F: FnMut(I::Item) -> Fut,
+    Fut: std::future::Future,
+{
+    Ok(multi_thread_runtime()?.block_on(
+        futures::stream::iter(items)
+            .map(task)
+            .buffered(concurrency)
+            .collect(),
+    ))
+}
+
+/// The error message of a request about a past block, which only an archival RPC node can answer
+/// for old blocks, e.g. `old_block_request_error("block #100", network_config)`.
+pub fn old_block_request_error(
+    requested: impl std::fmt::Display,
+    network_config: &crate::config::NetworkConfig,
+) -> String {
+    format!(
+        "Failed to fetch {requested} on network <{}> (the archival RPC is required for old blocks)",
+        network_config.network_name
+    )
+}
+
 #[tracing::instrument(name = "Getting a list of validators ...", skip_all)]
 pub fn get_validator_list(
     network_config: &crate::config::NetworkConfig,
@@ -1591,24 +1962,17 @@ pub fn get_validator_list(
 
     let validators_stake = get_validators_stake(&json_rpc_client)?;
 
-    let runtime = tokio::runtime::Builder::new_multi_thread()
-        .enable_all()
-        .build()?;
     let concurrency = 10;
 
-    let mut validator_list = runtime.block_on(
-        futures::stream::iter(validators_stake.iter())
-            .map(|(validator_account_id, stake)| async {
-                get_staking_pool_info(
-                    &json_rpc_client.clone(),
-                    validator_account_id.clone(),
-                    *stake,
-                )
-                .await
-            })
-            .buffer_unordered(concurrency)
-            .try_collect::<Vec<_>>(),
-    )?;
+    let mut validator_list = run_concurrently(
+        validators_stake.iter(),
+        concurrency,
+        |(validator_account_id, stake)| {
+            get_staking_pool_info(&json_rpc_client, validator_account_id.clone(), *stake)
+        },
+    )?
+    .into_iter()
+    .collect::<color_eyre::eyre::Result<Vec<_>>>()?;
     validator_list.sort_by(|a, b| b.stake.cmp(&a.stake));
     Ok(validator_list)
 }
@@ -1625,7 +1989,7 @@ struct StakingResponse {
 
 #[tracing::instrument(name = "Getting historically delegated staking pools ...", skip_all)]
 pub fn fetch_historically_delegated_staking_pools(
-    fastnear_url: &url::Url,
+    fastnear_url: &crate::config::config_url::ConfigUrl,
     account_id: &near_primitives::types::AccountId,
 ) -> color_eyre::Result<std::collections::BTreeSet<near_primitives::types::AccountId>> {
     let request =
@@ -1715,16 +2079,18 @@ async fn get_staking_pool_info(
     validator_account_id: near_primitives::types::AccountId,
     stake: u128,
 ) -> color_eyre::Result<StakingPoolInfo> {
-    let fee = match json_rpc_client
-        .call(near_jsonrpc_client::methods::query::RpcQueryRequest {
+    let fee = match crate::rpc_rate_limit::call(
+        json_rpc_client,
+        near_jsonrpc_client::methods::query::RpcQueryRequest {
             block_reference: near_primitives::types::Finality::Final.into(),
             request: near_primitives::views::QueryRequest::CallFunction {
                 account_id: validator_account_id.clone(),
                 method_name: "get_reward_fee_fraction".to_string(),
                 args: near_primitives::types::FunctionArgs::from(vec![]),
             },
-        })
-        .await
+        },
+    )
+    .await
     {
         Ok(response) => Some(
             response
@@ -1745,16 +2111,18 @@ async fn get_staking_pool_info(
         Err(err) => return Err(err.into()),
     };
 
-    let delegators = match json_rpc_client
-        .call(near_jsonrpc_client::methods::query::RpcQueryRequest {
+    let delegators = match crate::rpc_rate_limit::call(
+        json_rpc_client,
+        near_jsonrpc_client::methods::query::RpcQueryRequest {
             block_reference: near_primitives::types::Finality::Final.into(),
             request: near_primitives::views::QueryRequest::CallFunction {
                 account_id: validator_account_id.clone(),
                 method_name: "get_number_of_accounts".to_string(),
                 args: near_primitives::types::FunctionArgs::from(vec![]),
             },
-        })
-        .await
+        },
+    )
+    .await
     {
         Ok(response) => Some(
             response
@@ -1781,6 +2149,14 @@ async fn get_staking_pool_info(
     })
 }
 
+/// A specialized section of the account summary for well-known contracts (multisig, DAO).
+#[derive(Debug)]
+pub struct KnownContractSection {
+    pub title: String,
+    pub rows: Vec<(String, color_eyre::eyre::Result<String>)>,
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn display_account_info(
     viewed_at_block_hash: &CryptoHash,
     viewed_at_block_height: &near_primitives::types::BlockHeight,
@@ -1791,6 +2167,7 @@ pub fn display_account_info(
     account_view: &near_primitives::views::AccountView,
     access_key_list: Option<&near_primitives::views::AccessKeyList>,
     optional_account_profile: Option<&near_socialdb_client::types::socialdb_types::AccountProfile>,
+    known_contract_section: Option<&KnownContractSection>,
 ) {
     eprintln!();
     let mut table: Table = Table::new();
@@ -1803,6 +2180,12 @@ pub fn display_account_info(
         optional_account_profile,
         &mut table,
     );
+    if let Some(eth_address) = crate::types::account_id::eth_address(account_id) {
+        table.add_row(prettytable::row![
+            Fg->"ETH address",
+            Fy->eth_address
+        ]);
+    }
 
     table.add_row(prettytable::row![
         Fg->"Native account balance",
@@ -1875,6 +2258,25 @@ pub fn display_account_info(
         Fg->"Access keys",
         Fy->access_keys_summary
     ]);
+
+    if let Some(known_contract_section) = known_contract_section {
+        table.add_row(prettytable::row![
+            Fg->known_contract_section.title,
+            ""
+        ]);
+        for (name, value) in &known_contract_section.rows {
+            match value {
+                Ok(value) => table.add_row(prettytable::row![
+                    Fg->format!("  {name}"),
+                    Fy->value
+                ]),
+                Err(err) => table.add_row(prettytable::row![
+                    Fg->format!("  {name}"),
+                    Fr->format!("{err:#}")
+                ]),
+            };
+        }
+    }
     table.printstd();
 }
 
@@ -1998,50 +2400,157 @@ fn profile_table(
 }
 
 pub fn display_access_key_list(access_keys: &[near_primitives::views::AccessKeyInfoView]) {
+    display_access_keys(access_keys.iter().enumerate(), &[]);
+}
+
+/// Displays a single access key with its position (0-based `index`) in the account's key list.
+pub fn display_access_key(index: usize, access_key: &near_primitives::views::AccessKeyInfoView) {
+    display_access_keys(std::iter::once((index, access_key)), &[]);
+}
+
+/// Displays the access keys with their positions (0-based) in the account's key list, and extra
+/// columns (a title and the cells in the same order as the keys).
+pub fn display_access_keys<'a>(
+    access_keys: impl Iterator<Item = (usize, &'a near_primitives::views::AccessKeyInfoView)>,
+    extra_columns: &[(&str, &[String])],
+) {
     let mut table = Table::new();
-    table.set_titles(prettytable::row![Fg=>"#", "Public Key", "Nonce", "Permissions"]);
-
-    for (index, access_key) in access_keys.iter().enumerate() {
-        let permissions_message = match &access_key.access_key.permission {
-            AccessKeyPermissionView::FullAccess => "full access".to_owned(),
-            AccessKeyPermissionView::FunctionCall {
-                allowance,
-                receiver_id,
-                method_names,
-            } => {
-                let allowance_message = match allowance {
-                    Some(amount) => format!(
-                        "with an allowance of {}",
-                        near_token::NearToken::from_yoctonear(*amount)
-                    ),
-                    None => "with no limit".to_string(),
-                };
-                if method_names.is_empty() {
-                    format!(
-                        "do any function calls on {} {}",
-                        receiver_id, allowance_message
-                    )
-                } else {
-                    format!(
-                        "only do {:?} function calls on {} {}",
-                        method_names, receiver_id, allowance_message
-                    )
-                }
-            }
-        };
+    let mut titles = prettytable::row![Fg=>"#", "Public Key", "Nonce", "Permissions"];
+    for (title, _) in extra_columns {
+        titles.add_cell(prettytable::Cell::new(title).style_spec("Fg"));
+    }
+    table.set_titles(titles);
 
-        table.add_row(prettytable::row![
+    for (position, (index, access_key)) in access_keys.enumerate() {
+        let permissions_message = access_key_permission_message(&access_key.access_key.permission);
+
+        let mut row = prettytable::row![
             Fg->index + 1,
             access_key.public_key,
             access_key.access_key.nonce,
             permissions_message
-        ]);
+        ];
+        for (_, cells) in extra_columns {
+            row.add_cell(prettytable::Cell::new(
+                cells.get(position).map_or("", String::as_str),
+            ));
+        }
+        table.add_row(row);
     }
 
     table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
     table.printstd();
 }
 
+/// Describes what an access key with the given permission is allowed to do.
+pub fn access_key_permission_message(permission: &AccessKeyPermissionView) -> String {
+    match permission {
+        AccessKeyPermissionView::FullAccess => "full access".to_owned(),
+        AccessKeyPermissionView::FunctionCall {
+            allowance,
+            receiver_id,
+            method_names,
+        } => {
+            let allowance_message = match allowance {
+                Some(amount) => format!(
+                    "with an allowance of {}",
+                    near_token::NearToken::from_yoctonear(*amount)
+                ),
+                None => "with no limit".to_string(),
+            };
+            if method_names.is_empty() {
+                format!(
+                    "do any function calls on {} {}",
+                    receiver_id, allowance_message
+                )
+            } else {
+                format!(
+                    "only do {:?} function calls on {} {}",
+                    method_names, receiver_id, allowance_message
+                )
+            }
+        }
+    }
+}
+
+/// Whether the interactive selectors can be rendered, i.e. both stdin and stderr (where the prompts
+/// are drawn) are terminals (it is detected once, on the first prompt) and the plain prompt style
+/// is not requested.
+pub fn is_interactive_terminal() -> bool {
+    use std::io::IsTerminal;
+
+    static IS_INTERACTIVE_TERMINAL: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+    !crate::prompt_style::is_plain()
+        && *IS_INTERACTIVE_TERMINAL
+            .get_or_init(|| std::io::stdin().is_terminal() && std::io::stderr().is_terminal())
+}
+
+/// `inquire::Select` that degrades gracefully without a TTY (scripts, pipes, `git bash`) and with
+/// the plain prompt style.
+///
+/// Instead of the interactive list, the options are printed as a numbered list and a line is
+/// read from stdin (an option number or the option itself). If stdin is already exhausted, the
+/// "missing argument" error is returned, as in the non-interactive mode.
+/// Returns `None` if the prompt was cancelled.
+pub fn select<T: std::fmt::Display>(
+    message: &str,
+    options: Vec<T>,
+) -> color_eyre::eyre::Result<Option<T>> {
+    if is_interactive_terminal() {
+        return match Select::new(message, options).prompt() {
+            Ok(value) => Ok(Some(value)),
+            Err(
+                inquire::error::InquireError::OperationCanceled
+                | inquire::error::InquireError::OperationInterrupted,
+            ) => Ok(None),
+            Err(err) => Err(err.into()),
+        };
+    }
+    select_from_lines(
+        message,
+        options,
+        &mut std::io::stdin().lock(),
+        &mut std::io::stderr(),
+    )
+    .map(Some)
+}
+
+fn select_from_lines<T: std::fmt::Display>(
+    message: &str,
+    mut options: Vec<T>,
+    input: &mut impl std::io::BufRead,
+    output: &mut impl std::io::Write,
+) -> color_eyre::eyre::Result<T> {
+    writeln!(output, "{message}")?;
+    for (index, option) in options.iter().enumerate() {
+        writeln!(output, "  {}) {option}", index + 1)?;
+    }
+    loop {
+        write!(output, "Enter a number (1-{}): ", options.len())?;
+        output.flush()?;
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Err(color_eyre::eyre::eyre!(
+                "Missing argument: {} (stdin is not a terminal, so pass the value on the command line)",
+                message.trim_end_matches(':')
+            ));
+        }
+        let line = line.trim();
+        let selected = match line.parse::<usize>() {
+            Ok(number) => number.checked_sub(1).filter(|index| *index < options.len()),
+            // The variant names of the commands come first in the option (e.g. "sign-with-keychain  - ...")
+            Err(_) => options.iter().position(|option| {
+                let option = option.to_string();
+                option == line || option.split_whitespace().next() == Some(line)
+            }),
+        };
+        match selected {
+            Some(index) => return Ok(options.swap_remove(index)),
+            None => writeln!(output, "<{line}> is not one of the options.")?,
+        }
+    }
+}
+
 /// Interactive prompt for network name.
 ///
 /// If account_ids is provided, show the network connections that are more
@@ -2053,11 +2562,20 @@ pub fn input_network_name(
     if config.network_connection.len() == 1 {
         return Ok(config.network_names().pop());
     }
+    // The default network (of the profile) is offered first, unless the accounts point to other ones
+    let network_connection = config
+        .network_connection
+        .iter()
+        .filter(|(name, _)| config.default_network.as_ref() == Some(*name))
+        .chain(
+            config
+                .network_connection
+                .iter()
+                .filter(|(name, _)| config.default_network.as_ref() != Some(*name)),
+        );
     let variants = if !account_ids.is_empty() {
-        let (mut matches, non_matches): (Vec<_>, Vec<_>) = config
-            .network_connection
-            .iter()
-            .partition(|(_, network_config)| {
+        let (mut matches, non_matches): (Vec<_>, Vec<_>) =
+            network_connection.partition(|(_, network_config)| {
                 // We use `linkdrop_account_id` as a heuristic to determine if
                 // the accounts are on the same network. In the future, we
                 // might consider to have a better way to do this.
@@ -2078,18 +2596,10 @@ pub fn input_network_name(
         };
         variants.into_iter().map(|(k, _)| k).collect()
     } else {
-        config.network_connection.keys().collect()
+        network_connection.map(|(k, _)| k).collect()
     };
 
-    let select_submit = Select::new("What is the name of the network?", variants).prompt();
-    match select_submit {
-        Ok(value) => Ok(Some(value.clone())),
-        Err(
-            inquire::error::InquireError::OperationCanceled
-            | inquire::error::InquireError::OperationInterrupted,
-        ) => Ok(None),
-        Err(err) => Err(err.into()),
-    }
+    Ok(select("What is the name of the network?", variants)?.cloned())
 }
 
 pub trait JsonRpcClientExt {
@@ -2197,7 +2707,7 @@ impl JsonRpcClientExt for near_jsonrpc_client::JsonRpcClient {
 
         tokio::runtime::Runtime::new()
             .unwrap()
-            .block_on(self.call(method))
+            .block_on(crate::rpc_rate_limit::call(self, method))
             .inspect_err(|err| match err {
                 near_jsonrpc_client::errors::JsonRpcError::TransportError(transport_error) => {
                     tracing::info!(
@@ -2708,6 +3218,15 @@ pub fn input_signer_account_id_from_used_account_list(
     credentials_home_dir: &std::path::Path,
     message: &str,
 ) -> color_eyre::eyre::Result<Option<crate::types::account_id::AccountId>> {
+    if let Some(account_id) = crate::config::env_file::var::<crate::types::account_id::AccountId>(
+        crate::config::env_file::NEAR_ACCOUNT_ID,
+    )? {
+        crate::config::env_file::notify_override(
+            crate::config::env_file::NEAR_ACCOUNT_ID,
+            format!("the signer account is <{account_id}>"),
+        );
+        return Ok(Some(account_id));
+    }
     let account_is_signer = true;
     input_account_id_from_used_account_list(credentials_home_dir, message, account_is_signer)
 }
@@ -2738,14 +3257,16 @@ fn input_account_id_from_used_account_list(
                 .cloned()
                 .collect())
         })
-        .with_validator(|account_id_str: &str| {
-            match near_primitives::types::AccountId::validate(account_id_str) {
+        .with_validator(
+            |account_id_str: &str| match crate::types::account_id::AccountId::from_str(
+                account_id_str,
+            ) {
                 Ok(_) => Ok(inquire::validator::Validation::Valid),
                 Err(err) => Ok(inquire::validator::Validation::Invalid(
                     inquire::validator::ErrorMessage::Custom(format!("Invalid account ID: {err}")),
                 )),
-            }
-        })
+            },
+        )
         .prompt()
     {
         Ok(value) => value,
@@ -2777,3 +3298,162 @@ pub fn save_cli_command(cli_cmd_str: &str) {
         eprintln!("Failed to store a cli command in a temporary file: {}", err);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn select_piped(input: &str) -> color_eyre::eyre::Result<&'static str> {
+        select_from_lines(
+            "Select a keychain:",
+            vec![
+                "keychain    - The keychain",
+                "legacy      - The legacy keychain",
+            ],
+            &mut input.as_bytes(),
+            &mut Vec::new(),
+        )
+    }
+
+    #[test]
+    fn select_from_piped_lines() {
+        assert_eq!(
+            select_piped("2\n").unwrap(),
+            "legacy      - The legacy keychain"
+        );
+        assert_eq!(
+            select_piped("keychain\n").unwrap(),
+            "keychain    - The keychain"
+        );
+        assert_eq!(
+            select_piped("0\n3\nwat\n 1 \n").unwrap(),
+            "keychain    - The keychain"
+        );
+    }
+
+    #[test]
+    fn select_without_input_is_a_missing_argument() {
+        let err = select_piped("").unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Missing argument: Select a keychain ("));
+        assert!(select_piped("5\n").is_err());
+    }
+
+    #[test]
+    fn select_prints_numbered_options() {
+        let mut output = Vec::new();
+        select_from_lines("Pick:", vec!["a", "b"], &mut "b\n".as_bytes(), &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "Pick:\n  1) a\n  2) b\nEnter a number (1-2): "
+        );
+    }
+
+    #[test]
+    fn legacy_keychain_file_paths_use_path_joins() {
+        let (key_file, account_file) = legacy_keychain_file_paths(
+            std::path::Path::new("credentials"),
+            "testnet",
+            "alice.testnet",
+            "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp",
+        );
+        assert_eq!(
+            key_file,
+            std::path::Path::new("credentials")
+                .join("testnet")
+                .join("alice.testnet")
+                .join("ed25519_6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp.json")
+        );
+        assert_eq!(
+            account_file,
+            std::path::Path::new("credentials")
+                .join("testnet")
+                .join("alice.testnet.json")
+        );
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn legacy_keychain_file_paths_on_windows() {
+        let (key_file, account_file) = legacy_keychain_file_paths(
+            std::path::Path::new(r"C:\Users\x\.near-credentials"),
+            "testnet",
+            "alice.testnet",
+            "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp",
+        );
+        assert_eq!(
+            key_file.to_str().unwrap(),
+            r"C:\Users\x\.near-credentials\testnet\alice.testnet\ed25519_6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp.json"
+        );
+        assert_eq!(
+            account_file.to_str().unwrap(),
+            r"C:\Users\x\.near-credentials\testnet\alice.testnet.json"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn credential_files_are_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = std::env::temp_dir().join(format!(
+            "near-cli-test-credential-file-{}.json",
+            std::process::id()
+        ));
+        create_credential_file(&path).unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn key_pair_properties_from_legacy_key_files() {
+        let secret_key = near_crypto::SecretKey::from_seed(near_crypto::KeyType::ED25519, "test");
+        let public_key = secret_key.public_key();
+        let key_pair_properties = KeyPairProperties::try_from(serde_json::json!({
+            "account_id": "alice.testnet",
+            "public_key": public_key.to_string(),
+            "private_key": secret_key.to_string(),
+        }))
+        .unwrap();
+        assert_eq!(key_pair_properties.public_key().unwrap(), public_key);
+        assert_eq!(key_pair_properties.secret_key().unwrap(), secret_key);
+        assert!(matches!(
+            key_pair_properties.key_type().unwrap(),
+            near_crypto::KeyType::ED25519
+        ));
+        assert_eq!(
+            key_pair_properties.implicit_account_id.as_str(),
+            "bb4dc639b212e075a751685b26bdcea5920a504181ff2910e8549742127092a0"
+        );
+        let display = key_pair_properties.to_string();
+        assert!(display.contains(&public_key.to_string()), "{display}");
+        assert!(!display.contains(&secret_key.to_string()), "{display}");
+
+        let from_secret_key_only = KeyPairProperties::try_from(serde_json::json!({
+            "secret_key": secret_key.to_string(),
+        }))
+        .unwrap();
+        assert_eq!(from_secret_key_only.public_key_str, public_key.to_string());
+
+        // The files with all the properties are read as they are
+        let all_properties =
+            KeyPairProperties::try_from(serde_json::to_value(&key_pair_properties).unwrap())
+                .unwrap();
+        assert_eq!(
+            all_properties.public_key_str,
+            key_pair_properties.public_key_str
+        );
+
+        let other_public_key =
+            near_crypto::PublicKey::from_seed(near_crypto::KeyType::ED25519, "other");
+        let err = KeyPairProperties::try_from(serde_json::json!({
+            "public_key": other_public_key.to_string(),
+            "private_key": secret_key.to_string(),
+        }))
+        .unwrap_err();
+        assert!(err.to_string().contains("does not belong"), "{err}");
+        assert!(KeyPairProperties::try_from(serde_json::json!({})).is_err());
+    }
+}
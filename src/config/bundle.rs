@@ -0,0 +1,454 @@
+//! A shareable bundle of the CLI configuration: network connections, defaults (with the known
+//! contracts and the profiles) and the watchlist. Credentials (RPC API keys) and machine-specific
+//! paths (credentials directories) are never exported.
+
+use color_eyre::eyre::WrapErr;
+
+const CONFIG_BUNDLE_VERSION: u32 = 1;
+
+/// Field names (or parts of them) that look like credentials and must never be shared.
+const CREDENTIAL_FIELD_PATTERNS: &[&str] = &[
+    "api_key",
+    "private_key",
+    "secret",
+    "seed_phrase",
+    "password",
+    "credentials",
+];
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ConfigBundle {
+    version: u32,
+    network_connection: linked_hash_map::LinkedHashMap<String, super::NetworkConfig>,
+    #[serde(default)]
+    defaults: ConfigBundleDefaults,
+    #[serde(default)]
+    watchlist: Vec<super::WatchedAccount>,
+}
+
+/// The global settings of the config besides the network connections and the watchlist (the
+/// missing ones are the defaults of a new config).
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+struct ConfigBundleDefaults {
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        with = "super::near_token_as_string"
+    )]
+    require_ledger_above: Option<crate::types::near_token::NearToken>,
+    wait_for_finality: bool,
+    audit_log: bool,
+    notify: crate::notification::NotifyMode,
+    notify_after_secs: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_duration: Option<crate::types::duration::Duration>,
+    prompt_style: crate::prompt_style::PromptStyle,
+    #[serde(skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    known_contracts:
+        std::collections::BTreeMap<near_primitives::hash::CryptoHash, super::KnownContractKind>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default_network: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default_profile: Option<String>,
+    #[serde(skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    profiles: std::collections::BTreeMap<String, super::profiles::ConfigProfile>,
+}
+
+impl Default for ConfigBundleDefaults {
+    fn default() -> Self {
+        Self::from_config(&super::Config::default())
+    }
+}
+
+impl ConfigBundleDefaults {
+    fn from_config(config: &super::Config) -> Self {
+        Self {
+            require_ledger_above: config.require_ledger_above,
+            wait_for_finality: config.wait_for_finality,
+            audit_log: config.audit_log,
+            notify: config.notify,
+            notify_after_secs: config.notify_after_secs,
+            max_duration: config.max_duration,
+            prompt_style: config.prompt_style,
+            known_contracts: config.known_contracts.clone(),
+            default_network: config.default_network.clone(),
+            default_profile: config.default_profile.clone(),
+            profiles: config.profiles.clone(),
+        }
+    }
+
+    fn apply_to(self, config: &mut super::Config) {
+        config.require_ledger_above = self.require_ledger_above;
+        config.wait_for_finality = self.wait_for_finality;
+        config.audit_log = self.audit_log;
+        config.notify = self.notify;
+        config.notify_after_secs = self.notify_after_secs;
+        config.max_duration = self.max_duration;
+        config.prompt_style = self.prompt_style;
+        config.known_contracts = self.known_contracts;
+        config.default_network = self.default_network;
+        config.default_profile = self.default_profile;
+        config.profiles = self.profiles;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportMode {
+    /// Keep the existing entries and add the new ones; conflicting entries are skipped
+    Merge,
+    /// Replace the network connections, defaults and watchlist with the ones from the bundle
+    Replace,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    pub added: Vec<String>,
+    pub updated: Vec<String>,
+    pub removed: Vec<String>,
+    pub skipped: Vec<String>,
+}
+
+impl ConfigBundle {
+    /// The bundle of the global settings (not the ones of the applied profile).
+    pub fn from_config(config: &super::Config) -> Self {
+        let config = config.clone().without_applied_profile();
+        let mut defaults = ConfigBundleDefaults::from_config(&config);
+        for profile in defaults.profiles.values_mut() {
+            profile.credentials_dir = None;
+        }
+        Self {
+            version: CONFIG_BUNDLE_VERSION,
+            network_connection: config
+                .network_connection
+                .iter()
+                .map(|(connection_name, network_config)| {
+                    let mut network_config = network_config.clone();
+                    network_config.rpc_api_key = None;
+                    network_config.credentials_dir = None;
+                    (connection_name.clone(), network_config)
+                })
+                .collect(),
+            defaults,
+            watchlist: config.watchlist.clone(),
+        }
+    }
+
+    pub fn from_json(bundle_json: &str) -> color_eyre::eyre::Result<Self> {
+        let bundle_value: serde_json::Value =
+            serde_json::from_str(bundle_json).wrap_err("The bundle is not a valid JSON")?;
+        let credential_fields = find_credential_fields(&bundle_value, "");
+        if !credential_fields.is_empty() {
+            color_eyre::eyre::bail!(
+                "The bundle contains credential-looking fields, which must never be shared: {}",
+                credential_fields.join(", ")
+            );
+        }
+        let bundle: Self = serde_json::from_value(bundle_value)
+            .wrap_err("The bundle does not match the configuration format")?;
+        if bundle.version != CONFIG_BUNDLE_VERSION {
+            color_eyre::eyre::bail!(
+                "Unsupported bundle version {} (expected {CONFIG_BUNDLE_VERSION})",
+                bundle.version
+            );
+        }
+        Ok(bundle)
+    }
+
+    pub fn apply(
+        self,
+        config: &mut super::Config,
+        mode: ImportMode,
+    ) -> color_eyre::eyre::Result<ImportReport> {
+        let mut report = ImportReport::default();
+
+        if mode == ImportMode::Replace {
+            for connection_name in config.network_connection.keys() {
+                if !self.network_connection.contains_key(connection_name) {
+                    report
+                        .removed
+                        .push(format!("network connection \"{connection_name}\""));
+                }
+            }
+        }
+        let mut network_connection = match mode {
+            ImportMode::Merge => config.network_connection.clone(),
+            ImportMode::Replace => linked_hash_map::LinkedHashMap::new(),
+        };
+        for (connection_name, mut network_config) in self.network_connection {
+            let entry = format!("network connection \"{connection_name}\"");
+            match config.network_connection.get(&connection_name) {
+                None => {
+                    report.added.push(entry);
+                }
+                Some(existing_network_config) => {
+                    // RPC API keys and credentials directories are local, so they are kept for the
+                    // connections with the same name
+                    network_config.rpc_api_key = existing_network_config.rpc_api_key.clone();
+                    network_config.credentials_dir =
+                        existing_network_config.credentials_dir.clone();
+                    let differences =
+                        network_config_differences(existing_network_config, &network_config)?;
+                    if differences.is_empty() {
+                        report.skipped.push(format!("{entry} (unchanged)"));
+                    } else if mode == ImportMode::Merge {
+                        report
+                            .skipped
+                            .push(format!("{entry} (conflict: {})", differences.join("; ")));
+                        continue;
+                    } else {
+                        report
+                            .updated
+                            .push(format!("{entry} ({})", differences.join("; ")));
+                    }
+                }
+            }
+            network_connection.insert(connection_name, network_config);
+        }
+
+        let current_defaults = ConfigBundleDefaults::from_config(config);
+        let mut bundle_defaults = self.defaults;
+        // The credentials directories of the profiles are local, as the ones of the connections
+        for (profile_name, profile) in bundle_defaults.profiles.iter_mut() {
+            profile.credentials_dir = current_defaults
+                .profiles
+                .get(profile_name)
+                .and_then(|current_profile| current_profile.credentials_dir.clone());
+        }
+        let defaults = if bundle_defaults == current_defaults {
+            report.skipped.push("defaults (unchanged)".to_string());
+            current_defaults
+        } else if mode == ImportMode::Merge && current_defaults != ConfigBundleDefaults::default() {
+            report
+                .skipped
+                .push("defaults (conflict: the local defaults are already customized)".to_string());
+            current_defaults
+        } else {
+            report.updated.push("defaults".to_string());
+            bundle_defaults
+        };
+
+        let mut watchlist = match mode {
+            ImportMode::Merge => config.watchlist.clone(),
+            ImportMode::Replace => {
+                report.removed.extend(
+                    config
+                        .watchlist
+                        .iter()
+                        .filter(|watched_account| !self.watchlist.contains(watched_account))
+                        .map(watched_account_entry),
+                );
+                Vec::new()
+            }
+        };
+        for watched_account in self.watchlist {
+            if watchlist.contains(&watched_account) {
+                continue;
+            }
+            if !config.watchlist.contains(&watched_account) {
+                report.added.push(watched_account_entry(&watched_account));
+            }
+            watchlist.push(watched_account);
+        }
+
+        config.network_connection = network_connection;
+        defaults.apply_to(config);
+        config.watchlist = watchlist;
+        Ok(report)
+    }
+}
+
+fn watched_account_entry(watched_account: &super::WatchedAccount) -> String {
+    format!(
+        "watchlist account <{}> on \"{}\"",
+        watched_account.account_id, watched_account.network_name
+    )
+}
+
+fn network_config_differences(
+    existing_network_config: &super::NetworkConfig,
+    network_config: &super::NetworkConfig,
+) -> color_eyre::eyre::Result<Vec<String>> {
+    let existing_value = serde_json::to_value(existing_network_config)?;
+    let value = serde_json::to_value(network_config)?;
+    let (Some(existing_fields), Some(fields)) = (existing_value.as_object(), value.as_object())
+    else {
+        return Ok(Vec::new());
+    };
+    Ok(fields
+        .iter()
+        .filter(|(key, _)| !matches!(key.as_str(), "rpc_api_key" | "credentials_dir"))
+        .filter_map(|(key, value)| {
+            let existing_value = existing_fields.get(key).unwrap_or(&serde_json::Value::Null);
+            (existing_value != value).then(|| format!("{key}: {existing_value} -> {value}"))
+        })
+        .collect())
+}
+
+/// Returns the paths of non-empty fields whose names look like credentials.
+fn find_credential_fields(value: &serde_json::Value, path: &str) -> Vec<String> {
+    match value {
+        serde_json::Value::Object(fields) => fields
+            .iter()
+            .flat_map(|(key, value)| {
+                let field_path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                let key = key.to_lowercase();
+                if !value.is_null()
+                    && CREDENTIAL_FIELD_PATTERNS
+                        .iter()
+                        .any(|pattern| key.contains(pattern))
+                {
+                    vec![field_path]
+                } else {
+                    find_credential_fields(value, &field_path)
+                }
+            })
+            .collect(),
+        serde_json::Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .flat_map(|(index, item)| find_credential_fields(item, &format!("{path}[{index}]")))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_with_api_key() -> super::super::Config {
+        let mut config = super::super::Config::default();
+        config
+            .network_connection
+            .get_mut("mainnet")
+            .unwrap()
+            .rpc_api_key = Some("my-secret-api-key".parse().unwrap());
+        config
+    }
+
+    #[test]
+    fn export_never_contains_credentials() {
+        let bundle = ConfigBundle::from_config(&config_with_api_key());
+        let bundle_json = serde_json::to_string(&bundle).unwrap();
+        assert!(!bundle_json.contains("my-secret-api-key"));
+        assert!(!bundle_json.contains("credentials_home_dir"));
+        assert!(ConfigBundle::from_json(&bundle_json).is_ok());
+    }
+
+    #[test]
+    fn export_then_import_keeps_the_local_credentials_dir() {
+        let mut config = config_with_api_key();
+        config
+            .network_connection
+            .get_mut("mainnet")
+            .unwrap()
+            .credentials_dir = Some("/mnt/encrypted/near-credentials".into());
+        let bundle_json = serde_json::to_string(&ConfigBundle::from_config(&config)).unwrap();
+        assert!(!bundle_json.contains("/mnt/encrypted"));
+
+        let report = ConfigBundle::from_json(&bundle_json)
+            .unwrap()
+            .apply(&mut config, ImportMode::Replace)
+            .unwrap();
+        assert!(report.updated.is_empty());
+        assert!(report.removed.is_empty());
+        assert_eq!(
+            config.network_connection["mainnet"].credentials_dir,
+            Some("/mnt/encrypted/near-credentials".into())
+        );
+    }
+
+    #[test]
+    fn all_the_defaults_are_exported_and_imported() {
+        let mut team_config = super::super::Config {
+            notify: crate::notification::NotifyMode::Always,
+            prompt_style: crate::prompt_style::PromptStyle::Plain,
+            default_network: Some("testnet".to_string()),
+            ..Default::default()
+        };
+        team_config.profiles.insert(
+            "ci".to_string(),
+            super::super::profiles::ConfigProfile {
+                default_network: Some("testnet".to_string()),
+                credentials_dir: Some("/home/ci/keys".into()),
+                ..Default::default()
+            },
+        );
+        let bundle_json = serde_json::to_string(&ConfigBundle::from_config(&team_config)).unwrap();
+        assert!(!bundle_json.contains("/home/ci/keys"));
+
+        let mut config = super::super::Config::default();
+        let report = ConfigBundle::from_json(&bundle_json)
+            .unwrap()
+            .apply(&mut config, ImportMode::Merge)
+            .unwrap();
+        assert_eq!(report.updated, vec!["defaults"]);
+        assert_eq!(config.notify, crate::notification::NotifyMode::Always);
+        assert_eq!(config.prompt_style, crate::prompt_style::PromptStyle::Plain);
+        assert_eq!(config.default_network.as_deref(), Some("testnet"));
+        assert_eq!(config.profiles["ci"].credentials_dir, None);
+    }
+
+    #[test]
+    fn import_refuses_credential_looking_fields() {
+        let mut bundle_value =
+            serde_json::to_value(ConfigBundle::from_config(&Default::default())).unwrap();
+        bundle_value["network_connection"]["mainnet"]["rpc_api_key"] = "leaked".into();
+        bundle_value["defaults"]["signer_private_key"] = "ed25519:leaked".into();
+        let err = ConfigBundle::from_json(&bundle_value.to_string()).unwrap_err();
+        assert!(err
+            .to_string()
+            .ends_with("defaults.signer_private_key, network_connection.mainnet.rpc_api_key"));
+    }
+
+    #[test]
+    fn merge_reports_conflicts_and_keeps_local_entries() {
+        let mut config = config_with_api_key();
+        let mut bundle = ConfigBundle::from_config(&config);
+        let mut custom_network = bundle.network_connection["testnet"].clone();
+        custom_network.network_name = "custom".to_string();
+        bundle
+            .network_connection
+            .insert("custom".to_string(), custom_network);
+        bundle
+            .network_connection
+            .get_mut("testnet")
+            .unwrap()
+            .rpc_url = "https://rpc.testnet.example.com".parse().unwrap();
+
+        let report = bundle.apply(&mut config, ImportMode::Merge).unwrap();
+        assert_eq!(report.added, vec!["network connection \"custom\""]);
+        assert!(report.updated.is_empty());
+        assert_eq!(report.skipped.len(), 3);
+        assert!(report.skipped[1].starts_with("network connection \"testnet\" (conflict: rpc_url"));
+        assert_eq!(
+            config.network_connection["testnet"].rpc_url.as_str(),
+            "https://archival-rpc.testnet.near.org/"
+        );
+        assert!(config.network_connection["mainnet"].rpc_api_key.is_some());
+    }
+
+    #[test]
+    fn replace_overwrites_and_removes_entries() {
+        let mut config = config_with_api_key();
+        let mut bundle = ConfigBundle::from_config(&config);
+        bundle.network_connection.remove("testnet");
+        bundle
+            .network_connection
+            .get_mut("mainnet")
+            .unwrap()
+            .rpc_url = "https://rpc.mainnet.example.com".parse().unwrap();
+        bundle.defaults.wait_for_finality = true;
+
+        let report = bundle.apply(&mut config, ImportMode::Replace).unwrap();
+        assert_eq!(report.removed, vec!["network connection \"testnet\""]);
+        assert_eq!(report.updated.len(), 2);
+        assert_eq!(config.network_names(), vec!["mainnet"]);
+        assert!(config.wait_for_finality);
+        assert!(config.network_connection["mainnet"].rpc_api_key.is_some());
+    }
+}
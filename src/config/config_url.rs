@@ -0,0 +1,121 @@
+//! URLs from the network connections of the config.
+//!
+//! The URLs are valid (they are parsed when the config is loaded), but building a URL from them
+//! may still fail, e.g. `mailto:` or `data:` URLs cannot have paths joined to them. The errors name
+//! the network connection, the config key and its value, and how to fix it.
+
+#[derive(Debug, Clone)]
+pub struct ConfigUrl {
+    network_name: String,
+    key: &'static str,
+    url: url::Url,
+}
+
+impl ConfigUrl {
+    pub fn new(
+        network_config: &crate::config::NetworkConfig,
+        key: &'static str,
+        url: &url::Url,
+    ) -> Self {
+        Self {
+            network_name: network_config.network_name.clone(),
+            key,
+            url: url.clone(),
+        }
+    }
+
+    /// [`url::Url::join`] with an error that explains which config value is wrong.
+    pub fn join(&self, path: &str) -> color_eyre::eyre::Result<url::Url> {
+        if self.url.cannot_be_a_base() {
+            return Err(self.error(format!(
+                "<{path}> cannot be appended to it because it is not a base URL (e.g. https://example.com/)"
+            )));
+        }
+        self.url
+            .join(path)
+            .map_err(|err| self.error(format!("<{path}> cannot be appended to it: {err}")))
+    }
+
+    /// The link to a transaction in the explorer (`explorer_transaction_url` followed by the
+    /// transaction hash).
+    pub fn transaction(&self, transaction_hash: &str) -> color_eyre::eyre::Result<url::Url> {
+        if !self.url.path().ends_with('/') {
+            return Err(self.error(format!(
+                "the transaction hash <{transaction_hash}> cannot be appended to it because it does not end with '/'"
+            )));
+        }
+        self.join(transaction_hash)
+    }
+
+    fn error(&self, reason: String) -> color_eyre::eyre::Report {
+        color_eyre::eyre::eyre!(
+            "The URL `network_connection.{network_name}.{key}` = \"{url}\" in the config is not usable: {reason}.\nFix it with: near config edit-connection {network_name} --key {key} --value <URL>",
+            network_name = self.network_name,
+            key = self.key,
+            url = self.url,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_url(key: &'static str, url: &str) -> ConfigUrl {
+        let config = crate::config::Config::default();
+        let testnet = config.network_connection.get("testnet").unwrap();
+        ConfigUrl::new(testnet, key, &url.parse().unwrap())
+    }
+
+    #[test]
+    fn join_to_a_base_url() {
+        assert_eq!(
+            config_url("wallet_url", "https://testnet.mynearwallet.com/")
+                .join("login/")
+                .unwrap()
+                .as_str(),
+            "https://testnet.mynearwallet.com/login/"
+        );
+    }
+
+    #[test]
+    fn join_to_a_url_that_is_not_a_base() {
+        let err = config_url("wallet_url", "mailto:wallet@example.com")
+            .join("login/")
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.starts_with(
+                "The URL `network_connection.testnet.wallet_url` = \"mailto:wallet@example.com\""
+            ),
+            "{err}"
+        );
+        assert!(err.contains("<login/> cannot be appended to it because it is not a base URL"));
+        assert!(err.ends_with(
+            "Fix it with: near config edit-connection testnet --key wallet_url --value <URL>"
+        ));
+    }
+
+    #[test]
+    fn transaction_links() {
+        assert_eq!(
+            config_url(
+                "explorer_transaction_url",
+                "https://explorer.testnet.near.org/transactions/"
+            )
+            .transaction("9Y7Sq")
+            .unwrap()
+            .as_str(),
+            "https://explorer.testnet.near.org/transactions/9Y7Sq"
+        );
+        let err = config_url(
+            "explorer_transaction_url",
+            "https://explorer.testnet.near.org/transactions",
+        )
+        .transaction("9Y7Sq")
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("`network_connection.testnet.explorer_transaction_url`"));
+        assert!(err.contains("does not end with '/'"), "{err}");
+    }
+}
@@ -0,0 +1,305 @@
+//! Loading of `KEY=VALUE` pairs from a `.env` file into the process environment.
+//!
+//! Variables that are already set in the environment are never overridden.
+
+use color_eyre::eyre::WrapErr;
+
+/// Used as the signer account ID when it is not given on the command line
+pub const NEAR_ACCOUNT_ID: &str = "NEAR_ACCOUNT_ID";
+/// Used as `--signer-private-key` of `sign-with-plaintext-private-key` when it is not given
+pub const NEAR_PRIVATE_KEY: &str = "NEAR_PRIVATE_KEY";
+/// Overrides the RPC URL of the network connection named by `NEAR_RPC_NETWORK`
+pub const NEAR_RPC_URL: &str = "NEAR_RPC_URL";
+/// The name of the network connection whose RPC URL `NEAR_RPC_URL` overrides
+pub const NEAR_RPC_NETWORK: &str = "NEAR_RPC_NETWORK";
+/// Used as `--credentials-dir` when it is not given
+pub const NEAR_CREDENTIALS_DIR: &str = "NEAR_CREDENTIALS_DIR";
+
+const DEFAULT_ENV_FILE: &str = ".env";
+
+/// Finds `--env-file <path>` among the top-level flags (the ones before the subcommand).
+pub fn env_file_from_args(args: impl IntoIterator<Item = String>) -> Option<std::path::PathBuf> {
+    let mut args = args.into_iter().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--env-file" {
+            return args.next().map(Into::into);
+        }
+        if let Some(env_file) = arg.strip_prefix("--env-file=") {
+            return Some(env_file.into());
+        }
+        if !arg.starts_with('-') {
+            break;
+        }
+    }
+    None
+}
+
+/// Loads the given env file, or `.env` from the current directory if it exists.
+pub fn load(env_file: Option<&std::path::Path>) -> color_eyre::eyre::Result<()> {
+    let (env_file, content) = match env_file {
+        Some(env_file) => (
+            env_file,
+            std::fs::read_to_string(env_file)
+                .wrap_err_with(|| format!("Failed to read the env file {env_file:?}"))?,
+        ),
+        None => {
+            let env_file = std::path::Path::new(DEFAULT_ENV_FILE);
+            match std::fs::read_to_string(env_file) {
+                Ok(content) => (env_file, content),
+                Err(_) => return Ok(()),
+            }
+        }
+    };
+    for (key, value) in
+        parse(&content).wrap_err_with(|| format!("Failed to parse the env file {env_file:?}"))?
+    {
+        if std::env::var_os(&key).is_none() {
+            std::env::set_var(key, value);
+        }
+    }
+    Ok(())
+}
+
+/// Returns the parsed value of the environment variable, if it is set and not empty.
+pub fn var<T>(key: &str) -> color_eyre::eyre::Result<Option<T>>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    match std::env::var(key) {
+        Ok(value) if !value.trim().is_empty() => value
+            .trim()
+            .parse()
+            .map(Some)
+            .map_err(|err| color_eyre::eyre::eyre!("Invalid value of {key}: {err}")),
+        _ => Ok(None),
+    }
+}
+
+/// Tells once per variable that its value from the environment is used instead of a prompt or
+/// of the config, so that an override from a forgotten `.env` file does not go unnoticed.
+pub fn notify_override(key: &str, message: impl std::fmt::Display) {
+    static NOTIFIED: std::sync::Mutex<std::collections::BTreeSet<String>> =
+        std::sync::Mutex::new(std::collections::BTreeSet::new());
+    let first_time = NOTIFIED
+        .lock()
+        .map_or(true, |mut notified| notified.insert(key.to_string()));
+    if first_time {
+        eprintln!("Note: {message} (from the environment variable {key})");
+    }
+}
+
+/// The RPC URL that overrides the one of the given network connection: `NEAR_RPC_URL` applies
+/// only to the connection named by `NEAR_RPC_NETWORK`.
+pub fn rpc_url_override(network_name: &str) -> color_eyre::eyre::Result<Option<url::Url>> {
+    let Some(rpc_url) = var::<url::Url>(NEAR_RPC_URL)? else {
+        return Ok(None);
+    };
+    match var::<String>(NEAR_RPC_NETWORK)? {
+        Some(rpc_network) if rpc_network == network_name => Ok(Some(rpc_url)),
+        Some(_) => Ok(None),
+        None => {
+            notify_override(
+                NEAR_RPC_URL,
+                format!(
+                    "{NEAR_RPC_URL} is ignored because {NEAR_RPC_NETWORK} does not name the network connection it overrides"
+                ),
+            );
+            Ok(None)
+        }
+    }
+}
+
+fn parse(content: &str) -> color_eyre::eyre::Result<Vec<(String, String)>> {
+    let mut variables: Vec<(String, String)> = Vec::new();
+    let mut lines = content.lines().enumerate();
+    while let Some((line_index, line)) = lines.next() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let (key, value) = line.split_once('=').ok_or_else(|| {
+            color_eyre::eyre::eyre!("Line {}: expected KEY=VALUE", line_index + 1)
+        })?;
+        let key = key.trim();
+        if key.is_empty()
+            || !key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+        {
+            color_eyre::eyre::bail!("Line {}: invalid variable name {key:?}", line_index + 1);
+        }
+        let lookup = |name: &str| {
+            std::env::var(name).ok().or_else(|| {
+                variables
+                    .iter()
+                    .rev()
+                    .find(|(key, _)| key == name)
+                    .map(|(_, value)| value.clone())
+            })
+        };
+        // A quoted value goes on over the next lines up to its closing quote
+        let mut value = value.trim().to_string();
+        let value = loop {
+            if let Some(value) = parse_value(&value, lookup) {
+                break value;
+            }
+            let Some((_, next_line)) = lines.next() else {
+                color_eyre::eyre::bail!("Line {}: unterminated quote", line_index + 1);
+            };
+            value.push('\n');
+            value.push_str(next_line);
+        };
+        variables.push((key.to_string(), value));
+    }
+    Ok(variables)
+}
+
+/// Single-quoted values are literal, double-quoted values support `\n`, `\"`, `\$` and `\\`
+/// escapes, and unquoted values end at an inline ` #` comment. `$NAME` and `${NAME}` in
+/// double-quoted and unquoted values are replaced with the variable from the environment or
+/// from an earlier line (or with nothing), as `dotenvy` does.
+fn parse_value(value: &str, lookup: impl Fn(&str) -> Option<String>) -> Option<String> {
+    if let Some(value) = value.strip_prefix('\'') {
+        return value.find('\'').map(|end| value[..end].to_string());
+    }
+    if let Some(value) = value.strip_prefix('"') {
+        let mut result = String::new();
+        let mut chars = value.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => return Some(result),
+                '\\' => match chars.next()? {
+                    'n' => result.push('\n'),
+                    c => result.push(c),
+                },
+                '$' => result.push_str(&substitute(&mut chars, &lookup)?),
+                c => result.push(c),
+            }
+        }
+        return None;
+    }
+    let value = value
+        .split_once(" #")
+        .map_or(value, |(value, _comment)| value)
+        .trim_end();
+    let mut result = String::new();
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '$' => result.push_str(&substitute(&mut chars, &lookup)?),
+            c => result.push(c),
+        }
+    }
+    Some(result)
+}
+
+/// The value of the variable named after a `$`, or the `$` itself if no name follows it.
+fn substitute(
+    chars: &mut std::iter::Peekable<std::str::Chars>,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Option<String> {
+    let name: String = if chars.next_if_eq(&'{').is_some() {
+        let name = std::iter::from_fn(|| chars.next_if(|c| *c != '}')).collect();
+        chars.next_if_eq(&'}')?;
+        name
+    } else {
+        std::iter::from_fn(|| chars.next_if(|c| c.is_ascii_alphanumeric() || *c == '_')).collect()
+    };
+    if name.is_empty() {
+        return Some("$".to_string());
+    }
+    Some(lookup(&name).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_env_file() {
+        let content = r#"
+# deployment settings
+NEAR_ACCOUNT_ID=alice.testnet # the signer
+export NEAR_RPC_URL = "https://rpc.testnet.near.org"
+NEAR_PRIVATE_KEY='ed25519:abc#def'
+MULTILINE="first\nsecond \"quoted\""
+EMPTY=
+"#;
+        assert_eq!(
+            parse(content).unwrap(),
+            vec![
+                ("NEAR_ACCOUNT_ID".to_string(), "alice.testnet".to_string()),
+                (
+                    "NEAR_RPC_URL".to_string(),
+                    "https://rpc.testnet.near.org".to_string()
+                ),
+                (
+                    "NEAR_PRIVATE_KEY".to_string(),
+                    "ed25519:abc#def".to_string()
+                ),
+                (
+                    "MULTILINE".to_string(),
+                    "first\nsecond \"quoted\"".to_string()
+                ),
+                ("EMPTY".to_string(), String::new()),
+            ]
+        );
+        assert!(parse("NO_VALUE").is_err());
+        assert!(parse("KEY=${UNTERMINATED").is_err());
+        assert!(parse("BAD KEY=1").is_err());
+        assert!(parse("KEY=\"unterminated").is_err());
+    }
+
+    #[test]
+    fn multiline_values_and_substitutions() {
+        let content = r#"
+NEAR_ENV_FILE_TEST_NETWORK=testnet
+NEAR_ENV_FILE_TEST_URL="https://rpc.${NEAR_ENV_FILE_TEST_NETWORK}.near.org"
+NEAR_ENV_FILE_TEST_NOTE='first line
+second line $NEAR_ENV_FILE_TEST_NETWORK'
+NEAR_ENV_FILE_TEST_PRICE="\$5 on $NEAR_ENV_FILE_TEST_NETWORK, $ NEAR"
+NEAR_ENV_FILE_TEST_MISSING=a${NEAR_ENV_FILE_TEST_UNSET}b
+"#;
+        assert_eq!(
+            parse(content).unwrap(),
+            vec![
+                (
+                    "NEAR_ENV_FILE_TEST_NETWORK".to_string(),
+                    "testnet".to_string()
+                ),
+                (
+                    "NEAR_ENV_FILE_TEST_URL".to_string(),
+                    "https://rpc.testnet.near.org".to_string()
+                ),
+                (
+                    "NEAR_ENV_FILE_TEST_NOTE".to_string(),
+                    "first line\nsecond line $NEAR_ENV_FILE_TEST_NETWORK".to_string()
+                ),
+                (
+                    "NEAR_ENV_FILE_TEST_PRICE".to_string(),
+                    "$5 on testnet, $ NEAR".to_string()
+                ),
+                ("NEAR_ENV_FILE_TEST_MISSING".to_string(), "ab".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn env_file_is_taken_from_top_level_flags_only() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            env_file_from_args(args(&["near", "--env-file", "deploy.env", "account"])),
+            Some("deploy.env".into())
+        );
+        assert_eq!(
+            env_file_from_args(args(&["near", "--offline", "--env-file=ci.env"])),
+            Some("ci.env".into())
+        );
+        assert_eq!(
+            env_file_from_args(args(&["near", "account", "--env-file", "x.env"])),
+            None
+        );
+    }
+}
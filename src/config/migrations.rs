@@ -26,11 +26,25 @@ impl From<ConfigV1> for ConfigV2 {
     fn from(config: ConfigV1) -> Self {
         ConfigV2 {
             credentials_home_dir: config.credentials_home_dir,
+            require_ledger_above: None,
+            wait_for_finality: false,
+            audit_log: false,
+            notify: Default::default(),
+            notify_after_secs: 20,
+            max_duration: None,
+            prompt_style: Default::default(),
+            credentials_dir_override: None,
             network_connection: config
                 .network_connection
                 .into_iter()
                 .map(|(network_name, network_config)| (network_name, network_config.into()))
                 .collect(),
+            watchlist: Vec::new(),
+            known_contracts: Default::default(),
+            default_network: None,
+            default_profile: None,
+            profiles: Default::default(),
+            applied_profile: None,
         }
     }
 }
@@ -52,6 +66,10 @@ impl From<NetworkConfigV1> for NetworkConfigV2 {
                 fastnear_url: Some("https://api.fastnear.com".parse().unwrap()),
                 staking_pools_factory_account_id: Some("poolv1.near".parse().unwrap()),
                 coingecko_url: Some("https://api.coingecko.com/".parse().unwrap()),
+                indexer_url: Some("https://api.nearblocks.io/".parse().unwrap()),
+                credentials_dir: None,
+                rpc_rate_limit_rps: None,
+                price_oracle_account_id: Some("priceoracle.near".parse().unwrap()),
             },
             "testnet" => NetworkConfigV2 {
                 network_name: network_config.network_name,
@@ -67,6 +85,10 @@ impl From<NetworkConfigV1> for NetworkConfigV2 {
                 fastnear_url: None,
                 staking_pools_factory_account_id: Some("pool.f863973.m0".parse().unwrap()),
                 coingecko_url: None,
+                indexer_url: Some("https://api-testnet.nearblocks.io/".parse().unwrap()),
+                credentials_dir: None,
+                rpc_rate_limit_rps: None,
+                price_oracle_account_id: None,
             },
             _ => NetworkConfigV2 {
                 network_name: network_config.network_name,
@@ -82,6 +104,10 @@ impl From<NetworkConfigV1> for NetworkConfigV2 {
                 fastnear_url: None,
                 staking_pools_factory_account_id: None,
                 coingecko_url: None,
+                indexer_url: None,
+                credentials_dir: None,
+                rpc_rate_limit_rps: None,
+                price_oracle_account_id: None,
             },
         }
     }
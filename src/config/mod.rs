@@ -1,4 +1,8 @@
+pub mod bundle;
+pub mod config_url;
+pub mod env_file;
 mod migrations;
+pub mod profiles;
 
 pub type CliResult = color_eyre::eyre::Result<()>;
 
@@ -9,7 +13,54 @@ use tracing_indicatif::span_ext::IndicatifSpanExt;
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Config {
     pub credentials_home_dir: std::path::PathBuf,
+    /// Transactions on mainnet with a total outgoing value above this amount must be signed with Ledger
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "near_token_as_string"
+    )]
+    pub require_ledger_above: Option<crate::types::near_token::NearToken>,
+    /// Wait for the transaction finality after broadcasting it (as if `--watch-tx` was passed)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub wait_for_finality: bool,
+    /// Append every transaction that is signed or sent to the audit log (see `audit-log show`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub audit_log: bool,
+    /// When to show a desktop notification about the transaction completion (as if `--notify` was passed)
+    #[serde(default)]
+    pub notify: crate::notification::NotifyMode,
+    /// The duration of a transaction after which it is notified with `--notify long`
+    #[serde(default = "default_notify_after_secs")]
+    pub notify_after_secs: u64,
+    /// Exit with code 124 when a command takes longer than this (as if `--max-duration` was passed)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_duration: Option<crate::types::duration::Duration>,
+    /// `plain` renders the prompts as numbered lists without colors (as if `--plain-prompts` was passed)
+    #[serde(default)]
+    pub prompt_style: crate::prompt_style::PromptStyle,
+    /// The credentials directory passed with `--credentials-dir` or `NEAR_CREDENTIALS_DIR`, it
+    /// takes precedence over the directories of the config for this run only
+    #[serde(skip)]
+    pub credentials_dir_override: Option<std::path::PathBuf>,
     pub network_connection: linked_hash_map::LinkedHashMap<String, NetworkConfig>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub watchlist: Vec<WatchedAccount>,
+    /// Code hashes of contracts that get a specialized section in the account summary
+    /// (in addition to the builtin SputnikDAO ones, the multisig contracts are only known from here)
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub known_contracts:
+        std::collections::BTreeMap<near_primitives::hash::CryptoHash, KnownContractKind>,
+    /// The network connection offered first when the network is selected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_network: Option<String>,
+    /// The profile used when neither `--profile` nor `NEAR_CLI_PROFILE` is given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_profile: Option<String>,
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub profiles: std::collections::BTreeMap<String, profiles::ConfigProfile>,
+    /// The profile whose settings replaced the global ones for this run
+    #[serde(skip)]
+    pub applied_profile: Option<profiles::AppliedProfile>,
 }
 
 impl Default for Config {
@@ -36,6 +87,10 @@ impl Default for Config {
                 fastnear_url: Some("https://api.fastnear.com/".parse().unwrap()),
                 staking_pools_factory_account_id: Some("poolv1.near".parse().unwrap()),
                 coingecko_url: Some("https://api.coingecko.com/".parse().unwrap()),
+                indexer_url: Some("https://api.nearblocks.io/".parse().unwrap()),
+                credentials_dir: None,
+                rpc_rate_limit_rps: None,
+                price_oracle_account_id: Some("priceoracle.near".parse().unwrap()),
             },
         );
         network_connection.insert(
@@ -55,16 +110,38 @@ impl Default for Config {
                 fastnear_url: None,
                 staking_pools_factory_account_id: Some("pool.f863973.m0".parse().unwrap()),
                 coingecko_url: None,
+                indexer_url: Some("https://api-testnet.nearblocks.io/".parse().unwrap()),
+                credentials_dir: None,
+                rpc_rate_limit_rps: None,
+                price_oracle_account_id: None,
             },
         );
 
         Self {
             credentials_home_dir,
+            require_ledger_above: None,
+            wait_for_finality: false,
+            audit_log: false,
+            notify: Default::default(),
+            notify_after_secs: default_notify_after_secs(),
+            max_duration: None,
+            prompt_style: Default::default(),
+            credentials_dir_override: None,
             network_connection,
+            watchlist: Vec::new(),
+            known_contracts: Default::default(),
+            default_network: None,
+            default_profile: None,
+            profiles: Default::default(),
+            applied_profile: None,
         }
     }
 }
 
+fn default_notify_after_secs() -> u64 {
+    20
+}
+
 impl Config {
     pub fn network_names(&self) -> Vec<String> {
         self.network_connection
@@ -73,40 +150,123 @@ impl Config {
             .collect()
     }
 
+    /// The legacy keychain directory for the network connection: `--credentials-dir`, then
+    /// `NEAR_CREDENTIALS_DIR`, then the `credentials_dir` of the applied profile, then the global
+    /// settings (the `credentials_dir` of the network connection, then `credentials_home_dir`).
+    /// The keys are stored in its `<network_name>` subdirectory.
+    pub fn credentials_dir(&self, network_config: &NetworkConfig) -> std::path::PathBuf {
+        let profile_credentials_dir = self
+            .applied_profile
+            .as_ref()
+            .filter(|applied_profile| applied_profile.overrides_credentials_dir())
+            .map(|_| &self.credentials_home_dir);
+        self.credentials_dir_override
+            .as_ref()
+            .or(profile_credentials_dir)
+            .or(network_config.credentials_dir.as_ref())
+            .unwrap_or(&self.credentials_home_dir)
+            .clone()
+    }
+
+    /// Sets the directory that overrides the ones of the config: `--credentials-dir` (`flag`),
+    /// then `NEAR_CREDENTIALS_DIR` (`env`).
+    pub fn with_credentials_dir_override(
+        mut self,
+        flag: Option<std::path::PathBuf>,
+        env: Option<std::path::PathBuf>,
+    ) -> Self {
+        self.credentials_dir_override = flag.or(env);
+        self
+    }
+
+    /// All the distinct legacy keychain directories with the network connections resolved to them.
+    pub fn credentials_dirs(&self) -> Vec<(std::path::PathBuf, Vec<&NetworkConfig>)> {
+        let mut credentials_dirs: Vec<(std::path::PathBuf, Vec<&NetworkConfig>)> = Vec::new();
+        for network_config in self.network_connection.values() {
+            let credentials_dir = self.credentials_dir(network_config);
+            match credentials_dirs
+                .iter_mut()
+                .find(|(dir, _)| *dir == credentials_dir)
+            {
+                Some((_, network_configs)) => network_configs.push(network_config),
+                None => credentials_dirs.push((credentials_dir, vec![network_config])),
+            }
+        }
+        credentials_dirs
+    }
+
     pub fn into_latest_version(self) -> migrations::ConfigVersion {
         migrations::ConfigVersion::V2(self)
     }
 
-    pub fn get_config_toml() -> color_eyre::eyre::Result<Self> {
-        if let Some(mut path_config_toml) = dirs::config_dir() {
+    /// The location of `config.toml` (`None` if there is no config dir on this platform).
+    pub fn config_toml_path() -> Option<std::path::PathBuf> {
+        dirs::config_dir().map(|mut path_config_toml| {
             path_config_toml.extend(&["near-cli", "config.toml"]);
+            path_config_toml
+        })
+    }
 
+    /// Loads `config.toml` with the settings of the selected profile (see [`profiles`]).
+    pub fn get_config_toml(profile: Option<&str>) -> color_eyre::eyre::Result<Self> {
+        Self::read_config_toml()?.with_profile(
+            profile,
+            std::env::var(profiles::NEAR_CLI_PROFILE).ok().as_deref(),
+        )
+    }
+
+    /// Applies the profile selected with `--profile` (`flag`), `NEAR_CLI_PROFILE` (`env`) or
+    /// `default_profile`, in this order.
+    pub fn with_profile(
+        mut self,
+        flag: Option<&str>,
+        env: Option<&str>,
+    ) -> color_eyre::eyre::Result<Self> {
+        if let Some(profile_name) =
+            profiles::resolve_profile_name(flag, env, self.default_profile.as_deref())
+        {
+            self.apply_profile(&profile_name)?;
+        }
+        Ok(self)
+    }
+
+    fn read_config_toml() -> color_eyre::eyre::Result<Self> {
+        if let Some(path_config_toml) = Self::config_toml_path() {
             if !path_config_toml.is_file() {
                 Self::write_config_toml(crate::config::Config::default())?;
             };
 
             let config_toml = std::fs::read_to_string(&path_config_toml)?;
 
-            let config_version = toml::from_str::<migrations::ConfigVersion>(&config_toml).or_else::<color_eyre::eyre::Report, _>(|err| {
-                if let Ok(config_v1) = toml::from_str::<migrations::ConfigV1>(&config_toml) {
-                    Ok(migrations::ConfigVersion::V1(config_v1))
-                } else {
+            match Self::parse_config_toml(&config_toml) {
+                Ok(config) => Ok(config),
+                Err(err) => {
                     eprintln!("Warning: `near` CLI configuration file stored at {path_config_toml:?} could not be parsed due to: {err}");
-                    eprintln!("Note: The default configuration printed below will be used instead:\n");
+                    eprintln!(
+                        "Note: The default configuration printed below will be used instead:\n"
+                    );
                     let default_config = crate::config::Config::default();
                     eprintln!("{}", toml::to_string(&default_config)?);
-                    Ok(default_config.into_latest_version())
+                    Ok(default_config)
                 }
-            })?;
-
-            Ok(config_version.into())
+            }
         } else {
             Ok(crate::config::Config::default())
         }
     }
 
+    /// Parses the content of `config.toml` of any version.
+    pub fn parse_config_toml(config_toml: &str) -> Result<Self, toml::de::Error> {
+        match toml::from_str::<migrations::ConfigVersion>(config_toml) {
+            Ok(config_version) => Ok(config_version.into()),
+            Err(err) => toml::from_str::<migrations::ConfigV1>(config_toml)
+                .map(|config_v1| migrations::ConfigVersion::V1(config_v1).into())
+                .map_err(|_| err),
+        }
+    }
+
     pub fn write_config_toml(self) -> CliResult {
-        let config_toml = toml::to_string(&self.into_latest_version())?;
+        let config_toml = toml::to_string(&self.without_applied_profile().into_latest_version())?;
         let mut path_config_toml =
             dirs::config_dir().wrap_err("Impossible to get your config dir!")?;
 
@@ -141,6 +301,17 @@ pub struct NetworkConfig {
     pub fastnear_url: Option<url::Url>,
     pub staking_pools_factory_account_id: Option<near_primitives::types::AccountId>,
     pub coingecko_url: Option<url::Url>,
+    /// An indexer with the Nearblocks API, e.g. to look up the transactions that added the access keys
+    pub indexer_url: Option<url::Url>,
+    /// The legacy keychain directory of this network connection (instead of `credentials_home_dir`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credentials_dir: Option<std::path::PathBuf>,
+    /// Client-side limit of RPC requests per second (default: 10 for the public endpoints, 0 disables the limit)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rpc_rate_limit_rps: Option<u32>,
+    /// A price oracle contract with `get_price_data` (e.g. priceoracle.near) for `tokens view-near-price`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_oracle_account_id: Option<near_primitives::types::AccountId>,
 }
 
 impl NetworkConfig {
@@ -157,10 +328,33 @@ impl NetworkConfig {
 
     #[tracing::instrument(name = "Connecting to RPC", skip_all)]
     pub fn json_rpc_client(&self) -> near_jsonrpc_client::JsonRpcClient {
-        tracing::Span::current().pb_set_message(self.rpc_url.as_str());
-        let mut json_rpc_client =
-            near_jsonrpc_client::JsonRpcClient::connect(self.rpc_url.as_ref());
-        if let Some(rpc_api_key) = &self.rpc_api_key {
+        let rpc_url_override = match env_file::rpc_url_override(&self.network_name) {
+            Ok(rpc_url_override) => rpc_url_override,
+            Err(err) => {
+                tracing::warn!("{err}, using the RPC URL from the config: {}", self.rpc_url);
+                None
+            }
+        };
+        if let Some(rpc_url_override) = &rpc_url_override {
+            env_file::notify_override(
+                env_file::NEAR_RPC_URL,
+                format!(
+                    "the RPC of network <{}> is {rpc_url_override} instead of {}, without the API key of the config",
+                    self.network_name, self.rpc_url
+                ),
+            );
+        }
+        let rpc_url = rpc_url_override
+            .clone()
+            .unwrap_or_else(|| self.rpc_url.clone());
+        tracing::Span::current().pb_set_message(rpc_url.as_str());
+        crate::rpc_rate_limit::set_rpc_rate_limit(
+            rpc_url.as_str(),
+            crate::rpc_rate_limit::rpc_rate_limit_rps(&rpc_url, self.rpc_rate_limit_rps),
+        );
+        let mut json_rpc_client = near_jsonrpc_client::JsonRpcClient::connect(rpc_url.as_ref());
+        // The API key of the config is meant for its RPC, not for the one of the override
+        if let (Some(rpc_api_key), None) = (&self.rpc_api_key, &rpc_url_override) {
             json_rpc_client =
                 json_rpc_client.header(near_jsonrpc_client::auth::ApiKey::from(rpc_api_key.clone()))
         };
@@ -185,6 +379,53 @@ impl NetworkConfig {
     }
 }
 
+/// (De)serializes amounts in a human-readable form, e.g. `require_ledger_above = "100 NEAR"`
+mod near_token_as_string {
+    use std::str::FromStr;
+
+    use serde::Deserialize;
+
+    pub fn serialize<S>(
+        value: &Option<crate::types::near_token::NearToken>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match value {
+            Some(amount) => serializer.serialize_str(&amount.to_string()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(
+        deserializer: D,
+    ) -> Result<Option<crate::types::near_token::NearToken>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Option::<String>::deserialize(deserializer)?
+            .map(|amount| {
+                crate::types::near_token::NearToken::from_str(&amount)
+                    .map_err(serde::de::Error::custom)
+            })
+            .transpose()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct WatchedAccount {
+    pub account_id: near_primitives::types::AccountId,
+    pub network_name: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KnownContractKind {
+    Multisig,
+    SputnikDao,
+}
+
 impl From<migrations::ConfigVersion> for Config {
     fn from(mut config_version: migrations::ConfigVersion) -> Self {
         loop {
@@ -199,3 +440,84 @@ impl From<migrations::ConfigVersion> for Config {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn credentials_dir_precedence() {
+        let mut config = Config {
+            credentials_home_dir: "/home/near/.near-credentials".into(),
+            ..Default::default()
+        };
+        config.profiles.insert(
+            "work".to_string(),
+            profiles::ConfigProfile {
+                credentials_dir: Some("/mnt/company/keys".into()),
+                ..Default::default()
+            },
+        );
+        config
+            .network_connection
+            .get_mut("mainnet")
+            .unwrap()
+            .credentials_dir = Some("/mnt/vault".into());
+        let mainnet = config.network_connection["mainnet"].clone();
+        let testnet = config.network_connection["testnet"].clone();
+
+        assert_eq!(
+            config.credentials_dir(&mainnet),
+            std::path::PathBuf::from("/mnt/vault")
+        );
+        assert_eq!(
+            config.credentials_dir(&testnet),
+            std::path::PathBuf::from("/home/near/.near-credentials")
+        );
+        assert_eq!(config.credentials_dirs().len(), 2);
+
+        // profile > global, the network connection directory included
+        let mut config = config.with_profile(Some("work"), None).unwrap();
+        assert_eq!(
+            config.credentials_dir(&mainnet),
+            std::path::PathBuf::from("/mnt/company/keys")
+        );
+        assert_eq!(config.credentials_dirs().len(), 1);
+
+        // env > profile
+        config = config.with_credentials_dir_override(None, Some("/run/keys".into()));
+        assert_eq!(
+            config.credentials_dir(&mainnet),
+            std::path::PathBuf::from("/run/keys")
+        );
+
+        // flag > env
+        config = config
+            .with_credentials_dir_override(Some("/tmp/keys".into()), Some("/run/keys".into()));
+        assert_eq!(
+            config.credentials_dir(&mainnet),
+            std::path::PathBuf::from("/tmp/keys")
+        );
+        assert_eq!(config.credentials_dirs().len(), 1);
+        assert!(!toml::to_string(&config).unwrap().contains("/tmp/keys"));
+
+        // without a credentials_dir the profile keeps the global directories
+        let mut config = Config {
+            credentials_home_dir: "/home/near/.near-credentials".into(),
+            ..Default::default()
+        };
+        config
+            .network_connection
+            .get_mut("mainnet")
+            .unwrap()
+            .credentials_dir = Some("/mnt/vault".into());
+        config
+            .profiles
+            .insert("personal".to_string(), Default::default());
+        let config = config.with_profile(Some("personal"), None).unwrap();
+        assert_eq!(
+            config.credentials_dir(&mainnet),
+            std::path::PathBuf::from("/mnt/vault")
+        );
+    }
+}
@@ -0,0 +1,338 @@
+//! Named profiles of the configuration (`[profiles.work]`, `[profiles.personal]`) that override
+//! a few global settings, e.g. to switch between a personal and a company identity.
+//!
+//! The profile is selected with `--profile`, then `NEAR_CLI_PROFILE`, then the `default_profile`
+//! of the config (see `config profile use`); without any of them the global settings are used.
+
+/// Selects the profile when `--profile` is not passed
+pub const NEAR_CLI_PROFILE: &str = "NEAR_CLI_PROFILE";
+/// The name that selects no profile (e.g. `config profile use global`)
+pub const GLOBAL_PROFILE_NAME: &str = "global";
+
+/// The settings that a profile overrides; the ones that are not set are taken from the global
+/// settings.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigProfile {
+    /// The network connection offered first when the network is selected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_network: Option<String>,
+    /// The legacy keychain directory (instead of `credentials_home_dir` and the `credentials_dir`
+    /// of the network connections)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credentials_dir: Option<std::path::PathBuf>,
+    #[serde(default, skip_serializing_if = "ProfileDefaults::is_empty")]
+    pub defaults: ProfileDefaults,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub watchlist: Option<Vec<super::WatchedAccount>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProfileDefaults {
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "super::near_token_as_string"
+    )]
+    pub require_ledger_above: Option<crate::types::near_token::NearToken>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_for_finality: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notify: Option<crate::notification::NotifyMode>,
+}
+
+impl AppliedProfile {
+    /// Whether the `credentials_home_dir` of the config is the `credentials_dir` of the profile
+    pub fn overrides_credentials_dir(&self) -> bool {
+        self.overrides_credentials_dir
+    }
+}
+
+impl ProfileDefaults {
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// The profile applied to the loaded config, with the global values it replaced, so that the
+/// config is written back with the global settings untouched.
+#[derive(Debug, Clone)]
+pub struct AppliedProfile {
+    pub name: String,
+    overrides_credentials_dir: bool,
+    global: GlobalSettings,
+}
+
+#[derive(Debug, Clone)]
+struct GlobalSettings {
+    default_network: Option<String>,
+    credentials_home_dir: std::path::PathBuf,
+    require_ledger_above: Option<crate::types::near_token::NearToken>,
+    wait_for_finality: bool,
+    notify: crate::notification::NotifyMode,
+    watchlist: Vec<super::WatchedAccount>,
+}
+
+/// The name of the selected profile: `--profile`, then `NEAR_CLI_PROFILE`, then the default
+/// profile of the config (`None` for the global settings).
+pub fn resolve_profile_name(
+    flag: Option<&str>,
+    env: Option<&str>,
+    default_profile: Option<&str>,
+) -> Option<String> {
+    [flag, env, default_profile]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .find(|name| !name.is_empty())
+        .filter(|name| *name != GLOBAL_PROFILE_NAME)
+        .map(str::to_string)
+}
+
+pub fn validate_profile_name(name: &str) -> color_eyre::eyre::Result<()> {
+    if name == GLOBAL_PROFILE_NAME {
+        color_eyre::eyre::bail!(
+            "The profile name \"{GLOBAL_PROFILE_NAME}\" is reserved: it selects the global settings"
+        );
+    }
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        color_eyre::eyre::bail!(
+            "Invalid profile name \"{name}\": only letters, digits, '-' and '_' are allowed"
+        );
+    }
+    Ok(())
+}
+
+impl super::Config {
+    /// Replaces the global settings with the ones overridden by the profile.
+    pub fn apply_profile(&mut self, name: &str) -> color_eyre::eyre::Result<()> {
+        let profile = self.profiles.get(name).cloned().ok_or_else(|| {
+            color_eyre::eyre::eyre!(
+                "There is no profile \"{name}\" in the config (the profiles are: {})",
+                self.profile_names().join(", ")
+            )
+        })?;
+        if let Some(default_network) = &profile.default_network {
+            if !self.network_connection.contains_key(default_network) {
+                color_eyre::eyre::bail!(
+                    "The default network \"{default_network}\" of profile \"{name}\" is not a network connection of the config"
+                );
+            }
+        }
+        let global = GlobalSettings {
+            default_network: self.default_network.clone(),
+            credentials_home_dir: self.credentials_home_dir.clone(),
+            require_ledger_above: self.require_ledger_above,
+            wait_for_finality: self.wait_for_finality,
+            notify: self.notify,
+            watchlist: self.watchlist.clone(),
+        };
+        if let Some(default_network) = profile.default_network {
+            self.default_network = Some(default_network);
+        }
+        let overrides_credentials_dir = profile.credentials_dir.is_some();
+        if let Some(credentials_dir) = profile.credentials_dir {
+            self.credentials_home_dir = credentials_dir;
+        }
+        if let Some(require_ledger_above) = profile.defaults.require_ledger_above {
+            self.require_ledger_above = Some(require_ledger_above);
+        }
+        if let Some(wait_for_finality) = profile.defaults.wait_for_finality {
+            self.wait_for_finality = wait_for_finality;
+        }
+        if let Some(notify) = profile.defaults.notify {
+            self.notify = notify;
+        }
+        if let Some(watchlist) = profile.watchlist {
+            self.watchlist = watchlist;
+        }
+        self.applied_profile = Some(AppliedProfile {
+            name: name.to_string(),
+            overrides_credentials_dir,
+            global,
+        });
+        Ok(())
+    }
+
+    /// The config as it is stored: the settings overridden by the applied profile are saved in
+    /// the profile (e.g. a watchlist edited while the profile is used) and the global ones are
+    /// restored.
+    pub fn without_applied_profile(mut self) -> Self {
+        let Some(AppliedProfile { name, global, .. }) = self.applied_profile.take() else {
+            return self;
+        };
+        let Some(profile) = self.profiles.get_mut(&name) else {
+            return self;
+        };
+        if profile.default_network.is_some() {
+            profile.default_network =
+                std::mem::replace(&mut self.default_network, global.default_network);
+        }
+        if profile.credentials_dir.is_some() {
+            profile.credentials_dir = Some(std::mem::replace(
+                &mut self.credentials_home_dir,
+                global.credentials_home_dir,
+            ));
+        }
+        if profile.defaults.require_ledger_above.is_some() {
+            profile.defaults.require_ledger_above =
+                std::mem::replace(&mut self.require_ledger_above, global.require_ledger_above);
+        }
+        if profile.defaults.wait_for_finality.is_some() {
+            profile.defaults.wait_for_finality = Some(std::mem::replace(
+                &mut self.wait_for_finality,
+                global.wait_for_finality,
+            ));
+        }
+        if profile.defaults.notify.is_some() {
+            profile.defaults.notify = Some(std::mem::replace(&mut self.notify, global.notify));
+        }
+        if profile.watchlist.is_some() {
+            profile.watchlist = Some(std::mem::replace(&mut self.watchlist, global.watchlist));
+        }
+        self
+    }
+
+    pub fn profile_names(&self) -> Vec<String> {
+        self.profiles.keys().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG_TOML: &str = r#"
+version = "2"
+credentials_home_dir = "/home/near/.near-credentials"
+wait_for_finality = true
+default_profile = "personal"
+
+[[watchlist]]
+account_id = "alice.near"
+network_name = "mainnet"
+
+[network_connection.mainnet]
+network_name = "mainnet"
+rpc_url = "https://archival-rpc.mainnet.near.org/"
+wallet_url = "https://app.mynearwallet.com/"
+explorer_transaction_url = "https://explorer.near.org/transactions/"
+
+[network_connection.testnet]
+network_name = "testnet"
+rpc_url = "https://archival-rpc.testnet.near.org/"
+wallet_url = "https://testnet.mynearwallet.com/"
+explorer_transaction_url = "https://explorer.testnet.near.org/transactions/"
+
+[profiles.work]
+default_network = "testnet"
+credentials_dir = "/mnt/company/keys"
+watchlist = []
+
+[profiles.work.defaults]
+require_ledger_above = "10 NEAR"
+wait_for_finality = false
+
+[profiles.personal]
+"#;
+
+    fn load(
+        flag: Option<&str>,
+        env: Option<&str>,
+    ) -> color_eyre::eyre::Result<super::super::Config> {
+        super::super::Config::parse_config_toml(CONFIG_TOML)
+            .unwrap()
+            .with_profile(flag, env)
+    }
+
+    #[test]
+    fn profile_name_precedence() {
+        assert_eq!(
+            resolve_profile_name(Some("work"), Some("personal"), Some("other")),
+            Some("work".to_string())
+        );
+        assert_eq!(
+            resolve_profile_name(None, Some("personal"), Some("other")),
+            Some("personal".to_string())
+        );
+        assert_eq!(
+            resolve_profile_name(None, Some(" "), Some("other")),
+            Some("other".to_string())
+        );
+        assert_eq!(resolve_profile_name(None, None, None), None);
+        assert_eq!(
+            resolve_profile_name(Some("global"), Some("personal"), Some("other")),
+            None
+        );
+    }
+
+    #[test]
+    fn profile_overrides_the_global_settings() {
+        let config = load(Some("work"), None).unwrap();
+        assert_eq!(config.default_network.as_deref(), Some("testnet"));
+        assert_eq!(
+            config.credentials_home_dir,
+            std::path::PathBuf::from("/mnt/company/keys")
+        );
+        assert_eq!(
+            config.require_ledger_above,
+            Some("10 NEAR".parse().unwrap())
+        );
+        assert!(!config.wait_for_finality);
+        assert!(config.watchlist.is_empty());
+        // not overridden by the profile
+        assert_eq!(config.notify, crate::notification::NotifyMode::default());
+
+        let config = load(None, Some("work")).unwrap();
+        assert_eq!(config.applied_profile.unwrap().name, "work");
+
+        // the default profile overrides nothing
+        let config = load(None, None).unwrap();
+        assert_eq!(config.applied_profile.as_ref().unwrap().name, "personal");
+        assert_eq!(
+            config.credentials_home_dir,
+            std::path::PathBuf::from("/home/near/.near-credentials")
+        );
+        assert!(config.wait_for_finality);
+        assert_eq!(config.watchlist.len(), 1);
+
+        let config = load(Some("global"), None).unwrap();
+        assert!(config.applied_profile.is_none());
+
+        assert!(load(Some("unknown"), None).is_err());
+    }
+
+    #[test]
+    fn edits_under_a_profile_are_saved_in_the_profile() {
+        let mut config = load(Some("work"), None).unwrap();
+        config.watchlist.push(super::super::WatchedAccount {
+            account_id: "company.testnet".parse().unwrap(),
+            network_name: "testnet".to_string(),
+        });
+        config.notify = crate::notification::NotifyMode::Always;
+
+        let stored = config.without_applied_profile();
+        assert_eq!(stored.watchlist.len(), 1);
+        assert_eq!(stored.watchlist[0].account_id.as_str(), "alice.near");
+        assert!(stored.wait_for_finality);
+        assert_eq!(
+            stored.credentials_home_dir,
+            std::path::PathBuf::from("/home/near/.near-credentials")
+        );
+        assert_eq!(stored.default_network, None);
+        // not overridden by the profile, so it is a global edit
+        assert_eq!(stored.notify, crate::notification::NotifyMode::Always);
+        let work = &stored.profiles["work"];
+        assert_eq!(work.watchlist.as_ref().unwrap().len(), 1);
+        assert_eq!(work.defaults.wait_for_finality, Some(false));
+        assert_eq!(
+            work.credentials_dir,
+            Some(std::path::PathBuf::from("/mnt/company/keys"))
+        );
+    }
+}
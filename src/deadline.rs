@@ -0,0 +1,170 @@
+//! The global deadline of a command (`--max-duration`): the command runs under
+//! `tokio::time::timeout`, and when it takes longer the CLI exits with the last stage it was in
+//! (the last progress span that was entered) and the hashes of the transactions that were already
+//! broadcast.
+
+use color_eyre::owo_colors::OwoColorize;
+
+/// The exit code when the deadline is exceeded (as the `timeout` utility)
+pub const EXIT_CODE: i32 = 124;
+
+static STAGE: std::sync::Mutex<Option<&'static str>> = std::sync::Mutex::new(None);
+static TRANSACTION_HASHES: std::sync::Mutex<Vec<near_primitives::hash::CryptoHash>> =
+    std::sync::Mutex::new(Vec::new());
+
+/// Records the stages from the names of the spans (e.g. "Sending transaction ...").
+pub struct StageLayer;
+
+impl<S> tracing_subscriber::Layer<S> for StageLayer
+where
+    S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+{
+    fn on_enter(&self, id: &tracing::span::Id, ctx: tracing_subscriber::layer::Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if let Ok(mut stage) = STAGE.lock() {
+                *stage = Some(span.name());
+            }
+        }
+    }
+}
+
+/// Called before a transaction is broadcast (or once a wallet has sent it), so that the timeout
+/// message points to it.
+pub fn record_transaction_hash(transaction_hash: near_primitives::hash::CryptoHash) {
+    if let Ok(mut transaction_hashes) = TRANSACTION_HASHES.lock() {
+        transaction_hashes.push(transaction_hash);
+    }
+}
+
+/// The error of a command that did not finish within `--max-duration`
+#[derive(Debug)]
+pub struct DeadlineExceeded(String);
+
+impl std::fmt::Display for DeadlineExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for DeadlineExceeded {}
+
+/// Runs the command, or fails with `DeadlineExceeded` once `max_duration` has passed. The command
+/// runs on a blocking thread (it makes its own blocking calls); when the deadline is exceeded it
+/// is abandoned and stops with the process.
+pub fn run<T: Send + 'static>(
+    max_duration: Option<crate::types::duration::Duration>,
+    command: impl FnOnce() -> T + Send + 'static,
+) -> color_eyre::eyre::Result<T> {
+    let Some(max_duration) = max_duration else {
+        return Ok(command());
+    };
+    let runtime = crate::common::multi_thread_runtime()?;
+    let result = runtime.block_on(async {
+        tokio::time::timeout(max_duration.0, tokio::task::spawn_blocking(command)).await
+    });
+    // Does not wait for an abandoned command (e.g. waiting for an input)
+    runtime.shutdown_background();
+    match result {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(join_error)) => match join_error.try_into_panic() {
+            Ok(panic) => std::panic::resume_unwind(panic),
+            Err(join_error) => Err(color_eyre::eyre::eyre!(
+                "The command was cancelled: {join_error}"
+            )),
+        },
+        Err(_elapsed) => {
+            let stage = STAGE.lock().ok().and_then(|stage| *stage);
+            let transaction_hashes = TRANSACTION_HASHES
+                .lock()
+                .map(|transaction_hashes| transaction_hashes.clone())
+                .unwrap_or_default();
+            Err(DeadlineExceeded(timeout_message(max_duration, stage, &transaction_hashes)).into())
+        }
+    }
+}
+
+/// Exits with `EXIT_CODE` if the error is `DeadlineExceeded`, otherwise returns it.
+pub fn exit_if_exceeded(err: color_eyre::eyre::Report) -> color_eyre::eyre::Report {
+    match err.downcast::<DeadlineExceeded>() {
+        Ok(deadline_exceeded) => {
+            eprintln!("\n{}", deadline_exceeded.red());
+            std::process::exit(EXIT_CODE);
+        }
+        Err(err) => err,
+    }
+}
+
+fn timeout_message(
+    max_duration: crate::types::duration::Duration,
+    stage: Option<&str>,
+    transaction_hashes: &[near_primitives::hash::CryptoHash],
+) -> String {
+    let mut message = format!("The command did not finish within --max-duration {max_duration}");
+    match stage {
+        Some(stage) => message.push_str(&format!(
+            " (last stage: {})",
+            stage.trim_end_matches(['.', ' '])
+        )),
+        None => message.push_str(" (before any network request, e.g. waiting for an input)"),
+    }
+    message.push('.');
+    match transaction_hashes {
+        [] => {}
+        [transaction_hash] => message.push_str(&format!(
+            "\nThe transaction {transaction_hash} was already sent: check its status with `near transaction view-status {transaction_hash}` before sending it again."
+        )),
+        transaction_hashes => message.push_str(&format!(
+            "\nThe transactions {} were already sent: check their status with `near transaction view-status <transaction-hash>` before sending them again.",
+            transaction_hashes
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timeout_message_with_the_stage_and_the_transaction() {
+        let max_duration: crate::types::duration::Duration = "90s".parse().unwrap();
+        assert_eq!(
+            timeout_message(max_duration, Some("Getting a list of validators ..."), &[]),
+            "The command did not finish within --max-duration 90s (last stage: Getting a list of validators)."
+        );
+        let transaction_hash: near_primitives::hash::CryptoHash =
+            "9FtHUFBQsZ2MG77K3x3MJ9wjX3UT8zE1TczCrhZEcG8U"
+                .parse()
+                .unwrap();
+        let message = timeout_message(
+            max_duration,
+            Some("Waiting for the transaction finality"),
+            &[transaction_hash],
+        );
+        assert!(message.starts_with(
+            "The command did not finish within --max-duration 90s (last stage: Waiting for the transaction finality).\nThe transaction 9FtHUFBQ"
+        ));
+        assert!(timeout_message(max_duration, None, &[]).contains("before any network request"));
+        assert!(
+            timeout_message(max_duration, None, &[transaction_hash, transaction_hash]).contains(
+                "The transactions 9FtHUFBQsZ2MG77K3x3MJ9wjX3UT8zE1TczCrhZEcG8U, 9FtHUFBQ"
+            )
+        );
+    }
+
+    #[test]
+    fn the_command_is_abandoned_after_the_deadline() {
+        let max_duration = crate::types::duration::Duration(std::time::Duration::from_millis(50));
+        assert_eq!(run(Some(max_duration), || 42).unwrap(), 42);
+        assert_eq!(run(None, || 42).unwrap(), 42);
+        let err = run(Some(max_duration), || {
+            std::thread::sleep(std::time::Duration::from_secs(5))
+        })
+        .unwrap_err();
+        assert!(err.downcast_ref::<DeadlineExceeded>().is_some(), "{err}");
+    }
+}
@@ -0,0 +1,222 @@
+//! The fee of an executed transaction: the gas attached to its function calls, the gas burnt and
+//! the NEAR refunded.
+//!
+//! The refunds are receipts from `system` that transfer NEAR back. They return the unused prepaid
+//! gas and, when a receipt fails, its deposits; the deposit refunds are told apart by summing the
+//! deposits of the failed receipts. They are only counted once every receipt of the transaction
+//! is executed (the refunds come last).
+use near_primitives::views::{
+    ActionView, ExecutionStatusView, FinalExecutionOutcomeView, ReceiptEnumView, ReceiptView,
+};
+
+use crate::types::near_token::NearToken;
+
+const SYSTEM_ACCOUNT_ID: &str = "system";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeSummary {
+    pub gas_attached: near_primitives::types::Gas,
+    pub gas_burnt: near_primitives::types::Gas,
+    pub tokens_burnt: near_primitives::types::Balance,
+    /// `None` if the receipts of the transaction are not known or not all executed yet
+    pub refunds: Option<Refunds>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Refunds {
+    pub gas: near_primitives::types::Balance,
+    pub deposits: near_primitives::types::Balance,
+}
+
+impl FeeSummary {
+    pub fn new(outcome: &FinalExecutionOutcomeView, receipts: Option<&[ReceiptView]>) -> Self {
+        let outcomes =
+            std::iter::once(&outcome.transaction_outcome).chain(&outcome.receipts_outcome);
+        Self {
+            gas_attached: outcome
+                .transaction
+                .actions
+                .iter()
+                .map(|action| match action {
+                    ActionView::FunctionCall { gas, .. } => *gas,
+                    _ => 0,
+                })
+                .sum(),
+            gas_burnt: outcomes
+                .clone()
+                .map(|outcome| outcome.outcome.gas_burnt)
+                .sum(),
+            tokens_burnt: outcomes.map(|outcome| outcome.outcome.tokens_burnt).sum(),
+            refunds: receipts
+                .filter(|_| all_receipts_executed(outcome))
+                .map(|receipts| refunds(outcome, receipts)),
+        }
+    }
+}
+
+/// Whether the outcome has the execution of every receipt that the transaction produced.
+fn all_receipts_executed(outcome: &FinalExecutionOutcomeView) -> bool {
+    let executed_receipt_ids: std::collections::HashSet<_> = outcome
+        .receipts_outcome
+        .iter()
+        .map(|outcome| outcome.id)
+        .collect();
+    std::iter::once(&outcome.transaction_outcome)
+        .chain(&outcome.receipts_outcome)
+        .flat_map(|outcome| &outcome.outcome.receipt_ids)
+        .all(|receipt_id| executed_receipt_ids.contains(receipt_id))
+}
+
+fn refunds(outcome: &FinalExecutionOutcomeView, receipts: &[ReceiptView]) -> Refunds {
+    let failed_receipt_ids: std::collections::HashSet<_> = outcome
+        .receipts_outcome
+        .iter()
+        .filter(|outcome| matches!(outcome.outcome.status, ExecutionStatusView::Failure(_)))
+        .map(|outcome| outcome.id)
+        .collect();
+    let deposits = |receipt: &ReceiptView| -> near_primitives::types::Balance {
+        match &receipt.receipt {
+            ReceiptEnumView::Action { actions, .. } => actions
+                .iter()
+                .map(|action| match action {
+                    ActionView::Transfer { deposit } | ActionView::FunctionCall { deposit, .. } => {
+                        *deposit
+                    }
+                    _ => 0,
+                })
+                .sum(),
+            ReceiptEnumView::Data { .. } => 0,
+        }
+    };
+    let (refund_receipts, other_receipts): (Vec<_>, Vec<_>) = receipts
+        .iter()
+        .partition(|receipt| receipt.predecessor_id.as_str() == SYSTEM_ACCOUNT_ID);
+
+    let refunded: near_primitives::types::Balance = refund_receipts.into_iter().map(deposits).sum();
+    let deposits_refunded = other_receipts
+        .into_iter()
+        .filter(|receipt| failed_receipt_ids.contains(&receipt.receipt_id))
+        .map(deposits)
+        .sum::<near_primitives::types::Balance>()
+        .min(refunded);
+    Refunds {
+        gas: refunded - deposits_refunded,
+        deposits: deposits_refunded,
+    }
+}
+
+/// For example: "0.00073 NEAR (300 Tgas attached, 7.3 Tgas burnt, 0.0292 NEAR refunded)"
+impl std::fmt::Display for FeeSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut details = Vec::new();
+        if self.gas_attached > 0 {
+            details.push(format!(
+                "{} attached",
+                near_gas::NearGas::from_gas(self.gas_attached)
+            ));
+        }
+        details.push(format!(
+            "{} burnt",
+            near_gas::NearGas::from_gas(self.gas_burnt)
+        ));
+        if let Some(refunds) = self.refunds {
+            details.push(format!(
+                "{} refunded",
+                NearToken::from_yoctonear(refunds.gas)
+            ));
+        }
+        write!(
+            f,
+            "{} ({})",
+            NearToken::from_yoctonear(self.tokens_burnt),
+            details.join(", ")
+        )?;
+        if let Some(refunds) = self.refunds.filter(|refunds| refunds.deposits > 0) {
+            write!(
+                f,
+                "\nDeposits refunded: {} (the receipts that carried them failed)",
+                NearToken::from_yoctonear(refunds.deposits)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The fixtures are hand-built in the format of the `EXPERIMENTAL_tx_status` responses (with
+    /// round numbers), not recorded from a network.
+    fn fixture(json: &str) -> near_primitives::views::FinalExecutionOutcomeWithReceiptView {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn gas_refund_of_a_successful_call() {
+        let outcome = fixture(include_str!("fixtures/function_call_succeeded.json"));
+        let fee_summary = FeeSummary::new(&outcome.final_outcome, Some(&outcome.receipts));
+        assert_eq!(
+            fee_summary,
+            FeeSummary {
+                gas_attached: 300_000_000_000_000,
+                gas_burnt: 7_300_000_000_000,
+                tokens_burnt: 730_000_000_000_000_000_000,
+                refunds: Some(Refunds {
+                    gas: 29_200_000_000_000_000_000_000,
+                    deposits: 0,
+                }),
+            }
+        );
+        let summary = fee_summary.to_string();
+        assert!(summary.contains("attached"), "{summary}");
+        assert!(!summary.contains("Deposits refunded"), "{summary}");
+    }
+
+    #[test]
+    fn deposit_of_a_failed_call_is_refunded_separately() {
+        let outcome = fixture(include_str!("fixtures/function_call_failed.json"));
+        let fee_summary = FeeSummary::new(&outcome.final_outcome, Some(&outcome.receipts));
+        assert_eq!(
+            fee_summary.refunds,
+            Some(Refunds {
+                gas: 29_200_000_000_000_000_000_000,
+                deposits: 10u128.pow(24),
+            })
+        );
+        assert_eq!(fee_summary.tokens_burnt, 730_000_000_000_000_000_000);
+        assert!(
+            fee_summary
+                .to_string()
+                .contains("\nDeposits refunded: 1 NEAR"),
+            "{fee_summary}"
+        );
+    }
+
+    #[test]
+    fn refunds_are_not_counted_before_all_the_receipts_are_executed() {
+        let mut outcome = fixture(include_str!("fixtures/function_call_succeeded.json"));
+        let refund_receipt = outcome.final_outcome.receipts_outcome.pop().unwrap();
+        assert!(outcome
+            .final_outcome
+            .receipts_outcome
+            .iter()
+            .any(|receipt_outcome| receipt_outcome
+                .outcome
+                .receipt_ids
+                .contains(&refund_receipt.id)));
+        let fee_summary = FeeSummary::new(&outcome.final_outcome, Some(&outcome.receipts));
+        assert_eq!(fee_summary.refunds, None);
+    }
+
+    #[test]
+    fn without_receipts_only_the_burnt_gas_is_known() {
+        let outcome = fixture(include_str!("fixtures/function_call_succeeded.json"));
+        let fee_summary = FeeSummary::new(&outcome.final_outcome, None);
+        assert_eq!(fee_summary.refunds, None);
+        assert!(
+            !fee_summary.to_string().contains("refunded"),
+            "{fee_summary}"
+        );
+    }
+}
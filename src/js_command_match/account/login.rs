@@ -8,16 +8,17 @@ pub struct LoginArgs {
 }
 
 impl LoginArgs {
-    pub fn to_cli_args(&self, network_config: String) -> Vec<String> {
-        let network_id = self.network_id.clone().unwrap_or(network_config);
-
-        let command = vec![
+    /// Without a network ID or a default network, the network is selected interactively (which
+    /// is skipped if only one network is configured).
+    pub fn to_cli_args(&self, default_network: Option<String>) -> Vec<String> {
+        let mut command = vec![
             "account".to_string(),
             "import-account".to_string(),
             "using-web-wallet".to_string(),
-            "network-config".to_string(),
-            network_id,
         ];
+        if let Some(network_id) = self.network_id.clone().or(default_network) {
+            command.extend(["network-config".to_string(), network_id]);
+        }
 
         command
     }
@@ -57,7 +58,33 @@ mod tests {
                 );
             };
             assert_eq!(
-                shell_words::join(LoginArgs::to_cli_args(&login_args, "testnet".to_string())),
+                shell_words::join(LoginArgs::to_cli_args(
+                    &login_args,
+                    Some("testnet".to_string())
+                )),
+                expected_output
+            );
+        }
+    }
+
+    #[test]
+    fn login_without_default_network() {
+        for (input, expected_output) in [
+            ("near login", "account import-account using-web-wallet"),
+            (
+                "near login --networkId mainnet",
+                "account import-account using-web-wallet network-config mainnet",
+            ),
+        ] {
+            let input_cmd =
+                shell_words::split(input).expect("Input command must be a valid shell command");
+            let JsCmd::Login(login_args) = JsCmd::parse_from(&input_cmd) else {
+                panic!(
+                    "Login command was expected, but something else was parsed out from {input}"
+                );
+            };
+            assert_eq!(
+                shell_words::join(LoginArgs::to_cli_args(&login_args, None)),
                 expected_output
             );
         }
@@ -38,15 +38,23 @@ pub enum JsCmd {
 }
 
 impl JsCmd {
-    pub fn rust_command_generation(&self) -> Vec<String> {
-        let network = std::env::var("NEAR_NETWORK")
-            .or_else(|_| std::env::var("NEAR_ENV"))
-            .unwrap_or_else(|_| "testnet".to_owned());
+    /// `config_default_network` is the `default_network` of the config (and profile), which the
+    /// legacy `NEAR_NETWORK` and `NEAR_ENV` variables override.
+    pub fn rust_command_generation(&self, config_default_network: Option<String>) -> Vec<String> {
+        let default_network = default_network(
+            std::env::var("NEAR_NETWORK")
+                .or_else(|_| std::env::var("NEAR_ENV"))
+                .ok(),
+            config_default_network,
+        );
+        let network = default_network
+            .clone()
+            .unwrap_or_else(|| "testnet".to_owned());
 
         match self {
             Self::CreateAccount(args) => args.to_cli_args(network),
             Self::DeleteAccount(args) => args.to_cli_args(network),
-            Self::Login(args) => args.to_cli_args(network),
+            Self::Login(args) => args.to_cli_args(default_network),
             Self::State(args) => args.to_cli_args(network),
 
             Self::Call(args) => args.to_cli_args(network),
@@ -66,3 +74,49 @@ impl JsCmd {
         }
     }
 }
+
+/// Whether `near <name>` is a legacy command (or one of its aliases).
+pub fn is_js_command(name: &str) -> bool {
+    <JsCmd as clap::CommandFactory>::command()
+        .find_subcommand(name)
+        .is_some()
+}
+
+fn default_network(
+    legacy_env_network: Option<String>,
+    config_default_network: Option<String>,
+) -> Option<String> {
+    legacy_env_network
+        .filter(|network| !network.is_empty())
+        .or(config_default_network)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_legacy_env_overrides_the_default_network_of_the_config() {
+        assert_eq!(
+            default_network(Some("mainnet".to_string()), Some("testnet".to_string())),
+            Some("mainnet".to_string())
+        );
+        assert_eq!(
+            default_network(None, Some("testnet".to_string())),
+            Some("testnet".to_string())
+        );
+        assert_eq!(
+            default_network(Some(String::new()), Some("testnet".to_string())),
+            Some("testnet".to_string())
+        );
+        assert_eq!(default_network(None, None), None);
+    }
+
+    #[test]
+    fn js_commands_are_told_by_name_or_alias() {
+        assert!(is_js_command("login"));
+        assert!(is_js_command("import-account"));
+        assert!(is_js_command("send-near"));
+        assert!(!is_js_command("envdump"));
+    }
+}
@@ -1,22 +1,40 @@
 #![allow(clippy::arc_with_non_send_sync)]
 pub use common::CliResult;
 
+pub mod audit_log;
 pub mod commands;
 pub mod common;
 pub mod config;
+pub mod deadline;
+pub mod fee_summary;
 pub mod js_command_match;
 pub mod network;
 pub mod network_for_transaction;
 pub mod network_view_at_block;
+pub mod notification;
+pub mod onboarding;
+pub mod prompt_style;
+pub mod qr_code;
+pub mod rpc_rate_limit;
 pub mod transaction_signature_options;
+pub mod tx_builder;
 pub mod types;
 pub mod utils_command;
+pub mod view_pagination;
 
 #[derive(Debug, Clone)]
 pub struct GlobalContext {
     pub config: crate::config::Config,
     pub offline: bool,
     pub teach_me: bool,
+    pub replay_on_sandbox: bool,
+    pub strict_protocol_check: bool,
+    pub require_access_key_for: Option<near_primitives::types::AccountId>,
+    pub fee_estimate: bool,
+    pub fee_estimate_only: bool,
+    pub notify: crate::notification::NotifyMode,
+    /// `--yes-below`: transactions with a higher outgoing value need a confirmation
+    pub yes_below: Option<crate::types::near_token::NearToken>,
 }
 
 pub fn setup_tracing(teach_me_flag_is_set: bool) -> CliResult {
@@ -37,6 +55,7 @@ pub fn setup_tracing(teach_me_flag_is_set: bool) -> CliResult {
                     .without_time()
                     .with_target(false),
             )
+            .with(crate::deadline::StageLayer)
             .with(env_filter)
             .init();
     } else {
@@ -67,6 +86,7 @@ pub fn setup_tracing(teach_me_flag_is_set: bool) -> CliResult {
                     .with_writer(indicatif_layer.get_stderr_writer()),
             )
             .with(indicatif_layer)
+            .with(crate::deadline::StageLayer)
             .with(env_filter)
             .init();
     };
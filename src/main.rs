@@ -17,6 +17,7 @@ pub use near_cli_rs::js_command_match;
 pub use near_cli_rs::network;
 pub use near_cli_rs::network_for_transaction;
 pub use near_cli_rs::network_view_at_block;
+pub use near_cli_rs::notification;
 pub use near_cli_rs::transaction_signature_options;
 pub use near_cli_rs::types;
 pub use near_cli_rs::utils_command;
@@ -34,6 +35,55 @@ struct Cmd {
     /// TEACH-ME mode
     #[interactive_clap(long)]
     teach_me: bool,
+    /// Replay transactions on a sandbox node: they are executed and committed there like on any network, with the accounts and keys the sandbox already has (the RPC server must run in sandbox mode)
+    #[interactive_clap(long)]
+    replay_on_sandbox: bool,
+    /// Refuse to sign transactions if the network runs a protocol version older than this CLI supports or newer than the one it was built with (without this flag a newer version is only noted)
+    #[interactive_clap(long)]
+    strict_protocol_check: bool,
+    /// Print the estimated fee of transactions before signing them
+    #[interactive_clap(long)]
+    fee_estimate: bool,
+    /// Print the estimated fee of transactions (the maximum fee in yoctoNEAR to stdout) and exit without signing them
+    #[interactive_clap(long)]
+    fee_estimate_only: bool,
+    /// Refuse to sign transactions if the signer account has no function-call access key for this receiver
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    require_access_key_for: Option<crate::types::account_id::AccountId>,
+    /// Show a desktop notification when a transaction completes: always, long (default, see `notify_after_secs` in the config) or never
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    notify: Option<crate::notification::NotifyMode>,
+    /// Sign transactions without a confirmation only if they send less than this amount (transfers, attached deposits and stakes), others need to be confirmed (and fail non-interactively)
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    yes_below: Option<crate::types::near_token::NearToken>,
+    /// Exit with code 124 (and the stage it was in) if the command takes longer than this, e.g. 90s or 5m (see `max_duration` in the config)
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    max_duration: Option<crate::types::duration::Duration>,
+    /// Render the prompts as numbered lists without colors or redrawing (see `prompt_style` in the config)
+    #[interactive_clap(long)]
+    plain_prompts: bool,
+    /// Load environment variables (NEAR_ACCOUNT_ID, NEAR_PRIVATE_KEY, NEAR_RPC_URL with NEAR_RPC_NETWORK, NEAR_CREDENTIALS_DIR) from the file (default: .env if it exists)
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    env_file: Option<crate::types::path_buf::PathBuf>,
+    /// Use the settings of this profile of the config (default: NEAR_CLI_PROFILE, then the profile chosen with `config profile use`)
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    profile: Option<String>,
+    /// Use the legacy keychain in this directory instead of the credentials directories from the config (default: NEAR_CREDENTIALS_DIR, then the credentials_dir of the profile, then the config)
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    credentials_dir: Option<crate::types::path_buf::PathBuf>,
+    /// Print the generated and imported access keys instead of saving them in the keychain or on disk
+    #[interactive_clap(long)]
+    no_keychain: bool,
+    /// Do not run the setup wizard on the first run
+    #[interactive_clap(long)]
+    no_onboarding: bool,
     #[interactive_clap(subcommand)]
     top_level: crate::commands::TopLevelCommand,
 }
@@ -46,10 +96,39 @@ impl CmdContext {
         previous_context: ConfigContext,
         scope: &<Cmd as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let config = previous_context.0;
+        let notify = scope.notify.unwrap_or(config.notify);
+        crate::common::set_no_keychain(scope.no_keychain);
+        let env_credentials_dir = crate::config::env_file::var::<std::path::PathBuf>(
+            crate::config::env_file::NEAR_CREDENTIALS_DIR,
+        )?;
+        if let (None, Some(env_credentials_dir)) = (&scope.credentials_dir, &env_credentials_dir) {
+            crate::config::env_file::notify_override(
+                crate::config::env_file::NEAR_CREDENTIALS_DIR,
+                format!(
+                    "Using the legacy keychain in {}",
+                    env_credentials_dir.display()
+                ),
+            );
+        }
+        let config = config.with_credentials_dir_override(
+            scope
+                .credentials_dir
+                .clone()
+                .map(|credentials_dir| credentials_dir.into()),
+            env_credentials_dir,
+        );
         Ok(Self(crate::GlobalContext {
-            config: previous_context.0,
+            config,
             offline: scope.offline,
             teach_me: scope.teach_me,
+            replay_on_sandbox: scope.replay_on_sandbox,
+            strict_protocol_check: scope.strict_protocol_check,
+            require_access_key_for: scope.require_access_key_for.clone().map(Into::into),
+            fee_estimate: scope.fee_estimate || scope.fee_estimate_only,
+            fee_estimate_only: scope.fee_estimate_only,
+            notify,
+            yes_below: scope.yes_below,
         }))
     }
 }
@@ -60,12 +139,36 @@ impl From<CmdContext> for crate::GlobalContext {
     }
 }
 
-fn main() -> crate::common::CliResult {
-    let config = crate::config::Config::get_config_toml()?;
-
-    if !crate::common::is_used_account_list_exist(&config.credentials_home_dir) {
-        crate::common::create_used_account_list_from_keychain(&config.credentials_home_dir)?;
+fn invalid_subcommand(error: &clap::Error) -> Option<&str> {
+    match error.get(clap::error::ContextKind::InvalidSubcommand) {
+        Some(clap::error::ContextValue::String(subcommand)) => Some(subcommand),
+        _ => None,
     }
+}
+
+/// Splits `near [top-level flags] <extension> [args]` into the `--profile` of the top-level
+/// flags and the extension with its arguments.
+fn split_extension_args<'a>(
+    error: &clap::Error,
+    args: &'a [String],
+) -> (Option<String>, &'a [String]) {
+    let Some(subcommand) = invalid_subcommand(error) else {
+        return (None, args.get(1..).unwrap_or_default());
+    };
+    (1..args.len())
+        .filter(|index| args[*index] == *subcommand)
+        .find_map(|index| {
+            Cmd::try_parse_from(&args[..index])
+                .ok()
+                .map(|cli| (cli.profile, &args[index..]))
+        })
+        .unwrap_or((None, args.get(1..).unwrap_or_default()))
+}
+
+fn main() -> crate::common::CliResult {
+    crate::config::env_file::load(
+        crate::config::env_file::env_file_from_args(std::env::args()).as_deref(),
+    )?;
 
     #[cfg(not(debug_assertions))]
     let display_env_section = false;
@@ -91,16 +194,28 @@ fn main() -> crate::common::CliResult {
             _ => {
                 match crate::js_command_match::JsCmd::try_parse() {
                     Ok(js_cmd) => {
-                        let vec_cmd = js_cmd.rust_command_generation();
+                        let vec_cmd = js_cmd.rust_command_generation(
+                            crate::config::Config::get_config_toml(None)?.default_network,
+                        );
                         let cmd = std::iter::once(near_cli_exec_path.to_owned()).chain(vec_cmd);
                         Parser::parse_from(cmd)
                     }
                     Err(js_cmd_error) => {
-                        // js and rust both don't understand the subcommand
+                        // js and rust both don't understand the subcommand (the js commands fail
+                        // on the top-level flags before it, e.g. `near --profile work <extension>`)
                         if cmd_error.kind() == clap::error::ErrorKind::InvalidSubcommand
-                            && js_cmd_error.kind() == clap::error::ErrorKind::InvalidSubcommand
+                            && (js_cmd_error.kind() == clap::error::ErrorKind::InvalidSubcommand
+                                || invalid_subcommand(&cmd_error).is_some_and(|subcommand| {
+                                    !crate::js_command_match::is_js_command(subcommand)
+                                }))
                         {
-                            return crate::common::try_external_subcommand_execution(cmd_error);
+                            let args = std::env::args().collect::<Vec<_>>();
+                            let (profile, extension_args) = split_extension_args(&cmd_error, &args);
+                            return crate::common::try_external_subcommand_execution(
+                                cmd_error,
+                                extension_args,
+                                &crate::config::Config::get_config_toml(profile.as_deref())?,
+                            );
                         }
 
                         // js understand the subcommand
@@ -114,9 +229,50 @@ fn main() -> crate::common::CliResult {
             }
         },
     };
+    let mut cli = cli;
+    let run_onboarding =
+        near_cli_rs::onboarding::should_run(cli.top_level.is_some(), cli.no_onboarding);
+    // The config is written by the wizard
+    let mut config = if run_onboarding {
+        crate::config::Config::default()
+    } else {
+        crate::config::Config::get_config_toml(cli.profile.as_deref())?
+    };
+
+    if !crate::common::is_used_account_list_exist(&config.credentials_home_dir) {
+        crate::common::create_used_account_list_from_keychain(&config.credentials_home_dir)?;
+    }
+
     near_cli_rs::setup_tracing(cli.teach_me)?;
+    let max_duration = cli.max_duration.or(config.max_duration);
+    near_cli_rs::prompt_style::apply(
+        if cli.plain_prompts || !near_cli_rs::prompt_style::terminal_supports_ansi() {
+            near_cli_rs::prompt_style::PromptStyle::Plain
+        } else {
+            config.prompt_style
+        },
+    );
 
-    let cli_cmd = match <Cmd as interactive_clap::FromCli>::from_cli(Some(cli), (config,)) {
+    let onboarding = if run_onboarding {
+        near_cli_rs::onboarding::run(&mut config)?
+    } else {
+        None
+    };
+    if let Some(onboarding) = onboarding
+        .as_ref()
+        .filter(|onboarding| !onboarding.next_command.is_empty())
+    {
+        cli.top_level = Cmd::try_parse_from(
+            std::iter::once(&near_cli_exec_path).chain(&onboarding.next_command),
+        )?
+        .top_level;
+    }
+
+    let result_from_cli = near_cli_rs::deadline::run(max_duration, move || {
+        <Cmd as interactive_clap::FromCli>::from_cli(Some(cli), (config,))
+    })
+    .map_err(near_cli_rs::deadline::exit_if_exceeded)?;
+    let cli_cmd = match result_from_cli {
         interactive_clap::ResultFromCli::Ok(cli_cmd)
         | interactive_clap::ResultFromCli::Cancel(Some(cli_cmd)) => {
             let cli_cmd_str = shell_words::join(
@@ -156,6 +312,10 @@ fn main() -> crate::common::CliResult {
         }
     };
 
+    if let (Some(onboarding), Ok(Some(_))) = (&onboarding, &cli_cmd) {
+        onboarding.print_examples();
+    }
+
     #[cfg(feature = "self-update")]
     // We don't need to check the version if user has just called self-update
     if !matches!(
@@ -187,6 +347,20 @@ fn main() -> crate::common::CliResult {
                 let self_update_cli_cmd = CliCmd {
                     offline: false,
                     teach_me: false,
+                    replay_on_sandbox: false,
+                    strict_protocol_check: false,
+                    require_access_key_for: None,
+                    fee_estimate: false,
+                    fee_estimate_only: false,
+                    notify: None,
+                    yes_below: None,
+                    max_duration: None,
+                    plain_prompts: false,
+                    env_file: None,
+                    profile: None,
+                    credentials_dir: None,
+                    no_keychain: false,
+                    no_onboarding: false,
                     top_level:
                         Some(crate::commands::CliTopLevelCommand::Extensions(
                             crate::commands::extensions::CliExtensionsCommands {
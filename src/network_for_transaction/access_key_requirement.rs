@@ -0,0 +1,94 @@
+//! `--require-access-key-for <receiver-id>`: the signer account must have a function-call access
+//! key for the receiver before anything is signed, instead of an `InvalidAccessKeyError` when the
+//! transaction is sent.
+
+use color_eyre::eyre::WrapErr;
+
+use crate::common::{JsonRpcClientExt, RpcQueryResponseExt};
+
+pub fn check_required_access_key(
+    network_config: &crate::config::NetworkConfig,
+    signer_account_id: &near_primitives::types::AccountId,
+    receiver_account_id: &near_primitives::types::AccountId,
+) -> crate::CliResult {
+    let access_key_list = network_config
+        .json_rpc_client()
+        .blocking_call_view_access_key_list(
+            signer_account_id,
+            near_primitives::types::Finality::Final.into(),
+        )
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch query AccessKeyList for {signer_account_id} (--require-access-key-for)"
+            )
+        })?
+        .access_key_list_view()?;
+    if has_function_call_access_key_for(&access_key_list.keys, receiver_account_id) {
+        return Ok(());
+    }
+    Err(color_eyre::eyre::eyre!(
+        "The account <{signer_account_id}> has no function-call access key for <{receiver_account_id}> (--require-access-key-for), so nothing was signed.\nAdd one with:\n    {}",
+        add_access_key_command(signer_account_id, receiver_account_id, &network_config.network_name)
+    ))
+}
+
+fn has_function_call_access_key_for(
+    access_keys: &[near_primitives::views::AccessKeyInfoView],
+    receiver_account_id: &near_primitives::types::AccountId,
+) -> bool {
+    access_keys.iter().any(|access_key| {
+        matches!(
+            &access_key.access_key.permission,
+            near_primitives::views::AccessKeyPermissionView::FunctionCall { receiver_id, .. }
+                if receiver_id == receiver_account_id.as_str()
+        )
+    })
+}
+
+fn add_access_key_command(
+    signer_account_id: &near_primitives::types::AccountId,
+    receiver_account_id: &near_primitives::types::AccountId,
+    network_name: &str,
+) -> String {
+    format!(
+        "near account add-key {signer_account_id} grant-function-call-access --allowance '0.25 NEAR' --contract-account-id {receiver_account_id} --function-names '' autogenerate-new-keypair save-to-keychain network-config {network_name} sign-with-keychain send"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn function_call_access_key_for_the_receiver() {
+        let access_key = |permission| near_primitives::views::AccessKeyInfoView {
+            public_key: near_crypto::PublicKey::from_seed(near_crypto::KeyType::ED25519, "test"),
+            access_key: near_primitives::views::AccessKeyView {
+                nonce: 0,
+                permission,
+            },
+        };
+        let function_call =
+            |receiver_id: &str| near_primitives::views::AccessKeyPermissionView::FunctionCall {
+                allowance: None,
+                receiver_id: receiver_id.to_string(),
+                method_names: vec![],
+            };
+        let receiver_account_id: near_primitives::types::AccountId = "game.near".parse().unwrap();
+        assert!(!has_function_call_access_key_for(
+            &[
+                access_key(near_primitives::views::AccessKeyPermissionView::FullAccess),
+                access_key(function_call("other.near")),
+            ],
+            &receiver_account_id
+        ));
+        assert!(has_function_call_access_key_for(
+            &[access_key(function_call("game.near"))],
+            &receiver_account_id
+        ));
+        assert_eq!(
+            add_access_key_command(&"alice.near".parse().unwrap(), &receiver_account_id, "mainnet"),
+            "near account add-key alice.near grant-function-call-access --allowance '0.25 NEAR' --contract-account-id game.near --function-names '' autogenerate-new-keypair save-to-keychain network-config mainnet sign-with-keychain send"
+        );
+    }
+}
@@ -0,0 +1,135 @@
+//! The pre-flight check of the assembled actions against the state of the receiver account: the
+//! actions that do nothing or that will definitely fail are flagged before anything is signed.
+
+use color_eyre::eyre::WrapErr;
+
+use crate::common::{JsonRpcClientExt, RpcQueryResponseExt};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum_macros::Display)]
+enum IssueResolution {
+    #[strum(to_string = "Continue with the action")]
+    Continue,
+    #[strum(to_string = "Remove the action from the transaction")]
+    Remove,
+    #[strum(to_string = "Abort")]
+    Abort,
+}
+
+/// Flags the no-op and failing actions and, in a terminal, asks what to do with each of them.
+/// Returns `false` if the transaction is aborted (or no action is left).
+pub fn check_actions(
+    network_config: &crate::config::NetworkConfig,
+    prepopulated_transaction: &mut crate::commands::PrepopulatedTransaction,
+    offline: bool,
+) -> color_eyre::eyre::Result<bool> {
+    let needs_receiver_state = prepopulated_transaction.actions.iter().any(|action| {
+        matches!(
+            action,
+            near_primitives::transaction::Action::AddKey(_)
+                | near_primitives::transaction::Action::DeleteKey(_)
+                | near_primitives::transaction::Action::DeployContract(_)
+        )
+    });
+    let receiver_state = if needs_receiver_state && !offline {
+        get_receiver_state(network_config, &prepopulated_transaction.receiver_id).unwrap_or_else(
+            |err| {
+                tracing::warn!("Failed to check the actions against the receiver account: {err:#}");
+                None
+            },
+        )
+    } else {
+        None
+    };
+    let issues = crate::tx_builder::find_action_issues(
+        &prepopulated_transaction.actions,
+        receiver_state.as_ref(),
+    );
+    if issues.is_empty() {
+        return Ok(true);
+    }
+
+    let mut removed_action_indexes = Vec::new();
+    for issue in issues {
+        let message = format!(
+            "Action #{} of {}: {}",
+            issue.action_index + 1,
+            prepopulated_transaction.actions.len(),
+            issue.message
+        );
+        if !crate::common::is_interactive_terminal() {
+            eprintln!("WARNING: {message}");
+            continue;
+        }
+        match crate::common::select(
+            &format!("{message}. What do you want to do?"),
+            vec![
+                IssueResolution::Continue,
+                IssueResolution::Remove,
+                IssueResolution::Abort,
+            ],
+        )? {
+            Some(IssueResolution::Continue) => {}
+            Some(IssueResolution::Remove) => removed_action_indexes.push(issue.action_index),
+            Some(IssueResolution::Abort) | None => return Ok(false),
+        }
+    }
+    for action_index in removed_action_indexes.into_iter().rev() {
+        prepopulated_transaction.actions.remove(action_index);
+    }
+    if prepopulated_transaction.actions.is_empty() {
+        eprintln!("No action is left in the transaction, so nothing is signed.");
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+/// The access keys and the code hash of the account, or `None` if it does not exist yet.
+fn get_receiver_state(
+    network_config: &crate::config::NetworkConfig,
+    account_id: &near_primitives::types::AccountId,
+) -> color_eyre::eyre::Result<Option<crate::tx_builder::ReceiverState>> {
+    let json_rpc_client = network_config.json_rpc_client();
+    let account_view = match json_rpc_client
+        .blocking_call_view_account(account_id, near_primitives::types::Finality::Final.into())
+    {
+        Ok(rpc_query_response) => rpc_query_response.account_view()?,
+        Err(near_jsonrpc_client::errors::JsonRpcError::ServerError(
+            near_jsonrpc_client::errors::JsonRpcServerError::HandlerError(
+                near_jsonrpc_primitives::types::query::RpcQueryError::UnknownAccount { .. },
+            ),
+        )) => return Ok(None),
+        Err(err) => {
+            return Err(err).wrap_err_with(|| {
+                format!(
+                    "Failed to fetch query ViewAccount for account <{account_id}> on network <{}>",
+                    network_config.network_name
+                )
+            })
+        }
+    };
+    let access_key_list = json_rpc_client
+        .blocking_call_view_access_key_list(
+            account_id,
+            near_primitives::types::Finality::Final.into(),
+        )
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch query AccessKeyList for account <{account_id}> on network <{}>",
+                network_config.network_name
+            )
+        })?
+        .access_key_list_view()?;
+    Ok(Some(crate::tx_builder::ReceiverState {
+        access_keys: access_key_list
+            .keys
+            .into_iter()
+            .map(|access_key| {
+                (
+                    access_key.public_key,
+                    access_key.access_key.permission.into(),
+                )
+            })
+            .collect(),
+        code_hash: account_view.code_hash,
+    }))
+}
@@ -0,0 +1,305 @@
+//! The estimate of the fee of a transaction (`--fee-estimate`), computed from its actions with the
+//! gas cost schedule of the network's protocol config and the current gas price.
+
+use color_eyre::eyre::WrapErr;
+
+use near_primitives::transaction::Action;
+use near_primitives::types::Gas;
+
+use crate::common::JsonRpcClientExt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransactionGas {
+    /// The send and execution fees of the transaction and its actions
+    pub burnt: Gas,
+    /// The gas attached to the function calls (what is not used is refunded)
+    pub attached: Gas,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct FeeEstimate {
+    pub gas: TransactionGas,
+    pub gas_price: near_primitives::types::Balance,
+}
+
+impl FeeEstimate {
+    /// The fee without the attached gas.
+    pub fn fee(&self) -> crate::types::near_token::NearToken {
+        crate::types::near_token::NearToken::from_yoctonear(
+            u128::from(self.gas.burnt).saturating_mul(self.gas_price),
+        )
+    }
+
+    /// The fee if all the attached gas is used.
+    pub fn max_fee(&self) -> crate::types::near_token::NearToken {
+        crate::types::near_token::NearToken::from_yoctonear(
+            u128::from(self.gas.burnt.saturating_add(self.gas.attached))
+                .saturating_mul(self.gas_price),
+        )
+    }
+}
+
+impl std::fmt::Display for FeeEstimate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Estimated transaction fee (gas price: {} yoctoNEAR):",
+            self.gas_price
+        )?;
+        write!(
+            f,
+            "  {:<40} {} * gas price = {} ({} yoctoNEAR)",
+            "transaction and action fees:",
+            crate::common::NearGas::from_gas(self.gas.burnt),
+            self.fee(),
+            self.fee().as_yoctonear()
+        )?;
+        if self.gas.attached > 0 {
+            write!(
+                f,
+                "\n  {:<40} {} * gas price = {} ({} yoctoNEAR), the unused gas is refunded",
+                "with all the attached gas used:",
+                crate::common::NearGas::from_gas(self.gas.burnt.saturating_add(self.gas.attached)),
+                self.max_fee(),
+                self.max_fee().as_yoctonear()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+pub fn estimate_fee(
+    network_config: &crate::config::NetworkConfig,
+    prepopulated_transaction: &crate::commands::PrepopulatedTransaction,
+) -> color_eyre::eyre::Result<FeeEstimate> {
+    estimate_batch_fee(
+        network_config,
+        std::slice::from_ref(prepopulated_transaction),
+    )
+}
+
+/// The fee of all the transactions together, with the protocol config and the gas price fetched
+/// once.
+#[tracing::instrument(name = "Estimating the transaction fee ...", skip_all)]
+pub fn estimate_batch_fee(
+    network_config: &crate::config::NetworkConfig,
+    prepopulated_transactions: &[crate::commands::PrepopulatedTransaction],
+) -> color_eyre::eyre::Result<FeeEstimate> {
+    let json_rpc_client = network_config.json_rpc_client();
+    let runtime_config = json_rpc_client
+        .blocking_call(
+            near_jsonrpc_client::methods::EXPERIMENTAL_protocol_config::RpcProtocolConfigRequest {
+                block_reference: near_primitives::types::Finality::Final.into(),
+            },
+        )
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch the protocol config of network <{}>",
+                network_config.network_name
+            )
+        })?
+        .runtime_config;
+    let gas_price = json_rpc_client
+        .blocking_call(
+            near_jsonrpc_client::methods::gas_price::RpcGasPriceRequest { block_id: None },
+        )
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch the gas price of network <{}>",
+                network_config.network_name
+            )
+        })?
+        .gas_price;
+    let gas = prepopulated_transactions.iter().fold(
+        TransactionGas {
+            burnt: 0,
+            attached: 0,
+        },
+        |total, prepopulated_transaction| {
+            let gas = transaction_gas(
+                &runtime_config.transaction_costs,
+                &prepopulated_transaction.signer_id,
+                &prepopulated_transaction.receiver_id,
+                &prepopulated_transaction.actions,
+            );
+            TransactionGas {
+                burnt: total.burnt.saturating_add(gas.burnt),
+                attached: total.attached.saturating_add(gas.attached),
+            }
+        },
+    );
+    Ok(FeeEstimate { gas, gas_price })
+}
+
+/// The gas of the transaction as the runtime computes it: the action receipt and the send and
+/// execution fees of each action (the actions of a delegate action are sent in a receipt of
+/// their own).
+pub fn transaction_gas(
+    fees: &near_parameters::RuntimeFeesConfigView,
+    signer_id: &near_primitives::types::AccountId,
+    receiver_id: &near_primitives::types::AccountId,
+    actions: &[Action],
+) -> TransactionGas {
+    let sender_is_receiver = signer_id == receiver_id;
+    let mut gas = TransactionGas {
+        burnt: fee_gas(&fees.action_receipt_creation_config, sender_is_receiver),
+        attached: 0,
+    };
+    for action in actions {
+        gas.burnt = gas.burnt.saturating_add(action_gas(
+            &fees.action_creation_config,
+            sender_is_receiver,
+            action,
+        ));
+        match action {
+            Action::FunctionCall(function_call_action) => {
+                gas.attached = gas.attached.saturating_add(function_call_action.gas);
+            }
+            Action::Delegate(signed_delegate_action) => {
+                let delegate_action = &signed_delegate_action.delegate_action;
+                let delegated_gas = transaction_gas(
+                    fees,
+                    &delegate_action.sender_id,
+                    &delegate_action.receiver_id,
+                    &delegate_action.get_actions(),
+                );
+                gas.burnt = gas.burnt.saturating_add(delegated_gas.burnt);
+                gas.attached = gas.attached.saturating_add(delegated_gas.attached);
+            }
+            _ => {}
+        }
+    }
+    gas
+}
+
+fn fee_gas(fee: &near_parameters::Fee, sender_is_receiver: bool) -> Gas {
+    fee.send_fee(sender_is_receiver)
+        .saturating_add(fee.exec_fee())
+}
+
+fn action_gas(
+    fees: &near_parameters::view::ActionCreationConfigView,
+    sender_is_receiver: bool,
+    action: &Action,
+) -> Gas {
+    let gas = |fee| fee_gas(fee, sender_is_receiver);
+    let gas_per_byte =
+        |fee, bytes: usize| gas(fee).saturating_mul(u64::try_from(bytes).unwrap_or(u64::MAX));
+    match action {
+        Action::CreateAccount(_) => gas(&fees.create_account_cost),
+        Action::DeployContract(deploy_contract_action) => gas(&fees.deploy_contract_cost)
+            .saturating_add(gas_per_byte(
+                &fees.deploy_contract_cost_per_byte,
+                deploy_contract_action.code.len(),
+            )),
+        Action::FunctionCall(function_call_action) => {
+            gas(&fees.function_call_cost).saturating_add(gas_per_byte(
+                &fees.function_call_cost_per_byte,
+                function_call_action.method_name.len() + function_call_action.args.len(),
+            ))
+        }
+        Action::Transfer(_) => gas(&fees.transfer_cost),
+        Action::Stake(_) => gas(&fees.stake_cost),
+        Action::AddKey(add_key_action) => match &add_key_action.access_key.permission {
+            near_primitives::account::AccessKeyPermission::FullAccess => {
+                gas(&fees.add_key_cost.full_access_cost)
+            }
+            near_primitives::account::AccessKeyPermission::FunctionCall(permission) => {
+                gas(&fees.add_key_cost.function_call_cost).saturating_add(gas_per_byte(
+                    &fees.add_key_cost.function_call_cost_per_byte,
+                    // Each method name is counted with a separator
+                    permission
+                        .method_names
+                        .iter()
+                        .map(|method_name| method_name.len() + 1)
+                        .sum(),
+                ))
+            }
+        },
+        Action::DeleteKey(_) => gas(&fees.delete_key_cost),
+        Action::DeleteAccount(_) => gas(&fees.delete_account_cost),
+        Action::Delegate(_) => gas(&fees.delegate_cost),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use near_primitives::transaction::{FunctionCallAction, TransferAction};
+
+    use super::*;
+
+    fn fees() -> near_parameters::RuntimeFeesConfigView {
+        near_parameters::RuntimeConfigView::from(near_parameters::RuntimeConfig::test())
+            .transaction_costs
+    }
+
+    #[test]
+    fn gas_of_a_transfer() {
+        let fees = fees();
+        let alice: near_primitives::types::AccountId = "alice.near".parse().unwrap();
+        let bob: near_primitives::types::AccountId = "bob.near".parse().unwrap();
+        let transfer = Action::Transfer(TransferAction { deposit: 1 });
+        let receipt = &fees.action_receipt_creation_config;
+        let transfer_fee = &fees.action_creation_config.transfer_cost;
+
+        assert_eq!(
+            transaction_gas(&fees, &alice, &bob, &[transfer.clone()]),
+            TransactionGas {
+                burnt: receipt.send_not_sir
+                    + receipt.execution
+                    + transfer_fee.send_not_sir
+                    + transfer_fee.execution,
+                attached: 0,
+            }
+        );
+        assert_eq!(
+            transaction_gas(&fees, &alice, &alice, &[transfer.clone(), transfer]).burnt,
+            receipt.send_sir
+                + receipt.execution
+                + 2 * (transfer_fee.send_sir + transfer_fee.execution)
+        );
+    }
+
+    #[test]
+    fn gas_of_a_function_call() {
+        let fees = fees();
+        let alice: near_primitives::types::AccountId = "alice.near".parse().unwrap();
+        let app: near_primitives::types::AccountId = "app.near".parse().unwrap();
+        let function_call = Action::FunctionCall(Box::new(FunctionCallAction {
+            method_name: "vote".to_string(),
+            args: br#"{"id":1}"#.to_vec(),
+            gas: 30_000_000_000_000,
+            deposit: 0,
+        }));
+        let receipt = &fees.action_receipt_creation_config;
+        let base = &fees.action_creation_config.function_call_cost;
+        let per_byte = &fees.action_creation_config.function_call_cost_per_byte;
+
+        let gas = transaction_gas(&fees, &alice, &app, &[function_call]);
+        assert_eq!(
+            gas,
+            TransactionGas {
+                burnt: receipt.send_not_sir
+                    + receipt.execution
+                    + base.send_not_sir
+                    + base.execution
+                    + 12 * (per_byte.send_not_sir + per_byte.execution),
+                attached: 30_000_000_000_000,
+            }
+        );
+
+        let estimate = FeeEstimate {
+            gas,
+            gas_price: 100_000_000,
+        };
+        assert_eq!(
+            estimate.fee().as_yoctonear(),
+            u128::from(gas.burnt) * 100_000_000
+        );
+        assert_eq!(
+            estimate.max_fee().as_yoctonear(),
+            u128::from(gas.burnt + 30_000_000_000_000) * 100_000_000
+        );
+        assert!(estimate.to_string().contains("the unused gas is refunded"));
+    }
+}
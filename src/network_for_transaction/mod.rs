@@ -1,5 +1,10 @@
 use color_eyre::eyre::ContextCompat;
 
+pub mod access_key_requirement;
+mod action_check;
+pub mod fee_estimate;
+pub mod protocol_version;
+
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = crate::commands::ActionContext)]
 #[interactive_clap(output_context = NetworkForTransactionArgsContext)]
@@ -97,7 +102,7 @@ impl interactive_clap::FromCli for NetworkForTransactionArgs {
 
         let new_context_scope =
             InteractiveClapContextScopeForNetworkForTransactionArgs { network_name };
-        let new_context = match NetworkForTransactionArgsContext::from_previous_context(
+        let mut new_context = match NetworkForTransactionArgsContext::from_previous_context(
             context,
             &new_context_scope,
         ) {
@@ -108,6 +113,37 @@ impl interactive_clap::FromCli for NetworkForTransactionArgs {
         if new_context.prepopulated_transaction.actions.is_empty() {
             return interactive_clap::ResultFromCli::Cancel(Some(clap_variant));
         }
+        if let Err(err) =
+            crate::tx_builder::validate_actions(&new_context.prepopulated_transaction.actions)
+        {
+            return interactive_clap::ResultFromCli::Err(Some(clap_variant), err);
+        }
+        match self::action_check::check_actions(
+            &new_context.network_config,
+            &mut new_context.prepopulated_transaction,
+            new_context.global_context.offline,
+        ) {
+            Ok(true) => {}
+            Ok(false) => return interactive_clap::ResultFromCli::Cancel(Some(clap_variant)),
+            Err(err) => return interactive_clap::ResultFromCli::Err(Some(clap_variant), err),
+        }
+        if !new_context.global_context.offline {
+            if let Err(err) = self::protocol_version::check_protocol_version(
+                &new_context.network_config,
+                new_context.global_context.strict_protocol_check,
+            ) {
+                return interactive_clap::ResultFromCli::Err(Some(clap_variant), err);
+            }
+            if let Some(receiver_account_id) = &new_context.global_context.require_access_key_for {
+                if let Err(err) = self::access_key_requirement::check_required_access_key(
+                    &new_context.network_config,
+                    &new_context.prepopulated_transaction.signer_id,
+                    receiver_account_id,
+                ) {
+                    return interactive_clap::ResultFromCli::Err(Some(clap_variant), err);
+                }
+            }
+        }
         if new_context
             .network_config
             .meta_transaction_relayer_url
@@ -120,6 +156,54 @@ impl interactive_clap::FromCli for NetworkForTransactionArgs {
         crate::common::print_unsigned_transaction(&new_context.prepopulated_transaction);
         eprintln!();
 
+        if new_context.global_context.fee_estimate {
+            if new_context.global_context.offline {
+                if new_context.global_context.fee_estimate_only {
+                    return interactive_clap::ResultFromCli::Err(
+                        Some(clap_variant),
+                        color_eyre::eyre::eyre!("The fee cannot be estimated in offline mode"),
+                    );
+                }
+                eprintln!("The fee cannot be estimated in offline mode.\n");
+            } else {
+                match self::fee_estimate::estimate_fee(
+                    &new_context.network_config,
+                    &new_context.prepopulated_transaction,
+                ) {
+                    Ok(fee_estimate) => {
+                        eprintln!("{fee_estimate}\n");
+                        if new_context.global_context.fee_estimate_only {
+                            println!("{}", fee_estimate.max_fee().as_yoctonear());
+                            return interactive_clap::ResultFromCli::Cancel(Some(clap_variant));
+                        }
+                    }
+                    Err(err) => {
+                        return interactive_clap::ResultFromCli::Err(Some(clap_variant), err)
+                    }
+                }
+            }
+        }
+
+        if let Err(err) =
+            crate::transaction_signature_options::signing_policy::enforce_require_ledger_policy(
+                &new_context.global_context,
+                &new_context.network_config,
+                &new_context.prepopulated_transaction,
+                &mut clap_variant.transaction_signature_options,
+            )
+        {
+            return interactive_clap::ResultFromCli::Err(Some(clap_variant), err);
+        }
+        if let Err(err) =
+            crate::transaction_signature_options::signing_policy::enforce_yes_below_policy(
+                &new_context.global_context,
+                &new_context.network_config,
+                &new_context.prepopulated_transaction,
+            )
+        {
+            return interactive_clap::ResultFromCli::Err(Some(clap_variant), err);
+        }
+
         match <crate::transaction_signature_options::SignWith as interactive_clap::FromCli>::from_cli(
                 clap_variant.transaction_signature_options.take(),
                 new_context.into(),
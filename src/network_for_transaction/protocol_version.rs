@@ -0,0 +1,189 @@
+//! The check that the protocol version of the network is supported by the `near_primitives` this
+//! CLI was built with: a newer protocol may have changed the actions or their serialization, and
+//! the errors of the node are then confusing.
+
+use color_eyre::eyre::WrapErr;
+
+use crate::common::JsonRpcClientExt;
+
+/// The protocol versions supported by the `near_primitives` this CLI was built with. The networks
+/// usually upgrade before the CLI does and the newer protocols stay compatible, so only the older
+/// ones get a warning.
+pub const SUPPORTED_PROTOCOL_VERSIONS: std::ops::RangeInclusive<
+    near_primitives::types::ProtocolVersion,
+> = near_primitives::version::PEER_MIN_ALLOWED_PROTOCOL_VERSION
+    ..=near_primitives::version::PROTOCOL_VERSION;
+
+/// The protocol versions of the networks that were already fetched in this invocation.
+static PROTOCOL_VERSIONS: std::sync::Mutex<
+    std::collections::BTreeMap<String, near_primitives::types::ProtocolVersion>,
+> = std::sync::Mutex::new(std::collections::BTreeMap::new());
+
+/// Warns if the network runs a protocol version older than this CLI supports (a newer one is only
+/// noted), or fails with `strict`.
+pub fn check_protocol_version(
+    network_config: &crate::config::NetworkConfig,
+    strict: bool,
+) -> crate::CliResult {
+    let protocol_version = cached_protocol_version(&network_config.network_name, || {
+        get_protocol_version(network_config)
+    });
+    let protocol_version = match protocol_version {
+        Ok(protocol_version) => protocol_version,
+        Err(err) if strict => {
+            return Err(
+                err.wrap_err("Failed to check the protocol version (--strict-protocol-check)")
+            )
+        }
+        Err(err) => {
+            tracing::warn!("Failed to check the protocol version of the network: {err:#}");
+            return Ok(());
+        }
+    };
+    match incompatibility(&network_config.network_name, protocol_version) {
+        None => Ok(()),
+        Some(Incompatibility::Older(message) | Incompatibility::Newer(message)) if strict => {
+            Err(color_eyre::eyre::eyre!(
+                "{message}\nThe transaction was not signed (--strict-protocol-check)."
+            ))
+        }
+        Some(Incompatibility::Older(message)) => {
+            eprintln!("\nWARNING: {message}\n");
+            Ok(())
+        }
+        Some(Incompatibility::Newer(message)) => {
+            eprintln!("Note: {message}");
+            Ok(())
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Incompatibility {
+    Older(String),
+    Newer(String),
+}
+
+fn cached_protocol_version(
+    network_name: &str,
+    fetch: impl FnOnce() -> color_eyre::eyre::Result<near_primitives::types::ProtocolVersion>,
+) -> color_eyre::eyre::Result<near_primitives::types::ProtocolVersion> {
+    if let Some(protocol_version) = PROTOCOL_VERSIONS
+        .lock()
+        .expect("The protocol versions lock is poisoned")
+        .get(network_name)
+    {
+        return Ok(*protocol_version);
+    }
+    let protocol_version = fetch()?;
+    PROTOCOL_VERSIONS
+        .lock()
+        .expect("The protocol versions lock is poisoned")
+        .insert(network_name.to_string(), protocol_version);
+    Ok(protocol_version)
+}
+
+#[tracing::instrument(name = "Checking the protocol version of the network ...", skip_all)]
+fn get_protocol_version(
+    network_config: &crate::config::NetworkConfig,
+) -> color_eyre::eyre::Result<near_primitives::types::ProtocolVersion> {
+    let status = network_config
+        .json_rpc_client()
+        .blocking_call(near_jsonrpc_client::methods::status::RpcStatusRequest)
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch the status of the node of network <{}>",
+                network_config.network_name
+            )
+        })?;
+    Ok(status.protocol_version)
+}
+
+fn incompatibility(
+    network_name: &str,
+    protocol_version: near_primitives::types::ProtocolVersion,
+) -> Option<Incompatibility> {
+    let (min, max) = (
+        SUPPORTED_PROTOCOL_VERSIONS.start(),
+        SUPPORTED_PROTOCOL_VERSIONS.end(),
+    );
+    if protocol_version > *max {
+        Some(Incompatibility::Newer(format!(
+            "The network <{network_name}> runs protocol version {protocol_version}, newer than the version {max} this `near` CLI was built with. The transactions are usually still compatible; if one is rejected in a confusing way, upgrade the CLI (e.g. `near extensions self-update`)."
+        )))
+    } else if protocol_version < *min {
+        Some(Incompatibility::Older(format!(
+            "The network <{network_name}> runs protocol version {protocol_version}, which is older than the versions {min}-{max} supported by this `near` CLI, so the node may not understand the transaction."
+        )))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn supported_protocol_versions() {
+        let max = *SUPPORTED_PROTOCOL_VERSIONS.end();
+        let min = *SUPPORTED_PROTOCOL_VERSIONS.start();
+        assert_eq!(incompatibility("mainnet", max), None);
+        assert_eq!(incompatibility("mainnet", min), None);
+        assert!(matches!(
+            incompatibility("mainnet", max + 1),
+            Some(Incompatibility::Newer(message)) if message.contains("upgrade the CLI")
+        ));
+        assert!(matches!(
+            incompatibility("mainnet", min - 1),
+            Some(Incompatibility::Older(message)) if message.contains("is older than")
+        ));
+    }
+
+    #[test]
+    fn protocol_version_is_fetched_once_per_network() {
+        let status_response: near_primitives::views::StatusResponse =
+            serde_json::from_value(serde_json::json!({
+                "version": { "version": "2.5.0", "build": "2.5.0", "rustc_version": "1.84.0" },
+                "chain_id": "testnet",
+                "protocol_version": 1000,
+                "latest_protocol_version": 1000,
+                "rpc_addr": "0.0.0.0:3030",
+                "validators": [],
+                "sync_info": {
+                    "latest_block_hash": "11111111111111111111111111111111",
+                    "latest_block_height": 1,
+                    "latest_state_root": "11111111111111111111111111111111",
+                    "latest_block_time": "2025-01-01T00:00:00.000000000Z",
+                    "syncing": false,
+                    "earliest_block_hash": null,
+                    "earliest_block_height": null,
+                    "earliest_block_time": null,
+                    "epoch_id": "11111111111111111111111111111111",
+                    "epoch_start_height": null
+                },
+                "validator_account_id": null,
+                "validator_public_key": null,
+                "node_public_key": "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp",
+                "node_key": null,
+                "uptime_sec": 1,
+                "genesis_hash": "11111111111111111111111111111111",
+                "detailed_debug_status": null
+            }))
+            .unwrap();
+        let mut fetches = 0;
+        for _ in 0..2 {
+            let protocol_version = cached_protocol_version("mocked-network", || {
+                fetches += 1;
+                Ok(status_response.protocol_version)
+            })
+            .unwrap();
+            assert_eq!(protocol_version, 1000);
+        }
+        assert_eq!(fetches, 1);
+        assert!(matches!(
+            incompatibility("mocked-network", 1000),
+            Some(Incompatibility::Newer(_))
+        ));
+    }
+}
@@ -63,17 +63,27 @@ impl NetworkViewAtBlockArgs {
 /// Сhoose block for view:
 pub enum ViewAtBlock {
     #[strum_discriminants(strum(
-        message = "now               - View properties in the final block"
+        message = "now                  - View properties in the final block"
     ))]
     /// View properties in the final block
     Now(Now),
     #[strum_discriminants(strum(
-        message = "at-block-height   - View properties in a height-selected block"
+        message = "at-final-block       - View properties in the final block"
+    ))]
+    /// View properties in the final block
+    AtFinalBlock(AtFinalBlock),
+    #[strum_discriminants(strum(
+        message = "at-optimistic-block  - View properties in the latest (optimistic, not yet final) block"
+    ))]
+    /// View properties in the latest (optimistic, not yet final) block
+    AtOptimisticBlock(AtOptimisticBlock),
+    #[strum_discriminants(strum(
+        message = "at-block-height      - View properties in a height-selected block"
     ))]
     /// View properties in a height-selected block
     AtBlockHeight(AtBlockHeight),
     #[strum_discriminants(strum(
-        message = "at-block-hash     - View properties in a hash-selected block"
+        message = "at-block-hash        - View properties in a hash-selected block"
     ))]
     /// View properties in a hash-selected block
     AtBlockHash(BlockIdHash),
@@ -102,12 +112,58 @@ impl NowContext {
     }
 }
 
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = NetworkViewAtBlockArgsContext)]
+#[interactive_clap(output_context = AtFinalBlockContext)]
+pub struct AtFinalBlock;
+
+#[derive(Debug, Clone)]
+pub struct AtFinalBlockContext;
+
+impl AtFinalBlockContext {
+    pub fn from_previous_context(
+        previous_context: NetworkViewAtBlockArgsContext,
+        _scope: &<AtFinalBlock as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let block_reference = Finality::Final.into();
+
+        (previous_context.on_after_getting_block_reference_callback)(
+            &previous_context.network_config,
+            &block_reference,
+        )?;
+        Ok(Self)
+    }
+}
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = NetworkViewAtBlockArgsContext)]
+#[interactive_clap(output_context = AtOptimisticBlockContext)]
+pub struct AtOptimisticBlock;
+
+#[derive(Debug, Clone)]
+pub struct AtOptimisticBlockContext;
+
+impl AtOptimisticBlockContext {
+    pub fn from_previous_context(
+        previous_context: NetworkViewAtBlockArgsContext,
+        _scope: &<AtOptimisticBlock as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let block_reference = Finality::None.into();
+
+        (previous_context.on_after_getting_block_reference_callback)(
+            &previous_context.network_config,
+            &block_reference,
+        )?;
+        Ok(Self)
+    }
+}
+
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = NetworkViewAtBlockArgsContext)]
 #[interactive_clap(output_context = AtBlockHeightContext)]
 pub struct AtBlockHeight {
     /// Type the block ID height:
-    block_id_height: near_primitives::types::BlockHeight,
+    block_id_height: crate::types::block_height::BlockHeight,
 }
 
 #[derive(Debug, Clone)]
@@ -118,7 +174,7 @@ impl AtBlockHeightContext {
         previous_context: NetworkViewAtBlockArgsContext,
         scope: &<AtBlockHeight as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
-        let block_reference = BlockReference::BlockId(BlockId::Height(scope.block_id_height));
+        let block_reference = BlockReference::BlockId(BlockId::Height(scope.block_id_height.0));
 
         (previous_context.on_after_getting_block_reference_callback)(
             &previous_context.network_config,
@@ -155,3 +211,11 @@ impl BlockIdHashContext {
         Ok(Self)
     }
 }
+
+/// The block that answered the query, which is not known in advance for the final and optimistic blocks.
+pub fn display_viewed_at_block(
+    block_height: near_primitives::types::BlockHeight,
+    block_hash: &near_primitives::hash::CryptoHash,
+) {
+    eprintln!("\nViewed at block #{block_height} ({block_hash})");
+}
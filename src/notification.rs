@@ -0,0 +1,146 @@
+/// When to show a desktop notification about the transaction completion
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+    strum_macros::EnumString,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum NotifyMode {
+    Always,
+    /// Only when the transaction takes longer than `notify_after_secs` from the config
+    #[default]
+    Long,
+    Never,
+}
+
+impl std::fmt::Display for NotifyMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NotifyMode::Always => write!(f, "always"),
+            NotifyMode::Long => write!(f, "long"),
+            NotifyMode::Never => write!(f, "never"),
+        }
+    }
+}
+
+impl interactive_clap::ToCli for NotifyMode {
+    type CliVariant = NotifyMode;
+}
+
+/// Runs the broadcast (and the polling) of a transaction and notifies about its completion, so
+/// that users who switched to another window do not miss it.
+pub fn with_transaction_completion_notification<T>(
+    global_context: &crate::GlobalContext,
+    transaction_hash: near_primitives::hash::CryptoHash,
+    send: impl FnOnce() -> color_eyre::eyre::Result<T>,
+) -> color_eyre::eyre::Result<T> {
+    let started_at = std::time::Instant::now();
+    let result = send();
+    let should_notify = match global_context.notify {
+        NotifyMode::Always => true,
+        NotifyMode::Long => {
+            started_at.elapsed().as_secs() >= global_context.config.notify_after_secs
+        }
+        NotifyMode::Never => false,
+    };
+    if should_notify {
+        let outcome = if result.is_ok() {
+            "succeeded"
+        } else {
+            "failed"
+        };
+        show_notification(
+            "NEAR CLI",
+            &format!(
+                "transaction {} {outcome}",
+                abbreviate_hash(&transaction_hash)
+            ),
+        );
+    }
+    result
+}
+
+fn abbreviate_hash(transaction_hash: &near_primitives::hash::CryptoHash) -> String {
+    let transaction_hash = transaction_hash.to_string();
+    format!("{}…", &transaction_hash[..6])
+}
+
+/// Uses the notification tool of the platform without waiting for it (it may be slow to start or
+/// hang without a notification daemon); any failure (e.g. no graphical session) is ignored.
+fn show_notification(title: &str, body: &str) {
+    let mut command = if cfg!(target_os = "macos") {
+        let mut command = std::process::Command::new("osascript");
+        command.arg("-e").arg(format!(
+            "display notification {} with title {}",
+            apple_script_string(body),
+            apple_script_string(title)
+        ));
+        command
+    } else if cfg!(target_os = "windows") {
+        let mut command = std::process::Command::new("powershell");
+        command.args(["-NoProfile", "-NonInteractive", "-Command"]).arg(format!(
+            "[Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime] > $null; \
+            $template = [Windows.UI.Notifications.ToastNotificationManager]::GetTemplateContent([Windows.UI.Notifications.ToastTemplateType]::ToastText02); \
+            $text = $template.GetElementsByTagName('text'); \
+            $text.Item(0).AppendChild($template.CreateTextNode({})) > $null; \
+            $text.Item(1).AppendChild($template.CreateTextNode({})) > $null; \
+            [Windows.UI.Notifications.ToastNotificationManager]::CreateToastNotifier('NEAR CLI').Show([Windows.UI.Notifications.ToastNotification]::new($template))",
+            powershell_string(title),
+            powershell_string(body)
+        ));
+        command
+    } else {
+        if std::env::var_os("DISPLAY").is_none() && std::env::var_os("WAYLAND_DISPLAY").is_none() {
+            return;
+        }
+        let mut command = std::process::Command::new("notify-send");
+        command.arg(title).arg(body);
+        command
+    };
+    let Ok(mut child) = command
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+    else {
+        return;
+    };
+    // Reaps the tool if it finishes before the CLI does
+    std::thread::spawn(move || child.wait());
+}
+
+fn apple_script_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn powershell_string(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notify_mode_and_message() {
+        assert_eq!("always".parse::<NotifyMode>().unwrap(), NotifyMode::Always);
+        assert_eq!("never".parse::<NotifyMode>().unwrap(), NotifyMode::Never);
+        assert!("sometimes".parse::<NotifyMode>().is_err());
+        assert_eq!(NotifyMode::default().to_string(), "long");
+
+        let transaction_hash = near_primitives::hash::CryptoHash::hash_bytes(b"transaction");
+        let abbreviated = abbreviate_hash(&transaction_hash);
+        assert!(transaction_hash
+            .to_string()
+            .starts_with(abbreviated.trim_end_matches('…')));
+        assert_eq!(apple_script_string(r#"say "hi""#), r#""say \"hi\"""#);
+        assert_eq!(powershell_string("it's"), "'it''s'");
+    }
+}
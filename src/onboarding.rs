@@ -0,0 +1,211 @@
+//! The first-run wizard: when `near` is run without a command and there is no config yet, it
+//! creates the config, routes to the import or the creation of an account, offers a default
+//! network and prints a few commands to start with.
+//!
+//! It is skipped with `--no-onboarding` and never runs without a terminal (scripts, CI).
+
+use std::io::IsTerminal;
+
+use inquire::CustomType;
+
+/// The account ID shown in the examples when the user did not give one
+const EXAMPLE_ACCOUNT_ID_PLACEHOLDER: &str = "your-account.testnet";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum_macros::Display)]
+enum SetupChoice {
+    #[strum(to_string = "Yes, create the config with the mainnet and testnet networks")]
+    Create,
+    #[strum(to_string = "No, skip the setup (the default config is created)")]
+    Skip,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum_macros::Display)]
+enum AccountChoice {
+    #[strum(to_string = "I already have an account, import it")]
+    Import,
+    #[strum(to_string = "I want a new testnet account (sponsored by the faucet service)")]
+    CreateTestnetAccount,
+    #[strum(to_string = "Not now")]
+    NotNow,
+}
+
+/// What the wizard decided: the command to continue with (empty for the main menu) and what the
+/// examples are personalized with.
+#[derive(Debug, Clone)]
+pub struct Onboarding {
+    pub next_command: Vec<String>,
+    account_id: Option<crate::types::account_id::AccountId>,
+    network_name: String,
+}
+
+/// Whether the wizard should run: the first run (no config file), without a command, in a
+/// terminal and without `--no-onboarding`.
+pub fn should_run(has_command: bool, no_onboarding: bool) -> bool {
+    !has_command
+        && !no_onboarding
+        && std::io::stdin().is_terminal()
+        && std::io::stderr().is_terminal()
+        && crate::config::Config::config_toml_path().is_some_and(|path| !path.is_file())
+}
+
+/// Runs the wizard and writes the config. Returns `None` if the setup was skipped.
+pub fn run(config: &mut crate::config::Config) -> color_eyre::eyre::Result<Option<Onboarding>> {
+    eprintln!("Welcome to near CLI! It looks like this is the first time you run it.\n");
+    if crate::common::select(
+        "Do you want to set up near CLI now?",
+        vec![SetupChoice::Create, SetupChoice::Skip],
+    )? != Some(SetupChoice::Create)
+    {
+        config.clone().write_config_toml()?;
+        return Ok(None);
+    }
+
+    let account_choice = crate::common::select(
+        "Do you already have a NEAR account?",
+        vec![
+            AccountChoice::Import,
+            AccountChoice::CreateTestnetAccount,
+            AccountChoice::NotNow,
+        ],
+    )?
+    .unwrap_or(AccountChoice::NotNow);
+    let account_id = match account_choice {
+        AccountChoice::Import => {
+            CustomType::new("What is your account ID (for the examples, press Esc to skip)?")
+                .prompt_skippable()?
+        }
+        AccountChoice::CreateTestnetAccount => {
+            CustomType::new("What is the new account ID (e.g. alice.testnet)?")
+                .prompt_skippable()?
+        }
+        AccountChoice::NotNow => None,
+    };
+    let account_choice = match (account_choice, &account_id) {
+        (AccountChoice::CreateTestnetAccount, None) => AccountChoice::NotNow,
+        _ => account_choice,
+    };
+
+    let mut network_names = config
+        .network_connection
+        .keys()
+        .cloned()
+        .collect::<Vec<_>>();
+    if account_choice == AccountChoice::CreateTestnetAccount {
+        network_names.sort_by_key(|network_name| network_name != "testnet");
+    }
+    let no_default_network = "No default network (choose it every time)".to_string();
+    let default_network = crate::common::select(
+        "Which network do you want to use by default (offered first when a network is selected)?",
+        network_names
+            .into_iter()
+            .chain(std::iter::once(no_default_network.clone()))
+            .collect(),
+    )?
+    .filter(|network_name| *network_name != no_default_network);
+    config.default_network = default_network.clone();
+    config.clone().write_config_toml()?;
+
+    let network_name = match account_choice {
+        AccountChoice::CreateTestnetAccount => "testnet".to_string(),
+        _ => default_network.unwrap_or_else(|| "testnet".to_string()),
+    };
+    let next_command = next_command(account_choice, account_id.as_ref());
+    if !next_command.is_empty() {
+        eprintln!(
+            "\nLet's continue with: near {}\n",
+            shell_words::join(&next_command)
+        );
+    }
+    Ok(Some(Onboarding {
+        next_command,
+        account_id,
+        network_name,
+    }))
+}
+
+fn next_command(
+    account_choice: AccountChoice,
+    account_id: Option<&crate::types::account_id::AccountId>,
+) -> Vec<String> {
+    match (account_choice, account_id) {
+        (AccountChoice::Import, _) => vec!["account".to_string(), "import-account".to_string()],
+        (AccountChoice::CreateTestnetAccount, Some(account_id)) => [
+            "account",
+            "create-account",
+            "sponsor-by-faucet-service",
+            account_id.as_ref(),
+            "autogenerate-new-keypair",
+            "save-to-keychain",
+            "network-config",
+            "testnet",
+            "create",
+        ]
+        .map(str::to_string)
+        .to_vec(),
+        _ => Vec::new(),
+    }
+}
+
+impl Onboarding {
+    pub fn print_examples(&self) {
+        eprintln!("\nYou are all set! Here are a few commands to try:");
+        for example in self.examples() {
+            eprintln!("    {example}");
+        }
+        eprintln!("Run `near` without arguments to explore all the commands interactively.");
+    }
+
+    fn examples(&self) -> Vec<String> {
+        let account_id = self
+            .account_id
+            .as_ref()
+            .map_or(EXAMPLE_ACCOUNT_ID_PLACEHOLDER, |account_id| {
+                account_id.as_ref()
+            });
+        let network_name = &self.network_name;
+        vec![
+            format!(
+                "near account view-account-summary {account_id} network-config {network_name} now"
+            ),
+            format!("near tokens {account_id} view-near-balance network-config {network_name} now"),
+            format!("near account list-keys {account_id} network-config {network_name} now"),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_next_command_and_the_examples_use_the_account_id() {
+        let account_id: crate::types::account_id::AccountId = "alice.testnet".parse().unwrap();
+        assert_eq!(
+            shell_words::join(next_command(
+                AccountChoice::CreateTestnetAccount,
+                Some(&account_id)
+            )),
+            "account create-account sponsor-by-faucet-service alice.testnet autogenerate-new-keypair save-to-keychain network-config testnet create"
+        );
+        assert_eq!(
+            next_command(AccountChoice::Import, None),
+            vec!["account", "import-account"]
+        );
+        assert!(next_command(AccountChoice::NotNow, Some(&account_id)).is_empty());
+
+        let onboarding = Onboarding {
+            next_command: Vec::new(),
+            account_id: Some(account_id),
+            network_name: "testnet".to_string(),
+        };
+        assert_eq!(
+            onboarding.examples()[0],
+            "near account view-account-summary alice.testnet network-config testnet now"
+        );
+        let onboarding = Onboarding {
+            account_id: None,
+            ..onboarding
+        };
+        assert!(onboarding.examples()[1].starts_with("near tokens your-account.testnet "));
+    }
+}
@@ -0,0 +1,83 @@
+/// How the interactive prompts are rendered
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+    strum_macros::EnumString,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum PromptStyle {
+    /// Colored selectors that are redrawn as the cursor moves
+    #[default]
+    Fancy,
+    /// Numbered lists read line by line, without colors or redrawing (for screen readers and light terminals)
+    Plain,
+}
+
+impl std::fmt::Display for PromptStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PromptStyle::Fancy => write!(f, "fancy"),
+            PromptStyle::Plain => write!(f, "plain"),
+        }
+    }
+}
+
+static PLAIN_PROMPTS: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// Applies the prompt style to all the prompts of this run. It must be called before the first
+/// prompt: the selectors of [`crate::common::select`] become numbered lists and the other prompts
+/// (including the ones of the command menus) lose their colors.
+pub fn apply(prompt_style: PromptStyle) {
+    PLAIN_PROMPTS.store(
+        prompt_style == PromptStyle::Plain,
+        std::sync::atomic::Ordering::Relaxed,
+    );
+    if prompt_style == PromptStyle::Plain {
+        inquire::set_global_render_config(inquire::ui::RenderConfig::empty());
+    }
+}
+
+/// Whether the console renders the colors and the redrawing of the prompts. On Windows, the
+/// virtual terminal processing is enabled first (without it, legacy consoles print the escape
+/// codes as garbage).
+pub fn terminal_supports_ansi() -> bool {
+    #[cfg(windows)]
+    {
+        crossterm::ansi_support::supports_ansi()
+    }
+    #[cfg(not(windows))]
+    {
+        true
+    }
+}
+
+pub fn is_plain() -> bool {
+    PLAIN_PROMPTS.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prompt_style_in_config() {
+        #[derive(serde::Deserialize)]
+        struct Config {
+            #[serde(default)]
+            prompt_style: PromptStyle,
+        }
+        let config: Config = toml::from_str(r#"prompt_style = "plain""#).unwrap();
+        assert_eq!(config.prompt_style, PromptStyle::Plain);
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.prompt_style, PromptStyle::Fancy);
+        assert!(toml::from_str::<Config>(r#"prompt_style = "colorful""#).is_err());
+        assert_eq!("plain".parse::<PromptStyle>().unwrap(), PromptStyle::Plain);
+    }
+}
@@ -0,0 +1,541 @@
+//! QR codes of URLs for the terminal, so that a wallet on another device (e.g. a phone) can
+//! open them.
+//!
+//! The codes use the byte mode and the low error correction level (the largest capacity, which
+//! is enough on a screen), the smallest version that fits the data and the mask with the lowest
+//! penalty, as described in ISO/IEC 18004. They are rendered with the Unicode half blocks, two
+//! rows of modules per line, for terminals with a dark background.
+
+/// The error correction codewords per block of the low level, by version (index 0 is unused)
+const ECC_CODEWORDS_PER_BLOCK: [usize; 41] = [
+    0, 7, 10, 15, 20, 26, 18, 20, 24, 30, 18, 20, 24, 26, 30, 22, 24, 28, 30, 28, 28, 28, 28, 30,
+    30, 26, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30,
+];
+
+/// The error correction blocks of the low level, by version (index 0 is unused)
+const ERROR_CORRECTION_BLOCKS: [usize; 41] = [
+    0, 1, 1, 1, 1, 1, 2, 2, 2, 2, 4, 4, 4, 4, 4, 6, 6, 6, 6, 7, 8, 8, 9, 9, 10, 12, 12, 12, 13, 14,
+    15, 16, 17, 18, 19, 19, 20, 21, 22, 24, 25,
+];
+
+/// The light modules around the code (the standard asks for 4, scanners read 2 on a screen)
+const QUIET_ZONE: usize = 2;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QrCode {
+    version: usize,
+    size: usize,
+    /// `modules[y][x]` is true for the dark modules
+    modules: Vec<Vec<bool>>,
+    is_function: Vec<Vec<bool>>,
+}
+
+impl QrCode {
+    /// The code of the bytes, or an error if they do not fit in a QR code (2953 bytes).
+    pub fn encode(data: &[u8]) -> color_eyre::eyre::Result<Self> {
+        let version = (1..=40)
+            .find(|version| data_bits_needed(*version, data.len()) <= data_codewords(*version) * 8)
+            .ok_or_else(|| {
+                color_eyre::eyre::eyre!("The data ({} bytes) is too long for a QR code", data.len())
+            })?;
+
+        let mut bits = BitBuffer::default();
+        bits.append(0b0100, 4);
+        bits.append(data.len() as u32, char_count_bits(version));
+        for byte in data {
+            bits.append(u32::from(*byte), 8);
+        }
+        let capacity = data_codewords(version) * 8;
+        bits.append(0, (capacity - bits.0.len()).min(4));
+        bits.append(0, (8 - bits.0.len() % 8) % 8);
+        for pad in [0xEC, 0x11].into_iter().cycle() {
+            if bits.0.len() >= capacity {
+                break;
+            }
+            bits.append(pad, 8);
+        }
+        let codewords: Vec<u8> = bits
+            .0
+            .chunks(8)
+            .map(|byte| byte.iter().fold(0, |acc, bit| acc << 1 | u8::from(*bit)))
+            .collect();
+
+        let size = version * 4 + 17;
+        let mut code = Self {
+            version,
+            size,
+            modules: vec![vec![false; size]; size],
+            is_function: vec![vec![false; size]; size],
+        };
+        code.draw_function_patterns();
+        code.draw_codewords(&add_ecc_and_interleave(version, &codewords));
+
+        let mask = (0..8)
+            .min_by_key(|mask| {
+                let mut masked = code.clone();
+                masked.apply_mask(*mask);
+                masked.draw_format_bits(*mask);
+                masked.penalty_score()
+            })
+            .unwrap_or(0);
+        code.apply_mask(mask);
+        code.draw_format_bits(mask);
+        Ok(code)
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_dark(&self, x: usize, y: usize) -> bool {
+        self.modules[y][x]
+    }
+
+    /// The code in the Unicode half blocks, with the light modules drawn (for terminals with a
+    /// dark background) and a quiet zone.
+    pub fn to_terminal_string(&self) -> String {
+        let full_size = self.size + QUIET_ZONE * 2;
+        let is_light = |x: usize, y: usize| {
+            let (Some(x), Some(y)) = (x.checked_sub(QUIET_ZONE), y.checked_sub(QUIET_ZONE)) else {
+                return true;
+            };
+            x >= self.size || y >= self.size || !self.is_dark(x, y)
+        };
+        let mut rendered = String::new();
+        for y in (0..full_size).step_by(2) {
+            for x in 0..full_size {
+                rendered.push(
+                    match (is_light(x, y), y + 1 < full_size && is_light(x, y + 1)) {
+                        (true, true) => '█',
+                        (true, false) => '▀',
+                        (false, true) => '▄',
+                        (false, false) => ' ',
+                    },
+                );
+            }
+            rendered.push('\n');
+        }
+        rendered
+    }
+
+    fn set_function_module(&mut self, x: usize, y: usize, is_dark: bool) {
+        self.modules[y][x] = is_dark;
+        self.is_function[y][x] = true;
+    }
+
+    fn draw_function_patterns(&mut self) {
+        for i in 0..self.size {
+            self.set_function_module(6, i, i % 2 == 0);
+            self.set_function_module(i, 6, i % 2 == 0);
+        }
+        for (x, y) in [(3, 3), (self.size - 4, 3), (3, self.size - 4)] {
+            self.draw_finder_pattern(x, y);
+        }
+        let positions = alignment_pattern_positions(self.version);
+        let last = positions.len().saturating_sub(1);
+        for (i, x) in positions.iter().enumerate() {
+            for (j, y) in positions.iter().enumerate() {
+                // The corners of the finder patterns
+                if (i, j) != (0, 0) && (i, j) != (0, last) && (i, j) != (last, 0) {
+                    self.draw_alignment_pattern(*x, *y);
+                }
+            }
+        }
+        // Reserves the format areas, drawn once the mask is chosen
+        self.draw_format_bits(0);
+        self.draw_version();
+    }
+
+    fn draw_finder_pattern(&mut self, x: usize, y: usize) {
+        for dy in -4..=4_isize {
+            for dx in -4..=4_isize {
+                let distance = dx.abs().max(dy.abs());
+                let (Some(xx), Some(yy)) = (x.checked_add_signed(dx), y.checked_add_signed(dy))
+                else {
+                    continue;
+                };
+                if xx < self.size && yy < self.size {
+                    self.set_function_module(xx, yy, distance != 2 && distance != 4);
+                }
+            }
+        }
+    }
+
+    fn draw_alignment_pattern(&mut self, x: usize, y: usize) {
+        for dy in -2..=2_isize {
+            for dx in -2..=2_isize {
+                self.set_function_module(
+                    x.wrapping_add_signed(dx),
+                    y.wrapping_add_signed(dy),
+                    dx.abs().max(dy.abs()) != 1,
+                );
+            }
+        }
+    }
+
+    fn draw_format_bits(&mut self, mask: u32) {
+        // The low error correction level is 0b01
+        let data = 0b01 << 3 | mask;
+        let mut remainder = data;
+        for _ in 0..10 {
+            remainder = (remainder << 1) ^ ((remainder >> 9) * 0x537);
+        }
+        let bits = (data << 10 | remainder) ^ 0x5412;
+        let bit = |i: usize| (bits >> i) & 1 != 0;
+
+        for i in 0..=5 {
+            self.set_function_module(8, i, bit(i));
+        }
+        self.set_function_module(8, 7, bit(6));
+        self.set_function_module(8, 8, bit(7));
+        self.set_function_module(7, 8, bit(8));
+        for i in 9..15 {
+            self.set_function_module(14 - i, 8, bit(i));
+        }
+
+        let size = self.size;
+        for i in 0..8 {
+            self.set_function_module(size - 1 - i, 8, bit(i));
+        }
+        for i in 8..15 {
+            self.set_function_module(8, size - 15 + i, bit(i));
+        }
+        // The dark module
+        self.set_function_module(8, size - 8, true);
+    }
+
+    fn draw_version(&mut self) {
+        if self.version < 7 {
+            return;
+        }
+        let version = self.version as u32;
+        let mut remainder = version;
+        for _ in 0..12 {
+            remainder = (remainder << 1) ^ ((remainder >> 11) * 0x1F25);
+        }
+        let bits = version << 12 | remainder;
+        for i in 0..18 {
+            let is_dark = (bits >> i) & 1 != 0;
+            let a = self.size - 11 + i % 3;
+            let b = i / 3;
+            self.set_function_module(a, b, is_dark);
+            self.set_function_module(b, a, is_dark);
+        }
+    }
+
+    /// Places the bits in the zigzag of two columns, from the bottom right corner.
+    fn draw_codewords(&mut self, codewords: &[u8]) {
+        let mut bit_index = 0;
+        let mut right = self.size - 1;
+        loop {
+            if right == 6 {
+                right = 5;
+            }
+            let upward = (right + 1) & 2 == 0;
+            for vertical in 0..self.size {
+                for x in [right, right - 1] {
+                    let y = if upward {
+                        self.size - 1 - vertical
+                    } else {
+                        vertical
+                    };
+                    if !self.is_function[y][x] && bit_index < codewords.len() * 8 {
+                        self.modules[y][x] =
+                            (codewords[bit_index / 8] >> (7 - bit_index % 8)) & 1 != 0;
+                        bit_index += 1;
+                    }
+                }
+            }
+            if right < 2 {
+                break;
+            }
+            right -= 2;
+        }
+    }
+
+    fn apply_mask(&mut self, mask: u32) {
+        for y in 0..self.size {
+            for x in 0..self.size {
+                let invert = match mask {
+                    0 => (x + y) % 2 == 0,
+                    1 => y % 2 == 0,
+                    2 => x % 3 == 0,
+                    3 => (x + y) % 3 == 0,
+                    4 => (x / 3 + y / 2) % 2 == 0,
+                    5 => x * y % 2 + x * y % 3 == 0,
+                    6 => (x * y % 2 + x * y % 3) % 2 == 0,
+                    _ => ((x + y) % 2 + x * y % 3) % 2 == 0,
+                };
+                self.modules[y][x] ^= invert && !self.is_function[y][x];
+            }
+        }
+    }
+
+    fn penalty_score(&self) -> usize {
+        let rows: Vec<Vec<bool>> = self.modules.clone();
+        let columns: Vec<Vec<bool>> = (0..self.size)
+            .map(|x| (0..self.size).map(|y| self.modules[y][x]).collect())
+            .collect();
+        let mut score = 0;
+        for line in rows.iter().chain(&columns) {
+            // Runs of five or more modules of the same color
+            for run in line.chunk_by(|a, b| a == b) {
+                if run.len() >= 5 {
+                    score += 3 + run.len() - 5;
+                }
+            }
+            // Patterns that look like the finder patterns
+            let finder_like = [true, false, true, true, true, false, true];
+            let light = [false; 4];
+            for window in line.windows(11) {
+                if window[..7] == finder_like && window[7..] == light
+                    || window[..4] == light && window[4..] == finder_like
+                {
+                    score += 40;
+                }
+            }
+        }
+        // Blocks of 2x2 modules of the same color
+        for y in 0..self.size - 1 {
+            for x in 0..self.size - 1 {
+                let color = self.modules[y][x];
+                if color == self.modules[y][x + 1]
+                    && color == self.modules[y + 1][x]
+                    && color == self.modules[y + 1][x + 1]
+                {
+                    score += 3;
+                }
+            }
+        }
+        // The balance of the dark and the light modules
+        let total = self.size * self.size;
+        let dark = self.modules.iter().flatten().filter(|dark| **dark).count();
+        let deviation = (dark * 20).abs_diff(total * 10);
+        score + (deviation.div_ceil(total)).saturating_sub(1) * 10
+    }
+}
+
+/// Prints the caption and the QR code of the URL, or why the URL has no QR code.
+pub fn eprint_url(caption: &str, url: &url::Url) {
+    match QrCode::encode(url.as_str().as_bytes()) {
+        Ok(qr_code) => eprintln!("{caption}\n{}", qr_code.to_terminal_string()),
+        Err(err) => eprintln!("The URL has no QR code: {err}\n"),
+    }
+}
+
+#[derive(Debug, Default)]
+struct BitBuffer(Vec<bool>);
+
+impl BitBuffer {
+    fn append(&mut self, value: u32, length: usize) {
+        self.0
+            .extend((0..length).rev().map(|i| (value >> i) & 1 != 0));
+    }
+}
+
+fn char_count_bits(version: usize) -> usize {
+    if version < 10 {
+        8
+    } else {
+        16
+    }
+}
+
+fn data_bits_needed(version: usize, data_len: usize) -> usize {
+    if data_len >= 1 << char_count_bits(version) {
+        return usize::MAX;
+    }
+    4 + char_count_bits(version) + data_len * 8
+}
+
+/// The modules left for the data and the error correction codewords, once the function patterns
+/// are drawn.
+fn raw_data_modules(version: usize) -> usize {
+    let mut modules = (16 * version + 128) * version + 64;
+    if version >= 2 {
+        let alignment_patterns = version / 7 + 2;
+        modules -= (25 * alignment_patterns - 10) * alignment_patterns - 55;
+        if version >= 7 {
+            modules -= 36;
+        }
+    }
+    modules
+}
+
+fn data_codewords(version: usize) -> usize {
+    raw_data_modules(version) / 8
+        - ECC_CODEWORDS_PER_BLOCK[version] * ERROR_CORRECTION_BLOCKS[version]
+}
+
+fn alignment_pattern_positions(version: usize) -> Vec<usize> {
+    if version == 1 {
+        return vec![];
+    }
+    let count = version / 7 + 2;
+    let step = (version * 8 + count * 3 + 5) / (count * 4 - 4) * 2;
+    let size = version * 4 + 17;
+    let mut positions: Vec<usize> = (0..count - 1).map(|i| size - 7 - i * step).collect();
+    positions.push(6);
+    positions.reverse();
+    positions
+}
+
+/// Splits the data into the blocks, appends the error correction codewords to each and
+/// interleaves the blocks.
+fn add_ecc_and_interleave(version: usize, data: &[u8]) -> Vec<u8> {
+    let blocks_count = ERROR_CORRECTION_BLOCKS[version];
+    let ecc_len = ECC_CODEWORDS_PER_BLOCK[version];
+    let raw_codewords = raw_data_modules(version) / 8;
+    let short_blocks_count = blocks_count - raw_codewords % blocks_count;
+    let short_block_len = raw_codewords / blocks_count;
+
+    let divisor = reed_solomon_divisor(ecc_len);
+    let mut blocks = Vec::with_capacity(blocks_count);
+    let mut data = data;
+    for i in 0..blocks_count {
+        let data_len = short_block_len - ecc_len + usize::from(i >= short_blocks_count);
+        let (block_data, rest) = data.split_at(data_len);
+        data = rest;
+        let mut block = block_data.to_vec();
+        let ecc = reed_solomon_remainder(block_data, &divisor);
+        if i < short_blocks_count {
+            // A placeholder that is skipped when interleaving
+            block.push(0);
+        }
+        block.extend(ecc);
+        blocks.push(block);
+    }
+
+    let mut interleaved = Vec::with_capacity(raw_codewords);
+    for i in 0..blocks[0].len() {
+        for (j, block) in blocks.iter().enumerate() {
+            if i != short_block_len - ecc_len || j >= short_blocks_count {
+                interleaved.push(block[i]);
+            }
+        }
+    }
+    interleaved
+}
+
+fn reed_solomon_divisor(degree: usize) -> Vec<u8> {
+    let mut divisor = vec![0; degree];
+    divisor[degree - 1] = 1;
+    let mut root = 1;
+    for _ in 0..degree {
+        for j in 0..degree {
+            divisor[j] = gf_multiply(divisor[j], root);
+            if j + 1 < degree {
+                divisor[j] ^= divisor[j + 1];
+            }
+        }
+        root = gf_multiply(root, 0x02);
+    }
+    divisor
+}
+
+fn reed_solomon_remainder(data: &[u8], divisor: &[u8]) -> Vec<u8> {
+    let mut remainder = vec![0; divisor.len()];
+    for byte in data {
+        let factor = byte ^ remainder.remove(0);
+        remainder.push(0);
+        for (coefficient, value) in divisor.iter().zip(remainder.iter_mut()) {
+            *value ^= gf_multiply(*coefficient, factor);
+        }
+    }
+    remainder
+}
+
+/// The product in GF(2^8) modulo x^8 + x^4 + x^3 + x^2 + 1
+fn gf_multiply(x: u8, y: u8) -> u8 {
+    let mut product: u32 = 0;
+    for i in (0..8).rev() {
+        product = (product << 1) ^ ((product >> 7) * 0x11D);
+        product ^= u32::from((y >> i) & 1) * u32::from(x);
+    }
+    product as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_correction_of_hello_world() {
+        // The 1-M example of "HELLO WORLD" in the alphanumeric mode
+        let data = [
+            32, 91, 11, 120, 209, 114, 220, 77, 67, 64, 236, 17, 236, 17, 236, 17,
+        ];
+        assert_eq!(
+            reed_solomon_remainder(&data, &reed_solomon_divisor(10)),
+            [196, 35, 39, 119, 235, 215, 231, 226, 93, 23]
+        );
+    }
+
+    #[test]
+    fn versions_and_capacity() {
+        assert_eq!(data_codewords(1), 19);
+        assert_eq!(data_codewords(40), 2956);
+        assert_eq!(alignment_pattern_positions(2), [6, 18]);
+        assert_eq!(alignment_pattern_positions(32), [6, 34, 60, 86, 112, 138]);
+
+        assert_eq!(QrCode::encode(&[b'a'; 17]).unwrap().size(), 21);
+        assert_eq!(QrCode::encode(&[b'a'; 18]).unwrap().size(), 25);
+        assert_eq!(QrCode::encode(&[b'a'; 2953]).unwrap().size(), 177);
+        assert!(QrCode::encode(&[b'a'; 2954]).is_err());
+    }
+
+    #[test]
+    fn function_patterns_and_format() {
+        let code = QrCode::encode(b"https://wallet.near.org/sign?transactions=...").unwrap();
+        let size = code.size();
+        // The finder patterns with their separators
+        for (x, y) in [(0, 0), (size - 7, 0), (0, size - 7)] {
+            for offset in 0..7 {
+                assert!(code.is_dark(x + offset, y));
+                assert!(code.is_dark(x, y + offset));
+            }
+            assert!(!code.is_dark(x + 1, y + 1));
+            assert!(code.is_dark(x + 3, y + 3));
+        }
+        assert!(!code.is_dark(7, 7));
+        assert!(code.is_dark(8, size - 8));
+
+        // Both copies of the format bits are the same
+        let first: Vec<bool> = (0..=5)
+            .map(|y| code.is_dark(8, y))
+            .chain([code.is_dark(8, 7), code.is_dark(8, 8), code.is_dark(7, 8)])
+            .chain((9..15).map(|i| code.is_dark(14 - i, 8)))
+            .collect();
+        let second: Vec<bool> = (0..8)
+            .map(|i| code.is_dark(size - 1 - i, 8))
+            .chain((8..15).map(|i| code.is_dark(8, size - 15 + i)))
+            .collect();
+        assert_eq!(first, second);
+        // The format of the low level with the mask 0 (0b111011111000100)
+        let mut mask_0 = code.clone();
+        mask_0.draw_format_bits(0);
+        let bits = (0..15).fold(0, |bits, i| {
+            let bit = if i < 8 {
+                mask_0.is_dark(size - 1 - i, 8)
+            } else {
+                mask_0.is_dark(8, size - 15 + i)
+            };
+            bits | u32::from(bit) << i
+        });
+        assert_eq!(bits, 0b111011111000100);
+    }
+
+    #[test]
+    fn rendering_in_half_blocks() {
+        let code = QrCode::encode(b"near").unwrap();
+        let rendered = code.to_terminal_string();
+        let lines: Vec<&str> = rendered.lines().collect();
+        // 21 modules with the quiet zone, two rows per line
+        assert_eq!(lines.len(), 13);
+        assert!(lines.iter().all(|line| line.chars().count() == 25));
+        assert_eq!(lines[0], "█".repeat(25));
+        // The first two rows of the finder pattern and its separator
+        assert!(lines[1].starts_with("██ ▄▄▄▄▄ █"));
+        assert!(lines[12].chars().all(|c| c == '▀'));
+    }
+}
@@ -0,0 +1,403 @@
+//! Client-side rate limiting of JSON RPC requests.
+//!
+//! All the requests of one invocation to the same RPC server share a single token bucket, so the
+//! concurrency of batch commands does not multiply the request rate. When the server still
+//! responds with HTTP 429, the bucket is paused for the `Retry-After` of the response (or with an
+//! exponential backoff without it) so that the concurrent requests back off together.
+//!
+//! near-jsonrpc-client does not expose the headers of the response, so the requests are sent
+//! here the same way as its `call` does, with the headers of the client.
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use near_jsonrpc_client::errors::{
+    JsonRpcError, JsonRpcServerError, JsonRpcServerResponseStatusError,
+    JsonRpcTransportHandlerResponseError, JsonRpcTransportRecvError, JsonRpcTransportSendError,
+    RpcTransportError,
+};
+
+/// The default rate limit of the public RPC endpoints (see [`PUBLIC_RPC_HOSTS`])
+pub const DEFAULT_PUBLIC_RPC_RATE_LIMIT_RPS: u32 = 10;
+
+/// The hosts (and their subdomains) of the public RPC endpoints that are rate limited by default
+const PUBLIC_RPC_HOSTS: &[&str] = &["near.org", "fastnear.com", "lava.build", "drpc.org"];
+
+const MAX_RETRIES_ON_TOO_MANY_REQUESTS: u32 = 3;
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+#[derive(Debug)]
+struct TokenBucket {
+    rps: f64,
+    tokens: f64,
+    /// The time of the last refill, may be in the future while the bucket is paused
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    fn new(rps: u32, now: Instant) -> Self {
+        Self {
+            rps: f64::from(rps),
+            tokens: f64::from(rps),
+            refilled_at: now,
+        }
+    }
+
+    /// Takes a token and returns how long to wait before sending the request.
+    ///
+    /// The number of tokens goes below zero when the requests are queued.
+    fn reserve(&mut self, now: Instant) -> Duration {
+        if now > self.refilled_at {
+            let elapsed = now.duration_since(self.refilled_at).as_secs_f64();
+            self.tokens = (self.tokens + elapsed * self.rps).min(self.rps);
+            self.refilled_at = now;
+        }
+        self.tokens -= 1.0;
+        let paused_for = self.refilled_at.saturating_duration_since(now);
+        if self.tokens >= 0.0 {
+            paused_for
+        } else {
+            paused_for + Duration::from_secs_f64(-self.tokens / self.rps)
+        }
+    }
+
+    /// Lets no requests through for `delay`, one request right after it.
+    fn pause(&mut self, now: Instant, delay: Duration) {
+        let resume_at = now + delay;
+        if resume_at > self.refilled_at {
+            self.refilled_at = resume_at;
+            self.tokens = 1.0;
+        }
+    }
+}
+
+fn token_buckets() -> &'static Mutex<HashMap<String, TokenBucket>> {
+    static TOKEN_BUCKETS: OnceLock<Mutex<HashMap<String, TokenBucket>>> = OnceLock::new();
+    TOKEN_BUCKETS.get_or_init(Default::default)
+}
+
+fn with_token_bucket<T>(server_addr: &str, f: impl FnOnce(&mut TokenBucket) -> T) -> Option<T> {
+    let mut token_buckets = token_buckets()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    token_buckets.get_mut(server_addr).map(f)
+}
+
+/// The rate limit of a network connection: `rpc_rate_limit_rps` from the config, otherwise
+/// [`DEFAULT_PUBLIC_RPC_RATE_LIMIT_RPS`] for the public endpoints. Zero means no limit.
+pub fn rpc_rate_limit_rps(rpc_url: &url::Url, rpc_rate_limit_rps: Option<u32>) -> Option<u32> {
+    let rps = rpc_rate_limit_rps.or_else(|| {
+        let host = rpc_url.host_str()?;
+        PUBLIC_RPC_HOSTS
+            .iter()
+            .any(|public_host| host == *public_host || host.ends_with(&format!(".{public_host}")))
+            .then_some(DEFAULT_PUBLIC_RPC_RATE_LIMIT_RPS)
+    })?;
+    (rps > 0).then_some(rps)
+}
+
+/// Sets up the token bucket of an RPC server (the bucket is kept if the limit has not changed).
+pub fn set_rpc_rate_limit(server_addr: &str, rps: Option<u32>) {
+    let mut token_buckets = token_buckets()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    match rps {
+        Some(rps) => {
+            if token_buckets
+                .get(server_addr)
+                .map_or(true, |token_bucket| token_bucket.rps != f64::from(rps))
+            {
+                token_buckets.insert(
+                    server_addr.to_string(),
+                    TokenBucket::new(rps, Instant::now()),
+                );
+            }
+        }
+        None => {
+            token_buckets.remove(server_addr);
+        }
+    }
+}
+
+fn too_many_requests_delay(attempt: u32, retry_after: Option<Duration>) -> Duration {
+    retry_after
+        .unwrap_or_else(|| Duration::from_secs(1 << attempt))
+        .min(MAX_BACKOFF)
+}
+
+/// The delay of a `Retry-After` header: a number of seconds or an HTTP date.
+fn parse_retry_after(value: &str, now: chrono::DateTime<chrono::Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let retry_at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (retry_at.with_timezone(&chrono::Utc) - now)
+            .to_std()
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+fn http_client() -> &'static reqwest::Client {
+    static HTTP_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    HTTP_CLIENT.get_or_init(|| {
+        let mut headers = reqwest::header::HeaderMap::with_capacity(1);
+        headers.insert(
+            reqwest::header::CONTENT_TYPE,
+            reqwest::header::HeaderValue::from_static("application/json"),
+        );
+        reqwest::Client::builder()
+            .default_headers(headers)
+            .build()
+            .expect("The HTTP client of the RPC requests cannot be built")
+    })
+}
+
+/// Sends the request as [`near_jsonrpc_client::JsonRpcClient::call`] does, and returns the
+/// `Retry-After` delay of an HTTP 429 response along with the error.
+async fn send_request<M>(
+    json_rpc_client: &near_jsonrpc_client::JsonRpcClient,
+    method: &M,
+) -> (
+    near_jsonrpc_client::MethodCallResult<M::Response, M::Error>,
+    Option<Duration>,
+)
+where
+    M: near_jsonrpc_client::methods::RpcMethod,
+{
+    let send_error = |err| {
+        Err(JsonRpcError::TransportError(RpcTransportError::SendError(
+            err,
+        )))
+    };
+    let recv_error = |err| {
+        Err(JsonRpcError::TransportError(RpcTransportError::RecvError(
+            err,
+        )))
+    };
+
+    let request_payload = match near_jsonrpc_client::methods::to_json(method)
+        .and_then(|payload| serde_json::to_vec(&payload).map_err(Into::into))
+    {
+        Ok(request_payload) => request_payload,
+        Err(err) => {
+            return (
+                send_error(JsonRpcTransportSendError::PayloadSerializeError(err)),
+                None,
+            )
+        }
+    };
+    let response = match http_client()
+        .post(json_rpc_client.server_addr())
+        .headers(json_rpc_client.headers().clone())
+        .body(request_payload)
+        .send()
+        .await
+    {
+        Ok(response) => response,
+        Err(err) => {
+            return (
+                send_error(JsonRpcTransportSendError::PayloadSendError(err)),
+                None,
+            )
+        }
+    };
+    let status_error = match response.status() {
+        reqwest::StatusCode::OK => None,
+        reqwest::StatusCode::UNAUTHORIZED => Some(JsonRpcServerError::ResponseStatusError(
+            JsonRpcServerResponseStatusError::Unauthorized,
+        )),
+        reqwest::StatusCode::TOO_MANY_REQUESTS => {
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| parse_retry_after(value, chrono::Utc::now()));
+            return (
+                Err(JsonRpcError::ServerError(
+                    JsonRpcServerError::ResponseStatusError(
+                        JsonRpcServerResponseStatusError::TooManyRequests,
+                    ),
+                )),
+                retry_after,
+            );
+        }
+        reqwest::StatusCode::BAD_REQUEST => Some(JsonRpcServerError::ResponseStatusError(
+            JsonRpcServerResponseStatusError::BadRequest,
+        )),
+        reqwest::StatusCode::INTERNAL_SERVER_ERROR => Some(JsonRpcServerError::InternalError {
+            info: Some(String::from("Internal server error")),
+        }),
+        reqwest::StatusCode::SERVICE_UNAVAILABLE => Some(JsonRpcServerError::ResponseStatusError(
+            JsonRpcServerResponseStatusError::ServiceUnavailable,
+        )),
+        reqwest::StatusCode::REQUEST_TIMEOUT => Some(JsonRpcServerError::ResponseStatusError(
+            JsonRpcServerResponseStatusError::TimeoutError,
+        )),
+        status => Some(JsonRpcServerError::ResponseStatusError(
+            JsonRpcServerResponseStatusError::Unexpected { status },
+        )),
+    };
+    if let Some(status_error) = status_error {
+        return (Err(JsonRpcError::ServerError(status_error)), None);
+    }
+
+    let response_payload = match response.bytes().await {
+        Ok(response_payload) => response_payload,
+        Err(err) => {
+            return (
+                recv_error(JsonRpcTransportRecvError::PayloadRecvError(err)),
+                None,
+            )
+        }
+    };
+    let response_message = match near_jsonrpc_primitives::message::decoded_to_parsed(
+        serde_json::from_slice::<serde_json::Value>(&response_payload)
+            .and_then(serde_json::from_value),
+    ) {
+        Ok(response_message) => response_message,
+        Err(err) => {
+            return (
+                recv_error(JsonRpcTransportRecvError::PayloadParseError(err)),
+                None,
+            )
+        }
+    };
+    let near_jsonrpc_primitives::message::Message::Response(response) = response_message else {
+        return (
+            recv_error(JsonRpcTransportRecvError::UnexpectedServerResponse(
+                response_message,
+            )),
+            None,
+        );
+    };
+    let result = match response.result {
+        Ok(result) => match M::parse_handler_response(result) {
+            Ok(result) => result
+                .map_err(|err| JsonRpcError::ServerError(JsonRpcServerError::HandlerError(err))),
+            Err(err) => recv_error(JsonRpcTransportRecvError::ResponseParseError(
+                JsonRpcTransportHandlerResponseError::ResultParseError(err),
+            )),
+        },
+        Err(err) => Err(err.into()),
+    };
+    (result, None)
+}
+
+/// The same as [`near_jsonrpc_client::JsonRpcClient::call`], but the request waits for the rate
+/// limit of the RPC server and is retried on HTTP 429 (after the `Retry-After` of the response).
+pub async fn call<M>(
+    json_rpc_client: &near_jsonrpc_client::JsonRpcClient,
+    method: M,
+) -> near_jsonrpc_client::MethodCallResult<M::Response, M::Error>
+where
+    M: near_jsonrpc_client::methods::RpcMethod,
+{
+    let server_addr = json_rpc_client.server_addr();
+    let mut attempt = 0;
+    loop {
+        if let Some(delay) = with_token_bucket(server_addr, |token_bucket| {
+            token_bucket.reserve(Instant::now())
+        }) {
+            tokio::time::sleep(delay).await;
+        }
+        let (result, retry_after) = send_request(json_rpc_client, &method).await;
+        if !matches!(
+            result,
+            Err(JsonRpcError::ServerError(
+                JsonRpcServerError::ResponseStatusError(
+                    JsonRpcServerResponseStatusError::TooManyRequests
+                )
+            ))
+        ) || attempt == MAX_RETRIES_ON_TOO_MANY_REQUESTS
+        {
+            return result;
+        }
+        let delay = too_many_requests_delay(attempt, retry_after);
+        tracing::warn!(
+            parent: &tracing::Span::none(),
+            "The RPC server <{server_addr}> is rate limiting the requests, retrying in {}s",
+            delay.as_secs_f64()
+        );
+        if with_token_bucket(server_addr, |token_bucket| {
+            token_bucket.pause(Instant::now(), delay)
+        })
+        .is_none()
+        {
+            tokio::time::sleep(delay).await;
+        }
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_bucket_queues_and_pauses_requests() {
+        let now = Instant::now();
+        let mut token_bucket = TokenBucket::new(2, now);
+        assert_eq!(token_bucket.reserve(now), Duration::ZERO);
+        assert_eq!(token_bucket.reserve(now), Duration::ZERO);
+        assert_eq!(token_bucket.reserve(now), Duration::from_millis(500));
+        assert_eq!(token_bucket.reserve(now), Duration::from_secs(1));
+
+        let now = now + Duration::from_secs(10);
+        assert_eq!(token_bucket.reserve(now), Duration::ZERO);
+        token_bucket.pause(now, Duration::from_secs(5));
+        assert_eq!(token_bucket.reserve(now), Duration::from_secs(5));
+        assert_eq!(
+            token_bucket.reserve(now + Duration::from_secs(1)),
+            Duration::from_millis(4_500)
+        );
+    }
+
+    #[test]
+    fn rate_limit_of_public_endpoints_and_backoff() {
+        let rps = |rpc_url: &str, rps| rpc_rate_limit_rps(&rpc_url.parse().unwrap(), rps);
+        assert_eq!(
+            rps("https://archival-rpc.mainnet.near.org/", None),
+            Some(10)
+        );
+        assert_eq!(rps("https://rpc.mainnet.near.org/", Some(3)), Some(3));
+        assert_eq!(rps("https://rpc.mainnet.near.org/", Some(0)), None);
+        assert_eq!(rps("https://notnear.org/", None), None);
+        assert_eq!(rps("http://localhost:3030/", None), None);
+        assert_eq!(rps("http://localhost:3030/", Some(50)), Some(50));
+
+        assert_eq!(too_many_requests_delay(0, None), Duration::from_secs(1));
+        assert_eq!(too_many_requests_delay(2, None), Duration::from_secs(4));
+        assert_eq!(too_many_requests_delay(10, None), MAX_BACKOFF);
+        assert_eq!(
+            too_many_requests_delay(0, Some(Duration::from_secs(7))),
+            Duration::from_secs(7)
+        );
+        assert_eq!(
+            too_many_requests_delay(0, Some(Duration::from_secs(3600))),
+            MAX_BACKOFF
+        );
+    }
+
+    #[test]
+    fn retry_after_in_seconds_or_as_a_date() {
+        let now = chrono::DateTime::parse_from_rfc3339("2015-10-21T07:28:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        assert_eq!(
+            parse_retry_after("120", now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:30 GMT", now),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:27:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+        assert_eq!(parse_retry_after("-5", now), None);
+    }
+}
@@ -17,6 +17,12 @@ impl DisplayContext {
         )
         .map_err(color_eyre::Report::msg)?;
 
+        crate::audit_log::record(
+            &previous_context.global_context.config,
+            &previous_context.network_config,
+            (&previous_context.signed_transaction_or_signed_delegate_action).into(),
+            "printed to the terminal",
+        );
         match previous_context.signed_transaction_or_signed_delegate_action {
             super::SignedTransactionOrSignedDelegateAction::SignedTransaction(
                 signed_transaction,
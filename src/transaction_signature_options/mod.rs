@@ -12,6 +12,8 @@ pub mod sign_with_ledger;
 pub mod sign_with_legacy_keychain;
 pub mod sign_with_private_key;
 pub mod sign_with_seed_phrase;
+pub mod sign_with_wallet;
+pub mod signing_policy;
 
 pub const META_TRANSACTION_VALID_FOR_DEFAULT: u64 = 1000;
 
@@ -51,6 +53,11 @@ pub enum SignWith {
     ))]
     /// Sign the transaction using the seed phrase
     SignWithSeedPhrase(self::sign_with_seed_phrase::SignSeedPhrase),
+    #[strum_discriminants(strum(
+        message = "sign-with-wallet                 - Approve the transaction in the web wallet (the wallet signs and sends it)"
+    ))]
+    /// Approve the transaction in the web wallet (the wallet signs and sends it)
+    SignWithWallet(self::sign_with_wallet::SignWallet),
     #[strum_discriminants(strum(
         message = "sign-later                       - Prepare an unsigned transaction to sign it later"
     ))]
@@ -83,6 +90,8 @@ pub enum Submit {
 #[derive(Debug, Deserialize)]
 pub struct AccountKeyPair {
     pub public_key: near_crypto::PublicKey,
+    /// `secret_key` in the key files of neard (e.g. validator_key.json)
+    #[serde(alias = "secret_key")]
     pub private_key: near_crypto::SecretKey,
 }
 
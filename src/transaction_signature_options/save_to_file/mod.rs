@@ -28,6 +28,9 @@ impl SaveToFileContext {
         )
         .map_err(color_eyre::Report::msg)?;
 
+        let signed_transaction_or_signed_delegate_action = previous_context
+            .signed_transaction_or_signed_delegate_action
+            .clone();
         match previous_context.signed_transaction_or_signed_delegate_action {
             super::SignedTransactionOrSignedDelegateAction::SignedTransaction(
                 signed_transaction,
@@ -78,6 +81,12 @@ impl SaveToFileContext {
                 eprintln!("{storage_message}");
             }
         }
+        crate::audit_log::record(
+            &previous_context.global_context.config,
+            &previous_context.network_config,
+            (&signed_transaction_or_signed_delegate_action).into(),
+            format!("saved to file {}", file_path.display()),
+        );
         Ok(Self)
     }
 }
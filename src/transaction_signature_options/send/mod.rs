@@ -6,7 +6,21 @@ use crate::common::JsonRpcClientExt;
 #[derive(Debug, Clone, interactive_clap_derive::InteractiveClap)]
 #[interactive_clap(input_context = super::SubmitContext)]
 #[interactive_clap(output_context = SendContext)]
-pub struct Send;
+pub struct Send {
+    #[interactive_clap(long)]
+    /// Wait until the transaction reaches finality (can be enabled by default with `near config set-default-wait-for-finality true`)
+    watch_tx: bool,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Warn if the block hash of the transaction is older than this number of blocks (default: 80)
+    max_block_lag: Option<u64>,
+    #[interactive_clap(long)]
+    /// Refuse to send the transaction if its block hash is older than --max-block-lag
+    strict: bool,
+}
+
+/// The default for `--max-block-lag`
+pub const DEFAULT_MAX_BLOCK_LAG: u64 = 80;
 
 #[derive(Debug, Clone)]
 pub struct SendContext;
@@ -15,8 +29,28 @@ impl SendContext {
     #[tracing::instrument(name = "Sending transaction ...", skip_all)]
     pub fn from_previous_context(
         previous_context: super::SubmitContext,
-        _scope: &<Send as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+        scope: &<Send as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        if let super::SignedTransactionOrSignedDelegateAction::SignedTransaction(
+            signed_transaction,
+        ) = &previous_context.signed_transaction_or_signed_delegate_action
+        {
+            check_block_hash_age(
+                &previous_context.network_config,
+                *signed_transaction.transaction.block_hash(),
+                scope.max_block_lag.unwrap_or(DEFAULT_MAX_BLOCK_LAG),
+                scope.strict,
+            )?;
+        }
+
+        if previous_context.global_context.replay_on_sandbox {
+            replay_on_sandbox(
+                &previous_context.network_config,
+                &previous_context.signed_transaction_or_signed_delegate_action,
+            )?;
+            return Ok(Self);
+        }
+
         let storage_message = (previous_context.on_before_sending_transaction_callback)(
             &previous_context.signed_transaction_or_signed_delegate_action,
             &previous_context.network_config,
@@ -27,34 +61,77 @@ impl SendContext {
             super::SignedTransactionOrSignedDelegateAction::SignedTransaction(
                 signed_transaction,
             ) => {
-                let transaction_info = sending_signed_transaction(
-                    &previous_context.network_config,
-                    &signed_transaction,
-                )?;
+                crate::notification::with_transaction_completion_notification(
+                    &previous_context.global_context,
+                    signed_transaction.get_hash(),
+                    || {
+                        let transaction_info = sending_signed_transaction(
+                            &previous_context.network_config,
+                            &signed_transaction,
+                        );
+                        crate::audit_log::record(
+                            &previous_context.global_context.config,
+                            &previous_context.network_config,
+                            (&signed_transaction).into(),
+                            crate::audit_log::sent_transaction_outcome(&transaction_info),
+                        );
+                        let transaction_info = transaction_info?;
 
-                crate::common::print_transaction_status(
-                    &transaction_info,
-                    &previous_context.network_config,
-                )?;
+                        crate::common::print_transaction_status(
+                            &transaction_info,
+                            &previous_context.network_config,
+                        )?;
 
-                (previous_context.on_after_sending_transaction_callback)(
-                    &transaction_info,
-                    &previous_context.network_config,
-                )
-                .map_err(color_eyre::Report::msg)?;
+                        (previous_context.on_after_sending_transaction_callback)(
+                            &transaction_info,
+                            &previous_context.network_config,
+                        )
+                        .map_err(color_eyre::Report::msg)?;
+
+                        if scope.watch_tx
+                            || previous_context.global_context.config.wait_for_finality
+                        {
+                            wait_for_transaction_finality(
+                                &previous_context.network_config,
+                                transaction_info.transaction_outcome.id,
+                                signed_transaction.transaction.signer_id(),
+                                WATCH_TX_TIMEOUT,
+                            )?;
+                        }
+                        Ok(())
+                    },
+                )?;
             }
             super::SignedTransactionOrSignedDelegateAction::SignedDelegateAction(
                 signed_delegate_action,
             ) => {
-                match sending_delegate_action(
-                    signed_delegate_action,
+                let relayer_response = sending_delegate_action(
+                    signed_delegate_action.clone(),
                     previous_context.network_config
                         .meta_transaction_relayer_url
+                        .clone()
                         .expect("Internal error: Meta-transaction relayer URL must be Some() at this point"),
-                ){
+                );
+                crate::audit_log::record(
+                    &previous_context.global_context.config,
+                    &previous_context.network_config,
+                    (&super::SignedTransactionOrSignedDelegateAction::from(signed_delegate_action))
+                        .into(),
+                    match &relayer_response {
+                        Ok(relayer_response) => format!(
+                            "sent to the relayer (HTTP status {})",
+                            relayer_response.status()
+                        ),
+                        // Only a failed connection means that the relayer did not get it
+                        Err(err) if err.is_connect() => format!("not sent: {err}"),
+                        Err(err) => format!("unknown ({err})"),
+                    },
+                );
+                match relayer_response {
                     Ok(relayer_response) => {
                         if relayer_response.status().is_success() {
-                            let response_text = relayer_response.text().map_err(color_eyre::Report::msg)?;
+                            let response_text =
+                                relayer_response.text().map_err(color_eyre::Report::msg)?;
                             eprintln!("\nRelayer Response text: {}", response_text);
                         } else {
                             eprintln!(
@@ -72,6 +149,114 @@ impl SendContext {
     }
 }
 
+/// Sends the transaction to the sandbox node, so that its outcome, gas and logs can be checked
+/// without affecting a real network. It is a replay, not a dry run: the sandbox executes and
+/// commits the transaction with the state it has, nothing is patched into it from another
+/// network. The callbacks are not invoked since they may store keys or other local data as if
+/// the transaction had been sent to the selected network.
+fn replay_on_sandbox(
+    network_config: &crate::config::NetworkConfig,
+    signed_transaction_or_signed_delegate_action: &super::SignedTransactionOrSignedDelegateAction,
+) -> crate::CliResult {
+    let super::SignedTransactionOrSignedDelegateAction::SignedTransaction(signed_transaction) =
+        signed_transaction_or_signed_delegate_action
+    else {
+        return Err(color_eyre::eyre::eyre!(
+            "Meta-transactions are sent through a relayer and cannot be replayed with --replay-on-sandbox"
+        ));
+    };
+    ensure_sandbox_node(network_config)?;
+
+    let transaction_info = sending_signed_transaction(network_config, signed_transaction)?;
+    eprintln!(
+        "\n--- Replayed on the sandbox node {} ---",
+        network_config.rpc_url
+    );
+    crate::common::print_transaction_status(&transaction_info, network_config)
+}
+
+/// Only sandbox nodes serve `sandbox_patch_state`, so a patch without records is a probe that
+/// does not change the state.
+#[tracing::instrument(
+    name = "Checking that the RPC server runs in sandbox mode ...",
+    skip_all
+)]
+fn ensure_sandbox_node(network_config: &crate::config::NetworkConfig) -> crate::CliResult {
+    network_config
+        .json_rpc_client()
+        .blocking_call(
+            near_jsonrpc_client::methods::sandbox_patch_state::RpcSandboxPatchStateRequest {
+                records: vec![],
+            },
+        )
+        .map(|_| ())
+        .map_err(|err| {
+            color_eyre::eyre::eyre!(
+                "--replay-on-sandbox requires an RPC server in sandbox mode, but <{}> ({}) does not support it: {err}\nAdd a connection to a sandbox node with `near config add-connection` and select it as the network.",
+                network_config.network_name,
+                network_config.rpc_url
+            )
+        })
+}
+
+/// Compares the block of the transaction block hash with the final block. A failure to fetch the
+/// blocks is not an error: the node validates the block hash anyway.
+#[tracing::instrument(name = "Checking the age of the transaction block hash ...", skip_all)]
+pub fn check_block_hash_age(
+    network_config: &crate::config::NetworkConfig,
+    block_hash: near_primitives::hash::CryptoHash,
+    max_block_lag: u64,
+    strict: bool,
+) -> crate::CliResult {
+    let json_rpc_client = network_config.json_rpc_client();
+    let get_block_height = |block_reference| {
+        json_rpc_client
+            .blocking_call(near_jsonrpc_client::methods::block::RpcBlockRequest { block_reference })
+            .map(|block_view| block_view.header.height)
+    };
+    let final_block_height = match get_block_height(near_primitives::types::Finality::Final.into())
+    {
+        Ok(final_block_height) => final_block_height,
+        Err(err) => {
+            tracing::warn!("Failed to check the age of the transaction block hash: {err}");
+            return Ok(());
+        }
+    };
+    let message = match get_block_height(near_primitives::types::BlockReference::BlockId(
+        near_primitives::types::BlockId::Hash(block_hash),
+    )) {
+        Ok(block_height) => {
+            let block_lag = final_block_height.saturating_sub(block_height);
+            eprintln!(
+                "Transaction block hash: {block_hash} (block #{block_height}, the current final block is #{final_block_height}: {block_lag} blocks later)"
+            );
+            if block_lag <= max_block_lag {
+                return Ok(());
+            }
+            format!(
+                "The block hash of the transaction is {block_lag} blocks old, which is more than {max_block_lag} blocks (--max-block-lag); the transaction may expire, sign it again with a recent block hash."
+            )
+        }
+        Err(near_jsonrpc_client::errors::JsonRpcError::ServerError(
+            near_jsonrpc_client::errors::JsonRpcServerError::HandlerError(
+                near_jsonrpc_primitives::types::blocks::RpcBlockError::UnknownBlock { .. },
+            ),
+        )) => format!(
+            "The block hash of the transaction {block_hash} is unknown on network <{}> (the current final block is #{final_block_height}); sign the transaction again with a recent block hash.",
+            network_config.network_name
+        ),
+        Err(err) => {
+            tracing::warn!("Failed to check the age of the transaction block hash: {err}");
+            return Ok(());
+        }
+    };
+    if strict {
+        color_eyre::eyre::bail!("{message}");
+    }
+    eprintln!("\nWarning: {message}\n");
+    Ok(())
+}
+
 #[tracing::instrument(name = "Broadcasting transaction via RPC", skip_all)]
 pub fn sending_signed_transaction(
     network_config: &crate::config::NetworkConfig,
@@ -79,6 +264,7 @@ pub fn sending_signed_transaction(
 ) -> color_eyre::Result<near_primitives::views::FinalExecutionOutcomeView> {
     tracing::Span::current().pb_set_message(network_config.rpc_url.as_str());
     tracing::info!(target: "near_teach_me", "{}", network_config.rpc_url.as_str());
+    crate::deadline::record_transaction_hash(signed_transaction.get_hash());
 
     let retries_number = 5;
     let mut retries = (1..=retries_number).rev();
@@ -115,7 +301,24 @@ pub fn sending_signed_transaction(
                         return Err(color_eyre::eyre::eyre!(err.to_string()));
                     }
                 }
-                Err(report) => return Err(color_eyre::Report::msg(report)),
+                Err(report) => {
+                    return Err(
+                        if matches!(
+                            err,
+                            near_jsonrpc_client::errors::JsonRpcError::ServerError(
+                                near_jsonrpc_client::errors::JsonRpcServerError::HandlerError(
+                                    near_jsonrpc_client::methods::broadcast_tx_commit::RpcTransactionError::InvalidTransaction { .. }
+                                )
+                            )
+                        ) {
+                            color_eyre::Report::new(crate::audit_log::RejectedTransaction(
+                                report.to_string(),
+                            ))
+                        } else {
+                            color_eyre::Report::msg(report)
+                        },
+                    )
+                }
             },
         };
     };
@@ -123,6 +326,88 @@ pub fn sending_signed_transaction(
     Ok(transaction_info)
 }
 
+/// How long `--watch-tx` waits for the finality of the sent transaction
+const WATCH_TX_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(300);
+
+#[tracing::instrument(name = "Waiting for the transaction finality", skip_all)]
+pub fn wait_for_transaction_finality(
+    network_config: &crate::config::NetworkConfig,
+    transaction_hash: near_primitives::hash::CryptoHash,
+    sender_account_id: &near_primitives::types::AccountId,
+    timeout: std::time::Duration,
+) -> crate::CliResult {
+    let poll_interval = std::time::Duration::from_secs(2);
+    let started_at = std::time::Instant::now();
+    let mut last_status_name = None;
+
+    loop {
+        let status_name = match network_config.json_rpc_client().blocking_call(
+            near_jsonrpc_client::methods::tx::RpcTransactionStatusRequest {
+                transaction_info:
+                    near_jsonrpc_client::methods::tx::TransactionInfo::TransactionId {
+                        tx_hash: transaction_hash,
+                        sender_account_id: sender_account_id.clone(),
+                    },
+                wait_until: near_primitives::views::TxExecutionStatus::None,
+            },
+        ) {
+            Ok(response) => tx_execution_status_name(&response.final_execution_status),
+            Err(near_jsonrpc_client::errors::JsonRpcError::ServerError(
+                near_jsonrpc_client::errors::JsonRpcServerError::HandlerError(
+                    near_jsonrpc_client::methods::tx::RpcTransactionError::UnknownTransaction {
+                        ..
+                    },
+                ),
+            )) => "Unknown",
+            Err(err) => {
+                return Err(color_eyre::eyre::eyre!(
+                    "Failed to fetch the status of the transaction {transaction_hash}: {err}"
+                ))
+            }
+        };
+
+        if last_status_name != Some(status_name) {
+            tracing_indicatif::suspend_tracing_indicatif(|| {
+                eprintln!(
+                    "[{:>5.1}s] Transaction status: {status_name}",
+                    started_at.elapsed().as_secs_f32()
+                )
+            });
+            last_status_name = Some(status_name);
+        }
+        if status_name == "Final" {
+            eprintln!(
+                "Transaction {transaction_hash} has reached finality in {:.1}s",
+                started_at.elapsed().as_secs_f32()
+            );
+            return Ok(());
+        }
+        if started_at.elapsed() > timeout {
+            return Err(color_eyre::eyre::eyre!(
+                "The transaction {transaction_hash} has not reached finality in {} seconds (the last known status is {status_name})",
+                timeout.as_secs()
+            ));
+        }
+
+        tracing::Span::current().pb_set_message(&format!(
+            "{transaction_hash} ({status_name}, {}s elapsed)",
+            started_at.elapsed().as_secs()
+        ));
+        std::thread::sleep(poll_interval);
+    }
+}
+
+fn tx_execution_status_name(status: &near_primitives::views::TxExecutionStatus) -> &'static str {
+    match status {
+        near_primitives::views::TxExecutionStatus::None => "Pending",
+        near_primitives::views::TxExecutionStatus::Included => "Included",
+        near_primitives::views::TxExecutionStatus::ExecutedOptimistic => "Executed (optimistic)",
+        near_primitives::views::TxExecutionStatus::IncludedFinal => "Included (final)",
+        near_primitives::views::TxExecutionStatus::Executed => "Executed",
+        near_primitives::views::TxExecutionStatus::Final => "Final",
+    }
+}
+
 #[tracing::instrument(
     name = "Waiting 5 seconds before broadcasting transaction via RPC",
     skip_all
@@ -13,7 +13,7 @@ pub struct SignLater {
     signer_public_key: crate::types::public_key::PublicKey,
     #[interactive_clap(long)]
     /// Enter a nonce for the access key:
-    nonce: u64,
+    nonce: crate::types::nonce::Nonce,
     #[interactive_clap(long)]
     /// Enter recent block hash:
     block_hash: crate::types::crypto_hash::CryptoHash,
@@ -34,7 +34,7 @@ impl SignLaterContext {
         let unsigned_transaction = near_primitives::transaction::Transaction::V0(TransactionV0 {
             signer_id: previous_context.prepopulated_transaction.signer_id,
             public_key: scope.signer_public_key.clone().into(),
-            nonce: scope.nonce,
+            nonce: scope.nonce.into(),
             receiver_id: previous_context.prepopulated_transaction.receiver_id,
             block_hash: scope.block_hash.into(),
             actions: previous_context.prepopulated_transaction.actions,
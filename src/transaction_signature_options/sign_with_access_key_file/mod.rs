@@ -13,13 +13,13 @@ pub struct SignAccessKeyFile {
     file_path: crate::types::path_buf::PathBuf,
     #[interactive_clap(long)]
     #[interactive_clap(skip_default_input_arg)]
-    pub nonce: Option<u64>,
+    pub nonce: Option<crate::types::nonce::Nonce>,
     #[interactive_clap(long)]
     #[interactive_clap(skip_default_input_arg)]
     pub block_hash: Option<crate::types::crypto_hash::CryptoHash>,
     #[interactive_clap(long)]
     #[interactive_clap(skip_default_input_arg)]
-    pub block_height: Option<near_primitives::types::BlockHeight>,
+    pub block_height: Option<crate::types::block_height::BlockHeight>,
     #[interactive_clap(long)]
     #[interactive_clap(skip_interactive_input)]
     meta_transaction_valid_for: Option<u64>,
@@ -58,14 +58,16 @@ impl SignAccessKeyFileContext {
             (
                 scope
                     .nonce
-                    .wrap_err("Nonce is required to sign a transaction in offline mode")?,
+                    .wrap_err("Nonce is required to sign a transaction in offline mode")?
+                    .0,
                 scope
                     .block_hash
                     .wrap_err("Block Hash is required to sign a transaction in offline mode")?
                     .0,
                 scope
                     .block_height
-                    .wrap_err("Block Height is required to sign a transaction in offline mode")?,
+                    .wrap_err("Block Height is required to sign a transaction in offline mode")?
+                    .0,
             )
         } else {
             let rpc_query_response = network_config
@@ -79,12 +81,17 @@ impl SignAccessKeyFileContext {
                 format!("Cannot sign a transaction due to an error while fetching the most recent nonce value on network <{}>", network_config.network_name)
             )?;
 
+            let access_key_view = rpc_query_response
+                .access_key_view()
+                .wrap_err("Error current_nonce")?;
+            crate::tx_builder::validate_actions_for_key(
+                &near_primitives::account::AccessKeyPermission::from(access_key_view.permission),
+                &previous_context.prepopulated_transaction.receiver_id,
+                &previous_context.prepopulated_transaction.actions,
+            )?;
+
             (
-                rpc_query_response
-                    .access_key_view()
-                    .wrap_err("Error current_nonce")?
-                    .nonce
-                    + 1,
+                crate::tx_builder::next_nonce(access_key_view.nonce)?,
                 rpc_query_response.block_hash,
                 rpc_query_response.block_height,
             )
@@ -167,10 +174,11 @@ impl From<SignAccessKeyFileContext> for super::SubmitContext {
 impl SignAccessKeyFile {
     fn input_nonce(
         context: &crate::commands::TransactionContext,
-    ) -> color_eyre::eyre::Result<Option<u64>> {
+    ) -> color_eyre::eyre::Result<Option<crate::types::nonce::Nonce>> {
         if context.global_context.offline {
             return Ok(Some(
-                CustomType::<u64>::new("Enter a nonce for the access key:").prompt()?,
+                CustomType::<crate::types::nonce::Nonce>::new("Enter a nonce for the access key:")
+                    .prompt()?,
             ));
         }
         Ok(None)
@@ -192,10 +200,10 @@ impl SignAccessKeyFile {
 
     fn input_block_height(
         context: &crate::commands::TransactionContext,
-    ) -> color_eyre::eyre::Result<Option<near_primitives::types::BlockHeight>> {
+    ) -> color_eyre::eyre::Result<Option<crate::types::block_height::BlockHeight>> {
         if context.global_context.offline {
             return Ok(Some(
-                CustomType::<near_primitives::types::BlockHeight>::new(
+                CustomType::<crate::types::block_height::BlockHeight>::new(
                     "Enter recent block height:",
                 )
                 .prompt()?,
@@ -27,13 +27,13 @@ pub struct SignLedger {
     signer_public_key: crate::types::public_key::PublicKey,
     #[interactive_clap(long)]
     #[interactive_clap(skip_default_input_arg)]
-    nonce: Option<u64>,
+    nonce: Option<crate::types::nonce::Nonce>,
     #[interactive_clap(long)]
     #[interactive_clap(skip_default_input_arg)]
     pub block_hash: Option<crate::types::crypto_hash::CryptoHash>,
     #[interactive_clap(long)]
     #[interactive_clap(skip_default_input_arg)]
-    block_height: Option<near_primitives::types::BlockHeight>,
+    block_height: Option<crate::types::block_height::BlockHeight>,
     #[interactive_clap(long)]
     #[interactive_clap(skip_interactive_input)]
     meta_transaction_valid_for: Option<u64>,
@@ -69,14 +69,16 @@ impl SignLedgerContext {
             (
                 scope
                     .nonce
-                    .wrap_err("Nonce is required to sign a transaction in offline mode")?,
+                    .wrap_err("Nonce is required to sign a transaction in offline mode")?
+                    .0,
                 scope
                     .block_hash
                     .wrap_err("Block Hash is required to sign a transaction in offline mode")?
                     .0,
                 scope
                     .block_height
-                    .wrap_err("Block Height is required to sign a transaction in offline mode")?,
+                    .wrap_err("Block Height is required to sign a transaction in offline mode")?
+                    .0,
             )
         } else {
             let rpc_query_response = network_config
@@ -90,12 +92,17 @@ impl SignLedgerContext {
                     format!("Cannot sign a transaction due to an error while fetching the most recent nonce value on network <{}>", network_config.network_name)
                 )?;
 
+            let access_key_view = rpc_query_response
+                .access_key_view()
+                .wrap_err("Error current_nonce")?;
+            crate::tx_builder::validate_actions_for_key(
+                &near_primitives::account::AccessKeyPermission::from(access_key_view.permission),
+                &previous_context.prepopulated_transaction.receiver_id,
+                &previous_context.prepopulated_transaction.actions,
+            )?;
+
             (
-                rpc_query_response
-                    .access_key_view()
-                    .wrap_err("Error current_nonce")?
-                    .nonce
-                    + 1,
+                crate::tx_builder::next_nonce(access_key_view.nonce)?,
                 rpc_query_response.block_hash,
                 rpc_query_response.block_height,
             )
@@ -339,10 +346,11 @@ impl SignLedger {
 
     fn input_nonce(
         context: &crate::commands::TransactionContext,
-    ) -> color_eyre::eyre::Result<Option<u64>> {
+    ) -> color_eyre::eyre::Result<Option<crate::types::nonce::Nonce>> {
         if context.global_context.offline {
             return Ok(Some(
-                CustomType::<u64>::new("Enter a nonce for the access key:").prompt()?,
+                CustomType::<crate::types::nonce::Nonce>::new("Enter a nonce for the access key:")
+                    .prompt()?,
             ));
         }
         Ok(None)
@@ -3,7 +3,7 @@ extern crate dirs;
 use std::str::FromStr;
 
 use color_eyre::eyre::{ContextCompat, WrapErr};
-use inquire::{CustomType, Select};
+use inquire::CustomType;
 use near_primitives::transaction::TransactionV0;
 
 use crate::common::JsonRpcClientExt;
@@ -18,13 +18,13 @@ pub struct SignLegacyKeychain {
     signer_public_key: Option<crate::types::public_key::PublicKey>,
     #[interactive_clap(long)]
     #[interactive_clap(skip_default_input_arg)]
-    nonce: Option<u64>,
+    nonce: Option<crate::types::nonce::Nonce>,
     #[interactive_clap(long)]
     #[interactive_clap(skip_default_input_arg)]
     pub block_hash: Option<crate::types::crypto_hash::CryptoHash>,
     #[interactive_clap(long)]
     #[interactive_clap(skip_default_input_arg)]
-    pub block_height: Option<near_primitives::types::BlockHeight>,
+    pub block_height: Option<crate::types::block_height::BlockHeight>,
     #[interactive_clap(long)]
     #[interactive_clap(skip_interactive_input)]
     meta_transaction_valid_for: Option<u64>,
@@ -58,22 +58,20 @@ impl SignLegacyKeychainContext {
         let keychain_folder = previous_context
             .global_context
             .config
-            .credentials_home_dir
+            .credentials_dir(&network_config)
             .join(&network_config.network_name);
         let signer_keychain_folder =
             keychain_folder.join(previous_context.prepopulated_transaction.signer_id.as_str());
         let signer_access_key_file_path: std::path::PathBuf = {
             if previous_context.global_context.offline {
-                signer_keychain_folder.join(format!(
-                    "{}.json",
-                    scope
+                signer_keychain_folder.join(crate::common::key_file_name(
+                    &scope
                         .signer_public_key
                         .as_ref()
                         .wrap_err(
-                            "Signer public key is required to sign a transaction in offline mode"
+                            "Signer public key is required to sign a transaction in offline mode",
                         )?
-                        .to_string()
-                        .replace(':', "_")
+                        .to_string(),
                 ))
             } else if signer_keychain_folder.exists() {
                 let full_access_key_filenames = network_config
@@ -100,11 +98,7 @@ impl SignLegacyKeychainContext {
                         },
                     )
                     .map(|access_key_info| {
-                        format!(
-                            "{}.json",
-                            access_key_info.public_key.to_string().replace(":", "_")
-                        )
-                        .into()
+                        crate::common::key_file_name(&access_key_info.public_key.to_string()).into()
                     })
                     .collect::<std::collections::HashSet<std::ffi::OsString>>();
 
@@ -145,14 +139,16 @@ impl SignLegacyKeychainContext {
             (
                 scope
                     .nonce
-                    .wrap_err("Nonce is required to sign a transaction in offline mode")?,
+                    .wrap_err("Nonce is required to sign a transaction in offline mode")?
+                    .0,
                 scope
                     .block_hash
                     .wrap_err("Block Hash is required to sign a transaction in offline mode")?
                     .0,
                 scope
                     .block_height
-                    .wrap_err("Block Height is required to sign a transaction in offline mode")?,
+                    .wrap_err("Block Height is required to sign a transaction in offline mode")?
+                    .0,
             )
         } else {
             let rpc_query_response = network_config
@@ -165,12 +161,17 @@ impl SignLegacyKeychainContext {
                 .wrap_err(
                     "Cannot sign a transaction due to an error while fetching the most recent nonce value",
                 )?;
+            let access_key_view = rpc_query_response
+                .access_key_view()
+                .wrap_err("Error current_nonce")?;
+            crate::tx_builder::validate_actions_for_key(
+                &near_primitives::account::AccessKeyPermission::from(access_key_view.permission),
+                &previous_context.prepopulated_transaction.receiver_id,
+                &previous_context.prepopulated_transaction.actions,
+            )?;
+
             (
-                rpc_query_response
-                    .access_key_view()
-                    .wrap_err("Error current_nonce")?
-                    .nonce
-                    + 1,
+                crate::tx_builder::next_nonce(access_key_view.nonce)?,
                 rpc_query_response.block_hash,
                 rpc_query_response.block_height,
             )
@@ -257,8 +258,10 @@ impl SignLegacyKeychain {
         if context.global_context.offline {
             let network_config = context.network_config.clone();
 
-            let mut path =
-                std::path::PathBuf::from(&context.global_context.config.credentials_home_dir);
+            let mut path = context
+                .global_context
+                .config
+                .credentials_dir(&network_config);
 
             let dir_name = network_config.network_name;
             path.push(&dir_name);
@@ -274,7 +277,10 @@ impl SignLegacyKeychain {
                 .map(|file_name_str| file_name_str.replace(".json", "").replace('_', ":"))
                 .collect::<Vec<_>>();
 
-            let selected_input = Select::new("Choose public_key:", key_list).prompt()?;
+            let Some(selected_input) = crate::common::select("Choose public_key:", key_list)?
+            else {
+                return Ok(None);
+            };
 
             return Ok(Some(crate::types::public_key::PublicKey::from_str(
                 &selected_input,
@@ -285,10 +291,11 @@ impl SignLegacyKeychain {
 
     fn input_nonce(
         context: &crate::commands::TransactionContext,
-    ) -> color_eyre::eyre::Result<Option<u64>> {
+    ) -> color_eyre::eyre::Result<Option<crate::types::nonce::Nonce>> {
         if context.global_context.offline {
             return Ok(Some(
-                CustomType::<u64>::new("Enter a nonce for the access key:").prompt()?,
+                CustomType::<crate::types::nonce::Nonce>::new("Enter a nonce for the access key:")
+                    .prompt()?,
             ));
         }
         Ok(None)
@@ -310,10 +317,10 @@ impl SignLegacyKeychain {
 
     fn input_block_height(
         context: &crate::commands::TransactionContext,
-    ) -> color_eyre::eyre::Result<Option<near_primitives::types::BlockHeight>> {
+    ) -> color_eyre::eyre::Result<Option<crate::types::block_height::BlockHeight>> {
         if context.global_context.offline {
             return Ok(Some(
-                CustomType::<near_primitives::types::BlockHeight>::new(
+                CustomType::<crate::types::block_height::BlockHeight>::new(
                     "Enter recent block height:",
                 )
                 .prompt()?,
@@ -10,20 +10,22 @@ use crate::common::RpcQueryResponseExt;
 #[interactive_clap(output_context = SignPrivateKeyContext)]
 pub struct SignPrivateKey {
     #[interactive_clap(long)]
+    #[interactive_clap(skip_default_input_arg)]
     /// Enter sender (signer) public key:
     pub signer_public_key: crate::types::public_key::PublicKey,
     #[interactive_clap(long)]
+    #[interactive_clap(skip_default_input_arg)]
     /// Enter sender (signer) private (secret) key:
     pub signer_private_key: crate::types::secret_key::SecretKey,
     #[interactive_clap(long)]
     #[interactive_clap(skip_default_input_arg)]
-    pub nonce: Option<u64>,
+    pub nonce: Option<crate::types::nonce::Nonce>,
     #[interactive_clap(long)]
     #[interactive_clap(skip_default_input_arg)]
     pub block_hash: Option<crate::types::crypto_hash::CryptoHash>,
     #[interactive_clap(long)]
     #[interactive_clap(skip_default_input_arg)]
-    pub block_height: Option<near_primitives::types::BlockHeight>,
+    pub block_height: Option<crate::types::block_height::BlockHeight>,
     #[interactive_clap(long)]
     #[interactive_clap(skip_interactive_input)]
     meta_transaction_valid_for: Option<u64>,
@@ -59,14 +61,16 @@ impl SignPrivateKeyContext {
             (
                 scope
                     .nonce
-                    .wrap_err("Nonce is required to sign a transaction in offline mode")?,
+                    .wrap_err("Nonce is required to sign a transaction in offline mode")?
+                    .0,
                 scope
                     .block_hash
                     .wrap_err("Block Hash is required to sign a transaction in offline mode")?
                     .0,
                 scope
                     .block_height
-                    .wrap_err("Block Height is required to sign a transaction in offline mode")?,
+                    .wrap_err("Block Height is required to sign a transaction in offline mode")?
+                    .0,
             )
         } else {
             let rpc_query_response = network_config
@@ -79,12 +83,17 @@ impl SignPrivateKeyContext {
                 .wrap_err_with(||
                     format!("Cannot sign a transaction due to an error while fetching the most recent nonce value on network <{}>", network_config.network_name)
                 )?;
+            let access_key_view = rpc_query_response
+                .access_key_view()
+                .wrap_err("Error current_nonce")?;
+            crate::tx_builder::validate_actions_for_key(
+                &near_primitives::account::AccessKeyPermission::from(access_key_view.permission),
+                &previous_context.prepopulated_transaction.receiver_id,
+                &previous_context.prepopulated_transaction.actions,
+            )?;
+
             (
-                rpc_query_response
-                    .access_key_view()
-                    .wrap_err("Error current_nonce")?
-                    .nonce
-                    + 1,
+                crate::tx_builder::next_nonce(access_key_view.nonce)?,
                 rpc_query_response.block_hash,
                 rpc_query_response.block_height,
             )
@@ -163,12 +172,57 @@ impl From<SignPrivateKeyContext> for super::SubmitContext {
 }
 
 impl SignPrivateKey {
+    fn input_signer_public_key(
+        _context: &crate::commands::TransactionContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::public_key::PublicKey>> {
+        if let Some(signer_private_key) = crate::config::env_file::var::<
+            crate::types::secret_key::SecretKey,
+        >(crate::config::env_file::NEAR_PRIVATE_KEY)?
+        {
+            let signer_private_key: near_crypto::SecretKey = signer_private_key.into();
+            crate::config::env_file::notify_override(
+                crate::config::env_file::NEAR_PRIVATE_KEY,
+                format!("the signer key is {}", signer_private_key.public_key()),
+            );
+            return Ok(Some(signer_private_key.public_key().into()));
+        }
+        Ok(Some(
+            CustomType::<crate::types::public_key::PublicKey>::new(
+                "Enter sender (signer) public key:",
+            )
+            .prompt()?,
+        ))
+    }
+
+    fn input_signer_private_key(
+        _context: &crate::commands::TransactionContext,
+    ) -> color_eyre::eyre::Result<Option<crate::types::secret_key::SecretKey>> {
+        if let Some(signer_private_key) = crate::config::env_file::var::<
+            crate::types::secret_key::SecretKey,
+        >(crate::config::env_file::NEAR_PRIVATE_KEY)?
+        {
+            let public_key = near_crypto::SecretKey::from(signer_private_key.clone()).public_key();
+            crate::config::env_file::notify_override(
+                crate::config::env_file::NEAR_PRIVATE_KEY,
+                format!("the signer key is {public_key}"),
+            );
+            return Ok(Some(signer_private_key));
+        }
+        Ok(Some(
+            CustomType::<crate::types::secret_key::SecretKey>::new(
+                "Enter sender (signer) private (secret) key:",
+            )
+            .prompt()?,
+        ))
+    }
+
     fn input_nonce(
         context: &crate::commands::TransactionContext,
-    ) -> color_eyre::eyre::Result<Option<u64>> {
+    ) -> color_eyre::eyre::Result<Option<crate::types::nonce::Nonce>> {
         if context.global_context.offline {
             return Ok(Some(
-                CustomType::<u64>::new("Enter a nonce for the access key:").prompt()?,
+                CustomType::<crate::types::nonce::Nonce>::new("Enter a nonce for the access key:")
+                    .prompt()?,
             ));
         }
         Ok(None)
@@ -190,10 +244,10 @@ impl SignPrivateKey {
 
     fn input_block_height(
         context: &crate::commands::TransactionContext,
-    ) -> color_eyre::eyre::Result<Option<near_primitives::types::BlockHeight>> {
+    ) -> color_eyre::eyre::Result<Option<crate::types::block_height::BlockHeight>> {
         if context.global_context.offline {
             return Ok(Some(
-                CustomType::<near_primitives::types::BlockHeight>::new(
+                CustomType::<crate::types::block_height::BlockHeight>::new(
                     "Enter recent block height:",
                 )
                 .prompt()?,
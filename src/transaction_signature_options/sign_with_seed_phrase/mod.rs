@@ -12,20 +12,25 @@ use crate::common::RpcQueryResponseExt;
 #[interactive_clap(input_context = crate::commands::TransactionContext)]
 #[interactive_clap(output_context = SignSeedPhraseContext)]
 pub struct SignSeedPhrase {
-    /// Enter the seed-phrase for this account:
-    master_seed_phrase: String,
+    #[interactive_clap(skip_interactive_input)]
+    /// The seed-phrase for this account (it is visible in the process list, prefer --seed-phrase-env)
+    master_seed_phrase: Option<String>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// The name of the environment variable with the seed-phrase for this account
+    seed_phrase_env: Option<String>,
     #[interactive_clap(long)]
     #[interactive_clap(skip_default_input_arg)]
     seed_phrase_hd_path: crate::types::slip10::BIP32Path,
     #[interactive_clap(long)]
     #[interactive_clap(skip_default_input_arg)]
-    pub nonce: Option<u64>,
+    pub nonce: Option<crate::types::nonce::Nonce>,
     #[interactive_clap(long)]
     #[interactive_clap(skip_default_input_arg)]
     pub block_hash: Option<crate::types::crypto_hash::CryptoHash>,
     #[interactive_clap(long)]
     #[interactive_clap(skip_default_input_arg)]
-    pub block_height: Option<near_primitives::types::BlockHeight>,
+    pub block_height: Option<crate::types::block_height::BlockHeight>,
     #[interactive_clap(long)]
     #[interactive_clap(skip_interactive_input)]
     meta_transaction_valid_for: Option<u64>,
@@ -52,9 +57,24 @@ impl SignSeedPhraseContext {
     ) -> color_eyre::eyre::Result<Self> {
         let network_config = previous_context.network_config.clone();
 
+        let master_seed_phrase = match (&scope.master_seed_phrase, &scope.seed_phrase_env) {
+            (Some(_), Some(_)) => {
+                return Err(color_eyre::eyre::eyre!(
+                    "The seed-phrase and --seed-phrase-env cannot be used together"
+                ))
+            }
+            (Some(master_seed_phrase), None) => {
+                eprintln!("WARNING: The seed-phrase passed on the command line is visible to other users in the process list and is kept in the shell history. Use --seed-phrase-env <VARIABLE> to pass it through an environment variable instead.");
+                master_seed_phrase.clone()
+            }
+            (None, Some(variable)) => seed_phrase_from_env(variable, std::env::var(variable))?,
+            (None, None) => inquire::Password::new("Enter the seed-phrase for this account:")
+                .without_confirmation()
+                .prompt()?,
+        };
         let key_pair_properties = crate::common::get_key_pair_properties_from_seed_phrase(
             scope.seed_phrase_hd_path.clone(),
-            scope.master_seed_phrase.clone(),
+            master_seed_phrase,
         )?;
 
         let signer_secret_key: near_crypto::SecretKey =
@@ -66,14 +86,16 @@ impl SignSeedPhraseContext {
             (
                 scope
                     .nonce
-                    .wrap_err("Nonce is required to sign a transaction in offline mode")?,
+                    .wrap_err("Nonce is required to sign a transaction in offline mode")?
+                    .0,
                 scope
                     .block_hash
                     .wrap_err("Block Hash is required to sign a transaction in offline mode")?
                     .0,
                 scope
                     .block_height
-                    .wrap_err("Block Height is required to sign a transaction in offline mode")?,
+                    .wrap_err("Block Height is required to sign a transaction in offline mode")?
+                    .0,
             )
         } else {
             let rpc_query_response = network_config
@@ -86,12 +108,17 @@ impl SignSeedPhraseContext {
                 .wrap_err_with(||
                     format!("Cannot sign a transaction due to an error while fetching the most recent nonce value on network <{}>", network_config.network_name)
                 )?;
+            let access_key_view = rpc_query_response
+                .access_key_view()
+                .wrap_err("Error current_nonce")?;
+            crate::tx_builder::validate_actions_for_key(
+                &near_primitives::account::AccessKeyPermission::from(access_key_view.permission),
+                &previous_context.prepopulated_transaction.receiver_id,
+                &previous_context.prepopulated_transaction.actions,
+            )?;
+
             (
-                rpc_query_response
-                    .access_key_view()
-                    .wrap_err("Error current_nonce")?
-                    .nonce
-                    + 1,
+                crate::tx_builder::next_nonce(access_key_view.nonce)?,
                 rpc_query_response.block_hash,
                 rpc_query_response.block_height,
             )
@@ -154,6 +181,23 @@ impl SignSeedPhraseContext {
     }
 }
 
+fn seed_phrase_from_env(
+    variable: &str,
+    value: Result<String, std::env::VarError>,
+) -> color_eyre::eyre::Result<String> {
+    match value {
+        Ok(master_seed_phrase) if !master_seed_phrase.trim().is_empty() => {
+            Ok(master_seed_phrase.trim().to_string())
+        }
+        Ok(_) => Err(color_eyre::eyre::eyre!(
+            "The environment variable <{variable}> with the seed-phrase is empty"
+        )),
+        Err(err) => Err(err).wrap_err_with(|| {
+            format!("Failed to read the seed-phrase from the environment variable <{variable}>")
+        }),
+    }
+}
+
 impl From<SignSeedPhraseContext> for super::SubmitContext {
     fn from(item: SignSeedPhraseContext) -> Self {
         Self {
@@ -170,10 +214,11 @@ impl From<SignSeedPhraseContext> for super::SubmitContext {
 impl SignSeedPhrase {
     fn input_nonce(
         context: &crate::commands::TransactionContext,
-    ) -> color_eyre::eyre::Result<Option<u64>> {
+    ) -> color_eyre::eyre::Result<Option<crate::types::nonce::Nonce>> {
         if context.global_context.offline {
             return Ok(Some(
-                CustomType::<u64>::new("Enter a nonce for the access key:").prompt()?,
+                CustomType::<crate::types::nonce::Nonce>::new("Enter a nonce for the access key:")
+                    .prompt()?,
             ));
         }
         Ok(None)
@@ -195,10 +240,10 @@ impl SignSeedPhrase {
 
     fn input_block_height(
         context: &crate::commands::TransactionContext,
-    ) -> color_eyre::eyre::Result<Option<near_primitives::types::BlockHeight>> {
+    ) -> color_eyre::eyre::Result<Option<crate::types::block_height::BlockHeight>> {
         if context.global_context.offline {
             return Ok(Some(
-                CustomType::<near_primitives::types::BlockHeight>::new(
+                CustomType::<crate::types::block_height::BlockHeight>::new(
                     "Enter recent block height:",
                 )
                 .prompt()?,
@@ -217,3 +262,28 @@ impl SignSeedPhrase {
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seed_phrase_from_environment_variable() {
+        assert_eq!(
+            seed_phrase_from_env("NEAR_SEED_PHRASE", Ok(" word1 word2\n".to_string())).unwrap(),
+            "word1 word2"
+        );
+        assert!(
+            seed_phrase_from_env("NEAR_SEED_PHRASE", Ok("  ".to_string()))
+                .unwrap_err()
+                .to_string()
+                .contains("<NEAR_SEED_PHRASE> with the seed-phrase is empty")
+        );
+        assert!(
+            seed_phrase_from_env("NEAR_SEED_PHRASE", Err(std::env::VarError::NotPresent))
+                .unwrap_err()
+                .to_string()
+                .contains("from the environment variable <NEAR_SEED_PHRASE>")
+        );
+    }
+}
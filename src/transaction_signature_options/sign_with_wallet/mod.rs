@@ -0,0 +1,437 @@
+use std::io::{BufRead, Write};
+
+use color_eyre::eyre::WrapErr;
+use tracing_indicatif::span_ext::IndicatifSpanExt;
+
+use crate::common::JsonRpcClientExt;
+
+/// How long to wait for the wallet to redirect back (when --timeout is not given)
+const DEFAULT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(300);
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = crate::commands::TransactionContext)]
+#[interactive_clap(output_context = SignWalletContext)]
+pub struct SignWallet {
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// How long to wait for the approval in the wallet, e.g. 90s or 10m (default: 5m)
+    timeout: Option<crate::types::duration::Duration>,
+    #[interactive_clap(long)]
+    /// Only print the URL, do not open it in the browser
+    no_browser: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct SignWalletContext;
+
+impl SignWalletContext {
+    pub fn from_previous_context(
+        previous_context: crate::commands::TransactionContext,
+        scope: &<SignWallet as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        if previous_context.global_context.offline {
+            color_eyre::eyre::bail!(
+                "The web wallet signs and sends the transaction itself, so sign-with-wallet cannot be used in offline mode"
+            );
+        }
+        let network_config = previous_context.network_config;
+        let signer_id = previous_context.prepopulated_transaction.signer_id;
+
+        // The wallet sets the access key, the nonce and the block hash of the transaction
+        let mut unsigned_transaction = near_primitives::transaction::Transaction::V0(
+            near_primitives::transaction::TransactionV0 {
+                signer_id: signer_id.clone(),
+                public_key: near_crypto::PublicKey::empty(near_crypto::KeyType::ED25519),
+                nonce: 0,
+                receiver_id: previous_context.prepopulated_transaction.receiver_id,
+                block_hash: Default::default(),
+                actions: previous_context.prepopulated_transaction.actions,
+            },
+        );
+        (previous_context.on_before_signing_callback)(&mut unsigned_transaction, &network_config)?;
+
+        // The callbacks run before the transaction leaves the CLI (e.g. create-account saves the
+        // new key there, before the funds are sent). The wallet signs the transaction, so they
+        // get it with an empty signature.
+        let storage_message = (previous_context.on_before_sending_transaction_callback)(
+            &crate::transaction_signature_options::SignedTransactionOrSignedDelegateAction::SignedTransaction(
+                near_primitives::transaction::SignedTransaction::new(
+                    near_crypto::Signature::empty(near_crypto::KeyType::ED25519),
+                    unsigned_transaction.clone(),
+                ),
+            ),
+            &network_config,
+        )
+        .map_err(color_eyre::Report::msg)?;
+
+        let listener = std::net::TcpListener::bind(("127.0.0.1", 0))
+            .wrap_err("Failed to start the local server that receives the wallet callback")?;
+        let state = callback_state();
+        let callback_url = callback_url(listener.local_addr()?.port(), &state)?;
+        let url = wallet_sign_url(
+            &network_config,
+            std::slice::from_ref(&unsigned_transaction),
+            Some(&callback_url),
+        )?;
+
+        eprintln!("Approve the transaction in the wallet, this URL is waiting for you:\n {url}\n");
+        crate::qr_code::eprint_url("Or scan it with the wallet on your phone:", &url);
+        if !scope.no_browser && open::that(url.as_str()).is_err() {
+            eprintln!("Failed to open the browser, open the URL manually.");
+        }
+
+        let timeout = scope.timeout.map_or(DEFAULT_TIMEOUT, Into::into);
+        let transaction_hashes = match wait_for_wallet_callback(&listener, &state, timeout)? {
+            WalletCallback::Signed(transaction_hashes) => transaction_hashes,
+            WalletCallback::Rejected {
+                error_code,
+                error_message,
+            } => color_eyre::eyre::bail!(
+                "The wallet did not sign the transaction ({error_code}){}",
+                error_message
+                    .map(|message| format!(": {message}"))
+                    .unwrap_or_default()
+            ),
+        };
+
+        for transaction_hash in transaction_hashes {
+            eprintln!("The wallet has sent the transaction {transaction_hash}");
+            crate::deadline::record_transaction_hash(transaction_hash);
+            let transaction_info =
+                get_transaction_outcome(&network_config, transaction_hash, &signer_id, timeout);
+            crate::audit_log::record(
+                &previous_context.global_context.config,
+                &network_config,
+                crate::audit_log::AuditedTransaction {
+                    signer_id: &signer_id,
+                    receiver_id: unsigned_transaction.receiver_id(),
+                    actions: unsigned_transaction
+                        .actions()
+                        .iter()
+                        .cloned()
+                        .map(Into::into)
+                        .collect(),
+                    transaction_hash: Some(transaction_hash),
+                },
+                format!(
+                    "signed and sent by the wallet, {}",
+                    crate::audit_log::sent_transaction_outcome(&transaction_info)
+                ),
+            );
+            let transaction_info = transaction_info?;
+            crate::common::print_transaction_status(&transaction_info, &network_config)?;
+            (previous_context.on_after_sending_transaction_callback)(
+                &transaction_info,
+                &network_config,
+            )
+            .map_err(color_eyre::Report::msg)?;
+        }
+        eprintln!("{storage_message}");
+        Ok(Self)
+    }
+}
+
+/// The `sign` page of the wallet (the URL that near-api-js `requestSignTransactions` opens):
+/// the borsh-serialized transactions in base64, separated by commas, and the URL that the
+/// wallet redirects to once the transactions are sent or rejected.
+pub fn wallet_sign_url(
+    network_config: &crate::config::NetworkConfig,
+    transactions: &[near_primitives::transaction::Transaction],
+    callback_url: Option<&url::Url>,
+) -> color_eyre::eyre::Result<url::Url> {
+    let transactions = transactions
+        .iter()
+        .map(|transaction| {
+            Ok(near_primitives::serialize::to_base64(
+                &near_primitives::borsh::to_vec(transaction)?,
+            ))
+        })
+        .collect::<color_eyre::eyre::Result<Vec<_>>>()?
+        .join(",");
+    let mut url = crate::config::config_url::ConfigUrl::new(
+        network_config,
+        "wallet_url",
+        &network_config.wallet_url,
+    )
+    .join("sign")?;
+    url.query_pairs_mut()
+        .append_pair("transactions", &transactions);
+    if let Some(callback_url) = callback_url {
+        url.query_pairs_mut()
+            .append_pair("callbackUrl", callback_url.as_str());
+    }
+    Ok(url)
+}
+
+/// A random value of this run, in the path of the callback URL, so that the local server only
+/// accepts the redirect of the wallet (and not any request that reaches the port).
+fn callback_state() -> String {
+    let random_key = near_crypto::SecretKey::from_random(near_crypto::KeyType::ED25519);
+    near_primitives::hash::CryptoHash::hash_bytes(random_key.to_string().as_bytes()).to_string()
+}
+
+fn callback_url(port: u16, state: &str) -> color_eyre::eyre::Result<url::Url> {
+    Ok(format!("http://127.0.0.1:{port}/{state}").parse()?)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum WalletCallback {
+    Signed(Vec<near_primitives::hash::CryptoHash>),
+    Rejected {
+        error_code: String,
+        error_message: Option<String>,
+    },
+}
+
+/// The outcome that the wallet passes in the query of the callback URL, or `None` for other
+/// requests (e.g. `/favicon.ico` or a request without the state of this run).
+fn parse_callback(
+    request_target: &str,
+    state: &str,
+) -> color_eyre::eyre::Result<Option<WalletCallback>> {
+    let url = url::Url::parse("http://127.0.0.1")?.join(request_target)?;
+    if url.path().trim_end_matches('/') != format!("/{state}") {
+        return Ok(None);
+    }
+    let mut transaction_hashes = None;
+    let mut error_code = None;
+    let mut error_message = None;
+    for (name, value) in url.query_pairs() {
+        match name.as_ref() {
+            "transactionHashes" => transaction_hashes = Some(value.into_owned()),
+            "errorCode" => error_code = Some(value.into_owned()),
+            "errorMessage" => error_message = Some(value.into_owned()),
+            _ => {}
+        }
+    }
+    if let Some(error_code) = error_code {
+        return Ok(Some(WalletCallback::Rejected {
+            error_code,
+            error_message,
+        }));
+    }
+    let Some(transaction_hashes) = transaction_hashes else {
+        return Ok(None);
+    };
+    let transaction_hashes = transaction_hashes
+        .split(',')
+        .filter(|hash| !hash.is_empty())
+        .map(|hash| {
+            hash.parse().map_err(|err| {
+                color_eyre::eyre::eyre!(
+                    "The wallet returned an invalid transaction hash '{hash}': {err}"
+                )
+            })
+        })
+        .collect::<color_eyre::eyre::Result<Vec<_>>>()?;
+    Ok(Some(WalletCallback::Signed(transaction_hashes)))
+}
+
+#[tracing::instrument(name = "Waiting for the approval in the wallet", skip_all)]
+fn wait_for_wallet_callback(
+    listener: &std::net::TcpListener,
+    state: &str,
+    timeout: std::time::Duration,
+) -> color_eyre::eyre::Result<WalletCallback> {
+    listener.set_nonblocking(true)?;
+    let started_at = std::time::Instant::now();
+    loop {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                if let Some(callback) = respond_to_callback(stream, state)? {
+                    return Ok(callback);
+                }
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => {
+                if started_at.elapsed() > timeout {
+                    color_eyre::eyre::bail!(
+                        "The transaction has not been approved in the wallet in {} seconds",
+                        timeout.as_secs()
+                    );
+                }
+                tracing::Span::current()
+                    .pb_set_message(&format!("{}s elapsed", started_at.elapsed().as_secs()));
+                std::thread::sleep(std::time::Duration::from_millis(100));
+            }
+            Err(err) => return Err(err).wrap_err("Failed to accept the wallet callback"),
+        }
+    }
+}
+
+fn respond_to_callback(
+    stream: std::net::TcpStream,
+    state: &str,
+) -> color_eyre::eyre::Result<Option<WalletCallback>> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(std::time::Duration::from_secs(5)))?;
+    let mut request_line = String::new();
+    if std::io::BufReader::new(&stream)
+        .read_line(&mut request_line)
+        .is_err()
+    {
+        return Ok(None);
+    }
+    // "GET /<state>?transactionHashes=... HTTP/1.1"
+    let request_target = request_line.split_whitespace().nth(1).unwrap_or("/");
+    let callback = parse_callback(request_target, state);
+    let (status, body) = match &callback {
+        Ok(Some(WalletCallback::Signed(_))) => (
+            "200 OK",
+            "The transaction has been sent, you can close this tab and return to the terminal.",
+        ),
+        Ok(Some(WalletCallback::Rejected { .. })) => (
+            "200 OK",
+            "The transaction has been rejected, you can close this tab and return to the terminal.",
+        ),
+        Ok(None) => ("404 Not Found", "Not found"),
+        Err(_) => ("400 Bad Request", "Invalid callback from the wallet"),
+    };
+    let _ = write!(
+        &stream,
+        "HTTP/1.1 {status}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    callback
+}
+
+/// The outcome of the transaction sent by the wallet (which may not be known to the RPC node yet).
+#[tracing::instrument(name = "Getting the outcome of the transaction", skip_all)]
+fn get_transaction_outcome(
+    network_config: &crate::config::NetworkConfig,
+    transaction_hash: near_primitives::hash::CryptoHash,
+    sender_account_id: &near_primitives::types::AccountId,
+    timeout: std::time::Duration,
+) -> color_eyre::eyre::Result<near_primitives::views::FinalExecutionOutcomeView> {
+    let started_at = std::time::Instant::now();
+    loop {
+        match network_config.json_rpc_client().blocking_call(
+            near_jsonrpc_client::methods::tx::RpcTransactionStatusRequest {
+                transaction_info:
+                    near_jsonrpc_client::methods::tx::TransactionInfo::TransactionId {
+                        tx_hash: transaction_hash,
+                        sender_account_id: sender_account_id.clone(),
+                    },
+                wait_until: near_primitives::views::TxExecutionStatus::Final,
+            },
+        ) {
+            Ok(near_jsonrpc_client::methods::tx::RpcTransactionResponse {
+                final_execution_outcome: Some(final_execution_outcome),
+                ..
+            }) => return Ok(final_execution_outcome.into_outcome()),
+            Ok(_)
+            | Err(near_jsonrpc_client::errors::JsonRpcError::ServerError(
+                near_jsonrpc_client::errors::JsonRpcServerError::HandlerError(
+                    near_jsonrpc_client::methods::tx::RpcTransactionError::UnknownTransaction {
+                        ..
+                    }
+                    | near_jsonrpc_client::methods::tx::RpcTransactionError::TimeoutError,
+                ),
+            )) => {}
+            Err(err) => {
+                return Err(color_eyre::eyre::eyre!(
+                    "Failed to fetch the outcome of the transaction {transaction_hash}: {err}"
+                ))
+            }
+        }
+        if started_at.elapsed() > timeout {
+            color_eyre::eyre::bail!(
+                "The outcome of the transaction {transaction_hash} is not known after {} seconds",
+                timeout.as_secs()
+            );
+        }
+        tracing::Span::current().pb_set_message(&format!(
+            "{transaction_hash} ({}s elapsed)",
+            started_at.elapsed().as_secs()
+        ));
+        std::thread::sleep(std::time::Duration::from_secs(2));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use near_primitives::borsh::BorshDeserialize;
+
+    use super::*;
+
+    #[test]
+    fn the_sign_url_contains_the_transactions_and_the_callback() {
+        let config = crate::config::Config::default();
+        let testnet = config.network_connection.get("testnet").unwrap();
+        let transactions: Vec<_> = ["alice.testnet", "bob.testnet"]
+            .into_iter()
+            .map(|receiver_id| {
+                near_primitives::transaction::Transaction::V0(
+                    near_primitives::transaction::TransactionV0 {
+                        signer_id: "carol.testnet".parse().unwrap(),
+                        public_key: near_crypto::PublicKey::empty(near_crypto::KeyType::ED25519),
+                        nonce: 0,
+                        receiver_id: receiver_id.parse().unwrap(),
+                        block_hash: Default::default(),
+                        actions: vec![],
+                    },
+                )
+            })
+            .collect();
+        let callback_url: url::Url = "http://127.0.0.1:8123/".parse().unwrap();
+        let url = wallet_sign_url(testnet, &transactions, Some(&callback_url)).unwrap();
+
+        let query: std::collections::HashMap<_, _> = url.query_pairs().collect();
+        assert_eq!(query["callbackUrl"], callback_url.as_str());
+        let decoded: Vec<_> = query["transactions"]
+            .split(',')
+            .map(|transaction| {
+                near_primitives::transaction::Transaction::try_from_slice(
+                    &near_primitives::serialize::from_base64(transaction).unwrap(),
+                )
+                .unwrap()
+            })
+            .collect();
+        assert_eq!(decoded, transactions);
+    }
+
+    #[test]
+    fn callbacks_of_the_wallet() {
+        let state = callback_state();
+        assert_ne!(state, callback_state());
+        assert_eq!(
+            callback_url(8123, &state).unwrap().as_str(),
+            format!("http://127.0.0.1:8123/{state}")
+        );
+
+        let hash = near_primitives::hash::CryptoHash::hash_bytes(b"tx");
+        assert_eq!(
+            parse_callback(&format!("/{state}?transactionHashes={hash}"), &state).unwrap(),
+            Some(WalletCallback::Signed(vec![hash]))
+        );
+        assert_eq!(
+            parse_callback(
+                &format!("/{state}?transactionHashes={hash}%2C{hash}"),
+                &state
+            )
+            .unwrap(),
+            Some(WalletCallback::Signed(vec![hash, hash]))
+        );
+        assert_eq!(
+            parse_callback(
+                &format!("/{state}?errorCode=userRejected&errorMessage=User%20rejected"),
+                &state
+            )
+            .unwrap(),
+            Some(WalletCallback::Rejected {
+                error_code: "userRejected".to_string(),
+                error_message: Some("User rejected".to_string()),
+            })
+        );
+        assert_eq!(parse_callback("/favicon.ico", &state).unwrap(), None);
+        assert!(parse_callback(&format!("/{state}?transactionHashes=not-a-hash"), &state).is_err());
+        // Requests without the state of this run are not the redirect of the wallet
+        assert_eq!(
+            parse_callback(&format!("/?transactionHashes={hash}"), &state).unwrap(),
+            None
+        );
+        assert_eq!(
+            parse_callback("/other-state?errorCode=userRejected", &state).unwrap(),
+            None
+        );
+    }
+}
@@ -0,0 +1,332 @@
+use std::io::IsTerminal;
+
+use color_eyre::eyre::WrapErr;
+
+use crate::common::{JsonRpcClientExt, RpcQueryResponseExt};
+
+/// The chain ID that the mainnet nodes report in their status
+const MAINNET_CHAIN_ID: &str = "mainnet";
+
+/// Enforces the `require_ledger_above` policy from the config file.
+///
+/// When the total outgoing value of a mainnet transaction exceeds the configured threshold, the
+/// transaction must be signed with Ledger: Ledger is preselected if no signing option was given,
+/// and any other signing option is rejected. Mainnet is told by the chain ID of the node, whatever
+/// the name of the connection.
+pub fn enforce_require_ledger_policy(
+    global_context: &crate::GlobalContext,
+    network_config: &crate::config::NetworkConfig,
+    prepopulated_transaction: &crate::commands::PrepopulatedTransaction,
+    sign_with: &mut Option<super::CliSignWith>,
+) -> crate::CliResult {
+    let Some(threshold) = global_context.config.require_ledger_above else {
+        return Ok(());
+    };
+    let chain_id = get_chain_id(global_context, network_config);
+    if !is_mainnet(chain_id.as_deref()) {
+        return Ok(());
+    }
+
+    let outgoing_value =
+        get_transaction_outgoing_value(global_context, network_config, prepopulated_transaction)?;
+    if outgoing_value <= threshold {
+        return Ok(());
+    }
+
+    match sign_with {
+        #[cfg(feature = "ledger")]
+        Some(super::CliSignWith::SignWithLedger(_)) => Ok(()),
+        #[cfg(feature = "ledger")]
+        None => {
+            eprintln!(
+                "The total outgoing value of this transaction ({outgoing_value}) exceeds {threshold}, so it has to be signed with Ledger.\n"
+            );
+            *sign_with = Some(super::CliSignWith::SignWithLedger(Default::default()));
+            Ok(())
+        }
+        _ => Err(color_eyre::eyre::eyre!(
+            "The total outgoing value of this transaction ({outgoing_value}) exceeds {threshold}, and the `require_ledger_above` policy requires such mainnet transactions to be signed with Ledger (sign-with-ledger){}.\nTo change the policy, edit or remove the `require_ledger_above` option in the `near` CLI configuration file (see `near config show-connections`).",
+            if chain_id.is_none() {
+                " (the chain ID of the network is not known, so it is treated as mainnet)"
+            } else {
+                ""
+            }
+        )),
+    }
+}
+
+/// The chain ID of the network, `None` if it is not known (in offline mode or if the node does
+/// not respond).
+fn get_chain_id(
+    global_context: &crate::GlobalContext,
+    network_config: &crate::config::NetworkConfig,
+) -> Option<String> {
+    if global_context.offline {
+        return None;
+    }
+    network_config
+        .json_rpc_client()
+        .blocking_call(near_jsonrpc_client::methods::status::RpcStatusRequest)
+        .map(|status| status.chain_id)
+        .inspect_err(|err| {
+            tracing::warn!(
+                "Failed to fetch the chain ID of network <{}>: {err}",
+                network_config.network_name
+            )
+        })
+        .ok()
+}
+
+/// A network with an unknown chain ID is taken for mainnet, so that the policy is not bypassed.
+fn is_mainnet(chain_id: Option<&str>) -> bool {
+    chain_id.map_or(true, |chain_id| chain_id == MAINNET_CHAIN_ID)
+}
+
+/// Enforces `--yes-below`: a transaction that sends at least the given amount, or deletes the
+/// account (the whole balance goes to the beneficiary), needs a confirmation, and fails if there
+/// is no terminal to confirm it.
+pub fn enforce_yes_below_policy(
+    global_context: &crate::GlobalContext,
+    network_config: &crate::config::NetworkConfig,
+    prepopulated_transaction: &crate::commands::PrepopulatedTransaction,
+) -> crate::CliResult {
+    let Some(threshold) = global_context.yes_below else {
+        return Ok(());
+    };
+    let outgoing_value =
+        get_transaction_outgoing_value(global_context, network_config, prepopulated_transaction)?;
+    let Some(reason) =
+        confirmation_reason(&prepopulated_transaction.actions, outgoing_value, threshold)
+    else {
+        return Ok(());
+    };
+    if !std::io::stdin().is_terminal() {
+        color_eyre::eyre::bail!(
+            "{reason}, so it needs a confirmation, but there is no terminal to confirm it. Nothing was signed."
+        );
+    }
+    #[derive(strum_macros::Display, PartialEq)]
+    enum ConfirmOptions {
+        #[strum(to_string = "Yes, continue with this transaction.")]
+        Yes,
+        #[strum(to_string = "No, cancel the transaction.")]
+        No,
+    }
+    let select_choose_input = crate::common::select(
+        &format!("{reason}. Do you want to continue?"),
+        vec![ConfirmOptions::Yes, ConfirmOptions::No],
+    )?;
+    if select_choose_input != Some(ConfirmOptions::Yes) {
+        color_eyre::eyre::bail!("The transaction was cancelled");
+    }
+    Ok(())
+}
+
+/// Why a transaction is not confirmed automatically by `--yes-below`, if it is not.
+fn confirmation_reason(
+    actions: &[near_primitives::transaction::Action],
+    outgoing_value: crate::types::near_token::NearToken,
+    threshold: crate::types::near_token::NearToken,
+) -> Option<String> {
+    if has_delete_account_action(actions) {
+        return Some(
+            "This transaction deletes the account and sends its whole balance to the beneficiary (--yes-below)"
+                .to_string(),
+        );
+    }
+    (outgoing_value >= threshold).then(|| {
+        format!(
+            "The total outgoing value of this transaction ({outgoing_value}) is not below {threshold} (--yes-below)"
+        )
+    })
+}
+
+fn has_delete_account_action(actions: &[near_primitives::transaction::Action]) -> bool {
+    actions.iter().any(|action| match action {
+        near_primitives::transaction::Action::DeleteAccount(_) => true,
+        near_primitives::transaction::Action::Delegate(signed_delegate_action) => {
+            has_delete_account_action(&signed_delegate_action.delegate_action.get_actions())
+        }
+        _ => false,
+    })
+}
+
+/// The outgoing value of the transaction, with the stake increases relative to the currently
+/// locked balance of the signer.
+fn get_transaction_outgoing_value(
+    global_context: &crate::GlobalContext,
+    network_config: &crate::config::NetworkConfig,
+    prepopulated_transaction: &crate::commands::PrepopulatedTransaction,
+) -> color_eyre::eyre::Result<crate::types::near_token::NearToken> {
+    let has_stake_action = prepopulated_transaction
+        .actions
+        .iter()
+        .any(|action| matches!(action, near_primitives::transaction::Action::Stake(_)));
+    // The currently locked balance is unknown in offline mode, so the whole stake is counted
+    let locked_balance = if has_stake_action && !global_context.offline {
+        network_config
+            .json_rpc_client()
+            .blocking_call_view_account(
+                &prepopulated_transaction.signer_id,
+                near_primitives::types::Finality::Final.into(),
+            )
+            .wrap_err_with(|| {
+                format!(
+                    "Failed to fetch the locked balance of <{}>",
+                    prepopulated_transaction.signer_id
+                )
+            })?
+            .account_view()?
+            .locked
+    } else {
+        0
+    };
+
+    Ok(crate::types::near_token::NearToken::from_yoctonear(
+        get_outgoing_value(&prepopulated_transaction.actions, locked_balance),
+    ))
+}
+
+/// Sums up transfers, attached deposits and stake increases (relative to `locked_balance`).
+fn get_outgoing_value(
+    actions: &[near_primitives::transaction::Action],
+    locked_balance: near_primitives::types::Balance,
+) -> near_primitives::types::Balance {
+    actions
+        .iter()
+        .map(|action| match action {
+            near_primitives::transaction::Action::Transfer(transfer_action) => {
+                transfer_action.deposit
+            }
+            near_primitives::transaction::Action::FunctionCall(function_call_action) => {
+                function_call_action.deposit
+            }
+            near_primitives::transaction::Action::Stake(stake_action) => {
+                stake_action.stake.saturating_sub(locked_balance)
+            }
+            near_primitives::transaction::Action::Delegate(signed_delegate_action) => {
+                get_outgoing_value(
+                    &signed_delegate_action.delegate_action.get_actions(),
+                    locked_balance,
+                )
+            }
+            _ => 0,
+        })
+        .fold(0, u128::saturating_add)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_primitives::transaction::{Action, FunctionCallAction, StakeAction, TransferAction};
+
+    const ONE_NEAR: u128 = 10u128.pow(24);
+
+    fn transfer(deposit: u128) -> Action {
+        Action::Transfer(TransferAction { deposit })
+    }
+
+    fn function_call(deposit: u128) -> Action {
+        Action::FunctionCall(Box::new(FunctionCallAction {
+            method_name: "ft_transfer".to_string(),
+            args: vec![],
+            gas: 30_000_000_000_000,
+            deposit,
+        }))
+    }
+
+    fn stake(stake: u128) -> Action {
+        Action::Stake(Box::new(StakeAction {
+            stake,
+            public_key: near_crypto::PublicKey::empty(near_crypto::KeyType::ED25519),
+        }))
+    }
+
+    #[test]
+    fn mainnet_is_told_by_the_chain_id() {
+        assert!(is_mainnet(Some("mainnet")));
+        assert!(!is_mainnet(Some("testnet")));
+        assert!(!is_mainnet(Some("localnet")));
+        assert!(is_mainnet(None));
+    }
+
+    #[test]
+    fn outgoing_value_of_single_transfer() {
+        assert_eq!(
+            get_outgoing_value(&[transfer(100 * ONE_NEAR)], 0),
+            100 * ONE_NEAR
+        );
+    }
+
+    #[test]
+    fn outgoing_value_of_multi_action_transaction() {
+        let actions = [
+            transfer(60 * ONE_NEAR),
+            function_call(40 * ONE_NEAR),
+            function_call(1),
+            Action::CreateAccount(near_primitives::transaction::CreateAccountAction {}),
+        ];
+        assert_eq!(get_outgoing_value(&actions, 0), 100 * ONE_NEAR + 1);
+    }
+
+    #[test]
+    fn outgoing_value_counts_only_stake_increase() {
+        assert_eq!(
+            get_outgoing_value(&[stake(150 * ONE_NEAR)], 100 * ONE_NEAR),
+            50 * ONE_NEAR
+        );
+        assert_eq!(
+            get_outgoing_value(&[stake(50 * ONE_NEAR)], 100 * ONE_NEAR),
+            0
+        );
+    }
+
+    #[test]
+    fn outgoing_value_boundaries() {
+        let threshold = crate::types::near_token::NearToken::from_yoctonear(100 * ONE_NEAR);
+        let at_threshold = crate::types::near_token::NearToken::from_yoctonear(get_outgoing_value(
+            &[transfer(50 * ONE_NEAR), transfer(50 * ONE_NEAR)],
+            0,
+        ));
+        let above_threshold =
+            crate::types::near_token::NearToken::from_yoctonear(get_outgoing_value(
+                &[transfer(50 * ONE_NEAR), function_call(50 * ONE_NEAR + 1)],
+                0,
+            ));
+        assert!(at_threshold <= threshold);
+        assert!(above_threshold > threshold);
+    }
+
+    #[test]
+    fn yes_below_counts_all_deposits_of_a_batch() {
+        let threshold = crate::types::near_token::NearToken::from_yoctonear(10 * ONE_NEAR);
+        let reason = |actions: &[Action]| {
+            confirmation_reason(
+                actions,
+                crate::types::near_token::NearToken::from_yoctonear(get_outgoing_value(actions, 0)),
+                threshold,
+            )
+        };
+        assert_eq!(
+            reason(&[transfer(5 * ONE_NEAR), function_call(ONE_NEAR)]),
+            None
+        );
+        // Each action is below the threshold, the batch is not
+        assert!(reason(&[
+            transfer(5 * ONE_NEAR),
+            function_call(3 * ONE_NEAR),
+            function_call(2 * ONE_NEAR),
+        ])
+        .unwrap()
+        .contains("(10 NEAR) is not below 10 NEAR"));
+        assert!(reason(&[function_call(6 * ONE_NEAR), stake(4 * ONE_NEAR)]).is_some());
+        assert!(reason(&[
+            transfer(1),
+            Action::DeleteAccount(near_primitives::transaction::DeleteAccountAction {
+                beneficiary_id: "bob.near".parse().unwrap(),
+            }),
+        ])
+        .unwrap()
+        .contains("deletes the account"));
+    }
+}
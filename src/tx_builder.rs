@@ -0,0 +1,465 @@
+//! The rules of transaction assembly as pure functions (no prompts, no RPC calls), so that the
+//! action ordering, the deposit math, the access key checks and the no-op action checks can be
+//! unit tested.
+
+use near_primitives::transaction::Action;
+
+/// Appends the action to the actions of a transaction.
+pub fn append_action(
+    mut actions: Vec<Action>,
+    action: Action,
+) -> color_eyre::eyre::Result<Vec<Action>> {
+    if let Some(Action::DeleteAccount(_)) = actions.last() {
+        color_eyre::eyre::bail!(
+            "No action can be added after DeleteAccount: it must be the last action of a transaction"
+        );
+    }
+    actions.push(action);
+    Ok(actions)
+}
+
+/// The NEAR sent with the actions (transfers and deposits attached to function calls). Stakes are
+/// not included: the staked NEAR is locked, it does not leave the account.
+pub fn compute_required_deposit(
+    actions: &[Action],
+) -> color_eyre::eyre::Result<crate::types::near_token::NearToken> {
+    actions
+        .iter()
+        .try_fold(0u128, |total_deposit, action| {
+            let deposit = match action {
+                Action::Transfer(transfer_action) => transfer_action.deposit,
+                Action::FunctionCall(function_call_action) => function_call_action.deposit,
+                _ => 0,
+            };
+            total_deposit.checked_add(deposit).ok_or_else(|| {
+                color_eyre::eyre::eyre!(
+                    "The total deposit of the actions overflows the maximum amount of yoctoNEAR"
+                )
+            })
+        })
+        .map(crate::types::near_token::NearToken::from_yoctonear)
+}
+
+/// The checks the runtime makes on the actions of any transaction.
+pub fn validate_actions(actions: &[Action]) -> color_eyre::eyre::Result<()> {
+    if let Some(position) = actions
+        .iter()
+        .position(|action| matches!(action, Action::DeleteAccount(_)))
+    {
+        if position + 1 != actions.len() {
+            color_eyre::eyre::bail!(
+                "DeleteAccount must be the last action of a transaction, but it is action #{} of {}",
+                position + 1,
+                actions.len()
+            );
+        }
+    }
+    for action in actions {
+        if let Action::Delegate(signed_delegate_action) = action {
+            let delegated_actions = signed_delegate_action.delegate_action.get_actions();
+            if delegated_actions
+                .iter()
+                .any(|action| matches!(action, Action::Delegate(_)))
+            {
+                color_eyre::eyre::bail!("A delegate action cannot contain another delegate action");
+            }
+            validate_actions(&delegated_actions)?;
+        }
+    }
+    compute_required_deposit(actions)?;
+    Ok(())
+}
+
+/// Whether an access key with the permission can sign the actions for the receiver (the checks
+/// of the runtime for function-call access keys).
+pub fn validate_actions_for_key(
+    permission: &near_primitives::account::AccessKeyPermission,
+    receiver_id: &near_primitives::types::AccountId,
+    actions: &[Action],
+) -> color_eyre::eyre::Result<()> {
+    let near_primitives::account::AccessKeyPermission::FunctionCall(permission) = permission else {
+        return Ok(());
+    };
+    let [Action::FunctionCall(function_call_action)] = actions else {
+        color_eyre::eyre::bail!(
+            "A function-call access key can only sign a single FunctionCall action, this transaction has to be signed with a full access key"
+        );
+    };
+    if permission.receiver_id != receiver_id.as_str() {
+        color_eyre::eyre::bail!(
+            "The function-call access key can only call <{}>, not <{receiver_id}>",
+            permission.receiver_id
+        );
+    }
+    if function_call_action.deposit > 0 {
+        color_eyre::eyre::bail!(
+            "A function-call access key cannot attach a deposit ({}) to the call",
+            crate::types::near_token::NearToken::from_yoctonear(function_call_action.deposit)
+        );
+    }
+    if !permission.method_names.is_empty()
+        && !permission
+            .method_names
+            .contains(&function_call_action.method_name)
+    {
+        color_eyre::eyre::bail!(
+            "The function-call access key can only call {}, not '{}'",
+            permission.method_names.join(", "),
+            function_call_action.method_name
+        );
+    }
+    Ok(())
+}
+
+/// The state of the receiver account that the actions are checked against.
+#[derive(Debug, Clone, Default)]
+pub struct ReceiverState {
+    pub access_keys: Vec<(
+        near_crypto::PublicKey,
+        near_primitives::account::AccessKeyPermission,
+    )>,
+    pub code_hash: near_primitives::hash::CryptoHash,
+}
+
+/// An action that does nothing or that will definitely fail.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActionIssue {
+    pub action_index: usize,
+    pub message: String,
+}
+
+/// The actions that do nothing or that fail against the state of the receiver account, as the
+/// actions are applied one after another. Without the state (offline, or the account is created
+/// by the transaction), only the actions themselves are checked.
+pub fn find_action_issues(
+    actions: &[Action],
+    receiver_state: Option<&ReceiverState>,
+) -> Vec<ActionIssue> {
+    let keys_are_known = receiver_state.is_some();
+    let mut access_keys: std::collections::HashMap<_, _> = receiver_state
+        .map(|receiver_state| receiver_state.access_keys.iter().cloned().collect())
+        .unwrap_or_default();
+    let mut deleted_keys = std::collections::HashSet::new();
+    let mut code_hash = receiver_state.map(|receiver_state| receiver_state.code_hash);
+
+    let mut issues = Vec::new();
+    for (action_index, action) in actions.iter().enumerate() {
+        let message = match action {
+            Action::Transfer(transfer_action) if transfer_action.deposit == 0 => {
+                Some("The transfer of 0 NEAR does nothing".to_string())
+            }
+            Action::AddKey(add_key_action) => {
+                let public_key = &add_key_action.public_key;
+                match access_keys.get(public_key) {
+                    Some(permission) if *permission == add_key_action.access_key.permission => {
+                        Some(format!(
+                            "The access key {public_key} already exists with the same permission (the transaction would fail with AddKeyAlreadyExists)"
+                        ))
+                    }
+                    Some(_) => Some(format!(
+                        "The access key {public_key} already exists with another permission, delete it first (the transaction would fail with AddKeyAlreadyExists)"
+                    )),
+                    None => {
+                        access_keys
+                            .insert(public_key.clone(), add_key_action.access_key.permission.clone());
+                        None
+                    }
+                }
+            }
+            Action::DeleteKey(delete_key_action) => {
+                let public_key = &delete_key_action.public_key;
+                let existed = access_keys.remove(public_key).is_some();
+                let newly_deleted = deleted_keys.insert(public_key.clone());
+                (!existed && (keys_are_known || !newly_deleted)).then(|| {
+                    format!(
+                        "The access key {public_key} does not exist (the transaction would fail with DeleteKeyDoesNotExist)"
+                    )
+                })
+            }
+            Action::DeployContract(deploy_contract_action) => {
+                let new_code_hash = near_primitives::hash::hash(&deploy_contract_action.code);
+                let message = (code_hash == Some(new_code_hash)).then(|| {
+                    format!("The code is already deployed (hash {new_code_hash}), the deployment does nothing")
+                });
+                code_hash = Some(new_code_hash);
+                message
+            }
+            _ => None,
+        };
+        if let Some(message) = message {
+            issues.push(ActionIssue {
+                action_index,
+                message,
+            });
+        }
+    }
+    issues
+}
+
+/// The nonce of the next transaction signed with an access key.
+pub fn next_nonce(
+    access_key_nonce: near_primitives::types::Nonce,
+) -> color_eyre::eyre::Result<near_primitives::types::Nonce> {
+    access_key_nonce.checked_add(1).ok_or_else(|| {
+        color_eyre::eyre::eyre!(
+            "The nonce of the access key has reached its maximum, sign with another access key"
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use near_primitives::transaction::{
+        AddKeyAction, DeleteAccountAction, DeleteKeyAction, DeployContractAction,
+        FunctionCallAction, StakeAction, TransferAction,
+    };
+
+    use super::*;
+
+    const ONE_NEAR: u128 = 10u128.pow(24);
+
+    fn transfer(deposit: u128) -> Action {
+        Action::Transfer(TransferAction { deposit })
+    }
+
+    fn function_call(method_name: &str, deposit: u128) -> Action {
+        Action::FunctionCall(Box::new(FunctionCallAction {
+            method_name: method_name.to_string(),
+            args: vec![],
+            gas: 30_000_000_000_000,
+            deposit,
+        }))
+    }
+
+    fn delete_account() -> Action {
+        Action::DeleteAccount(DeleteAccountAction {
+            beneficiary_id: "bob.near".parse().unwrap(),
+        })
+    }
+
+    fn public_key() -> near_crypto::PublicKey {
+        "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp"
+            .parse()
+            .unwrap()
+    }
+
+    fn function_call_permission(
+        method_names: &[&str],
+    ) -> near_primitives::account::AccessKeyPermission {
+        near_primitives::account::AccessKeyPermission::FunctionCall(
+            near_primitives::account::FunctionCallPermission {
+                allowance: Some(ONE_NEAR / 4),
+                receiver_id: "app.near".to_string(),
+                method_names: method_names.iter().map(ToString::to_string).collect(),
+            },
+        )
+    }
+
+    #[test]
+    fn actions_are_appended_in_order() {
+        let actions = append_action(vec![], transfer(1)).unwrap();
+        let actions = append_action(actions, function_call("a", 2)).unwrap();
+        let actions = append_action(actions, delete_account()).unwrap();
+        assert_eq!(
+            actions,
+            [transfer(1), function_call("a", 2), delete_account()]
+        );
+        let err = append_action(actions, transfer(3)).unwrap_err();
+        assert!(err.to_string().contains("after DeleteAccount"), "{err}");
+    }
+
+    #[test]
+    fn required_deposit_of_multi_action_batches() {
+        assert_eq!(compute_required_deposit(&[]).unwrap().as_yoctonear(), 0);
+        let actions = [
+            transfer(ONE_NEAR),
+            function_call("a", ONE_NEAR / 2),
+            Action::Stake(Box::new(StakeAction {
+                stake: 100 * ONE_NEAR,
+                public_key: public_key(),
+            })),
+            Action::DeleteKey(Box::new(DeleteKeyAction {
+                public_key: public_key(),
+            })),
+            transfer(1),
+        ];
+        assert_eq!(
+            compute_required_deposit(&actions).unwrap().as_yoctonear(),
+            ONE_NEAR + ONE_NEAR / 2 + 1
+        );
+    }
+
+    #[test]
+    fn required_deposit_overflow() {
+        let err =
+            compute_required_deposit(&[transfer(u128::MAX), function_call("a", 1)]).unwrap_err();
+        assert!(err.to_string().contains("overflows"), "{err}");
+        assert!(validate_actions(&[transfer(u128::MAX), transfer(1)]).is_err());
+        assert!(compute_required_deposit(&[transfer(u128::MAX)]).is_ok());
+    }
+
+    #[test]
+    fn delete_account_must_be_last() {
+        assert!(validate_actions(&[transfer(1), delete_account()]).is_ok());
+        assert!(validate_actions(&[delete_account()]).is_ok());
+        let err = validate_actions(&[delete_account(), transfer(1)]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "DeleteAccount must be the last action of a transaction, but it is action #1 of 2"
+        );
+        assert!(validate_actions(&[delete_account(), delete_account()]).is_err());
+    }
+
+    #[test]
+    fn full_access_keys_can_sign_anything() {
+        let receiver_id: near_primitives::types::AccountId = "bob.near".parse().unwrap();
+        assert!(validate_actions_for_key(
+            &near_primitives::account::AccessKeyPermission::FullAccess,
+            &receiver_id,
+            &[
+                transfer(ONE_NEAR),
+                function_call("a", ONE_NEAR),
+                delete_account()
+            ],
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn function_call_keys_restrictions() {
+        let app: near_primitives::types::AccountId = "app.near".parse().unwrap();
+        let permission = function_call_permission(&["vote", "unvote"]);
+        assert!(validate_actions_for_key(&permission, &app, &[function_call("vote", 0)]).is_ok());
+        assert!(validate_actions_for_key(
+            &function_call_permission(&[]),
+            &app,
+            &[function_call("anything", 0)]
+        )
+        .is_ok());
+
+        let error = |receiver_id: &str, actions: &[Action]| {
+            validate_actions_for_key(&permission, &receiver_id.parse().unwrap(), actions)
+                .unwrap_err()
+                .to_string()
+        };
+        assert!(error("app.near", &[transfer(1)]).contains("full access key"));
+        assert!(error(
+            "app.near",
+            &[function_call("vote", 0), function_call("vote", 0)]
+        )
+        .contains("single FunctionCall action"));
+        assert!(error("app.near", &[]).contains("single FunctionCall action"));
+        assert_eq!(
+            error("other.near", &[function_call("vote", 0)]),
+            "The function-call access key can only call <app.near>, not <other.near>"
+        );
+        assert!(error("app.near", &[function_call("vote", 1)]).contains("cannot attach a deposit"));
+        assert_eq!(
+            error("app.near", &[function_call("withdraw", 0)]),
+            "The function-call access key can only call vote, unvote, not 'withdraw'"
+        );
+    }
+
+    fn add_key(permission: near_primitives::account::AccessKeyPermission) -> Action {
+        Action::AddKey(Box::new(AddKeyAction {
+            public_key: public_key(),
+            access_key: near_primitives::account::AccessKey {
+                nonce: 0,
+                permission,
+            },
+        }))
+    }
+
+    fn delete_key() -> Action {
+        Action::DeleteKey(Box::new(DeleteKeyAction {
+            public_key: public_key(),
+        }))
+    }
+
+    fn issue_indexes(actions: &[Action], receiver_state: Option<&ReceiverState>) -> Vec<usize> {
+        find_action_issues(actions, receiver_state)
+            .into_iter()
+            .map(|issue| issue.action_index)
+            .collect()
+    }
+
+    #[test]
+    fn action_issues_against_the_receiver_state() {
+        let full_access = near_primitives::account::AccessKeyPermission::FullAccess;
+        let code = vec![0, 97, 115, 109];
+        let receiver_state = ReceiverState {
+            access_keys: vec![(public_key(), full_access.clone())],
+            code_hash: near_primitives::hash::hash(&code),
+        };
+
+        let issues = find_action_issues(
+            &[transfer(0), add_key(full_access.clone())],
+            Some(&receiver_state),
+        );
+        assert_eq!(issues.len(), 2);
+        assert!(issues[0].message.contains("0 NEAR"), "{issues:?}");
+        assert!(issues[1].message.contains("same permission"), "{issues:?}");
+        assert!(find_action_issues(
+            &[add_key(function_call_permission(&[]))],
+            Some(&receiver_state)
+        )[0]
+        .message
+        .contains("another permission"));
+        assert_eq!(
+            issue_indexes(
+                &[
+                    Action::DeployContract(DeployContractAction { code: code.clone() }),
+                    Action::DeployContract(DeployContractAction { code: vec![1] }),
+                ],
+                Some(&receiver_state)
+            ),
+            [0]
+        );
+        // The key is deleted and then added again
+        assert!(issue_indexes(
+            &[delete_key(), add_key(full_access.clone()), transfer(1)],
+            Some(&receiver_state)
+        )
+        .is_empty());
+        assert_eq!(
+            issue_indexes(
+                &[delete_key(), delete_key()],
+                Some(&ReceiverState::default())
+            ),
+            [0, 1]
+        );
+    }
+
+    #[test]
+    fn action_issues_without_the_receiver_state() {
+        let full_access = near_primitives::account::AccessKeyPermission::FullAccess;
+        assert!(issue_indexes(&[delete_key(), add_key(full_access.clone())], None).is_empty());
+        assert_eq!(
+            issue_indexes(
+                &[
+                    add_key(full_access.clone()),
+                    add_key(full_access),
+                    delete_key(),
+                    delete_key(),
+                    transfer(0),
+                ],
+                None
+            ),
+            [1, 3, 4]
+        );
+        assert!(issue_indexes(
+            &[Action::DeployContract(DeployContractAction {
+                code: vec![1]
+            })],
+            None
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn nonce_handling() {
+        assert_eq!(next_nonce(0).unwrap(), 1);
+        assert_eq!(next_nonce(41).unwrap(), 42);
+        assert!(next_nonce(u64::MAX).is_err());
+    }
+}
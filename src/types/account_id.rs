@@ -21,15 +21,99 @@ impl std::fmt::Display for AccountId {
     }
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum ParseAccountIdError {
+    #[error("{0}\nAn account ID is 2-64 lowercase letters, digits and the separators `-`, `_` and `.` (e.g. alice.near or app.alice.testnet), a 64-character hex implicit account ID or an ETH address (0x followed by 40 hex digits)")]
+    InvalidAccountId(#[from] near_primitives::account::id::ParseAccountError),
+    #[error("{0}")]
+    InvalidEthAddress(String),
+}
+
 impl std::str::FromStr for AccountId {
-    type Err = <near_primitives::types::AccountId as std::str::FromStr>::Err;
+    type Err = ParseAccountIdError;
 
     fn from_str(account_id: &str) -> Result<Self, Self::Err> {
-        let account_id = near_primitives::types::AccountId::from_str(account_id)?;
+        let account_id = match normalize_eth_address(account_id)
+            .map_err(ParseAccountIdError::InvalidEthAddress)?
+        {
+            Some(eth_implicit_account_id) => {
+                near_primitives::types::AccountId::from_str(&eth_implicit_account_id)?
+            }
+            None => near_primitives::types::AccountId::from_str(account_id)?,
+        };
         Ok(Self(account_id))
     }
 }
 
+/// ETH addresses are usually written with the EIP-55 checksum (mixed case), while the
+/// eth-implicit account IDs are lowercase. Returns the account ID for `0x` + 40 hex digits and
+/// rejects the addresses with a wrong checksum or with one or two hex digits too many or too few.
+fn normalize_eth_address(account_id: &str) -> Result<Option<String>, String> {
+    let Some(hex_digits) = account_id.strip_prefix("0x") else {
+        return Ok(None);
+    };
+    if hex_digits.is_empty() || !hex_digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Ok(None);
+    }
+    match hex_digits.len() {
+        40 => {}
+        38..=42 => {
+            return Err(format!(
+                "<{account_id}> looks like an ETH address, but it has {} hex digits instead of 40",
+                hex_digits.len()
+            ))
+        }
+        _ => return Ok(None),
+    }
+    let eth_implicit_account_id = format!("0x{}", hex_digits.to_ascii_lowercase());
+    let is_mixed_case = hex_digits.chars().any(|c| c.is_ascii_uppercase())
+        && hex_digits.chars().any(|c| c.is_ascii_lowercase());
+    if is_mixed_case {
+        let checksummed_address = eth_checksummed_address(&eth_implicit_account_id);
+        if checksummed_address != account_id {
+            return Err(format!(
+                "<{account_id}> has an invalid ETH address checksum (expected {checksummed_address})"
+            ));
+        }
+    }
+    Ok(Some(eth_implicit_account_id))
+}
+
+/// The EIP-55 checksummed address of a lowercase `0x` + 40 hex digits account ID.
+fn eth_checksummed_address(eth_implicit_account_id: &str) -> String {
+    use sha3::Digest;
+
+    let hex_digits = eth_implicit_account_id.trim_start_matches("0x");
+    let hash = sha3::Keccak256::digest(hex_digits.as_bytes());
+    let checksummed_hex_digits: String = hex_digits
+        .chars()
+        .enumerate()
+        .map(|(index, c)| {
+            let nibble = if index % 2 == 0 {
+                hash[index / 2] >> 4
+            } else {
+                hash[index / 2] & 0x0f
+            };
+            if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect();
+    format!("0x{checksummed_hex_digits}")
+}
+
+/// The checksummed `0x` address of an eth-implicit account (`None` for other accounts).
+pub fn eth_address(account_id: &near_primitives::types::AccountId) -> Option<String> {
+    match account_id.get_account_type() {
+        near_primitives::account::id::AccountType::EthImplicitAccount => {
+            Some(eth_checksummed_address(account_id.as_str()))
+        }
+        _ => None,
+    }
+}
+
 impl AsRef<str> for AccountId {
     fn as_ref(&self) -> &str {
         self.0.as_ref()
@@ -53,3 +137,42 @@ impl AccountId {
         Self::from_str(owner_account_id).unwrap()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_eth_implicit_account_id() {
+        // EIP-55 test vectors
+        for address in [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ] {
+            let account_id = AccountId::from_str(address).unwrap();
+            assert_eq!(account_id.to_string(), address.to_ascii_lowercase());
+            assert_eq!(eth_address(&account_id.0).as_deref(), Some(address));
+        }
+        assert!(AccountId::from_str("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").is_ok());
+        assert!(AccountId::from_str("0x5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED").is_ok());
+
+        // wrong checksum
+        assert!(AccountId::from_str("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD").is_err());
+        // 39 and 41 hex digits
+        assert!(AccountId::from_str("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beae").is_err());
+        assert!(AccountId::from_str("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed0").is_err());
+
+        assert!(eth_address(&AccountId::from_str("alice.near").unwrap().0).is_none());
+    }
+
+    #[test]
+    fn invalid_account_id_error_has_the_format_rules() {
+        let err = AccountId::from_str("Alice.near").unwrap_err().to_string();
+        assert!(
+            err.contains("An account ID is 2-64 lowercase letters"),
+            "{err}"
+        );
+    }
+}
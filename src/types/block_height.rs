@@ -0,0 +1,45 @@
+const EXAMPLE_BLOCK_HEIGHT: &str = "137000000";
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BlockHeight(pub near_primitives::types::BlockHeight);
+
+impl From<BlockHeight> for near_primitives::types::BlockHeight {
+    fn from(item: BlockHeight) -> Self {
+        item.0
+    }
+}
+
+impl std::fmt::Display for BlockHeight {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::str::FromStr for BlockHeight {
+    type Err = color_eyre::eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let block_height = s.trim().parse().map_err(|err| {
+            color_eyre::eyre::eyre!("'{s}' is not a block height: {err}\nA block height is a non-negative integer, e.g. {EXAMPLE_BLOCK_HEIGHT} (as shown by the explorers for blocks)")
+        })?;
+        Ok(Self(block_height))
+    }
+}
+
+impl interactive_clap::ToCli for BlockHeight {
+    type CliVariant = BlockHeight;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn invalid_block_height_error_has_a_valid_example() {
+        assert!(BlockHeight::from_str(EXAMPLE_BLOCK_HEIGHT).is_ok());
+        let err = BlockHeight::from_str("abc").unwrap_err().to_string();
+        assert!(err.contains(EXAMPLE_BLOCK_HEIGHT), "{err}");
+    }
+}
@@ -0,0 +1,190 @@
+use color_eyre::eyre::{ContextCompat, WrapErr};
+
+use crate::common::JsonRpcClientExt;
+
+/// Blocks are produced at least every 0.5 seconds, which bounds the height of a block at a given time.
+const MIN_BLOCK_TIME_NANOSEC: u64 = 500_000_000;
+/// Heights without a block are rare and short, a longer gap is treated as the end of the search range.
+const MAX_SKIPPED_HEIGHTS: u64 = 10;
+
+/// A block selected by height (`123456`), hash (base58), timestamp (`timestamp:<unix seconds>`)
+/// or the final block (`final`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockSelector {
+    Final,
+    Height(near_primitives::types::BlockHeight),
+    Hash(near_primitives::hash::CryptoHash),
+    /// Unix timestamp in seconds; the last block produced at or before it is selected
+    Timestamp(u64),
+}
+
+impl std::fmt::Display for BlockSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BlockSelector::Final => write!(f, "final"),
+            BlockSelector::Height(height) => write!(f, "{height}"),
+            BlockSelector::Hash(hash) => write!(f, "{hash}"),
+            BlockSelector::Timestamp(timestamp) => write!(f, "timestamp:{timestamp}"),
+        }
+    }
+}
+
+impl std::str::FromStr for BlockSelector {
+    type Err = color_eyre::eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s == "final" {
+            return Ok(BlockSelector::Final);
+        }
+        if let Some(timestamp) = s.strip_prefix("timestamp:") {
+            return Ok(BlockSelector::Timestamp(timestamp.parse().wrap_err(
+                "The timestamp must be the number of seconds since the Unix epoch",
+            )?));
+        }
+        if let Ok(height) = s.parse() {
+            return Ok(BlockSelector::Height(height));
+        }
+        near_primitives::hash::CryptoHash::from_str(s)
+            .map(BlockSelector::Hash)
+            .map_err(|_| {
+                color_eyre::eyre::eyre!(
+                    "Expected a block height, a block hash, \"timestamp:<unix seconds>\" or \"final\", got: {s}"
+                )
+            })
+    }
+}
+
+impl interactive_clap::ToCli for BlockSelector {
+    type CliVariant = BlockSelector;
+}
+
+impl BlockSelector {
+    pub fn to_block_reference(
+        self,
+        network_config: &crate::config::NetworkConfig,
+    ) -> color_eyre::eyre::Result<near_primitives::types::BlockReference> {
+        Ok(match self {
+            BlockSelector::Final => near_primitives::types::Finality::Final.into(),
+            BlockSelector::Height(height) => near_primitives::types::BlockReference::BlockId(
+                near_primitives::types::BlockId::Height(height),
+            ),
+            BlockSelector::Hash(hash) => near_primitives::types::BlockReference::BlockId(
+                near_primitives::types::BlockId::Hash(hash),
+            ),
+            BlockSelector::Timestamp(timestamp) => near_primitives::types::BlockReference::BlockId(
+                near_primitives::types::BlockId::Height(get_block_height_at_timestamp(
+                    network_config,
+                    timestamp.saturating_mul(1_000_000_000),
+                )?),
+            ),
+        })
+    }
+}
+
+/// Binary search of the last block produced at or before the timestamp.
+#[tracing::instrument(name = "Searching for the block at the timestamp ...", skip_all)]
+fn get_block_height_at_timestamp(
+    network_config: &crate::config::NetworkConfig,
+    timestamp_nanosec: u64,
+) -> color_eyre::eyre::Result<near_primitives::types::BlockHeight> {
+    let json_rpc_client = network_config.json_rpc_client();
+    let final_block = get_block_header(
+        &json_rpc_client,
+        near_primitives::types::Finality::Final.into(),
+    )?
+    .wrap_err("Failed to fetch the final block")?;
+    if final_block.timestamp_nanosec <= timestamp_nanosec {
+        return Ok(final_block.height);
+    }
+
+    let mut high = final_block.height;
+    let max_blocks_since_timestamp =
+        (final_block.timestamp_nanosec - timestamp_nanosec) / MIN_BLOCK_TIME_NANOSEC + 1;
+    let mut low = final_block
+        .height
+        .saturating_sub(max_blocks_since_timestamp);
+    let low_block = find_block_header_from_height(&json_rpc_client, low, high)?;
+    match low_block {
+        Some(low_block) if low_block.timestamp_nanosec <= timestamp_nanosec => {
+            low = low_block.height
+        }
+        _ => color_eyre::eyre::bail!(
+            "There are no blocks at the timestamp on network <{}> (is it before the genesis or the history available on the RPC server?)",
+            network_config.network_name
+        ),
+    }
+
+    while high - low > 1 {
+        let middle = low + (high - low) / 2;
+        match find_block_header_from_height(&json_rpc_client, middle, high)? {
+            Some(block) if block.timestamp_nanosec <= timestamp_nanosec => low = block.height,
+            _ => high = middle,
+        }
+    }
+    Ok(low)
+}
+
+/// The first block at or above the height and below `end_height` (some heights are skipped).
+fn find_block_header_from_height(
+    json_rpc_client: &near_jsonrpc_client::JsonRpcClient,
+    height: near_primitives::types::BlockHeight,
+    end_height: near_primitives::types::BlockHeight,
+) -> color_eyre::eyre::Result<Option<near_primitives::views::BlockHeaderView>> {
+    for height in height..end_height.min(height + MAX_SKIPPED_HEIGHTS) {
+        if let Some(block_header) = get_block_header(
+            json_rpc_client,
+            near_primitives::types::BlockReference::BlockId(
+                near_primitives::types::BlockId::Height(height),
+            ),
+        )? {
+            return Ok(Some(block_header));
+        }
+    }
+    Ok(None)
+}
+
+fn get_block_header(
+    json_rpc_client: &near_jsonrpc_client::JsonRpcClient,
+    block_reference: near_primitives::types::BlockReference,
+) -> color_eyre::eyre::Result<Option<near_primitives::views::BlockHeaderView>> {
+    match json_rpc_client
+        .blocking_call(near_jsonrpc_client::methods::block::RpcBlockRequest { block_reference })
+    {
+        Ok(block_view) => Ok(Some(block_view.header)),
+        Err(near_jsonrpc_client::errors::JsonRpcError::ServerError(
+            near_jsonrpc_client::errors::JsonRpcServerError::HandlerError(
+                near_jsonrpc_primitives::types::blocks::RpcBlockError::UnknownBlock { .. },
+            ),
+        )) => Ok(None),
+        Err(err) => Err(err).wrap_err("Failed to fetch the block"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_block_selector() {
+        assert_eq!(
+            "final".parse::<BlockSelector>().unwrap(),
+            BlockSelector::Final
+        );
+        assert_eq!(
+            "123456".parse::<BlockSelector>().unwrap(),
+            BlockSelector::Height(123456)
+        );
+        assert_eq!(
+            "timestamp:1700000000".parse::<BlockSelector>().unwrap(),
+            BlockSelector::Timestamp(1700000000)
+        );
+        let hash = near_primitives::hash::CryptoHash::hash_bytes(b"block");
+        assert_eq!(
+            hash.to_string().parse::<BlockSelector>().unwrap(),
+            BlockSelector::Hash(hash)
+        );
+        assert!("timestamp:yesterday".parse::<BlockSelector>().is_err());
+        assert!("latest".parse::<BlockSelector>().is_err());
+    }
+}
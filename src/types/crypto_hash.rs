@@ -1,3 +1,5 @@
+const EXAMPLE_CRYPTO_HASH: &str = "7Nb6kSTDHhbTWDdzD5EHyBxmDWVZmYRwzGbjmGC3ARm3";
+
 #[derive(Debug, Copy, Clone)]
 pub struct CryptoHash(pub near_primitives::hash::CryptoHash);
 
@@ -17,8 +19,9 @@ impl std::str::FromStr for CryptoHash {
     type Err = color_eyre::eyre::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let crypto_hash = near_primitives::hash::CryptoHash::from_str(s)
-            .map_err(color_eyre::eyre::Report::msg)?;
+        let crypto_hash = near_primitives::hash::CryptoHash::from_str(s).map_err(|err| {
+            color_eyre::eyre::eyre!("{err}\nA hash is 32 bytes in base58, e.g. {EXAMPLE_CRYPTO_HASH} (as shown by the explorers for transactions and blocks)")
+        })?;
         Ok(Self(crypto_hash))
     }
 }
@@ -26,3 +29,17 @@ impl std::str::FromStr for CryptoHash {
 impl interactive_clap::ToCli for CryptoHash {
     type CliVariant = CryptoHash;
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn invalid_crypto_hash_error_has_a_valid_example() {
+        assert!(CryptoHash::from_str(EXAMPLE_CRYPTO_HASH).is_ok());
+        let err = CryptoHash::from_str("abc").unwrap_err().to_string();
+        assert!(err.contains(EXAMPLE_CRYPTO_HASH), "{err}");
+    }
+}
@@ -0,0 +1,89 @@
+/// A duration with a unit: `500ms`, `90s`, `2m` or `1h` (a bare number is in seconds)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::From, derive_more::Into)]
+pub struct Duration(pub std::time::Duration);
+
+impl std::fmt::Display for Duration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let millis = self.0.as_millis();
+        if millis % 1_000 != 0 {
+            write!(f, "{millis}ms")
+        } else if millis % 3_600_000 == 0 && millis > 0 {
+            write!(f, "{}h", millis / 3_600_000)
+        } else if millis % 60_000 == 0 && millis > 0 {
+            write!(f, "{}m", millis / 60_000)
+        } else {
+            write!(f, "{}s", millis / 1_000)
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Invalid duration '{0}': expected a number with the unit ms, s, m or h, e.g. 90s or 2m")]
+pub struct ParseDurationError(String);
+
+impl std::str::FromStr for Duration {
+    type Err = ParseDurationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let unit_start = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+        let (number, unit) = s.split_at(unit_start);
+        let number: u64 = number
+            .parse()
+            .map_err(|_| ParseDurationError(s.to_string()))?;
+        let duration = match unit.trim() {
+            "ms" => std::time::Duration::from_millis(number),
+            "" | "s" => std::time::Duration::from_secs(number),
+            "m" => std::time::Duration::from_secs(number.saturating_mul(60)),
+            "h" => std::time::Duration::from_secs(number.saturating_mul(3_600)),
+            _ => return Err(ParseDurationError(s.to_string())),
+        };
+        Ok(Self(duration))
+    }
+}
+
+impl serde::Serialize for Duration {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Duration {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+impl interactive_clap::ToCli for Duration {
+    type CliVariant = Duration;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duration_from_str_and_to_string() {
+        for (input, seconds, output) in [
+            ("90s", 90, "90s"),
+            ("90", 90, "90s"),
+            ("2m", 120, "2m"),
+            ("120s", 120, "2m"),
+            ("1h", 3_600, "1h"),
+        ] {
+            let duration: Duration = input.parse().unwrap();
+            assert_eq!(
+                duration.0,
+                std::time::Duration::from_secs(seconds),
+                "{input}"
+            );
+            assert_eq!(duration.to_string(), output);
+        }
+        assert_eq!("1500ms".parse::<Duration>().unwrap().to_string(), "1500ms");
+        for invalid in ["", "s", "1.5s", "10d", "-1s"] {
+            assert!(invalid.parse::<Duration>().is_err(), "{invalid}");
+        }
+    }
+}
@@ -1,12 +1,16 @@
 pub mod account_id;
 pub mod api_key;
 pub mod base64_bytes;
+pub mod block_height;
+pub mod block_selector;
 pub mod crypto_hash;
+pub mod duration;
 pub mod file_bytes;
 pub mod ft_properties;
 pub mod json;
 pub mod near_allowance;
 pub mod near_token;
+pub mod nonce;
 pub mod path_buf;
 pub mod public_key;
 pub mod public_key_list;
@@ -16,5 +20,6 @@ pub mod signed_delegate_action;
 pub mod signed_transaction;
 pub mod slip10;
 pub mod transaction;
+pub mod transfer_amount;
 pub mod url;
 pub mod vec_string;
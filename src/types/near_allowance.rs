@@ -23,8 +23,12 @@ impl std::fmt::Display for NearAllowance {
     }
 }
 
+#[derive(Debug, thiserror::Error)]
+#[error("{0}, or {UNLIMITED}")]
+pub struct ParseNearAllowanceError(#[from] crate::types::near_token::ParseNearTokenError);
+
 impl std::str::FromStr for NearAllowance {
-    type Err = near_token::NearTokenError;
+    type Err = ParseNearAllowanceError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if s == UNLIMITED {
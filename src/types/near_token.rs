@@ -14,11 +14,22 @@ const ONE_NEAR: u128 = 10u128.pow(24);
     derive_more::AsRef,
     derive_more::From,
     derive_more::Into,
-    derive_more::FromStr,
 )]
 #[as_ref(forward)]
 pub struct NearToken(pub near_token::NearToken);
 
+#[derive(Debug, thiserror::Error)]
+#[error("{0}\nAn amount is a number with the unit NEAR or yoctoNEAR, e.g. 10NEAR, 0.5 near or 10000yoctonear")]
+pub struct ParseNearTokenError(#[from] near_token::NearTokenError);
+
+impl std::str::FromStr for NearToken {
+    type Err = ParseNearTokenError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(near_token::NearToken::from_str(s)?))
+    }
+}
+
 impl std::fmt::Display for NearToken {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if self.as_yoctonear() == 0 {
@@ -50,6 +61,60 @@ impl NearToken {
     pub const fn is_zero(&self) -> bool {
         self.0.is_zero()
     }
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(Self)
+    }
+
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.0.checked_sub(other.0).map(Self)
+    }
+
+    pub fn saturating_add(self, other: Self) -> Self {
+        Self(self.0.saturating_add(other.0))
+    }
+
+    pub fn saturating_sub(self, other: Self) -> Self {
+        Self(self.0.saturating_sub(other.0))
+    }
+}
+
+impl std::ops::Add for NearToken {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.checked_add(other)
+            .expect("attempt to add NEAR amounts with overflow")
+    }
+}
+
+impl std::ops::Sub for NearToken {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.checked_sub(other)
+            .expect("attempt to subtract NEAR amounts with overflow")
+    }
+}
+
+impl std::ops::Mul<u64> for NearToken {
+    type Output = Self;
+
+    fn mul(self, rhs: u64) -> Self {
+        Self::from_yoctonear(
+            self.as_yoctonear()
+                .checked_mul(u128::from(rhs))
+                .expect("attempt to multiply NEAR amount with overflow"),
+        )
+    }
+}
+
+impl std::ops::Div<u64> for NearToken {
+    type Output = Self;
+
+    fn div(self, rhs: u64) -> Self {
+        Self::from_yoctonear(self.as_yoctonear() / u128::from(rhs))
+    }
 }
 
 impl interactive_clap::ToCli for NearToken {
@@ -61,6 +126,15 @@ mod tests {
     use super::*;
     use std::str::FromStr;
 
+    #[test]
+    fn invalid_near_token_error_has_examples() {
+        let err = NearToken::from_str("10").unwrap_err().to_string();
+        assert!(
+            err.ends_with("e.g. 10NEAR, 0.5 near or 10000yoctonear"),
+            "{err}"
+        );
+    }
+
     #[test]
     fn near_token_to_string_0_near() {
         assert_eq!(
@@ -68,6 +142,7 @@ mod tests {
             "0 NEAR".to_string()
         )
     }
+
     #[test]
     fn near_token_to_string_0_millinear() {
         assert_eq!(
@@ -75,6 +150,7 @@ mod tests {
             "0 NEAR".to_string()
         )
     }
+
     #[test]
     fn near_token_to_string_0_yoctonear() {
         assert_eq!(
@@ -93,6 +169,7 @@ mod tests {
             "0.02 NEAR".to_string()
         )
     }
+
     #[test]
     fn near_token_to_string_0dot00001230045600789_near() {
         assert_eq!(
@@ -104,6 +181,7 @@ mod tests {
             "0.00001230045600789 NEAR".to_string()
         )
     }
+
     #[test]
     fn near_token_to_string_10_near() {
         assert_eq!(
@@ -114,6 +192,7 @@ mod tests {
             "10 NEAR".to_string()
         )
     }
+
     #[test]
     fn near_token_to_string_10dot02_000_01near() {
         assert_eq!(
@@ -124,6 +203,7 @@ mod tests {
             "10.020000000000000000000001 NEAR".to_string()
         )
     }
+
     #[test]
     fn near_token_to_string_1_yocto_near() {
         assert_eq!(
@@ -131,6 +211,7 @@ mod tests {
             "1 yoctoNEAR".to_string()
         )
     }
+
     #[test]
     fn near_token_to_string_100_yocto_near() {
         assert_eq!(
@@ -138,4 +219,33 @@ mod tests {
             "100 yoctoNEAR".to_string()
         )
     }
+
+    #[test]
+    fn near_token_arithmetic() {
+        let one_near = NearToken(near_token::NearToken::from_near(1));
+        let half_near = NearToken(near_token::NearToken::from_millinear(500));
+        assert_eq!(half_near + half_near, one_near);
+        assert_eq!(one_near - half_near, half_near);
+        assert_eq!(half_near * 2, one_near);
+        assert_eq!(one_near / 2, half_near);
+    }
+
+    #[test]
+    fn near_token_checked_and_saturating_arithmetic() {
+        let max = NearToken::from_yoctonear(u128::MAX);
+        let one_yocto = NearToken::from_yoctonear(1);
+        assert_eq!(max.checked_add(one_yocto), None);
+        assert_eq!(NearToken::default().checked_sub(one_yocto), None);
+        assert_eq!(max.saturating_add(one_yocto), max);
+        assert_eq!(
+            NearToken::default().saturating_sub(one_yocto),
+            NearToken::default()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn near_token_sub_overflow() {
+        let _ = NearToken::default() - NearToken::from_yoctonear(1);
+    }
 }
@@ -0,0 +1,45 @@
+const EXAMPLE_NONCE: &str = "113514442000001";
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Nonce(pub near_primitives::types::Nonce);
+
+impl From<Nonce> for near_primitives::types::Nonce {
+    fn from(item: Nonce) -> Self {
+        item.0
+    }
+}
+
+impl std::fmt::Display for Nonce {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::str::FromStr for Nonce {
+    type Err = color_eyre::eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let nonce = s.trim().parse().map_err(|err| {
+            color_eyre::eyre::eyre!("'{s}' is not a nonce: {err}\nA nonce is a non-negative integer, e.g. {EXAMPLE_NONCE} (`near account list-keys` shows the current nonce of each access key)")
+        })?;
+        Ok(Self(nonce))
+    }
+}
+
+impl interactive_clap::ToCli for Nonce {
+    type CliVariant = Nonce;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn invalid_nonce_error_has_a_valid_example() {
+        assert!(Nonce::from_str(EXAMPLE_NONCE).is_ok());
+        let err = Nonce::from_str("abc").unwrap_err().to_string();
+        assert!(err.contains(EXAMPLE_NONCE), "{err}");
+    }
+}
@@ -7,8 +7,12 @@ impl std::fmt::Display for PublicKey {
     }
 }
 
+#[derive(Debug, thiserror::Error)]
+#[error("{0}\nA public key is ed25519:<base58> or secp256k1:<base58>, e.g. ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp (generate a key pair with `near account create-account fund-later use-auto-generation save-to-folder <folder>`)")]
+pub struct ParsePublicKeyError(#[from] near_crypto::ParseKeyError);
+
 impl std::str::FromStr for PublicKey {
-    type Err = near_crypto::ParseKeyError;
+    type Err = ParsePublicKeyError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let public_key = near_crypto::PublicKey::from_str(s)?;
@@ -31,3 +35,22 @@ impl From<near_crypto::PublicKey> for PublicKey {
 impl interactive_clap::ToCli for PublicKey {
     type CliVariant = PublicKey;
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn invalid_public_key_error_has_an_example() {
+        let err = PublicKey::from_str("ed25519:abc").unwrap_err().to_string();
+        assert!(
+            err.contains("e.g. ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp"),
+            "{err}"
+        );
+        assert!(
+            PublicKey::from_str("ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp").is_ok()
+        );
+    }
+}
@@ -0,0 +1,61 @@
+const ALL: &str = "all";
+
+/// The NEAR to transfer: an amount, or `all` (everything the account can spend, see
+/// `send_near::send_all`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferAmount {
+    Exact(crate::types::near_token::NearToken),
+    All,
+}
+
+impl std::fmt::Display for TransferAmount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Exact(amount) => amount.fmt(f),
+            Self::All => write!(f, "{ALL}"),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("{0}, or {ALL}")]
+pub struct ParseTransferAmountError(#[from] crate::types::near_token::ParseNearTokenError);
+
+impl std::str::FromStr for TransferAmount {
+    type Err = ParseTransferAmountError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().eq_ignore_ascii_case(ALL) {
+            return Ok(Self::All);
+        }
+        Ok(Self::Exact(crate::types::near_token::NearToken::from_str(
+            s,
+        )?))
+    }
+}
+
+impl interactive_clap::ToCli for TransferAmount {
+    type CliVariant = TransferAmount;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn transfer_amount_from_str() {
+        assert_eq!(
+            TransferAmount::from_str("all").unwrap(),
+            TransferAmount::All
+        );
+        assert_eq!(TransferAmount::from_str("ALL").unwrap().to_string(), "all");
+        assert_eq!(
+            TransferAmount::from_str("0.5 NEAR").unwrap(),
+            TransferAmount::Exact(crate::types::near_token::NearToken::from_yoctonear(
+                5 * 10u128.pow(23)
+            ))
+        );
+        assert!(TransferAmount::from_str("everything").is_err());
+    }
+}
@@ -0,0 +1,494 @@
+//! Reading view results that are too large for one request.
+//!
+//! The RPC nodes refuse to return a contract state over their size limit, and a view call fails
+//! when its result exceeds the returned value limit (4 MiB) or the gas limit of view calls. When
+//! that happens the request is narrowed and retried: SocialDB key wildcards are split into their
+//! children, NFT lists are paginated with a smaller `limit` and contract states are split by the
+//! next byte of the key prefix. All the narrowed requests are made at the same block, and the
+//! partial results are merged.
+use color_eyre::eyre::WrapErr;
+use tracing_indicatif::span_ext::IndicatifSpanExt;
+
+use crate::common::{CallResultExt, JsonRpcClientExt};
+
+/// The page size to start with when a result has to be split (and --max-page-size is not set)
+pub const DEFAULT_PAGE_SIZE: u64 = 50;
+
+/// Whether the request failed because its result is too large, so that a narrower request may
+/// succeed.
+pub fn is_result_too_large(err: &color_eyre::eyre::Report) -> bool {
+    err.chain().any(|cause| {
+        matches!(
+            cause.downcast_ref(),
+            Some(near_jsonrpc_client::errors::JsonRpcError::ServerError(
+                near_jsonrpc_client::errors::JsonRpcServerError::HandlerError(
+                    near_jsonrpc_primitives::types::query::RpcQueryError::TooLargeContractState { .. }
+                )
+            ))
+        ) || is_too_large_message(&cause.to_string())
+    })
+}
+
+fn is_too_large_message(message: &str) -> bool {
+    let message = message.to_lowercase();
+    [
+        "too large",
+        "returnedvaluelengthexceeded",
+        "gaslimitexceeded",
+        "exceeded the maximum amount of gas",
+        "response size",
+    ]
+    .iter()
+    .any(|marker| message.contains(marker))
+}
+
+/// Pins the block (e.g. `final`) so that all the narrowed requests read the same state.
+pub fn pin_block_reference(
+    network_config: &crate::config::NetworkConfig,
+    block_reference: &near_primitives::types::BlockReference,
+) -> color_eyre::eyre::Result<near_primitives::types::BlockReference> {
+    if let near_primitives::types::BlockReference::BlockId(_) = block_reference {
+        return Ok(block_reference.clone());
+    }
+    let block = network_config
+        .json_rpc_client()
+        .blocking_call(near_jsonrpc_client::methods::block::RpcBlockRequest {
+            block_reference: block_reference.clone(),
+        })
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch the block to read from on network <{}>",
+                network_config.network_name
+            )
+        })?;
+    Ok(near_primitives::types::BlockId::Hash(block.header.hash).into())
+}
+
+fn print_pagination_note(requests: usize) {
+    eprintln!(
+        "\nNote: the result was paginated, it was fetched with {requests} requests and merged."
+    );
+}
+
+/// Merges `source` into `target`: the objects are merged key by key, any other value replaces
+/// the previous one.
+pub fn merge_json(target: &mut serde_json::Value, source: serde_json::Value) {
+    match (target, source) {
+        (serde_json::Value::Object(target), serde_json::Value::Object(source)) => {
+            for (key, value) in source {
+                match target.get_mut(&key) {
+                    Some(existing) => merge_json(existing, value),
+                    None => {
+                        target.insert(key, value);
+                    }
+                }
+            }
+        }
+        (target, source) => *target = source,
+    }
+}
+
+/// Fetches the items page by page: `fetch_page(from_index, limit)` returns the page, or `None`
+/// when the page is too large (then the page size is halved). Returns the items and the number
+/// of requests made.
+pub fn paginate<T>(
+    page_size: u64,
+    mut fetch_page: impl FnMut(u64, u64) -> color_eyre::eyre::Result<Option<Vec<T>>>,
+) -> color_eyre::eyre::Result<(Vec<T>, usize)> {
+    let mut items = Vec::new();
+    let mut page_size = page_size.max(1);
+    let mut requests = 0;
+    loop {
+        requests += 1;
+        let from_index = u64::try_from(items.len())?;
+        match fetch_page(from_index, page_size)? {
+            Some(page) => {
+                let page_len = u64::try_from(page.len())?;
+                items.extend(page);
+                if page_len < page_size {
+                    return Ok((items, requests));
+                }
+            }
+            None if page_size > 1 => page_size /= 2,
+            None => {
+                color_eyre::eyre::bail!("The item #{from_index} alone is too large to be fetched")
+            }
+        }
+    }
+}
+
+/// The NFTs of the owner (`nft_tokens_for_owner`). Without `max_page_size` a single request is
+/// tried first, as the contract returns its default number of tokens.
+#[tracing::instrument(name = "Getting the NFTs of the owner ...", skip_all)]
+pub fn get_nft_tokens_for_owner(
+    network_config: &crate::config::NetworkConfig,
+    nft_contract_account_id: &near_primitives::types::AccountId,
+    owner_account_id: &near_primitives::types::AccountId,
+    block_reference: &near_primitives::types::BlockReference,
+    max_page_size: Option<u64>,
+) -> color_eyre::eyre::Result<Vec<serde_json::Value>> {
+    let call = |args: serde_json::Value,
+                block_reference: near_primitives::types::BlockReference| {
+        let call_result = network_config
+            .json_rpc_client()
+            .blocking_call_view_function(
+                nft_contract_account_id,
+                "nft_tokens_for_owner",
+                serde_json::to_vec(&args)?,
+                block_reference,
+            )
+            .wrap_err_with(|| {
+                format!("Failed to fetch query for view method: 'nft_tokens_for_owner' (contract <{}> on network <{}>)",
+                    nft_contract_account_id,
+                    network_config.network_name
+                )
+            })?;
+        call_result.print_logs();
+        call_result.parse_result_from_json::<Vec<serde_json::Value>>()
+    };
+
+    let page_size = match max_page_size {
+        Some(max_page_size) => max_page_size,
+        None => match call(
+            serde_json::json!({ "account_id": owner_account_id }),
+            block_reference.clone(),
+        ) {
+            Ok(tokens) => return Ok(tokens),
+            Err(err) if is_result_too_large(&err) => DEFAULT_PAGE_SIZE,
+            Err(err) => return Err(err),
+        },
+    };
+    let block_reference = pin_block_reference(network_config, block_reference)?;
+    let (tokens, requests) = paginate(page_size, |from_index, limit| {
+        match call(
+            serde_json::json!({
+                "account_id": owner_account_id,
+                "from_index": from_index.to_string(),
+                "limit": limit,
+            }),
+            block_reference.clone(),
+        ) {
+            Ok(tokens) => Ok(Some(tokens)),
+            Err(err) if is_result_too_large(&err) => Ok(None),
+            Err(err) => Err(err),
+        }
+    })?;
+    if requests > 1 {
+        print_pagination_note(requests);
+    }
+    Ok(tokens)
+}
+
+/// The SocialDB data of the keys (`get`). A key ending with a wildcard (`profile/**`,
+/// `post/*`) is split into its children when its data is too large, or always with
+/// `max_page_size` children per request when it is set.
+#[tracing::instrument(name = "Getting the data from SocialDB ...", skip_all)]
+pub fn get_social_data(
+    network_config: &crate::config::NetworkConfig,
+    keys: &[String],
+    block_reference: &near_primitives::types::BlockReference,
+    max_page_size: Option<u64>,
+) -> color_eyre::eyre::Result<serde_json::Value> {
+    let mut social_db = SocialDbReader {
+        network_config,
+        contract_account_id: network_config.get_near_social_account_id_from_network()?,
+        block_reference: block_reference.clone(),
+        page_size: max_page_size,
+        requests: std::cell::Cell::new(0),
+    };
+    if max_page_size.is_none() {
+        match social_db.get(keys) {
+            Ok(data) => return Ok(data),
+            Err(err) if is_result_too_large(&err) => {}
+            Err(err) => return Err(err),
+        }
+    }
+    social_db.block_reference = pin_block_reference(network_config, block_reference)?;
+    let data = social_db.get_split(keys)?;
+    print_pagination_note(social_db.requests.get());
+    Ok(data)
+}
+
+struct SocialDbReader<'a> {
+    network_config: &'a crate::config::NetworkConfig,
+    contract_account_id: near_primitives::types::AccountId,
+    block_reference: near_primitives::types::BlockReference,
+    page_size: Option<u64>,
+    requests: std::cell::Cell<usize>,
+}
+
+impl SocialDbReader<'_> {
+    fn view(
+        &self,
+        method_name: &str,
+        keys: &[String],
+    ) -> color_eyre::eyre::Result<serde_json::Value> {
+        self.requests.set(self.requests.get() + 1);
+        self.network_config
+            .json_rpc_client()
+            .blocking_call_view_function(
+                &self.contract_account_id,
+                method_name,
+                serde_json::to_vec(&serde_json::json!({ "keys": keys }))?,
+                self.block_reference.clone(),
+            )
+            .wrap_err_with(|| {
+                format!("Failed to fetch query for view method: '{method_name} {}' (contract <{}> on network <{}>)",
+                    keys.join(", "),
+                    self.contract_account_id,
+                    self.network_config.network_name
+                )
+            })?
+            .parse_result_from_json()
+            .wrap_err_with(|| {
+                format!(
+                    "Failed to parse view function call return value for {}.",
+                    keys.join(", ")
+                )
+            })
+    }
+
+    fn get(&self, keys: &[String]) -> color_eyre::eyre::Result<serde_json::Value> {
+        self.view("get", keys)
+    }
+
+    /// Like `get`, but narrows the keys whose data is too large.
+    fn get_split(&self, keys: &[String]) -> color_eyre::eyre::Result<serde_json::Value> {
+        let mut data = serde_json::Value::Object(Default::default());
+        for key in keys {
+            let Some((prefix, recursive)) = split_wildcard(key) else {
+                merge_json(&mut data, self.get_or_narrow(std::slice::from_ref(key))?);
+                continue;
+            };
+            let children = child_keys(&self.view("keys", &[format!("{prefix}/*")])?, prefix)?;
+            let page_size = usize::try_from(self.page_size.unwrap_or(DEFAULT_PAGE_SIZE))?.max(1);
+            for children in children.chunks(page_size) {
+                let child_keys = children
+                    .iter()
+                    .flat_map(|child| {
+                        let child_key = format!("{prefix}/{child}");
+                        let descendants = recursive.then(|| format!("{child_key}/**"));
+                        std::iter::once(child_key).chain(descendants)
+                    })
+                    .collect::<Vec<_>>();
+                merge_json(&mut data, self.get_or_narrow(&child_keys)?);
+            }
+        }
+        Ok(data)
+    }
+
+    fn get_or_narrow(&self, keys: &[String]) -> color_eyre::eyre::Result<serde_json::Value> {
+        match self.get(keys) {
+            Ok(data) => Ok(data),
+            Err(err) if is_result_too_large(&err) => {
+                if keys.len() > 1 {
+                    let (first, second) = keys.split_at(keys.len() / 2);
+                    let mut data = self.get_or_narrow(first)?;
+                    merge_json(&mut data, self.get_or_narrow(second)?);
+                    Ok(data)
+                } else if split_wildcard(&keys[0]).is_some_and(|(_, recursive)| recursive) {
+                    self.get_split(keys)
+                } else {
+                    Err(err.wrap_err(format!(
+                        "The data of the key <{}> is too large to be viewed",
+                        keys[0]
+                    )))
+                }
+            }
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// `("alice.near/post", true)` for `alice.near/post/**`, `("alice.near/post", false)` for
+/// `alice.near/post/*`
+fn split_wildcard(key: &str) -> Option<(&str, bool)> {
+    let (prefix, recursive) = if let Some(prefix) = key.strip_suffix("/**") {
+        (prefix, true)
+    } else {
+        (key.strip_suffix("/*")?, false)
+    };
+    (!prefix.contains('*')).then_some((prefix, recursive))
+}
+
+/// The names of the children of the prefix in the result of SocialDB `keys` for `<prefix>/*`.
+fn child_keys(keys: &serde_json::Value, prefix: &str) -> color_eyre::eyre::Result<Vec<String>> {
+    let node = prefix
+        .split('/')
+        .try_fold(keys, |node, segment| node.get(segment));
+    match node {
+        None => Ok(Vec::new()),
+        Some(serde_json::Value::Object(children)) => Ok(children.keys().cloned().collect()),
+        Some(_) => color_eyre::eyre::bail!("Unexpected result of SocialDB keys for <{prefix}/*>"),
+    }
+}
+
+/// The state of the contract with the prefix; when it is too large for the RPC node it is read
+/// in parts, one per next byte of the prefix (the proof is then not available, and a key equal
+/// to a split prefix cannot be read).
+#[tracing::instrument(name = "Obtaining the state of the contract in parts ...", skip_all)]
+pub fn get_contract_state_in_parts(
+    network_config: &crate::config::NetworkConfig,
+    contract_account_id: &near_primitives::types::AccountId,
+    prefix: &[u8],
+    block_reference: &near_primitives::types::BlockReference,
+) -> color_eyre::eyre::Result<near_jsonrpc_client::methods::query::RpcQueryResponse> {
+    let block_reference = pin_block_reference(network_config, block_reference)?;
+    let mut values = Vec::new();
+    let mut requests = 0;
+    let mut prefixes = next_byte_prefixes(prefix);
+    let (mut block_height, mut block_hash) = (0, Default::default());
+    while let Some(prefix) = prefixes.pop() {
+        requests += 1;
+        tracing::Span::current().pb_set_message(&format!(
+            "{requests} requests, {} prefixes left",
+            prefixes.len()
+        ));
+        let response = network_config.json_rpc_client().blocking_call(
+            near_jsonrpc_client::methods::query::RpcQueryRequest {
+                block_reference: block_reference.clone(),
+                request: near_primitives::views::QueryRequest::ViewState {
+                    account_id: contract_account_id.clone(),
+                    prefix: prefix.clone().into(),
+                    include_proof: false,
+                },
+            },
+        );
+        match response {
+            Ok(near_jsonrpc_client::methods::query::RpcQueryResponse {
+                kind: near_jsonrpc_primitives::types::query::QueryResponseKind::ViewState(result),
+                block_height: height,
+                block_hash: hash,
+            }) => {
+                values.extend(result.values);
+                (block_height, block_hash) = (height, hash);
+            }
+            Ok(_) => color_eyre::eyre::bail!(
+                "Received unexpected query kind in response to a ViewState query"
+            ),
+            Err(near_jsonrpc_client::errors::JsonRpcError::ServerError(
+                near_jsonrpc_client::errors::JsonRpcServerError::HandlerError(
+                    near_jsonrpc_primitives::types::query::RpcQueryError::TooLargeContractState {
+                        ..
+                    },
+                ),
+            )) => prefixes.extend(next_byte_prefixes(&prefix)),
+            Err(err) => {
+                return Err(err).wrap_err_with(|| {
+                    format!(
+                    "Failed to fetch query ViewState for <{contract_account_id}> on network <{}>",
+                    network_config.network_name
+                )
+                })
+            }
+        }
+    }
+    values.sort_by(|a, b| a.key.cmp(&b.key));
+    print_pagination_note(requests);
+    Ok(near_jsonrpc_client::methods::query::RpcQueryResponse {
+        kind: near_jsonrpc_primitives::types::query::QueryResponseKind::ViewState(
+            near_primitives::views::ViewStateResult {
+                values,
+                proof: Vec::new(),
+            },
+        ),
+        block_height,
+        block_hash,
+    })
+}
+
+/// The prefixes of the keys that start with the prefix and are longer than it (in reverse
+/// order, so that they are popped in order)
+fn next_byte_prefixes(prefix: &[u8]) -> Vec<Vec<u8>> {
+    (0..=u8::MAX)
+        .rev()
+        .map(|byte| [prefix, &[byte]].concat())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn too_large_errors() {
+        assert!(is_too_large_message(
+            "State of contract app.near is too large to be viewed"
+        ));
+        assert!(is_too_large_message(
+            "wasm execution failed with error: HostError(ReturnedValueLengthExceeded { length: 5000000, limit: 4194304 })"
+        ));
+        assert!(is_too_large_message(
+            "wasm execution failed with error: HostError(GasLimitExceeded)"
+        ));
+        assert!(!is_too_large_message(
+            "wasm execution failed with error: MethodResolveError(MethodNotFound)"
+        ));
+        let err = color_eyre::eyre::eyre!("HostError(GasLimitExceeded)")
+            .wrap_err("Read-only function execution failed");
+        assert!(is_result_too_large(&err));
+    }
+
+    #[test]
+    fn json_is_merged_key_by_key() {
+        let mut data = serde_json::json!({ "alice.near": { "post": { "main": "a" } } });
+        merge_json(
+            &mut data,
+            serde_json::json!({ "alice.near": { "post": { "comment": "b" }, "profile": { "name": "Alice" } } }),
+        );
+        assert_eq!(
+            data,
+            serde_json::json!({
+                "alice.near": {
+                    "post": { "main": "a", "comment": "b" },
+                    "profile": { "name": "Alice" }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn pages_are_halved_until_they_fit() {
+        // 23 items, a page of more than 4 items is too large
+        let mut calls = Vec::new();
+        let (items, requests) = paginate(16, |from_index, limit| {
+            calls.push((from_index, limit));
+            if limit > 4 {
+                return Ok(None);
+            }
+            Ok(Some((from_index..(from_index + limit).min(23)).collect()))
+        })
+        .unwrap();
+        assert_eq!(items, (0..23).collect::<Vec<_>>());
+        assert_eq!(requests, calls.len());
+        assert_eq!(&calls[..3], [(0, 16), (0, 8), (0, 4)]);
+        assert_eq!(calls.last(), Some(&(20, 4)));
+
+        assert!(paginate(2, |_, _| Ok(None::<Vec<u8>>)).is_err());
+    }
+
+    #[test]
+    fn wildcards_are_split_into_children() {
+        assert_eq!(
+            split_wildcard("alice.near/post/**"),
+            Some(("alice.near/post", true))
+        );
+        assert_eq!(
+            split_wildcard("alice.near/graph/follow/*"),
+            Some(("alice.near/graph/follow", false))
+        );
+        assert_eq!(split_wildcard("*/profile/**"), None);
+        assert_eq!(split_wildcard("alice.near/profile/name"), None);
+
+        let keys =
+            serde_json::json!({ "alice.near": { "post": { "main": true, "comment": true } } });
+        let mut children = child_keys(&keys, "alice.near/post").unwrap();
+        children.sort();
+        assert_eq!(children, ["comment", "main"]);
+        assert!(child_keys(&keys, "bob.near/post").unwrap().is_empty());
+
+        let prefixes = next_byte_prefixes(b"STATE");
+        assert_eq!(prefixes.len(), 256);
+        assert_eq!(prefixes.last().unwrap(), b"STATE\0");
+    }
+}